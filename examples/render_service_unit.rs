@@ -16,8 +16,9 @@ fn main() -> Result<(), unitbus::Error> {
     spec.group = Some("demo".to_string());
     spec.environment = env;
     spec.restart = Some("always".to_string());
-    spec.restart_sec = Some(3);
-    spec.timeout_start_sec = Some(10);
+    spec.set_restart_sec(3);
+    spec.set_timeout_start_sec(10);
+    spec.memory_max = Some("512M".parse()?);
     spec.wanted_by = vec!["multi-user.target".to_string()];
 
     let rendered = spec.render()?;
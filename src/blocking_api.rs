@@ -1,7 +1,7 @@
 use crate::{
-    Capabilities, Diagnosis, DiagnosisOptions, JobHandle, JobOutcome, Journal, JournalFilter,
-    JournalResult, Manager, ManagerInfo, Properties, Result, UnitBus, UnitBusOptions,
-    UnitListEntry, UnitStartMode, UnitStatus, Units,
+    Capabilities, Diagnosis, DiagnosisOptions, HostFacts, JobHandle, JobOutcome, Journal,
+    JournalFilter, JournalResult, Manager, ManagerDumpReport, ManagerInfo, PolkitAuthorization,
+    Properties, Result, UnitBus, UnitBusOptions, UnitListEntry, UnitStartMode, UnitStatus, Units,
 };
 
 use std::time::Duration;
@@ -29,11 +29,41 @@ impl BlockingUnitBus {
         Ok(Self { inner })
     }
 
+    /// Connect to the user's service manager on the session D-Bus (blocking).
+    pub fn connect_session() -> Result<Self> {
+        let inner = crate::runtime::block_on_result(UnitBus::connect_session())?;
+        Ok(Self { inner })
+    }
+
+    /// Connect to the user's service manager with custom options (blocking).
+    pub fn connect_session_with(opts: UnitBusOptions) -> Result<Self> {
+        let inner = crate::runtime::block_on_result(UnitBus::connect_session_with(opts))?;
+        Ok(Self { inner })
+    }
+
+    /// The service manager this connection controls.
+    pub fn scope(&self) -> crate::ManagerScope {
+        self.inner.scope()
+    }
+
+    pub fn clear_property_cache(&self) {
+        self.inner.clear_property_cache()
+    }
+
     /// Probe environment capabilities conservatively (blocking).
     pub fn capabilities(&self) -> Result<Capabilities> {
         crate::runtime::block_on_result(async { Ok(self.inner.capabilities().await) })
     }
 
+    /// Pre-flight an exact polkit action (blocking).
+    pub fn check_polkit(
+        &self,
+        action_id: &str,
+        details: &std::collections::HashMap<String, String>,
+    ) -> Result<PolkitAuthorization> {
+        crate::runtime::block_on_result(self.inner.capabilities().check_polkit(action_id, details))
+    }
+
     /// Access unit/job control APIs (blocking wrappers).
     pub fn units(&self) -> BlockingUnits {
         BlockingUnits {
@@ -48,6 +78,13 @@ impl BlockingUnitBus {
         }
     }
 
+    /// Access coredump listing APIs (blocking wrappers).
+    pub fn coredumps(&self) -> BlockingCoredumps {
+        BlockingCoredumps {
+            inner: self.inner.coredumps(),
+        }
+    }
+
     /// Access systemd manager/global APIs (blocking wrappers).
     pub fn manager(&self) -> BlockingManager {
         BlockingManager {
@@ -70,6 +107,21 @@ impl BlockingUnitBus {
             inner: self.inner.config(),
         }
     }
+
+    /// Access local container APIs (blocking wrappers).
+    #[cfg(feature = "machines")]
+    pub fn machines(&self) -> BlockingMachines {
+        BlockingMachines {
+            inner: self.inner.machines(),
+        }
+    }
+
+    /// Connect to the system bus inside local container `name` (blocking).
+    #[cfg(feature = "machines")]
+    pub fn connect_machine(&self, name: &str) -> Result<Self> {
+        let inner = crate::runtime::block_on_result(self.inner.connect_machine(name))?;
+        Ok(Self { inner })
+    }
 }
 
 /// Blocking wrapper for `Units`.
@@ -111,10 +163,118 @@ impl BlockingUnits {
         crate::runtime::block_on_result(self.inner.get_timer_properties_by_path(unit_path))
     }
 
+    pub fn snapshot_properties(&self, unit: &str) -> Result<Properties> {
+        crate::runtime::block_on_result(self.inner.snapshot_properties(unit))
+    }
+
+    pub fn dependencies(&self, unit: &str) -> Result<crate::UnitDependencies> {
+        crate::runtime::block_on_result(self.inner.dependencies(unit))
+    }
+
+    pub fn dependency_graph(
+        &self,
+        roots: &[&str],
+        opts: crate::DependencyGraphOptions,
+    ) -> Result<crate::DependencyGraph> {
+        crate::runtime::block_on_result(self.inner.dependency_graph(roots, opts))
+    }
+
     pub fn get_status(&self, unit: &str) -> Result<UnitStatus> {
         crate::runtime::block_on_result(self.inner.get_status(unit))
     }
 
+    pub fn get_typed_status(&self, unit: &str) -> Result<crate::TypedUnitStatus> {
+        crate::runtime::block_on_result(self.inner.get_typed_status(unit))
+    }
+
+    pub fn restart_many(
+        &self,
+        units: &[&str],
+        opts: crate::BatchOptions,
+    ) -> Result<crate::BatchReport> {
+        crate::runtime::block_on_result(self.inner.restart_many(units, opts))
+    }
+
+    pub fn start_group(&self, target: &str, timeout: Duration) -> Result<crate::GroupReport> {
+        crate::runtime::block_on_result(self.inner.start_group(target, timeout))
+    }
+
+    pub fn stop_group(&self, target: &str, timeout: Duration) -> Result<crate::GroupReport> {
+        crate::runtime::block_on_result(self.inner.stop_group(target, timeout))
+    }
+
+    pub fn restart_group(&self, target: &str, timeout: Duration) -> Result<crate::GroupReport> {
+        crate::runtime::block_on_result(self.inner.restart_group(target, timeout))
+    }
+
+    pub fn restart_and_verify(
+        &self,
+        unit: &str,
+        opts: crate::VerifyOptions,
+    ) -> Result<crate::VerifiedOutcome> {
+        crate::runtime::block_on_result(self.inner.restart_and_verify(unit, opts))
+    }
+
+    pub fn ensure(
+        &self,
+        unit: &str,
+        desired: crate::DesiredUnitState,
+    ) -> Result<crate::EnsureReport> {
+        crate::runtime::block_on_result(self.inner.ensure(unit, desired))
+    }
+
+    pub fn enable(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileEnableOptions,
+    ) -> Result<crate::UnitFileEnableReport> {
+        crate::runtime::block_on_result(self.inner.enable(unit, opts))
+    }
+
+    pub fn disable(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileDisableOptions,
+    ) -> Result<crate::UnitFileDisableReport> {
+        crate::runtime::block_on_result(self.inner.disable(unit, opts))
+    }
+
+    pub fn mask(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileMaskOptions,
+    ) -> Result<crate::UnitFileChangeReport> {
+        crate::runtime::block_on_result(self.inner.mask(unit, opts))
+    }
+
+    pub fn unmask(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileUnmaskOptions,
+    ) -> Result<crate::UnitFileChangeReport> {
+        crate::runtime::block_on_result(self.inner.unmask(unit, opts))
+    }
+
+    pub fn preset(
+        &self,
+        unit: &str,
+        opts: crate::UnitFilePresetOptions,
+    ) -> Result<crate::UnitFileEnableReport> {
+        crate::runtime::block_on_result(self.inner.preset(unit, opts))
+    }
+
+    pub fn pressure(&self, unit: &str) -> Result<crate::UnitPressure> {
+        crate::runtime::block_on_result(self.inner.pressure(unit))
+    }
+
+    pub fn restart_history(
+        &self,
+        unit: &str,
+        window: std::time::Duration,
+    ) -> Result<crate::RestartHistory> {
+        crate::runtime::block_on_result(self.inner.restart_history(unit, window))
+    }
+
     pub fn start(&self, unit: &str, mode: UnitStartMode) -> Result<BlockingJobHandle> {
         let job = crate::runtime::block_on_result(self.inner.start(unit, mode))?;
         Ok(BlockingJobHandle { inner: job })
@@ -125,6 +285,75 @@ impl BlockingUnits {
         Ok(BlockingJobHandle { inner: job })
     }
 
+    pub fn kill(&self, unit: &str, who: crate::KillWho, signal: crate::Signal) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.kill(unit, who, signal))
+    }
+
+    pub fn reset_failed(&self, unit: &str) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.reset_failed(unit))
+    }
+
+    pub fn reset_failed_all(&self) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.reset_failed_all())
+    }
+
+    pub fn resource_usage(&self, unit: &str) -> Result<crate::UnitResources> {
+        crate::runtime::block_on_result(self.inner.resource_usage(unit))
+    }
+
+    pub fn processes(&self, unit: &str) -> Result<Vec<crate::UnitProcess>> {
+        crate::runtime::block_on_result(self.inner.processes(unit))
+    }
+
+    pub fn freeze(&self, unit: &str) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.freeze(unit))
+    }
+
+    pub fn thaw(&self, unit: &str) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.thaw(unit))
+    }
+
+    pub fn freezer_state(&self, unit: &str) -> Result<crate::FreezerState> {
+        crate::runtime::block_on_result(self.inner.freezer_state(unit))
+    }
+
+    pub fn start_ordered(
+        &self,
+        units: &[&str],
+        timeout: Duration,
+    ) -> Result<crate::OrderedStartReport> {
+        crate::runtime::block_on_result(self.inner.start_ordered(units, timeout))
+    }
+
+    pub fn start_ordered_progress(
+        &self,
+        units: &[&str],
+        timeout: Duration,
+    ) -> Result<BlockingOrderedStartProgress> {
+        Ok(BlockingOrderedStartProgress {
+            inner: crate::runtime::block_on_result(
+                self.inner.start_ordered_progress(units, timeout),
+            )?,
+            done: false,
+        })
+    }
+
+    pub fn set_log_level_max(
+        &self,
+        unit: &str,
+        level: crate::Priority,
+    ) -> Result<Option<crate::Priority>> {
+        crate::runtime::block_on_result(self.inner.set_log_level_max(unit, level))
+    }
+
+    pub fn stop_graceful(
+        &self,
+        unit: &str,
+        policy: crate::EscalationPolicy,
+    ) -> Result<crate::GracefulStopReport> {
+        crate::runtime::block_on_result(self.inner.stop_graceful(unit, policy))
+    }
+
     pub fn restart(&self, unit: &str, mode: UnitStartMode) -> Result<BlockingJobHandle> {
         let job = crate::runtime::block_on_result(self.inner.restart(unit, mode))?;
         Ok(BlockingJobHandle { inner: job })
@@ -136,6 +365,40 @@ impl BlockingUnits {
     }
 }
 
+/// Blocking iterator over `Units::start_ordered_progress`.
+///
+/// Yields events until `OrderedStartEvent::Finished`; a runtime error is yielded once and ends
+/// the iteration.
+#[derive(Debug)]
+pub struct BlockingOrderedStartProgress {
+    inner: crate::OrderedStartProgress,
+    done: bool,
+}
+
+impl Iterator for BlockingOrderedStartProgress {
+    type Item = Result<crate::OrderedStartEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = crate::runtime::block_on_result(async {
+            Ok(futures_util::StreamExt::next(&mut self.inner).await)
+        });
+        match next {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 /// Blocking wrapper for `JobHandle`.
 #[derive(Clone, Debug)]
 pub struct BlockingJobHandle {
@@ -154,6 +417,18 @@ impl BlockingJobHandle {
     pub fn wait(&self, timeout: Duration) -> Result<JobOutcome> {
         crate::runtime::block_on_result(self.inner.wait(timeout))
     }
+
+    pub fn wait_stable(&self, timeout: Duration, stable_for: Duration) -> Result<JobOutcome> {
+        crate::runtime::block_on_result(self.inner.wait_stable(timeout, stable_for))
+    }
+
+    pub fn wait_with_shutdown(
+        &self,
+        timeout: Duration,
+        shutdown: &crate::shutdown::ShutdownToken,
+    ) -> Result<JobOutcome> {
+        crate::runtime::block_on_result(self.inner.wait_with_shutdown(timeout, shutdown))
+    }
 }
 
 /// Blocking wrapper for `Journal`.
@@ -167,11 +442,107 @@ impl BlockingJournal {
         crate::runtime::block_on_result(self.inner.query(filter))
     }
 
+    pub fn query_since_bookmark(
+        &self,
+        unit: &str,
+        store: &dyn crate::CursorStore,
+    ) -> Result<crate::BookmarkPage> {
+        crate::runtime::block_on_result(self.inner.query_since_bookmark(unit, store))
+    }
+
+    /// `BookmarkPage::commit` for blocking callers.
+    pub fn commit_bookmark(
+        &self,
+        page: &crate::BookmarkPage,
+        store: &dyn crate::CursorStore,
+    ) -> Result<()> {
+        crate::runtime::block_on_result(page.commit(store))
+    }
+
+    pub fn follow(
+        &self,
+        filter: JournalFilter,
+        opts: crate::FollowOptions,
+    ) -> Result<BlockingJournalFollow> {
+        Ok(BlockingJournalFollow {
+            inner: self.inner.follow(filter, opts)?,
+            done: false,
+        })
+    }
+
+    /// Runs until caught up with `opts.follow == false`; otherwise blocks until an error.
+    pub fn forward(
+        &self,
+        filter: JournalFilter,
+        sink: &dyn crate::JournalSink,
+        store: &dyn crate::CursorStore,
+        opts: crate::ForwardOptions,
+    ) -> Result<crate::ForwardReport> {
+        crate::runtime::block_on_result(self.inner.forward(filter, sink, store, opts))
+    }
+
+    pub fn query_machines(
+        &self,
+        machines: &[&str],
+        filter: JournalFilter,
+    ) -> Result<Vec<crate::MachineJournalResult>> {
+        crate::runtime::block_on_result(self.inner.query_machines(machines, filter))
+    }
+
     pub fn diagnose_unit_failure(&self, unit: &str, opts: DiagnosisOptions) -> Result<Diagnosis> {
         crate::runtime::block_on_result(self.inner.diagnose_unit_failure(unit, opts))
     }
 }
 
+/// Blocking iterator over `Journal::follow`.
+///
+/// Blocks until the next entry arrives; an error is yielded once and ends the iteration.
+#[derive(Debug)]
+pub struct BlockingJournalFollow {
+    inner: crate::JournalFollow,
+    done: bool,
+}
+
+impl Iterator for BlockingJournalFollow {
+    type Item = Result<crate::JournalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = crate::runtime::block_on_result(async {
+            Ok(futures_util::StreamExt::next(&mut self.inner).await)
+        });
+        match next {
+            Ok(Some(Ok(entry))) => Some(Ok(entry)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Ok(Some(Err(e))) | Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Blocking wrapper for `Coredumps`.
+#[derive(Clone, Debug)]
+pub struct BlockingCoredumps {
+    inner: crate::Coredumps,
+}
+
+impl BlockingCoredumps {
+    pub fn list(&self, filter: crate::CoredumpFilter) -> Result<Vec<crate::CoredumpEntry>> {
+        crate::runtime::block_on_result(self.inner.list(filter))
+    }
+
+    pub fn info(&self, id: impl Into<crate::CoredumpId>) -> Result<Option<crate::CoredumpEntry>> {
+        crate::runtime::block_on_result(self.inner.info(id))
+    }
+}
+
 /// Blocking wrapper for `Manager`.
 #[derive(Clone, Debug)]
 pub struct BlockingManager {
@@ -179,14 +550,59 @@ pub struct BlockingManager {
 }
 
 impl BlockingManager {
+    pub fn reboot_required(
+        &self,
+        opts: crate::RebootCheckOptions,
+    ) -> Result<crate::RebootRequiredReport> {
+        crate::runtime::block_on_result(self.inner.reboot_required(opts))
+    }
+
+    pub fn daemon_reload(&self) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.daemon_reload())
+    }
+
+    pub fn log_level(&self) -> Result<crate::Priority> {
+        crate::runtime::block_on_result(self.inner.log_level())
+    }
+
+    pub fn set_log_level(&self, level: crate::Priority) -> Result<crate::Priority> {
+        crate::runtime::block_on_result(self.inner.set_log_level(level))
+    }
+
+    pub fn log_target(&self) -> Result<crate::LogTarget> {
+        crate::runtime::block_on_result(self.inner.log_target())
+    }
+
+    pub fn set_log_target(&self, target: crate::LogTarget) -> Result<crate::LogTarget> {
+        crate::runtime::block_on_result(self.inner.set_log_target(target))
+    }
+
     pub fn list_units(&self) -> Result<Vec<UnitListEntry>> {
         crate::runtime::block_on_result(self.inner.list_units())
     }
 
+    pub fn unit_inventory(&self) -> Result<Vec<crate::UnitInventoryEntry>> {
+        crate::runtime::block_on_result(self.inner.unit_inventory())
+    }
+
     pub fn list_units_filtered(&self, states: &[&str]) -> Result<Vec<UnitListEntry>> {
         crate::runtime::block_on_result(self.inner.list_units_filtered(states))
     }
 
+    pub fn list_units_by_patterns(
+        &self,
+        patterns: &[&str],
+        states: &[&str],
+    ) -> Result<Vec<UnitListEntry>> {
+        crate::runtime::block_on_result(self.inner.list_units_by_patterns(patterns, states))
+    }
+
+    pub fn list_units_stream(&self, query: crate::UnitListQuery) -> Result<BlockingUnitListStream> {
+        Ok(BlockingUnitListStream {
+            inner: self.inner.list_units_stream(query)?,
+        })
+    }
+
     pub fn properties(&self) -> Result<Properties> {
         crate::runtime::block_on_result(self.inner.properties())
     }
@@ -194,6 +610,37 @@ impl BlockingManager {
     pub fn info(&self) -> Result<ManagerInfo> {
         crate::runtime::block_on_result(self.inner.info())
     }
+
+    pub fn host_facts(&self) -> Result<HostFacts> {
+        crate::runtime::block_on_result(self.inner.host_facts())
+    }
+
+    pub fn dump<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        max_bytes: u64,
+    ) -> Result<ManagerDumpReport> {
+        let mut writer = futures_lite::io::AssertAsync::new(writer);
+        crate::runtime::block_on_result(self.inner.dump(&mut writer, max_bytes))
+    }
+}
+
+/// Blocking iterator over `Manager::list_units_stream`; a D-Bus error is yielded once and ends
+/// the iteration.
+#[derive(Debug)]
+pub struct BlockingUnitListStream {
+    inner: crate::UnitListStream,
+}
+
+impl Iterator for BlockingUnitListStream {
+    type Item = Result<UnitListEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::runtime::block_on_result(async {
+            Ok(futures_util::StreamExt::next(&mut self.inner).await)
+        })
+        .unwrap_or_else(|e| Some(Err(e)))
+    }
 }
 
 /// Blocking wrapper for `Tasks` (feature=`tasks`).
@@ -209,6 +656,41 @@ impl BlockingTasks {
         let handle = crate::runtime::block_on_result(self.inner.run(spec))?;
         Ok(BlockingTaskHandle { inner: handle })
     }
+
+    pub fn schedule(&self, spec: crate::TimerTaskSpec) -> Result<BlockingScheduledTaskHandle> {
+        let handle = crate::runtime::block_on_result(self.inner.schedule(spec))?;
+        Ok(BlockingScheduledTaskHandle { inner: handle })
+    }
+}
+
+/// Blocking wrapper for `ScheduledTaskHandle` (feature=`tasks`).
+#[cfg(feature = "tasks")]
+#[derive(Clone, Debug)]
+pub struct BlockingScheduledTaskHandle {
+    inner: crate::ScheduledTaskHandle,
+}
+
+#[cfg(feature = "tasks")]
+impl BlockingScheduledTaskHandle {
+    pub fn timer_unit(&self) -> &str {
+        &self.inner.timer_unit
+    }
+
+    pub fn service_unit(&self) -> &str {
+        &self.inner.service_unit
+    }
+
+    pub fn timer_outcome(&self) -> &JobOutcome {
+        &self.inner.timer_outcome
+    }
+
+    pub fn next_elapse(&self) -> Result<Option<std::time::SystemTime>> {
+        crate::runtime::block_on_result(self.inner.next_elapse())
+    }
+
+    pub fn cancel(&self) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.cancel())
+    }
 }
 
 /// Blocking wrapper for `TaskHandle` (feature=`tasks`).
@@ -231,6 +713,55 @@ impl BlockingTaskHandle {
     pub fn wait(&self, timeout: Duration) -> Result<crate::TaskResult> {
         crate::runtime::block_on_result(self.inner.wait(timeout))
     }
+
+    pub fn wait_with_shutdown(
+        &self,
+        timeout: Duration,
+        shutdown: &crate::shutdown::ShutdownToken,
+    ) -> Result<crate::TaskResult> {
+        crate::runtime::block_on_result(self.inner.wait_with_shutdown(timeout, shutdown))
+    }
+
+    /// Follow the task's journal output (see `TaskHandle::logs`).
+    pub fn logs(&self, opts: crate::TaskLogOptions) -> Result<BlockingTaskLogs> {
+        Ok(BlockingTaskLogs {
+            inner: self.inner.logs(opts)?,
+            done: false,
+        })
+    }
+}
+
+/// Blocking iterator over a task's journal output (feature=`tasks`).
+///
+/// Yields entries until the task finishes and its output is drained; an error (including
+/// `Error::Timeout` once the deadline passes) is yielded once and ends the iteration.
+#[cfg(feature = "tasks")]
+#[derive(Debug)]
+pub struct BlockingTaskLogs {
+    inner: crate::TaskLogStream,
+    done: bool,
+}
+
+#[cfg(feature = "tasks")]
+impl Iterator for BlockingTaskLogs {
+    type Item = Result<crate::JournalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match crate::runtime::block_on_result(self.inner.next()) {
+            Ok(Some(entry)) => Some(Ok(entry)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
 }
 
 /// Blocking wrapper for `Config` (feature=`config`).
@@ -289,11 +820,238 @@ impl BlockingConfig {
         crate::runtime::block_on_result(self.inner.apply_dropin(spec))
     }
 
+    pub fn apply_instance_dropin(
+        &self,
+        template: &str,
+        instance: &str,
+        spec: crate::DropInSpec,
+    ) -> Result<crate::ApplyReport> {
+        crate::runtime::block_on_result(self.inner.apply_instance_dropin(template, instance, spec))
+    }
+
     pub fn remove_dropin(&self, unit: &str, name: &str) -> Result<crate::RemoveReport> {
         crate::runtime::block_on_result(self.inner.remove_dropin(unit, name))
     }
 
+    pub fn wire_on_failure(
+        &self,
+        unit: &str,
+        handler: crate::ServiceUnitSpec,
+    ) -> Result<crate::OnFailureReport> {
+        crate::runtime::block_on_result(self.inner.wire_on_failure(unit, handler))
+    }
+
+    pub fn define_app_target(&self, spec: crate::AppTargetSpec) -> Result<crate::AppTargetReport> {
+        crate::runtime::block_on_result(self.inner.define_app_target(spec))
+    }
+
+    pub fn apply_dropin_verified(
+        &self,
+        spec: crate::DropInSpec,
+    ) -> Result<crate::VerifiedApplyReport> {
+        crate::runtime::block_on_result(self.inner.apply_dropin_verified(spec))
+    }
+
+    pub fn write_service_unit_verified(
+        &self,
+        spec: crate::ServiceUnitSpec,
+    ) -> Result<crate::VerifiedApplyReport> {
+        crate::runtime::block_on_result(self.inner.write_service_unit_verified(spec))
+    }
+
+    pub fn update_socket_listener(
+        &self,
+        socket_unit: &str,
+        new_listen: crate::SocketListenerUpdate,
+    ) -> Result<crate::SocketListenerReport> {
+        crate::runtime::block_on_result(self.inner.update_socket_listener(socket_unit, new_listen))
+    }
+
+    pub fn read_unit_file(&self, unit: &str) -> Result<crate::UnitFileContent> {
+        crate::runtime::block_on_result(self.inner.read_unit_file(unit))
+    }
+
+    pub fn diff_service_unit(&self, spec: crate::ServiceUnitSpec) -> Result<crate::UnitFileDiff> {
+        crate::runtime::block_on_result(self.inner.diff_service_unit(spec))
+    }
+
+    pub fn fingerprint(&self, unit: &str) -> Result<crate::UnitConfigFingerprint> {
+        crate::runtime::block_on_result(self.inner.fingerprint(unit))
+    }
+
+    pub fn detect_drift(
+        &self,
+        expected: &crate::UnitConfigFingerprint,
+    ) -> Result<crate::ConfigDrift> {
+        crate::runtime::block_on_result(self.inner.detect_drift(expected))
+    }
+
+    pub fn analyze_calendar(&self, expr: &str, iterations: u32) -> Result<crate::CalendarAnalysis> {
+        crate::runtime::block_on_result(self.inner.analyze_calendar(expr, iterations))
+    }
+
     pub fn daemon_reload(&self) -> Result<()> {
         crate::runtime::block_on_result(self.inner.daemon_reload())
     }
+
+    pub fn stage_offline_update(
+        &self,
+        spec: crate::OfflineUpdateSpec,
+    ) -> Result<crate::OfflineUpdateReport> {
+        crate::runtime::block_on_result(self.inner.stage_offline_update(spec))
+    }
+
+    pub fn schedule_offline_update_reboot(
+        &self,
+        spec: &crate::OfflineUpdateSpec,
+        delay: std::time::Duration,
+    ) -> Result<std::time::SystemTime> {
+        crate::runtime::block_on_result(self.inner.schedule_offline_update_reboot(spec, delay))
+    }
+
+    pub fn offline_update_status(
+        &self,
+        spec: &crate::OfflineUpdateSpec,
+    ) -> Result<crate::OfflineUpdateStatus> {
+        crate::runtime::block_on_result(self.inner.offline_update_status(spec))
+    }
+
+    pub fn remove_offline_update(
+        &self,
+        spec: &crate::OfflineUpdateSpec,
+    ) -> Result<crate::UnitUninstallReport> {
+        crate::runtime::block_on_result(self.inner.remove_offline_update(spec))
+    }
+
+    pub fn inhibit_shutdown(&self, why: &str) -> Result<crate::ShutdownInhibitor> {
+        crate::runtime::block_on_result(self.inner.inhibit_shutdown(why))
+    }
+
+    pub fn reload_coalescer(&self) -> BlockingReloadCoalescer {
+        BlockingReloadCoalescer {
+            inner: self.inner.reload_coalescer(),
+        }
+    }
+}
+
+/// Blocking wrapper for `ReloadCoalescer` (feature=`config`).
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub struct BlockingReloadCoalescer {
+    inner: crate::ReloadCoalescer,
+}
+
+#[cfg(feature = "config")]
+impl BlockingReloadCoalescer {
+    pub fn apply_dropin(&mut self, spec: crate::DropInSpec) -> Result<crate::ApplyReport> {
+        crate::runtime::block_on_result(self.inner.apply_dropin(spec))
+    }
+
+    pub fn remove_dropin(&mut self, unit: &str, name: &str) -> Result<crate::RemoveReport> {
+        crate::runtime::block_on_result(self.inner.remove_dropin(unit, name))
+    }
+
+    pub fn write_service_unit(
+        &mut self,
+        spec: crate::ServiceUnitSpec,
+    ) -> Result<crate::UnitFileWriteReport> {
+        crate::runtime::block_on_result(self.inner.write_service_unit(spec))
+    }
+
+    pub fn remove_unit_file(&mut self, unit: &str) -> Result<crate::UnitFileRemoveReport> {
+        crate::runtime::block_on_result(self.inner.remove_unit_file(unit))
+    }
+
+    pub fn inhibit_shutdown(&mut self, why: &str) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.inhibit_shutdown(why))
+    }
+
+    pub fn pending(&self) -> Vec<String> {
+        self.inner.pending()
+    }
+
+    pub fn flush(&mut self) -> Result<crate::ReloadFlushReport> {
+        crate::runtime::block_on_result(self.inner.flush())
+    }
+}
+
+/// Blocking wrapper for `Machines` (feature=`machines`).
+#[cfg(feature = "machines")]
+#[derive(Clone, Debug)]
+pub struct BlockingMachines {
+    inner: crate::Machines,
+}
+
+#[cfg(feature = "machines")]
+impl BlockingMachines {
+    pub fn run_container(
+        &self,
+        image: crate::ImageSpec,
+        opts: crate::ContainerOptions,
+    ) -> Result<crate::ContainerHandle> {
+        crate::runtime::block_on_result(self.inner.run_container(image, opts))
+    }
+
+    /// Start an image download (see `Machines::pull_image`).
+    pub fn pull_image(&self, url: &str, verify: crate::ImageVerify) -> Result<BlockingImagePull> {
+        let inner = crate::runtime::block_on_result(self.inner.pull_image(url, verify))?;
+        Ok(BlockingImagePull { inner, done: false })
+    }
+
+    pub fn leader(&self, name: &str) -> Result<u32> {
+        crate::runtime::block_on_result(self.inner.leader(name))
+    }
+
+    pub fn terminate(&self, name: &str) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.terminate(name))
+    }
+}
+
+/// Blocking iterator over the events of an image download (feature=`machines`).
+///
+/// Yields progress until `ImagePullEvent::Done`; an error (failed or canceled transfer) is
+/// yielded once and ends the iteration.
+#[cfg(feature = "machines")]
+#[derive(Debug)]
+pub struct BlockingImagePull {
+    inner: crate::ImagePull,
+    done: bool,
+}
+
+#[cfg(feature = "machines")]
+impl BlockingImagePull {
+    pub fn local(&self) -> &str {
+        self.inner.local()
+    }
+
+    pub fn cancel(&self) -> Result<()> {
+        crate::runtime::block_on_result(self.inner.cancel())
+    }
+
+    /// Wait for the download to finish; returns the local image name.
+    pub fn wait(self) -> Result<String> {
+        crate::runtime::block_on_result(self.inner.wait())
+    }
+}
+
+#[cfg(feature = "machines")]
+impl Iterator for BlockingImagePull {
+    type Item = Result<crate::ImagePullEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match crate::runtime::block_on_result(self.inner.next()) {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use crate::{Error, Result, UnitBusOptions};
+use crate::propcache::PropertyCache;
+use crate::{Error, ManagerScope, Result, UnitBusOptions};
 
 use std::collections::HashMap;
 use std::time::Duration;
@@ -13,6 +14,34 @@ pub(crate) const DBUS_PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Propert
 
 const SYSTEMD_JOB_INTERFACE: &str = "org.freedesktop.systemd1.Job";
 
+#[cfg(feature = "config")]
+const LOGIND_DESTINATION: &str = "org.freedesktop.login1";
+#[cfg(feature = "config")]
+const LOGIND_MANAGER_PATH: &str = "/org/freedesktop/login1";
+#[cfg(feature = "config")]
+const LOGIND_MANAGER_INTERFACE: &str = "org.freedesktop.login1.Manager";
+
+#[cfg(feature = "machines")]
+const MACHINED_DESTINATION: &str = "org.freedesktop.machine1";
+#[cfg(feature = "machines")]
+const MACHINED_MANAGER_PATH: &str = "/org/freedesktop/machine1";
+#[cfg(feature = "machines")]
+const MACHINED_MANAGER_INTERFACE: &str = "org.freedesktop.machine1.Manager";
+#[cfg(feature = "machines")]
+const MACHINED_MACHINE_INTERFACE: &str = "org.freedesktop.machine1.Machine";
+#[cfg(feature = "machines")]
+const IMPORTD_DESTINATION: &str = "org.freedesktop.import1";
+#[cfg(feature = "machines")]
+const IMPORTD_MANAGER_PATH: &str = "/org/freedesktop/import1";
+#[cfg(feature = "machines")]
+const IMPORTD_MANAGER_INTERFACE: &str = "org.freedesktop.import1.Manager";
+#[cfg(feature = "machines")]
+const IMPORTD_TRANSFER_INTERFACE: &str = "org.freedesktop.import1.Transfer";
+
+const POLKIT_DESTINATION: &str = "org.freedesktop.PolicyKit1";
+const POLKIT_AUTHORITY_PATH: &str = "/org/freedesktop/PolicyKit1/Authority";
+const POLKIT_AUTHORITY_INTERFACE: &str = "org.freedesktop.PolicyKit1.Authority";
+
 pub(crate) type ListUnitItem = (
     String,
     String,
@@ -26,45 +55,113 @@ pub(crate) type ListUnitItem = (
     OwnedObjectPath,
 );
 
-#[cfg(feature = "config")]
+/// `(path, state)` row returned by `Manager.ListUnitFiles`.
+pub(crate) type UnitFileItem = (String, String);
+
+/// `(cgroup, pid, command line)` row returned by `Manager.GetUnitProcesses`.
+pub(crate) type UnitProcessItem = (String, u32, String);
+
 pub(crate) type UnitFileChangeItem = (String, String, String);
-#[cfg(feature = "config")]
 pub(crate) type UnitFileChanges = Vec<UnitFileChangeItem>;
-#[cfg(feature = "config")]
 pub(crate) type EnableUnitFilesReply = (bool, UnitFileChanges);
 
+/// `CheckAuthorization` reply: `(is_authorized, is_challenge, details)`.
+pub(crate) type PolkitAuthorizationReply = (bool, bool, HashMap<String, String>);
+
+#[cfg(feature = "tasks")]
+pub(crate) type TransientProperties = Vec<(String, OwnedValue)>;
+#[cfg(feature = "tasks")]
+pub(crate) type TransientAuxUnits = Vec<(String, TransientProperties)>;
+
 #[derive(Debug)]
 pub(crate) struct Bus {
     conn: zbus::Connection,
     dbus_call_timeout: Duration,
+    property_cache: Option<PropertyCache>,
+}
+
+/// Clears the property cache when dropped, i.e. once a mutating call has returned.
+struct InvalidateOnReturn<'a>(Option<&'a PropertyCache>);
+
+impl Drop for InvalidateOnReturn<'_> {
+    fn drop(&mut self) {
+        if let Some(cache) = self.0 {
+            cache.clear();
+        }
+    }
 }
 
 impl Bus {
+    pub(crate) fn unique_name(&self) -> Option<String> {
+        self.conn.unique_name().map(|n| n.to_string())
+    }
+
     #[cfg(feature = "observe")]
     pub(crate) fn connection(&self) -> zbus::Connection {
         self.conn.clone()
     }
 
-    pub(crate) async fn connect_system(opts: &UnitBusOptions) -> Result<Self> {
+    /// Connect to the bus of the `scope` manager: the system bus, or the session bus for the
+    /// user manager.
+    pub(crate) async fn connect(opts: &UnitBusOptions, scope: ManagerScope) -> Result<Self> {
         let dbus_call_timeout = opts.dbus_call_timeout;
-        let conn = zbus::connection::Builder::system()
+        let (builder, backend) = match scope {
+            ManagerScope::User => (zbus::connection::Builder::session(), "session_bus"),
+            _ => (zbus::connection::Builder::system(), "system_bus"),
+        };
+        let conn = builder
             .map_err(|e| Error::BackendUnavailable {
-                backend: "system_bus",
+                backend,
                 detail: e.to_string(),
             })?
             .method_timeout(dbus_call_timeout)
             .build()
             .await
             .map_err(|e| Error::BackendUnavailable {
-                backend: "system_bus",
+                backend,
                 detail: e.to_string(),
             })?;
         Ok(Self {
             conn,
             dbus_call_timeout,
+            property_cache: opts.property_cache.as_ref().and_then(PropertyCache::new),
         })
     }
 
+    /// Connect to the bus at `address` (e.g. a container's system bus socket).
+    #[cfg(feature = "machines")]
+    pub(crate) async fn connect_address(address: &str, opts: &UnitBusOptions) -> Result<Self> {
+        let dbus_call_timeout = opts.dbus_call_timeout;
+        let conn = zbus::connection::Builder::address(address)
+            .map_err(|e| Error::BackendUnavailable {
+                backend: "machine_bus",
+                detail: e.to_string(),
+            })?
+            .method_timeout(dbus_call_timeout)
+            .build()
+            .await
+            .map_err(|e| Error::BackendUnavailable {
+                backend: "machine_bus",
+                detail: format!("{address}: {e}"),
+            })?;
+        Ok(Self {
+            conn,
+            dbus_call_timeout,
+            property_cache: opts.property_cache.as_ref().and_then(PropertyCache::new),
+        })
+    }
+
+    /// Drop cached properties, e.g. after a job changed unit state.
+    pub(crate) fn invalidate_properties(&self) {
+        if let Some(cache) = &self.property_cache {
+            cache.clear();
+        }
+    }
+
+    fn invalidate_properties_on_return(&self) -> InvalidateOnReturn<'_> {
+        InvalidateOnReturn(self.property_cache.as_ref())
+    }
+
     pub(crate) async fn manager_proxy(&self) -> Result<zbus::Proxy<'_>> {
         zbus::Proxy::new(
             &self.conn,
@@ -78,91 +175,236 @@ impl Bus {
 
     pub(crate) async fn get_unit_path(&self, unit: &str) -> Result<OwnedObjectPath> {
         let proxy = self.manager_proxy().await?;
-        proxy
-            .call("GetUnit", &(unit))
+        crate::instrument::dbus_call("GetUnit", proxy.call("GetUnit", &(unit)))
             .await
             .map_err(|e| map_zbus_method_error("get_unit", self.dbus_call_timeout, e, Some(unit)))
     }
 
+    pub(crate) async fn load_unit(&self, unit: &str) -> Result<OwnedObjectPath> {
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call("LoadUnit", proxy.call("LoadUnit", &(unit)))
+            .await
+            .map_err(|e| map_zbus_method_error("load_unit", self.dbus_call_timeout, e, Some(unit)))
+    }
+
     pub(crate) async fn start_unit(&self, unit: &str, mode: &str) -> Result<OwnedObjectPath> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
-        proxy
-            .call("StartUnit", &(unit, mode))
+        crate::instrument::dbus_call("StartUnit", proxy.call("StartUnit", &(unit, mode)))
             .await
             .map_err(|e| map_zbus_method_error("start_unit", self.dbus_call_timeout, e, Some(unit)))
     }
 
     pub(crate) async fn stop_unit(&self, unit: &str, mode: &str) -> Result<OwnedObjectPath> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
-        proxy
-            .call("StopUnit", &(unit, mode))
+        crate::instrument::dbus_call("StopUnit", proxy.call("StopUnit", &(unit, mode)))
             .await
             .map_err(|e| map_zbus_method_error("stop_unit", self.dbus_call_timeout, e, Some(unit)))
     }
 
-    pub(crate) async fn restart_unit(&self, unit: &str, mode: &str) -> Result<OwnedObjectPath> {
+    /// `Manager.KillUnit`: send `signal` to the unit's `whom` (`main`, `control` or `all`)
+    /// processes.
+    pub(crate) async fn kill_unit(&self, unit: &str, whom: &str, signal: i32) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
-        proxy.call("RestartUnit", &(unit, mode)).await.map_err(|e| {
-            map_zbus_method_error("restart_unit", self.dbus_call_timeout, e, Some(unit))
+        crate::instrument::dbus_call(
+            "KillUnit",
+            proxy.call::<_, _, ()>("KillUnit", &(unit, whom, signal)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("kill_unit", self.dbus_call_timeout, e, Some(unit)))
+    }
+
+    /// `Manager.ResetFailedUnit`: clear the failed state and start-rate counter of one unit.
+    pub(crate) async fn reset_failed_unit(&self, unit: &str) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call(
+            "ResetFailedUnit",
+            proxy.call::<_, _, ()>("ResetFailedUnit", &(unit,)),
+        )
+        .await
+        .map_err(|e| {
+            map_zbus_method_error("reset_failed_unit", self.dbus_call_timeout, e, Some(unit))
         })
     }
 
+    /// `Manager.FreezeUnit` / `Manager.ThawUnit` (systemd >= 246); replies once the cgroup
+    /// freezer has settled.
+    pub(crate) async fn freeze_unit(&self, unit: &str, freeze: bool) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        let (method, action) = if freeze {
+            ("FreezeUnit", "freeze_unit")
+        } else {
+            ("ThawUnit", "thaw_unit")
+        };
+        crate::instrument::dbus_call(method, proxy.call::<_, _, ()>(method, &(unit,)))
+            .await
+            .map_err(|e| map_zbus_method_error(action, self.dbus_call_timeout, e, Some(unit)))
+    }
+
+    /// `Manager.ResetFailed`: `reset_failed_unit` for every unit.
+    pub(crate) async fn reset_failed(&self) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call("ResetFailed", proxy.call::<_, _, ()>("ResetFailed", &()))
+            .await
+            .map_err(|e| map_zbus_method_error("reset_failed", self.dbus_call_timeout, e, None))
+    }
+
+    pub(crate) async fn restart_unit(&self, unit: &str, mode: &str) -> Result<OwnedObjectPath> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call("RestartUnit", proxy.call("RestartUnit", &(unit, mode)))
+            .await
+            .map_err(|e| {
+                map_zbus_method_error("restart_unit", self.dbus_call_timeout, e, Some(unit))
+            })
+    }
+
     pub(crate) async fn reload_unit(&self, unit: &str, mode: &str) -> Result<OwnedObjectPath> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
-        proxy.call("ReloadUnit", &(unit, mode)).await.map_err(|e| {
-            map_zbus_method_error("reload_unit", self.dbus_call_timeout, e, Some(unit))
-        })
+        crate::instrument::dbus_call("ReloadUnit", proxy.call("ReloadUnit", &(unit, mode)))
+            .await
+            .map_err(|e| {
+                map_zbus_method_error("reload_unit", self.dbus_call_timeout, e, Some(unit))
+            })
     }
 
     pub(crate) async fn list_units(&self) -> Result<Vec<ListUnitItem>> {
         let proxy = self.manager_proxy().await?;
-        proxy
-            .call("ListUnits", &())
+        crate::instrument::dbus_call("ListUnits", proxy.call("ListUnits", &()))
             .await
             .map_err(|e| map_zbus_method_error("list_units", self.dbus_call_timeout, e, None))
     }
 
-    pub(crate) async fn list_units_filtered(&self, states: &[&str]) -> Result<Vec<ListUnitItem>> {
+    pub(crate) async fn list_unit_files(&self) -> Result<Vec<UnitFileItem>> {
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call("ListUnitFiles", proxy.call("ListUnitFiles", &()))
+            .await
+            .map_err(|e| map_zbus_method_error("list_unit_files", self.dbus_call_timeout, e, None))
+    }
+
+    pub(crate) async fn get_unit_processes(&self, unit: &str) -> Result<Vec<UnitProcessItem>> {
         let proxy = self.manager_proxy().await?;
-        proxy
-            .call("ListUnitsFiltered", &(states))
+        crate::instrument::dbus_call("GetUnitProcesses", proxy.call("GetUnitProcesses", &(unit,)))
             .await
             .map_err(|e| {
-                map_zbus_method_error("list_units_filtered", self.dbus_call_timeout, e, None)
+                map_zbus_method_error("get_unit_processes", self.dbus_call_timeout, e, Some(unit))
             })
     }
 
-    #[cfg(feature = "config")]
+    pub(crate) async fn list_units_by_patterns(
+        &self,
+        states: &[&str],
+        patterns: &[&str],
+    ) -> Result<Vec<ListUnitItem>> {
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call(
+            "ListUnitsByPatterns",
+            proxy.call("ListUnitsByPatterns", &(states, patterns)),
+        )
+        .await
+        .map_err(|e| {
+            map_zbus_method_error("list_units_by_patterns", self.dbus_call_timeout, e, None)
+        })
+    }
+
+    pub(crate) async fn list_units_filtered(&self, states: &[&str]) -> Result<Vec<ListUnitItem>> {
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call(
+            "ListUnitsFiltered",
+            proxy.call("ListUnitsFiltered", &(states)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("list_units_filtered", self.dbus_call_timeout, e, None))
+    }
+
     pub(crate) async fn enable_unit_files(
         &self,
         files: &[String],
         runtime: bool,
         force: bool,
     ) -> Result<EnableUnitFilesReply> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
         let files: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
-        proxy
-            .call("EnableUnitFiles", &(files, runtime, force))
-            .await
-            .map_err(|e| {
-                map_zbus_method_error("enable_unit_files", self.dbus_call_timeout, e, None)
-            })
+        crate::instrument::dbus_call(
+            "EnableUnitFiles",
+            proxy.call("EnableUnitFiles", &(files, runtime, force)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("enable_unit_files", self.dbus_call_timeout, e, None))
     }
 
-    #[cfg(feature = "config")]
     pub(crate) async fn disable_unit_files(
         &self,
         files: &[String],
         runtime: bool,
     ) -> Result<UnitFileChanges> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
         let files: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
-        proxy
-            .call("DisableUnitFiles", &(files, runtime))
-            .await
-            .map_err(|e| {
-                map_zbus_method_error("disable_unit_files", self.dbus_call_timeout, e, None)
-            })
+        crate::instrument::dbus_call(
+            "DisableUnitFiles",
+            proxy.call("DisableUnitFiles", &(files, runtime)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("disable_unit_files", self.dbus_call_timeout, e, None))
+    }
+
+    pub(crate) async fn mask_unit_files(
+        &self,
+        files: &[String],
+        runtime: bool,
+        force: bool,
+    ) -> Result<UnitFileChanges> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        let files: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
+        crate::instrument::dbus_call(
+            "MaskUnitFiles",
+            proxy.call("MaskUnitFiles", &(files, runtime, force)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("mask_unit_files", self.dbus_call_timeout, e, None))
+    }
+
+    pub(crate) async fn unmask_unit_files(
+        &self,
+        files: &[String],
+        runtime: bool,
+    ) -> Result<UnitFileChanges> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        let files: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
+        crate::instrument::dbus_call(
+            "UnmaskUnitFiles",
+            proxy.call("UnmaskUnitFiles", &(files, runtime)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("unmask_unit_files", self.dbus_call_timeout, e, None))
+    }
+
+    pub(crate) async fn preset_unit_files(
+        &self,
+        files: &[String],
+        mode: &str,
+        runtime: bool,
+        force: bool,
+    ) -> Result<EnableUnitFilesReply> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        let files: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
+        crate::instrument::dbus_call(
+            "PresetUnitFilesWithMode",
+            proxy.call("PresetUnitFilesWithMode", &(files, mode, runtime, force)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("preset_unit_files", self.dbus_call_timeout, e, None))
     }
 
     #[cfg(feature = "tasks")]
@@ -170,30 +412,222 @@ impl Bus {
         &self,
         name: &str,
         mode: &str,
-        properties: Vec<(String, OwnedValue)>,
+        properties: TransientProperties,
+        aux: TransientAuxUnits,
     ) -> Result<OwnedObjectPath> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
-        let aux: Vec<(String, Vec<(String, OwnedValue)>)> = Vec::new();
-        proxy
-            .call("StartTransientUnit", &(name, mode, properties, aux))
+        crate::instrument::dbus_call(
+            "StartTransientUnit",
+            proxy.call("StartTransientUnit", &(name, mode, properties, aux)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("run_task", self.dbus_call_timeout, e, Some(name)))
+    }
+
+    /// Ask polkit whether this connection is authorized for `action_id` without interaction.
+    pub(crate) async fn polkit_check_authorization(
+        &self,
+        action_id: &str,
+        details: &HashMap<String, String>,
+    ) -> Result<PolkitAuthorizationReply> {
+        let unique_name = self
+            .conn
+            .unique_name()
+            .map(|n| n.to_string())
+            .ok_or_else(|| Error::BackendUnavailable {
+                backend: "system_bus",
+                detail: "connection has no unique name".to_string(),
+            })?;
+        let proxy = zbus::Proxy::new(
+            &self.conn,
+            POLKIT_DESTINATION,
+            POLKIT_AUTHORITY_PATH,
+            POLKIT_AUTHORITY_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)?;
+
+        let mut subject_details = HashMap::<&str, zbus::zvariant::Value<'_>>::new();
+        subject_details.insert("name", unique_name.as_str().into());
+        let subject = ("system-bus-name", subject_details);
+        // Flags 0: never trigger an interactive authentication dialog.
+        crate::instrument::dbus_call(
+            "CheckAuthorization",
+            proxy.call(
+                "CheckAuthorization",
+                &(subject, action_id, details, 0u32, ""),
+            ),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("check_authorization", self.dbus_call_timeout, e, None))
+    }
+
+    pub(crate) async fn dump(&self) -> Result<String> {
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call("Dump", proxy.call("Dump", &()))
             .await
-            .map_err(|e| map_zbus_method_error("run_task", self.dbus_call_timeout, e, Some(name)))
+            .map_err(|e| map_zbus_method_error("dump", self.dbus_call_timeout, e, None))
+    }
+
+    #[cfg(unix)]
+    pub(crate) async fn dump_by_fd(&self) -> Result<std::os::fd::OwnedFd> {
+        let proxy = self.manager_proxy().await?;
+        let fd: zbus::zvariant::OwnedFd = crate::instrument::dbus_call(
+            "DumpByFileDescriptor",
+            proxy.call("DumpByFileDescriptor", &()),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("dump", self.dbus_call_timeout, e, None))?;
+        Ok(fd.into())
     }
 
+    /// Schedule a logind shutdown of `kind` (`"reboot"`, `"poweroff"`, ...) at `usec` (realtime).
     #[cfg(feature = "config")]
+    pub(crate) async fn logind_schedule_shutdown(&self, kind: &str, usec: u64) -> Result<()> {
+        let proxy = zbus::Proxy::new(
+            &self.conn,
+            LOGIND_DESTINATION,
+            LOGIND_MANAGER_PATH,
+            LOGIND_MANAGER_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)?;
+        crate::instrument::dbus_call(
+            "ScheduleShutdown",
+            proxy.call::<_, _, ()>("ScheduleShutdown", &(kind, usec)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("schedule_shutdown", self.dbus_call_timeout, e, None))
+    }
+
+    /// Take a logind inhibitor lock (`org.freedesktop.login1.Manager.Inhibit`); the lock is held
+    /// until the returned descriptor is closed.
+    #[cfg(all(feature = "config", unix))]
+    pub(crate) async fn logind_inhibit(
+        &self,
+        what: &str,
+        who: &str,
+        why: &str,
+        mode: &str,
+    ) -> Result<std::os::fd::OwnedFd> {
+        let proxy = zbus::Proxy::new(
+            &self.conn,
+            LOGIND_DESTINATION,
+            LOGIND_MANAGER_PATH,
+            LOGIND_MANAGER_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)?;
+        let fd: zbus::zvariant::OwnedFd =
+            crate::instrument::dbus_call("Inhibit", proxy.call("Inhibit", &(what, who, why, mode)))
+                .await
+                .map_err(|e| map_zbus_method_error("inhibit", self.dbus_call_timeout, e, None))?;
+        Ok(fd.into())
+    }
+
     pub(crate) async fn daemon_reload(&self) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
         let proxy = self.manager_proxy().await?;
-        proxy
-            .call::<_, _, ()>("Reload", &())
+        crate::instrument::dbus_call("Reload", proxy.call::<_, _, ()>("Reload", &()))
             .await
             .map_err(|e| map_zbus_method_error("daemon_reload", self.dbus_call_timeout, e, None))
     }
 
+    /// Enable unit/job signal broadcasting for this connection (`Manager.Subscribe`).
+    #[cfg(feature = "observe")]
+    pub(crate) async fn subscribe(&self) -> Result<()> {
+        let proxy = self.manager_proxy().await?;
+        match crate::instrument::dbus_call("Subscribe", proxy.call::<_, _, ()>("Subscribe", &()))
+            .await
+        {
+            Ok(()) => Ok(()),
+            // Already subscribed on this connection.
+            Err(zbus::Error::MethodError(name, _, _)) if name.contains("AlreadySubscribed") => {
+                Ok(())
+            }
+            Err(e) => Err(map_zbus_method_error(
+                "subscribe",
+                self.dbus_call_timeout,
+                e,
+                None,
+            )),
+        }
+    }
+
+    pub(crate) async fn unsubscribe(&self) -> Result<()> {
+        let proxy = self.manager_proxy().await?;
+        match crate::instrument::dbus_call(
+            "Unsubscribe",
+            proxy.call::<_, _, ()>("Unsubscribe", &()),
+        )
+        .await
+        {
+            Ok(()) => Ok(()),
+            // Never subscribed (or already unsubscribed) on this connection.
+            Err(zbus::Error::MethodError(name, _, _)) if name.contains("NotSubscribed") => Ok(()),
+            Err(e) => Err(map_zbus_method_error(
+                "unsubscribe",
+                self.dbus_call_timeout,
+                e,
+                None,
+            )),
+        }
+    }
+
+    /// `GetAll`, served from the property cache (if enabled) for unit objects.
     pub(crate) async fn get_all_properties(
         &self,
         object_path: &str,
         interface: &str,
     ) -> Result<HashMap<String, OwnedValue>> {
+        let Some(cache) = self
+            .property_cache
+            .as_ref()
+            .filter(|_| PropertyCache::cacheable(object_path))
+        else {
+            return self
+                .get_all_properties_uncached(object_path, interface)
+                .await;
+        };
+        if let Some(props) = cache.get(object_path, interface) {
+            return Ok(props);
+        }
+        let props = self
+            .get_all_properties_uncached(object_path, interface)
+            .await?;
+        cache.insert(object_path, interface, &props);
+        Ok(props)
+    }
+
+    /// `GetAll`, bypassing the property cache (baselines for signal-driven views).
+    pub(crate) async fn get_all_properties_uncached(
+        &self,
+        object_path: &str,
+        interface: &str,
+    ) -> Result<HashMap<String, OwnedValue>> {
+        let proxy = zbus::Proxy::new(
+            &self.conn,
+            SYSTEMD_DESTINATION,
+            object_path,
+            DBUS_PROPERTIES_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)?;
+
+        crate::instrument::dbus_call("GetAll", proxy.call("GetAll", &(interface)))
+            .await
+            .map_err(|e| {
+                map_zbus_method_error("get_all_properties", self.dbus_call_timeout, e, None)
+            })
+    }
+
+    pub(crate) async fn get_property(
+        &self,
+        object_path: &str,
+        interface: &str,
+        name: &str,
+    ) -> Result<OwnedValue> {
         let proxy = zbus::Proxy::new(
             &self.conn,
             SYSTEMD_DESTINATION,
@@ -203,12 +637,192 @@ impl Bus {
         .await
         .map_err(map_zbus_error)?;
 
-        proxy.call("GetAll", &(interface)).await.map_err(|e| {
-            map_zbus_method_error("get_all_properties", self.dbus_call_timeout, e, None)
+        crate::instrument::dbus_call("Get", proxy.call("Get", &(interface, name)))
+            .await
+            .map_err(|e| map_zbus_method_error("get_property", self.dbus_call_timeout, e, None))
+    }
+
+    /// `org.freedesktop.DBus.Properties.Set` on a systemd object.
+    pub(crate) async fn set_property(
+        &self,
+        object_path: &str,
+        interface: &str,
+        name: &str,
+        value: zbus::zvariant::Value<'_>,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = zbus::Proxy::new(
+            &self.conn,
+            SYSTEMD_DESTINATION,
+            object_path,
+            DBUS_PROPERTIES_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)?;
+
+        crate::instrument::dbus_call(
+            "Set",
+            proxy.call::<_, _, ()>("Set", &(interface, name, value)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("set_property", self.dbus_call_timeout, e, None))
+    }
+
+    /// `Manager.SetUnitProperties`: change unit settings, either until reboot (`runtime`) or
+    /// persistently via a control drop-in.
+    pub(crate) async fn set_unit_properties(
+        &self,
+        unit: &str,
+        runtime: bool,
+        properties: Vec<(String, OwnedValue)>,
+    ) -> Result<()> {
+        let _invalidate = self.invalidate_properties_on_return();
+        let proxy = self.manager_proxy().await?;
+        crate::instrument::dbus_call(
+            "SetUnitProperties",
+            proxy.call::<_, _, ()>("SetUnitProperties", &(unit, runtime, properties)),
+        )
+        .await
+        .map_err(|e| {
+            map_zbus_method_error("set_unit_properties", self.dbus_call_timeout, e, Some(unit))
         })
     }
 
+    #[cfg(feature = "machines")]
+    async fn machined_proxy(&self) -> Result<zbus::Proxy<'_>> {
+        zbus::Proxy::new(
+            &self.conn,
+            MACHINED_DESTINATION,
+            MACHINED_MANAGER_PATH,
+            MACHINED_MANAGER_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)
+    }
+
+    /// `org.freedesktop.machine1.Manager.GetMachine`; a `NoSuchMachine` D-Bus error when `name`
+    /// is not registered.
+    #[cfg(feature = "machines")]
+    pub(crate) async fn get_machine(&self, name: &str) -> Result<OwnedObjectPath> {
+        let proxy = self.machined_proxy().await?;
+        crate::instrument::dbus_call("GetMachine", proxy.call("GetMachine", &(name)))
+            .await
+            .map_err(|e| map_zbus_method_error("get_machine", self.dbus_call_timeout, e, None))
+    }
+
+    /// `org.freedesktop.machine1.Manager.TerminateMachine`: kill all processes of `name`.
+    #[cfg(feature = "machines")]
+    pub(crate) async fn terminate_machine(&self, name: &str) -> Result<()> {
+        let proxy = self.machined_proxy().await?;
+        crate::instrument::dbus_call(
+            "TerminateMachine",
+            proxy.call::<_, _, ()>("TerminateMachine", &(name)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("terminate_machine", self.dbus_call_timeout, e, None))
+    }
+
+    /// A property of a machined machine object (see `get_machine`).
+    #[cfg(feature = "machines")]
+    pub(crate) async fn get_machine_property(
+        &self,
+        object_path: &str,
+        name: &str,
+    ) -> Result<OwnedValue> {
+        self.get_service_property(
+            MACHINED_DESTINATION,
+            object_path,
+            MACHINED_MACHINE_INTERFACE,
+            name,
+        )
+        .await
+    }
+
+    /// `org.freedesktop.import1.Manager` proxy; owned so signal streams can outlive `&self`.
+    #[cfg(feature = "machines")]
+    pub(crate) async fn import_proxy(&self) -> Result<zbus::Proxy<'static>> {
+        zbus::Proxy::new(
+            &self.conn,
+            IMPORTD_DESTINATION,
+            IMPORTD_MANAGER_PATH,
+            IMPORTD_MANAGER_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)
+    }
+
+    /// `PullTar`/`PullRaw`: start an importd download; returns `(transfer id, transfer path)`.
+    #[cfg(feature = "machines")]
+    pub(crate) async fn import_pull(
+        &self,
+        method: &'static str,
+        url: &str,
+        local: &str,
+        verify: &str,
+        force: bool,
+    ) -> Result<(u32, OwnedObjectPath)> {
+        let proxy = self.import_proxy().await?;
+        crate::instrument::dbus_call(method, proxy.call(method, &(url, local, verify, force)))
+            .await
+            .map_err(|e| map_zbus_method_error("pull_image", self.dbus_call_timeout, e, None))
+    }
+
+    /// `org.freedesktop.import1.Manager.CancelTransfer`.
+    #[cfg(feature = "machines")]
+    pub(crate) async fn cancel_transfer(&self, id: u32) -> Result<()> {
+        let proxy = self.import_proxy().await?;
+        crate::instrument::dbus_call(
+            "CancelTransfer",
+            proxy.call::<_, _, ()>("CancelTransfer", &(id)),
+        )
+        .await
+        .map_err(|e| map_zbus_method_error("cancel_transfer", self.dbus_call_timeout, e, None))
+    }
+
+    /// A property of an importd transfer object (see `import_pull`).
+    #[cfg(feature = "machines")]
+    pub(crate) async fn get_transfer_property(
+        &self,
+        object_path: &str,
+        name: &str,
+    ) -> Result<OwnedValue> {
+        self.get_service_property(
+            IMPORTD_DESTINATION,
+            object_path,
+            IMPORTD_TRANSFER_INTERFACE,
+            name,
+        )
+        .await
+    }
+
+    /// `org.freedesktop.DBus.Properties.Get` on a non-systemd service object.
+    #[cfg(feature = "machines")]
+    async fn get_service_property(
+        &self,
+        destination: &'static str,
+        object_path: &str,
+        interface: &str,
+        name: &str,
+    ) -> Result<OwnedValue> {
+        let proxy = zbus::Proxy::new(
+            &self.conn,
+            destination,
+            object_path,
+            DBUS_PROPERTIES_INTERFACE,
+        )
+        .await
+        .map_err(map_zbus_error)?;
+
+        crate::instrument::dbus_call("Get", proxy.call("Get", &(interface, name)))
+            .await
+            .map_err(|e| map_zbus_method_error("get_property", self.dbus_call_timeout, e, None))
+    }
+
     pub(crate) async fn job_exists(&self, job_path: &str) -> Result<bool> {
+        // systemd's "no job" placeholder (e.g. a unit's `Job` property when idle).
+        if job_path == "/" {
+            return Ok(false);
+        }
         match self
             .get_all_properties(job_path, SYSTEMD_JOB_INTERFACE)
             .await
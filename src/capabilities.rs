@@ -1,10 +1,22 @@
+use futures_util::future::Either;
+use std::future::Future;
+use std::pin::pin;
+use std::time::{Duration, Instant};
+
 /// Probe capabilities conservatively (never guess true).
+///
+/// The probes run concurrently under one deadline (`UnitBusOptions.capabilities_timeout`); a
+/// probe still running at the deadline counts as `false` (or an empty `EnvironmentReport`).
 pub(crate) async fn probe(bus: &crate::UnitBus) -> Capabilities {
-    let can_read_units = bus.units().get_status("dbus.service").await.is_ok();
+    let deadline = Instant::now() + bus.inner.opts.capabilities_timeout;
+
+    let read_units = timed("read_units", deadline, false, async {
+        bus.units().get_status("dbus.service").await.is_ok()
+    });
 
-    let can_control_units = probe_control_units(bus).await;
+    let control_units = timed("control_units", deadline, false, probe_control_units(bus));
 
-    let can_read_journal = {
+    let read_journal = timed("read_journal", deadline, false, async {
         #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
         {
             let filter = crate::types::journal::JournalFilter {
@@ -18,42 +30,226 @@ pub(crate) async fn probe(bus: &crate::UnitBus) -> Capabilities {
         {
             false
         }
-    };
+    });
 
-    let can_write_dropins = {
+    let run_tasks = timed("run_tasks", deadline, false, async {
+        #[cfg(feature = "tasks")]
+        {
+            probe_run_tasks(bus).await
+        }
+
+        #[cfg(not(feature = "tasks"))]
+        {
+            false
+        }
+    });
+
+    let write_dropins = timed("write_dropins", deadline, false, async {
         #[cfg(feature = "config")]
         {
-            probe_write_dropins(&bus.inner.opts.systemd_system_dir)
+            match bus.inner.opts.resolve_unit_dir(bus.inner.scope) {
+                Ok(dir) => {
+                    blocking::unblock(move || probe_write_dropins(&dir.to_string_lossy())).await
+                }
+                Err(_) => false,
+            }
         }
 
         #[cfg(not(feature = "config"))]
         {
             false
         }
-    };
+    });
+
+    let environment = timed(
+        "environment",
+        deadline,
+        EnvironmentReport::default(),
+        probe_environment(bus),
+    );
+
+    let (
+        (can_read_units, t_read_units),
+        (can_control_units, t_control_units),
+        (can_read_journal, t_read_journal),
+        (can_run_tasks, t_run_tasks),
+        (can_write_dropins, t_write_dropins),
+        (environment, t_environment),
+    ) = futures_util::join!(
+        read_units,
+        control_units,
+        read_journal,
+        run_tasks,
+        write_dropins,
+        environment
+    );
 
     Capabilities {
         can_read_units,
         can_control_units,
         can_read_journal,
+        can_run_tasks,
         can_write_dropins,
+        environment,
+        probe_timings: vec![
+            t_read_units,
+            t_control_units,
+            t_read_journal,
+            t_run_tasks,
+            t_write_dropins,
+            t_environment,
+        ],
+    }
+}
+
+/// Run one probe until `deadline`, returning `fallback` if it does not finish in time.
+async fn timed<T>(
+    name: &'static str,
+    deadline: Instant,
+    fallback: T,
+    fut: impl Future<Output = T>,
+) -> (T, ProbeTiming) {
+    let started = Instant::now();
+    let timer = crate::runtime::sleep(deadline.saturating_duration_since(started));
+    let (value, timed_out) = match futures_util::future::select(pin!(fut), timer).await {
+        Either::Left((value, _)) => (value, false),
+        Either::Right(((), _)) => (fallback, true),
+    };
+    let timing = ProbeTiming {
+        name,
+        duration: started.elapsed(),
+        timed_out,
+    };
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(probe = name, duration = ?timing.duration, timed_out, "capability probe");
+
+    (value, timing)
+}
+
+async fn probe_environment(bus: &crate::UnitBus) -> EnvironmentReport {
+    let info = crate::manager::Manager::new(bus.inner.clone())
+        .info()
+        .await
+        .ok();
+    let (systemd_version, virtualization) = match info {
+        Some(i) => (i.version, i.virtualization),
+        None => (None, None),
+    };
+
+    let container = virtualization
+        .as_deref()
+        .filter(|v| is_container_virtualization(v))
+        .map(str::to_string)
+        .or_else(detect_container_from_files);
+
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+
+    EnvironmentReport {
+        container,
+        virtualization,
+        cgroup: cgroup_version_from_mounts(&mounts),
+        selinux: read_selinux_mode(),
+        apparmor_enabled: read_trimmed("/sys/module/apparmor/parameters/enabled")
+            .is_some_and(|v| v == "Y"),
+        etc_read_only: mount_read_only(&mounts, "/etc"),
+        systemd_version,
+        manager_scope: bus.inner.scope,
+    }
+}
+
+/// Container technologies as reported by systemd's `Virtualization` (see `systemd-detect-virt`).
+fn is_container_virtualization(v: &str) -> bool {
+    matches!(
+        v,
+        "openvz"
+            | "lxc"
+            | "lxc-libvirt"
+            | "systemd-nspawn"
+            | "docker"
+            | "podman"
+            | "rkt"
+            | "wsl"
+            | "proot"
+            | "pouch"
+    )
+}
+
+fn detect_container_from_files() -> Option<String> {
+    if let Some(v) = read_trimmed("/run/systemd/container").filter(|v| !v.is_empty()) {
+        return Some(v);
+    }
+    if std::path::Path::new("/run/.containerenv").exists() {
+        return Some("podman".to_string());
+    }
+    if std::path::Path::new("/.dockerenv").exists() {
+        return Some("docker".to_string());
+    }
+    None
+}
+
+fn cgroup_version_from_mounts(mounts: &str) -> CgroupVersion {
+    let mut unified_root = false;
+    let mut unified_hybrid = false;
+    let mut legacy = false;
+    for line in mounts.lines() {
+        let mut it = line.split_whitespace();
+        let (Some(_dev), Some(mountpoint), Some(fstype)) = (it.next(), it.next(), it.next()) else {
+            continue;
+        };
+        match (fstype, mountpoint) {
+            ("cgroup2", "/sys/fs/cgroup") => unified_root = true,
+            ("cgroup2", "/sys/fs/cgroup/unified") => unified_hybrid = true,
+            ("cgroup", m) if m.starts_with("/sys/fs/cgroup/") => legacy = true,
+            _ => {}
+        }
+    }
+
+    if unified_root {
+        CgroupVersion::V2
+    } else if legacy && unified_hybrid {
+        CgroupVersion::Hybrid
+    } else if legacy {
+        CgroupVersion::V1
+    } else {
+        CgroupVersion::Unknown
     }
 }
 
+fn read_selinux_mode() -> Option<SelinuxMode> {
+    match read_trimmed("/sys/fs/selinux/enforce")?.as_str() {
+        "1" => Some(SelinuxMode::Enforcing),
+        "0" => Some(SelinuxMode::Permissive),
+        _ => None,
+    }
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
 async fn probe_control_units(bus: &crate::UnitBus) -> bool {
     let proxy = match bus.inner.bus.manager_proxy().await {
         Ok(p) => p,
         Err(_) => return false,
     };
 
-    let two_arg: std::result::Result<String, zbus::Error> = proxy
-        .call("CanStartUnit", &("dbus.service", "replace"))
-        .await;
+    let two_arg: std::result::Result<String, zbus::Error> = crate::instrument::dbus_call(
+        "CanStartUnit",
+        proxy.call("CanStartUnit", &("dbus.service", "replace")),
+    )
+    .await;
 
     let res = match two_arg {
         Ok(s) => Ok(s),
         Err(zbus::Error::MethodError(name, _, _)) if name.contains("InvalidArgs") => {
-            proxy.call("CanStartUnit", &("dbus.service")).await
+            crate::instrument::dbus_call(
+                "CanStartUnit",
+                proxy.call("CanStartUnit", &("dbus.service")),
+            )
+            .await
         }
         Err(e) => Err(e),
     };
@@ -71,11 +267,95 @@ async fn probe_control_units(bus: &crate::UnitBus) -> bool {
     }
 }
 
+/// `StartTransientUnit` is gated by the `manage-units` polkit action; systemd lets privileged
+/// callers through without consulting polkit at all.
+#[cfg(feature = "tasks")]
+async fn probe_run_tasks(bus: &crate::UnitBus) -> bool {
+    #[cfg(unix)]
+    if ProcCreds::read().is_some_and(|c| c.euid == 0) {
+        return true;
+    }
+
+    check_polkit(
+        bus,
+        "org.freedesktop.systemd1.manage-units",
+        &Default::default(),
+    )
+    .await
+    .is_ok_and(|r| r.authorized)
+}
+
+/// Capability checks of a `UnitBus`, created by `UnitBus::capabilities`.
+///
+/// Awaiting it runs the full probe and yields `Capabilities`.
+#[derive(Clone, Debug)]
+pub struct CapabilityChecks {
+    bus: crate::UnitBus,
+}
+
+impl CapabilityChecks {
+    pub(crate) fn new(bus: crate::UnitBus) -> Self {
+        Self { bus }
+    }
+
+    /// Pre-flight an exact polkit action (e.g. `org.freedesktop.systemd1.manage-unit-files`,
+    /// `org.freedesktop.systemd1.reload-daemon`) via `org.freedesktop.PolicyKit1.Authority`.
+    ///
+    /// The check is non-interactive; `challenge=true` means the action would need
+    /// authentication. Callers running as root are usually authorized by systemd without
+    /// polkit being consulted, so check `euid` first if that matters.
+    pub async fn check_polkit(
+        &self,
+        action_id: &str,
+        details: &std::collections::HashMap<String, String>,
+    ) -> crate::Result<PolkitAuthorization> {
+        check_polkit(&self.bus, action_id, details).await
+    }
+}
+
+impl std::future::IntoFuture for CapabilityChecks {
+    type Output = Capabilities;
+    type IntoFuture = crate::runtime::BoxFuture<Capabilities>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move { probe(&self.bus).await })
+    }
+}
+
+/// Ask polkit (`CheckAuthorization`, non-interactive) about `action_id` for this connection.
+async fn check_polkit(
+    bus: &crate::UnitBus,
+    action_id: &str,
+    details: &std::collections::HashMap<String, String>,
+) -> crate::Result<PolkitAuthorization> {
+    crate::util::validate_no_control("action_id", action_id)?;
+    let action_id = action_id.trim();
+    if action_id.is_empty() {
+        return Err(crate::Error::invalid_input("action_id must not be empty"));
+    }
+    for (k, v) in details {
+        crate::util::validate_no_control("polkit detail key", k)?;
+        crate::util::validate_no_control("polkit detail value", v)?;
+    }
+
+    let (authorized, challenge, details) = bus
+        .inner
+        .bus
+        .polkit_check_authorization(action_id, details)
+        .await?;
+    Ok(PolkitAuthorization {
+        action_id: action_id.to_string(),
+        authorized,
+        challenge,
+        details,
+    })
+}
+
 #[cfg(feature = "config")]
-fn probe_write_dropins(_systemd_system_dir: &str) -> bool {
+fn probe_write_dropins(_unit_dir: &str) -> bool {
     #[cfg(unix)]
     {
-        probe_write_dropins_unix(_systemd_system_dir)
+        probe_write_dropins_unix(_unit_dir)
     }
 
     #[cfg(not(unix))]
@@ -130,7 +410,11 @@ fn is_mount_read_only(path: &str) -> bool {
         Ok(s) => s,
         Err(_) => return false,
     };
+    mount_read_only(&mounts, path)
+}
 
+/// Whether the mount containing `path` is read-only according to `/proc/mounts` content.
+fn mount_read_only(mounts: &str, path: &str) -> bool {
     let mut best_mount_len = 0usize;
     let mut best_ro = false;
 
@@ -160,7 +444,6 @@ fn is_mount_read_only(path: &str) -> bool {
     best_ro
 }
 
-#[cfg(all(unix, feature = "config"))]
 fn is_under_mount(path: &str, mountpoint: &str) -> bool {
     if mountpoint == "/" {
         return path.starts_with('/');
@@ -177,7 +460,6 @@ fn is_under_mount(path: &str, mountpoint: &str) -> bool {
         .is_some_and(|b| b == b'/')
 }
 
-#[cfg(all(unix, feature = "config"))]
 fn unescape_mount_field(input: &str) -> String {
     let mut out = String::new();
     let mut chars = input.chars().peekable();
@@ -209,13 +491,14 @@ fn unescape_mount_field(input: &str) -> String {
     out
 }
 
-#[cfg(all(unix, feature = "config"))]
+#[cfg(all(unix, any(feature = "config", feature = "tasks")))]
 struct ProcCreds {
     euid: u32,
+    #[cfg_attr(not(feature = "config"), allow(dead_code))]
     groups: Vec<u32>,
 }
 
-#[cfg(all(unix, feature = "config"))]
+#[cfg(all(unix, any(feature = "config", feature = "tasks")))]
 impl ProcCreds {
     fn read() -> Option<Self> {
         let status = std::fs::read_to_string("/proc/self/status").ok()?;
@@ -257,6 +540,155 @@ pub struct Capabilities {
     pub can_control_units: bool,
     /// Whether journald queries are likely to work (backend available + permission).
     pub can_read_journal: bool,
-    /// Whether drop-in writes under `/etc/systemd/system` are likely to succeed.
+    /// Whether transient tasks (`StartTransientUnit`) are likely to be authorized (feature=`tasks`).
+    pub can_run_tasks: bool,
+    /// Whether drop-in writes under the unit directory (`UnitBusOptions.unit_dir`) are likely to
+    /// succeed.
     pub can_write_dropins: bool,
+    /// Host environment facts (container, cgroups, LSMs, manager), e.g. for support bundles.
+    pub environment: EnvironmentReport,
+    /// How long each probe took, in probe order, for debugging slow buses.
+    pub probe_timings: Vec<ProbeTiming>,
+}
+
+/// Duration of one capability probe (`Capabilities.probe_timings`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ProbeTiming {
+    /// `read_units`, `control_units`, `read_journal`, `run_tasks`, `write_dropins` or
+    /// `environment`.
+    pub name: &'static str,
+    pub duration: Duration,
+    /// `true` if the probe hit `UnitBusOptions.capabilities_timeout` and was reported negative.
+    pub timed_out: bool,
+}
+
+/// Result of `CapabilityChecks::check_polkit`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PolkitAuthorization {
+    /// The polkit action that was checked (e.g. `org.freedesktop.systemd1.reload-daemon`).
+    pub action_id: String,
+    /// Whether the caller is authorized without interaction.
+    pub authorized: bool,
+    /// Whether the action would be allowed after interactive authentication.
+    pub challenge: bool,
+    /// Details returned by polkit.
+    pub details: std::collections::HashMap<String, String>,
+}
+
+/// Facts about the environment unitbus runs in (best-effort; unknown values are `None`).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EnvironmentReport {
+    /// Container technology (e.g. `"docker"`, `"podman"`, `"lxc"`, `"systemd-nspawn"`).
+    pub container: Option<String>,
+    /// systemd `Virtualization` as reported by the manager (containers and VMs, e.g. `"kvm"`).
+    pub virtualization: Option<String>,
+    /// cgroup hierarchy layout.
+    pub cgroup: CgroupVersion,
+    /// SELinux mode (`None` when SELinux is disabled or unavailable).
+    pub selinux: Option<SelinuxMode>,
+    /// Whether AppArmor is enabled in the kernel.
+    pub apparmor_enabled: bool,
+    /// Whether `/etc` is on a read-only mount.
+    pub etc_read_only: bool,
+    /// systemd version string (manager `Version`).
+    pub systemd_version: Option<String>,
+    /// Which service manager the connection talks to.
+    pub manager_scope: ManagerScope,
+}
+
+/// cgroup hierarchy layout.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CgroupVersion {
+    /// Legacy hierarchy (one mount per controller).
+    V1,
+    /// Legacy controllers plus the unified hierarchy at `/sys/fs/cgroup/unified`.
+    Hybrid,
+    /// Unified hierarchy only.
+    V2,
+    #[default]
+    Unknown,
+}
+
+/// SELinux enforcement mode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SelinuxMode {
+    Enforcing,
+    Permissive,
+}
+
+/// Service manager scope.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ManagerScope {
+    /// The system manager (PID 1) on the system bus.
+    #[default]
+    System,
+    /// A per-user manager on the session bus.
+    User,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    // `block_on` only drives the async-io timer; tokio sleeps need a tokio runtime.
+    #[cfg(feature = "rt-async-io")]
+    #[test]
+    fn timed_falls_back_at_the_deadline() {
+        let deadline = Instant::now() + Duration::from_millis(50);
+        let ((fast, t_fast), (slow, t_slow)) = futures_lite::future::block_on(async {
+            futures_util::join!(
+                timed("fast", deadline, false, async { true }),
+                timed("slow", deadline, false, async {
+                    crate::runtime::sleep(Duration::from_secs(5)).await;
+                    true
+                }),
+            )
+        });
+        assert!(fast && !t_fast.timed_out);
+        assert_eq!(t_fast.name, "fast");
+        assert!(!slow && t_slow.timed_out);
+        assert!(t_slow.duration < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn cgroup_version_is_derived_from_mounts() {
+        let v2 = "cgroup2 /sys/fs/cgroup cgroup2 rw,nosuid 0 0\n";
+        assert_eq!(cgroup_version_from_mounts(v2), CgroupVersion::V2);
+
+        let hybrid = "tmpfs /sys/fs/cgroup tmpfs ro 0 0\n\
+                      cgroup2 /sys/fs/cgroup/unified cgroup2 rw 0 0\n\
+                      cgroup /sys/fs/cgroup/memory cgroup rw,memory 0 0\n";
+        assert_eq!(cgroup_version_from_mounts(hybrid), CgroupVersion::Hybrid);
+
+        let v1 = "cgroup /sys/fs/cgroup/cpu,cpuacct cgroup rw,cpu,cpuacct 0 0\n";
+        assert_eq!(cgroup_version_from_mounts(v1), CgroupVersion::V1);
+        assert_eq!(cgroup_version_from_mounts(""), CgroupVersion::Unknown);
+    }
+
+    #[test]
+    fn mount_read_only_uses_longest_matching_mount() {
+        let mounts = "/dev/sda1 / ext4 rw 0 0\n/dev/sda2 /etc ext4 ro 0 0\n";
+        assert!(mount_read_only(mounts, "/etc"));
+        assert!(mount_read_only(mounts, "/etc/systemd/system"));
+        assert!(!mount_read_only(mounts, "/etcetera"));
+        assert!(!mount_read_only(mounts, "/var"));
+    }
+
+    #[test]
+    fn container_virtualization_excludes_vms() {
+        assert!(is_container_virtualization("docker"));
+        assert!(is_container_virtualization("systemd-nspawn"));
+        assert!(!is_container_virtualization("kvm"));
+        assert!(!is_container_virtualization("none"));
+    }
 }
@@ -0,0 +1,152 @@
+use crate::{Error, Result};
+
+use std::io::Read;
+use std::os::fd::OwnedFd;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const READ_CHUNK: usize = 8 * 1024;
+const DRAIN_POLL: Duration = Duration::from_millis(10);
+
+/// Write ends of the stdout/stderr pipes, handed to systemd as
+/// `StandardOutputFileDescriptor=` / `StandardErrorFileDescriptor=`.
+#[derive(Debug)]
+pub(crate) struct OutputPipes {
+    pub(crate) stdout: OwnedFd,
+    pub(crate) stderr: OwnedFd,
+}
+
+/// Bounded stdout/stderr of a task, filled by one reader thread per pipe.
+#[derive(Debug)]
+pub(crate) struct OutputCapture {
+    stdout: Arc<Mutex<Captured>>,
+    stderr: Arc<Mutex<Captured>>,
+}
+
+#[derive(Debug, Default)]
+struct Captured {
+    buf: Vec<u8>,
+    truncated: bool,
+    closed: bool,
+}
+
+/// Create both pipes and start draining their read ends, keeping at most `max_bytes` per stream.
+///
+/// The readers see EOF once every copy of the write ends is closed: ours when the returned
+/// `OutputPipes` are dropped after `StartTransientUnit`, systemd's when the unit stops.
+pub(crate) fn open(max_bytes: usize) -> Result<(OutputPipes, OutputCapture)> {
+    let (stdout_rx, stdout_tx) = std::io::pipe().map_err(|e| Error::IoError {
+        context: format!("create stdout pipe: {e}"),
+    })?;
+    let (stderr_rx, stderr_tx) = std::io::pipe().map_err(|e| Error::IoError {
+        context: format!("create stderr pipe: {e}"),
+    })?;
+
+    let capture = OutputCapture {
+        stdout: spawn_reader("stdout", stdout_rx, max_bytes)?,
+        stderr: spawn_reader("stderr", stderr_rx, max_bytes)?,
+    };
+    let pipes = OutputPipes {
+        stdout: stdout_tx.into(),
+        stderr: stderr_tx.into(),
+    };
+    Ok((pipes, capture))
+}
+
+fn spawn_reader(
+    stream: &'static str,
+    mut rx: std::io::PipeReader,
+    max_bytes: usize,
+) -> Result<Arc<Mutex<Captured>>> {
+    let captured = Arc::new(Mutex::new(Captured::default()));
+    let shared = captured.clone();
+    std::thread::Builder::new()
+        .name(format!("unitbus-task-{stream}"))
+        .spawn(move || {
+            let mut chunk = [0u8; READ_CHUNK];
+            loop {
+                let n = match rx.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                };
+                // Keep reading past the cap so the task never blocks on a full pipe.
+                let mut c = shared.lock().unwrap_or_else(|e| e.into_inner());
+                let room = max_bytes.saturating_sub(c.buf.len());
+                if n > room {
+                    c.truncated = true;
+                }
+                c.buf.extend_from_slice(&chunk[..n.min(room)]);
+            }
+            shared.lock().unwrap_or_else(|e| e.into_inner()).closed = true;
+        })
+        .map_err(|e| Error::IoError {
+            context: format!("spawn task {stream} reader: {e}"),
+        })?;
+    Ok(captured)
+}
+
+impl OutputCapture {
+    /// Wait until both pipes reach EOF or `deadline` passes, then return what was read so far.
+    ///
+    /// Output still open at the deadline (e.g. a process that outlived the unit holds the pipe)
+    /// is reported as truncated.
+    pub(crate) async fn collect(&self, deadline: Instant) -> crate::types::task::TaskOutput {
+        loop {
+            let closed = [&self.stdout, &self.stderr]
+                .iter()
+                .all(|c| c.lock().unwrap_or_else(|e| e.into_inner()).closed);
+            let now = Instant::now();
+            if closed || now >= deadline {
+                break;
+            }
+            crate::runtime::sleep(std::cmp::min(DRAIN_POLL, deadline - now)).await;
+        }
+
+        let stdout = self.stdout.lock().unwrap_or_else(|e| e.into_inner());
+        let stderr = self.stderr.lock().unwrap_or_else(|e| e.into_inner());
+        crate::types::task::TaskOutput {
+            stdout: stdout.buf.clone(),
+            stderr: stderr.buf.clone(),
+            truncated: stdout.truncated || stderr.truncated || !stdout.closed || !stderr.closed,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rt-async-io"))]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn capture_is_bounded_and_drains_to_eof() {
+        let (pipes, capture) = open(4).unwrap();
+        let mut stdout = std::fs::File::from(pipes.stdout);
+        stdout.write_all(b"hello world").unwrap();
+        drop(stdout);
+        drop(pipes.stderr);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let out = futures_lite::future::block_on(capture.collect(deadline));
+        assert_eq!(out.stdout, b"hell");
+        assert!(out.stderr.is_empty());
+        assert!(out.truncated);
+    }
+
+    #[test]
+    fn open_pipe_at_deadline_is_truncated() {
+        let (pipes, capture) = open(1024).unwrap();
+        let mut stderr = std::fs::File::from(pipes.stderr);
+        stderr.write_all(b"oops").unwrap();
+        drop(pipes.stdout);
+
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let out = futures_lite::future::block_on(capture.collect(deadline));
+        assert!(out.truncated);
+        drop(stderr);
+    }
+}
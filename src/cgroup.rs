@@ -0,0 +1,85 @@
+use crate::types::pressure::{Pressure, PressureResource};
+use crate::{Error, Result};
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CGROUPFS_ROOT: &str = "/sys/fs/cgroup";
+const CGROUPFS_HYBRID_ROOT: &str = "/sys/fs/cgroup/unified";
+
+/// Mount point of the unified (cgroup v2) hierarchy: `/sys/fs/cgroup`, or
+/// `/sys/fs/cgroup/unified` on hybrid hosts.
+pub(crate) fn unified_root() -> Result<&'static Path> {
+    [CGROUPFS_ROOT, CGROUPFS_HYBRID_ROOT]
+        .into_iter()
+        .map(Path::new)
+        .find(|root| root.join("cgroup.controllers").is_file())
+        .ok_or_else(|| Error::BackendUnavailable {
+            backend: "cgroupfs",
+            detail: "no unified cgroup hierarchy mounted".to_string(),
+        })
+}
+
+/// Absolute cgroupfs directory of a `ControlGroup` value (e.g. `/system.slice/app.service`).
+pub(crate) fn cgroupfs_path(control_group: &str) -> Result<PathBuf> {
+    join_control_group(unified_root()?, control_group)
+}
+
+fn join_control_group(root: &Path, control_group: &str) -> Result<PathBuf> {
+    crate::util::validate_no_control("control group", control_group)?;
+    let Some(relative) = control_group.strip_prefix('/') else {
+        return Err(Error::invalid_input(format!(
+            "control group must be absolute: {control_group}"
+        )));
+    };
+    if relative.split('/').any(|c| c == "..") {
+        return Err(Error::invalid_input(format!(
+            "control group must not contain '..': {control_group}"
+        )));
+    }
+    Ok(root.join(relative))
+}
+
+/// Read one PSI file from a cgroup directory; `None` if the file does not exist.
+pub(crate) fn read_pressure(dir: &Path, resource: PressureResource) -> Result<Option<Pressure>> {
+    let path = dir.join(resource.file_name());
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        // Missing controller or `psi=0`; `EOPNOTSUPP` is what a disabled PSI reports on read.
+        Err(e) if e.kind() == io::ErrorKind::NotFound || e.kind() == io::ErrorKind::Unsupported => {
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(Error::IoError {
+                context: format!("read {}: {e}", path.to_string_lossy()),
+            });
+        }
+    };
+    Pressure::parse(&text)
+        .map(Some)
+        .ok_or_else(|| Error::ParseError {
+            context: format!("malformed {}", path.to_string_lossy()),
+            sample: text.chars().take(256).collect(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn control_group_is_joined_under_root() {
+        let root = Path::new("/sys/fs/cgroup");
+        assert_eq!(
+            join_control_group(root, "/system.slice/app.service").unwrap(),
+            PathBuf::from("/sys/fs/cgroup/system.slice/app.service")
+        );
+        assert_eq!(join_control_group(root, "/").unwrap(), root);
+        assert!(join_control_group(root, "system.slice").is_err());
+        assert!(join_control_group(root, "/system.slice/../etc").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::types::coredump::{COREDUMP_FIELDS, COREDUMP_MESSAGE_ID};
+use crate::{CoredumpEntry, CoredumpFilter, CoredumpId, Error, Result, util};
+
+use std::sync::Arc;
+
+/// Coredump listing backed by the `systemd-coredump` journal records.
+#[derive(Clone, Debug)]
+pub struct Coredumps {
+    inner: Arc<crate::Inner>,
+}
+
+impl Coredumps {
+    pub(crate) fn new(inner: Arc<crate::Inner>) -> Self {
+        Self { inner }
+    }
+
+    /// List the newest `filter.limit` recorded coredumps, oldest first (like `coredumpctl list`).
+    ///
+    /// `core_present`/`size` are read from the core file on disk, so they reflect the current
+    /// state rather than the state at crash time.
+    pub async fn list(&self, filter: CoredumpFilter) -> Result<Vec<CoredumpEntry>> {
+        if filter.limit == 0 {
+            return Err(Error::invalid_input("coredump limit must be > 0"));
+        }
+        let unit = match &filter.unit {
+            Some(u) => Some(util::canonicalize_unit_name(u)?),
+            None => None,
+        };
+
+        crate::telemetry::traced("coredump_list", unit.as_deref(), async {
+            let mut matches = Vec::new();
+            if let Some(unit) = unit.clone() {
+                matches.push(("COREDUMP_UNIT".to_string(), unit));
+            }
+            if let Some(exe) = &filter.executable {
+                util::validate_no_control("executable", exe)?;
+                matches.push(("COREDUMP_EXE".to_string(), exe.clone()));
+            }
+
+            let mut journal_filter = journal_filter(matches, filter.limit);
+            journal_filter.direction = crate::QueryDirection::Reverse;
+            journal_filter.since = filter.since;
+            journal_filter.until = filter.until;
+            journal_filter.timeout = filter.timeout;
+
+            let res = crate::Journal::new(self.inner.clone())
+                .query(journal_filter)
+                .await?;
+            let mut dumps = to_coredumps(&res.entries).await?;
+            dumps.reverse();
+            Ok(dumps)
+        })
+        .await
+    }
+
+    /// The most recent coredump matching `id` (a PID, command name or executable path), like
+    /// `coredumpctl info`.
+    pub async fn info(&self, id: impl Into<CoredumpId>) -> Result<Option<CoredumpEntry>> {
+        let (field, value) = match id.into() {
+            CoredumpId::Pid(pid) => ("COREDUMP_PID", pid.to_string()),
+            CoredumpId::Command(comm) => ("COREDUMP_COMM", comm),
+            CoredumpId::Executable(exe) => ("COREDUMP_EXE", exe),
+        };
+        util::validate_no_control("coredump id", &value)?;
+        if value.is_empty() {
+            return Err(Error::invalid_input("coredump id must not be empty"));
+        }
+
+        crate::telemetry::traced("coredump_info", None, async {
+            let mut filter = journal_filter(vec![(field.to_string(), value.clone())], 1);
+            filter.direction = crate::QueryDirection::Reverse;
+            let res = crate::Journal::new(self.inner.clone())
+                .query(filter)
+                .await?;
+            Ok(to_coredumps(&res.entries).await?.pop())
+        })
+        .await
+    }
+}
+
+fn journal_filter(mut matches: Vec<(String, String)>, limit: u32) -> crate::JournalFilter {
+    matches.insert(
+        0,
+        ("MESSAGE_ID".to_string(), COREDUMP_MESSAGE_ID.to_string()),
+    );
+    crate::JournalFilter {
+        limit,
+        // Entries can carry the core itself when `Storage=journal`; leave room for it.
+        max_bytes: 64 * 1024 * 1024,
+        // The message is a rendered stack trace; the typed fields carry everything needed.
+        max_message_bytes: 1,
+        fields: Some(COREDUMP_FIELDS.iter().map(|f| f.to_string()).collect()),
+        matches,
+        ..Default::default()
+    }
+}
+
+/// Decode `entries`, then stat their core files off the executor.
+async fn to_coredumps(entries: &[crate::JournalEntry]) -> Result<Vec<CoredumpEntry>> {
+    let dumps: Vec<CoredumpEntry> = entries
+        .iter()
+        .filter_map(CoredumpEntry::from_journal)
+        .collect();
+    if dumps.iter().all(|d| d.core_file.is_none()) {
+        return Ok(dumps);
+    }
+    crate::runtime::unblock(move || Ok(dumps.into_iter().map(with_core_file_state).collect())).await
+}
+
+fn with_core_file_state(mut entry: CoredumpEntry) -> CoredumpEntry {
+    if let Some(meta) = entry
+        .core_file
+        .as_deref()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .filter(|m| m.is_file())
+    {
+        entry.core_present = true;
+        entry.size = Some(meta.len());
+    }
+    entry
+}
@@ -39,6 +39,17 @@ pub enum Error {
         timeout: Duration,
     },
 
+    /// A journald query timed out midway.
+    ///
+    /// `partial` holds the entries collected before the deadline (with `truncated=true`), so
+    /// callers can still use what was read.
+    #[error("timeout for {action}: {timeout:?} ({} partial entries)", partial.entries.len())]
+    JournalTimeout {
+        action: &'static str,
+        timeout: Duration,
+        partial: Box<crate::types::journal::JournalResult>,
+    },
+
     /// A backend is unavailable in the current environment (missing binary, missing D-Bus, feature
     /// disabled, unsupported journald option, etc).
     #[error("backend unavailable ({backend}): {detail}")]
@@ -55,12 +66,28 @@ pub enum Error {
     #[error("io error: {context}")]
     IoError { context: String },
 
-    /// Failed to parse an external payload (e.g. a `journalctl --output=json` line).
+    /// Failed to parse an external payload (e.g. a `journalctl --output=json` line), or to
+    /// serialize an output type with `to_json()`.
     ///
     /// `sample` is truncated to avoid unbounded output.
     #[error("parse error: {context}; sample={sample}")]
     ParseError { context: String, sample: String },
 
+    /// systemd does not see a configuration change that was written (see
+    /// `Config::apply_dropin_verified`).
+    #[error("config not applied for {unit}: {property} is {actual:?}, expected {expected:?}")]
+    ConfigMismatch {
+        unit: String,
+        /// The D-Bus property that disagrees (e.g. `FragmentPath`, `DropInPaths`).
+        property: &'static str,
+        expected: String,
+        actual: String,
+    },
+
+    /// The operation was aborted because its `ShutdownToken` fired (see `unitbus::shutdown`).
+    #[error("cancelled by shutdown: {action}")]
+    Cancelled { action: &'static str },
+
     /// A subprocess failed (non-zero exit or other failure mode).
     ///
     /// `stderr` is truncated to avoid unbounded output.
@@ -0,0 +1,16 @@
+use crate::Error;
+
+use std::path::Path;
+
+#[cfg(feature = "config")]
+mod unit_dir;
+
+#[cfg(feature = "config")]
+pub(crate) use unit_dir::*;
+
+/// `Error::IoError` for `e` hit while doing `context` on `path`.
+pub(crate) fn io_error(context: &str, path: &Path, e: std::io::Error) -> Error {
+    Error::IoError {
+        context: format!("{context} {}: {e}", path.to_string_lossy()),
+    }
+}
@@ -0,0 +1,1009 @@
+use crate::types::config::{
+    ApplyReport, ConfigDrift, ConfigFileDigest, DiffLine, DropInSpec, RecommendedAction,
+    RemoveReport, UnitConfigFile, UnitConfigFingerprint, UnitFileContent, UnitFileDiff,
+};
+use crate::types::specifier;
+use crate::types::unit_file::{UnitFileRemoveReport, UnitFileWriteReport};
+use crate::util::{FNV_OFFSET, fnv1a64};
+use crate::{Error, Result, util};
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn render_dropin(spec: &DropInSpec) -> Result<String> {
+    util::validate_no_control("unit", &spec.unit)?;
+    util::validate_dropin_name(&spec.name)?;
+
+    for (k, v) in &spec.environment {
+        util::validate_env_key(k)?;
+        util::validate_no_control("env value", v)?;
+    }
+    if let Some(v) = &spec.working_directory {
+        util::validate_no_control("working_directory", v)?;
+    }
+    if let Some(v) = &spec.restart {
+        util::validate_no_control("restart", v)?;
+        specifier::reject_specifiers("restart", v)?;
+    }
+
+    let template = specifier::is_template_unit(&spec.unit);
+    for (k, v) in &spec.environment {
+        specifier::validate_specifiers("environment", k, template)?;
+        specifier::validate_specifiers("environment", v, template)?;
+    }
+    if let Some(v) = &spec.working_directory {
+        specifier::validate_specifiers("working_directory", v, template)?;
+    }
+    for v in spec.exec_start_override.iter().flatten() {
+        specifier::validate_specifiers("exec_start_override", v, template)?;
+    }
+
+    let mut out = String::new();
+    out.push_str("# Managed by unitbus. DO NOT EDIT.\n");
+    out.push_str("[Service]\n");
+
+    for (k, v) in &spec.environment {
+        let assignment = format!("{k}={v}");
+        out.push_str("Environment=");
+        out.push_str(&util::quote_systemd_value(&assignment));
+        out.push('\n');
+    }
+
+    if let Some(dir) = &spec.working_directory {
+        out.push_str("WorkingDirectory=");
+        out.push_str(&util::quote_systemd_value(dir));
+        out.push('\n');
+    }
+
+    if let Some(restart) = &spec.restart {
+        out.push_str("Restart=");
+        out.push_str(restart);
+        out.push('\n');
+    }
+
+    if let Some(v) = spec.timeout_start_sec {
+        out.push_str("TimeoutStartSec=");
+        out.push_str(&v.to_string());
+        out.push('\n');
+    }
+
+    if let Some(v) = spec.memory_max {
+        out.push_str("MemoryMax=");
+        out.push_str(&v.to_string());
+        out.push('\n');
+    }
+
+    if let Some(v) = crate::types::unit_file::validate_cpu_quota(spec.cpu_quota)? {
+        out.push_str("CPUQuota=");
+        out.push_str(&v.to_string());
+        out.push('\n');
+    }
+
+    crate::types::unit_file::render_exec_directories(
+        &mut out,
+        spec.dynamic_user,
+        [
+            &spec.state_directory,
+            &spec.cache_directory,
+            &spec.logs_directory,
+            &spec.runtime_directory,
+        ],
+        template,
+    )?;
+    crate::types::credential::render_credentials(&mut out, &spec.credentials, template)?;
+
+    if let Some(argv) = &spec.exec_start_override {
+        if argv.is_empty() {
+            return Err(Error::invalid_input(
+                "exec_start_override must not be empty",
+            ));
+        }
+        out.push_str("ExecStart=\n");
+        out.push_str("ExecStart=");
+        out.push_str(&util::render_systemd_exec(argv)?);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// Render a `[Socket]` drop-in that replaces all `Listen*=` settings of `unit`.
+pub(crate) fn render_socket_listen_dropin(
+    unit: &str,
+    update: &crate::SocketListenerUpdate,
+) -> Result<String> {
+    if update.listen_stream.is_empty() && update.listen_datagram.is_empty() {
+        return Err(Error::invalid_input(
+            "socket listener update must contain at least one listen address",
+        ));
+    }
+
+    let template = specifier::is_template_unit(unit);
+    let mut out = String::new();
+    out.push_str("# Managed by unitbus. DO NOT EDIT.\n");
+    out.push_str("[Socket]\n");
+    // An empty assignment to any Listen*= setting resets all of them.
+    out.push_str("ListenStream=\n");
+    for (key, addrs) in [
+        ("ListenStream", &update.listen_stream),
+        ("ListenDatagram", &update.listen_datagram),
+    ] {
+        for addr in addrs {
+            util::validate_no_control("listen address", addr)?;
+            let addr = addr.trim();
+            if addr.is_empty() || addr.chars().any(char::is_whitespace) {
+                return Err(Error::invalid_input(format!(
+                    "invalid listen address: {addr:?}"
+                )));
+            }
+            specifier::validate_specifiers("listen address", addr, template)?;
+            out.push_str(&format!("{key}={addr}\n"));
+        }
+    }
+    Ok(out)
+}
+
+/// Render a `[Unit]` drop-in adding `target` to `OnFailure=`.
+pub(crate) fn render_on_failure_dropin(target: &str) -> Result<String> {
+    util::validate_no_control("on_failure", target)?;
+    if target.is_empty() || target.chars().any(char::is_whitespace) {
+        return Err(Error::invalid_input(format!(
+            "invalid OnFailure= target: {target:?}"
+        )));
+    }
+    Ok(format!(
+        "# Managed by unitbus. DO NOT EDIT.\n[Unit]\nOnFailure={target}\n"
+    ))
+}
+
+/// Render the unit file of an application group target that `Wants=` all `members`.
+pub(crate) fn render_app_target(
+    description: Option<&str>,
+    members: &[String],
+    wanted_by: &[String],
+) -> Result<String> {
+    let mut out = String::new();
+    out.push_str("# Managed by unitbus. DO NOT EDIT.\n");
+    out.push_str("[Unit]\n");
+    if let Some(description) = description {
+        util::validate_no_control("description", description)?;
+        out.push_str(&format!("Description={}\n", description.trim()));
+    }
+    if !members.is_empty() {
+        out.push_str(&format!("Wants={}\n", members.join(" ")));
+    }
+    if !wanted_by.is_empty() {
+        out.push_str("\n[Install]\n");
+        out.push_str(&format!("WantedBy={}\n", wanted_by.join(" ")));
+    }
+    Ok(out)
+}
+
+/// Render a `[Unit]` drop-in making a unit part of the application group `target`.
+pub(crate) fn render_part_of_dropin(target: &str) -> String {
+    format!("# Managed by unitbus. DO NOT EDIT.\n[Unit]\nPartOf={target}\n")
+}
+
+pub(crate) fn apply_dropin_file(
+    unit_dir: &Path,
+    unit: &str,
+    name: &str,
+    contents: String,
+) -> Result<ApplyReport> {
+    let path = dropin_path(unit_dir, unit, name);
+    let dir = path
+        .parent()
+        .ok_or_else(|| Error::invalid_input("invalid drop-in path"))?;
+    fs::create_dir_all(dir).map_err(|e| map_dropin_io("create drop-in directory", dir, e))?;
+
+    let existing = match fs::read(&path) {
+        Ok(b) => Some(b),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(map_dropin_io("read drop-in", &path, e)),
+    };
+
+    if let Some(existing) = existing
+        && existing == contents.as_bytes()
+    {
+        return Ok(ApplyReport {
+            changed: false,
+            path_written: path.to_string_lossy().into_owned(),
+            requires_daemon_reload: false,
+            recommended_action: RecommendedAction::None,
+            dry_run: false,
+            diff: None,
+        });
+    }
+
+    atomic_write(&path, contents.as_bytes())
+        .map_err(|e| map_dropin_io("write drop-in", &path, e))?;
+
+    Ok(ApplyReport {
+        changed: true,
+        path_written: path.to_string_lossy().into_owned(),
+        requires_daemon_reload: true,
+        recommended_action: RecommendedAction::DaemonReload,
+        dry_run: false,
+        diff: None,
+    })
+}
+
+pub(crate) fn remove_dropin_file(unit_dir: &Path, unit: &str, name: &str) -> Result<RemoveReport> {
+    let path = dropin_path(unit_dir, unit, name);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(RemoveReport {
+            changed: true,
+            path_removed: path.to_string_lossy().into_owned(),
+            requires_daemon_reload: true,
+            dry_run: false,
+            diff: None,
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(RemoveReport {
+            changed: false,
+            path_removed: path.to_string_lossy().into_owned(),
+            requires_daemon_reload: false,
+            dry_run: false,
+            diff: None,
+        }),
+        Err(e) => Err(map_dropin_io("remove drop-in", &path, e)),
+    }
+}
+
+/// Units under `unit_dir` that have a drop-in called `name` (`<unit>.d/<name>.conf`), sorted.
+pub(crate) fn units_with_dropin(unit_dir: &Path, name: &str) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(unit_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(map_dropin_io("read unit directory", unit_dir, e)),
+    };
+    let mut units = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| map_dropin_io("read unit directory", unit_dir, e))?;
+        let file_name = entry.file_name();
+        let Some(unit) = file_name.to_str().and_then(|n| n.strip_suffix(".d")) else {
+            continue;
+        };
+        if dropin_path(unit_dir, unit, name).is_file() {
+            units.push(unit.to_string());
+        }
+    }
+    units.sort();
+    Ok(units)
+}
+
+pub(crate) fn apply_unit_file(
+    unit_dir: &Path,
+    unit: &str,
+    contents: String,
+) -> Result<UnitFileWriteReport> {
+    validate_unit_file_name(unit)?;
+
+    let path = unit_file_path(unit_dir, unit);
+    let dir = path
+        .parent()
+        .ok_or_else(|| Error::invalid_input("invalid unit file path"))?;
+
+    fs::create_dir_all(dir).map_err(|e| map_unitfile_io("create unit directory", dir, e))?;
+
+    let existing = match fs::read(&path) {
+        Ok(b) => Some(b),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(map_unitfile_io("read unit file", &path, e)),
+    };
+
+    if let Some(existing) = existing
+        && existing == contents.as_bytes()
+    {
+        return Ok(UnitFileWriteReport {
+            changed: false,
+            path_written: path.to_string_lossy().into_owned(),
+            requires_daemon_reload: false,
+            dry_run: false,
+            diff: None,
+        });
+    }
+
+    atomic_write(&path, contents.as_bytes())
+        .map_err(|e| map_unitfile_io("write unit file", &path, e))?;
+
+    Ok(UnitFileWriteReport {
+        changed: true,
+        path_written: path.to_string_lossy().into_owned(),
+        requires_daemon_reload: true,
+        dry_run: false,
+        diff: None,
+    })
+}
+
+pub(crate) fn remove_unit_file(unit_dir: &Path, unit: &str) -> Result<UnitFileRemoveReport> {
+    validate_unit_file_name(unit)?;
+
+    let path = unit_file_path(unit_dir, unit);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(UnitFileRemoveReport {
+            changed: true,
+            path_removed: path.to_string_lossy().into_owned(),
+            requires_daemon_reload: true,
+            dry_run: false,
+            diff: None,
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(UnitFileRemoveReport {
+            changed: false,
+            path_removed: path.to_string_lossy().into_owned(),
+            requires_daemon_reload: false,
+            dry_run: false,
+            diff: None,
+        }),
+        Err(e) => Err(map_unitfile_io("remove unit file", &path, e)),
+    }
+}
+
+/// Report what `apply_dropin_file` would do, without touching the filesystem.
+pub(crate) fn preview_dropin_file(
+    unit_dir: &Path,
+    unit: &str,
+    name: &str,
+    contents: &str,
+) -> Result<ApplyReport> {
+    let path = dropin_path(unit_dir, unit, name);
+    let current = read_config_file(&path)?;
+    let changed = current.as_deref() != Some(contents);
+
+    Ok(ApplyReport {
+        changed,
+        path_written: path.to_string_lossy().into_owned(),
+        requires_daemon_reload: changed,
+        recommended_action: if changed {
+            RecommendedAction::DaemonReload
+        } else {
+            RecommendedAction::None
+        },
+        dry_run: true,
+        diff: Some(diff_lines(current.as_deref().unwrap_or(""), contents)),
+    })
+}
+
+/// Report what `remove_dropin_file` would do, without touching the filesystem.
+pub(crate) fn preview_remove_dropin_file(
+    unit_dir: &Path,
+    unit: &str,
+    name: &str,
+) -> Result<RemoveReport> {
+    let path = dropin_path(unit_dir, unit, name);
+    let current = read_config_file(&path)?;
+
+    Ok(RemoveReport {
+        changed: current.is_some(),
+        path_removed: path.to_string_lossy().into_owned(),
+        requires_daemon_reload: current.is_some(),
+        dry_run: true,
+        diff: Some(diff_lines(current.as_deref().unwrap_or(""), "")),
+    })
+}
+
+/// Report what `apply_unit_file` would do, without touching the filesystem.
+pub(crate) fn preview_unit_file(
+    unit_dir: &Path,
+    unit: &str,
+    contents: &str,
+) -> Result<UnitFileWriteReport> {
+    validate_unit_file_name(unit)?;
+
+    let path = unit_file_path(unit_dir, unit);
+    let current = read_config_file(&path)?;
+    let changed = current.as_deref() != Some(contents);
+
+    Ok(UnitFileWriteReport {
+        changed,
+        path_written: path.to_string_lossy().into_owned(),
+        requires_daemon_reload: changed,
+        dry_run: true,
+        diff: Some(diff_lines(current.as_deref().unwrap_or(""), contents)),
+    })
+}
+
+/// Report what `remove_unit_file` would do, without touching the filesystem.
+pub(crate) fn preview_remove_unit_file(
+    unit_dir: &Path,
+    unit: &str,
+) -> Result<UnitFileRemoveReport> {
+    validate_unit_file_name(unit)?;
+
+    let path = unit_file_path(unit_dir, unit);
+    let current = read_config_file(&path)?;
+
+    Ok(UnitFileRemoveReport {
+        changed: current.is_some(),
+        path_removed: path.to_string_lossy().into_owned(),
+        requires_daemon_reload: current.is_some(),
+        dry_run: true,
+        diff: Some(diff_lines(current.as_deref().unwrap_or(""), "")),
+    })
+}
+
+/// Hash the fragment and drop-ins of `unit` (missing files are recorded, not errors).
+pub(crate) fn fingerprint_files(
+    unit: String,
+    fragment_path: Option<String>,
+    dropin_paths: Vec<String>,
+    need_daemon_reload: bool,
+) -> Result<UnitConfigFingerprint> {
+    let mut files = Vec::new();
+    for path in fragment_path.iter().chain(dropin_paths.iter()) {
+        let digest = match fs::read(path) {
+            Ok(bytes) => Some(format!("{:016x}", fnv1a64(FNV_OFFSET, &bytes))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(map_config_read_io(Path::new(path), e)),
+        };
+        files.push(ConfigFileDigest {
+            path: path.clone(),
+            digest,
+        });
+    }
+
+    let mut hash = FNV_OFFSET;
+    for f in &files {
+        hash = fnv1a64(hash, f.path.as_bytes());
+        hash = fnv1a64(hash, b"\0");
+        hash = fnv1a64(hash, f.digest.as_deref().unwrap_or("-").as_bytes());
+        hash = fnv1a64(hash, b"\n");
+    }
+
+    Ok(UnitConfigFingerprint {
+        unit,
+        fragment_path,
+        files,
+        digest: format!("{hash:016x}"),
+        need_daemon_reload,
+    })
+}
+
+pub(crate) fn compare_fingerprints(
+    expected: &UnitConfigFingerprint,
+    current: UnitConfigFingerprint,
+) -> ConfigDrift {
+    let mut changed = Vec::new();
+    let mut added = Vec::new();
+    for f in &current.files {
+        match expected.files.iter().find(|e| e.path == f.path) {
+            Some(e) if e.digest != f.digest => changed.push(f.path.clone()),
+            Some(_) => {}
+            None => added.push(f.path.clone()),
+        }
+    }
+    let removed = expected
+        .files
+        .iter()
+        .filter(|e| !current.files.iter().any(|f| f.path == e.path))
+        .map(|e| e.path.clone())
+        .collect();
+
+    ConfigDrift {
+        drifted: current.digest != expected.digest || current.need_daemon_reload,
+        changed,
+        added,
+        removed,
+        current,
+    }
+}
+
+/// Line diffs above this many LCS cells fall back to "remove all, add all".
+const MAX_DIFF_CELLS: usize = 4 * 1024 * 1024;
+
+/// Read the fragment (systemd's, or the one in `unit_dir`) and drop-ins of `unit`.
+pub(crate) fn read_unit_config(
+    unit_dir: &Path,
+    unit: String,
+    fragment_path: Option<String>,
+    dropin_paths: Vec<String>,
+    need_daemon_reload: bool,
+) -> Result<UnitFileContent> {
+    validate_unit_file_name(&unit)?;
+    let fragment_path = match fragment_path {
+        Some(path) => PathBuf::from(path),
+        None => unit_file_path(unit_dir, &unit),
+    };
+    let fragment = read_config_file(&fragment_path)?.map(|content| UnitConfigFile {
+        path: fragment_path.to_string_lossy().into_owned(),
+        content,
+    });
+
+    let mut dropins = Vec::new();
+    for path in dropin_paths {
+        if let Some(content) = read_config_file(Path::new(&path))? {
+            dropins.push(UnitConfigFile { path, content });
+        }
+    }
+
+    Ok(UnitFileContent {
+        unit,
+        fragment,
+        dropins,
+        need_daemon_reload,
+    })
+}
+
+/// Diff the unit file `apply_unit_file` would replace against `rendered`.
+pub(crate) fn diff_unit_file(
+    unit_dir: &Path,
+    installed: UnitFileContent,
+    rendered: &str,
+) -> Result<UnitFileDiff> {
+    validate_unit_file_name(&installed.unit)?;
+    let path = unit_file_path(unit_dir, &installed.unit);
+    let path_str = path.to_string_lossy().into_owned();
+
+    let loaded_fragment = installed.fragment.as_ref().map(|f| f.path.clone());
+    let current = match installed.fragment {
+        Some(f) if f.path == path_str => Some(f.content),
+        _ => read_config_file(&path)?,
+    };
+
+    Ok(UnitFileDiff {
+        unit: installed.unit,
+        exists: current.is_some(),
+        changed: current.as_deref() != Some(rendered),
+        lines: diff_lines(current.as_deref().unwrap_or(""), rendered),
+        path: path_str,
+        loaded_fragment,
+        dropins: installed.dropins.into_iter().map(|d| d.path).collect(),
+    })
+}
+
+/// Line-based LCS diff from `old` to `new`.
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let removed = |l: &&str| DiffLine::Removed(l.to_string());
+    let added = |l: &&str| DiffLine::Added(l.to_string());
+    if (a.len() + 1).saturating_mul(b.len() + 1) > MAX_DIFF_CELLS {
+        return a.iter().map(removed).chain(b.iter().map(added)).collect();
+    }
+
+    // lcs[i][j]: length of the longest common subsequence of a[i..] and b[j..].
+    let width = b.len() + 1;
+    let mut lcs = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            out.push(removed(&a[i]));
+            i += 1;
+        } else {
+            out.push(added(&b[j]));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(removed));
+    out.extend(b[j..].iter().map(added));
+    out
+}
+
+fn read_config_file(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(map_config_read_io(path, e)),
+    }
+}
+
+fn dropin_path(unit_dir: &Path, unit: &str, name: &str) -> PathBuf {
+    unit_dir
+        .join(format!("{unit}.d"))
+        .join(format!("{name}.conf"))
+}
+
+fn unit_file_path(unit_dir: &Path, unit: &str) -> PathBuf {
+    unit_dir.join(unit)
+}
+
+fn validate_unit_file_name(unit: &str) -> Result<()> {
+    util::validate_no_control("unit", unit)?;
+    let unit = unit.trim();
+    if unit.is_empty() {
+        return Err(Error::invalid_input("unit must not be empty"));
+    }
+    if unit.contains('/') || unit.contains('\\') {
+        return Err(Error::invalid_input(
+            "unit must not contain path separators",
+        ));
+    }
+    if unit.contains("..") {
+        return Err(Error::invalid_input("unit must not contain '..'"));
+    }
+    Ok(())
+}
+
+fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no parent"))?;
+
+    let tmp_path = loop {
+        let n = TMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let candidate = dir.join(format!(
+            ".{}.tmp-{}-{}",
+            path.file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("dropin.conf"),
+            std::process::id(),
+            n
+        ));
+        if !candidate.exists() {
+            break candidate;
+        }
+    };
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    fs::rename(&tmp_path, path)?;
+    fsync_dir(dir)?;
+    Ok(())
+}
+
+fn map_dropin_io(context: &'static str, path: &Path, e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        return Error::PermissionDenied {
+            action: "write_dropins",
+            detail: format!("{context} {}: {e}", path.to_string_lossy()),
+        };
+    }
+    Error::IoError {
+        context: format!("{context} {}: {e}", path.to_string_lossy()),
+    }
+}
+
+fn map_unitfile_io(context: &'static str, path: &Path, e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        return Error::PermissionDenied {
+            action: "write_unit_files",
+            detail: format!("{context} {}: {e}", path.to_string_lossy()),
+        };
+    }
+    Error::IoError {
+        context: format!("{context} {}: {e}", path.to_string_lossy()),
+    }
+}
+
+fn map_config_read_io(path: &Path, e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        return Error::PermissionDenied {
+            action: "read_unit_config",
+            detail: format!("read {}: {e}", path.to_string_lossy()),
+        };
+    }
+    Error::IoError {
+        context: format!("read {}: {e}", path.to_string_lossy()),
+    }
+}
+
+#[cfg(unix)]
+fn fsync_dir(dir: &Path) -> io::Result<()> {
+    let f = fs::File::open(dir)?;
+    f.sync_all()
+}
+
+#[cfg(not(unix))]
+fn fsync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TEST_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let n = TEST_DIR_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("unitbus-{name}-{}-{n}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn render_socket_listen_dropin_resets_listeners() {
+        let mut update = crate::SocketListenerUpdate::stream("0.0.0.0:8081");
+        update.listen_datagram.push("/run/app.dgram".to_string());
+        let rendered = render_socket_listen_dropin("app.socket", &update).expect("render ok");
+        assert_eq!(
+            rendered,
+            "# Managed by unitbus. DO NOT EDIT.\n[Socket]\nListenStream=\n\
+             ListenStream=0.0.0.0:8081\nListenDatagram=/run/app.dgram\n"
+        );
+
+        for bad in [
+            crate::SocketListenerUpdate::default(),
+            crate::SocketListenerUpdate::stream("0.0.0.0:80 0.0.0.0:81"),
+            crate::SocketListenerUpdate::stream("/run/%i.sock"),
+        ] {
+            let err = render_socket_listen_dropin("app.socket", &bad).expect_err("must fail");
+            let Error::InvalidInput { .. } = err else {
+                panic!("unexpected error: {err:?}");
+            };
+        }
+    }
+
+    #[test]
+    fn diff_unit_file_reports_line_changes_against_unit_dir() {
+        assert_eq!(
+            diff_lines("a\nb\nc\n", "a\nx\nc\nd\n"),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Added("x".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Added("d".to_string()),
+            ]
+        );
+
+        let dir = temp_dir("diff-unit");
+        let installed = |fragment: Option<&str>| UnitFileContent {
+            unit: "app.service".to_string(),
+            fragment: fragment.map(|path| UnitConfigFile {
+                path: path.to_string(),
+                content: "[Service]\nExecStart=/old\n".to_string(),
+            }),
+            dropins: Vec::new(),
+            need_daemon_reload: false,
+        };
+        let rendered = "[Service]\nExecStart=/new\n";
+
+        let diff = diff_unit_file(&dir, installed(None), rendered).expect("diff");
+        assert!(!diff.exists && diff.changed && !diff.shadowed());
+        assert_eq!(diff.unified(), "+[Service]\n+ExecStart=/new\n");
+
+        std::fs::write(dir.join("app.service"), rendered).expect("write");
+        let diff = diff_unit_file(
+            &dir,
+            installed(Some("/etc/systemd/system/app.service")),
+            rendered,
+        )
+        .expect("diff");
+        assert!(diff.exists && !diff.changed && diff.shadowed());
+    }
+
+    #[test]
+    fn render_on_failure_dropin_adds_target() {
+        assert_eq!(
+            render_on_failure_dropin("notify@%n.service").expect("render ok"),
+            "# Managed by unitbus. DO NOT EDIT.\n[Unit]\nOnFailure=notify@%n.service\n"
+        );
+        assert!(render_on_failure_dropin("a.service b.service").is_err());
+        assert!(render_on_failure_dropin("").is_err());
+    }
+
+    #[test]
+    fn render_app_target_wants_members() {
+        let members = ["api.service".to_string(), "worker.service".to_string()];
+        assert_eq!(
+            render_app_target(Some("My app"), &members, &["multi-user.target".to_string()])
+                .expect("render ok"),
+            "# Managed by unitbus. DO NOT EDIT.\n[Unit]\nDescription=My app\n\
+             Wants=api.service worker.service\n\n[Install]\nWantedBy=multi-user.target\n"
+        );
+        assert_eq!(
+            render_part_of_dropin("myapp.target"),
+            "# Managed by unitbus. DO NOT EDIT.\n[Unit]\nPartOf=myapp.target\n"
+        );
+    }
+
+    #[test]
+    fn render_dropin_is_stable_and_sorted() {
+        let mut env = std::collections::BTreeMap::new();
+        env.insert("B".to_string(), "2".to_string());
+        env.insert("A".to_string(), "1".to_string());
+
+        let spec = DropInSpec {
+            unit: "nginx.service".to_string(),
+            name: "unitbus".to_string(),
+            environment: env,
+            working_directory: Some("/srv/app".to_string()),
+            restart: Some("always".to_string()),
+            timeout_start_sec: Some(10.into()),
+            memory_max: None,
+            cpu_quota: None,
+            exec_start_override: None,
+            ..Default::default()
+        };
+
+        let rendered = render_dropin(&spec).expect("render ok");
+        let idx_a = rendered.find("Environment=\"A=1\"").expect("A exists");
+        let idx_b = rendered.find("Environment=\"B=2\"").expect("B exists");
+        assert!(idx_a < idx_b);
+        assert!(rendered.ends_with('\n'));
+    }
+
+    #[test]
+    fn render_dropin_validates_specifiers() {
+        let mut spec = DropInSpec {
+            unit: "worker@.service".to_string(),
+            name: "unitbus".to_string(),
+            working_directory: Some("/srv/%i".to_string()),
+            ..Default::default()
+        };
+        let preview = spec.preview_expansion(Some("a")).expect("preview ok");
+        assert!(preview.contains("WorkingDirectory=\"/srv/a\"\n"));
+
+        spec.unit = "worker.service".to_string();
+        assert!(render_dropin(&spec).is_err());
+
+        spec.working_directory = None;
+        spec.restart = Some("on-%n".to_string());
+        assert!(render_dropin(&spec).is_err());
+    }
+
+    #[test]
+    fn fingerprint_detects_edits_added_and_removed_dropins() {
+        let dir = temp_dir("fingerprint");
+        let fragment = dir.join("app.service");
+        let dropin_a = dir.join("a.conf");
+        let dropin_b = dir.join("b.conf");
+        std::fs::write(&fragment, "[Service]\nExecStart=/bin/true\n").unwrap();
+        std::fs::write(&dropin_a, "[Service]\nRestart=always\n").unwrap();
+        std::fs::write(&dropin_b, "[Service]\nNice=5\n").unwrap();
+
+        let path = |p: &Path| p.to_string_lossy().into_owned();
+        let take = |dropins: Vec<String>| {
+            fingerprint_files(
+                "app.service".to_string(),
+                Some(path(&fragment)),
+                dropins,
+                false,
+            )
+            .expect("fingerprint")
+        };
+
+        let expected = take(vec![path(&dropin_a)]);
+        assert_eq!(expected.files.len(), 2);
+        assert_eq!(expected, take(vec![path(&dropin_a)]));
+        assert!(!compare_fingerprints(&expected, take(vec![path(&dropin_a)])).drifted);
+
+        std::fs::write(&dropin_a, "[Service]\nRestart=no\n").unwrap();
+        let drift = compare_fingerprints(&expected, take(vec![path(&dropin_b)]));
+        assert!(drift.drifted);
+        assert!(drift.changed.is_empty());
+        assert_eq!(drift.added, vec![path(&dropin_b)]);
+        assert_eq!(drift.removed, vec![path(&dropin_a)]);
+
+        let drift = compare_fingerprints(&expected, take(vec![path(&dropin_a)]));
+        assert_eq!(drift.changed, vec![path(&dropin_a)]);
+
+        std::fs::remove_file(&fragment).unwrap();
+        let gone = take(Vec::new());
+        assert_eq!(gone.files[0].digest, None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn apply_and_remove_unit_file_is_idempotent() {
+        let dir = temp_dir("unitfile");
+        let unit = "unitbus-test.service";
+
+        let r1 = apply_unit_file(&dir, unit, "a\n".to_string()).expect("write ok");
+        assert!(r1.changed);
+        assert!(r1.requires_daemon_reload);
+
+        let r2 = apply_unit_file(&dir, unit, "a\n".to_string()).expect("write ok");
+        assert!(!r2.changed);
+        assert!(!r2.requires_daemon_reload);
+
+        let r3 = apply_unit_file(&dir, unit, "b\n".to_string()).expect("write ok");
+        assert!(r3.changed);
+        assert!(r3.requires_daemon_reload);
+
+        let rm1 = remove_unit_file(&dir, unit).expect("remove ok");
+        assert!(rm1.changed);
+        assert!(rm1.requires_daemon_reload);
+
+        let rm2 = remove_unit_file(&dir, unit).expect("remove ok");
+        assert!(!rm2.changed);
+        assert!(!rm2.requires_daemon_reload);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn units_with_dropin_scans_dropin_dirs() {
+        let dir = temp_dir("dropin-scan");
+        for unit in ["b.service", "a.service"] {
+            apply_dropin_file(&dir, unit, "group", "x\n".to_string()).expect("apply ok");
+        }
+        apply_dropin_file(&dir, "c.service", "other", "x\n".to_string()).expect("apply ok");
+
+        assert_eq!(
+            units_with_dropin(&dir, "group").expect("scan ok"),
+            vec!["a.service", "b.service"]
+        );
+        assert!(
+            units_with_dropin(&dir.join("missing"), "group")
+                .expect("scan ok")
+                .is_empty()
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn apply_and_remove_dropin_is_idempotent() {
+        let dir = temp_dir("dropin");
+        let unit = "unitbus-test.service";
+        let name = "demo";
+        let contents = "[Service]\nEnvironment=\"A=1\"\n".to_string();
+
+        let r1 = apply_dropin_file(&dir, unit, name, contents.clone()).expect("apply ok");
+        assert!(r1.changed);
+        assert!(r1.requires_daemon_reload);
+
+        let r2 = apply_dropin_file(&dir, unit, name, contents).expect("apply ok");
+        assert!(!r2.changed);
+        assert!(!r2.requires_daemon_reload);
+
+        let rm1 = remove_dropin_file(&dir, unit, name).expect("remove ok");
+        assert!(rm1.changed);
+        assert!(rm1.requires_daemon_reload);
+
+        let rm2 = remove_dropin_file(&dir, unit, name).expect("remove ok");
+        assert!(!rm2.changed);
+        assert!(!rm2.requires_daemon_reload);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn previews_report_changes_without_writing() {
+        let dir = temp_dir("preview");
+        let unit = "unitbus-test.service";
+
+        let w = preview_unit_file(&dir, unit, "a\n").expect("preview ok");
+        assert!(w.dry_run && w.changed && w.requires_daemon_reload);
+        assert_eq!(w.diff, Some(vec![DiffLine::Added("a".to_string())]));
+        assert!(!unit_file_path(&dir, unit).exists());
+
+        apply_unit_file(&dir, unit, "a\nb\n".to_string()).expect("write ok");
+        let rm = preview_remove_unit_file(&dir, unit).expect("preview ok");
+        assert!(rm.changed);
+        assert_eq!(rm.diff.as_ref().map(Vec::len), Some(2));
+        assert!(unit_file_path(&dir, unit).exists());
+
+        let d = preview_dropin_file(&dir, unit, "demo", "[Service]\n").expect("preview ok");
+        assert_eq!(d.recommended_action, RecommendedAction::DaemonReload);
+        assert!(!dropin_path(&dir, unit, "demo").exists());
+        apply_dropin_file(&dir, unit, "demo", "[Service]\n".to_string()).expect("apply ok");
+        let same = preview_dropin_file(&dir, unit, "demo", "[Service]\n").expect("preview ok");
+        assert!(!same.changed);
+        assert_eq!(same.recommended_action, RecommendedAction::None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
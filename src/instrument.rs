@@ -0,0 +1,109 @@
+//! SDK self-metrics via the `metrics` facade (feature=`metrics`).
+//!
+//! Recorded series (install any `metrics` recorder, e.g. a Prometheus exporter, to collect them):
+//! - `unitbus_dbus_call_duration_seconds` histogram, labels `method`, `outcome` (`ok`/`error`)
+//! - `unitbus_journal_query_entries` / `unitbus_journal_query_bytes` histograms, label
+//!   `truncated`
+//! - `unitbus_job_wait_duration_seconds` histogram, label `outcome` (`success`, `failed`,
+//!   `canceled`, or the error kind)
+//! - `unitbus_watcher_events_total` counter, label `watcher`
+//!
+//! Without the feature every function here is a no-op.
+
+use crate::Result;
+
+use std::future::Future;
+
+/// Time one D-Bus method call.
+pub(crate) async fn dbus_call<T>(
+    method: &'static str,
+    fut: impl Future<Output = zbus::Result<T>>,
+) -> zbus::Result<T> {
+    #[cfg(feature = "metrics")]
+    {
+        let started = std::time::Instant::now();
+        let res = fut.await;
+        let outcome = if res.is_ok() { "ok" } else { "error" };
+        ::metrics::histogram!(
+            "unitbus_dbus_call_duration_seconds",
+            "method" => method,
+            "outcome" => outcome
+        )
+        .record(started.elapsed().as_secs_f64());
+        res
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = method;
+        fut.await
+    }
+}
+
+/// Record the size of a finished journal query.
+pub(crate) fn journal_query(res: &Result<crate::types::journal::JournalResult>) {
+    #[cfg(feature = "metrics")]
+    if let Ok(res) = res {
+        let truncated = if res.truncated { "true" } else { "false" };
+        ::metrics::histogram!("unitbus_journal_query_entries", "truncated" => truncated)
+            .record(res.entries.len() as f64);
+        ::metrics::histogram!("unitbus_journal_query_bytes", "truncated" => truncated)
+            .record(f64::from(res.stats.bytes_read));
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = res;
+}
+
+/// Time a job wait, labelled by its outcome.
+pub(crate) async fn job_wait(
+    fut: impl Future<Output = Result<crate::JobOutcome>>,
+) -> Result<crate::JobOutcome> {
+    #[cfg(feature = "metrics")]
+    {
+        let started = std::time::Instant::now();
+        let res = fut.await;
+        let outcome = match &res {
+            Ok(crate::JobOutcome::Success { .. }) => "success",
+            Ok(crate::JobOutcome::Failed { .. }) => "failed",
+            Ok(crate::JobOutcome::Canceled { .. }) => "canceled",
+            Err(e) => crate::telemetry::error_kind(e),
+        };
+        ::metrics::histogram!("unitbus_job_wait_duration_seconds", "outcome" => outcome)
+            .record(started.elapsed().as_secs_f64());
+        res
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    {
+        fut.await
+    }
+}
+
+/// Count one event yielded by a watcher.
+#[cfg(feature = "observe")]
+pub(crate) fn watcher_event(watcher: &'static str) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("unitbus_watcher_events_total", "watcher" => watcher).increment(1);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = watcher;
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    #[test]
+    fn wrappers_pass_results_through() {
+        let ok = futures_lite::future::block_on(super::dbus_call("GetUnit", async { Ok(3u8) }));
+        assert_eq!(ok.unwrap(), 3);
+
+        let err = futures_lite::future::block_on(super::job_wait(async {
+            Err(crate::Error::invalid_input("nope"))
+        }));
+        assert!(matches!(err, Err(crate::Error::InvalidInput { .. })));
+    }
+}
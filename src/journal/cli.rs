@@ -13,6 +13,7 @@ const STDERR_MAX_BYTES: usize = 8 * 1024;
 
 pub(crate) async fn query_journalctl(
     opts: &UnitBusOptions,
+    scope: crate::ManagerScope,
     mut filter: JournalFilter,
 ) -> Result<JournalResult> {
     if filter.limit == 0 {
@@ -30,12 +31,26 @@ pub(crate) async fn query_journalctl(
     if let Some(unit) = &filter.unit {
         filter.unit = Some(util::canonicalize_unit_name(unit)?);
     }
-    if let Some(cursor) = &filter.after_cursor {
+    for cursor in [&filter.after_cursor, &filter.before_cursor]
+        .into_iter()
+        .flatten()
+    {
         util::validate_no_control("cursor", cursor)?;
     }
+    filter.validate_direction()?;
+    filter.validate_fields()?;
+    if let Some(collapse) = &filter.collapse_repeats {
+        collapse.validate()?;
+    }
+    let since_monotonic = filter.resolve_since_monotonic()?;
 
-    let timeout = filter.timeout.unwrap_or(opts.journal_default_timeout);
-    let wants_cursor = filter.after_cursor.is_some();
+    let sandbox = opts.journal_cli_sandbox.as_ref();
+    let mut timeout = filter.timeout.unwrap_or(opts.journal_default_timeout);
+    if let Some(max) = sandbox.and_then(|s| s.max_runtime) {
+        timeout = timeout.min(max);
+    }
+    let launcher = launcher_argv(sandbox)?;
+    let wants_cursor = filter.after_cursor.is_some() || filter.before_cursor.is_some();
 
     #[cfg(feature = "tracing")]
     tracing::debug!(
@@ -50,11 +65,21 @@ pub(crate) async fn query_journalctl(
         "journalctl query"
     );
 
-    let mut cmd = async_process::Command::new("journalctl");
+    let mut cmd = async_process::Command::new(&launcher[0]);
+    cmd.args(&launcher[1..]);
+    if sandbox.is_some_and(|s| s.clear_env) {
+        cmd.env_clear();
+        if let Some(path) = std::env::var_os("PATH") {
+            cmd.env("PATH", path);
+        }
+    }
     cmd.arg("--no-pager").arg("--output=json");
 
     if let Some(unit) = &filter.unit {
-        cmd.arg("-u").arg(unit);
+        match scope {
+            crate::ManagerScope::User => cmd.arg(format!("--user-unit={unit}")),
+            _ => cmd.arg("-u").arg(unit),
+        };
     }
 
     if let Some(since) = filter.since {
@@ -71,19 +96,48 @@ pub(crate) async fn query_journalctl(
         cmd.arg(format!("--after-cursor={cursor}"));
     }
 
-    let lines = filter.limit.saturating_add(1);
-    cmd.arg(format!("--lines={lines}"));
+    if filter.direction == crate::QueryDirection::Reverse {
+        cmd.arg("--reverse");
+        // Read backwards, "after" the cursor means older than it.
+        if let Some(cursor) = &filter.before_cursor {
+            cmd.arg(format!("--after-cursor={cursor}"));
+        }
+    }
+
+    if let Some(machine) = &filter.machine {
+        util::validate_machine_name(machine)?;
+        cmd.arg(format!("--machine={machine}"));
+    }
+
+    for (field, value) in &filter.matches {
+        util::validate_no_control("journal match", value)?;
+        cmd.arg(format!("{field}={value}"));
+    }
+
+    if let Some((most_severe, least_severe)) = filter.priority_range()? {
+        cmd.arg(format!("--priority={most_severe}..{least_severe}"));
+    }
+
+    // journalctl cannot filter on the monotonic clock, so with `since_monotonic` the earlier
+    // entries of the boot are read and dropped by the collector; `--lines` would count them.
+    if since_monotonic.is_none() {
+        let lines = filter
+            .collapse_repeats
+            .map_or(filter.limit, |c| c.max_scanned)
+            .saturating_add(1);
+        cmd.arg(format!("--lines={lines}"));
+    }
     cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
     let mut child = cmd.spawn().map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
             return Error::BackendUnavailable {
                 backend: "journalctl",
-                detail: "journalctl not found".to_string(),
+                detail: format!("{} not found", launcher[0]),
             };
         }
         Error::IoError {
-            context: format!("spawn journalctl failed: {e}"),
+            context: format!("spawn {} failed: {e}", launcher[0]),
         }
     })?;
 
@@ -103,6 +157,7 @@ pub(crate) async fn query_journalctl(
     let mut reader = BufReader::new(stdout);
     let mut line = String::new();
     let mut collector = JournalCollector::new(&filter);
+    collector.since_monotonic = since_monotonic;
 
     let mut deadline = crate::runtime::sleep(timeout).fuse();
 
@@ -114,7 +169,7 @@ pub(crate) async fn query_journalctl(
                 _ = deadline => {
                     let _ = child.kill();
                     let _ = child.status().await;
-                    return Err(Error::Timeout { action: "journalctl", timeout });
+                    return Err(collector.timed_out(timeout));
                 }
                 n = s.read(&mut stderr_tmp).fuse() => {
                     let n = n.map_err(|e| Error::IoError { context: format!("read journalctl stderr: {e}") })?;
@@ -134,7 +189,7 @@ pub(crate) async fn query_journalctl(
                 _ = deadline => {
                     let _ = child.kill();
                     let _ = child.status().await;
-                    return Err(Error::Timeout { action: "journalctl", timeout });
+                    return Err(collector.timed_out(timeout));
                 }
                 n = reader.read_line(&mut line).fuse() => {
                     n.map_err(|e| Error::IoError { context: format!("read journalctl stdout: {e}") })?
@@ -205,15 +260,50 @@ pub(crate) async fn query_journalctl(
         "journalctl result"
     );
 
-    let entries = collector.entries;
-    let next_cursor = entries.last().and_then(|e| e.cursor.clone());
+    Ok(JournalResult::from_entries(
+        collector.entries,
+        collector.truncated,
+        collector.stats,
+    ))
+}
 
-    Ok(JournalResult {
-        entries,
-        next_cursor,
-        truncated: collector.truncated,
-        stats: collector.stats,
-    })
+/// Program and leading arguments that end in `journalctl`, with the sandbox wrappers in front.
+fn launcher_argv(sandbox: Option<&crate::JournalCliSandbox>) -> Result<Vec<String>> {
+    let mut argv = Vec::new();
+    if let Some(sandbox) = sandbox {
+        if sandbox.max_runtime.is_some_and(|d| d.is_zero()) {
+            return Err(Error::invalid_input(
+                "journal_cli_sandbox max_runtime must be > 0",
+            ));
+        }
+        if sandbox.memory_max.is_some() && !sandbox.transient_scope {
+            return Err(Error::invalid_input(
+                "journal_cli_sandbox memory_max requires transient_scope",
+            ));
+        }
+        if sandbox.transient_scope {
+            argv.extend(["systemd-run", "--scope", "--quiet", "--collect"].map(String::from));
+            if let Some(bytes) = sandbox.memory_max {
+                if bytes == 0 {
+                    return Err(Error::invalid_input(
+                        "journal_cli_sandbox memory_max must be > 0",
+                    ));
+                }
+                argv.push(format!("--property=MemoryMax={bytes}"));
+            }
+            argv.push("--".to_string());
+        }
+        if let Some(nice) = sandbox.nice {
+            if !(-20..=19).contains(&nice) {
+                return Err(Error::invalid_input(format!(
+                    "journal_cli_sandbox nice must be in -20..=19, got {nice}"
+                )));
+            }
+            argv.extend(["nice".to_string(), "-n".to_string(), nice.to_string()]);
+        }
+    }
+    argv.push("journalctl".to_string());
+    Ok(argv)
 }
 
 async fn drain_to_end_limited(
@@ -279,6 +369,12 @@ struct JournalCollector {
     max_bytes: u32,
     max_message_bytes: u32,
     parse_error: ParseErrorMode,
+    fields: Option<Vec<String>>,
+    /// Minimum `__MONOTONIC_TIMESTAMP`; the boot is already matched by `_BOOT_ID`.
+    since_monotonic: Option<u64>,
+    collapse: Option<crate::CollapseOptions>,
+    /// Bytes of the lines kept as entries, checked against `max_bytes` when collapsing.
+    kept_bytes: u32,
     stats: JournalStats,
     entries: Vec<JournalEntry>,
     truncated: bool,
@@ -292,6 +388,10 @@ impl JournalCollector {
             max_bytes: filter.max_bytes,
             max_message_bytes: filter.max_message_bytes,
             parse_error: filter.parse_error.clone(),
+            fields: filter.fields.clone(),
+            since_monotonic: None,
+            collapse: filter.collapse_repeats,
+            kept_bytes: 0,
             stats: JournalStats::default(),
             entries: Vec::new(),
             truncated: false,
@@ -299,24 +399,65 @@ impl JournalCollector {
         }
     }
 
+    /// Build the timeout error, handing over everything collected so far.
+    fn timed_out(&mut self, timeout: std::time::Duration) -> Error {
+        let entries = std::mem::take(&mut self.entries);
+        let stats = std::mem::take(&mut self.stats);
+        Error::JournalTimeout {
+            action: "journalctl",
+            timeout,
+            partial: Box::new(JournalResult::from_entries(entries, true, stats)),
+        }
+    }
+
     fn push_line(&mut self, line: &str) -> Result<CollectAction> {
+        let parsed = parse_entry(line, self.max_message_bytes, self.fields.as_deref());
+        // Entries before `since_monotonic` are dropped without counting against the bounds.
+        if let (Ok(entry), Some(min)) = (&parsed, self.since_monotonic)
+            && entry
+                .monotonic
+                .is_some_and(|m| m < std::time::Duration::from_micros(min))
+        {
+            return Ok(CollectAction::Continue);
+        }
+
         self.stats.lines_read = self.stats.lines_read.saturating_add(1);
 
         let line_len = u32::try_from(line.len()).unwrap_or(u32::MAX);
         let next_bytes = self.stats.bytes_read.saturating_add(line_len);
-        if next_bytes > self.max_bytes {
+        // When collapsing, `limit`/`max_bytes` apply to the kept entries (checked below) and
+        // only `max_scanned` bounds the raw lines.
+        if self.collapse.is_none() && next_bytes > self.max_bytes {
             self.truncated = true;
             return Ok(CollectAction::StopTruncated);
         }
         self.stats.bytes_read = next_bytes;
 
-        if self.stats.lines_read > self.limit {
+        let max_lines = self.collapse.map_or(self.limit, |c| c.max_scanned);
+        if self.stats.lines_read > max_lines {
             self.truncated = true;
             return Ok(CollectAction::StopTruncated);
         }
 
-        match parse_entry(line, self.max_message_bytes) {
-            Ok(entry) => self.entries.push(entry),
+        match parsed {
+            Ok(entry) => {
+                let Some(collapse) = self.collapse else {
+                    self.entries.push(entry);
+                    return Ok(CollectAction::Continue);
+                };
+                let Some(entry) = collapse.fold(&mut self.entries, entry) else {
+                    return Ok(CollectAction::Continue);
+                };
+                let kept_bytes = self.kept_bytes.saturating_add(line_len);
+                if self.entries.len() >= usize::try_from(self.limit).unwrap_or(usize::MAX)
+                    || kept_bytes > self.max_bytes
+                {
+                    self.truncated = true;
+                    return Ok(CollectAction::StopTruncated);
+                }
+                self.kept_bytes = kept_bytes;
+                self.entries.push(entry);
+            }
             Err(e) => match &self.parse_error {
                 ParseErrorMode::FailFast => return Err(e),
                 ParseErrorMode::Skip { max_skipped } => {
@@ -334,12 +475,122 @@ impl JournalCollector {
     }
 }
 
-fn parse_entry(line: &str, max_message_bytes: u32) -> Result<JournalEntry> {
-    let v: serde_json::Value = serde_json::from_str(line)
+/// Fields decoded into typed `JournalEntry` members.
+const TYPED_FIELDS: [&str; 8] = [
+    "__REALTIME_TIMESTAMP",
+    "__MONOTONIC_TIMESTAMP",
+    "_SOURCE_REALTIME_TIMESTAMP",
+    "__CURSOR",
+    "MESSAGE",
+    "PRIORITY",
+    "_SYSTEMD_UNIT",
+    "_PID",
+];
+
+/// One JSON line, decoded only as far as needed.
+///
+/// `typed` holds the values of `TYPED_FIELDS`; `fields` the allowlisted fields already converted
+/// to bytes. Values of all other fields are skipped by the parser without being materialized.
+struct PartialLine {
+    typed: serde_json::Map<String, serde_json::Value>,
+    fields: BTreeMap<String, Vec<u8>>,
+}
+
+struct PartialLineSeed<'a> {
+    allowlist: Option<&'a [String]>,
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for PartialLineSeed<'_> {
+    type Value = PartialLine;
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<PartialLine, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for PartialLineSeed<'_> {
+    type Value = PartialLine;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a journal entry object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<PartialLine, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut line = PartialLine {
+            typed: serde_json::Map::new(),
+            fields: BTreeMap::new(),
+        };
+        while let Some(key) = map.next_key::<FieldName<'de>>()? {
+            let key = key.0.as_ref();
+            let typed = TYPED_FIELDS.contains(&key);
+            let kept = crate::types::journal::keeps_field(self.allowlist, key);
+            if !typed && !kept {
+                map.next_value::<serde::de::IgnoredAny>()?;
+                continue;
+            }
+
+            let value: serde_json::Value = map.next_value()?;
+            if kept {
+                line.fields
+                    .insert(key.to_string(), json_value_to_bytes(&value));
+            }
+            if typed {
+                line.typed.insert(key.to_string(), value);
+            }
+        }
+        Ok(line)
+    }
+}
+
+/// A JSON object key, borrowed from the input line when it contains no escapes.
+struct FieldName<'de>(std::borrow::Cow<'de, str>);
+
+impl<'de> serde::Deserialize<'de> for FieldName<'de> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct KeyVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for KeyVisitor {
+            type Value = FieldName<'de>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a field name")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> std::result::Result<FieldName<'de>, E> {
+                Ok(FieldName(std::borrow::Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> std::result::Result<FieldName<'de>, E> {
+                Ok(FieldName(std::borrow::Cow::Owned(v.to_string())))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+fn parse_entry(
+    line: &str,
+    max_message_bytes: u32,
+    allowlist: Option<&[String]>,
+) -> Result<JournalEntry> {
+    use serde::de::DeserializeSeed;
+
+    let mut de = serde_json::Deserializer::from_str(line);
+    let PartialLine { typed, fields } = PartialLineSeed { allowlist }
+        .deserialize(&mut de)
+        .and_then(|parsed| de.end().map(|()| parsed))
         .map_err(|_| Error::parse_error("journalctl json line parse", line))?;
-    let obj = v
-        .as_object()
-        .ok_or_else(|| Error::parse_error("journalctl json line is not an object", line))?;
+    let obj = &typed;
 
     let ts = parse_timestamp_micros(obj).ok_or_else(|| {
         Error::parse_error("journalctl missing/invalid __REALTIME_TIMESTAMP", line)
@@ -384,11 +635,6 @@ fn parse_entry(line: &str, max_message_bytes: u32) -> Result<JournalEntry> {
         _ => None,
     });
 
-    let mut fields = BTreeMap::new();
-    for (k, v) in obj {
-        fields.insert(k.clone(), json_value_to_bytes(v));
-    }
-
     Ok(JournalEntry {
         timestamp,
         cursor,
@@ -397,6 +643,8 @@ fn parse_entry(line: &str, max_message_bytes: u32) -> Result<JournalEntry> {
         priority,
         unit,
         pid,
+        monotonic: parse_u64(obj, "__MONOTONIC_TIMESTAMP").map(std::time::Duration::from_micros),
+        repeat_count: 1,
         fields,
     })
 }
@@ -455,6 +703,46 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn launcher_wraps_journalctl_per_sandbox() {
+        assert_eq!(launcher_argv(None).unwrap(), ["journalctl"]);
+
+        let sandbox = crate::JournalCliSandbox::default();
+        assert_eq!(
+            launcher_argv(Some(&sandbox)).unwrap(),
+            ["nice", "-n", "10", "journalctl"]
+        );
+
+        let scoped = crate::JournalCliSandbox {
+            nice: None,
+            ..Default::default()
+        }
+        .with_memory_max(64 << 20);
+        assert_eq!(
+            launcher_argv(Some(&scoped)).unwrap(),
+            [
+                "systemd-run",
+                "--scope",
+                "--quiet",
+                "--collect",
+                "--property=MemoryMax=67108864",
+                "--",
+                "journalctl"
+            ]
+        );
+
+        let unscoped_limit = crate::JournalCliSandbox {
+            memory_max: Some(1),
+            ..Default::default()
+        };
+        assert!(launcher_argv(Some(&unscoped_limit)).is_err());
+        let bad_nice = crate::JournalCliSandbox {
+            nice: Some(40),
+            ..Default::default()
+        };
+        assert!(launcher_argv(Some(&bad_nice)).is_err());
+    }
+
     #[test]
     fn collector_marks_truncated_when_limit_exceeded() {
         let filter = JournalFilter {
@@ -477,6 +765,96 @@ mod tests {
         assert_eq!(collector.stats.lines_read, 2);
     }
 
+    #[test]
+    fn collector_drops_entries_before_since_monotonic() {
+        let mut collector = JournalCollector::new(&JournalFilter::default());
+        collector.since_monotonic = Some(5_000_000);
+
+        let early = r#"{"__REALTIME_TIMESTAMP":"9","__MONOTONIC_TIMESTAMP":"1000","MESSAGE":"a"}"#;
+        let late =
+            r#"{"__REALTIME_TIMESTAMP":"1","__MONOTONIC_TIMESTAMP":"6000000","MESSAGE":"b"}"#;
+        collector.push_line(early).expect("ok");
+        collector.push_line(late).expect("ok");
+
+        assert_eq!(collector.entries.len(), 1);
+        assert_eq!(collector.entries[0].message.as_deref(), Some("b"));
+        assert_eq!(
+            collector.entries[0].monotonic,
+            Some(std::time::Duration::from_secs(6))
+        );
+    }
+
+    #[test]
+    fn collector_does_not_count_entries_before_since_monotonic() {
+        let filter = JournalFilter {
+            limit: 2,
+            ..Default::default()
+        };
+        let mut collector = JournalCollector::new(&filter);
+        collector.since_monotonic = Some(5_000_000);
+
+        let line = |mono: u64| {
+            format!(
+                r#"{{"__REALTIME_TIMESTAMP":"{mono}","__MONOTONIC_TIMESTAMP":"{mono}","__CURSOR":"c{mono}","MESSAGE":"m"}}"#
+            )
+        };
+        for mono in 1..=10 {
+            assert_eq!(
+                collector.push_line(&line(mono)).unwrap(),
+                CollectAction::Continue
+            );
+        }
+        for mono in [6_000_000, 7_000_000] {
+            assert_eq!(
+                collector.push_line(&line(mono)).unwrap(),
+                CollectAction::Continue
+            );
+        }
+        assert_eq!(collector.entries.len(), 2);
+        assert!(!collector.truncated);
+        assert_eq!(collector.stats.lines_read, 2);
+
+        assert_eq!(
+            collector.push_line(&line(8_000_000)).unwrap(),
+            CollectAction::StopTruncated
+        );
+        assert!(collector.truncated);
+        assert_eq!(collector.entries[1].cursor.as_deref(), Some("c7000000"));
+    }
+
+    #[test]
+    fn collector_collapses_repeats_before_applying_limit() {
+        let filter = JournalFilter {
+            limit: 2,
+            collapse_repeats: Some(crate::CollapseOptions::default()),
+            ..Default::default()
+        };
+        let mut collector = JournalCollector::new(&filter);
+
+        let line = |ts: u32, msg: &str| {
+            format!(r#"{{"__REALTIME_TIMESTAMP":"{ts}","__CURSOR":"c{ts}","MESSAGE":"{msg}"}}"#)
+        };
+        for ts in 1..=50 {
+            assert_eq!(
+                collector.push_line(&line(ts, "crash")).unwrap(),
+                CollectAction::Continue
+            );
+        }
+        assert_eq!(
+            collector.push_line(&line(51, "useful")).unwrap(),
+            CollectAction::Continue
+        );
+        assert_eq!(
+            collector.push_line(&line(52, "more")).unwrap(),
+            CollectAction::StopTruncated
+        );
+
+        assert_eq!(collector.entries.len(), 2);
+        assert_eq!(collector.entries[0].repeat_count, 50);
+        assert_eq!(collector.entries[0].cursor.as_deref(), Some("c50"));
+        assert_eq!(collector.entries[1].message.as_deref(), Some("useful"));
+    }
+
     #[test]
     fn collector_skip_mode_tracks_errors_and_stops_after_threshold() {
         let filter = JournalFilter {
@@ -508,6 +886,26 @@ mod tests {
         };
     }
 
+    #[test]
+    fn collector_timeout_carries_partial_result() {
+        let mut collector = JournalCollector::new(&JournalFilter::default());
+        let a = r#"{"__REALTIME_TIMESTAMP":"1","__CURSOR":"s=a","MESSAGE":"a"}"#;
+        assert_eq!(collector.push_line(a).expect("ok"), CollectAction::Continue);
+
+        let err = collector.timed_out(std::time::Duration::from_secs(1));
+        let Error::JournalTimeout {
+            action, partial, ..
+        } = err
+        else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(action, "journalctl");
+        assert!(partial.truncated);
+        assert_eq!(partial.entries.len(), 1);
+        assert_eq!(partial.next_cursor.as_deref(), Some("s=a"));
+        assert_eq!(partial.stats.lines_read, 1);
+    }
+
     #[test]
     fn classify_after_cursor_unknown_option_as_backend_unavailable() {
         let err = classify_journalctl_failure(true, "Unknown option --after-cursor=abc")
@@ -531,7 +929,7 @@ mod tests {
     #[test]
     fn parse_entry_extracts_basic_fields() {
         let line = r#"{"__REALTIME_TIMESTAMP":"1000000","__CURSOR":"c","MESSAGE":"hello","PRIORITY":"6","_SYSTEMD_UNIT":"nginx.service","_PID":"123"}"#;
-        let e = parse_entry(line, 16 * 1024).expect("parse ok");
+        let e = parse_entry(line, 16 * 1024, None).expect("parse ok");
         assert_eq!(e.cursor.as_deref(), Some("c"));
         assert_eq!(e.message.as_deref(), Some("hello"));
         assert_eq!(e.priority, Some(6));
@@ -544,7 +942,7 @@ mod tests {
     #[test]
     fn parse_entry_truncates_message() {
         let line = r#"{"__REALTIME_TIMESTAMP":"1","MESSAGE":"abcdef"}"#;
-        let e = parse_entry(line, 3).expect("parse ok");
+        let e = parse_entry(line, 3, None).expect("parse ok");
         assert_eq!(e.message.as_deref(), Some("abc"));
         assert!(e.message_truncated);
     }
@@ -552,11 +950,66 @@ mod tests {
     #[test]
     fn parse_entry_accepts_non_string_message() {
         let line = r#"{"__REALTIME_TIMESTAMP":"1","MESSAGE":[104,101,108,108,111]}"#;
-        let e = parse_entry(line, 16 * 1024).expect("parse ok");
+        let e = parse_entry(line, 16 * 1024, None).expect("parse ok");
         assert_eq!(e.message.as_deref(), Some("hello"));
         assert!(!e.message_truncated);
     }
 
+    #[test]
+    fn parse_entry_allowlist_keeps_only_listed_fields() {
+        let line = r#"{"__REALTIME_TIMESTAMP":"1","MESSAGE":"hi","PRIORITY":"3","_HOSTNAME":"h","CODE_FILE":"a.c","_CMDLINE":{"nested":[1,2]}}"#;
+        let allowlist = vec!["_HOSTNAME".to_string()];
+        let e = parse_entry(line, 16 * 1024, Some(&allowlist)).expect("parse ok");
+        assert_eq!(e.message.as_deref(), Some("hi"));
+        assert_eq!(e.priority, Some(3));
+        assert_eq!(e.fields.keys().collect::<Vec<_>>(), vec!["_HOSTNAME"]);
+        assert_eq!(e.fields["_HOSTNAME"], b"h");
+
+        let e = parse_entry(line, 16 * 1024, Some(&[])).expect("parse ok");
+        assert!(e.fields.is_empty());
+        assert!(parse_entry(r#"{"MESSAGE":"x"} {}"#, 16, Some(&[])).is_err());
+        assert!(parse_entry(r#"["MESSAGE"]"#, 16, None).is_err());
+    }
+
+    #[test]
+    fn validate_fields_rejects_malformed_names() {
+        let mut filter = JournalFilter {
+            fields: Some(vec!["MESSAGE".to_string(), "_PID".to_string()]),
+            ..Default::default()
+        };
+        assert!(filter.validate_fields().is_ok());
+        for bad in ["", "message", "1ABC", "A B"] {
+            filter.fields = Some(vec![bad.to_string()]);
+            assert!(filter.validate_fields().is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn parse_entry_allowlist_matches_full_parse_for_listed_fields() {
+        let mut line = String::from(
+            r#"{"__REALTIME_TIMESTAMP":"1700000000000000","__CURSOR":"s=0;i=1","MESSAGE":"served","PRIORITY":"6","_SYSTEMD_UNIT":"app.service","_PID":"4242""#,
+        );
+        for i in 0..40 {
+            line.push_str(&format!(r#","FIELD_{i}":"value {i}""#));
+        }
+        line.push_str(r#","_CMDLINE":[47,97,112,112]}"#);
+
+        let full = parse_entry(&line, 16 * 1024, None).expect("parse ok");
+        let allowlist = vec!["FIELD_7".to_string(), "_CMDLINE".to_string()];
+        let kept = parse_entry(&line, 16 * 1024, Some(&allowlist)).expect("parse ok");
+
+        // Typed fields are decoded whatever the allowlist says.
+        assert_eq!(kept.timestamp, full.timestamp);
+        assert_eq!(kept.cursor, full.cursor);
+        assert_eq!(kept.message, full.message);
+        assert_eq!(kept.priority, full.priority);
+        assert_eq!(kept.unit, full.unit);
+        assert_eq!(kept.pid, full.pid);
+        assert_eq!(kept.fields.len(), 2);
+        assert_eq!(kept.fields["FIELD_7"], full.fields["FIELD_7"]);
+        assert_eq!(kept.fields["_CMDLINE"], b"/app");
+    }
+
     #[test]
     fn json_value_to_bytes_handles_byte_arrays() {
         let v = serde_json::json!([0, 255, 1]);
@@ -1,5 +1,6 @@
 use crate::Result;
 
+use futures_util::StreamExt;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -23,25 +24,212 @@ impl Journal {
         &self,
         filter: crate::types::journal::JournalFilter,
     ) -> Result<crate::types::journal::JournalResult> {
-        #[cfg(feature = "journal-cli")]
-        {
-            return crate::journal::cli::query_journalctl(&self.inner.opts, filter).await;
+        let unit = filter.unit.clone();
+        let res = crate::telemetry::traced("journal_query", unit.as_deref(), async {
+            #[cfg(feature = "journal-cli")]
+            {
+                return crate::journal::cli::query_journalctl(
+                    &self.inner.opts,
+                    self.inner.scope,
+                    filter,
+                )
+                .await;
+            }
+
+            #[cfg(all(not(feature = "journal-cli"), feature = "journal-sdjournal"))]
+            {
+                return crate::journal::sdjournal::query_sdjournal(
+                    &self.inner.opts,
+                    self.inner.scope,
+                    filter,
+                )
+                .await;
+            }
+
+            #[cfg(all(not(feature = "journal-cli"), not(feature = "journal-sdjournal")))]
+            {
+                let _ = filter;
+                return Err(crate::Error::BackendUnavailable {
+                    backend: "journald",
+                    detail: "no journald backend enabled (enable journal-cli or journal-sdjournal)"
+                        .to_string(),
+                });
+            }
+        })
+        .await;
+        crate::instrument::journal_query(&res);
+        res
+    }
+
+    /// Fetch the entries of `unit` logged after the cursor bookmarked in `store`.
+    ///
+    /// Without a bookmark the query starts at the oldest entry. Each call returns at most one
+    /// default-sized page (see `JournalFilter::default`); call again while
+    /// `JournalResult.truncated` is set to catch up. The bookmark is not moved here: call
+    /// `BookmarkPage::commit` once the page is processed, so a collector that crashes mid-page
+    /// reads it again.
+    pub async fn query_since_bookmark(
+        &self,
+        unit: &str,
+        store: &dyn crate::CursorStore,
+    ) -> Result<crate::BookmarkPage> {
+        let unit = crate::util::canonicalize_unit_name(unit)?;
+        let filter = crate::types::journal::JournalFilter {
+            unit: Some(unit.clone()),
+            after_cursor: store.load(&unit).await?,
+            direction: crate::QueryDirection::Forward,
+            ..Default::default()
+        };
+
+        let result = self.query(filter).await?;
+        Ok(crate::BookmarkPage { result, key: unit })
+    }
+
+    /// Ship entries matching `filter` to `sink` in batches, resuming from the position saved in
+    /// `store` under `opts.cursor_key` (or `filter.after_cursor` on the first run). `filter`
+    /// must read forward (`QueryDirection::Forward`).
+    ///
+    /// Each batch is one bounded query (`filter.limit` / `filter.max_bytes`). The next batch is
+    /// only read once `sink` accepted the previous one, so a slow sink throttles reading rather
+    /// than buffering, and the cursor is saved after every delivered batch (at-least-once).
+    /// Failing queries and sends are retried with exponential backoff; after
+    /// `opts.max_attempts` the last error is returned with the cursor left at the last
+    /// delivered batch.
+    ///
+    /// With `opts.follow` (the default) this polls for new entries until the future is dropped
+    /// or an error is returned; otherwise it returns once caught up.
+    pub async fn forward(
+        &self,
+        filter: crate::types::journal::JournalFilter,
+        sink: &dyn crate::JournalSink,
+        store: &dyn crate::CursorStore,
+        opts: crate::ForwardOptions,
+    ) -> Result<crate::ForwardReport> {
+        opts.validate()?;
+        filter.require_forward("forward")?;
+        crate::util::validate_no_control("cursor_key", &opts.cursor_key)?;
+        let mut filter = filter;
+        if let Some(cursor) = store.load(&opts.cursor_key).await? {
+            filter.after_cursor = Some(cursor);
         }
 
-        #[cfg(all(not(feature = "journal-cli"), feature = "journal-sdjournal"))]
-        {
-            return crate::journal::sdjournal::query_sdjournal(&self.inner.opts, filter).await;
+        let unit = filter.unit.clone();
+        crate::telemetry::traced("journal_forward", unit.as_deref(), async {
+            let mut report = crate::ForwardReport::default();
+            loop {
+                let res = retrying(&opts, &mut report, || self.query(filter.clone())).await?;
+                if !res.entries.is_empty() {
+                    retrying(&opts, &mut report, || sink.send(&res.entries)).await?;
+                    report.batches += 1;
+                    report.entries_forwarded += res.entries.len() as u64;
+                }
+                if let Some(cursor) = res.next_cursor {
+                    store.save(&opts.cursor_key, &cursor).await?;
+                    filter.after_cursor = Some(cursor.clone());
+                    report.last_cursor = Some(cursor);
+                }
+                if res.truncated {
+                    continue;
+                }
+
+                if !opts.follow {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(
+                        entries = report.entries_forwarded,
+                        batches = report.batches,
+                        retries = report.retries,
+                        "journal_forward caught up"
+                    );
+                    return Ok(report);
+                }
+                crate::runtime::sleep(opts.poll_interval).await;
+            }
+        })
+        .await
+    }
+
+    /// Tail entries matching `filter` as they are written, like `journalctl -f`.
+    /// `filter.direction` must be `QueryDirection::Forward`.
+    ///
+    /// Starts after `filter.after_cursor`, at `filter.since`, or (with neither) at the current
+    /// time. The journal is polled with bounded `query` calls (same `limit`, `max_bytes` and
+    /// `max_message_bytes` handling, on either backend), every `opts.poll_interval` once caught
+    /// up. The stream ends once caught up past `filter.until`; without it, it runs until
+    /// dropped or an error is yielded.
+    pub fn follow(
+        &self,
+        filter: crate::types::journal::JournalFilter,
+        opts: crate::FollowOptions,
+    ) -> Result<crate::JournalFollow> {
+        opts.validate()?;
+        filter.require_forward("follow")?;
+        let mut filter = filter;
+        if filter.limit == 0 {
+            return Err(crate::Error::invalid_input("journal limit must be > 0"));
+        }
+        if filter.after_cursor.is_none() && filter.since.is_none() {
+            filter.since = Some(std::time::SystemTime::now());
         }
 
-        #[cfg(all(not(feature = "journal-cli"), not(feature = "journal-sdjournal")))]
-        {
-            let _ = filter;
-            return Err(crate::Error::BackendUnavailable {
-                backend: "journald",
-                detail: "no journald backend enabled (enable journal-cli or journal-sdjournal)"
-                    .to_string(),
-            });
+        let state = FollowState {
+            journal: self.clone(),
+            filter,
+            opts,
+            buffered: std::collections::VecDeque::new(),
+            done: false,
+        };
+        Ok(crate::JournalFollow {
+            stream: Box::pin(futures_util::stream::unfold(state, FollowState::next)),
+        })
+    }
+
+    /// Run `filter` against the journals of several local machines (containers/VMs registered
+    /// with `systemd-machined`, like `journalctl --machine`).
+    ///
+    /// Queries run concurrently, at most 4 at a time, and each is bounded by `filter` on its
+    /// own. Results come back in the order of `machines`; a failure for one machine is recorded
+    /// in its entry instead of failing the whole call.
+    ///
+    /// With the `sdjournal` backend the journal files are read from the machine image under
+    /// `/var/lib/machines/<name>` (or the host directory named after its machine ID).
+    pub async fn query_machines(
+        &self,
+        machines: &[&str],
+        filter: crate::types::journal::JournalFilter,
+    ) -> Result<Vec<crate::types::journal::MachineJournalResult>> {
+        const MACHINE_QUERY_CONCURRENCY: usize = 4;
+
+        if machines.is_empty() {
+            return Err(crate::Error::invalid_input("machines must not be empty"));
         }
+        for machine in machines {
+            crate::util::validate_machine_name(machine)?;
+        }
+
+        crate::telemetry::traced("journal_query_machines", None, async {
+            let filter = &filter;
+            let results = futures_util::stream::iter(machines.iter().map(|machine| async move {
+                let mut filter = filter.clone();
+                filter.machine = Some(machine.to_string());
+                crate::types::journal::MachineJournalResult {
+                    machine: machine.to_string(),
+                    result: self.query(filter).await,
+                }
+            }))
+            .buffered(MACHINE_QUERY_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                machines = results.len(),
+                failed = results.iter().filter(|r| r.result.is_err()).count(),
+                "journal_query_machines done"
+            );
+
+            Ok(results)
+        })
+        .await
     }
 
     /// Convenience helper that fetches a status snapshot and a bounded log slice around "now".
@@ -54,39 +242,67 @@ impl Journal {
     ) -> Result<crate::types::journal::Diagnosis> {
         let unit = crate::util::canonicalize_unit_name(unit)?;
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %unit,
-            limit = opts.limit,
-            max_bytes = opts.max_bytes,
-            max_message_bytes = opts.max_message_bytes,
-            "diagnose_unit_failure"
-        );
+        crate::telemetry::traced("diagnose_unit_failure", Some(&unit), async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                limit = opts.limit,
+                max_bytes = opts.max_bytes,
+                max_message_bytes = opts.max_message_bytes,
+                "diagnose_unit_failure"
+            );
+
+            self.diagnose_at(&unit, std::time::SystemTime::now(), &opts)
+                .await
+        })
+        .await
+    }
 
+    /// Status snapshot plus the bounded log slice around `anchor` for an already canonicalized
+    /// unit name.
+    pub(crate) async fn diagnose_at(
+        &self,
+        unit: &str,
+        anchor: std::time::SystemTime,
+        opts: &crate::types::journal::DiagnosisOptions,
+    ) -> Result<crate::types::journal::Diagnosis> {
         let status = crate::units::Units::new(self.inner.clone())
-            .get_status(&unit)
+            .get_status(unit)
             .await?;
 
-        let now = std::time::SystemTime::now();
-        let since = match now.checked_sub(opts.window_before) {
+        let since = match anchor.checked_sub(opts.window_before) {
             Some(t) => t,
             None => std::time::UNIX_EPOCH,
         };
-        let until = now.checked_add(opts.window_after);
+        let until = anchor.checked_add(opts.window_after);
 
         let filter = crate::types::journal::JournalFilter {
-            unit: Some(unit),
+            unit: Some(unit.to_string()),
             since: Some(since),
+            since_monotonic: None,
             until,
             after_cursor: None,
+            direction: crate::QueryDirection::Forward,
+            before_cursor: None,
             limit: opts.limit,
             max_bytes: opts.max_bytes,
             max_message_bytes: opts.max_message_bytes,
             timeout: opts.timeout,
-            parse_error: opts.parse_error,
+            parse_error: opts.parse_error.clone(),
+            fields: None,
+            priority_max: None,
+            priority_min: None,
+            matches: Vec::new(),
+            collapse_repeats: None,
+            machine: None,
         };
 
-        let res = self.query(filter).await?;
+        // Under load a slow journal should still yield whatever was read before the deadline.
+        let res = match self.query(filter).await {
+            Ok(res) => res,
+            Err(crate::Error::JournalTimeout { partial, .. }) => *partial,
+            Err(e) => return Err(e),
+        };
         Ok(crate::types::journal::Diagnosis {
             status,
             logs: res.entries,
@@ -95,6 +311,97 @@ impl Journal {
     }
 }
 
+struct FollowState {
+    journal: Journal,
+    filter: crate::types::journal::JournalFilter,
+    opts: crate::FollowOptions,
+    buffered: std::collections::VecDeque<crate::JournalEntry>,
+    done: bool,
+}
+
+impl FollowState {
+    async fn next(mut self) -> Option<(Result<crate::JournalEntry>, Self)> {
+        loop {
+            if let Some(entry) = self.buffered.pop_front() {
+                return Some((Ok(entry), self));
+            }
+            if self.done {
+                return None;
+            }
+
+            let polled_at = std::time::SystemTime::now();
+            let res = match self.journal.query(self.filter.clone()).await {
+                Ok(res) => res,
+                // Keep what was read; the next poll resumes from its cursor.
+                Err(crate::Error::JournalTimeout { partial, .. })
+                    if !partial.entries.is_empty() =>
+                {
+                    *partial
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some((Err(e), self));
+                }
+            };
+            if let Some(cursor) = res.next_cursor {
+                // The cursor pins the position; a realtime `since` would hide new entries if
+                // the clock is set back.
+                self.filter.after_cursor = Some(cursor);
+                self.filter.since = None;
+            }
+            if !res.entries.is_empty() {
+                self.buffered.extend(res.entries);
+                continue;
+            }
+            if self.filter.until.is_some_and(|until| polled_at > until) {
+                self.done = true;
+                continue;
+            }
+            crate::runtime::sleep(self.opts.poll_interval).await;
+        }
+    }
+}
+
+impl futures_util::Stream for crate::JournalFollow {
+    type Item = Result<crate::JournalEntry>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+/// Run `op` up to `opts.max_attempts` times, sleeping `opts.backoff(n)` between attempts.
+async fn retrying<T, F, Fut>(
+    opts: &crate::ForwardOptions,
+    report: &mut crate::ForwardReport,
+    mut op: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(v) => {
+                report.retries += u64::from(attempt - 1);
+                return Ok(v);
+            }
+            Err(e) if attempt < opts.max_attempts => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(attempt, error = %e, "journal_forward retrying");
+                let _ = e;
+                crate::runtime::sleep(opts.backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 #[cfg(feature = "journal-cli")]
 mod cli;
 
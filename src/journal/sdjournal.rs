@@ -4,13 +4,23 @@ use crate::types::journal::{
 use crate::{Error, Result, UnitBusOptions};
 
 use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+/// Where `machinectl`/`systemd-nspawn` keep local machine images.
+const MACHINES_ROOT: &str = "/var/lib/machines";
+
 pub(crate) async fn query_sdjournal(
     opts: &UnitBusOptions,
+    scope: crate::ManagerScope,
     filter: JournalFilter,
 ) -> Result<JournalResult> {
     let mut filter = filter;
+    filter.validate_direction()?;
+    filter.validate_fields()?;
+    if let Some(collapse) = &filter.collapse_repeats {
+        collapse.validate()?;
+    }
     let timeout = filter
         .timeout
         .take()
@@ -21,6 +31,12 @@ pub(crate) async fn query_sdjournal(
         None => None,
     };
 
+    let since_monotonic = filter.resolve_since_monotonic()?;
+    let priorities = filter
+        .priority_range()?
+        .map(|(lo, hi)| (lo..=hi).collect())
+        .unwrap_or_default();
+
     let since_realtime = filter
         .since
         .take()
@@ -42,10 +58,21 @@ pub(crate) async fn query_sdjournal(
     let max_bytes = filter.max_bytes;
     let max_message_bytes = filter.max_message_bytes;
     let parse_error = filter.parse_error;
+    let fields = filter.fields;
+    let matches = filter.matches;
+    let reverse = filter.direction == crate::QueryDirection::Reverse;
+    let before_cursor = filter.before_cursor;
+    let collapse = filter.collapse_repeats;
+    let machine = filter.machine;
+    if let Some(machine) = &machine {
+        crate::util::validate_machine_name(machine)?;
+    }
 
     let args = SdJournalQueryArgs {
         unit,
+        user_unit: scope == crate::ManagerScope::User,
         since_realtime,
+        since_monotonic,
         until_realtime,
         after_cursor,
         limit,
@@ -53,14 +80,66 @@ pub(crate) async fn query_sdjournal(
         max_message_bytes,
         timeout,
         parse_error,
+        fields,
+        matches,
+        priorities,
+        collapse,
+        reverse,
+        before_cursor,
+        machine,
     };
 
     blocking::unblock(move || query_sdjournal_sync(args)).await
 }
 
+/// The alternatives of the unit and priority criteria as OR-groups of AND-ed matches.
+///
+/// Both criteria are disjunctions, so each unit field is paired with each priority value. A user
+/// manager's unit is matched on the `USER_UNIT` fields (like `journalctl --user-unit`).
+fn or_groups(
+    unit: Option<&str>,
+    user_unit: bool,
+    priorities: &[u8],
+) -> Vec<Vec<(&'static str, String)>> {
+    let mut groups = vec![Vec::new()];
+    if let Some(unit) = unit {
+        let fields = if user_unit {
+            [
+                "_SYSTEMD_USER_UNIT",
+                "USER_UNIT",
+                "OBJECT_SYSTEMD_USER_UNIT",
+            ]
+        } else {
+            ["_SYSTEMD_UNIT", "UNIT", "OBJECT_SYSTEMD_UNIT"]
+        };
+        groups = fields
+            .into_iter()
+            .map(|field| vec![(field, unit.to_string())])
+            .collect();
+    }
+    if !priorities.is_empty() {
+        groups = groups
+            .iter()
+            .flat_map(|group| {
+                priorities.iter().map(move |p| {
+                    let mut group = group.clone();
+                    group.push(("PRIORITY", p.to_string()));
+                    group
+                })
+            })
+            .collect();
+    }
+    groups.retain(|group| !group.is_empty());
+    groups
+}
+
 struct SdJournalQueryArgs {
     unit: Option<String>,
+    /// Match `unit` as a user manager's unit.
+    user_unit: bool,
     since_realtime: Option<u64>,
+    /// Minimum `__MONOTONIC_TIMESTAMP`; the boot is already matched by `_BOOT_ID`.
+    since_monotonic: Option<u64>,
     until_realtime: Option<u64>,
     after_cursor: Option<sdjournal::Cursor>,
     limit: u32,
@@ -68,12 +147,24 @@ struct SdJournalQueryArgs {
     max_message_bytes: u32,
     timeout: Duration,
     parse_error: ParseErrorMode,
+    fields: Option<Vec<String>>,
+    matches: Vec<(String, String)>,
+    /// Accepted `PRIORITY` values; empty means any.
+    priorities: Vec<u8>,
+    collapse: Option<crate::CollapseOptions>,
+    /// Newest first: the journal is read backwards from its tail.
+    reverse: bool,
+    /// Start below this cursor (older entries only); only with `reverse`.
+    before_cursor: Option<String>,
+    machine: Option<String>,
 }
 
 fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
     let SdJournalQueryArgs {
         unit,
+        user_unit,
         since_realtime,
+        since_monotonic,
         until_realtime,
         after_cursor,
         limit,
@@ -81,6 +172,13 @@ fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
         max_message_bytes,
         timeout,
         parse_error,
+        fields: field_allowlist,
+        matches,
+        priorities,
+        collapse,
+        reverse,
+        before_cursor,
+        machine,
     } = args;
     let mut stats = JournalStats::default();
     let mut entries: Vec<JournalEntry> = Vec::new();
@@ -89,20 +187,23 @@ fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
 
     let deadline = Instant::now().checked_add(timeout);
 
-    let journal = sdjournal::Journal::open_default().map_err(map_sdjournal_error)?;
+    let journal = match &machine {
+        Some(machine) => sdjournal::Journal::open_dirs(&machine_journal_dirs(machine)?),
+        None => sdjournal::Journal::open_default(),
+    }
+    .map_err(map_sdjournal_error)?;
     let mut q = journal.query();
 
-    if let Some(unit) = &unit {
-        q.or_group(|g| {
-            g.match_exact("_SYSTEMD_UNIT", unit.as_bytes());
-        });
+    for group in or_groups(unit.as_deref(), user_unit, &priorities) {
         q.or_group(|g| {
-            g.match_exact("UNIT", unit.as_bytes());
-        });
-        q.or_group(|g| {
-            g.match_exact("OBJECT_SYSTEMD_UNIT", unit.as_bytes());
+            for (field, value) in &group {
+                g.match_exact(field, value.as_bytes());
+            }
         });
     }
+    for (field, value) in &matches {
+        q.match_exact(field, value.as_bytes());
+    }
     if let Some(us) = since_realtime {
         q.since_realtime(us);
     }
@@ -114,26 +215,41 @@ fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
     }
 
     let want = usize::try_from(limit).unwrap_or(usize::MAX);
-    let probe = want.saturating_add(1);
-    q.limit(probe);
+    // When collapsing, `limit`/`max_bytes` apply to the kept entries and only `max_scanned`
+    // bounds the raw entries read.
+    let probe = match collapse {
+        Some(c) => usize::try_from(c.max_scanned).unwrap_or(usize::MAX),
+        None => want,
+    }
+    .saturating_add(1);
+    if reverse {
+        q.reverse(true);
+    }
+    // Entries before `since_monotonic` and above `before_cursor` are dropped below and must not
+    // use up the limit.
+    if since_monotonic.is_none() && before_cursor.is_none() {
+        q.limit(probe);
+    }
+    let mut skip_to = before_cursor;
+    let mut kept_bytes = 0u32;
 
     let iter = q.iter().map_err(map_sdjournal_error)?;
 
     for item in iter {
         if deadline.is_some_and(|d| Instant::now() >= d) {
-            return Err(Error::Timeout {
+            return Err(Error::JournalTimeout {
                 action: "sdjournal",
                 timeout,
+                partial: Box::new(JournalResult::from_entries(entries, true, stats)),
             });
         }
 
-        stats.lines_read = stats.lines_read.saturating_add(1);
-
         let entry = match item {
             Ok(e) => e,
             Err(e) => match &parse_error {
                 ParseErrorMode::FailFast => return Err(map_sdjournal_error(e)),
                 ParseErrorMode::Skip { max_skipped } => {
+                    stats.lines_read = stats.lines_read.saturating_add(1);
                     stats.parse_errors = stats.parse_errors.saturating_add(1);
                     stats.skipped_lines = stats.skipped_lines.saturating_add(1);
                     skipped = skipped.saturating_add(1);
@@ -145,25 +261,47 @@ fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
             },
         };
 
-        if entries.len() >= want {
+        if since_monotonic.is_some_and(|min| entry.monotonic_usec() < min) {
+            // The boot is matched, so reading backwards only older entries follow.
+            if reverse {
+                break;
+            }
+            continue;
+        }
+
+        let cursor = entry
+            .cursor()
+            .ok()
+            .map(|c| c.to_string())
+            .filter(|s| !s.is_empty());
+        if let Some(stop) = &skip_to {
+            // Reading newest first: pass over the previous page down to its last cursor.
+            if cursor.as_ref() == Some(stop) {
+                skip_to = None;
+            }
+            continue;
+        }
+        stats.lines_read = stats.lines_read.saturating_add(1);
+
+        let bounded = collapse.is_none();
+        if collapse.is_some_and(|c| stats.lines_read > c.max_scanned) {
+            truncated = true;
+            break;
+        }
+        if bounded && entries.len() >= want {
             truncated = true;
             break;
         }
 
         let entry_bytes = estimate_entry_bytes(&entry);
         let next_bytes = stats.bytes_read.saturating_add(entry_bytes);
-        if next_bytes > max_bytes {
+        if bounded && next_bytes > max_bytes {
             truncated = true;
             break;
         }
         stats.bytes_read = next_bytes;
 
         let timestamp = crate::util::system_time_from_unix_micros(entry.realtime_usec());
-        let cursor = entry
-            .cursor()
-            .ok()
-            .map(|c| c.to_string())
-            .filter(|s| !s.is_empty());
 
         let (message, message_truncated) = match entry.get("MESSAGE") {
             Some(bytes) => {
@@ -192,10 +330,12 @@ fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
 
         let mut fields = BTreeMap::new();
         for (k, v) in entry.iter_fields() {
-            fields.insert(k.to_string(), v.to_vec());
+            if crate::types::journal::keeps_field(field_allowlist.as_deref(), k) {
+                fields.insert(k.to_string(), v.to_vec());
+            }
         }
 
-        entries.push(JournalEntry {
+        let entry = JournalEntry {
             timestamp,
             cursor,
             message,
@@ -203,18 +343,27 @@ fn query_sdjournal_sync(args: SdJournalQueryArgs) -> Result<JournalResult> {
             priority,
             unit,
             pid,
+            monotonic: Some(Duration::from_micros(entry.monotonic_usec())),
+            repeat_count: 1,
             fields,
-        });
+        };
+        let Some(collapse) = collapse else {
+            entries.push(entry);
+            continue;
+        };
+        let Some(entry) = collapse.fold(&mut entries, entry) else {
+            continue;
+        };
+        let next_kept = kept_bytes.saturating_add(entry_bytes);
+        if entries.len() >= want || next_kept > max_bytes {
+            truncated = true;
+            break;
+        }
+        kept_bytes = next_kept;
+        entries.push(entry);
     }
 
-    let next_cursor = entries.last().and_then(|e| e.cursor.clone());
-
-    Ok(JournalResult {
-        entries,
-        next_cursor,
-        truncated,
-        stats,
-    })
+    Ok(JournalResult::from_entries(entries, truncated, stats))
 }
 
 fn parse_cursor(input: &str) -> Result<sdjournal::Cursor> {
@@ -242,6 +391,30 @@ fn non_empty_string(s: &str) -> Option<String> {
     }
 }
 
+/// Journal directories of a local machine: its own `/var/log/journal` and `/run/log/journal`
+/// under `/var/lib/machines/<name>`, plus the host directories named after its machine ID
+/// (used with `systemd-nspawn --link-journal=host`).
+fn machine_journal_dirs(machine: &str) -> Result<Vec<PathBuf>> {
+    let root = Path::new(MACHINES_ROOT).join(machine);
+    let mut candidates = vec![root.join("var/log/journal"), root.join("run/log/journal")];
+    if let Ok(id) = std::fs::read_to_string(root.join("etc/machine-id")) {
+        let id = id.trim();
+        if !id.is_empty() && id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            candidates.push(Path::new("/var/log/journal").join(id));
+            candidates.push(Path::new("/run/log/journal").join(id));
+        }
+    }
+
+    let dirs: Vec<PathBuf> = candidates.into_iter().filter(|p| p.is_dir()).collect();
+    if dirs.is_empty() {
+        return Err(Error::BackendUnavailable {
+            backend: "sdjournal",
+            detail: format!("no journal directory found for machine {machine}"),
+        });
+    }
+    Ok(dirs)
+}
+
 fn map_sdjournal_error(err: sdjournal::SdJournalError) -> Error {
     let detail = err.to_string();
     match &err {
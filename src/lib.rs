@@ -56,65 +56,165 @@ compile_error!(
 mod blocking_api;
 mod bus;
 mod capabilities;
+#[cfg(feature = "tasks")]
+mod capture;
+mod cgroup;
+mod coredump;
 mod error;
-#[cfg(feature = "config")]
 mod fsutil;
+mod instrument;
 mod journal;
+mod locks;
+#[cfg(feature = "machines")]
+mod machines;
 mod manager;
 #[cfg(feature = "observe")]
+mod model;
+#[cfg(feature = "observe")]
 mod observe;
 mod options;
+#[cfg(feature = "probes")]
+pub mod probes;
+mod propcache;
+#[cfg(feature = "tasks-pty")]
+mod pty;
 mod runtime;
+pub mod shutdown;
+mod telemetry;
 mod types;
 mod units;
 mod util;
 
+pub use crate::types::audit::{AuditActor, AuditOutcome, AuditRecord, AuditSink};
+pub use crate::types::bookmark::{BookmarkPage, CursorFuture, CursorStore, FsCursorStore};
+#[cfg(feature = "tasks")]
+pub use crate::types::builder::TaskSpecBuilder;
+pub use crate::types::builder::{ExecMissing, ExecSet, ServiceUnitSpecBuilder};
+#[cfg(feature = "config")]
+pub use crate::types::calendar::CalendarAnalysis;
+pub use crate::types::calendar::CalendarSpec;
 #[cfg(feature = "config")]
-pub use crate::types::config::{ApplyReport, DropInSpec, RecommendedAction, RemoveReport};
+pub use crate::types::config::{
+    AppTargetReport, AppTargetSpec, ApplyReport, ConfigDrift, ConfigFileDigest, DiffLine,
+    DropInSpec, OnFailureReport, RecommendedAction, ReloadFlushReport, RemoveReport,
+    ShutdownInhibitor, SocketListenerReport, SocketListenerUpdate, UnitConfigFile,
+    UnitConfigFingerprint, UnitFileContent, UnitFileDiff, VerifiedApplyReport,
+};
+pub use crate::types::coredump::{CoredumpEntry, CoredumpFilter, CoredumpId};
+pub use crate::types::credential::{
+    Credential, credentials_directory, read_credential, read_credential_string,
+};
+pub use crate::types::dependency::{
+    DependencyCycle, DependencyEdge, DependencyGraph, DependencyGraphOptions, DependencyKind,
+    DependencyNode, UnitDependencies,
+};
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+pub use crate::types::forward::{FileSink, SyslogSink};
+pub use crate::types::forward::{ForwardOptions, ForwardReport, JournalSink, SinkFuture};
+pub use crate::types::history::{RestartHistory, UnitLifecycleEvent, UnitLifecycleKind};
 pub use crate::types::journal::{
-    Diagnosis, DiagnosisOptions, JournalCursor, JournalEntry, JournalFilter, JournalResult,
-    JournalStats, ParseErrorMode,
+    CollapseOptions, Diagnosis, DiagnosisOptions, FollowOptions, JournalCursor, JournalEntry,
+    JournalFilter, JournalFollow, JournalFormatOptions, JournalOutputStyle, JournalResult,
+    JournalStats, MachineJournalResult, ParseErrorMode, Priority, QueryDirection, SeverityMarker,
+};
+pub use crate::types::lint::{LintFinding, LintKind, LintSeverity, LintStrictness, lint};
+#[cfg(feature = "machines")]
+pub use crate::types::machine::{
+    ContainerHandle, ContainerOptions, ImagePull, ImagePullEvent, ImageSpec, ImageVerify,
+};
+pub use crate::types::manager::{
+    CgroupMode, HostFacts, LogTarget, ManagerDumpReport, ManagerInfo, UnitFileState,
+    UnitInventoryEntry, UnitListEntry, UnitListQuery, UnitListStream, VendorPreset,
+};
+#[cfg(feature = "config")]
+pub use crate::types::offline_update::{
+    OfflineUpdateReport, OfflineUpdateSpec, OfflineUpdateStatus,
 };
-pub use crate::types::manager::{ManagerInfo, UnitListEntry};
-pub use crate::types::properties::Properties;
+pub use crate::types::pressure::{
+    Pressure, PressureKind, PressureLine, PressureResource, UnitPressure,
+};
+pub use crate::types::properties::{Properties, PropertyChange};
+pub use crate::types::reboot::{
+    KernelCheck, ModulesDirKernelCheck, RebootCheckOptions, RebootReason, RebootRequiredReport,
+};
+pub use crate::types::signal::{KillWho, Signal};
+pub use crate::types::specifier::SpecifierContext;
+#[cfg(feature = "tasks-pty")]
+pub use crate::types::task::{InteractiveTaskHandle, PtyReader, PtyWriter};
 #[cfg(feature = "tasks")]
-pub use crate::types::task::{TaskHandle, TaskResult, TaskSpec};
+pub use crate::types::task::{
+    NamingPolicy, ScheduledTaskHandle, SocketTaskHandle, TaskHandle, TaskLogOptions, TaskLogStream,
+    TaskOutput, TaskResult, TaskSocketSpec, TaskSpec, TimerTaskSpec, TimerTrigger,
+};
 pub use crate::types::unit::{
-    ActiveState, FailureHint, JobHandle, JobOutcome, LoadState, UnitStartMode, UnitStatus,
+    ActiveState, BatchOptions, BatchReport, BatchUnitResult, DesiredUnitState, EnsureReport,
+    EscalationPolicy, ExecCommandStatus, ExecPhase, FailureHint, FreezerState, GracefulStopReport,
+    GroupReport, JobHandle, JobOutcome, JobWarning, LoadState, MountStatus, OrderedStartEvent,
+    OrderedStartProgress, OrderedStartReport, PathStatus, PathWatch, SocketListen, SocketStatus,
+    StartWave, StopLevel, TimerStatus, TypedUnitStatus, UnitProcess, UnitResources, UnitStartMode,
+    UnitStatus, VerifiedOutcome, VerifyOptions, WaveUnitResult,
 };
-pub use crate::types::unit_file::{ServiceType, ServiceUnitSpec};
+pub use crate::types::unit_file::{
+    HardeningLevel, ServiceHardening, ServiceType, ServiceUnitSpec, UnitFileChange,
+    UnitFileChangeReport, UnitFileDisableOptions, UnitFileDisableReport, UnitFileEnableOptions,
+    UnitFileEnableReport, UnitFileMaskOptions, UnitFilePresetMode, UnitFilePresetOptions,
+    UnitFileUnmaskOptions,
+};
+pub use crate::types::values::{SystemdDuration, SystemdSize};
 
 #[cfg(feature = "config")]
 pub use crate::types::unit_file::{
-    ServiceUnitInstallOptions, ServiceUnitInstallReport, UnitFileChange, UnitFileDisableOptions,
-    UnitFileDisableReport, UnitFileEnableOptions, UnitFileEnableReport, UnitFileRemoveReport,
-    UnitFileWriteReport, UnitUninstallOptions, UnitUninstallReport,
+    ServiceUnitInstallOptions, ServiceUnitInstallReport, UnitFileRemoveReport, UnitFileWriteReport,
+    UnitUninstallOptions, UnitUninstallReport,
 };
 
-pub use crate::capabilities::Capabilities;
+pub use crate::capabilities::{
+    Capabilities, CapabilityChecks, CgroupVersion, EnvironmentReport, ManagerScope,
+    PolkitAuthorization, ProbeTiming, SelinuxMode,
+};
 pub use crate::error::{Error, Result};
-pub use crate::options::UnitBusOptions;
+pub use crate::options::{
+    JournalCliSandbox, PropertyCacheOptions, TraceRedaction, UnitBusOptions, UnitLockOptions,
+};
 
 #[cfg(feature = "blocking")]
 pub use crate::blocking_api::{
-    BlockingJobHandle, BlockingJournal, BlockingManager, BlockingUnitBus, BlockingUnits,
+    BlockingCoredumps, BlockingJobHandle, BlockingJournal, BlockingJournalFollow, BlockingManager,
+    BlockingOrderedStartProgress, BlockingUnitBus, BlockingUnitListStream, BlockingUnits,
 };
 
 #[cfg(all(feature = "blocking", feature = "tasks"))]
-pub use crate::blocking_api::{BlockingTaskHandle, BlockingTasks};
+pub use crate::blocking_api::{
+    BlockingScheduledTaskHandle, BlockingTaskHandle, BlockingTaskLogs, BlockingTasks,
+};
 
 #[cfg(all(feature = "blocking", feature = "config"))]
-pub use crate::blocking_api::BlockingConfig;
+pub use crate::blocking_api::{BlockingConfig, BlockingReloadCoalescer};
 
+#[cfg(all(feature = "blocking", feature = "machines"))]
+pub use crate::blocking_api::{BlockingImagePull, BlockingMachines};
+
+pub use crate::coredump::Coredumps;
 pub use crate::journal::Journal;
+#[cfg(feature = "machines")]
+pub use crate::machines::Machines;
 pub use crate::manager::Manager;
 #[cfg(feature = "observe")]
-pub use crate::observe::{Observe, ObserveOptions, UnitFailedEvent, UnitFailureWatcher};
-#[cfg(feature = "config")]
-pub use crate::units::Config;
+pub use crate::model::{UnitModel, UnitModelChange, UnitModelView};
+#[cfg(feature = "observe")]
+pub use crate::observe::{
+    AccountingEvent, AccountingMetric, AccountingWatcher, FailedReport, FailedReportOptions,
+    FailedUnitReport, FailureWatcher, LogErrorWatcher, LogRateEvent, LogWatchOptions, ManagerEvent,
+    ManagerWatcher, Observe, ObserveOptions, PressureEvent, PressureThreshold,
+    PressureWatchOptions, PressureWatcher, StateFilter, SystemHealthEvent, SystemHealthWatcher,
+    Thresholds, TimerElapsedEvent, TimerWatcher, UnitFailedEvent, UnitFailureWatcher,
+    UnitStateChangeEvent, UnitStateWatcher, WatchEvent, WatchGapReason, WatchHealth,
+};
 #[cfg(feature = "tasks")]
 pub use crate::units::Tasks;
 pub use crate::units::Units;
+#[cfg(feature = "config")]
+pub use crate::units::{Config, ReloadCoalescer};
 
 use std::sync::Arc;
 
@@ -127,7 +227,14 @@ pub struct UnitBus {
 #[derive(Debug)]
 struct Inner {
     opts: UnitBusOptions,
+    /// Which service manager `bus` talks to.
+    scope: ManagerScope,
+    /// Container `bus` reaches (see `UnitBus::connect_machine`); `None` for the local managers.
+    machine: Option<String>,
     bus: bus::Bus,
+    unit_locks: locks::UnitLocks,
+    #[cfg(feature = "observe")]
+    signals: observe::SignalRouter,
 }
 
 impl UnitBus {
@@ -138,15 +245,81 @@ impl UnitBus {
 
     /// Connect to the system D-Bus with custom options (timeouts, polling).
     pub async fn connect_system_with(opts: UnitBusOptions) -> Result<Self> {
-        let bus = bus::Bus::connect_system(&opts).await?;
+        Self::connect_scope(opts, ManagerScope::System).await
+    }
+
+    /// Connect to the calling user's service manager on the session D-Bus (`systemctl --user`).
+    ///
+    /// `Units`, `Manager`, `Tasks` and `Config` then act on per-user units; unit files and
+    /// drop-ins go to `~/.config/systemd/user` unless `UnitBusOptions.unit_dir` says otherwise.
+    /// Requires `DBUS_SESSION_BUS_ADDRESS` (or `XDG_RUNTIME_DIR/bus`), as in a login session or
+    /// a user service.
+    pub async fn connect_session() -> Result<Self> {
+        Self::connect_session_with(UnitBusOptions::default()).await
+    }
+
+    /// Connect to the user's service manager with custom options (timeouts, polling).
+    pub async fn connect_session_with(opts: UnitBusOptions) -> Result<Self> {
+        Self::connect_scope(opts, ManagerScope::User).await
+    }
+
+    async fn connect_scope(opts: UnitBusOptions, scope: ManagerScope) -> Result<Self> {
+        let bus = bus::Bus::connect(&opts, scope).await?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                opts,
+                scope,
+                machine: None,
+                bus,
+                unit_locks: locks::UnitLocks::default(),
+                #[cfg(feature = "observe")]
+                signals: observe::SignalRouter::default(),
+            }),
+        })
+    }
+
+    /// Connect to the system bus inside local container `name` (feature=`machines`), e.g. one
+    /// started by `Machines::run_container`.
+    ///
+    /// The container's `dbus-daemon` socket is reached through the root of its init process
+    /// (`/proc/<leader>/root/run/dbus/system_bus_socket`), which requires root on the host and a
+    /// container without user namespacing. Unit, manager and task APIs then act on the
+    /// container's systemd; journal and coredump APIs still read the host's files, and
+    /// `Manager::host_facts` is rejected.
+    #[cfg(feature = "machines")]
+    pub async fn connect_machine(&self, name: &str) -> Result<Self> {
+        let leader = self.machines().leader(name).await?;
+        let address = format!("unix:path=/proc/{leader}/root/run/dbus/system_bus_socket");
+        let opts = self.inner.opts.clone();
+        let bus = bus::Bus::connect_address(&address, &opts).await?;
         Ok(Self {
-            inner: Arc::new(Inner { opts, bus }),
+            inner: Arc::new(Inner {
+                opts,
+                scope: ManagerScope::System,
+                machine: Some(name.to_string()),
+                bus,
+                unit_locks: locks::UnitLocks::default(),
+                #[cfg(feature = "observe")]
+                signals: observe::SignalRouter::default(),
+            }),
         })
     }
 
-    /// Probe environment capabilities conservatively.
-    pub async fn capabilities(&self) -> Capabilities {
-        capabilities::probe(self).await
+    /// The service manager this connection controls.
+    pub fn scope(&self) -> ManagerScope {
+        self.inner.scope
+    }
+
+    /// Drop every cached property reply (`UnitBusOptions.property_cache`) so the next reads go
+    /// to systemd, e.g. after changing units through another connection or `systemctl`.
+    pub fn clear_property_cache(&self) {
+        self.inner.bus.invalidate_properties();
+    }
+
+    /// Access capability checks: `.await` it to probe environment capabilities conservatively,
+    /// or pre-flight a single polkit action with `CapabilityChecks::check_polkit`.
+    pub fn capabilities(&self) -> CapabilityChecks {
+        CapabilityChecks::new(self.clone())
     }
 
     /// Access unit/job control APIs.
@@ -159,6 +332,11 @@ impl UnitBus {
         Journal::new(self.inner.clone())
     }
 
+    /// Access coredump listing APIs (`systemd-coredump` records in journald).
+    pub fn coredumps(&self) -> Coredumps {
+        Coredumps::new(self.inner.clone())
+    }
+
     /// Access systemd manager/global APIs.
     pub fn manager(&self) -> Manager {
         Manager::new(self.inner.clone())
@@ -181,4 +359,10 @@ impl UnitBus {
     pub fn config(&self) -> Config {
         Config::new(self.inner.clone())
     }
+
+    /// Access local container APIs (feature=`machines`).
+    #[cfg(feature = "machines")]
+    pub fn machines(&self) -> Machines {
+        Machines::new(self.inner.clone())
+    }
 }
@@ -0,0 +1,232 @@
+use crate::fsutil::io_error;
+use crate::{Error, Result};
+
+use std::collections::HashMap;
+use std::fs::{File, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+type UnitMutex = futures_util::lock::Mutex<()>;
+
+/// Per-unit async mutexes of this process, present while a guard holds or awaits them.
+#[derive(Debug, Default)]
+pub(crate) struct UnitLocks {
+    units: Mutex<HashMap<String, Arc<UnitMutex>>>,
+}
+
+impl UnitLocks {
+    /// Forget the mutex of `unit` once nobody holds or awaits it.
+    fn prune(&self, unit: &str) {
+        let mut units = self.units.lock().unwrap_or_else(|e| e.into_inner());
+        if units
+            .get(unit)
+            .is_some_and(|mutex| Arc::strong_count(mutex) == 1)
+        {
+            units.remove(unit);
+        }
+    }
+}
+
+/// Advisory lock on a unit, released on drop. Holds nothing when `UnitBusOptions.unit_lock` is
+/// unset.
+#[derive(Debug)]
+pub(crate) struct UnitLockGuard<'a> {
+    locks: &'a UnitLocks,
+    unit: String,
+    local: Option<futures_util::lock::OwnedMutexGuard<()>>,
+    file: Option<File>,
+}
+
+impl UnitLockGuard<'_> {
+    /// Whether this guard is the lock of canonical `unit`.
+    pub(crate) fn covers(&self, unit: &str) -> bool {
+        self.unit == unit
+    }
+}
+
+impl Drop for UnitLockGuard<'_> {
+    fn drop(&mut self) {
+        // Closing the file releases the flock.
+        self.file = None;
+        if let Some(local) = self.local.take() {
+            drop(local);
+            self.locks.prune(&self.unit);
+        }
+    }
+}
+
+/// Take the advisory lock on `unit` (a no-op guard if `UnitBusOptions.unit_lock` is unset).
+///
+/// Within this process, callers queue on a per-unit async mutex; across processes, the holder
+/// also takes an exclusive `flock` on `<dir>/<unit>.lock`. The lock is not re-entrant: composite
+/// operations like `Units::ensure` take it once and pass the guard to their steps.
+pub(crate) async fn lock_unit<'a>(
+    inner: &'a crate::Inner,
+    unit: &str,
+) -> Result<UnitLockGuard<'a>> {
+    let locks = &inner.unit_locks;
+    let mut guard = UnitLockGuard {
+        locks,
+        unit: unit.to_string(),
+        local: None,
+        file: None,
+    };
+    let Some(opts) = inner.opts.unit_lock.as_ref() else {
+        return Ok(guard);
+    };
+    let deadline = Instant::now() + opts.timeout;
+    let timeout = || Error::Timeout {
+        action: "unit_lock",
+        timeout: opts.timeout,
+    };
+
+    let mutex = {
+        let mut units = locks.units.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(units.entry(unit.to_string()).or_default())
+    };
+    let local = futures_lite::future::or(async move { Some(mutex.lock_owned().await) }, async {
+        crate::runtime::sleep(opts.timeout).await;
+        None
+    })
+    .await;
+    let Some(local) = local else {
+        locks.prune(unit);
+        return Err(timeout());
+    };
+    guard.local = Some(local);
+
+    let (dir, scope, name) = (PathBuf::from(&opts.dir), inner.scope, unit.to_string());
+    let (path, file) = crate::runtime::unblock(move || {
+        let path = lock_path(&scope_dir(&dir, scope)?, &name);
+        let file = open_lock_file(&path)?;
+        Ok((path, file))
+    })
+    .await?;
+
+    loop {
+        if try_lock(&file, &path)? {
+            guard.file = Some(file);
+            return Ok(guard);
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(timeout());
+        }
+        crate::runtime::sleep(RETRY_INTERVAL.min(remaining)).await;
+    }
+}
+
+/// Open `path`, creating the directory and file if needed.
+fn open_lock_file(path: &Path) -> Result<File> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| io_error("create lock dir", dir, e))?;
+    }
+    File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(|e| io_error("open lock file", path, e))
+}
+
+/// Try to `flock` `file`; `false` if another process holds it.
+fn try_lock(file: &File, path: &Path) -> Result<bool> {
+    match file.try_lock() {
+        Ok(()) => Ok(true),
+        Err(TryLockError::WouldBlock) => Ok(false),
+        Err(TryLockError::Error(e)) => Err(io_error("lock", path, e)),
+    }
+}
+
+/// Lock directory for `scope`: `dir` itself for the system manager, `dir/user-<uid>` for a user
+/// manager, so the same unit name in different managers does not share a lock.
+fn scope_dir(dir: &Path, scope: crate::ManagerScope) -> Result<PathBuf> {
+    match scope {
+        crate::ManagerScope::User => {
+            use std::os::unix::fs::MetadataExt;
+            // `/proc/self` is owned by the process's effective uid.
+            let uid = std::fs::metadata("/proc/self")
+                .map_err(|e| io_error("stat", Path::new("/proc/self"), e))?
+                .uid();
+            Ok(dir.join(format!("user-{uid}")))
+        }
+        _ => Ok(dir.to_path_buf()),
+    }
+}
+
+fn lock_path(dir: &Path, unit: &str) -> PathBuf {
+    dir.join(format!("{unit}.lock"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn lock_file_excludes_other_holders() {
+        let dir = std::env::temp_dir().join(format!("unitbus-locks-{}", std::process::id()));
+        let path = lock_path(&dir, "app.service");
+        assert!(path.ends_with("app.service.lock"));
+
+        let held = open_lock_file(&path).unwrap();
+        assert!(try_lock(&held, &path).unwrap());
+        // A separate open file description conflicts, as it would in another process.
+        let other = open_lock_file(&path).unwrap();
+        assert!(!try_lock(&other, &path).unwrap());
+        drop(held);
+        assert!(try_lock(&other, &path).unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unit_mutex_is_pruned_once_released() {
+        let locks = UnitLocks::default();
+        let mutex = Arc::clone(
+            locks
+                .units
+                .lock()
+                .unwrap()
+                .entry("app.service".to_string())
+                .or_default(),
+        );
+        let local = futures_lite::future::block_on(Arc::clone(&mutex).lock_owned());
+        assert!(mutex.try_lock().is_none());
+        drop(mutex);
+        let guard = UnitLockGuard {
+            locks: &locks,
+            unit: "app.service".to_string(),
+            local: Some(local),
+            file: None,
+        };
+        assert!(guard.covers("app.service"));
+        drop(guard);
+        assert!(locks.units.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn user_scope_locks_live_in_a_per_uid_dir() {
+        let dir = Path::new("/run/unitbus/locks");
+        assert_eq!(
+            scope_dir(dir, crate::ManagerScope::System).unwrap(),
+            PathBuf::from("/run/unitbus/locks")
+        );
+        let user = scope_dir(dir, crate::ManagerScope::User).unwrap();
+        assert!(user.starts_with(dir) && user != dir);
+        assert!(
+            user.file_name()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("user-")
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use crate::types::machine::{
+    ContainerHandle, ContainerOptions, ImagePull, ImagePullEvent, ImageSpec, ImageVerify,
+};
+use crate::units::{JobInner, JobKind, duration_to_micros, owned_value};
+use crate::{Error, JobHandle, JobOutcome, Result, util};
+
+use futures_util::StreamExt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+
+/// Local containers registered with `systemd-machined` (feature=`machines`).
+#[derive(Clone, Debug)]
+pub struct Machines {
+    inner: Arc<crate::Inner>,
+}
+
+impl Machines {
+    pub(crate) fn new(inner: Arc<crate::Inner>) -> Self {
+        Self { inner }
+    }
+
+    /// Boot a container from `image` with `systemd-nspawn`, running as the transient service
+    /// `unitbus-nspawn-<machine>.service` (like `systemd-run systemd-nspawn --keep-unit ...`).
+    ///
+    /// Waits (bounded by `opts.timeout`) for `systemd-nspawn` to report readiness and for the
+    /// machine to show up in `systemd-machined`. A failed start is returned as
+    /// `Error::ProcessError`; the unit's journal has the `systemd-nspawn` output. Stop the unit
+    /// or call `terminate` to shut the container down; the transient unit is then
+    /// garbage-collected.
+    pub async fn run_container(
+        &self,
+        image: ImageSpec,
+        opts: ContainerOptions,
+    ) -> Result<ContainerHandle> {
+        let machine = crate::types::machine::container_machine_name(&image, &opts)?;
+        let argv = crate::types::machine::nspawn_argv(&image, &opts, &machine)?;
+        let unit = format!("unitbus-nspawn-{machine}.service");
+
+        crate::telemetry::audited(&self.inner, "run_container", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            let started = Instant::now();
+
+            let props = container_properties(&machine, &argv, opts.timeout)?;
+            let job_path = self
+                .inner
+                .bus
+                .start_transient_unit(&unit, "fail", props, Vec::new())
+                .await?;
+            crate::telemetry::record_job_path(job_path.as_str());
+
+            let job = JobHandle {
+                unit: unit.clone(),
+                job_path: job_path.to_string(),
+                inner: JobInner {
+                    root: self.inner.clone(),
+                    kind: JobKind::Start,
+                    invocation_before: None,
+                },
+            };
+            let failure = match job.wait(opts.timeout).await? {
+                JobOutcome::Success { .. } => None,
+                JobOutcome::Failed {
+                    unit_status,
+                    reason,
+                } => Some((unit_status.exec_main_status, format!("{reason:?}"))),
+                JobOutcome::Canceled { unit_status } => Some((
+                    unit_status.exec_main_status,
+                    "start job canceled".to_string(),
+                )),
+            };
+            if let Some((exit_code, reason)) = failure {
+                return Err(Error::ProcessError {
+                    command: argv.join(" "),
+                    exit_code,
+                    stderr: format!("{unit} did not start ({reason}); see its journal"),
+                });
+            }
+
+            let remaining = opts.timeout.saturating_sub(started.elapsed());
+            let leader = self.wait_registered(&machine, remaining).await?;
+            Ok(ContainerHandle {
+                machine,
+                unit: unit.clone(),
+                leader,
+            })
+        })
+        .await
+    }
+
+    /// Download a container image through `systemd-importd` (like `importctl pull-tar` /
+    /// `pull-raw`), verified as `verify` says.
+    ///
+    /// The local image name is derived from `url` the way `importctl` does (the file name
+    /// without archive, compression and image suffixes, see `ImagePull::local`) and passed to
+    /// importd explicitly; once `ImagePullEvent::Done` arrives it can be booted with
+    /// `ImageSpec::Machine`. Dropping the returned `ImagePull` does not stop the download; use
+    /// `ImagePull::cancel`.
+    pub async fn pull_image(&self, url: &str, verify: ImageVerify) -> Result<ImagePull> {
+        let method = crate::types::machine::pull_method(url)?;
+        let local = crate::types::machine::pull_local_name(url)?;
+        crate::telemetry::audited(&self.inner, "pull_image", None, async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(%url, %local, verify = verify.as_str(), method, "pull_image");
+
+            // Subscribe before starting, so the completion signal cannot be missed.
+            let proxy = self.inner.bus.import_proxy().await?;
+            let signals = proxy.receive_signal("TransferRemoved").await.map_err(|e| {
+                Error::BackendUnavailable {
+                    backend: "importd",
+                    detail: e.to_string(),
+                }
+            })?;
+
+            let (transfer_id, transfer_path) = self
+                .inner
+                .bus
+                .import_pull(method, url, &local, verify.as_str(), false)
+                .await?;
+
+            Ok(ImagePull {
+                inner: self.inner.clone(),
+                url: url.to_string(),
+                local,
+                transfer_id,
+                transfer_path: transfer_path.to_string(),
+                signals: Some(signals),
+                last_fraction: None,
+                finished: false,
+            })
+        })
+        .await
+    }
+
+    /// Host PID of the init process of the registered machine `name`.
+    pub async fn leader(&self, name: &str) -> Result<u32> {
+        util::validate_machine_name(name)?;
+        crate::telemetry::traced("machine_leader", None, async {
+            let path = self.inner.bus.get_machine(name).await?;
+            machine_leader(&self.inner, path.as_str(), name).await
+        })
+        .await
+    }
+
+    /// Kill all processes of machine `name` (`machinectl terminate`).
+    pub async fn terminate(&self, name: &str) -> Result<()> {
+        util::validate_machine_name(name)?;
+        crate::telemetry::audited(&self.inner, "terminate_machine", None, async {
+            self.inner.bus.terminate_machine(name).await
+        })
+        .await
+    }
+
+    /// Poll machined until `name` is registered; `Error::Timeout` after `timeout`.
+    async fn wait_registered(&self, name: &str, timeout: Duration) -> Result<u32> {
+        let deadline = Instant::now() + timeout;
+        let mut delay = self.inner.opts.job_poll_initial;
+        loop {
+            match self.inner.bus.get_machine(name).await {
+                Ok(path) => return machine_leader(&self.inner, path.as_str(), name).await,
+                Err(Error::DbusError { name: err, .. }) if err.ends_with(".NoSuchMachine") => {}
+                Err(e) => return Err(e),
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::Timeout {
+                    action: "machine_register",
+                    timeout,
+                });
+            }
+            crate::runtime::sleep(delay.min(deadline - now)).await;
+            delay = (delay * 2).min(self.inner.opts.job_poll_max);
+        }
+    }
+}
+
+impl ImagePull {
+    /// The importd transfer id (`importctl list-transfers`).
+    pub fn transfer_id(&self) -> u32 {
+        self.transfer_id
+    }
+
+    /// The local image name importd installs the download as.
+    pub fn local(&self) -> &str {
+        &self.local
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Next event of the download.
+    ///
+    /// Returns `Ok(None)` after `ImagePullEvent::Done`. A failed or canceled transfer is
+    /// returned as `Error::ProcessError` (the `systemd-importd` journal has the details).
+    pub async fn next(&mut self) -> Result<Option<ImagePullEvent>> {
+        loop {
+            if self.finished {
+                return Ok(None);
+            }
+            let Some(signals) = &mut self.signals else {
+                self.finished = true;
+                return Err(Error::BackendUnavailable {
+                    backend: "importd",
+                    detail: "signal stream closed before the transfer finished".to_string(),
+                });
+            };
+
+            let interval = self.inner.opts.job_poll_max;
+            let removed = futures_lite::future::or(async { Some(signals.next().await) }, async {
+                crate::runtime::sleep(interval).await;
+                None
+            })
+            .await;
+
+            match removed {
+                Some(Some(msg)) => {
+                    let Ok((id, _path, result)) =
+                        msg.body().deserialize::<(u32, OwnedObjectPath, String)>()
+                    else {
+                        continue;
+                    };
+                    if id != self.transfer_id {
+                        continue;
+                    }
+                    self.finished = true;
+                    if result == "done" {
+                        return Ok(Some(ImagePullEvent::Done {
+                            local: self.local.clone(),
+                        }));
+                    }
+                    return Err(Error::ProcessError {
+                        command: format!("systemd-pull {}", self.url),
+                        exit_code: None,
+                        stderr: format!(
+                            "import transfer {} {result}; see the systemd-importd journal",
+                            self.transfer_id
+                        ),
+                    });
+                }
+                Some(None) => self.signals = None,
+                None => {
+                    // The object disappears right before `TransferRemoved`; keep waiting.
+                    let Ok(progress) = self
+                        .inner
+                        .bus
+                        .get_transfer_property(&self.transfer_path, "Progress")
+                        .await
+                    else {
+                        continue;
+                    };
+                    let fraction = f64::try_from(progress).map_err(|e| Error::IoError {
+                        context: format!("decode Progress of import transfer: {e}"),
+                    })?;
+                    if self.last_fraction != Some(fraction) {
+                        self.last_fraction = Some(fraction);
+                        return Ok(Some(ImagePullEvent::Progress { fraction }));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wait for the download to finish, skipping progress events; returns the local image name.
+    pub async fn wait(mut self) -> Result<String> {
+        while let Some(event) = self.next().await? {
+            if let ImagePullEvent::Done { local } = event {
+                return Ok(local);
+            }
+        }
+        Ok(self.local)
+    }
+
+    /// Abort the download (`importctl cancel-transfer`); `next` then reports the cancellation.
+    pub async fn cancel(&self) -> Result<()> {
+        crate::telemetry::audited(&self.inner, "cancel_transfer", None, async {
+            self.inner.bus.cancel_transfer(self.transfer_id).await
+        })
+        .await
+    }
+}
+
+async fn machine_leader(inner: &crate::Inner, path: &str, name: &str) -> Result<u32> {
+    let leader = inner.bus.get_machine_property(path, "Leader").await?;
+    u32::try_from(leader).map_err(|e| Error::IoError {
+        context: format!("decode Leader property of machine {name}: {e}"),
+    })
+}
+
+/// Properties of the transient service running `systemd-nspawn`, modelled on
+/// `systemd-nspawn@.service`.
+fn container_properties(
+    machine: &str,
+    argv: &[String],
+    timeout: Duration,
+) -> Result<Vec<(String, OwnedValue)>> {
+    let exec = vec![(argv[0].clone(), argv.to_vec(), false)];
+    Ok(vec![
+        (
+            "Description".to_string(),
+            owned_value("Description", format!("Container {machine}"))?,
+        ),
+        // nspawn reports READY=1 once the container is up (forwarded from a booted init).
+        ("Type".to_string(), owned_value("Type", "notify")?),
+        ("ExecStart".to_string(), owned_value("ExecStart", exec)?),
+        ("KillMode".to_string(), owned_value("KillMode", "mixed")?),
+        ("Delegate".to_string(), owned_value("Delegate", true)?),
+        ("Slice".to_string(), owned_value("Slice", "machine.slice")?),
+        (
+            "TimeoutStartUSec".to_string(),
+            owned_value("TimeoutStartUSec", duration_to_micros(timeout))?,
+        ),
+    ])
+}
@@ -1,7 +1,28 @@
-use crate::{Error, ManagerInfo, Properties, Result, UnitListEntry, util};
+use crate::{
+    CgroupMode, Error, HostFacts, LogTarget, ManagerDumpReport, ManagerInfo, Priority, Properties,
+    Result, UnitInventoryEntry, UnitListEntry, UnitListQuery, UnitListStream, util,
+};
 
+use futures_lite::{AsyncWrite, AsyncWriteExt};
+use futures_util::StreamExt;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 
+/// One `ListUnitsByPatterns` call per unit type when `UnitListQuery.patterns` is empty.
+const UNIT_TYPE_PATTERNS: [&str; 11] = [
+    "*.service",
+    "*.socket",
+    "*.target",
+    "*.device",
+    "*.mount",
+    "*.automount",
+    "*.swap",
+    "*.timer",
+    "*.path",
+    "*.slice",
+    "*.scope",
+];
+
 /// systemd `Manager` APIs.
 #[derive(Clone, Debug)]
 pub struct Manager {
@@ -52,6 +73,105 @@ impl Manager {
         }
     }
 
+    /// List units whose names match any of the shell-style `patterns` (e.g. `"app-*.service"`),
+    /// keeping only `states` (load, active or sub state) unless it is empty.
+    ///
+    /// Uses `Manager.ListUnitsByPatterns` (systemd >= 230), so both filters run inside systemd
+    /// and only matching rows cross the bus.
+    pub async fn list_units_by_patterns(
+        &self,
+        patterns: &[&str],
+        states: &[&str],
+    ) -> Result<Vec<UnitListEntry>> {
+        if patterns.is_empty() {
+            return Err(Error::invalid_input("patterns must not be empty"));
+        }
+        validate_list_filters(patterns.iter().copied(), states.iter().copied())?;
+
+        let items = self
+            .inner
+            .bus
+            .list_units_by_patterns(states, patterns)
+            .await?;
+        Ok(items.into_iter().map(UnitListEntry::from_dbus).collect())
+    }
+
+    /// Stream units matching `query` instead of materializing them in one `ListUnits` reply.
+    ///
+    /// Each pattern (or, without patterns, each unit type) is fetched by its own
+    /// `ListUnitsByPatterns` call when the stream reaches it, so no single reply has to cover
+    /// every unit within `UnitBusOptions.dbus_call_timeout`. Units matched by several patterns are
+    /// yielded once. Entries reflect each call's moment, not one consistent snapshot.
+    pub fn list_units_stream(&self, query: UnitListQuery) -> Result<UnitListStream> {
+        validate_list_filters(
+            query.patterns.iter().map(String::as_str),
+            query.states.iter().map(String::as_str),
+        )?;
+
+        let run = UnitListRun {
+            manager: self.clone(),
+            shards: list_shards(&query.patterns),
+            states: query.states,
+            page: Vec::new().into_iter(),
+            seen: HashSet::new(),
+        };
+        Ok(UnitListStream {
+            stream: Box::pin(futures_util::stream::unfold(run, |mut run| async move {
+                let entry = run.next_entry().await?;
+                Some((entry, run))
+            })),
+        })
+    }
+
+    /// List every unit file and loaded unit with enablement, vendor preset and runtime state.
+    ///
+    /// Joins `ListUnitFiles` and `ListUnits` by unit name; the vendor preset is read from the
+    /// `UnitFilePreset` property of loaded units that have a unit file (best-effort: a failed
+    /// lookup leaves it unset). Entries are sorted by name.
+    pub async fn unit_inventory(&self) -> Result<Vec<UnitInventoryEntry>> {
+        const PRESET_LOOKUP_CONCURRENCY: usize = 8;
+
+        let files = self.inner.bus.list_unit_files().await?;
+        let units = self.list_units().await?;
+        let mut inventory = crate::types::manager::join_inventory(files, units);
+
+        let bus = &self.inner.bus;
+        let presets: Vec<(usize, Option<crate::VendorPreset>)> =
+            futures_util::stream::iter(inventory.iter().enumerate().filter_map(|(i, e)| {
+                let unit_path = e.unit_path.as_deref().filter(|_| e.path.is_some())?;
+                Some(async move {
+                    let preset = bus
+                        .get_property(unit_path, "org.freedesktop.systemd1.Unit", "UnitFilePreset")
+                        .await
+                        .ok()
+                        .and_then(|v| String::try_from(v).ok())
+                        .and_then(|s| crate::VendorPreset::parse(&s));
+                    (i, preset)
+                })
+            }))
+            .buffer_unordered(PRESET_LOOKUP_CONCURRENCY)
+            .collect()
+            .await;
+
+        for (i, preset) in presets {
+            if let Some(entry) = inventory.get_mut(i) {
+                entry.vendor_preset = preset;
+            }
+        }
+        Ok(inventory)
+    }
+
+    /// Reload the manager configuration (`org.freedesktop.systemd1.Manager.Reload`, like
+    /// `systemctl daemon-reload`), e.g. after `Units::enable` or `Units::mask`.
+    pub async fn daemon_reload(&self) -> Result<()> {
+        crate::telemetry::audited(&self.inner, "daemon_reload", None, async {
+            #[cfg(feature = "tracing")]
+            tracing::info!("daemon_reload");
+            self.inner.bus.daemon_reload().await
+        })
+        .await
+    }
+
     /// Fetch a snapshot of manager/global properties.
     pub async fn properties(&self) -> Result<Properties> {
         let props = self
@@ -65,6 +185,131 @@ impl Manager {
         Ok(Properties::from_dbus(props))
     }
 
+    /// The manager's current `LogLevel`.
+    pub async fn log_level(&self) -> Result<Priority> {
+        let level = self.properties().await?.get_string("LogLevel");
+        level.as_deref().unwrap_or_default().parse()
+    }
+
+    /// Change the manager's own log level at runtime (`systemd-analyze log-level`) and return
+    /// the previous one, so it can be restored after debugging. Not persisted across reboots.
+    pub async fn set_log_level(&self, level: Priority) -> Result<Priority> {
+        crate::telemetry::audited(&self.inner, "set_log_level", None, async {
+            let previous = self.log_level().await?;
+            self.set_manager_property("LogLevel", level.as_str())
+                .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(%previous, %level, "set_log_level");
+
+            Ok(previous)
+        })
+        .await
+    }
+
+    /// The manager's current `LogTarget`.
+    pub async fn log_target(&self) -> Result<LogTarget> {
+        let target = self.properties().await?.get_string("LogTarget");
+        Ok(LogTarget::parse(target.as_deref().unwrap_or_default()))
+    }
+
+    /// Change where the manager logs at runtime (`systemd-analyze log-target`) and return the
+    /// previous target. Not persisted across reboots.
+    pub async fn set_log_target(&self, target: LogTarget) -> Result<LogTarget> {
+        util::validate_no_control("log target", target.as_str())?;
+        if target.as_str().is_empty() {
+            return Err(Error::invalid_input("log target must not be empty"));
+        }
+
+        crate::telemetry::audited(&self.inner, "set_log_target", None, async {
+            let previous = self.log_target().await?;
+            self.set_manager_property("LogTarget", target.as_str())
+                .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                previous = previous.as_str(),
+                target = target.as_str(),
+                "set_log_target"
+            );
+
+            Ok(previous)
+        })
+        .await
+    }
+
+    async fn set_manager_property(&self, name: &str, value: &str) -> Result<()> {
+        self.inner
+            .bus
+            .set_property(
+                crate::bus::SYSTEMD_MANAGER_PATH,
+                crate::bus::SYSTEMD_MANAGER_INTERFACE,
+                name,
+                zbus::zvariant::Value::from(value),
+            )
+            .await
+    }
+
+    /// Check whether the host needs a reboot: marker files such as `/run/reboot-required`, and
+    /// a newer installed kernel (via `opts.kernel_check`). Loaded units with `NeedDaemonReload`
+    /// are listed alongside, since patching usually leaves both behind.
+    pub async fn reboot_required(
+        &self,
+        opts: crate::RebootCheckOptions,
+    ) -> Result<crate::RebootRequiredReport> {
+        const PROPERTY_CONCURRENCY: usize = 16;
+
+        crate::telemetry::traced("reboot_required", None, async {
+            let host_opts = opts.clone();
+            let reasons = crate::runtime::unblock(move || {
+                crate::types::reboot::host_reboot_reasons(&host_opts)
+            })
+            .await?;
+
+            let mut units_needing_daemon_reload = Vec::new();
+            if opts.check_units {
+                let units = self.list_units().await?;
+                let bus = &self.inner.bus;
+                let flags = futures_util::stream::iter(
+                    units
+                        .iter()
+                        .filter(|u| u.load_state == crate::LoadState::Loaded)
+                        .map(|u| async move {
+                            let need = bus
+                                .get_property(
+                                    &u.unit_path,
+                                    "org.freedesktop.systemd1.Unit",
+                                    "NeedDaemonReload",
+                                )
+                                .await
+                                .ok()
+                                .and_then(|v| bool::try_from(v).ok())
+                                .unwrap_or(false);
+                            need.then(|| u.name.clone())
+                        }),
+                )
+                .buffered(PROPERTY_CONCURRENCY)
+                .collect::<Vec<_>>()
+                .await;
+                units_needing_daemon_reload = flags.into_iter().flatten().collect();
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                reasons = reasons.len(),
+                units_needing_daemon_reload = units_needing_daemon_reload.len(),
+                "reboot_required done"
+            );
+
+            Ok(crate::RebootRequiredReport {
+                reboot_required: !reasons.is_empty(),
+                reasons,
+                units_needing_daemon_reload,
+            })
+        })
+        .await
+    }
+
     /// Fetch common manager/global information.
     pub async fn info(&self) -> Result<ManagerInfo> {
         let props = self.properties().await?;
@@ -74,4 +319,331 @@ impl Manager {
             virtualization: props.get_opt_string("Virtualization"),
         })
     }
+
+    /// Collect a host inventory snapshot: machine-id, hostname, virtualization, systemd
+    /// version/features, cgroup mode, boot time, timezone and the failed-unit count.
+    ///
+    /// Intended as the header agents attach to their reports. Only the manager property read can
+    /// fail; host files that are missing or unreadable leave their field `None`.
+    ///
+    /// The files are read from the local filesystem, so a connection from
+    /// `UnitBus::connect_machine` is rejected with `Error::InvalidInput` rather than mixing the
+    /// container's manager properties with the host's identity.
+    pub async fn host_facts(&self) -> Result<HostFacts> {
+        if let Some(machine) = &self.inner.machine {
+            return Err(Error::invalid_input(format!(
+                "host_facts reads local host files and cannot describe machine {machine}"
+            )));
+        }
+        crate::telemetry::traced("host_facts", None, async {
+            let props = self.properties().await?;
+            let mut facts = crate::runtime::unblock(|| Ok(read_host_files())).await?;
+            facts.virtualization = props.get_opt_string("Virtualization");
+            facts.systemd_version = props.get_opt_string("Version");
+            facts.systemd_features = props.get_opt_string("Features");
+            facts.failed_units = props.get_u32("NFailedUnits");
+            if let Some(us) = props.get_u64("KernelTimestamp").filter(|us| *us > 0) {
+                facts.boot_time =
+                    Some(std::time::UNIX_EPOCH + std::time::Duration::from_micros(us));
+            }
+            Ok(facts)
+        })
+        .await
+    }
+
+    /// Stream the systemd manager state dump (`systemd-analyze dump`) into `writer`.
+    ///
+    /// Uses `Manager.DumpByFileDescriptor` when available and falls back to `Manager.Dump`. The
+    /// dump can be several megabytes; at most `max_bytes` are written and the report indicates
+    /// whether the output was truncated.
+    pub async fn dump<W>(&self, writer: &mut W, max_bytes: u64) -> Result<ManagerDumpReport>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        if max_bytes == 0 {
+            return Err(Error::invalid_input("max_bytes must be > 0"));
+        }
+
+        #[cfg(unix)]
+        match self.inner.bus.dump_by_fd().await {
+            Ok(fd) => return copy_dump_fd(fd, writer, max_bytes).await,
+            Err(Error::DbusError { name, .. })
+                if name.contains("UnknownMethod")
+                    || name.contains("UnknownMember")
+                    || name.contains("NotSupported") => {}
+            Err(e) => return Err(e),
+        }
+
+        let text = self.inner.bus.dump().await?;
+        let mut report = ManagerDumpReport::default();
+        write_capped(writer, text.as_bytes(), max_bytes, &mut report).await?;
+        flush_dump(writer).await?;
+        Ok(report)
+    }
+}
+
+#[cfg(unix)]
+fn read_host_files() -> HostFacts {
+    let read = |path: &str| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let timezone = std::fs::read_link("/etc/localtime")
+        .ok()
+        .and_then(|target| timezone_from_localtime(&target.to_string_lossy()))
+        .or_else(|| read("/etc/timezone"));
+    let boot_time = read("/proc/stat")
+        .and_then(|stat| parse_btime(&stat))
+        .map(|secs| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs));
+    let controllers = |root: &str| {
+        std::path::Path::new(root)
+            .join("cgroup.controllers")
+            .is_file()
+    };
+    let cgroup_mode = if controllers("/sys/fs/cgroup") {
+        Some(CgroupMode::Unified)
+    } else if controllers("/sys/fs/cgroup/unified") {
+        Some(CgroupMode::Hybrid)
+    } else if std::path::Path::new("/sys/fs/cgroup/systemd").is_dir() {
+        Some(CgroupMode::Legacy)
+    } else {
+        None
+    };
+
+    HostFacts {
+        machine_id: read("/etc/machine-id"),
+        hostname: read("/proc/sys/kernel/hostname"),
+        cgroup_mode,
+        boot_time,
+        timezone,
+        ..HostFacts::default()
+    }
+}
+
+/// Zone name from an `/etc/localtime` link target such as `../usr/share/zoneinfo/Europe/Berlin`.
+fn timezone_from_localtime(target: &str) -> Option<String> {
+    let (_, zone) = target.split_once("zoneinfo/")?;
+    // Some distributions link into `zoneinfo/posix/` or `zoneinfo/right/`.
+    let zone = zone
+        .strip_prefix("posix/")
+        .or_else(|| zone.strip_prefix("right/"))
+        .unwrap_or(zone);
+    (!zone.is_empty()).then(|| zone.to_string())
+}
+
+/// `btime` (boot time in seconds since the epoch) from `/proc/stat`.
+fn parse_btime(stat: &str) -> Option<u64> {
+    stat.lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+async fn copy_dump_fd<W>(
+    fd: std::os::fd::OwnedFd,
+    writer: &mut W,
+    max_bytes: u64,
+) -> Result<ManagerDumpReport>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    use std::io::Read;
+
+    // The descriptor is a sealed memfd, so reads never block on I/O.
+    let mut file = std::fs::File::from(fd);
+    let mut report = ManagerDumpReport::default();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| Error::IoError {
+            context: format!("read manager dump: {e}"),
+        })?;
+        if n == 0 {
+            break;
+        }
+        if !write_capped(writer, &buf[..n], max_bytes, &mut report).await? {
+            break;
+        }
+    }
+    flush_dump(writer).await?;
+    Ok(report)
+}
+
+/// Write `chunk` while honoring `max_bytes`; returns `false` once the cap has been reached.
+async fn write_capped<W>(
+    writer: &mut W,
+    chunk: &[u8],
+    max_bytes: u64,
+    report: &mut ManagerDumpReport,
+) -> Result<bool>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let remaining = max_bytes.saturating_sub(report.bytes_written);
+    let take = usize::try_from(remaining)
+        .unwrap_or(usize::MAX)
+        .min(chunk.len());
+    writer
+        .write_all(&chunk[..take])
+        .await
+        .map_err(|e| Error::IoError {
+            context: format!("write manager dump: {e}"),
+        })?;
+    report.bytes_written = report.bytes_written.saturating_add(take as u64);
+    if take < chunk.len() {
+        report.truncated = true;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+async fn flush_dump<W>(writer: &mut W) -> Result<()>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    writer.flush().await.map_err(|e| Error::IoError {
+        context: format!("flush manager dump: {e}"),
+    })
+}
+
+struct UnitListRun {
+    manager: Manager,
+    shards: VecDeque<String>,
+    states: Vec<String>,
+    page: std::vec::IntoIter<crate::bus::ListUnitItem>,
+    seen: HashSet<String>,
+}
+
+impl UnitListRun {
+    async fn next_entry(&mut self) -> Option<Result<UnitListEntry>> {
+        loop {
+            if let Some(item) = self.page.next() {
+                if self.seen.insert(item.0.clone()) {
+                    return Some(Ok(UnitListEntry::from_dbus(item)));
+                }
+                continue;
+            }
+
+            let pattern = self.shards.pop_front()?;
+            let states: Vec<&str> = self.states.iter().map(String::as_str).collect();
+            match self
+                .manager
+                .inner
+                .bus
+                .list_units_by_patterns(&states, &[pattern.as_str()])
+                .await
+            {
+                Ok(items) => self.page = items.into_iter(),
+                Err(e) => {
+                    self.shards.clear();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl futures_util::Stream for UnitListStream {
+    type Item = Result<UnitListEntry>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+fn validate_list_filters<'a>(
+    patterns: impl Iterator<Item = &'a str>,
+    states: impl Iterator<Item = &'a str>,
+) -> Result<()> {
+    for p in patterns {
+        if p.trim().is_empty() {
+            return Err(Error::invalid_input("unit pattern must not be empty"));
+        }
+        util::validate_no_control("unit pattern", p)?;
+    }
+    for s in states {
+        util::validate_no_control("unit state filter", s)?;
+    }
+    Ok(())
+}
+
+/// Patterns to fetch one call at a time, in order and without duplicates.
+fn list_shards(patterns: &[String]) -> VecDeque<String> {
+    if patterns.is_empty() {
+        return UNIT_TYPE_PATTERNS.iter().map(|p| p.to_string()).collect();
+    }
+    let mut seen = HashSet::new();
+    patterns
+        .iter()
+        .filter(|p| seen.insert(p.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn list_shards_split_by_unit_type_or_dedup_patterns() {
+        let all = list_shards(&[]);
+        assert_eq!(all.len(), UNIT_TYPE_PATTERNS.len());
+        assert_eq!(all.front().map(String::as_str), Some("*.service"));
+
+        let query = UnitListQuery::new()
+            .with_pattern("app-*.service")
+            .with_pattern("db.service")
+            .with_pattern("app-*.service");
+        assert_eq!(
+            Vec::from(list_shards(&query.patterns)),
+            vec!["app-*.service".to_string(), "db.service".to_string()]
+        );
+        assert!(validate_list_filters([" "].into_iter(), std::iter::empty()).is_err());
+    }
+
+    #[test]
+    fn write_capped_truncates_at_max_bytes() {
+        futures_lite::future::block_on(async {
+            let mut out = Vec::<u8>::new();
+            let mut report = ManagerDumpReport::default();
+
+            assert!(
+                write_capped(&mut out, b"abc", 5, &mut report)
+                    .await
+                    .expect("ok")
+            );
+            assert!(
+                !write_capped(&mut out, b"defg", 5, &mut report)
+                    .await
+                    .expect("ok")
+            );
+            assert_eq!(out, b"abcde");
+            assert_eq!(report.bytes_written, 5);
+            assert!(report.truncated);
+        });
+    }
+
+    #[test]
+    fn host_file_parsers_extract_zone_and_btime() {
+        assert_eq!(
+            timezone_from_localtime("../usr/share/zoneinfo/Europe/Berlin").as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            timezone_from_localtime("/usr/share/zoneinfo/posix/UTC").as_deref(),
+            Some("UTC")
+        );
+        assert_eq!(timezone_from_localtime("/etc/localtime.custom"), None);
+
+        let stat = "cpu  1 2 3\nctxt 99\nbtime 1700000000\nprocesses 5\n";
+        assert_eq!(parse_btime(stat), Some(1_700_000_000));
+        assert_eq!(parse_btime("cpu 1\n"), None);
+    }
 }
@@ -0,0 +1,481 @@
+use crate::{ActiveState, Error, LoadState, Result, UnitListEntry};
+
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+
+const UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
+
+/// A change applied to a `UnitModel`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnitModelChange {
+    /// A unit was loaded by systemd (`UnitNew`).
+    Added { unit: UnitListEntry },
+    /// A unit was unloaded by systemd (`UnitRemoved`).
+    Removed { unit: UnitListEntry },
+    /// State or job information of a unit changed (`PropertiesChanged`, `JobNew`, `JobRemoved`).
+    Changed {
+        previous: Box<UnitListEntry>,
+        current: Box<UnitListEntry>,
+    },
+}
+
+/// Live, signal-driven model of all units known to systemd (feature=`observe`).
+///
+/// The model is hydrated once with `ListUnits` and then kept current from D-Bus signals
+/// (`UnitNew`, `UnitRemoved`, `JobNew`, `JobRemoved`, `PropertiesChanged`); no polling is involved.
+///
+/// The model is driven by calling `next()` in a loop; each call applies one change and returns
+/// it. Queries are synchronous and cheap. Use `view()` to share read-only access with other
+/// tasks/threads while one task drives the model.
+#[derive(Debug)]
+pub struct UnitModel {
+    inner: Arc<crate::Inner>,
+    view: UnitModelView,
+    stream: Option<zbus::MessageStream>,
+    manager: Option<crate::observe::ManagerSubscription>,
+}
+
+/// Read-only, cloneable handle to the state of a `UnitModel`.
+#[derive(Clone, Debug, Default)]
+pub struct UnitModelView {
+    state: Arc<RwLock<ModelState>>,
+}
+
+#[derive(Debug, Default)]
+struct ModelState {
+    units: HashMap<String, UnitListEntry>,
+    by_path: HashMap<String, String>,
+}
+
+impl UnitModel {
+    pub(crate) async fn start(inner: Arc<crate::Inner>) -> Result<Self> {
+        // Subscribe before listing so no change between the snapshot and the stream is lost.
+        let manager = inner.signals.subscribe_manager(&inner.bus).await?;
+        let stream = subscribe_systemd_signals(&inner.bus.connection()).await?;
+
+        let items = inner.bus.list_units().await?;
+        let mut state = ModelState::default();
+        for item in items {
+            state.insert(UnitListEntry::from_dbus(item));
+        }
+
+        Ok(Self {
+            inner,
+            view: UnitModelView {
+                state: Arc::new(RwLock::new(state)),
+            },
+            stream: Some(stream),
+            manager: Some(manager),
+        })
+    }
+
+    /// Cloneable read-only handle sharing this model's state.
+    pub fn view(&self) -> UnitModelView {
+        self.view.clone()
+    }
+
+    /// Current entry for `unit` (shorthand names are canonicalized).
+    pub fn status(&self, unit: &str) -> Option<UnitListEntry> {
+        self.view.status(unit)
+    }
+
+    /// All units currently in `ActiveState=failed`.
+    pub fn failed_units(&self) -> Vec<UnitListEntry> {
+        self.view.failed_units()
+    }
+
+    /// Snapshot of all units in the model.
+    pub fn units(&self) -> Vec<UnitListEntry> {
+        self.view.units()
+    }
+
+    /// Wait for the next signal that changes the model, apply it, and return the change.
+    ///
+    /// Returns `Ok(None)` when the signal stream ends or the model was closed.
+    pub async fn next(&mut self) -> Result<Option<UnitModelChange>> {
+        loop {
+            let Some(stream) = self.stream.as_mut() else {
+                return Ok(None);
+            };
+            let Some(msg) = stream.next().await else {
+                return Ok(None);
+            };
+            let msg = msg.map_err(|e| Error::IoError {
+                context: format!("unit model stream error: {e}"),
+            })?;
+
+            if let Some(change) = self.apply(msg).await? {
+                crate::instrument::watcher_event("unit_model");
+                return Ok(Some(change));
+            }
+        }
+    }
+
+    /// Stop following signals: drop the signal stream (removing its match rules) and send
+    /// `Unsubscribe` if this was the last subscriber. The last state stays queryable and `next()`
+    /// returns `Ok(None)` afterwards.
+    pub async fn close(&mut self) -> Result<()> {
+        self.stream = None;
+        match self.manager.take() {
+            Some(mut manager) => {
+                self.inner
+                    .signals
+                    .unsubscribe_manager(&self.inner.bus, &mut manager)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn apply(&self, msg: zbus::Message) -> Result<Option<UnitModelChange>> {
+        let header = msg.header();
+        let (Some(interface), Some(member)) = (header.interface(), header.member()) else {
+            return Ok(None);
+        };
+        let path = header.path().map(|p| p.to_string());
+
+        match (interface.as_str(), member.as_str()) {
+            (crate::bus::SYSTEMD_MANAGER_INTERFACE, "UnitNew") => {
+                let (name, unit_path): (String, OwnedObjectPath) = decode_body(&msg)?;
+                if self.view.read().units.contains_key(&name) {
+                    return Ok(None);
+                }
+                let props = match self
+                    .inner
+                    .bus
+                    .get_all_properties_uncached(unit_path.as_str(), UNIT_INTERFACE)
+                    .await
+                {
+                    Ok(p) => p,
+                    // Units can be loaded and garbage-collected again before we get to them.
+                    Err(Error::DbusError { name, .. }) if name.contains("UnknownObject") => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e),
+                };
+                let entry = entry_from_properties(&name, unit_path.as_str(), &props);
+                Ok(self.view.write().insert(entry))
+            }
+            (crate::bus::SYSTEMD_MANAGER_INTERFACE, "UnitRemoved") => {
+                let (name, _unit_path): (String, OwnedObjectPath) = decode_body(&msg)?;
+                Ok(self.view.write().remove(&name))
+            }
+            (crate::bus::SYSTEMD_MANAGER_INTERFACE, "JobNew") => {
+                let (id, job, unit): (u32, OwnedObjectPath, String) = decode_body(&msg)?;
+                Ok(self
+                    .view
+                    .write()
+                    .set_job(&unit, Some((id, job.to_string()))))
+            }
+            (crate::bus::SYSTEMD_MANAGER_INTERFACE, "JobRemoved") => {
+                let (_id, _job, unit, _result): (u32, OwnedObjectPath, String, String) =
+                    decode_body(&msg)?;
+                Ok(self.view.write().set_job(&unit, None))
+            }
+            (crate::bus::DBUS_PROPERTIES_INTERFACE, "PropertiesChanged") => {
+                let Some(path) = path else {
+                    return Ok(None);
+                };
+                let (iface, changed, _invalidated): (
+                    String,
+                    HashMap<String, OwnedValue>,
+                    Vec<String>,
+                ) = decode_body(&msg)?;
+                if iface != UNIT_INTERFACE {
+                    return Ok(None);
+                }
+                Ok(self.view.write().apply_properties(&path, &changed))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl UnitModelView {
+    /// Current entry for `unit` (shorthand names are canonicalized).
+    pub fn status(&self, unit: &str) -> Option<UnitListEntry> {
+        let unit = crate::util::canonicalize_unit_name(unit).ok()?;
+        self.read().units.get(&unit).cloned()
+    }
+
+    /// All units currently in `ActiveState=failed`.
+    pub fn failed_units(&self) -> Vec<UnitListEntry> {
+        let mut out: Vec<UnitListEntry> = self
+            .read()
+            .units
+            .values()
+            .filter(|u| u.active_state == ActiveState::Failed)
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+        out
+    }
+
+    /// Snapshot of all units in the model.
+    pub fn units(&self) -> Vec<UnitListEntry> {
+        let mut out: Vec<UnitListEntry> = self.read().units.values().cloned().collect();
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+        out
+    }
+
+    /// Number of units in the model.
+    pub fn len(&self) -> usize {
+        self.read().units.len()
+    }
+
+    /// Returns true if the model holds no units.
+    pub fn is_empty(&self) -> bool {
+        self.read().units.is_empty()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, ModelState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, ModelState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ModelState {
+    fn insert(&mut self, entry: UnitListEntry) -> Option<UnitModelChange> {
+        self.by_path
+            .insert(entry.unit_path.clone(), entry.name.clone());
+        match self.units.insert(entry.name.clone(), entry.clone()) {
+            Some(previous) if previous != entry => Some(UnitModelChange::Changed {
+                previous: Box::new(previous),
+                current: Box::new(entry),
+            }),
+            Some(_) => None,
+            None => Some(UnitModelChange::Added { unit: entry }),
+        }
+    }
+
+    fn remove(&mut self, name: &str) -> Option<UnitModelChange> {
+        let entry = self.units.remove(name)?;
+        self.by_path.remove(&entry.unit_path);
+        Some(UnitModelChange::Removed { unit: entry })
+    }
+
+    fn set_job(&mut self, unit: &str, job: Option<(u32, String)>) -> Option<UnitModelChange> {
+        let entry = self.units.get_mut(unit)?;
+        let previous = entry.clone();
+        match job {
+            Some((id, path)) => {
+                entry.job_id = Some(id);
+                entry.job_path = Some(path);
+            }
+            None => {
+                entry.job_id = None;
+                entry.job_type = None;
+                entry.job_path = None;
+            }
+        }
+        changed(previous, entry)
+    }
+
+    fn apply_properties(
+        &mut self,
+        path: &str,
+        props: &HashMap<String, OwnedValue>,
+    ) -> Option<UnitModelChange> {
+        let name = self.by_path.get(path)?;
+        let entry = self.units.get_mut(name)?;
+        let previous = entry.clone();
+
+        if let Some(v) = get_str(props, "LoadState") {
+            entry.load_state = LoadState::parse(v);
+        }
+        if let Some(v) = get_str(props, "ActiveState") {
+            entry.active_state = ActiveState::parse(v);
+        }
+        if let Some(v) = get_str(props, "SubState") {
+            entry.sub_state = non_empty(v);
+        }
+        if let Some(v) = get_str(props, "Description") {
+            entry.description = non_empty(v);
+        }
+        if let Some(v) = get_str(props, "Following") {
+            entry.followed = non_empty(v);
+        }
+        changed(previous, entry)
+    }
+}
+
+fn changed(previous: UnitListEntry, current: &UnitListEntry) -> Option<UnitModelChange> {
+    if previous == *current {
+        return None;
+    }
+    Some(UnitModelChange::Changed {
+        previous: Box::new(previous),
+        current: Box::new(current.clone()),
+    })
+}
+
+fn entry_from_properties(
+    name: &str,
+    unit_path: &str,
+    props: &HashMap<String, OwnedValue>,
+) -> UnitListEntry {
+    UnitListEntry {
+        name: name.to_string(),
+        description: get_str(props, "Description").and_then(non_empty),
+        load_state: get_str(props, "LoadState")
+            .map(LoadState::parse)
+            .unwrap_or_else(|| LoadState::Unknown("missing".to_string())),
+        active_state: get_str(props, "ActiveState")
+            .map(ActiveState::parse)
+            .unwrap_or_else(|| ActiveState::Unknown("missing".to_string())),
+        sub_state: get_str(props, "SubState").and_then(non_empty),
+        followed: get_str(props, "Following").and_then(non_empty),
+        unit_path: unit_path.to_string(),
+        job_id: None,
+        job_type: None,
+        job_path: None,
+    }
+}
+
+fn get_str<'a>(props: &'a HashMap<String, OwnedValue>, key: &str) -> Option<&'a str> {
+    props.get(key).and_then(|v| <&str>::try_from(v).ok())
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+fn decode_body<T>(msg: &zbus::Message) -> Result<T>
+where
+    T: for<'d> zbus::zvariant::DynamicDeserialize<'d>,
+{
+    msg.body().deserialize().map_err(|e| Error::DbusError {
+        name: "SignalDecode".to_string(),
+        message: e.to_string(),
+    })
+}
+
+async fn subscribe_systemd_signals(conn: &zbus::Connection) -> Result<zbus::MessageStream> {
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(crate::bus::SYSTEMD_DESTINATION)
+        .and_then(|b| b.path_namespace(crate::bus::SYSTEMD_MANAGER_PATH))
+        .map_err(|e| Error::IoError {
+            context: format!("unit model match rule error: {e}"),
+        })?
+        .build();
+
+    zbus::MessageStream::for_match_rule(rule, conn, Some(256))
+        .await
+        .map_err(|e| Error::IoError {
+            context: format!("unit model subscribe failed: {e}"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use zbus::zvariant::Value;
+
+    fn entry(name: &str, active: ActiveState) -> UnitListEntry {
+        UnitListEntry {
+            name: name.to_string(),
+            description: None,
+            load_state: LoadState::Loaded,
+            active_state: active,
+            sub_state: None,
+            followed: None,
+            unit_path: format!(
+                "/org/freedesktop/systemd1/unit/{}",
+                name.replace('.', "_2e")
+            ),
+            job_id: None,
+            job_type: None,
+            job_path: None,
+        }
+    }
+
+    fn owned_str(s: &str) -> OwnedValue {
+        OwnedValue::try_from(Value::from(s)).expect("owned string value")
+    }
+
+    #[test]
+    fn properties_changed_updates_entry_by_path() {
+        let mut state = ModelState::default();
+        let e = entry("nginx.service", ActiveState::Active);
+        let path = e.unit_path.clone();
+        assert!(matches!(
+            state.insert(e),
+            Some(UnitModelChange::Added { .. })
+        ));
+
+        let mut props = HashMap::new();
+        props.insert("ActiveState".to_string(), owned_str("failed"));
+        props.insert("SubState".to_string(), owned_str("failed"));
+
+        let change = state.apply_properties(&path, &props).expect("changed");
+        let UnitModelChange::Changed { previous, current } = change else {
+            panic!("unexpected change: {change:?}");
+        };
+        assert_eq!(previous.active_state, ActiveState::Active);
+        assert_eq!(current.active_state, ActiveState::Failed);
+        assert_eq!(current.sub_state.as_deref(), Some("failed"));
+
+        assert!(state.apply_properties(&path, &props).is_none());
+        assert!(state.apply_properties("/unknown", &props).is_none());
+    }
+
+    #[test]
+    fn job_signals_set_and_clear_job_fields() {
+        let mut state = ModelState::default();
+        state.insert(entry("a.service", ActiveState::Inactive));
+
+        let change = state
+            .set_job(
+                "a.service",
+                Some((7, "/org/freedesktop/systemd1/job/7".to_string())),
+            )
+            .expect("changed");
+        let UnitModelChange::Changed { current, .. } = change else {
+            panic!("unexpected change: {change:?}");
+        };
+        assert_eq!(current.job_id, Some(7));
+
+        state.set_job("a.service", None).expect("changed");
+        assert_eq!(state.units["a.service"].job_id, None);
+        assert!(state.set_job("missing.service", None).is_none());
+    }
+
+    #[test]
+    fn view_queries_failed_units_and_removal() {
+        let view = UnitModelView::default();
+        {
+            let mut state = view.write();
+            state.insert(entry("b.service", ActiveState::Failed));
+            state.insert(entry("a.service", ActiveState::Failed));
+            state.insert(entry("c.service", ActiveState::Active));
+        }
+
+        let failed: Vec<String> = view.failed_units().into_iter().map(|u| u.name).collect();
+        assert_eq!(failed, vec!["a.service", "b.service"]);
+        assert_eq!(
+            view.status("c").map(|u| u.active_state),
+            Some(ActiveState::Active)
+        );
+
+        let removed = view.write().remove("c.service").expect("removed");
+        assert!(matches!(removed, UnitModelChange::Removed { .. }));
+        assert!(view.status("c.service").is_none());
+        assert_eq!(view.len(), 2);
+    }
+}
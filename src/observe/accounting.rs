@@ -0,0 +1,314 @@
+use super::Observe;
+use crate::shutdown::ShutdownToken;
+use crate::{Error, Properties, Result, util};
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Alert levels for `Observe::watch_accounting`, in percent. `None` disables a metric.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Thresholds {
+    /// `MemoryCurrent` relative to the unit's memory limit (`EffectiveMemoryMax`, falling back to
+    /// `MemoryMax`). Units without a limit never cross it.
+    pub memory_max_pct: Option<f64>,
+    /// CPU time used per wall-clock time between two samples, like `CPUQuota=`: 100 is one full
+    /// CPU, 200 two.
+    pub cpu_pct: Option<f64>,
+    /// `TasksCurrent` relative to `EffectiveTasksMax` (falling back to `TasksMax`).
+    pub tasks_pct: Option<f64>,
+    /// A metric that exceeded its threshold is reported as recovered only once it falls to or
+    /// below `threshold - hysteresis_pct` (default: 5).
+    pub hysteresis_pct: f64,
+    /// How often the accounting properties are sampled (default: 5s).
+    pub interval: Duration,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            memory_max_pct: None,
+            cpu_pct: None,
+            tasks_pct: None,
+            hysteresis_pct: 5.0,
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Thresholds {
+    fn get(&self, metric: AccountingMetric) -> Option<f64> {
+        match metric {
+            AccountingMetric::Memory => self.memory_max_pct,
+            AccountingMetric::Cpu => self.cpu_pct,
+            AccountingMetric::Tasks => self.tasks_pct,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.interval.is_zero() {
+            return Err(Error::invalid_input("interval must be > 0"));
+        }
+        if AccountingMetric::ALL.iter().all(|m| self.get(*m).is_none()) {
+            return Err(Error::invalid_input("at least one threshold is required"));
+        }
+        for metric in AccountingMetric::ALL {
+            let Some(pct) = self.get(metric) else {
+                continue;
+            };
+            let capped = metric != AccountingMetric::Cpu;
+            if !(pct.is_finite() && pct > 0.0) || (capped && pct > 100.0) {
+                return Err(Error::invalid_input(format!(
+                    "{} threshold out of range: {pct}",
+                    metric.as_str()
+                )));
+            }
+        }
+        if !(self.hysteresis_pct.is_finite() && self.hysteresis_pct >= 0.0) {
+            return Err(Error::invalid_input("hysteresis_pct must be >= 0"));
+        }
+        Ok(())
+    }
+}
+
+/// Accounting value checked by `Observe::watch_accounting`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AccountingMetric {
+    Memory,
+    Cpu,
+    Tasks,
+}
+
+impl AccountingMetric {
+    const ALL: [AccountingMetric; 3] = [Self::Memory, Self::Cpu, Self::Tasks];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountingMetric::Memory => "memory",
+            AccountingMetric::Cpu => "cpu",
+            AccountingMetric::Tasks => "tasks",
+        }
+    }
+}
+
+/// A threshold was crossed: the metric rose above it (`exceeded`) or recovered below the
+/// hysteresis band.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct AccountingEvent {
+    pub unit: String,
+    pub metric: AccountingMetric,
+    /// Observed value in percent.
+    pub percent: f64,
+    /// Configured threshold in percent.
+    pub threshold: f64,
+    pub exceeded: bool,
+}
+
+/// Watcher that samples a unit's accounting properties and yields `AccountingEvent` on
+/// threshold crossings.
+///
+/// Metrics systemd does not report for the unit (accounting disabled, no limit, unit stopped)
+/// are skipped, and their threshold state carries over until the next usable sample.
+#[derive(Debug)]
+pub struct AccountingWatcher {
+    units: crate::Units,
+    unit: String,
+    thresholds: Thresholds,
+    exceeded: [bool; 3],
+    last_cpu: Option<(u64, Instant)>,
+    pending: VecDeque<AccountingEvent>,
+    sampled: bool,
+    closed: bool,
+    shutdown: Option<ShutdownToken>,
+}
+
+impl Observe {
+    /// Watch `unit`'s resource accounting (`MemoryCurrent`, `CPUUsageNSec`, `TasksCurrent`)
+    /// against `thresholds`.
+    ///
+    /// Every metric starts as not exceeded, so one already above its level is reported by the
+    /// first usable sample (the CPU rate needs two samples).
+    pub async fn watch_accounting(
+        &self,
+        unit: &str,
+        thresholds: Thresholds,
+    ) -> Result<AccountingWatcher> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        thresholds.validate()?;
+
+        Ok(AccountingWatcher {
+            units: crate::Units::new(self.inner.clone()),
+            unit,
+            thresholds,
+            exceeded: [false; 3],
+            last_cpu: None,
+            pending: VecDeque::new(),
+            sampled: false,
+            closed: false,
+            shutdown: None,
+        })
+    }
+}
+
+impl AccountingWatcher {
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Stop polling. This watcher holds no D-Bus subscription, so closing only makes `next()`
+    /// return `Ok(None)` from now on.
+    pub async fn close(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(())
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` returns `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Wait for the next threshold crossing. Returns `Ok(None)` only after `close()` or once the
+    /// `with_shutdown` token fires; otherwise the watcher runs until dropped or a sample fails.
+    pub async fn next(&mut self) -> Result<Option<AccountingEvent>> {
+        let shutdown = self.shutdown.clone();
+        crate::shutdown::race(shutdown.as_ref(), self.recv_event())
+            .await
+            .unwrap_or(Ok(None))
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<AccountingEvent>> {
+        if self.closed {
+            return Ok(None);
+        }
+        loop {
+            if let Some(ev) = self.pending.pop_front() {
+                crate::instrument::watcher_event("accounting");
+                return Ok(Some(ev));
+            }
+            if self.sampled {
+                crate::runtime::sleep(self.thresholds.interval).await;
+            }
+            self.sampled = true;
+
+            let props = self.units.accounting_properties(&self.unit).await?;
+            let now = Instant::now();
+            let cpu = match (self.last_cpu, unset(props.get_u64("CPUUsageNSec"))) {
+                (Some(prev), Some(nsec)) => cpu_percent(prev, (nsec, now)),
+                _ => None,
+            };
+            self.last_cpu = unset(props.get_u64("CPUUsageNSec")).map(|nsec| (nsec, now));
+
+            let values = [
+                ratio(
+                    props.get_u64("MemoryCurrent"),
+                    limit(&props, "EffectiveMemoryMax", "MemoryMax"),
+                ),
+                cpu,
+                ratio(
+                    props.get_u64("TasksCurrent"),
+                    limit(&props, "EffectiveTasksMax", "TasksMax"),
+                ),
+            ];
+            for (i, metric) in AccountingMetric::ALL.into_iter().enumerate() {
+                let (Some(threshold), Some(percent), Some(state)) = (
+                    self.thresholds.get(metric),
+                    values.get(i).copied().flatten(),
+                    self.exceeded.get_mut(i),
+                ) else {
+                    continue;
+                };
+                if crossed(state, percent, threshold, self.thresholds.hysteresis_pct) {
+                    self.pending.push_back(AccountingEvent {
+                        unit: self.unit.clone(),
+                        metric,
+                        percent,
+                        threshold,
+                        exceeded: *state,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// systemd reports `u64::MAX` for values that are not set (no accounting, no limit).
+fn unset(value: Option<u64>) -> Option<u64> {
+    value.filter(|v| *v != u64::MAX)
+}
+
+/// `effective` (systemd 256+, includes parent slice limits) or `fallback`.
+fn limit(props: &Properties, effective: &str, fallback: &str) -> Option<u64> {
+    unset(props.get_u64(effective)).or_else(|| unset(props.get_u64(fallback)))
+}
+
+fn ratio(current: Option<u64>, max: Option<u64>) -> Option<f64> {
+    let (current, max) = (unset(current)?, max.filter(|m| *m > 0)?);
+    Some(current as f64 * 100.0 / max as f64)
+}
+
+fn cpu_percent(prev: (u64, Instant), now: (u64, Instant)) -> Option<f64> {
+    let wall = now.1.checked_duration_since(prev.1)?.as_nanos();
+    let used = now.0.checked_sub(prev.0)?;
+    (wall > 0).then(|| used as f64 * 100.0 / wall as f64)
+}
+
+/// Update `exceeded` for one metric; returns true if it flipped.
+fn crossed(exceeded: &mut bool, percent: f64, threshold: f64, hysteresis: f64) -> bool {
+    let now = if *exceeded {
+        percent > threshold - hysteresis
+    } else {
+        percent > threshold
+    };
+    std::mem::replace(exceeded, now) != now
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn crossings_use_hysteresis_band() {
+        let mut exceeded = false;
+        let mut flips = Vec::new();
+        for pct in [50.0, 81.0, 79.0, 76.0, 75.0, 79.0, 80.5] {
+            if crossed(&mut exceeded, pct, 80.0, 5.0) {
+                flips.push((pct, exceeded));
+            }
+        }
+        assert_eq!(flips, vec![(81.0, true), (75.0, false), (80.5, true)]);
+    }
+
+    #[test]
+    fn percentages_skip_unset_values() {
+        assert_eq!(ratio(Some(256), Some(1024)), Some(25.0));
+        assert_eq!(ratio(Some(256), None), None);
+        assert_eq!(ratio(Some(u64::MAX), Some(1024)), None);
+
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_secs(2);
+        assert_eq!(cpu_percent((0, t0), (3_000_000_000, t1)), Some(150.0));
+        assert_eq!(cpu_percent((10, t0), (5, t1)), None);
+    }
+
+    #[test]
+    fn thresholds_require_one_metric_in_range() {
+        assert!(Thresholds::default().validate().is_err());
+        let t = Thresholds {
+            cpu_pct: Some(250.0),
+            ..Thresholds::default()
+        };
+        assert!(t.validate().is_ok());
+        let t = Thresholds {
+            memory_max_pct: Some(120.0),
+            ..Thresholds::default()
+        };
+        assert!(t.validate().is_err());
+    }
+}
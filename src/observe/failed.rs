@@ -0,0 +1,218 @@
+use super::{Observe, UNIT_INTERFACE};
+use crate::{Diagnosis, DiagnosisOptions, Result, UnitListEntry};
+
+use futures_util::StreamExt;
+use std::future::Future;
+use std::time::SystemTime;
+
+/// Options for `Observe::failed_report`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct FailedReportOptions {
+    /// Maximum number of diagnoses gathered concurrently (default: 4).
+    pub concurrency: usize,
+    /// Maximum number of failed units to diagnose (default: 32); further units are listed
+    /// without a diagnosis.
+    pub max_diagnosed: usize,
+    /// Per-unit journal bounds. The window is anchored at the time each unit failed.
+    pub diagnosis: DiagnosisOptions,
+}
+
+impl Default for FailedReportOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            max_diagnosed: 32,
+            diagnosis: DiagnosisOptions {
+                limit: 50,
+                max_bytes: 64 * 1024,
+                max_message_bytes: 2 * 1024,
+                ..DiagnosisOptions::default()
+            },
+        }
+    }
+}
+
+/// One failed unit in a `FailedReport`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FailedUnitReport {
+    pub unit: UnitListEntry,
+    /// When the unit entered the failed state (`InactiveEnterTimestamp`), if known.
+    pub failed_at: Option<SystemTime>,
+    /// `None` when the unit was beyond `max_diagnosed`.
+    pub diagnosis: Option<Result<Diagnosis>>,
+}
+
+/// Result of `Observe::failed_report`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FailedReport {
+    pub generated_at: SystemTime,
+    /// Failed units, sorted by name.
+    pub units: Vec<FailedUnitReport>,
+}
+
+impl Observe {
+    /// List every currently failed unit and gather a bounded `Diagnosis` for each.
+    ///
+    /// Diagnoses run with at most `opts.concurrency` in flight; a per-unit error is recorded in
+    /// that unit's entry instead of failing the whole report.
+    pub async fn failed_report(&self, opts: FailedReportOptions) -> Result<FailedReport> {
+        if opts.concurrency == 0 {
+            return Err(crate::Error::invalid_input("concurrency must be > 0"));
+        }
+
+        crate::telemetry::traced("failed_report", None, async {
+            let generated_at = SystemTime::now();
+            let failed = crate::Manager::new(self.inner.clone())
+                .list_units_filtered(&["failed"])
+                .await?;
+
+            let journal = crate::Journal::new(self.inner.clone());
+            let journal = &journal;
+            let bus = &self.inner.bus;
+            let diagnosis = &opts.diagnosis;
+            let units = unit_reports(
+                failed,
+                &opts,
+                generated_at,
+                |unit_path| async move { failed_at(bus, &unit_path).await },
+                |unit, anchor| async move { journal.diagnose_at(&unit, anchor, diagnosis).await },
+            )
+            .await;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(failed = units.len(), "failed_report done");
+
+            Ok(FailedReport {
+                generated_at,
+                units,
+            })
+        })
+        .await
+    }
+}
+
+/// Sort `failed` by name and build its entries, with at most `opts.concurrency` in flight. Only
+/// the first `opts.max_diagnosed` units are diagnosed, anchored at their failure time or, if it
+/// is unknown, at `generated_at`.
+async fn unit_reports<FA, FD>(
+    mut failed: Vec<UnitListEntry>,
+    opts: &FailedReportOptions,
+    generated_at: SystemTime,
+    failed_at: impl Fn(String) -> FA,
+    diagnose: impl Fn(String, SystemTime) -> FD,
+) -> Vec<FailedUnitReport>
+where
+    FA: Future<Output = Option<SystemTime>>,
+    FD: Future<Output = Result<Diagnosis>>,
+{
+    failed.sort_by(|a, b| a.name.cmp(&b.name));
+    let (failed_at, diagnose) = (&failed_at, &diagnose);
+    futures_util::stream::iter(failed.into_iter().enumerate().map(|(i, unit)| async move {
+        let failed_at = failed_at(unit.unit_path.clone()).await;
+        let diagnosis = if i < opts.max_diagnosed {
+            let anchor = failed_at.unwrap_or(generated_at);
+            Some(diagnose(unit.name.clone(), anchor).await)
+        } else {
+            None
+        };
+        FailedUnitReport {
+            unit,
+            failed_at,
+            diagnosis,
+        }
+    }))
+    .buffered(opts.concurrency)
+    .collect()
+    .await
+}
+
+async fn failed_at(bus: &crate::bus::Bus, unit_path: &str) -> Option<SystemTime> {
+    let value = bus
+        .get_property(unit_path, UNIT_INTERFACE, "InactiveEnterTimestamp")
+        .await
+        .ok()?;
+    u64::try_from(value)
+        .ok()
+        .filter(|us| *us != 0)
+        .map(crate::util::system_time_from_unix_micros)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{ActiveState, Error, LoadState};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn failed_unit(name: &str) -> UnitListEntry {
+        UnitListEntry {
+            name: name.to_string(),
+            description: None,
+            load_state: LoadState::Loaded,
+            active_state: ActiveState::Failed,
+            sub_state: Some("failed".to_string()),
+            followed: None,
+            unit_path: format!("/unit/{name}"),
+            job_id: None,
+            job_type: None,
+            job_path: None,
+        }
+    }
+
+    #[test]
+    fn unit_reports_sort_cap_and_anchor_diagnoses() {
+        let generated_at = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let crashed_at = SystemTime::UNIX_EPOCH + Duration::from_secs(40);
+        let opts = FailedReportOptions {
+            concurrency: 2,
+            max_diagnosed: 2,
+            ..FailedReportOptions::default()
+        };
+        let anchors = Mutex::new(Vec::new());
+
+        let units = futures_lite::future::block_on(unit_reports(
+            vec![
+                failed_unit("web.service"),
+                failed_unit("db.service"),
+                failed_unit("app.service"),
+            ],
+            &opts,
+            generated_at,
+            |unit_path| async move { (unit_path == "/unit/app.service").then_some(crashed_at) },
+            |unit, anchor| {
+                anchors.lock().unwrap().push((unit.clone(), anchor));
+                async move { Err(Error::invalid_input(format!("no journal for {unit}"))) }
+            },
+        ));
+
+        let names: Vec<_> = units.iter().map(|u| u.unit.name.as_str()).collect();
+        assert_eq!(names, ["app.service", "db.service", "web.service"]);
+        assert_eq!(units[0].failed_at, Some(crashed_at));
+        assert_eq!(units[1].failed_at, None);
+
+        // Per-unit errors are kept in the entry; units past `max_diagnosed` get no diagnosis.
+        assert!(matches!(
+            units[0].diagnosis,
+            Some(Err(Error::InvalidInput { .. }))
+        ));
+        assert!(units[1].diagnosis.is_some());
+        assert!(units[2].diagnosis.is_none());
+
+        let mut anchors = anchors.into_inner().unwrap();
+        anchors.sort();
+        assert_eq!(
+            anchors,
+            [
+                ("app.service".to_string(), crashed_at),
+                ("db.service".to_string(), generated_at),
+            ]
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use super::router::{ManagerSubscription, PathFilter, Subscription};
+use super::{
+    Heartbeat, Observe, Recv, UNIT_INTERFACE, UNIT_PATH_PREFIX, WatchEvent, active_state,
+    close_subscriptions, recv,
+};
+use crate::Result;
+use crate::shutdown::ShutdownToken;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Manager `SystemState` change (e.g. `running` → `degraded`) or change of the failed unit set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SystemHealthEvent {
+    /// `SystemState` before this event.
+    pub previous_state: String,
+    /// Current `SystemState` (`running`, `degraded`, `starting`, ...).
+    pub state: String,
+    /// Units that entered the failed state since the previous event (sorted).
+    pub entered_failed: Vec<String>,
+    /// Units that left the failed state since the previous event (sorted).
+    pub left_failed: Vec<String>,
+    /// All currently failed units (sorted).
+    pub failed: Vec<String>,
+}
+
+impl SystemHealthEvent {
+    /// `true` if `SystemState` itself changed (not only the failed set).
+    pub fn state_changed(&self) -> bool {
+        self.previous_state != self.state
+    }
+}
+
+/// Watcher that yields `SystemHealthEvent` as the system degrades or recovers.
+///
+/// Failed-set changes are detected from unit signals. `SystemState` transitions that no unit
+/// signal accompanies (e.g. `starting` → `running`) are only picked up on heartbeat ticks, so
+/// enable `with_heartbeat` to see them.
+#[derive(Debug)]
+pub struct SystemHealthWatcher {
+    inner: Arc<crate::Inner>,
+    stream: Subscription,
+    manager: Option<ManagerSubscription>,
+    heartbeat: Option<Heartbeat>,
+    shutdown: Option<ShutdownToken>,
+    state: String,
+    /// Failed units by name, with their object paths.
+    failed: BTreeMap<String, String>,
+    /// Resync result held back while a gap report is delivered first.
+    pending: Option<SystemHealthEvent>,
+}
+
+impl Observe {
+    /// Watch the manager's `SystemState` and the set of failed units.
+    ///
+    /// The current state is read when the watcher is created; events report changes from there.
+    pub async fn watch_system_health(&self) -> Result<SystemHealthWatcher> {
+        // systemd only broadcasts unit signals while at least one client is subscribed.
+        let manager = self
+            .inner
+            .signals
+            .subscribe_manager(&self.inner.bus)
+            .await?;
+
+        let stream = self
+            .subscribe_properties_changed(
+                PathFilter::Namespace(UNIT_PATH_PREFIX.to_string()),
+                UNIT_INTERFACE,
+            )
+            .await?;
+        let (state, failed) = snapshot(&self.inner).await?;
+
+        Ok(SystemHealthWatcher {
+            inner: self.inner.clone(),
+            stream,
+            manager: Some(manager),
+            heartbeat: None,
+            shutdown: None,
+            state,
+            failed,
+            pending: None,
+        })
+    }
+}
+
+impl SystemHealthWatcher {
+    /// Current `SystemState` as last observed.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Stop watching: leave the shared signal stream (its match rule goes away with the last
+    /// watcher) and send `Unsubscribe` if this was the last watcher holding a subscription.
+    /// `next()` returns `Ok(None)` afterwards. Dropping the watcher releases the same resources
+    /// but cannot send `Unsubscribe`.
+    pub async fn close(&mut self) -> Result<()> {
+        self.heartbeat = None;
+        close_subscriptions(&self.inner, &mut self.stream, self.manager.take()).await
+    }
+
+    /// Re-read the full state and emit `WatchHealth` every `interval` from `next_event()` (a zero
+    /// interval disables it).
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = Heartbeat::new(interval);
+        self
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` and `next_event()` return `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub async fn next(&mut self) -> Result<Option<SystemHealthEvent>> {
+        loop {
+            match self.next_event().await? {
+                None => return Ok(None),
+                Some(WatchEvent::Event(ev)) => return Ok(Some(ev)),
+                Some(WatchEvent::Health(_)) => continue,
+            }
+        }
+    }
+
+    /// Like `next()`, but also yields heartbeat/gap reports.
+    pub async fn next_event(&mut self) -> Result<Option<WatchEvent<SystemHealthEvent>>> {
+        let shutdown = self.shutdown.clone();
+        let Some(ev) = crate::shutdown::race(shutdown.as_ref(), self.recv_event()).await else {
+            return Ok(None);
+        };
+        let ev = ev?;
+        if matches!(ev, Some(WatchEvent::Event(_))) {
+            crate::instrument::watcher_event("system_health");
+        }
+        Ok(ev)
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<WatchEvent<SystemHealthEvent>>> {
+        if let Some(ev) = self.pending.take() {
+            return Ok(Some(WatchEvent::Event(ev)));
+        }
+        loop {
+            match recv(&mut self.stream, &mut self.heartbeat).await {
+                Recv::End => return Ok(None),
+                Recv::Gap(health) => {
+                    // Signals were dropped: report the gap, then the delta of a resync so the
+                    // next event is still accurate.
+                    self.pending = self.resync().await?;
+                    return Ok(Some(WatchEvent::Health(health)));
+                }
+                Recv::Tick { late_by } => {
+                    if let Some(ev) = self.resync().await? {
+                        return Ok(Some(WatchEvent::Event(ev)));
+                    }
+                    let Some(hb) = &self.heartbeat else {
+                        continue;
+                    };
+                    return Ok(Some(WatchEvent::Health(hb.health(late_by, true))));
+                }
+                Recv::Signal(signal) => {
+                    let Some(state) = active_state(&signal.changed) else {
+                        continue;
+                    };
+                    let was_failed = self.failed.values().any(|p| *p == signal.path);
+                    if (state == "failed") == was_failed {
+                        continue;
+                    }
+                    if let Some(ev) = self.resync().await? {
+                        return Ok(Some(WatchEvent::Event(ev)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Re-read `SystemState` and the failed set; returns an event if either changed.
+    async fn resync(&mut self) -> Result<Option<SystemHealthEvent>> {
+        let (state, failed) = snapshot(&self.inner).await?;
+        let (entered_failed, left_failed) = failed_delta(&self.failed, &failed);
+        if state == self.state && entered_failed.is_empty() && left_failed.is_empty() {
+            return Ok(None);
+        }
+
+        let previous_state = std::mem::replace(&mut self.state, state.clone());
+        self.failed = failed;
+        Ok(Some(SystemHealthEvent {
+            previous_state,
+            state,
+            entered_failed,
+            left_failed,
+            failed: self.failed.keys().cloned().collect(),
+        }))
+    }
+}
+
+async fn snapshot(inner: &Arc<crate::Inner>) -> Result<(String, BTreeMap<String, String>)> {
+    let state = crate::Manager::new(inner.clone())
+        .info()
+        .await?
+        .system_state
+        .unwrap_or_default();
+    let failed = crate::Manager::new(inner.clone())
+        .list_units_filtered(&["failed"])
+        .await?
+        .into_iter()
+        .map(|u| (u.name, u.unit_path))
+        .collect();
+    Ok((state, failed))
+}
+
+/// Units that entered and left the failed set between `old` and `new`.
+fn failed_delta(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> (Vec<String>, Vec<String>) {
+    let entered = new
+        .keys()
+        .filter(|u| !old.contains_key(*u))
+        .cloned()
+        .collect();
+    let left = old
+        .keys()
+        .filter(|u| !new.contains_key(*u))
+        .cloned()
+        .collect();
+    (entered, left)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn set(units: &[&str]) -> BTreeMap<String, String> {
+        units
+            .iter()
+            .map(|u| (u.to_string(), format!("/unit/{u}")))
+            .collect()
+    }
+
+    #[test]
+    fn failed_delta_reports_entered_and_left_units() {
+        let (entered, left) = failed_delta(
+            &set(&["a.service", "b.service"]),
+            &set(&["b.service", "c.service"]),
+        );
+        assert_eq!(entered, vec!["c.service"]);
+        assert_eq!(left, vec!["a.service"]);
+
+        let (entered, left) = failed_delta(&set(&["a.service"]), &set(&["a.service"]));
+        assert!(entered.is_empty() && left.is_empty());
+    }
+}
@@ -0,0 +1,338 @@
+use super::Observe;
+use crate::shutdown::ShutdownToken;
+use crate::{Error, JournalFilter, Result, util};
+
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// Options for `Observe::watch_log_errors`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LogWatchOptions {
+    /// Entries with `PRIORITY` at or below this value are counted (default: 3, `err`).
+    pub min_priority: u8,
+    /// Errors per second over `window` above which the unit is reported (default: 1.0).
+    pub rate_threshold: f64,
+    /// Sliding window the rate is computed over (default: 60s).
+    pub window: Duration,
+    /// How often the journal is polled for new entries (default: 2s).
+    pub poll_interval: Duration,
+    /// Maximum entries read per poll (default: 10,000). A busier unit is not worked through
+    /// entry by entry: the watcher skips to the newest entries, rebuilds the rate from (at most)
+    /// this many of them, and sets `LogRateEvent.saturated`.
+    pub max_entries_per_poll: u32,
+}
+
+impl Default for LogWatchOptions {
+    fn default() -> Self {
+        Self {
+            min_priority: 3,
+            rate_threshold: 1.0,
+            window: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(2),
+            max_entries_per_poll: 10_000,
+        }
+    }
+}
+
+/// The unit's error rate crossed `LogWatchOptions.rate_threshold` (up when `exceeded`, back
+/// down otherwise).
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct LogRateEvent {
+    pub unit: String,
+    pub exceeded: bool,
+    /// Errors per second over the window.
+    pub rate: f64,
+    /// Errors counted in the window.
+    pub count: usize,
+    pub window: Duration,
+    /// Most recent counted message (truncated).
+    pub last_message: Option<String>,
+    /// `true` if the last poll hit `max_entries_per_poll`, so `count` is a lower bound.
+    pub saturated: bool,
+}
+
+/// Watcher that tails a unit's journal and yields `LogRateEvent` on threshold crossings.
+#[derive(Debug)]
+pub struct LogErrorWatcher {
+    journal: crate::Journal,
+    unit: String,
+    opts: LogWatchOptions,
+    since: SystemTime,
+    cursor: Option<String>,
+    rate: ErrorRate,
+    exceeded: bool,
+    last_message: Option<String>,
+    polled: bool,
+    closed: bool,
+    shutdown: Option<ShutdownToken>,
+}
+
+impl Observe {
+    /// Tail `unit`'s journal and report when its error rate crosses `opts.rate_threshold`.
+    ///
+    /// Only entries logged after the watcher is created are counted. The journal is polled,
+    /// so events lag by up to `opts.poll_interval`.
+    pub async fn watch_log_errors(
+        &self,
+        unit: &str,
+        opts: LogWatchOptions,
+    ) -> Result<LogErrorWatcher> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if opts.min_priority > 7 {
+            return Err(Error::invalid_input("min_priority must be in 0..=7"));
+        }
+        if !(opts.rate_threshold.is_finite() && opts.rate_threshold >= 0.0) {
+            return Err(Error::invalid_input("rate_threshold must be >= 0"));
+        }
+        if opts.window.is_zero() || opts.poll_interval.is_zero() {
+            return Err(Error::invalid_input("window and poll_interval must be > 0"));
+        }
+        if opts.max_entries_per_poll == 0 {
+            return Err(Error::invalid_input("max_entries_per_poll must be > 0"));
+        }
+
+        Ok(LogErrorWatcher {
+            journal: crate::Journal::new(self.inner.clone()),
+            unit,
+            rate: ErrorRate::new(opts.window),
+            opts,
+            since: SystemTime::now(),
+            cursor: None,
+            exceeded: false,
+            last_message: None,
+            polled: false,
+            closed: false,
+            shutdown: None,
+        })
+    }
+}
+
+impl LogErrorWatcher {
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Stop polling. This watcher holds no D-Bus subscription, so closing only makes `next()`
+    /// return `Ok(None)` from now on.
+    pub async fn close(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(())
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` returns `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Wait for the next threshold crossing. Returns `Ok(None)` only after `close()` or once the
+    /// `with_shutdown` token fires; otherwise the watcher runs until dropped or a journal query
+    /// fails.
+    pub async fn next(&mut self) -> Result<Option<LogRateEvent>> {
+        let shutdown = self.shutdown.clone();
+        crate::shutdown::race(shutdown.as_ref(), self.recv_event())
+            .await
+            .unwrap_or(Ok(None))
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<LogRateEvent>> {
+        if self.closed {
+            return Ok(None);
+        }
+        loop {
+            if self.polled {
+                crate::runtime::sleep(self.opts.poll_interval).await;
+            }
+            self.polled = true;
+
+            let saturated = self.poll().await?;
+            let now = SystemTime::now();
+            let rate = self.rate.per_second(now);
+            let exceeded = rate > self.opts.rate_threshold;
+            if exceeded == self.exceeded {
+                continue;
+            }
+            self.exceeded = exceeded;
+
+            crate::instrument::watcher_event("log_errors");
+            return Ok(Some(LogRateEvent {
+                unit: self.unit.clone(),
+                exceeded,
+                rate,
+                count: self.rate.count(now),
+                window: self.opts.window,
+                last_message: self.last_message.clone(),
+                saturated,
+            }));
+        }
+    }
+
+    /// Read entries logged since the last poll; returns `true` if the per-poll cap was hit (the
+    /// rate is then resampled from the newest entries, see `resample`).
+    async fn poll(&mut self) -> Result<bool> {
+        const PAGE_LIMIT: u32 = 1_000;
+
+        let mut read = 0u32;
+        loop {
+            let filter = JournalFilter {
+                since: self.cursor.is_none().then_some(self.since),
+                after_cursor: self.cursor.clone(),
+                limit: PAGE_LIMIT.min(self.opts.max_entries_per_poll - read),
+                ..log_filter(&self.unit, self.opts.min_priority)
+            };
+            let res = self.journal.query(filter).await?;
+            read = read.saturating_add(u32::try_from(res.entries.len()).unwrap_or(u32::MAX));
+
+            for entry in &res.entries {
+                if counts(entry, self.opts.min_priority) {
+                    self.rate.record(entry.timestamp);
+                    self.last_message = entry.message.clone();
+                }
+            }
+            if res.next_cursor.is_some() {
+                self.cursor = res.next_cursor;
+            }
+
+            if !res.truncated || self.cursor.is_none() {
+                return Ok(false);
+            }
+            if read >= self.opts.max_entries_per_poll {
+                self.resample().await?;
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Replace the window with the newest `max_entries_per_poll` entries and move the cursor to
+    /// the journal's tail, so a backlog is skipped rather than replayed poll after poll.
+    async fn resample(&mut self) -> Result<()> {
+        let now = SystemTime::now();
+        let filter = JournalFilter {
+            since: Some(
+                now.checked_sub(self.opts.window)
+                    .unwrap_or(std::time::UNIX_EPOCH)
+                    .max(self.since),
+            ),
+            direction: crate::QueryDirection::Reverse,
+            limit: self.opts.max_entries_per_poll,
+            ..log_filter(&self.unit, self.opts.min_priority)
+        };
+        let res = self.journal.query(filter).await?;
+
+        self.rate.clear();
+        // Newest first: the first entry is the new tail.
+        if let Some(cursor) = res.entries.first().and_then(|e| e.cursor.clone()) {
+            self.cursor = Some(cursor);
+        }
+        if let Some(entry) = res
+            .entries
+            .iter()
+            .find(|e| counts(e, self.opts.min_priority))
+        {
+            self.last_message = entry.message.clone();
+        }
+        for entry in res.entries.iter().rev() {
+            if counts(entry, self.opts.min_priority) {
+                self.rate.record(entry.timestamp);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Query shared by `poll` and `resample`: the unit's entries at `min_priority` or more severe,
+/// with bounded messages and no extra fields.
+fn log_filter(unit: &str, min_priority: u8) -> JournalFilter {
+    JournalFilter {
+        unit: Some(unit.to_string()),
+        max_bytes: 4 * 1024 * 1024,
+        max_message_bytes: 512,
+        fields: Some(Vec::new()),
+        priority_max: crate::Priority::from_number(min_priority),
+        ..Default::default()
+    }
+}
+
+/// Whether `entry` counts toward the rate; entries without a `PRIORITY` never do.
+fn counts(entry: &crate::JournalEntry, min_priority: u8) -> bool {
+    entry.priority.is_some_and(|p| p <= min_priority)
+}
+
+/// Timestamps of counted entries within a sliding window.
+#[derive(Debug)]
+struct ErrorRate {
+    window: Duration,
+    times: VecDeque<SystemTime>,
+}
+
+impl ErrorRate {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            times: VecDeque::new(),
+        }
+    }
+
+    fn record(&mut self, at: SystemTime) {
+        self.times.push_back(at);
+    }
+
+    fn clear(&mut self) {
+        self.times.clear();
+    }
+
+    fn count(&mut self, now: SystemTime) -> usize {
+        let start = now
+            .checked_sub(self.window)
+            .unwrap_or(std::time::UNIX_EPOCH);
+        self.times.retain(|t| *t > start);
+        self.times.len()
+    }
+
+    fn per_second(&mut self, now: SystemTime) -> f64 {
+        self.count(now) as f64 / self.window.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn error_rate_slides_over_window() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut rate = ErrorRate::new(Duration::from_secs(10));
+        for s in 0..20 {
+            rate.record(t0 + Duration::from_millis(500 * (s + 1)));
+        }
+
+        assert_eq!(rate.count(t0 + Duration::from_secs(10)), 20);
+        assert_eq!(rate.per_second(t0 + Duration::from_secs(10)), 2.0);
+        // Entries at or before `now - window` fall out.
+        assert_eq!(rate.count(t0 + Duration::from_secs(15)), 10);
+        assert_eq!(rate.count(t0 + Duration::from_secs(60)), 0);
+    }
+
+    #[test]
+    fn poll_and_resample_count_only_entries_at_min_priority() {
+        let filter = log_filter("app.service", 3);
+        assert_eq!(filter.priority_max, Some(crate::Priority::Err));
+        assert_eq!(filter.unit.as_deref(), Some("app.service"));
+
+        let entry = |priority| crate::JournalEntry {
+            priority,
+            ..crate::JournalEntry::from_pairs(&[])
+        };
+        assert!(counts(&entry(Some(0)), 3));
+        assert!(counts(&entry(Some(3)), 3));
+        // Info-level chatter and entries without a priority do not count.
+        assert!(!counts(&entry(Some(6)), 3));
+        assert!(!counts(&entry(None), 3));
+    }
+}
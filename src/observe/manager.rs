@@ -0,0 +1,280 @@
+use super::{ManagerSubscription, Observe};
+use crate::shutdown::ShutdownToken;
+use crate::{Error, Result};
+
+use futures_util::StreamExt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use zbus::zvariant::OwnedObjectPath;
+
+/// Manager-level signal observed via D-Bus (feature=`observe`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ManagerEvent {
+    /// A unit was loaded into memory (`UnitNew`).
+    UnitNew { unit: String, unit_path: String },
+    /// A unit was unloaded (`UnitRemoved`).
+    UnitRemoved { unit: String, unit_path: String },
+    /// A job was queued (`JobNew`).
+    JobNew {
+        id: u32,
+        job_path: String,
+        unit: String,
+    },
+    /// A job finished (`JobRemoved`).
+    JobRemoved {
+        id: u32,
+        job_path: String,
+        unit: String,
+        /// `done`, `canceled`, `timeout`, `failed`, `dependency` or `skipped`.
+        result: String,
+    },
+    /// A daemon reload started (`active=true`) or finished (`Reloading`).
+    Reloading { active: bool },
+}
+
+/// Watcher that yields a `ManagerEvent` for every manager signal.
+///
+/// The watcher holds a manager `Subscribe` for its lifetime, so systemd keeps broadcasting
+/// these signals. It is driven by calling `next()` in a loop, or used as a `Stream` of the same
+/// events that ends where `next()` returns `Ok(None)`.
+pub struct ManagerWatcher {
+    inner: std::sync::Arc<crate::Inner>,
+    stream: Option<zbus::MessageStream>,
+    manager: Option<ManagerSubscription>,
+    shutdown: Option<ShutdownToken>,
+    /// `shutdown` firing, as polled by the `Stream` impl.
+    cancelled: Option<crate::runtime::BoxFuture<()>>,
+}
+
+impl std::fmt::Debug for ManagerWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagerWatcher")
+            .field("closed", &self.stream.is_none())
+            .field("shutdown", &self.shutdown)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Observe {
+    /// Watch manager-wide signals: `UnitNew`, `UnitRemoved`, `JobNew`, `JobRemoved` and
+    /// `Reloading`.
+    ///
+    /// `Subscribe` is called automatically and released again by `close()`.
+    pub async fn watch_manager(&self) -> Result<ManagerWatcher> {
+        let stream = subscribe_manager_signals(&self.inner.bus.connection()).await?;
+        let manager = self
+            .inner
+            .signals
+            .subscribe_manager(&self.inner.bus)
+            .await?;
+
+        Ok(ManagerWatcher {
+            inner: self.inner.clone(),
+            stream: Some(stream),
+            manager: Some(manager),
+            shutdown: None,
+            cancelled: None,
+        })
+    }
+}
+
+impl ManagerWatcher {
+    /// Stop watching: drop the signal stream (removing its match rule) and send `Unsubscribe` if
+    /// this was the last watcher holding a subscription. `next()` returns `Ok(None)` afterwards.
+    /// Dropping the watcher releases the same resources but cannot send `Unsubscribe`.
+    pub async fn close(&mut self) -> Result<()> {
+        self.stream = None;
+        match self.manager.take() {
+            Some(mut manager) => {
+                self.inner
+                    .signals
+                    .unsubscribe_manager(&self.inner.bus, &mut manager)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` returns `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        let token = shutdown.clone();
+        self.cancelled = Some(Box::pin(async move { token.cancelled().await }));
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Wait for the next manager signal.
+    ///
+    /// Returns `Ok(None)` when the signal stream ends or the watcher was closed or shut down.
+    pub async fn next(&mut self) -> Result<Option<ManagerEvent>> {
+        let shutdown = self.shutdown.clone();
+        crate::shutdown::race(shutdown.as_ref(), self.recv_event())
+            .await
+            .unwrap_or(Ok(None))
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<ManagerEvent>> {
+        futures_util::future::poll_fn(|cx| self.poll_event(cx))
+            .await
+            .transpose()
+    }
+
+    /// Next decoded event; `None` once the signal stream ended or was closed.
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<ManagerEvent>>> {
+        loop {
+            let Some(stream) = self.stream.as_mut() else {
+                return Poll::Ready(None);
+            };
+            let Some(msg) = std::task::ready!(stream.poll_next_unpin(cx)) else {
+                return Poll::Ready(None);
+            };
+            let decoded = msg
+                .map_err(|e| Error::IoError {
+                    context: format!("manager watcher stream error: {e}"),
+                })
+                .and_then(|msg| decode(&msg));
+            match decoded {
+                Ok(Some(ev)) => {
+                    crate::instrument::watcher_event("manager");
+                    return Poll::Ready(Some(Ok(ev)));
+                }
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+impl futures_util::Stream for ManagerWatcher {
+    type Item = Result<ManagerEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Checked first so the finished `cancelled` future is never polled again.
+        if self
+            .shutdown
+            .as_ref()
+            .is_some_and(ShutdownToken::is_cancelled)
+        {
+            return Poll::Ready(None);
+        }
+        if let Some(cancelled) = self.cancelled.as_mut()
+            && cancelled.as_mut().poll(cx).is_ready()
+        {
+            return Poll::Ready(None);
+        }
+        self.poll_event(cx)
+    }
+}
+
+fn decode(msg: &zbus::Message) -> Result<Option<ManagerEvent>> {
+    let header = msg.header();
+    let Some(member) = header.member() else {
+        return Ok(None);
+    };
+    let body = msg.body();
+    let decoded = match member.as_str() {
+        "UnitNew" => body
+            .deserialize::<(String, OwnedObjectPath)>()
+            .map(|(unit, path)| ManagerEvent::UnitNew {
+                unit,
+                unit_path: path.to_string(),
+            }),
+        "UnitRemoved" => body
+            .deserialize::<(String, OwnedObjectPath)>()
+            .map(|(unit, path)| ManagerEvent::UnitRemoved {
+                unit,
+                unit_path: path.to_string(),
+            }),
+        "JobNew" => body
+            .deserialize::<(u32, OwnedObjectPath, String)>()
+            .map(|(id, job, unit)| ManagerEvent::JobNew {
+                id,
+                job_path: job.to_string(),
+                unit,
+            }),
+        "JobRemoved" => body
+            .deserialize::<(u32, OwnedObjectPath, String, String)>()
+            .map(|(id, job, unit, result)| ManagerEvent::JobRemoved {
+                id,
+                job_path: job.to_string(),
+                unit,
+                result,
+            }),
+        "Reloading" => body
+            .deserialize::<bool>()
+            .map(|active| ManagerEvent::Reloading { active }),
+        _ => return Ok(None),
+    };
+    decoded.map(Some).map_err(|e| Error::DbusError {
+        name: "SignalDecode".to_string(),
+        message: format!("{member}: {e}"),
+    })
+}
+
+async fn subscribe_manager_signals(conn: &zbus::Connection) -> Result<zbus::MessageStream> {
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(crate::bus::SYSTEMD_DESTINATION)
+        .and_then(|b| b.path(crate::bus::SYSTEMD_MANAGER_PATH))
+        .and_then(|b| b.interface(crate::bus::SYSTEMD_MANAGER_INTERFACE))
+        .map_err(super::map_match_rule_error)?
+        .build();
+
+    zbus::MessageStream::for_match_rule(rule, conn, Some(256))
+        .await
+        .map_err(|e| Error::IoError {
+            context: format!("manager watcher subscribe failed: {e}"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn signal(member: &str) -> zbus::message::Builder<'_> {
+        zbus::Message::signal(
+            crate::bus::SYSTEMD_MANAGER_PATH,
+            crate::bus::SYSTEMD_MANAGER_INTERFACE,
+            member,
+        )
+        .expect("builder")
+    }
+
+    #[test]
+    fn decodes_manager_signals() {
+        let job = OwnedObjectPath::try_from("/org/freedesktop/systemd1/job/42").expect("path");
+        let msg = signal("JobRemoved")
+            .build(&(42u32, job, "nginx.service", "failed"))
+            .expect("msg");
+        assert_eq!(
+            decode(&msg).expect("decode"),
+            Some(ManagerEvent::JobRemoved {
+                id: 42,
+                job_path: "/org/freedesktop/systemd1/job/42".to_string(),
+                unit: "nginx.service".to_string(),
+                result: "failed".to_string(),
+            })
+        );
+
+        let msg = signal("Reloading").build(&true).expect("msg");
+        assert_eq!(
+            decode(&msg).expect("decode"),
+            Some(ManagerEvent::Reloading { active: true })
+        );
+
+        let msg = signal("StartupFinished")
+            .build(&(1u64, 2u64, 3u64, 4u64, 5u64, 6u64))
+            .expect("msg");
+        assert_eq!(decode(&msg).expect("decode"), None);
+
+        let msg = signal("UnitNew").build(&42u32).expect("msg");
+        assert!(decode(&msg).is_err());
+    }
+}
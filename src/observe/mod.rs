@@ -0,0 +1,1086 @@
+use crate::shutdown::ShutdownToken;
+use crate::{ActiveState, Diagnosis, DiagnosisOptions, Error, Result, UnitStatus};
+
+use futures_util::future::{Fuse, FutureExt};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use zbus::zvariant::OwnedValue;
+
+mod accounting;
+mod failed;
+mod health;
+mod logs;
+mod manager;
+mod pressure;
+mod router;
+
+pub use accounting::{AccountingEvent, AccountingMetric, AccountingWatcher, Thresholds};
+pub use failed::{FailedReport, FailedReportOptions, FailedUnitReport};
+pub use health::{SystemHealthEvent, SystemHealthWatcher};
+pub use logs::{LogErrorWatcher, LogRateEvent, LogWatchOptions};
+pub use manager::{ManagerEvent, ManagerWatcher};
+pub use pressure::{PressureEvent, PressureThreshold, PressureWatchOptions, PressureWatcher};
+pub(crate) use router::{ManagerSubscription, SignalRouter};
+use router::{PathFilter, Routed, Subscription};
+
+const UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
+const TIMER_INTERFACE: &str = "org.freedesktop.systemd1.Timer";
+const UNIT_PATH_PREFIX: &str = "/org/freedesktop/systemd1/unit";
+
+/// Options for observing unit failure events.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ObserveOptions {
+    /// Whether to attempt a journald diagnosis snapshot when a failure is observed.
+    pub include_diagnosis: bool,
+
+    /// Optional slice filter for multi-unit watchers (e.g. `"app.slice"`).
+    ///
+    /// Matches units placed directly in the slice and in nested slices (`app-web.slice`),
+    /// including units created after the watcher was started. `None` matches every unit.
+    pub slice: Option<String>,
+
+    /// Options used for `Journal::diagnose_unit_failure` when `include_diagnosis=true`.
+    pub diagnosis: DiagnosisOptions,
+}
+
+impl Default for ObserveOptions {
+    fn default() -> Self {
+        Self {
+            include_diagnosis: true,
+            slice: None,
+            diagnosis: DiagnosisOptions::default(),
+        }
+    }
+}
+
+/// Observe APIs (feature=`observe`).
+#[derive(Clone, Debug)]
+pub struct Observe {
+    inner: Arc<crate::Inner>,
+}
+
+impl Observe {
+    pub(crate) fn new(inner: Arc<crate::Inner>) -> Self {
+        Self { inner }
+    }
+
+    /// Watch a unit and yield events when it transitions to `ActiveState=failed`.
+    ///
+    /// This uses D-Bus signal subscription (`PropertiesChanged`) and does not start/stop units.
+    pub async fn watch_unit_failure(
+        &self,
+        unit: &str,
+        opts: ObserveOptions,
+    ) -> Result<UnitFailureWatcher> {
+        let unit = crate::util::canonicalize_unit_name(unit)?;
+        let unit_path = self.inner.bus.get_unit_path(&unit).await?;
+        let stream = self
+            .subscribe_properties_changed(PathFilter::Exact(unit_path.to_string()), UNIT_INTERFACE)
+            .await?;
+
+        Ok(UnitFailureWatcher {
+            inner: self.inner.clone(),
+            unit,
+            opts,
+            stream,
+            heartbeat: None,
+            shutdown: None,
+            believed_failed: None,
+        })
+    }
+
+    /// Watch a unit and yield an event for every `ActiveState` transition accepted by `filter`
+    /// (e.g. `activating -> active`, `active -> deactivating`).
+    ///
+    /// Changes that only touch `SubState` are not reported; the current `SubState` is carried on
+    /// each event instead.
+    pub async fn watch_unit(&self, unit: &str, filter: StateFilter) -> Result<UnitStateWatcher> {
+        let unit = crate::util::canonicalize_unit_name(unit)?;
+        let unit_path = self.inner.bus.get_unit_path(&unit).await?;
+        let stream = self
+            .subscribe_properties_changed(PathFilter::Exact(unit_path.to_string()), UNIT_INTERFACE)
+            .await?;
+
+        // Read the starting state after subscribing so no transition falls in between.
+        let status = crate::units::Units::new(self.inner.clone())
+            .get_status(&unit)
+            .await?;
+
+        Ok(UnitStateWatcher {
+            inner: self.inner.clone(),
+            unit,
+            filter,
+            stream,
+            heartbeat: None,
+            shutdown: None,
+            state: status.active_state,
+        })
+    }
+
+    /// Watch a timer unit and yield an event each time it triggers its unit.
+    ///
+    /// Triggers are detected via `PropertiesChanged` on `org.freedesktop.systemd1.Timer`
+    /// (`LastTriggerUSec`). `timer` is canonicalized; shorthand names without a suffix are treated
+    /// as `"<name>.timer"`.
+    pub async fn watch_timer(&self, timer: &str) -> Result<TimerWatcher> {
+        crate::util::validate_no_control("timer", timer)?;
+        let timer = if timer.trim().contains('.') {
+            crate::util::canonicalize_unit_name(timer)?
+        } else {
+            crate::util::canonicalize_unit_name(&format!("{}.timer", timer.trim()))?
+        };
+        if !timer.ends_with(".timer") {
+            return Err(Error::invalid_input("timer unit must end with .timer"));
+        }
+
+        let timer_path = self.inner.bus.get_unit_path(&timer).await?;
+        let stream = self
+            .subscribe_properties_changed(
+                PathFilter::Exact(timer_path.to_string()),
+                TIMER_INTERFACE,
+            )
+            .await?;
+
+        let props = self
+            .inner
+            .bus
+            .get_all_properties_uncached(timer_path.as_str(), TIMER_INTERFACE)
+            .await?;
+        let last_trigger_us = props
+            .get("LastTriggerUSec")
+            .and_then(|v| u64::try_from(v).ok())
+            .unwrap_or(0);
+
+        Ok(TimerWatcher {
+            inner: self.inner.clone(),
+            timer,
+            last_trigger_us,
+            stream,
+            heartbeat: None,
+            shutdown: None,
+        })
+    }
+
+    /// Watch all units (optionally scoped by `opts.slice`) and yield events when any of them
+    /// transitions to `ActiveState=failed`.
+    ///
+    /// Units that appear after the watcher was created are covered as well.
+    pub async fn watch_failures(&self, opts: ObserveOptions) -> Result<FailureWatcher> {
+        let slice = match &opts.slice {
+            Some(s) => Some(normalize_slice(s)?),
+            None => None,
+        };
+
+        // systemd only broadcasts unit signals while at least one client is subscribed.
+        let manager = self
+            .inner
+            .signals
+            .subscribe_manager(&self.inner.bus)
+            .await?;
+
+        let stream = self
+            .subscribe_properties_changed(
+                PathFilter::Namespace(UNIT_PATH_PREFIX.to_string()),
+                UNIT_INTERFACE,
+            )
+            .await?;
+
+        Ok(FailureWatcher {
+            inner: self.inner.clone(),
+            slice,
+            opts,
+            stream,
+            manager: Some(manager),
+            heartbeat: None,
+            shutdown: None,
+            believed_failed: None,
+        })
+    }
+
+    /// Build a live, signal-driven model of all units (see `UnitModel`).
+    ///
+    /// Performs one `ListUnits` for the initial state; afterwards the model is kept current from
+    /// D-Bus signals only.
+    pub async fn unit_model(&self) -> Result<crate::UnitModel> {
+        crate::model::UnitModel::start(self.inner.clone()).await
+    }
+
+    /// Subscribe to `PropertiesChanged` via the connection's shared signal router, so any
+    /// number of watchers costs a single match rule.
+    async fn subscribe_properties_changed(
+        &self,
+        filter: PathFilter,
+        interface: &'static str,
+    ) -> Result<Subscription> {
+        self.inner
+            .signals
+            .subscribe(&self.inner.bus.connection(), filter, interface)
+            .await
+    }
+}
+
+/// Item yielded by a watcher's `next_event()`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WatchEvent<T> {
+    /// A regular watcher event (the same value `next()` yields).
+    Event(T),
+    /// Liveness report: heartbeats (enabled via `with_heartbeat`) and detected gaps.
+    Health(WatchHealth),
+}
+
+/// Liveness of a signal-driven watcher.
+///
+/// On every heartbeat tick the watcher probes systemd for state that its signals should have
+/// reported. A `Gap` means signals were (probably) missed and consumers should resync the full
+/// state (e.g. `Units::get_status`, `Manager::list_units`) instead of trusting the event stream
+/// alone. Probes race with in-flight signals, so an occasional spurious `Gap` is possible.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum WatchHealth {
+    /// The watcher is alive and its view matched systemd at this tick.
+    Heartbeat {
+        /// Signals received since the watcher was created.
+        signals_seen: u64,
+    },
+    /// Signals may have been missed.
+    Gap { reason: WatchGapReason },
+}
+
+/// Why a watcher reported `WatchHealth::Gap`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum WatchGapReason {
+    /// The watcher was not polled for longer than the heartbeat interval; its bounded signal
+    /// queue may have overflowed and dropped signals.
+    Stalled { late_by: Duration },
+    /// The heartbeat probe found a state change that no received signal accounted for.
+    StateMismatch,
+    /// The watcher's bounded signal queue overflowed and `dropped` signals were discarded
+    /// (reported even without a heartbeat).
+    QueueOverflow { dropped: u64 },
+}
+
+/// Heartbeat timer shared by all watchers.
+struct Heartbeat {
+    interval: Duration,
+    due: Instant,
+    timer: Fuse<crate::runtime::BoxFuture<()>>,
+    signals_seen: u64,
+}
+
+impl Heartbeat {
+    /// `None` for a zero interval (heartbeat disabled).
+    fn new(interval: Duration) -> Option<Self> {
+        if interval.is_zero() {
+            return None;
+        }
+        Some(Self {
+            interval,
+            due: Instant::now() + interval,
+            timer: crate::runtime::sleep(interval).fuse(),
+            signals_seen: 0,
+        })
+    }
+
+    fn rearm(&mut self) -> Duration {
+        let now = Instant::now();
+        let late_by = now.saturating_duration_since(self.due);
+        self.due = now + self.interval;
+        self.timer = crate::runtime::sleep(self.interval).fuse();
+        late_by
+    }
+
+    /// Health for a tick, given whether the state probe matched.
+    fn health(&self, late_by: Duration, probe_matched: bool) -> WatchHealth {
+        if late_by > self.interval {
+            WatchHealth::Gap {
+                reason: WatchGapReason::Stalled { late_by },
+            }
+        } else if !probe_matched {
+            WatchHealth::Gap {
+                reason: WatchGapReason::StateMismatch,
+            }
+        } else {
+            WatchHealth::Heartbeat {
+                signals_seen: self.signals_seen,
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Heartbeat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Heartbeat")
+            .field("interval", &self.interval)
+            .field("signals_seen", &self.signals_seen)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Shared `close()` of the signal-driven watchers.
+async fn close_subscriptions(
+    inner: &crate::Inner,
+    stream: &mut Subscription,
+    manager: Option<ManagerSubscription>,
+) -> Result<()> {
+    stream.close();
+    match manager {
+        Some(mut manager) => {
+            inner
+                .signals
+                .unsubscribe_manager(&inner.bus, &mut manager)
+                .await
+        }
+        None => Ok(()),
+    }
+}
+
+enum Recv {
+    Signal(Arc<router::PropertiesChanged>),
+    End,
+    Tick { late_by: Duration },
+    Gap(WatchHealth),
+}
+
+/// Wait for the next signal, or for the heartbeat tick when one is enabled.
+async fn recv(sub: &mut Subscription, heartbeat: &mut Option<Heartbeat>) -> Recv {
+    let routed = match heartbeat {
+        None => sub.next().await,
+        Some(hb) => {
+            futures_util::select! {
+                _ = &mut hb.timer => return Recv::Tick { late_by: hb.rearm() },
+                routed = sub.next().fuse() => routed,
+            }
+        }
+    };
+
+    match routed {
+        None => Recv::End,
+        Some(Routed::Signal(signal)) => {
+            if let Some(hb) = heartbeat {
+                hb.signals_seen += 1;
+            }
+            Recv::Signal(signal)
+        }
+        Some(Routed::Overflow { dropped }) => Recv::Gap(WatchHealth::Gap {
+            reason: WatchGapReason::QueueOverflow { dropped },
+        }),
+    }
+}
+
+/// Unit failure event observed via D-Bus.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct UnitFailedEvent {
+    pub unit: String,
+    pub status: UnitStatus,
+    pub diagnosis: Option<Diagnosis>,
+    pub diagnosis_error: Option<Error>,
+}
+
+/// Watcher that yields `UnitFailedEvent` as the unit fails.
+///
+/// The watcher is driven by calling `next()` in a loop.
+#[derive(Debug)]
+pub struct UnitFailureWatcher {
+    inner: Arc<crate::Inner>,
+    unit: String,
+    opts: ObserveOptions,
+    stream: Subscription,
+    heartbeat: Option<Heartbeat>,
+    shutdown: Option<ShutdownToken>,
+    believed_failed: Option<bool>,
+}
+
+impl UnitFailureWatcher {
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Stop watching: leave the shared `PropertiesChanged` stream of the unit (its match rule
+    /// goes away with the last watcher) and stop the heartbeat. `next()` returns `Ok(None)`
+    /// afterwards. The watcher never calls `Subscribe`, so dropping it is equivalent.
+    pub async fn close(&mut self) -> Result<()> {
+        self.heartbeat = None;
+        close_subscriptions(&self.inner, &mut self.stream, None).await
+    }
+
+    /// Emit `WatchHealth` every `interval` from `next_event()` (a zero interval disables it).
+    ///
+    /// Each tick re-reads the unit's `ActiveState` and reports a gap if it failed (or recovered)
+    /// without a matching signal.
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = Heartbeat::new(interval);
+        self
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` and `next_event()` return `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub async fn next(&mut self) -> Result<Option<UnitFailedEvent>> {
+        loop {
+            match self.next_event().await? {
+                None => return Ok(None),
+                Some(WatchEvent::Event(ev)) => return Ok(Some(ev)),
+                Some(WatchEvent::Health(_)) => continue,
+            }
+        }
+    }
+
+    /// Like `next()`, but also yields heartbeat/gap reports.
+    pub async fn next_event(&mut self) -> Result<Option<WatchEvent<UnitFailedEvent>>> {
+        let shutdown = self.shutdown.clone();
+        let Some(ev) = crate::shutdown::race(shutdown.as_ref(), self.recv_event()).await else {
+            return Ok(None);
+        };
+        let ev = ev?;
+        if matches!(ev, Some(WatchEvent::Event(_))) {
+            crate::instrument::watcher_event("unit_failure");
+        }
+        Ok(ev)
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<WatchEvent<UnitFailedEvent>>> {
+        loop {
+            let signal = match recv(&mut self.stream, &mut self.heartbeat).await {
+                Recv::End => return Ok(None),
+                Recv::Gap(health) => return Ok(Some(WatchEvent::Health(health))),
+                Recv::Signal(signal) => signal,
+                Recv::Tick { late_by } => {
+                    let status = crate::units::Units::new(self.inner.clone())
+                        .get_status(&self.unit)
+                        .await?;
+                    let actual = status.active_state == crate::ActiveState::Failed;
+                    let matched = self.believed_failed.is_none_or(|b| b == actual);
+                    self.believed_failed = Some(actual);
+                    let Some(hb) = &self.heartbeat else {
+                        continue;
+                    };
+                    return Ok(Some(WatchEvent::Health(hb.health(late_by, matched))));
+                }
+            };
+
+            let Some(state) = active_state(&signal.changed) else {
+                continue;
+            };
+            let failed = state == "failed";
+            self.believed_failed = Some(failed);
+            if !failed {
+                continue;
+            }
+
+            return failed_event(&self.inner, &self.unit, &self.opts)
+                .await
+                .map(|ev| Some(WatchEvent::Event(ev)));
+        }
+    }
+}
+
+/// Which `ActiveState` transitions `Observe::watch_unit` reports.
+///
+/// An empty list accepts any state on that side of the transition; the default accepts every
+/// transition.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct StateFilter {
+    /// States the unit leaves.
+    pub from: Vec<ActiveState>,
+    /// States the unit enters.
+    pub to: Vec<ActiveState>,
+}
+
+impl StateFilter {
+    /// Accept every transition.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Accept transitions into one of `states`.
+    pub fn to(states: impl IntoIterator<Item = ActiveState>) -> Self {
+        Self {
+            from: Vec::new(),
+            to: states.into_iter().collect(),
+        }
+    }
+
+    /// Restrict to transitions out of one of `states`.
+    pub fn with_from(mut self, states: impl IntoIterator<Item = ActiveState>) -> Self {
+        self.from = states.into_iter().collect();
+        self
+    }
+
+    /// Returns true if the `from -> to` transition passes the filter.
+    pub fn matches(&self, from: &ActiveState, to: &ActiveState) -> bool {
+        (self.from.is_empty() || self.from.contains(from))
+            && (self.to.is_empty() || self.to.contains(to))
+    }
+}
+
+/// Unit `ActiveState` transition observed via D-Bus.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct UnitStateChangeEvent {
+    pub unit: String,
+    pub from: ActiveState,
+    pub to: ActiveState,
+    /// `SubState` after the transition, when the signal carried it.
+    pub sub_state: Option<String>,
+    /// `StateChangeTimestamp` of the transition; the local receive time if systemd omitted it.
+    pub timestamp: SystemTime,
+}
+
+/// Watcher that yields `UnitStateChangeEvent` as the unit changes `ActiveState`.
+///
+/// The watcher is driven by calling `next()` in a loop.
+#[derive(Debug)]
+pub struct UnitStateWatcher {
+    inner: Arc<crate::Inner>,
+    unit: String,
+    filter: StateFilter,
+    stream: Subscription,
+    heartbeat: Option<Heartbeat>,
+    shutdown: Option<ShutdownToken>,
+    state: ActiveState,
+}
+
+impl UnitStateWatcher {
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Last `ActiveState` the watcher saw (the starting state before any transition).
+    pub fn state(&self) -> &ActiveState {
+        &self.state
+    }
+
+    /// Stop watching: leave the unit's shared `PropertiesChanged` stream and stop the heartbeat.
+    /// `next()` returns `Ok(None)` afterwards. No `Subscribe` is held, so this does nothing that
+    /// dropping the watcher would not.
+    pub async fn close(&mut self) -> Result<()> {
+        self.heartbeat = None;
+        close_subscriptions(&self.inner, &mut self.stream, None).await
+    }
+
+    /// Emit `WatchHealth` every `interval` from `next_event()` (a zero interval disables it).
+    ///
+    /// Each tick re-reads the unit's `ActiveState` and reports a gap if it changed without a
+    /// matching signal (the missed transition itself is not replayed).
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = Heartbeat::new(interval);
+        self
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` and `next_event()` return `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub async fn next(&mut self) -> Result<Option<UnitStateChangeEvent>> {
+        loop {
+            match self.next_event().await? {
+                None => return Ok(None),
+                Some(WatchEvent::Event(ev)) => return Ok(Some(ev)),
+                Some(WatchEvent::Health(_)) => continue,
+            }
+        }
+    }
+
+    /// Like `next()`, but also yields heartbeat/gap reports.
+    pub async fn next_event(&mut self) -> Result<Option<WatchEvent<UnitStateChangeEvent>>> {
+        let shutdown = self.shutdown.clone();
+        let Some(ev) = crate::shutdown::race(shutdown.as_ref(), self.recv_event()).await else {
+            return Ok(None);
+        };
+        let ev = ev?;
+        if matches!(ev, Some(WatchEvent::Event(_))) {
+            crate::instrument::watcher_event("unit_state");
+        }
+        Ok(ev)
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<WatchEvent<UnitStateChangeEvent>>> {
+        loop {
+            let signal = match recv(&mut self.stream, &mut self.heartbeat).await {
+                Recv::End => return Ok(None),
+                Recv::Gap(health) => return Ok(Some(WatchEvent::Health(health))),
+                Recv::Signal(signal) => signal,
+                Recv::Tick { late_by } => {
+                    let status = crate::units::Units::new(self.inner.clone())
+                        .get_status(&self.unit)
+                        .await?;
+                    let matched = status.active_state == self.state;
+                    self.state = status.active_state;
+                    let Some(hb) = &self.heartbeat else {
+                        continue;
+                    };
+                    return Ok(Some(WatchEvent::Health(hb.health(late_by, matched))));
+                }
+            };
+
+            let Some(to) = active_state(&signal.changed).map(ActiveState::parse) else {
+                continue;
+            };
+            if to == self.state {
+                continue;
+            }
+            let from = std::mem::replace(&mut self.state, to.clone());
+            if !self.filter.matches(&from, &to) {
+                continue;
+            }
+
+            let timestamp = signal
+                .changed
+                .get("StateChangeTimestamp")
+                .and_then(|v| u64::try_from(v).ok())
+                .filter(|us| *us > 0)
+                .map(|us| UNIX_EPOCH + Duration::from_micros(us))
+                .unwrap_or_else(SystemTime::now);
+            let sub_state = signal
+                .changed
+                .get("SubState")
+                .and_then(|v| <&str>::try_from(v).ok())
+                .map(str::to_string);
+
+            return Ok(Some(WatchEvent::Event(UnitStateChangeEvent {
+                unit: self.unit.clone(),
+                from,
+                to,
+                sub_state,
+                timestamp,
+            })));
+        }
+    }
+}
+
+/// Watcher that yields `UnitFailedEvent` for every failing unit (optionally scoped to a slice).
+///
+/// The watcher is driven by calling `next()` in a loop.
+#[derive(Debug)]
+pub struct FailureWatcher {
+    inner: Arc<crate::Inner>,
+    slice: Option<String>,
+    opts: ObserveOptions,
+    stream: Subscription,
+    manager: Option<ManagerSubscription>,
+    heartbeat: Option<Heartbeat>,
+    shutdown: Option<ShutdownToken>,
+    /// Object paths of units believed to be failed (known after the first heartbeat tick).
+    believed_failed: Option<HashSet<String>>,
+}
+
+impl FailureWatcher {
+    /// Slice filter in effect (`None` means all units).
+    pub fn slice(&self) -> Option<&str> {
+        self.slice.as_deref()
+    }
+
+    /// Stop watching: leave the shared signal stream (its match rule goes away with the last
+    /// watcher) and send `Unsubscribe` if this was the last watcher holding a subscription.
+    /// `next()` returns `Ok(None)` afterwards. Dropping the watcher releases the same resources
+    /// but cannot send `Unsubscribe`.
+    pub async fn close(&mut self) -> Result<()> {
+        self.heartbeat = None;
+        close_subscriptions(&self.inner, &mut self.stream, self.manager.take()).await
+    }
+
+    /// Emit `WatchHealth` every `interval` from `next_event()` (a zero interval disables it).
+    ///
+    /// Each tick lists failed units (all slices) and reports a gap if a unit failed without a
+    /// matching signal.
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = Heartbeat::new(interval);
+        self
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` and `next_event()` return `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub async fn next(&mut self) -> Result<Option<UnitFailedEvent>> {
+        loop {
+            match self.next_event().await? {
+                None => return Ok(None),
+                Some(WatchEvent::Event(ev)) => return Ok(Some(ev)),
+                Some(WatchEvent::Health(_)) => continue,
+            }
+        }
+    }
+
+    /// Like `next()`, but also yields heartbeat/gap reports.
+    pub async fn next_event(&mut self) -> Result<Option<WatchEvent<UnitFailedEvent>>> {
+        let shutdown = self.shutdown.clone();
+        let Some(ev) = crate::shutdown::race(shutdown.as_ref(), self.recv_event()).await else {
+            return Ok(None);
+        };
+        let ev = ev?;
+        if matches!(ev, Some(WatchEvent::Event(_))) {
+            crate::instrument::watcher_event("failure");
+        }
+        Ok(ev)
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<WatchEvent<UnitFailedEvent>>> {
+        loop {
+            let signal = match recv(&mut self.stream, &mut self.heartbeat).await {
+                Recv::End => return Ok(None),
+                Recv::Gap(health) => return Ok(Some(WatchEvent::Health(health))),
+                Recv::Signal(signal) => signal,
+                Recv::Tick { late_by } => {
+                    let actual: HashSet<String> = crate::manager::Manager::new(self.inner.clone())
+                        .list_units_filtered(&["failed"])
+                        .await?
+                        .into_iter()
+                        .map(|u| u.unit_path)
+                        .collect();
+                    let matched = self
+                        .believed_failed
+                        .as_ref()
+                        .is_none_or(|believed| actual.is_subset(believed));
+                    self.believed_failed = Some(actual);
+                    let Some(hb) = &self.heartbeat else {
+                        continue;
+                    };
+                    return Ok(Some(WatchEvent::Health(hb.health(late_by, matched))));
+                }
+            };
+
+            let unit_path = signal.path.as_str();
+            let Some(state) = active_state(&signal.changed) else {
+                continue;
+            };
+            if let Some(believed) = &mut self.believed_failed {
+                if state == "failed" {
+                    believed.insert(unit_path.to_string());
+                } else {
+                    believed.remove(unit_path);
+                }
+            }
+            if state != "failed" {
+                continue;
+            }
+
+            let props = match crate::units::Units::new(self.inner.clone())
+                .get_unit_properties_by_path(unit_path)
+                .await
+            {
+                Ok(p) => p,
+                // The unit may have been garbage-collected between the signal and the lookup.
+                Err(Error::DbusError { name, .. }) if name.contains("UnknownObject") => continue,
+                Err(e) => return Err(e),
+            };
+            let Some(unit) = props.get_opt_string("Id") else {
+                continue;
+            };
+            if let Some(slice) = &self.slice
+                && !slice_contains(slice, props.get_opt_str("Slice"))
+            {
+                continue;
+            }
+
+            return failed_event(&self.inner, &unit, &self.opts)
+                .await
+                .map(|ev| Some(WatchEvent::Event(ev)));
+        }
+    }
+}
+
+async fn failed_event(
+    inner: &Arc<crate::Inner>,
+    unit: &str,
+    opts: &ObserveOptions,
+) -> Result<UnitFailedEvent> {
+    let status = crate::units::Units::new(inner.clone())
+        .get_status(unit)
+        .await?;
+
+    let mut diagnosis = None;
+    let mut diagnosis_error = None;
+    if opts.include_diagnosis {
+        match crate::journal::Journal::new(inner.clone())
+            .diagnose_unit_failure(unit, opts.diagnosis.clone())
+            .await
+        {
+            Ok(d) => diagnosis = Some(d),
+            Err(e) => diagnosis_error = Some(e),
+        }
+    }
+
+    Ok(UnitFailedEvent {
+        unit: unit.to_string(),
+        status,
+        diagnosis,
+        diagnosis_error,
+    })
+}
+
+fn normalize_slice(input: &str) -> Result<String> {
+    let slice = crate::util::canonicalize_unit_name(input)?;
+    if slice.ends_with(".slice") {
+        return Ok(slice);
+    }
+    if !input.trim().contains('.') {
+        return Ok(format!("{}.slice", input.trim()));
+    }
+    Err(Error::invalid_input("slice must end with .slice"))
+}
+
+/// Returns true if `unit_slice` is `slice` itself or nested below it (`a.slice` ⊃ `a-b.slice`).
+fn slice_contains(slice: &str, unit_slice: Option<&str>) -> bool {
+    let Some(unit_slice) = unit_slice else {
+        return false;
+    };
+    if slice == "-.slice" || unit_slice == slice {
+        return true;
+    }
+    let stem = slice.trim_end_matches(".slice");
+    unit_slice
+        .strip_prefix(stem)
+        .is_some_and(|rest| rest.starts_with('-'))
+}
+
+/// Timer trigger event observed via D-Bus (feature=`observe`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct TimerElapsedEvent {
+    /// Timer unit name.
+    pub timer: String,
+    /// Unit triggered by the timer (`Timer.Unit`, usually a service).
+    pub unit: Option<String>,
+    /// Wall-clock time of the trigger (`LastTriggerUSec`).
+    pub triggered_at: Option<SystemTime>,
+    /// `InvocationID` of the triggered unit (hex), read right after the trigger was observed.
+    ///
+    /// Best-effort: if the triggered unit has not started yet this may still be the previous
+    /// invocation's ID (or `None` when the unit has never run).
+    pub invocation_id: Option<String>,
+}
+
+/// Watcher that yields `TimerElapsedEvent` each time a timer fires.
+///
+/// The watcher is driven by calling `next()` in a loop.
+#[derive(Debug)]
+pub struct TimerWatcher {
+    inner: Arc<crate::Inner>,
+    timer: String,
+    last_trigger_us: u64,
+    stream: Subscription,
+    heartbeat: Option<Heartbeat>,
+    shutdown: Option<ShutdownToken>,
+}
+
+impl TimerWatcher {
+    pub fn timer(&self) -> &str {
+        &self.timer
+    }
+
+    /// Stop watching the timer: leave its shared `PropertiesChanged` stream (the match rule goes
+    /// with the last watcher) and stop the heartbeat; `next()` returns `Ok(None)` afterwards.
+    /// Property signals need no `Subscribe`, so dropping the watcher releases the same resources.
+    pub async fn close(&mut self) -> Result<()> {
+        self.heartbeat = None;
+        close_subscriptions(&self.inner, &mut self.stream, None).await
+    }
+
+    /// Emit `WatchHealth` every `interval` from `next_event()` (a zero interval disables it).
+    ///
+    /// Each tick re-reads `LastTriggerUSec` and reports a gap if the timer fired without a
+    /// matching signal (the missed trigger itself is not replayed).
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = Heartbeat::new(interval);
+        self
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` and `next_event()` return `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub async fn next(&mut self) -> Result<Option<TimerElapsedEvent>> {
+        loop {
+            match self.next_event().await? {
+                None => return Ok(None),
+                Some(WatchEvent::Event(ev)) => return Ok(Some(ev)),
+                Some(WatchEvent::Health(_)) => continue,
+            }
+        }
+    }
+
+    /// Like `next()`, but also yields heartbeat/gap reports.
+    pub async fn next_event(&mut self) -> Result<Option<WatchEvent<TimerElapsedEvent>>> {
+        let shutdown = self.shutdown.clone();
+        let Some(ev) = crate::shutdown::race(shutdown.as_ref(), self.recv_event()).await else {
+            return Ok(None);
+        };
+        let ev = ev?;
+        if matches!(ev, Some(WatchEvent::Event(_))) {
+            crate::instrument::watcher_event("timer");
+        }
+        Ok(ev)
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<WatchEvent<TimerElapsedEvent>>> {
+        loop {
+            let signal = match recv(&mut self.stream, &mut self.heartbeat).await {
+                Recv::End => return Ok(None),
+                Recv::Gap(health) => return Ok(Some(WatchEvent::Health(health))),
+                Recv::Signal(signal) => signal,
+                Recv::Tick { late_by } => {
+                    let trigger_us = crate::units::Units::new(self.inner.clone())
+                        .get_timer_properties(&self.timer)
+                        .await?
+                        .and_then(|p| p.get_u64("LastTriggerUSec"))
+                        .unwrap_or(0);
+                    let matched = trigger_us <= self.last_trigger_us;
+                    self.last_trigger_us = self.last_trigger_us.max(trigger_us);
+                    let Some(hb) = &self.heartbeat else {
+                        continue;
+                    };
+                    return Ok(Some(WatchEvent::Health(hb.health(late_by, matched))));
+                }
+            };
+
+            let Some(trigger_us) = signal
+                .changed
+                .get("LastTriggerUSec")
+                .and_then(|v| u64::try_from(v).ok())
+            else {
+                continue;
+            };
+            if trigger_us == 0 || trigger_us == self.last_trigger_us {
+                continue;
+            }
+            self.last_trigger_us = trigger_us;
+
+            let units = crate::units::Units::new(self.inner.clone());
+            let unit = units
+                .get_timer_properties(&self.timer)
+                .await?
+                .and_then(|p| p.get_opt_string("Unit"));
+
+            let invocation_id = match &unit {
+                Some(unit) => units
+                    .get_unit_properties(unit)
+                    .await?
+                    .get_bytes("InvocationID")
+                    .filter(|b| b.iter().any(|x| *x != 0))
+                    .map(|b| hex(&b)),
+                None => None,
+            };
+
+            return Ok(Some(WatchEvent::Event(TimerElapsedEvent {
+                timer: self.timer.clone(),
+                unit,
+                triggered_at: Some(UNIX_EPOCH + Duration::from_micros(trigger_us)),
+                invocation_id,
+            })));
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push_str(&format!("{b:02x}"));
+    }
+    out
+}
+
+/// `ActiveState` carried by a unit `PropertiesChanged` signal, if any.
+fn active_state(changed: &HashMap<String, OwnedValue>) -> Option<&str> {
+    changed
+        .get("ActiveState")
+        .and_then(|v| <&str>::try_from(v).ok())
+}
+
+fn map_match_rule_error(e: zbus::Error) -> Error {
+    Error::IoError {
+        context: format!("observe match rule error: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn slice_contains_matches_self_and_nested_slices() {
+        assert!(slice_contains("app.slice", Some("app.slice")));
+        assert!(slice_contains("app.slice", Some("app-web.slice")));
+        assert!(slice_contains("app.slice", Some("app-web-db.slice")));
+        assert!(!slice_contains("app.slice", Some("apple.slice")));
+        assert!(!slice_contains("app.slice", Some("system.slice")));
+        assert!(!slice_contains("app.slice", None));
+        assert!(slice_contains("-.slice", Some("system.slice")));
+    }
+
+    #[test]
+    fn heartbeat_health_reports_stalls_and_mismatches() {
+        let interval = Duration::from_secs(5);
+        assert!(Heartbeat::new(Duration::ZERO).is_none());
+        // No timer: arming one needs a running reactor under tokio.
+        let hb = Heartbeat {
+            interval,
+            due: Instant::now(),
+            timer: Fuse::terminated(),
+            signals_seen: 3,
+        };
+
+        assert_eq!(
+            hb.health(Duration::from_millis(10), true),
+            WatchHealth::Heartbeat { signals_seen: 3 }
+        );
+        assert_eq!(
+            hb.health(Duration::from_millis(10), false),
+            WatchHealth::Gap {
+                reason: WatchGapReason::StateMismatch
+            }
+        );
+        let late_by = Duration::from_secs(6);
+        assert_eq!(
+            hb.health(late_by, true),
+            WatchHealth::Gap {
+                reason: WatchGapReason::Stalled { late_by }
+            }
+        );
+    }
+
+    #[test]
+    fn state_filter_checks_both_sides_of_a_transition() {
+        use ActiveState::*;
+        assert!(StateFilter::any().matches(&Inactive, &Activating));
+
+        let ready = StateFilter::to([Active]);
+        assert!(ready.matches(&Activating, &Active));
+        assert!(ready.matches(&Reloading, &Active));
+        assert!(!ready.matches(&Active, &Deactivating));
+
+        let started = StateFilter::to([Active]).with_from([Activating]);
+        assert!(started.matches(&Activating, &Active));
+        assert!(!started.matches(&Reloading, &Active));
+    }
+
+    #[test]
+    fn normalize_slice_appends_suffix_and_rejects_other_types() {
+        assert_eq!(normalize_slice("app").expect("ok"), "app.slice");
+        assert_eq!(normalize_slice("app.slice").expect("ok"), "app.slice");
+        let err = normalize_slice("app.service").expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+}
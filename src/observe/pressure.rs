@@ -0,0 +1,247 @@
+use super::Observe;
+use crate::shutdown::ShutdownToken;
+use crate::{Error, PressureKind, PressureResource, Result, UnitPressure, util};
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Alert level for one PSI line, compared against its `avg10` value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct PressureThreshold {
+    pub resource: PressureResource,
+    pub kind: PressureKind,
+    /// Percentage of stalled wall time (0-100) over the last 10 seconds.
+    pub avg10: f64,
+}
+
+impl PressureThreshold {
+    pub fn new(resource: PressureResource, kind: PressureKind, avg10: f64) -> Self {
+        Self {
+            resource,
+            kind,
+            avg10,
+        }
+    }
+}
+
+/// Options for `Observe::watch_pressure`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PressureWatchOptions {
+    /// How often the PSI files are sampled (default: 5s).
+    pub interval: Duration,
+    /// Thresholds to watch; at least one is required.
+    pub thresholds: Vec<PressureThreshold>,
+}
+
+impl Default for PressureWatchOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            thresholds: Vec::new(),
+        }
+    }
+}
+
+/// A threshold was crossed: `avg10` rose above it (`exceeded`) or fell back to or below it.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct PressureEvent {
+    pub unit: String,
+    pub threshold: PressureThreshold,
+    /// Observed `avg10` of the threshold's PSI line.
+    pub avg10: f64,
+    pub exceeded: bool,
+    /// Full sample the crossing was detected in.
+    pub pressure: UnitPressure,
+}
+
+/// Watcher that samples a unit's PSI files and yields `PressureEvent` on threshold crossings.
+///
+/// Samples taken while the unit has no control group (e.g. it is stopped) are skipped, and
+/// threshold states carry over until the next sample.
+#[derive(Debug)]
+pub struct PressureWatcher {
+    units: crate::Units,
+    unit: String,
+    interval: Duration,
+    thresholds: Vec<PressureThreshold>,
+    exceeded: Vec<bool>,
+    pending: VecDeque<PressureEvent>,
+    sampled: bool,
+    closed: bool,
+    shutdown: Option<ShutdownToken>,
+}
+
+impl Observe {
+    /// Watch the PSI pressure of `unit`'s cgroup against `opts.thresholds`.
+    ///
+    /// Every threshold starts as not exceeded, so one already above its level is reported by
+    /// the first sample. Requires the unified (v2) cgroup hierarchy and, like `Units::pressure`,
+    /// a connection to the local system manager.
+    pub async fn watch_pressure(
+        &self,
+        unit: &str,
+        opts: PressureWatchOptions,
+    ) -> Result<PressureWatcher> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if opts.interval.is_zero() {
+            return Err(Error::invalid_input("interval must be > 0"));
+        }
+        if opts.thresholds.is_empty() {
+            return Err(Error::invalid_input("at least one threshold is required"));
+        }
+        if let Some(t) = opts
+            .thresholds
+            .iter()
+            .find(|t| !(t.avg10 >= 0.0 && t.avg10 < 100.0))
+        {
+            return Err(Error::invalid_input(format!(
+                "threshold avg10 must be in 0..100, got {}",
+                t.avg10
+            )));
+        }
+        let units = crate::Units::new(self.inner.clone());
+        units.require_local_system()?;
+        crate::cgroup::unified_root()?;
+
+        Ok(PressureWatcher {
+            units,
+            unit,
+            interval: opts.interval,
+            exceeded: vec![false; opts.thresholds.len()],
+            thresholds: opts.thresholds,
+            pending: VecDeque::new(),
+            sampled: false,
+            closed: false,
+            shutdown: None,
+        })
+    }
+}
+
+impl PressureWatcher {
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Stop polling. This watcher holds no D-Bus subscription, so closing only makes `next()`
+    /// return `Ok(None)` from now on.
+    pub async fn close(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(())
+    }
+
+    /// End the watcher when `shutdown` fires: `next()` returns `Ok(None)`.
+    pub fn with_shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Wait for the next threshold crossing. Returns `Ok(None)` only after `close()` or once the
+    /// `with_shutdown` token fires; otherwise the watcher runs until dropped or a sample fails.
+    pub async fn next(&mut self) -> Result<Option<PressureEvent>> {
+        let shutdown = self.shutdown.clone();
+        crate::shutdown::race(shutdown.as_ref(), self.recv_event())
+            .await
+            .unwrap_or(Ok(None))
+    }
+
+    async fn recv_event(&mut self) -> Result<Option<PressureEvent>> {
+        if self.closed {
+            return Ok(None);
+        }
+        loop {
+            if let Some(ev) = self.pending.pop_front() {
+                crate::instrument::watcher_event("pressure");
+                return Ok(Some(ev));
+            }
+            if self.sampled {
+                crate::runtime::sleep(self.interval).await;
+            }
+            self.sampled = true;
+
+            let Some(pressure) = self.units.pressure_sample(&self.unit).await? else {
+                continue;
+            };
+            for (i, avg10) in crossings(&self.thresholds, &mut self.exceeded, &pressure) {
+                let Some(threshold) = self.thresholds.get(i) else {
+                    continue;
+                };
+                self.pending.push_back(PressureEvent {
+                    unit: self.unit.clone(),
+                    threshold: *threshold,
+                    avg10,
+                    exceeded: self.exceeded.get(i).copied().unwrap_or_default(),
+                    pressure: pressure.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Update `exceeded` from `pressure`; returns `(index, avg10)` of every threshold that flipped.
+fn crossings(
+    thresholds: &[PressureThreshold],
+    exceeded: &mut [bool],
+    pressure: &UnitPressure,
+) -> Vec<(usize, f64)> {
+    let mut flipped = Vec::new();
+    for (i, (t, state)) in thresholds.iter().zip(exceeded.iter_mut()).enumerate() {
+        let Some(line) = pressure.get(t.resource).and_then(|p| p.line(t.kind)) else {
+            continue;
+        };
+        let now = line.avg10 > t.avg10;
+        if now != *state {
+            *state = now;
+            flipped.push((i, line.avg10));
+        }
+    }
+    flipped
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::Pressure;
+
+    fn sample(memory_some: f64, memory_full: f64) -> UnitPressure {
+        let text = format!(
+            "some avg10={memory_some:.2} avg60=0.00 avg300=0.00 total=0\n\
+             full avg10={memory_full:.2} avg60=0.00 avg300=0.00 total=0\n"
+        );
+        UnitPressure {
+            unit: "app.service".to_string(),
+            control_group: "/system.slice/app.service".to_string(),
+            cpu: None,
+            memory: Pressure::parse(&text),
+            io: None,
+        }
+    }
+
+    #[test]
+    fn crossings_are_edge_triggered() {
+        let thresholds = [
+            PressureThreshold::new(PressureResource::Memory, PressureKind::Some, 10.0),
+            PressureThreshold::new(PressureResource::Memory, PressureKind::Full, 5.0),
+            PressureThreshold::new(PressureResource::Cpu, PressureKind::Some, 1.0),
+        ];
+        let mut exceeded = [false; 3];
+
+        assert!(crossings(&thresholds, &mut exceeded, &sample(1.0, 0.0)).is_empty());
+        assert_eq!(
+            crossings(&thresholds, &mut exceeded, &sample(12.5, 0.0)),
+            vec![(0, 12.5)]
+        );
+        assert!(crossings(&thresholds, &mut exceeded, &sample(20.0, 1.0)).is_empty());
+        assert_eq!(
+            crossings(&thresholds, &mut exceeded, &sample(10.0, 6.0)),
+            vec![(0, 10.0), (1, 6.0)]
+        );
+        assert_eq!(exceeded, [false, true, false]);
+    }
+}
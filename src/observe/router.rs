@@ -0,0 +1,501 @@
+use crate::{Error, Result};
+
+use futures_util::StreamExt;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Poll, Waker};
+
+use zbus::zvariant::OwnedValue;
+
+/// Per-subscriber queue capacity; the oldest signal is dropped (and counted) on overflow.
+const SUBSCRIBER_QUEUE_CAPACITY: usize = 64;
+
+/// A decoded `PropertiesChanged` signal for a systemd unit object.
+#[derive(Debug)]
+pub(crate) struct PropertiesChanged {
+    pub(crate) path: String,
+    pub(crate) interface: String,
+    pub(crate) changed: HashMap<String, OwnedValue>,
+}
+
+/// Which unit object paths a subscription receives.
+#[derive(Clone, Debug)]
+pub(crate) enum PathFilter {
+    Exact(String),
+    Namespace(String),
+}
+
+impl PathFilter {
+    fn matches(&self, path: &str) -> bool {
+        match self {
+            PathFilter::Exact(p) => p == path,
+            PathFilter::Namespace(ns) => path
+                .strip_prefix(ns.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')),
+        }
+    }
+}
+
+/// Item received from a `Subscription`.
+#[derive(Debug)]
+pub(crate) enum Routed {
+    Signal(Arc<PropertiesChanged>),
+    /// Signals were dropped because this subscriber's queue was full.
+    Overflow {
+        dropped: u64,
+    },
+}
+
+/// Shared `PropertiesChanged` stream for all watchers of one connection.
+///
+/// Each unit interface with subscribers gets one match rule (`arg0` set to the interface, under
+/// the systemd unit path namespace) and one pump task that decodes each signal once and fans it
+/// out to the bounded queues of matching subscribers. The route is set up by the first
+/// subscriber of an interface; the last one to leave drops its stream, and with it the match
+/// rule, on the spot and wakes the pump so it exits.
+///
+/// The router also reference-counts the manager `Subscribe` held on behalf of watchers, so the
+/// last watcher to `close()` can send `Unsubscribe`.
+#[derive(Debug, Default)]
+pub(crate) struct SignalRouter {
+    state: Arc<Mutex<RouterState>>,
+    start: futures_util::lock::Mutex<()>,
+}
+
+#[derive(Debug, Default)]
+struct RouterState {
+    next_id: u64,
+    subscribers: HashMap<u64, Subscriber>,
+    /// Live routes, keyed by the interface their match rule selects.
+    routes: HashMap<&'static str, Route>,
+    /// Live `ManagerSubscription`s.
+    manager_users: usize,
+}
+
+/// The match rule stream of one interface and the pump draining it.
+struct Route {
+    /// Tells the pump of a removed route apart from the one of a route set up again later.
+    generation: u64,
+    stream: zbus::MessageStream,
+    /// Woken when the route is removed, so the pump can exit without waiting for a signal.
+    pump_waker: Option<Waker>,
+}
+
+impl std::fmt::Debug for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Route")
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct Subscriber {
+    filter: PathFilter,
+    interface: &'static str,
+    queue: VecDeque<Arc<PropertiesChanged>>,
+    dropped: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl Subscriber {
+    fn push(&mut self, signal: Arc<PropertiesChanged>) {
+        if self.queue.len() >= SUBSCRIBER_QUEUE_CAPACITY {
+            self.queue.pop_front();
+            self.dropped += 1;
+        }
+        self.queue.push_back(signal);
+        self.wake();
+    }
+
+    fn close(&mut self) {
+        self.closed = true;
+        self.wake();
+    }
+
+    fn wake(&mut self) {
+        if let Some(w) = self.waker.take() {
+            w.wake();
+        }
+    }
+}
+
+fn lock(state: &Mutex<RouterState>) -> MutexGuard<'_, RouterState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl SignalRouter {
+    /// Register a subscriber for `PropertiesChanged` on `interface` under `filter`.
+    ///
+    /// Signals emitted after this returns are guaranteed to be delivered (subject to the queue
+    /// bound).
+    pub(crate) async fn subscribe(
+        &self,
+        conn: &zbus::Connection,
+        filter: PathFilter,
+        interface: &'static str,
+    ) -> Result<Subscription> {
+        let _start = self.start.lock().await;
+
+        let (subscription, need_start) = self.register(filter, interface);
+        if need_start {
+            let stream = subscribe_unit_properties_changed(conn, interface).await?;
+            let generation = {
+                let mut st = lock(&self.state);
+                let generation = st.next_id;
+                st.next_id += 1;
+                st.routes.insert(
+                    interface,
+                    Route {
+                        generation,
+                        stream,
+                        pump_waker: None,
+                    },
+                );
+                generation
+            };
+            conn.executor()
+                .spawn(
+                    pump(self.state.clone(), interface, generation),
+                    "unitbus-signal-router",
+                )
+                .detach();
+        }
+
+        Ok(subscription)
+    }
+
+    /// Call the manager's `Subscribe` on behalf of one watcher; systemd only broadcasts unit
+    /// signals while at least one client is subscribed.
+    pub(crate) async fn subscribe_manager(
+        &self,
+        bus: &crate::bus::Bus,
+    ) -> Result<ManagerSubscription> {
+        let _start = self.start.lock().await;
+        bus.subscribe().await?;
+        lock(&self.state).manager_users += 1;
+        Ok(ManagerSubscription {
+            state: Some(self.state.clone()),
+        })
+    }
+
+    /// Release `sub`, sending `Unsubscribe` if it was the last one on this connection.
+    pub(crate) async fn unsubscribe_manager(
+        &self,
+        bus: &crate::bus::Bus,
+        sub: &mut ManagerSubscription,
+    ) -> Result<()> {
+        // Serialized with `subscribe_manager` so a concurrent `Subscribe` is never undone.
+        let _start = self.start.lock().await;
+        if sub.release() {
+            bus.unsubscribe().await?;
+        }
+        Ok(())
+    }
+
+    /// Add a subscriber; also returns whether its interface still needs a route.
+    fn register(&self, filter: PathFilter, interface: &'static str) -> (Subscription, bool) {
+        let mut st = lock(&self.state);
+        let id = st.next_id;
+        st.next_id += 1;
+        st.subscribers.insert(
+            id,
+            Subscriber {
+                filter,
+                interface,
+                queue: VecDeque::new(),
+                dropped: 0,
+                closed: false,
+                waker: None,
+            },
+        );
+        let subscription = Subscription {
+            id,
+            state: self.state.clone(),
+        };
+        (subscription, !st.routes.contains_key(interface))
+    }
+}
+
+impl RouterState {
+    fn dispatch(&mut self, signal: Arc<PropertiesChanged>) {
+        for sub in self.subscribers.values_mut() {
+            if sub.interface == signal.interface && sub.filter.matches(&signal.path) {
+                sub.push(signal.clone());
+            }
+        }
+    }
+}
+
+async fn pump(state: Arc<Mutex<RouterState>>, interface: &'static str, generation: u64) {
+    loop {
+        let next = futures_util::future::poll_fn(|cx| {
+            let mut st = lock(&state);
+            let Some(route) = st
+                .routes
+                .get_mut(interface)
+                .filter(|route| route.generation == generation)
+            else {
+                // The last subscriber removed the route (and with it the match rule).
+                return Poll::Ready(None);
+            };
+            route.pump_waker = Some(cx.waker().clone());
+            route.stream.poll_next_unpin(cx).map(Some)
+        })
+        .await;
+        let msg = match next {
+            None => return,
+            Some(None) => break,
+            Some(Some(msg)) => msg,
+        };
+        let signal = msg.ok().and_then(|m| decode(&m));
+
+        if let Some(signal) = signal {
+            lock(&state).dispatch(Arc::new(signal));
+        }
+    }
+
+    let mut st = lock(&state);
+    if st
+        .routes
+        .get(interface)
+        .is_some_and(|route| route.generation == generation)
+    {
+        st.routes.remove(interface);
+    }
+    for sub in st
+        .subscribers
+        .values_mut()
+        .filter(|sub| sub.interface == interface)
+    {
+        sub.close();
+    }
+}
+
+fn decode(msg: &zbus::Message) -> Option<PropertiesChanged> {
+    let path = msg.header().path()?.to_string();
+    let body = msg.body();
+    let (interface, changed, _invalidated): (String, HashMap<String, OwnedValue>, Vec<String>) =
+        body.deserialize().ok()?;
+    Some(PropertiesChanged {
+        path,
+        interface,
+        changed,
+    })
+}
+
+/// Match `PropertiesChanged` of `interface` on systemd unit objects.
+fn unit_properties_changed_rule(interface: &'static str) -> Result<zbus::MatchRule<'static>> {
+    Ok(zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(crate::bus::SYSTEMD_DESTINATION)
+        .and_then(|b| b.interface(crate::bus::DBUS_PROPERTIES_INTERFACE))
+        .and_then(|b| b.member("PropertiesChanged"))
+        .and_then(|b| b.path_namespace(super::UNIT_PATH_PREFIX))
+        .and_then(|b| b.arg(0, interface))
+        .map_err(super::map_match_rule_error)?
+        .build())
+}
+
+async fn subscribe_unit_properties_changed(
+    conn: &zbus::Connection,
+    interface: &'static str,
+) -> Result<zbus::MessageStream> {
+    let rule = unit_properties_changed_rule(interface)?;
+
+    // The pump drains promptly, so the connection-level queue only absorbs bursts.
+    zbus::MessageStream::for_match_rule(rule, conn, Some(256))
+        .await
+        .map_err(|e| Error::IoError {
+            context: format!("observe subscribe failed: {e}"),
+        })
+}
+
+/// A subscriber's handle; unregisters on drop.
+#[derive(Debug)]
+pub(crate) struct Subscription {
+    id: u64,
+    state: Arc<Mutex<RouterState>>,
+}
+
+impl Subscription {
+    /// Unregister now; `next()` then yields `None`. Also done on drop.
+    ///
+    /// The last subscriber of an interface removes its route right away, so the match rule is
+    /// dropped here rather than whenever the pump runs next.
+    pub(crate) fn close(&mut self) {
+        let route = {
+            let mut st = lock(&self.state);
+            let Some(sub) = st.subscribers.remove(&self.id) else {
+                return;
+            };
+            if st
+                .subscribers
+                .values()
+                .any(|s| s.interface == sub.interface)
+            {
+                return;
+            }
+            st.routes.remove(sub.interface)
+        };
+        if let Some(mut route) = route
+            && let Some(w) = route.pump_waker.take()
+        {
+            w.wake();
+        }
+    }
+
+    /// Next routed item, or `None` once the shared stream has ended or the subscription was
+    /// closed.
+    pub(crate) fn next(&mut self) -> impl Future<Output = Option<Routed>> + Unpin + '_ {
+        futures_util::future::poll_fn(|cx| {
+            let mut st = lock(&self.state);
+            let Some(sub) = st.subscribers.get_mut(&self.id) else {
+                return Poll::Ready(None);
+            };
+            if sub.dropped > 0 {
+                let dropped = std::mem::take(&mut sub.dropped);
+                return Poll::Ready(Some(Routed::Overflow { dropped }));
+            }
+            if let Some(signal) = sub.queue.pop_front() {
+                return Poll::Ready(Some(Routed::Signal(signal)));
+            }
+            if sub.closed {
+                return Poll::Ready(None);
+            }
+            sub.waker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// One watcher's share of the manager `Subscribe`.
+///
+/// Dropping it only releases the share; `Unsubscribe` is sent by
+/// `SignalRouter::unsubscribe_manager` (or implicitly when the connection closes).
+#[derive(Debug)]
+pub(crate) struct ManagerSubscription {
+    state: Option<Arc<Mutex<RouterState>>>,
+}
+
+impl ManagerSubscription {
+    /// Release the share once; returns `true` if it was the last one.
+    fn release(&mut self) -> bool {
+        let Some(state) = self.state.take() else {
+            return false;
+        };
+        let mut st = lock(&state);
+        st.manager_users = st.manager_users.saturating_sub(1);
+        st.manager_users == 0
+    }
+}
+
+impl Drop for ManagerSubscription {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn signal(path: &str, interface: &str) -> Arc<PropertiesChanged> {
+        Arc::new(PropertiesChanged {
+            path: path.to_string(),
+            interface: interface.to_string(),
+            changed: HashMap::new(),
+        })
+    }
+
+    fn subscribe(router: &SignalRouter, filter: PathFilter) -> Subscription {
+        router.register(filter, super::super::UNIT_INTERFACE).0
+    }
+
+    fn route(router: &SignalRouter, signal: Arc<PropertiesChanged>) {
+        lock(&router.state).dispatch(signal);
+    }
+
+    #[test]
+    fn path_filter_matches_exact_and_namespace() {
+        let ns = PathFilter::Namespace("/org/freedesktop/systemd1/unit".to_string());
+        assert!(ns.matches("/org/freedesktop/systemd1/unit/a_2eservice"));
+        assert!(!ns.matches("/org/freedesktop/systemd1/units"));
+        let exact = PathFilter::Exact("/u/a".to_string());
+        assert!(exact.matches("/u/a"));
+        assert!(!exact.matches("/u/ab"));
+    }
+
+    #[test]
+    fn router_demultiplexes_and_reports_overflow() {
+        let router = SignalRouter::default();
+        let mut a = subscribe(&router, PathFilter::Exact("/u/a".to_string()));
+        let mut all = subscribe(&router, PathFilter::Namespace("/u".to_string()));
+        let unit = super::super::UNIT_INTERFACE;
+
+        route(&router, signal("/u/a", unit));
+        route(&router, signal("/u/b", unit));
+        route(&router, signal("/u/a", "org.freedesktop.systemd1.Service"));
+
+        let next = |s: &mut Subscription| futures_lite::future::block_on(s.next());
+        let Some(Routed::Signal(sig)) = next(&mut a) else {
+            panic!("expected signal");
+        };
+        assert_eq!(sig.path, "/u/a");
+        assert!(lock(&router.state).subscribers[&a.id].queue.is_empty());
+
+        for _ in 0..SUBSCRIBER_QUEUE_CAPACITY {
+            route(&router, signal("/u/c", unit));
+        }
+        let Some(Routed::Overflow { dropped }) = next(&mut all) else {
+            panic!("expected overflow");
+        };
+        assert_eq!(dropped, 2);
+
+        drop(a);
+        assert_eq!(lock(&router.state).subscribers.len(), 1);
+
+        all.close();
+        assert!(lock(&router.state).subscribers.is_empty());
+        assert!(next(&mut all).is_none());
+    }
+
+    #[test]
+    fn match_rule_selects_the_interface_via_arg0() {
+        let rule = unit_properties_changed_rule(super::super::TIMER_INTERFACE).expect("rule");
+        let rule = rule.to_string();
+        assert!(
+            rule.contains("arg0='org.freedesktop.systemd1.Timer'"),
+            "{rule}"
+        );
+        assert!(rule.contains("member='PropertiesChanged'"), "{rule}");
+    }
+
+    #[test]
+    fn manager_subscription_reports_last_release() {
+        let router = SignalRouter::default();
+        lock(&router.state).manager_users = 2;
+        let sub = || ManagerSubscription {
+            state: Some(router.state.clone()),
+        };
+        let (mut a, mut b) = (sub(), sub());
+        assert!(!a.release());
+        assert!(!a.release());
+        assert!(b.release());
+        assert_eq!(lock(&router.state).manager_users, 0);
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 /// Configuration options for `UnitBus`.
 #[derive(Clone, Debug)]
@@ -16,10 +16,159 @@ pub struct UnitBusOptions {
     /// Maximum polling interval for job wait fallback.
     pub job_poll_max: Duration,
 
+    /// Joint deadline for the concurrent probes of `UnitBus::capabilities`. Default: 5s.
+    pub capabilities_timeout: Duration,
+
     /// Base directory for systemd unit files and drop-ins (feature=`config`).
     ///
-    /// Default: `/etc/systemd/system`.
-    pub systemd_system_dir: String,
+    /// Default: `None`, resolved per manager scope: `/etc/systemd/system` for the system
+    /// manager (`UnitBus::connect_system`), `$XDG_CONFIG_HOME/systemd/user` (usually
+    /// `~/.config/systemd/user`) for the user manager (`UnitBus::connect_session`).
+    pub unit_dir: Option<String>,
+
+    /// Preview configuration changes instead of applying them (feature=`config`).
+    ///
+    /// `Config::apply_dropin`, `remove_dropin`, `write_service_unit`, `remove_unit_file`,
+    /// `install_service_unit`, `uninstall_unit`, `update_socket_listener`, `wire_on_failure` and
+    /// `define_app_target` then return their usual reports with `dry_run` set and a line `diff`
+    /// against the file on disk, without writing anything or calling systemd mutators (no
+    /// daemon reload, restart, enable/disable or shutdown inhibitor). Writers that cannot
+    /// preview (the `_verified` variants and the offline update calls) return
+    /// `Error::InvalidInput`. Default: `false`.
+    pub dry_run: bool,
+
+    /// How task argv and environment values appear in `tracing` output (feature=`tracing`).
+    ///
+    /// Default: `TraceRedaction::Redact`.
+    pub trace_redaction: TraceRedaction,
+
+    /// Receiver of an `AuditRecord` for every mutating operation (unit start/stop/restart/reload,
+    /// unit file and drop-in writes, enablement changes, daemon reloads, task launches).
+    ///
+    /// Composite operations (e.g. `Units::ensure`) are recorded along with their steps.
+    /// Default: `None`.
+    pub audit_sink: Option<Arc<dyn crate::AuditSink>>,
+
+    /// Per-unit advisory lock serializing mutating operations on the same unit across
+    /// unitbus-based processes on the host.
+    ///
+    /// Default: `None` (no locking).
+    pub unit_lock: Option<UnitLockOptions>,
+
+    /// Cache `GetAll` replies for unit objects briefly, per connection, so flows that read the
+    /// same unit repeatedly (job wait, then status, then diagnosis) make fewer D-Bus calls.
+    ///
+    /// Mutating calls (start/stop/kill, unit file and property changes, daemon reloads) and
+    /// finished job waits clear the cache; other changes become visible after at most `ttl`.
+    /// Default: `None` (every read goes to systemd).
+    pub property_cache: Option<PropertyCacheOptions>,
+
+    /// Constraints for the `journalctl` child of the CLI journal backend (feature=`journal-cli`).
+    ///
+    /// Default: `None` (the child inherits the environment and priority of this process and is
+    /// bounded only by the query timeout).
+    pub journal_cli_sandbox: Option<JournalCliSandbox>,
+}
+
+/// Advisory unit locking (`UnitBusOptions.unit_lock`).
+///
+/// Each mutating operation on a unit (start/stop/restart/reload, `ensure`, unit file, drop-in
+/// and enablement changes) holds a per-unit mutex within the process and an exclusive `flock` on
+/// `<dir>/<unit>.lock` for the duration of the call (`<dir>/user-<uid>/<unit>.lock` on a
+/// `UnitBus::connect_session` connection, which needs a `dir` the user can write, e.g. below
+/// `$XDG_RUNTIME_DIR`). Start/stop-like calls
+/// return once the job is queued, so the lock does not cover the job itself; `Units::ensure`
+/// and `Units::stop_graceful` hold it until they return.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitLockOptions {
+    /// Lock file directory, created if missing. Default: `/run/unitbus/locks`.
+    pub dir: String,
+    /// Maximum time to wait for a lock held by another process before `Error::Timeout`.
+    /// Default: 30s.
+    pub timeout: Duration,
+}
+
+impl Default for UnitLockOptions {
+    fn default() -> Self {
+        Self {
+            dir: "/run/unitbus/locks".to_string(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Property read cache (`UnitBusOptions.property_cache`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PropertyCacheOptions {
+    /// How long a reply is reused. Default: 500ms; zero disables the cache.
+    pub ttl: Duration,
+    /// Maximum number of cached (object, interface) replies; the least recently used one is
+    /// evicted first. Default: 256.
+    pub capacity: usize,
+}
+
+impl Default for PropertyCacheOptions {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_millis(500),
+            capacity: 256,
+        }
+    }
+}
+
+/// Process-level constraints for `journalctl` (`UnitBusOptions.journal_cli_sandbox`).
+///
+/// Wrappers (`systemd-run`, `nice`) exec into `journalctl`, so the timeout kill still reaches it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct JournalCliSandbox {
+    /// Start `journalctl` with an empty environment, keeping only `PATH`. Default: `true`.
+    pub clear_env: bool,
+    /// Niceness of the child (`nice -n`), in `-20..=19`. Default: `Some(10)`.
+    pub nice: Option<i32>,
+    /// Hard cap on the child's runtime, applied even when a filter asks for a longer timeout;
+    /// the child is killed once it passes. Default: `Some(30s)`.
+    pub max_runtime: Option<Duration>,
+    /// Run the child in its own transient scope unit (`systemd-run --scope`), so the limits below
+    /// are enforced by its cgroup. Default: `false`.
+    pub transient_scope: bool,
+    /// `MemoryMax=` of the transient scope, in bytes; requires `transient_scope`.
+    /// Default: `None`.
+    pub memory_max: Option<u64>,
+}
+
+impl Default for JournalCliSandbox {
+    fn default() -> Self {
+        Self {
+            clear_env: true,
+            nice: Some(10),
+            max_runtime: Some(Duration::from_secs(30)),
+            transient_scope: false,
+            memory_max: None,
+        }
+    }
+}
+
+impl JournalCliSandbox {
+    /// Run in a transient scope capped at `bytes` of memory.
+    pub fn with_memory_max(mut self, bytes: u64) -> Self {
+        self.transient_scope = true;
+        self.memory_max = Some(bytes);
+        self
+    }
+}
+
+/// Redaction policy for potentially sensitive values in `tracing` output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TraceRedaction {
+    /// Log argv and environment values verbatim (for private hosts and debugging).
+    Off,
+    /// Log only argv length and environment keys.
+    #[default]
+    Redact,
 }
 
 impl Default for UnitBusOptions {
@@ -29,7 +178,110 @@ impl Default for UnitBusOptions {
             journal_default_timeout: Duration::from_secs(10),
             job_poll_initial: Duration::from_millis(200),
             job_poll_max: Duration::from_secs(2),
-            systemd_system_dir: "/etc/systemd/system".to_string(),
+            capabilities_timeout: Duration::from_secs(5),
+            unit_dir: None,
+            dry_run: false,
+            trace_redaction: TraceRedaction::default(),
+            audit_sink: None,
+            unit_lock: None,
+            property_cache: None,
+            journal_cli_sandbox: None,
         }
     }
 }
+
+impl UnitBusOptions {
+    /// `unit_dir`, or the default unit file directory of the `scope` manager.
+    #[cfg(feature = "config")]
+    pub(crate) fn resolve_unit_dir(
+        &self,
+        scope: crate::ManagerScope,
+    ) -> crate::Result<std::path::PathBuf> {
+        let dir = match &self.unit_dir {
+            Some(dir) => std::path::PathBuf::from(dir.trim()),
+            None => match scope {
+                crate::ManagerScope::User => user_unit_dir(
+                    std::env::var_os("XDG_CONFIG_HOME"),
+                    std::env::var_os("HOME"),
+                )?,
+                _ => std::path::PathBuf::from("/etc/systemd/system"),
+            },
+        };
+        let display = dir.to_string_lossy();
+        crate::util::validate_no_control("unit_dir", &display)?;
+        if !dir.is_absolute() {
+            return Err(crate::Error::invalid_input(format!(
+                "unit_dir must be an absolute path: {display:?}"
+            )));
+        }
+        Ok(dir)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/systemd/user`, falling back to `$HOME/.config/systemd/user` like
+/// `systemd.unit(5)`; relative values are ignored as the XDG spec requires.
+#[cfg(feature = "config")]
+fn user_unit_dir(
+    xdg_config_home: Option<std::ffi::OsString>,
+    home: Option<std::ffi::OsString>,
+) -> crate::Result<std::path::PathBuf> {
+    let absolute = |v: std::ffi::OsString| {
+        let path = std::path::PathBuf::from(v);
+        path.is_absolute().then_some(path)
+    };
+    let config = xdg_config_home
+        .and_then(absolute)
+        .or_else(|| home.and_then(absolute).map(|h| h.join(".config")))
+        .ok_or_else(|| {
+            crate::Error::invalid_input(
+                "cannot resolve the user unit directory: neither XDG_CONFIG_HOME nor HOME is set; set unit_dir",
+            )
+        })?;
+    Ok(config.join("systemd").join("user"))
+}
+
+#[cfg(all(test, feature = "config"))]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn unit_dir_resolves_per_scope() {
+        let opts = UnitBusOptions::default();
+        assert_eq!(
+            opts.resolve_unit_dir(crate::ManagerScope::System).unwrap(),
+            PathBuf::from("/etc/systemd/system")
+        );
+        let custom = UnitBusOptions {
+            unit_dir: Some("/srv/units".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            custom.resolve_unit_dir(crate::ManagerScope::User).unwrap(),
+            PathBuf::from("/srv/units")
+        );
+        let relative = UnitBusOptions {
+            unit_dir: Some("units".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            relative
+                .resolve_unit_dir(crate::ManagerScope::System)
+                .is_err()
+        );
+
+        assert_eq!(
+            user_unit_dir(Some("/cfg".into()), Some("/home/u".into())).unwrap(),
+            PathBuf::from("/cfg/systemd/user")
+        );
+        assert_eq!(
+            user_unit_dir(Some("rel".into()), Some("/home/u".into())).unwrap(),
+            PathBuf::from("/home/u/.config/systemd/user")
+        );
+        assert!(user_unit_dir(None, None).is_err());
+    }
+}
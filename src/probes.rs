@@ -0,0 +1,407 @@
+//! Readiness probes (feature=`probes`): wait until a service accepts connections.
+//!
+//! `ActiveState=active` only says systemd considers a unit started; these probes check that the
+//! service is actually reachable. Each probe retries (100ms, doubling up to 1s) until it succeeds
+//! or `timeout` elapses, then returns `Error::Timeout`.
+
+use crate::{Error, Result};
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const RETRY_INITIAL: Duration = Duration::from_millis(100);
+const RETRY_MAX: Duration = Duration::from_secs(1);
+/// Upper bound for a single connect/request attempt.
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_STATUS_LINE_BYTES: usize = 8 * 1024;
+
+/// Successful probe.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ProbeReport {
+    /// Attempts made, including the successful one.
+    pub attempts: u32,
+    /// Time from the first attempt until success.
+    pub elapsed: Duration,
+}
+
+/// A readiness check, for running probes as part of `Units::restart_and_verify` (see
+/// `VerifyOptions::probe`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Probe {
+    /// See `wait_tcp`.
+    Tcp { addr: String },
+    /// See `wait_unix_socket`.
+    UnixSocket { path: PathBuf },
+    /// See `wait_http`.
+    Http { url: String, expected_status: u16 },
+}
+
+impl Probe {
+    pub fn tcp(addr: impl Into<String>) -> Self {
+        Self::Tcp { addr: addr.into() }
+    }
+
+    pub fn unix_socket(path: impl Into<PathBuf>) -> Self {
+        Self::UnixSocket { path: path.into() }
+    }
+
+    pub fn http(url: impl Into<String>, expected_status: u16) -> Self {
+        Self::Http {
+            url: url.into(),
+            expected_status,
+        }
+    }
+
+    /// Run the probe until it succeeds or `timeout` elapses (`Error::Timeout`).
+    pub async fn wait(&self, timeout: Duration) -> Result<ProbeReport> {
+        match self {
+            Self::Tcp { addr } => wait_tcp(addr, timeout).await,
+            Self::UnixSocket { path } => wait_unix_socket(path.clone(), timeout).await,
+            Self::Http {
+                url,
+                expected_status,
+            } => wait_http(url, *expected_status, timeout).await,
+        }
+    }
+
+    /// Check the probe's arguments without running it.
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            Self::Tcp { addr } if addr.is_empty() => {
+                Err(Error::invalid_input("addr must not be empty"))
+            }
+            Self::UnixSocket { path } if !path.is_absolute() => {
+                Err(Error::invalid_input("unix socket path must be absolute"))
+            }
+            Self::Http {
+                url,
+                expected_status,
+            } => {
+                HttpTarget::parse(url)?;
+                if !(100..=599).contains(expected_status) {
+                    return Err(Error::invalid_input("expected_status must be in 100..=599"));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for Probe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp { addr } => write!(f, "tcp {addr}"),
+            Self::UnixSocket { path } => write!(f, "unix {}", path.display()),
+            Self::Http {
+                url,
+                expected_status,
+            } => write!(f, "http {url} (expect {expected_status})"),
+        }
+    }
+}
+
+/// Wait until a TCP connection to `addr` (`host:port`, resolved on every attempt) succeeds.
+pub async fn wait_tcp(addr: &str, timeout: Duration) -> Result<ProbeReport> {
+    if addr.is_empty() {
+        return Err(Error::invalid_input("addr must not be empty"));
+    }
+    let addr = addr.to_string();
+    retry("wait_tcp", timeout, move |attempt_timeout| {
+        let addr = addr.clone();
+        async move {
+            blocking::unblock(move || tcp_connect(&addr, attempt_timeout).map(|_| true)).await
+        }
+    })
+    .await
+}
+
+/// Wait until a connection to the unix stream socket at `path` succeeds.
+pub async fn wait_unix_socket(path: impl Into<PathBuf>, timeout: Duration) -> Result<ProbeReport> {
+    let path = path.into();
+    if !path.is_absolute() {
+        return Err(Error::invalid_input("unix socket path must be absolute"));
+    }
+    retry("wait_unix_socket", timeout, move |_| {
+        let path = path.clone();
+        async move {
+            blocking::unblock(move || {
+                std::os::unix::net::UnixStream::connect(&path)
+                    .map(|_| true)
+                    .map_err(|e| e.to_string())
+            })
+            .await
+        }
+    })
+    .await
+}
+
+/// Wait until `GET url` answers with `expected_status`.
+///
+/// Only plain `http://` URLs are supported; the response body is not read.
+pub async fn wait_http(url: &str, expected_status: u16, timeout: Duration) -> Result<ProbeReport> {
+    let target = HttpTarget::parse(url)?;
+    if !(100..=599).contains(&expected_status) {
+        return Err(Error::invalid_input("expected_status must be in 100..=599"));
+    }
+    retry("wait_http", timeout, move |attempt_timeout| {
+        let target = target.clone();
+        async move {
+            blocking::unblock(move || {
+                http_status(&target, attempt_timeout).map(|status| status == expected_status)
+            })
+            .await
+        }
+    })
+    .await
+}
+
+/// Run `attempt` until it returns `Ok(true)` or `timeout` elapses. `Ok(false)` and `Err` (the
+/// error text, kept for tracing) both mean "not ready yet".
+async fn retry<F, Fut>(
+    action: &'static str,
+    timeout: Duration,
+    mut attempt: F,
+) -> Result<ProbeReport>
+where
+    F: FnMut(Duration) -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<bool, String>>,
+{
+    if timeout.is_zero() {
+        return Err(Error::invalid_input("timeout must be > 0"));
+    }
+
+    crate::telemetry::traced(action, None, async {
+        let started = Instant::now();
+        let mut attempts = 0u32;
+        let mut delay = RETRY_INITIAL;
+        loop {
+            let remaining = timeout.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                return Err(Error::Timeout { action, timeout });
+            }
+
+            attempts = attempts.saturating_add(1);
+            match attempt(remaining.min(ATTEMPT_TIMEOUT)).await {
+                Ok(true) => {
+                    return Ok(ProbeReport {
+                        attempts,
+                        elapsed: started.elapsed(),
+                    });
+                }
+                Ok(false) => {}
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(action, attempts, error = %_e, "probe not ready");
+                }
+            }
+
+            let remaining = timeout.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                return Err(Error::Timeout { action, timeout });
+            }
+            crate::runtime::sleep(delay.min(remaining)).await;
+            delay = delay.saturating_mul(2).min(RETRY_MAX);
+        }
+    })
+    .await
+}
+
+fn tcp_connect(addr: &str, timeout: Duration) -> std::result::Result<TcpStream, String> {
+    let mut last = format!("{addr}: no addresses");
+    for sa in addr.to_socket_addrs().map_err(|e| format!("{addr}: {e}"))? {
+        match TcpStream::connect_timeout(&sa, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last = format!("{sa}: {e}"),
+        }
+    }
+    Err(last)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct HttpTarget {
+    /// `host:port` to connect to.
+    authority: String,
+    /// `Host` header value.
+    host: String,
+    path: String,
+}
+
+impl HttpTarget {
+    fn parse(url: &str) -> Result<Self> {
+        let Some(rest) = url.strip_prefix("http://") else {
+            return Err(Error::invalid_input(format!(
+                "only http:// URLs are supported: {url}"
+            )));
+        };
+        let (host, path) = match rest.find(['/', '?']) {
+            Some(i) if rest[i..].starts_with('/') => (&rest[..i], &rest[i..]),
+            Some(i) => (&rest[..i], ""),
+            None => (rest, "/"),
+        };
+        let path = if path.is_empty() {
+            format!("/{}", &rest[host.len()..])
+        } else {
+            path.to_string()
+        };
+        if host.is_empty() || host.contains('@') || host.chars().any(char::is_whitespace) {
+            return Err(Error::invalid_input(format!("invalid host in URL: {url}")));
+        }
+        if path.chars().any(|c| c.is_ascii_control() || c == ' ') {
+            return Err(Error::invalid_input(format!("invalid path in URL: {url}")));
+        }
+
+        let has_port = match host.rfind(':') {
+            // `[::1]` alone has no port; `[::1]:80` and `host:80` do.
+            Some(i) => !host[i..].contains(']'),
+            None => false,
+        };
+        let authority = if has_port {
+            host.to_string()
+        } else {
+            format!("{host}:80")
+        };
+        Ok(Self {
+            authority,
+            host: host.to_string(),
+            path,
+        })
+    }
+}
+
+fn http_status(target: &HttpTarget, timeout: Duration) -> std::result::Result<u16, String> {
+    let mut stream = tcp_connect(&target.authority, timeout)?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|()| stream.set_write_timeout(Some(timeout)))
+        .map_err(|e| e.to_string())?;
+
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: unitbus\r\nConnection: close\r\n\r\n",
+        target.path, target.host
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 512];
+    while !buf.contains(&b'\n') && buf.len() < MAX_STATUS_LINE_BYTES {
+        let n = stream.read(&mut chunk).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(chunk.get(..n).unwrap_or_default());
+    }
+    parse_status_line(&buf)
+}
+
+fn parse_status_line(buf: &[u8]) -> std::result::Result<u16, String> {
+    let line = buf.split(|b| *b == b'\n').next().unwrap_or_default();
+    let line = String::from_utf8_lossy(line);
+    let mut parts = line.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some(version), Some(code)) if version.starts_with("HTTP/") => code
+            .parse()
+            .map_err(|_| format!("invalid status line: {}", line.trim_end())),
+        _ => Err(format!("invalid status line: {}", line.trim_end())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn probe_validates_arguments_up_front() {
+        assert!(Probe::tcp("127.0.0.1:80").validate().is_ok());
+        assert!(Probe::tcp("").validate().is_err());
+        assert!(Probe::unix_socket("run/app.sock").validate().is_err());
+        assert!(
+            Probe::http("http://localhost/healthz", 200)
+                .validate()
+                .is_ok()
+        );
+        assert!(Probe::http("https://localhost/", 200).validate().is_err());
+        assert!(Probe::http("http://localhost/", 42).validate().is_err());
+        assert_eq!(
+            Probe::http("http://localhost/", 204).to_string(),
+            "http http://localhost/ (expect 204)"
+        );
+    }
+
+    #[test]
+    fn http_target_parses_host_port_and_path() {
+        let t = HttpTarget::parse("http://127.0.0.1:8080/healthz?full=1").unwrap();
+        assert_eq!(t.authority, "127.0.0.1:8080");
+        assert_eq!(t.host, "127.0.0.1:8080");
+        assert_eq!(t.path, "/healthz?full=1");
+
+        let t = HttpTarget::parse("http://localhost").unwrap();
+        assert_eq!(t.authority, "localhost:80");
+        assert_eq!(t.path, "/");
+
+        let t = HttpTarget::parse("http://[::1]?x").unwrap();
+        assert_eq!(t.authority, "[::1]:80");
+        assert_eq!(t.path, "/?x");
+
+        assert!(HttpTarget::parse("https://example.com/").is_err());
+        assert!(HttpTarget::parse("http:///x").is_err());
+        assert!(HttpTarget::parse("http://a b/").is_err());
+    }
+
+    #[test]
+    fn status_line_parses_code() {
+        assert_eq!(parse_status_line(b"HTTP/1.1 204 No Content\r\n"), Ok(204));
+        assert_eq!(parse_status_line(b"HTTP/1.0 503\r\n"), Ok(503));
+        assert!(parse_status_line(b"SSH-2.0-OpenSSH\r\n").is_err());
+        assert!(parse_status_line(b"").is_err());
+    }
+
+    #[test]
+    fn wait_http_sees_expected_status() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut req = [0u8; 1024];
+            let n = conn.read(&mut req).unwrap();
+            assert!(req[..n].starts_with(b"GET /ready HTTP/1.1\r\n"));
+            conn.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        });
+
+        let report = futures_lite::future::block_on(wait_http(
+            &format!("http://{addr}/ready"),
+            200,
+            Duration::from_secs(5),
+        ))
+        .unwrap();
+        assert_eq!(report.attempts, 1);
+        server.join().unwrap();
+    }
+
+    #[cfg(feature = "rt-async-io")]
+    #[test]
+    fn wait_unix_socket_times_out_when_nothing_listens() {
+        let path = std::env::temp_dir().join(format!("unitbus-probe-{}.sock", std::process::id()));
+        let err =
+            futures_lite::future::block_on(wait_unix_socket(path, Duration::from_millis(250)))
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Timeout {
+                action: "wait_unix_socket",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use zbus::zvariant::OwnedValue;
+
+/// Object path prefix of unit objects; only these are cached (jobs, the manager and other
+/// services are always read fresh).
+const UNIT_PATH_PREFIX: &str = "/org/freedesktop/systemd1/unit/";
+
+type Key = (String, String);
+
+struct Entry {
+    fetched: Instant,
+    last_used: u64,
+    props: HashMap<String, OwnedValue>,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<Key, Entry>,
+    clock: u64,
+}
+
+/// Short-lived LRU cache of `GetAll` replies keyed by (object path, interface).
+///
+/// See `UnitBusOptions.property_cache`. Any mutating call through the same connection clears
+/// it, so it only serves repeated reads in between (e.g. the status read after a job wait).
+pub(crate) struct PropertyCache {
+    ttl: Duration,
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl std::fmt::Debug for PropertyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PropertyCache")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PropertyCache {
+    pub(crate) fn new(opts: &crate::PropertyCacheOptions) -> Option<Self> {
+        if opts.ttl.is_zero() || opts.capacity == 0 {
+            return None;
+        }
+        Some(Self {
+            ttl: opts.ttl,
+            capacity: opts.capacity,
+            state: Mutex::new(State::default()),
+        })
+    }
+
+    pub(crate) fn cacheable(object_path: &str) -> bool {
+        object_path.starts_with(UNIT_PATH_PREFIX)
+    }
+
+    /// A copy of the cached reply if it is younger than the TTL.
+    pub(crate) fn get(
+        &self,
+        object_path: &str,
+        interface: &str,
+    ) -> Option<HashMap<String, OwnedValue>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.clock += 1;
+        let clock = state.clock;
+        let key = (object_path.to_string(), interface.to_string());
+        let entry = state.entries.get_mut(&key)?;
+        if entry.fetched.elapsed() >= self.ttl {
+            state.entries.remove(&key);
+            return None;
+        }
+        entry.last_used = clock;
+        // Values holding file descriptors cannot be cloned; fall back to a fresh read.
+        entry
+            .props
+            .iter()
+            .map(|(k, v)| Some((k.clone(), v.try_clone().ok()?)))
+            .collect()
+    }
+
+    pub(crate) fn insert(
+        &self,
+        object_path: &str,
+        interface: &str,
+        props: &HashMap<String, OwnedValue>,
+    ) {
+        let Some(props) = props
+            .iter()
+            .map(|(k, v)| Some((k.clone(), v.try_clone().ok()?)))
+            .collect::<Option<HashMap<_, _>>>()
+        else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.clock += 1;
+        let clock = state.clock;
+        let ttl = self.ttl;
+        state.entries.retain(|_, e| e.fetched.elapsed() < ttl);
+        if state.entries.len() >= self.capacity {
+            let lru = state
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone());
+            if let Some(lru) = lru {
+                state.entries.remove(&lru);
+            }
+        }
+        state.entries.insert(
+            (object_path.to_string(), interface.to_string()),
+            Entry {
+                fetched: Instant::now(),
+                last_used: clock,
+                props,
+            },
+        );
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn props(state: &str) -> HashMap<String, OwnedValue> {
+        HashMap::from([(
+            "ActiveState".to_string(),
+            OwnedValue::try_from(zbus::zvariant::Value::from(state)).unwrap(),
+        )])
+    }
+
+    fn active_state(props: &HashMap<String, OwnedValue>) -> String {
+        <&str>::try_from(&props["ActiveState"]).unwrap().to_string()
+    }
+
+    fn enabled(ttl: Duration, capacity: usize) -> PropertyCache {
+        PropertyCache::new(&crate::PropertyCacheOptions {
+            ttl,
+            capacity,
+            ..Default::default()
+        })
+        .expect("enabled")
+    }
+
+    const A: &str = "/org/freedesktop/systemd1/unit/a_2eservice";
+    const B: &str = "/org/freedesktop/systemd1/unit/b_2eservice";
+    const C: &str = "/org/freedesktop/systemd1/unit/c_2eservice";
+    const IFACE: &str = "org.freedesktop.systemd1.Unit";
+
+    #[test]
+    fn caches_unit_objects_until_cleared() {
+        assert!(PropertyCache::cacheable(A));
+        assert!(!PropertyCache::cacheable(
+            "/org/freedesktop/systemd1/job/42"
+        ));
+
+        let cache = enabled(Duration::from_secs(60), 8);
+        assert!(cache.get(A, IFACE).is_none());
+        cache.insert(A, IFACE, &props("active"));
+        assert_eq!(active_state(&cache.get(A, IFACE).unwrap()), "active");
+        assert!(cache.get(A, "org.freedesktop.systemd1.Service").is_none());
+
+        cache.clear();
+        assert!(cache.get(A, IFACE).is_none());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_and_expired_entries() {
+        let cache = enabled(Duration::from_secs(60), 2);
+        cache.insert(A, IFACE, &props("active"));
+        cache.insert(B, IFACE, &props("failed"));
+        assert!(cache.get(A, IFACE).is_some());
+        cache.insert(C, IFACE, &props("inactive"));
+        assert!(cache.get(A, IFACE).is_some());
+        assert!(cache.get(B, IFACE).is_none());
+        assert!(cache.get(C, IFACE).is_some());
+
+        let cache = enabled(Duration::from_millis(1), 2);
+        cache.insert(A, IFACE, &props("active"));
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.get(A, IFACE).is_none());
+
+        assert!(
+            PropertyCache::new(&crate::PropertyCacheOptions {
+                ttl: Duration::ZERO,
+                ..Default::default()
+            })
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::types::task::{PtyReader, PtyWriter};
+use crate::{Error, Result};
+
+use std::fs::File;
+
+/// A freshly allocated pseudo-terminal: the master side stays in-process, the slave side is
+/// referenced by path and opened by systemd for the transient unit (`TTYPath=`).
+pub(crate) struct Pty {
+    master: File,
+    pub(crate) tty_path: String,
+}
+
+impl Pty {
+    /// Split the master side into independent reader/writer streams.
+    pub(crate) fn into_streams(self) -> Result<(PtyReader, PtyWriter)> {
+        let read_half = self
+            .master
+            .try_clone()
+            .map_err(|e| map_pty_io("clone pty", e))?;
+        let control = self
+            .master
+            .try_clone()
+            .map_err(|e| map_pty_io("clone pty", e))?;
+        Ok((
+            PtyReader {
+                io: blocking::Unblock::new(read_half),
+            },
+            PtyWriter {
+                io: blocking::Unblock::new(self.master),
+                control,
+            },
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn open_pty() -> Result<Pty> {
+    use rustix::pty::OpenptFlags;
+
+    let fd = rustix::pty::openpt(OpenptFlags::RDWR | OpenptFlags::NOCTTY | OpenptFlags::CLOEXEC)
+        .map_err(|e| map_pty_io("open /dev/ptmx", e.into()))?;
+    rustix::pty::grantpt(&fd).map_err(|e| map_pty_io("grantpt", e.into()))?;
+    rustix::pty::unlockpt(&fd).map_err(|e| map_pty_io("unlockpt", e.into()))?;
+    let name =
+        rustix::pty::ptsname(&fd, Vec::new()).map_err(|e| map_pty_io("ptsname", e.into()))?;
+    let tty_path = name.into_string().map_err(|e| Error::IoError {
+        context: format!("pty path is not valid UTF-8: {e}"),
+    })?;
+
+    Ok(Pty {
+        master: File::from(fd),
+        tty_path,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn open_pty() -> Result<Pty> {
+    Err(Error::BackendUnavailable {
+        backend: "pty",
+        detail: "interactive tasks are only supported on Linux".to_string(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_window_size(master: &File, rows: u16, cols: u16) -> Result<()> {
+    let size = rustix::termios::Winsize {
+        ws_row: rows,
+        ws_col: cols,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    rustix::termios::tcsetwinsize(master, size).map_err(|e| map_pty_io("resize pty", e.into()))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn set_window_size(_master: &File, _rows: u16, _cols: u16) -> Result<()> {
+    Err(Error::BackendUnavailable {
+        backend: "pty",
+        detail: "interactive tasks are only supported on Linux".to_string(),
+    })
+}
+
+fn map_pty_io(context: &'static str, e: std::io::Error) -> Error {
+    if e.kind() == std::io::ErrorKind::PermissionDenied {
+        return Error::PermissionDenied {
+            action: "open_pty",
+            detail: format!("{context}: {e}"),
+        };
+    }
+    Error::IoError {
+        context: format!("{context}: {e}"),
+    }
+}
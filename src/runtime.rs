@@ -1,11 +1,9 @@
-#[cfg(feature = "blocking")]
-use crate::Result;
-
-#[cfg(all(feature = "blocking", feature = "rt-tokio"))]
-use crate::Error;
+use crate::{Error, Result};
 
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Poll, Waker};
 use std::time::Duration;
 
 pub(crate) type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
@@ -22,6 +20,50 @@ pub(crate) fn sleep(duration: Duration) -> BoxFuture<()> {
     Box::pin(tokio::time::sleep(duration))
 }
 
+/// Runs blocking `f` on a dedicated thread and awaits its result.
+///
+/// For the occasional file read in the core API. Feature-gated paths with heavier I/O use the
+/// `blocking` thread pool instead, so the core does not depend on it.
+pub(crate) async fn unblock<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    type Slot<T> = (Option<std::thread::Result<Result<T>>>, Option<Waker>);
+
+    let slot: Arc<Mutex<Slot<T>>> = Arc::new(Mutex::new((None, None)));
+    let worker = Arc::clone(&slot);
+    std::thread::Builder::new()
+        .name("unitbus-unblock".to_string())
+        .spawn(move || {
+            let out = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+            let waker = {
+                let mut slot = worker.lock().unwrap_or_else(PoisonError::into_inner);
+                slot.0 = Some(out);
+                slot.1.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        })
+        .map_err(|e| Error::IoError {
+            context: format!("spawn blocking worker: {e}"),
+        })?;
+
+    std::future::poll_fn(|cx| {
+        let mut slot = slot.lock().unwrap_or_else(PoisonError::into_inner);
+        match slot.0.take() {
+            Some(Ok(out)) => Poll::Ready(out),
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                slot.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
 #[cfg(feature = "blocking")]
 pub(crate) fn block_on_result<T>(future: impl Future<Output = Result<T>>) -> Result<T> {
     #[cfg(feature = "rt-async-io")]
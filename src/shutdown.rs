@@ -0,0 +1,319 @@
+//! Graceful shutdown for agents embedding unitbus.
+//!
+//! A `ShutdownToken` is a cloneable cancellation flag. Job and task waits accept it via
+//! `wait_with_shutdown` and return `Error::Cancelled` once it fires; watchers accept it via
+//! `with_shutdown` and end their stream (`next()` returns `Ok(None)`).
+//!
+//! `GracefulShutdown` (feature=`shutdown`) fires a token on `SIGTERM`/`SIGINT` and tells systemd
+//! through `sd_notify` that the service is stopping, so an agent can wind down inside its unit's
+//! `TimeoutStopSec=`.
+
+use crate::{Error, Result};
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Poll, Waker};
+
+/// Cloneable cancellation token; every clone observes the same `cancel()`.
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownToken {
+    state: Arc<Mutex<TokenState>>,
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: bool,
+    /// Wakers of pending `cancelled()` calls by key; each removes its own entry when dropped.
+    wakers: std::collections::BTreeMap<u64, Waker>,
+    next_key: u64,
+}
+
+impl ShutdownToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fire the token, waking every pending `cancelled()`. Idempotent.
+    pub fn cancel(&self) {
+        let wakers = {
+            let mut st = self.lock();
+            st.cancelled = true;
+            std::mem::take(&mut st.wakers)
+        };
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.lock().cancelled
+    }
+
+    /// Resolve once the token has fired.
+    ///
+    /// Dropping the future before then deregisters it, so repeated races against a long-lived
+    /// token do not accumulate wakers.
+    pub async fn cancelled(&self) {
+        let mut waiter = Waiter {
+            token: self,
+            key: None,
+        };
+        futures_util::future::poll_fn(|cx| waiter.poll(cx)).await
+    }
+
+    /// Run `fut` unless the token fires first, in which case it is dropped and
+    /// `Error::Cancelled { action }` is returned.
+    pub async fn run<T>(
+        &self,
+        action: &'static str,
+        fut: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        match race(Some(self), fut).await {
+            Some(result) => result,
+            None => Err(Error::Cancelled { action }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TokenState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A pending `ShutdownToken::cancelled`, registered under `key` once first polled.
+struct Waiter<'a> {
+    token: &'a ShutdownToken,
+    key: Option<u64>,
+}
+
+impl Waiter<'_> {
+    fn poll(&mut self, cx: &mut std::task::Context<'_>) -> Poll<()> {
+        let mut st = self.token.lock();
+        if st.cancelled {
+            return Poll::Ready(());
+        }
+        match self.key.and_then(|key| st.wakers.get_mut(&key)) {
+            Some(waker) => waker.clone_from(cx.waker()),
+            None => {
+                let key = st.next_key;
+                st.next_key = st.next_key.wrapping_add(1);
+                st.wakers.insert(key, cx.waker().clone());
+                self.key = Some(key);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.token.lock().wakers.remove(&key);
+        }
+    }
+}
+
+/// Run `fut` until it completes (`Some`) or `token` fires (`None`); without a token this is just
+/// `fut`.
+pub(crate) async fn race<T>(
+    token: Option<&ShutdownToken>,
+    fut: impl Future<Output = T>,
+) -> Option<T> {
+    let Some(token) = token else {
+        return Some(fut.await);
+    };
+    let fut = std::pin::pin!(fut);
+    let cancelled = std::pin::pin!(token.cancelled());
+    match futures_util::future::select(fut, cancelled).await {
+        futures_util::future::Either::Left((value, _)) => Some(value),
+        futures_util::future::Either::Right(_) => None,
+    }
+}
+
+/// What ended `GracefulShutdown::wait`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ShutdownReason {
+    /// `SIGTERM` (what systemd sends on `systemctl stop`).
+    Terminate,
+    /// `SIGINT`.
+    Interrupt,
+    /// The token was cancelled directly (`GracefulShutdown::trigger` or `ShutdownToken::cancel`).
+    Requested,
+}
+
+/// Turns `SIGTERM`/`SIGINT` into a `ShutdownToken` (feature=`shutdown`).
+///
+/// Creating it replaces the default signal disposition, so the process is no longer killed by
+/// `SIGTERM`; drive `wait()` (e.g. in the agent's main task) to react. When a signal arrives the
+/// watcher sends `STOPPING=1` to `$NOTIFY_SOCKET` (if set) and fires the token.
+#[cfg(feature = "shutdown")]
+pub struct GracefulShutdown {
+    token: ShutdownToken,
+    signals: Option<async_signal::Signals>,
+}
+
+#[cfg(feature = "shutdown")]
+impl std::fmt::Debug for GracefulShutdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GracefulShutdown")
+            .field("token", &self.token)
+            .field("listening", &self.signals.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "shutdown")]
+impl GracefulShutdown {
+    /// Start listening for `SIGTERM` and `SIGINT`.
+    pub fn new() -> Result<Self> {
+        use async_signal::{Signal, Signals};
+
+        let signals = Signals::new([Signal::Term, Signal::Int]).map_err(|e| Error::IoError {
+            context: format!("register shutdown signal handlers: {e}"),
+        })?;
+        Ok(Self {
+            token: ShutdownToken::new(),
+            signals: Some(signals),
+        })
+    }
+
+    /// Token fired when shutdown begins; hand clones to job waits, task waits and watchers.
+    pub fn token(&self) -> ShutdownToken {
+        self.token.clone()
+    }
+
+    /// Begin shutdown without a signal (e.g. after a fatal error in the agent).
+    pub fn trigger(&self) {
+        self.token.cancel();
+    }
+
+    /// Wait for a shutdown signal (or `trigger()`), then notify systemd and fire the token.
+    ///
+    /// Failing to reach `$NOTIFY_SOCKET` does not prevent the token from firing.
+    pub async fn wait(&mut self) -> ShutdownReason {
+        use async_signal::Signal;
+        use futures_util::StreamExt;
+
+        let reason = loop {
+            let Some(signals) = self.signals.as_mut() else {
+                self.token.cancelled().await;
+                break ShutdownReason::Requested;
+            };
+            let token = self.token.clone();
+            match race(Some(&token), signals.next()).await {
+                None => break ShutdownReason::Requested,
+                Some(Some(Ok(Signal::Int))) => break ShutdownReason::Interrupt,
+                Some(Some(Ok(_))) => break ShutdownReason::Terminate,
+                Some(Some(Err(_))) => continue,
+                // The signal stream is gone; only a direct trigger can end the wait now.
+                Some(None) => self.signals = None,
+            }
+        };
+
+        let notified = notify("STOPPING=1");
+        #[cfg(feature = "tracing")]
+        tracing::info!(?reason, ?notified, "graceful shutdown started");
+        #[cfg(not(feature = "tracing"))]
+        let _ = notified;
+
+        self.token.cancel();
+        reason
+    }
+
+    /// Ask systemd for `extra` more time before it escalates to `SIGKILL` (`EXTEND_TIMEOUT_USEC`).
+    ///
+    /// Returns `Ok(false)` when the process was not started with `$NOTIFY_SOCKET` (e.g. outside a
+    /// `Type=notify` unit, where the request would be ignored anyway).
+    pub fn extend_timeout(&self, extra: std::time::Duration) -> Result<bool> {
+        if extra.is_zero() {
+            return Err(Error::invalid_input("extra must be > 0"));
+        }
+        notify(&format!("EXTEND_TIMEOUT_USEC={}", extra.as_micros()))
+    }
+}
+
+/// Send one `sd_notify` datagram; `Ok(false)` when `$NOTIFY_SOCKET` is unset.
+#[cfg(all(feature = "shutdown", target_os = "linux"))]
+fn notify(state: &str) -> Result<bool> {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::{SocketAddr, UnixDatagram};
+
+    let Some(socket) = std::env::var_os("NOTIFY_SOCKET") else {
+        return Ok(false);
+    };
+    let io_err = |e: std::io::Error| Error::IoError {
+        context: format!("sd_notify {state}: {e}"),
+    };
+    let socket = socket.to_string_lossy().into_owned();
+    let addr = match socket.strip_prefix('@') {
+        Some(name) => SocketAddr::from_abstract_name(name.as_bytes()),
+        None => SocketAddr::from_pathname(&socket),
+    }
+    .map_err(io_err)?;
+    let sock = UnixDatagram::unbound().map_err(io_err)?;
+    sock.send_to_addr(state.as_bytes(), &addr).map_err(io_err)?;
+    Ok(true)
+}
+
+#[cfg(all(feature = "shutdown", not(target_os = "linux")))]
+fn notify(_state: &str) -> Result<bool> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn token_aborts_pending_work_and_clones_share_state() {
+        let token = ShutdownToken::new();
+        let clone = token.clone();
+
+        let done = futures_lite::future::block_on(token.run("work", async { Ok(7) }));
+        assert_eq!(done.expect("ok"), 7);
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+        let err = futures_lite::future::block_on(
+            token.run("wait_job", futures_util::future::pending::<Result<()>>()),
+        )
+        .expect_err("cancelled");
+        let Error::Cancelled { action } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(action, "wait_job");
+        assert_eq!(
+            futures_lite::future::block_on(race(None, async { 1 })),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn dropped_waits_deregister_their_waker() {
+        let token = ShutdownToken::new();
+        for _ in 0..100 {
+            let done = futures_lite::future::block_on(race(Some(&token), async { 1 }));
+            assert_eq!(done, Some(1));
+            let mut wait = std::pin::pin!(token.cancelled());
+            assert!(
+                futures_lite::future::block_on(futures_lite::future::poll_once(&mut wait))
+                    .is_none()
+            );
+            assert_eq!(token.lock().wakers.len(), 1);
+        }
+        assert!(token.lock().wakers.is_empty());
+
+        let mut wait = Box::pin(token.cancelled());
+        assert!(
+            futures_lite::future::block_on(futures_lite::future::poll_once(&mut wait)).is_none()
+        );
+        token.cancel();
+        assert!(
+            futures_lite::future::block_on(futures_lite::future::poll_once(&mut wait)).is_some()
+        );
+    }
+}
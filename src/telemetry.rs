@@ -0,0 +1,186 @@
+use crate::Result;
+
+use std::future::Future;
+
+/// Run one public operation inside an `unitbus.op` span (feature=`tracing`).
+///
+/// The span carries `action`, `unit`, `job_path` (recorded via `record_job_path`), `duration_ms`
+/// and `outcome`. On completion a single event is emitted with a `monotonic_counter.` and a
+/// `histogram.` field so metric layers (e.g. `tracing-opentelemetry`) can aggregate operations.
+/// Without the `tracing` feature this simply awaits `fut`.
+pub(crate) async fn traced<T>(
+    action: &'static str,
+    unit: Option<&str>,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        let span = tracing::info_span!(
+            "unitbus.op",
+            action,
+            unit = unit.unwrap_or(""),
+            job_path = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+            outcome = tracing::field::Empty,
+        );
+        let started = std::time::Instant::now();
+        let res = fut.instrument(span.clone()).await;
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let outcome = match &res {
+            Ok(_) => "ok",
+            Err(e) => error_kind(e),
+        };
+        span.record("duration_ms", duration_ms);
+        span.record("outcome", outcome);
+
+        span.in_scope(|| match &res {
+            Ok(_) => tracing::info!(
+                monotonic_counter.unitbus_operations = 1u64,
+                histogram.unitbus_operation_duration_ms = duration_ms,
+                action,
+                outcome,
+                "operation finished"
+            ),
+            Err(e) => tracing::warn!(
+                monotonic_counter.unitbus_operations = 1u64,
+                histogram.unitbus_operation_duration_ms = duration_ms,
+                action,
+                outcome,
+                error = %e,
+                "operation failed"
+            ),
+        });
+        res
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (action, unit);
+        fut.await
+    }
+}
+
+/// `traced`, plus an `AuditRecord` to `UnitBusOptions.audit_sink` for mutating operations.
+pub(crate) async fn audited<T>(
+    inner: &crate::Inner,
+    action: &'static str,
+    unit: Option<&str>,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(sink) = inner.opts.audit_sink.as_ref() else {
+        return traced(action, unit, fut).await;
+    };
+
+    let started_at = std::time::SystemTime::now();
+    let started = std::time::Instant::now();
+    let res = traced(action, unit, fut).await;
+    let actor = crate::AuditActor::current(inner.bus.unique_name());
+    sink.record(&crate::AuditRecord::new(
+        actor,
+        action,
+        unit,
+        started_at,
+        started.elapsed(),
+        &res,
+    ));
+    res
+}
+
+/// Attach a job object path to the current operation span.
+pub(crate) fn record_job_path(_job_path: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("job_path", _job_path);
+}
+
+/// Render argv for logs according to `UnitBusOptions.trace_redaction`.
+#[cfg(all(feature = "tracing", feature = "tasks"))]
+pub(crate) fn display_argv(opts: &crate::UnitBusOptions, argv: &[String]) -> String {
+    match opts.trace_redaction {
+        crate::TraceRedaction::Off => format!("{argv:?}"),
+        crate::TraceRedaction::Redact => format!("<{} args redacted>", argv.len()),
+    }
+}
+
+/// Render environment variables for logs according to `UnitBusOptions.trace_redaction`.
+///
+/// Keys are always logged; values only when redaction is off.
+#[cfg(all(feature = "tracing", feature = "tasks"))]
+pub(crate) fn display_env(
+    opts: &crate::UnitBusOptions,
+    env: &std::collections::BTreeMap<String, String>,
+) -> String {
+    let pairs: Vec<String> = env
+        .iter()
+        .map(|(k, v)| match opts.trace_redaction {
+            crate::TraceRedaction::Off => format!("{k}={v}"),
+            crate::TraceRedaction::Redact => format!("{k}=<redacted>"),
+        })
+        .collect();
+    pairs.join(" ")
+}
+
+pub(crate) fn error_kind(e: &crate::Error) -> &'static str {
+    use crate::Error;
+
+    match e {
+        Error::InvalidInput { .. } => "invalid_input",
+        Error::PermissionDenied { .. } => "permission_denied",
+        Error::UnitNotFound { .. } => "unit_not_found",
+        Error::JobTimeout { .. } => "job_timeout",
+        Error::Timeout { .. } => "timeout",
+        Error::JournalTimeout { .. } => "journal_timeout",
+        Error::BackendUnavailable { .. } => "backend_unavailable",
+        Error::DbusError { .. } => "dbus_error",
+        Error::IoError { .. } => "io_error",
+        Error::ParseError { .. } => "parse_error",
+        Error::ConfigMismatch { .. } => "config_mismatch",
+        Error::ProcessError { .. } => "process_error",
+        Error::Cancelled { .. } => "cancelled",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    #[cfg(all(feature = "tracing", feature = "tasks"))]
+    #[test]
+    fn redaction_hides_argv_and_env_values() {
+        use crate::{TraceRedaction, UnitBusOptions};
+
+        let redact = UnitBusOptions::default();
+        let off = UnitBusOptions {
+            trace_redaction: TraceRedaction::Off,
+            ..Default::default()
+        };
+        let argv = vec!["/bin/sh".to_string(), "--token=secret".to_string()];
+        let mut env = std::collections::BTreeMap::new();
+        env.insert("API_KEY".to_string(), "hunter2".to_string());
+
+        let argv_redacted = super::display_argv(&redact, &argv);
+        let env_redacted = super::display_env(&redact, &env);
+        assert_eq!(argv_redacted, "<2 args redacted>");
+        assert_eq!(env_redacted, "API_KEY=<redacted>");
+        assert!(!env_redacted.contains("hunter2"));
+
+        assert!(super::display_argv(&off, &argv).contains("--token=secret"));
+        assert_eq!(super::display_env(&off, &env), "API_KEY=hunter2");
+    }
+
+    #[test]
+    fn traced_passes_through_result() {
+        let ok = futures_lite::future::block_on(super::traced("test", Some("x.service"), async {
+            Ok(7u32)
+        }));
+        assert_eq!(ok.unwrap(), 7);
+
+        let err = futures_lite::future::block_on(super::traced::<()>("test", None, async {
+            Err(crate::Error::invalid_input("nope"))
+        }));
+        assert!(matches!(err, Err(crate::Error::InvalidInput { .. })));
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// Receiver of `AuditRecord`s, set via `UnitBusOptions.audit_sink`.
+///
+/// `record` is called synchronously once the operation has finished, so implementations should
+/// hand the record off (channel, buffered writer) rather than block.
+pub trait AuditSink: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+impl fmt::Debug for dyn AuditSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuditSink")
+    }
+}
+
+/// Identity of the process performing an audited operation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuditActor {
+    /// Real uid of this process (`None` if `/proc/self` is unreadable).
+    pub uid: Option<u32>,
+    pub pid: u32,
+    /// Unique D-Bus name of the connection (e.g. `:1.42`), as systemd and polkit see the caller.
+    pub bus_name: Option<String>,
+}
+
+impl AuditActor {
+    pub(crate) fn current(bus_name: Option<String>) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            uid: std::fs::metadata("/proc/self").ok().map(|m| m.uid()),
+            pid: std::process::id(),
+            bus_name,
+        }
+    }
+}
+
+/// How an audited operation ended.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "outcome", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuditOutcome {
+    /// The operation call succeeded (a start job may still fail; see its `JobOutcome`).
+    Ok,
+    Failed {
+        /// Error variant, e.g. `permission_denied`.
+        kind: &'static str,
+        message: String,
+    },
+}
+
+/// One mutating operation (unit start/stop, config write, task launch, ...).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuditRecord {
+    pub actor: AuditActor,
+    /// Operation name, e.g. `start`, `apply_dropin`, `run_task`.
+    pub action: &'static str,
+    pub unit: Option<String>,
+    pub started_at: SystemTime,
+    pub duration: Duration,
+    pub outcome: AuditOutcome,
+}
+
+impl AuditRecord {
+    pub(crate) fn new<T>(
+        actor: AuditActor,
+        action: &'static str,
+        unit: Option<&str>,
+        started_at: SystemTime,
+        duration: Duration,
+        res: &crate::Result<T>,
+    ) -> Self {
+        let outcome = match res {
+            Ok(_) => AuditOutcome::Ok,
+            Err(e) => AuditOutcome::Failed {
+                kind: crate::telemetry::error_kind(e),
+                message: e.to_string(),
+            },
+        };
+        Self {
+            actor,
+            action,
+            unit: unit.map(str::to_string),
+            started_at,
+            duration,
+            outcome,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(AuditRecord);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn record_maps_result_to_outcome() {
+        let actor = AuditActor::current(Some(":1.7".to_string()));
+        assert_eq!(actor.pid, std::process::id());
+
+        let ok = AuditRecord::new(
+            actor.clone(),
+            "start",
+            Some("app.service"),
+            SystemTime::UNIX_EPOCH,
+            Duration::from_millis(3),
+            &Ok(()),
+        );
+        assert_eq!(ok.outcome, AuditOutcome::Ok);
+        assert_eq!(ok.unit.as_deref(), Some("app.service"));
+
+        let err = AuditRecord::new::<()>(
+            actor,
+            "apply_dropin",
+            None,
+            SystemTime::UNIX_EPOCH,
+            Duration::ZERO,
+            &Err(crate::Error::UnitNotFound {
+                unit: "x.service".to_string(),
+            }),
+        );
+        let AuditOutcome::Failed { kind, message } = err.outcome else {
+            panic!("expected failure");
+        };
+        assert_eq!(kind, "unit_not_found");
+        assert!(message.contains("x.service"));
+    }
+
+    #[test]
+    fn options_with_sink_are_debug() {
+        #[derive(Default)]
+        struct Collect(std::sync::Mutex<Vec<&'static str>>);
+        impl AuditSink for Collect {
+            fn record(&self, record: &AuditRecord) {
+                self.0.lock().unwrap().push(record.action);
+            }
+        }
+
+        let opts = crate::UnitBusOptions {
+            audit_sink: Some(std::sync::Arc::new(Collect::default())),
+            ..Default::default()
+        };
+        assert!(format!("{opts:?}").contains("audit_sink: Some(AuditSink)"));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::fsutil::io_error;
+use crate::types::journal::{JournalCursor, JournalResult};
+use crate::{Error, Result};
+
+use std::fmt;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+/// Future returned by `CursorStore` methods.
+pub type CursorFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Persistent journal cursor per key (unit name), used by `Journal::query_since_bookmark` and
+/// `Journal::forward`.
+///
+/// Methods are awaited on the caller's executor, so implementations doing blocking I/O should
+/// move it off the executor, as `FsCursorStore` does.
+pub trait CursorStore: Send + Sync {
+    /// Last saved cursor for `key`, or `None` if nothing was saved yet.
+    fn load<'a>(&'a self, key: &'a str) -> CursorFuture<'a, Option<JournalCursor>>;
+    /// Replace the cursor for `key`.
+    fn save<'a>(&'a self, key: &'a str, cursor: &'a str) -> CursorFuture<'a, ()>;
+}
+
+impl fmt::Debug for dyn CursorStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CursorStore")
+    }
+}
+
+/// A page returned by `Journal::query_since_bookmark`; the bookmark only moves on `commit`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct BookmarkPage {
+    pub result: JournalResult,
+    /// Store key the page was read for (the canonical unit name).
+    pub key: String,
+}
+
+impl BookmarkPage {
+    /// Advance the bookmark past this page. Call once the entries are processed, so a collector
+    /// that crashes mid-page reads it again on restart (at-least-once). A page without entries
+    /// leaves the bookmark unchanged.
+    pub async fn commit(&self, store: &dyn CursorStore) -> Result<()> {
+        match &self.result.next_cursor {
+            Some(cursor) => store.save(&self.key, cursor).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// `CursorStore` keeping one `<key>.cursor` file per key in a directory.
+///
+/// Files are replaced atomically (write to a temporary file, then rename), so a crash leaves
+/// either the old or the new cursor. The directory is created on first save.
+///
+/// Reads and writes (including the fsync) run on a helper thread, not on the executor.
+#[derive(Clone, Debug)]
+pub struct FsCursorStore {
+    dir: PathBuf,
+}
+
+impl FsCursorStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        crate::util::validate_no_control("cursor key", key)?;
+        if key.is_empty() || key.starts_with('.') || key.contains('/') || key.contains('\\') {
+            return Err(Error::invalid_input(format!("invalid cursor key: {key}")));
+        }
+        Ok(self.dir.join(format!("{key}.cursor")))
+    }
+
+    fn load_file(path: &Path) -> Result<Option<JournalCursor>> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => {
+                let cursor = s.trim();
+                Ok((!cursor.is_empty()).then(|| cursor.to_string()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("read cursor", path, e)),
+        }
+    }
+
+    fn save_file(dir: &Path, path: &Path, tmp: &Path, cursor: &str) -> Result<()> {
+        std::fs::create_dir_all(dir).map_err(|e| io_error("create cursor dir", dir, e))?;
+
+        let write = || -> std::io::Result<()> {
+            let mut f = std::fs::File::create(tmp)?;
+            f.write_all(cursor.as_bytes())?;
+            f.write_all(b"\n")?;
+            f.sync_all()?;
+            std::fs::rename(tmp, path)
+        };
+        write().map_err(|e| {
+            let _ = std::fs::remove_file(tmp);
+            io_error("write cursor", path, e)
+        })
+    }
+}
+
+impl CursorStore for FsCursorStore {
+    fn load<'a>(&'a self, key: &'a str) -> CursorFuture<'a, Option<JournalCursor>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            crate::runtime::unblock(move || Self::load_file(&path)).await
+        })
+    }
+
+    fn save<'a>(&'a self, key: &'a str, cursor: &'a str) -> CursorFuture<'a, ()> {
+        Box::pin(async move {
+            crate::util::validate_no_control("cursor", cursor)?;
+            let path = self.path(key)?;
+            let dir = self.dir.clone();
+            let tmp = self.dir.join(format!(".{key}.cursor.tmp"));
+            let cursor = cursor.to_string();
+            crate::runtime::unblock(move || Self::save_file(&dir, &path, &tmp, &cursor)).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn fs_store_round_trips_and_rejects_bad_keys() {
+        let dir = std::env::temp_dir().join(format!("unitbus-cursors-{}", std::process::id()));
+        let store = FsCursorStore::new(&dir);
+
+        assert_eq!(block_on(store.load("app.service")).unwrap(), None);
+        block_on(store.save("app.service", "s=abc;i=1")).unwrap();
+        block_on(store.save("app.service", "s=abc;i=2")).unwrap();
+        assert_eq!(
+            block_on(store.load("app.service")).unwrap().as_deref(),
+            Some("s=abc;i=2")
+        );
+        assert!(dir.join("app.service.cursor").exists());
+
+        assert!(block_on(store.load("../etc/passwd")).is_err());
+        assert!(block_on(store.save(".hidden", "x")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn page_commit_saves_only_a_cursor() {
+        let dir = std::env::temp_dir().join(format!("unitbus-pages-{}", std::process::id()));
+        let store = FsCursorStore::new(&dir);
+
+        let mut page = BookmarkPage {
+            result: JournalResult {
+                entries: Vec::new(),
+                next_cursor: None,
+                truncated: false,
+                stats: Default::default(),
+            },
+            key: "app.service".to_string(),
+        };
+        block_on(page.commit(&store)).unwrap();
+        assert_eq!(block_on(store.load("app.service")).unwrap(), None);
+
+        page.result.next_cursor = Some("s=abc;i=7".to_string());
+        block_on(page.commit(&store)).unwrap();
+        assert_eq!(
+            block_on(store.load("app.service")).unwrap().as_deref(),
+            Some("s=abc;i=7")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,436 @@
+use crate::Result;
+use crate::types::lint::LintStrictness;
+use crate::types::unit_file::{HardeningLevel, ServiceType, ServiceUnitSpec};
+use crate::types::values::{SystemdDuration, SystemdSize};
+
+use std::marker::PhantomData;
+
+/// Builder state: the required argv (`exec`/`argv`) has not been set yet.
+#[derive(Clone, Copy, Debug)]
+pub struct ExecMissing;
+
+/// Builder state: the required argv has been set; `build()` is available.
+#[derive(Clone, Copy, Debug)]
+pub struct ExecSet;
+
+fn collect_argv<I, S>(argv: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    argv.into_iter().map(Into::into).collect()
+}
+
+/// Fluent builder for `ServiceUnitSpec` (see `ServiceUnitSpec::builder`).
+///
+/// `build()` only exists once `exec(...)` has been called; list setters (`after`, `wanted_by`,
+/// `env`, ...) append and may be called repeatedly.
+#[derive(Clone, Debug)]
+pub struct ServiceUnitSpecBuilder<E = ExecMissing> {
+    spec: ServiceUnitSpec,
+    _exec: PhantomData<E>,
+}
+
+impl ServiceUnitSpec {
+    /// Start building a spec; `exec(...)` must be called before `build()`.
+    pub fn builder() -> ServiceUnitSpecBuilder<ExecMissing> {
+        ServiceUnitSpecBuilder {
+            spec: ServiceUnitSpec::default(),
+            _exec: PhantomData,
+        }
+    }
+}
+
+impl ServiceUnitSpecBuilder<ExecMissing> {
+    /// Set the `ExecStart=` argv.
+    pub fn exec<I, S>(mut self, argv: I) -> ServiceUnitSpecBuilder<ExecSet>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.spec.exec_start = collect_argv(argv);
+        ServiceUnitSpecBuilder {
+            spec: self.spec,
+            _exec: PhantomData,
+        }
+    }
+}
+
+impl ServiceUnitSpecBuilder<ExecSet> {
+    /// Validate (by rendering) and return the spec.
+    pub fn build(self) -> Result<ServiceUnitSpec> {
+        self.spec.render()?;
+        Ok(self.spec)
+    }
+}
+
+impl<E> ServiceUnitSpecBuilder<E> {
+    /// Unit name (shorthand names are canonicalized to `<name>.service`).
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.spec.unit = unit.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.spec.description = Some(description.into());
+        self
+    }
+
+    pub fn after(mut self, unit: impl Into<String>) -> Self {
+        self.spec.after.push(unit.into());
+        self
+    }
+
+    pub fn wants(mut self, unit: impl Into<String>) -> Self {
+        self.spec.wants.push(unit.into());
+        self
+    }
+
+    pub fn requires(mut self, unit: impl Into<String>) -> Self {
+        self.spec.requires.push(unit.into());
+        self
+    }
+
+    /// Capture stdout/stderr, keeping at most `max_bytes` per stream (see
+    /// `TaskSpec.capture_output`).
+    pub fn capture_output(mut self, max_bytes: u32) -> Self {
+        self.spec.capture_output = true;
+        self.spec.max_output_bytes = max_bytes;
+        self
+    }
+
+    pub fn service_type(mut self, service_type: ServiceType) -> Self {
+        self.spec.service_type = Some(service_type);
+        self
+    }
+
+    /// Append an `ExecStartPre=` argv.
+    pub fn exec_start_pre<I, S>(mut self, argv: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.spec.exec_start_pre.push(collect_argv(argv));
+        self
+    }
+
+    /// Append an `ExecStartPost=` argv.
+    pub fn exec_start_post<I, S>(mut self, argv: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.spec.exec_start_post.push(collect_argv(argv));
+        self
+    }
+
+    pub fn working_directory(mut self, dir: impl Into<String>) -> Self {
+        self.spec.working_directory = Some(dir.into());
+        self
+    }
+
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.spec.user = Some(user.into());
+        self
+    }
+
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.spec.group = Some(group.into());
+        self
+    }
+
+    /// Add one credential (see `Credential`).
+    pub fn credential(mut self, credential: crate::Credential) -> Self {
+        self.spec.credentials.push(credential);
+        self
+    }
+
+    /// Set `DynamicUser=yes`.
+    pub fn dynamic_user(mut self) -> Self {
+        self.spec.dynamic_user = true;
+        self
+    }
+
+    /// Append a `StateDirectory=` entry (relative to `/var/lib`).
+    pub fn state_directory(mut self, dir: impl Into<String>) -> Self {
+        self.spec.state_directory.push(dir.into());
+        self
+    }
+
+    /// Append a `CacheDirectory=` entry (relative to `/var/cache`).
+    pub fn cache_directory(mut self, dir: impl Into<String>) -> Self {
+        self.spec.cache_directory.push(dir.into());
+        self
+    }
+
+    /// Append a `LogsDirectory=` entry (relative to `/var/log`).
+    pub fn logs_directory(mut self, dir: impl Into<String>) -> Self {
+        self.spec.logs_directory.push(dir.into());
+        self
+    }
+
+    /// Append a `RuntimeDirectory=` entry (relative to `/run`).
+    pub fn runtime_directory(mut self, dir: impl Into<String>) -> Self {
+        self.spec.runtime_directory.push(dir.into());
+        self
+    }
+
+    /// Add one environment variable.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.spec.environment.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn restart(mut self, restart: impl Into<String>) -> Self {
+        self.spec.restart = Some(restart.into());
+        self
+    }
+
+    /// `RestartSec=`; plain integers are seconds.
+    pub fn restart_sec(mut self, v: impl Into<SystemdDuration>) -> Self {
+        self.spec.restart_sec = Some(v.into());
+        self
+    }
+
+    pub fn timeout_start_sec(mut self, v: impl Into<SystemdDuration>) -> Self {
+        self.spec.timeout_start_sec = Some(v.into());
+        self
+    }
+
+    pub fn timeout_stop_sec(mut self, v: impl Into<SystemdDuration>) -> Self {
+        self.spec.timeout_stop_sec = Some(v.into());
+        self
+    }
+
+    /// `RuntimeMaxSec=`; plain integers are seconds.
+    pub fn runtime_max_sec(mut self, v: impl Into<SystemdDuration>) -> Self {
+        self.spec.runtime_max_sec = Some(v.into());
+        self
+    }
+
+    pub fn runtime_randomized_extra_sec(mut self, v: impl Into<SystemdDuration>) -> Self {
+        self.spec.runtime_randomized_extra_sec = Some(v.into());
+        self
+    }
+
+    pub fn memory_high(mut self, v: SystemdSize) -> Self {
+        self.spec.memory_high = Some(v);
+        self
+    }
+
+    pub fn memory_max(mut self, v: SystemdSize) -> Self {
+        self.spec.memory_max = Some(v);
+        self
+    }
+
+    /// `CPUQuota=` in percent of one CPU.
+    pub fn cpu_quota_percent(mut self, percent: u32) -> Self {
+        self.spec.cpu_quota = Some(SystemdSize::Percent(percent));
+        self
+    }
+
+    pub fn standard_output(mut self, v: impl Into<String>) -> Self {
+        self.spec.standard_output = Some(v.into());
+        self
+    }
+
+    pub fn standard_error(mut self, v: impl Into<String>) -> Self {
+        self.spec.standard_error = Some(v.into());
+        self
+    }
+
+    /// Apply a hardening preset (see `ServiceUnitSpec::apply_hardening`).
+    pub fn hardening(mut self, level: HardeningLevel) -> Self {
+        self.spec.apply_hardening(level);
+        self
+    }
+
+    pub fn wanted_by(mut self, unit: impl Into<String>) -> Self {
+        self.spec.wanted_by.push(unit.into());
+        self
+    }
+
+    pub fn required_by(mut self, unit: impl Into<String>) -> Self {
+        self.spec.required_by.push(unit.into());
+        self
+    }
+
+    pub fn alias(mut self, unit: impl Into<String>) -> Self {
+        self.spec.alias.push(unit.into());
+        self
+    }
+
+    /// Append a raw line under `[Service]` (escape hatch).
+    pub fn extra_service(mut self, line: impl Into<String>) -> Self {
+        self.spec.extra_service.push(line.into());
+        self
+    }
+
+    /// Lint the rendered unit file in `build()` (see `LintStrictness`).
+    pub fn lint(mut self, strictness: LintStrictness) -> Self {
+        self.spec.lint = strictness;
+        self
+    }
+}
+
+/// Fluent builder for `TaskSpec` (feature=`tasks`; see `TaskSpec::builder`).
+///
+/// `build()` only exists once `argv(...)` has been called.
+#[cfg(feature = "tasks")]
+#[derive(Clone, Debug)]
+pub struct TaskSpecBuilder<E = ExecMissing> {
+    spec: crate::types::task::TaskSpec,
+    _exec: PhantomData<E>,
+}
+
+#[cfg(feature = "tasks")]
+impl crate::types::task::TaskSpec {
+    /// Start building a spec; `argv(...)` must be called before `build()`.
+    pub fn builder() -> TaskSpecBuilder<ExecMissing> {
+        TaskSpecBuilder {
+            spec: crate::types::task::TaskSpec::default(),
+            _exec: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "tasks")]
+impl TaskSpecBuilder<ExecMissing> {
+    /// Set the process argv (executed without a shell).
+    pub fn argv<I, S>(mut self, argv: I) -> TaskSpecBuilder<ExecSet>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.spec.argv = collect_argv(argv);
+        TaskSpecBuilder {
+            spec: self.spec,
+            _exec: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "tasks")]
+impl TaskSpecBuilder<ExecSet> {
+    /// Validate and return the spec (a non-zero `timeout` is required).
+    pub fn build(self) -> Result<crate::types::task::TaskSpec> {
+        crate::units::task_properties(&self.spec, "exec")?;
+        Ok(self.spec)
+    }
+}
+
+#[cfg(feature = "tasks")]
+impl<E> TaskSpecBuilder<E> {
+    /// Add one environment variable.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.spec.env.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn workdir(mut self, dir: impl Into<String>) -> Self {
+        self.spec.workdir = Some(dir.into());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.spec.timeout = timeout;
+        self
+    }
+
+    pub fn runtime_max(mut self, runtime_max: std::time::Duration) -> Self {
+        self.spec.runtime_max = Some(runtime_max);
+        self
+    }
+
+    pub fn runtime_randomized_extra(mut self, extra: std::time::Duration) -> Self {
+        self.spec.runtime_randomized_extra = Some(extra);
+        self
+    }
+
+    pub fn name_hint(mut self, hint: impl Into<String>) -> Self {
+        self.spec.name_hint = Some(hint.into());
+        self
+    }
+
+    pub fn naming(mut self, naming: crate::types::task::NamingPolicy) -> Self {
+        self.spec.naming = naming;
+        self
+    }
+
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.spec.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Add one credential (see `Credential`).
+    pub fn credential(mut self, credential: crate::Credential) -> Self {
+        self.spec.credentials.push(credential);
+        self
+    }
+
+    pub fn after(mut self, unit: impl Into<String>) -> Self {
+        self.spec.after.push(unit.into());
+        self
+    }
+
+    pub fn requires(mut self, unit: impl Into<String>) -> Self {
+        self.spec.requires.push(unit.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn service_builder_builds_validated_spec() {
+        let spec = ServiceUnitSpec::builder()
+            .exec(["/usr/bin/app", "--serve"])
+            .unit("app")
+            .user("app")
+            .env("RUST_LOG", "info")
+            .restart("always")
+            .restart_sec(3)
+            .wanted_by("multi-user.target")
+            .build()
+            .unwrap();
+        assert_eq!(spec.exec_start, vec!["/usr/bin/app", "--serve"]);
+        let rendered = spec.render().unwrap();
+        assert!(rendered.contains("User=app\n"));
+        assert!(rendered.contains("RestartSec=3s\n"));
+        assert!(rendered.contains("WantedBy=multi-user.target\n"));
+
+        let err = ServiceUnitSpec::builder()
+            .exec(["/usr/bin/app"])
+            .build()
+            .expect_err("unit name is required");
+        let crate::Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn task_builder_requires_timeout() {
+        let spec = crate::TaskSpec::builder()
+            .argv(["/bin/true"])
+            .timeout(std::time::Duration::from_secs(5))
+            .after("network-online.target")
+            .build()
+            .unwrap();
+        assert_eq!(spec.argv, vec!["/bin/true"]);
+        assert_eq!(spec.after, vec!["network-online.target"]);
+
+        assert!(
+            crate::TaskSpec::builder()
+                .argv(["/bin/true"])
+                .build()
+                .is_err()
+        );
+    }
+}
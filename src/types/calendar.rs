@@ -0,0 +1,521 @@
+use crate::{Error, Result};
+
+use std::fmt;
+#[cfg(feature = "config")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const WEEKDAYS_LONG: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// A validated systemd calendar expression (`OnCalendar=`, see `systemd.time(7)`).
+///
+/// `parse` checks the syntax and value ranges locally and produces systemd's normalized form
+/// (e.g. `"Mon..Fri 9:00"` becomes `"Mon..Fri *-*-* 09:00:00"`). Time zone names other than
+/// `UTC` are kept as given and not checked against the tz database; use
+/// `Config::analyze_calendar` for an authoritative check and the next elapse times.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CalendarSpec {
+    /// Expression as given (trimmed).
+    pub original: String,
+    /// systemd's normalized form.
+    pub normalized: String,
+}
+
+impl CalendarSpec {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let original = expr.trim();
+        crate::util::validate_no_control("calendar expression", original)?;
+        let invalid =
+            |why: &str| Error::invalid_input(format!("invalid calendar {original:?}: {why}"));
+        if original.is_empty() {
+            return Err(invalid("empty expression"));
+        }
+
+        let normalized = if let Some(epoch) = original.strip_prefix('@') {
+            let secs: u64 = epoch.parse().map_err(|_| invalid("bad epoch seconds"))?;
+            format!("{} UTC", format_utc(secs))
+        } else {
+            normalize(original).map_err(invalid)?
+        };
+
+        Ok(Self {
+            original: original.to_string(),
+            normalized,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.normalized
+    }
+}
+
+impl fmt::Display for CalendarSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.normalized)
+    }
+}
+
+fn normalize(expr: &str) -> std::result::Result<String, &'static str> {
+    let mut tokens: Vec<&str> = expr.split_whitespace().collect();
+    let timezone = match tokens.last() {
+        Some(last) if tokens.len() > 1 && is_timezone(last) => tokens.pop(),
+        _ => None,
+    };
+
+    let mut body = match tokens.as_slice() {
+        [single] => shorthand(single).map(str::to_string),
+        _ => None,
+    };
+    if body.is_none() {
+        body = Some(normalize_parts(&tokens)?);
+    }
+    let mut out = body.unwrap_or_default();
+    if let Some(tz) = timezone {
+        out.push(' ');
+        out.push_str(tz);
+    }
+    Ok(out)
+}
+
+fn shorthand(token: &str) -> Option<&'static str> {
+    Some(match token {
+        "minutely" => "*-*-* *:*:00",
+        "hourly" => "*-*-* *:00:00",
+        "daily" => "*-*-* 00:00:00",
+        "weekly" => "Mon *-*-* 00:00:00",
+        "monthly" => "*-*-01 00:00:00",
+        "quarterly" => "*-01,04,07,10-01 00:00:00",
+        "semiannually" => "*-01,07-01 00:00:00",
+        "yearly" | "annually" => "*-01-01 00:00:00",
+        _ => return None,
+    })
+}
+
+fn is_timezone(token: &str) -> bool {
+    if token == "UTC" {
+        return true;
+    }
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '+' | '-');
+    if !token.starts_with(|c: char| c.is_ascii_alphabetic()) || !token.chars().all(allowed) {
+        return false;
+    }
+    // `Area/Location` names, or upper-case abbreviations such as `CET` or `EST5EDT`.
+    token.contains('/')
+        || (token.len() >= 3
+            && !token.chars().any(|c| c.is_ascii_lowercase())
+            && weekday_index(token.split(['-', '+']).next().unwrap_or("")).is_none())
+}
+
+fn normalize_parts(tokens: &[&str]) -> std::result::Result<String, &'static str> {
+    let mut rest = tokens;
+    let mut out = String::new();
+
+    if let Some((first, tail)) = rest.split_first()
+        && first.starts_with(|c: char| c.is_ascii_alphabetic())
+    {
+        out.push_str(&normalize_weekdays(first)?);
+        out.push(' ');
+        rest = tail;
+    }
+
+    let (date, time) = match rest {
+        [] if !out.is_empty() => (None, None),
+        [one] if one.contains(':') => (None, Some(*one)),
+        [one] => (Some(*one), None),
+        [date, time] => (Some(*date), Some(*time)),
+        [] => return Err("missing date or time"),
+        _ => return Err("too many components"),
+    };
+
+    out.push_str(&match date {
+        Some(date) => normalize_date(date)?,
+        None => "*-*-*".to_string(),
+    });
+    out.push(' ');
+    out.push_str(&match time {
+        Some(time) => normalize_time(time)?,
+        None => "00:00:00".to_string(),
+    });
+    Ok(out)
+}
+
+fn weekday_index(name: &str) -> Option<usize> {
+    WEEKDAYS
+        .iter()
+        .zip(WEEKDAYS_LONG)
+        .position(|(short, long)| {
+            name.eq_ignore_ascii_case(short) || name.eq_ignore_ascii_case(long)
+        })
+}
+
+/// `Mon,Wed..Fri` style list; consecutive runs of three or more days are written as ranges.
+fn normalize_weekdays(token: &str) -> std::result::Result<String, &'static str> {
+    let mut days = [false; 7];
+    for item in token.split(',') {
+        let (start, end) = match item.split_once("..").or_else(|| item.split_once('-')) {
+            Some((a, b)) => (a, b),
+            None => (item, item),
+        };
+        let start = weekday_index(start).ok_or("unknown weekday")?;
+        let end = weekday_index(end).ok_or("unknown weekday")?;
+        if start > end {
+            return Err("weekday range must go forward");
+        }
+        days.iter_mut()
+            .take(end + 1)
+            .skip(start)
+            .for_each(|d| *d = true);
+    }
+
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < 7 {
+        if !days[i] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i + 1 < 7 && days[i + 1] {
+            i += 1;
+        }
+        match i - start {
+            0 => parts.push(WEEKDAYS[start].to_string()),
+            1 => parts.push(format!("{},{}", WEEKDAYS[start], WEEKDAYS[i])),
+            _ => parts.push(format!("{}..{}", WEEKDAYS[start], WEEKDAYS[i])),
+        }
+        i += 1;
+    }
+    Ok(parts.join(","))
+}
+
+fn normalize_date(token: &str) -> std::result::Result<String, &'static str> {
+    let (head, day, sep) = match token.rsplit_once('~') {
+        Some((head, day)) => (head, day, '~'),
+        None => match token.rsplit_once('-') {
+            Some((head, day)) => (head, day, '-'),
+            None => return Err("date must be [YEAR-]MONTH-DAY"),
+        },
+    };
+    let (year, month) = match head.split_once('-') {
+        Some((y, m)) => (y, m),
+        None => ("*", head),
+    };
+
+    let year = component(year, 1970, 2199, |v| plain_value(v, 4))?;
+    let month = component(month, 1, 12, |v| plain_value(v, 2))?;
+    let day = component(day, 1, 31, |v| plain_value(v, 2))?;
+    Ok(format!("{year}-{month}{sep}{day}"))
+}
+
+fn normalize_time(token: &str) -> std::result::Result<String, &'static str> {
+    let mut parts = token.split(':');
+    let (Some(hour), Some(minute)) = (parts.next(), parts.next()) else {
+        return Err("time must be HOUR:MINUTE[:SECOND]");
+    };
+    let second = parts.next().unwrap_or("00");
+    if parts.next().is_some() {
+        return Err("time must be HOUR:MINUTE[:SECOND]");
+    }
+
+    let hour = component(hour, 0, 23, |v| plain_value(v, 2))?;
+    let minute = component(minute, 0, 59, |v| plain_value(v, 2))?;
+    let second = component(second, 0, 59, second_value)?;
+    Ok(format!("{hour}:{minute}:{second}"))
+}
+
+/// One date/time field: `*`, or a comma list of `value`, `value..value` and `value/step`.
+fn component(
+    field: &str,
+    min: u32,
+    max: u32,
+    value: impl Fn(&str) -> Option<(u32, String)>,
+) -> std::result::Result<String, &'static str> {
+    if field == "*" {
+        return Ok("*".to_string());
+    }
+    if field.is_empty() {
+        return Err("empty field");
+    }
+
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| "bad repetition")?;
+                if step == 0 {
+                    return Err("repetition must be > 0");
+                }
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+
+        let (start, end) = match range.split_once("..") {
+            Some((a, b)) => (
+                value(a).ok_or("bad value")?,
+                Some(value(b).ok_or("bad value")?),
+            ),
+            None => (value(range).ok_or("bad value")?, None),
+        };
+        if start.0 < min || start.0 > max || end.as_ref().is_some_and(|e| e.0 > max) {
+            return Err("value out of range");
+        }
+        if end.as_ref().is_some_and(|e| e.0 < start.0) {
+            return Err("range must go forward");
+        }
+
+        let mut out = start.1;
+        if let Some(end) = end {
+            out.push_str("..");
+            out.push_str(&end.1);
+        }
+        if let Some(step) = step {
+            out.push_str(&format!("/{step}"));
+        }
+        items.push(out);
+    }
+    Ok(items.join(","))
+}
+
+fn plain_value(v: &str, width: usize) -> Option<(u32, String)> {
+    if v.is_empty() || !v.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let n: u32 = v.parse().ok()?;
+    Some((n, format!("{n:0width$}")))
+}
+
+/// Seconds with an optional fraction of up to six digits (`05.25` becomes `05.250000`).
+fn second_value(v: &str) -> Option<(u32, String)> {
+    let (whole, frac) = v.split_once('.').unwrap_or((v, ""));
+    let (n, mut out) = plain_value(whole, 2)?;
+    if !frac.is_empty() {
+        if frac.len() > 6 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let micros: u32 = format!("{frac:0<6}").parse().ok()?;
+        if micros > 0 {
+            out.push_str(&format!(".{micros:06}"));
+        }
+    }
+    Some((n, out))
+}
+
+/// `YYYY-MM-DD HH:MM:SS` for seconds since the epoch.
+fn format_utc(secs: u64) -> String {
+    let days = i64::try_from(secs / 86_400).unwrap_or(i64::MAX);
+    let rem = secs % 86_400;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{y:04}-{m:02}-{d:02} {:02}:{:02}:{:02}",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Gregorian date for days since 1970-01-01 (Howard Hinnant's algorithm).
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (
+        y,
+        u32::try_from(m).unwrap_or(1),
+        u32::try_from(d).unwrap_or(1),
+    )
+}
+
+/// Days since 1970-01-01 for a Gregorian date.
+#[cfg(feature = "config")]
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = i64::from(m);
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Result of `Config::analyze_calendar` (`systemd-analyze calendar`).
+#[cfg(feature = "config")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CalendarAnalysis {
+    /// systemd's normalized form of the expression.
+    pub normalized: String,
+    /// Upcoming elapse times, soonest first; empty if the expression never elapses again.
+    pub next_elapses: Vec<SystemTime>,
+}
+
+#[cfg(feature = "config")]
+impl CalendarAnalysis {
+    /// Parse `systemd-analyze calendar` output.
+    ///
+    /// Each elapse is printed in the zone of the expression, followed by an `(in UTC):` line
+    /// unless that zone already is UTC.
+    pub(crate) fn from_output(output: &str) -> Option<Self> {
+        let mut normalized = None;
+        let mut next_elapses: Vec<Option<SystemTime>> = Vec::new();
+        for line in output.lines() {
+            let Some((key, value)) = line.trim().split_once(": ") else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "Normalized form" => normalized = Some(value.to_string()),
+                "Next elapse" if value == "never" => {}
+                "Next elapse" => next_elapses.push(parse_utc_timestamp(value)),
+                // `Iter. #N` since systemd 250, `Iteration #N` before.
+                k if k.starts_with("Iter") => next_elapses.push(parse_utc_timestamp(value)),
+                "(in UTC)" => {
+                    let utc = parse_utc_timestamp(value)?;
+                    *next_elapses.last_mut()? = Some(utc);
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            normalized: normalized?,
+            next_elapses: next_elapses.into_iter().collect::<Option<_>>()?,
+        })
+    }
+}
+
+/// `Sat 2026-10-17 00:00:00 UTC`; `None` for other zones.
+#[cfg(feature = "config")]
+fn parse_utc_timestamp(value: &str) -> Option<SystemTime> {
+    let mut parts = value.split_whitespace();
+    let (_weekday, date, time, zone) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    if zone != "UTC" {
+        return None;
+    }
+
+    let mut date = date.split('-');
+    let (y, m, d) = (date.next()?, date.next()?, date.next()?);
+    let days = days_from_civil(y.parse().ok()?, m.parse().ok()?, d.parse().ok()?);
+    let mut time = time.split(':');
+    let (h, min, s) = (time.next()?, time.next()?, time.next()?);
+    let secs = days * 86_400
+        + h.parse::<i64>().ok()? * 3600
+        + min.parse::<i64>().ok()? * 60
+        + s.parse::<i64>().ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(u64::try_from(secs).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn norm(expr: &str) -> String {
+        CalendarSpec::parse(expr).expect(expr).normalized
+    }
+
+    #[test]
+    fn calendar_spec_normalizes_like_systemd() {
+        assert_eq!(norm("daily"), "*-*-* 00:00:00");
+        assert_eq!(norm("weekly"), "Mon *-*-* 00:00:00");
+        assert_eq!(norm("hourly UTC"), "*-*-* *:00:00 UTC");
+        assert_eq!(norm("Mon..Fri 9:00"), "Mon..Fri *-*-* 09:00:00");
+        assert_eq!(norm("mon,tue,wed,fri"), "Mon..Wed,Fri *-*-* 00:00:00");
+        assert_eq!(norm("Sat,Sun 23:30"), "Sat,Sun *-*-* 23:30:00");
+        assert_eq!(norm("*:0/15"), "*-*-* *:00/15:00");
+        assert_eq!(norm("*-*-1,15 4:00:05.5"), "*-*-01,15 04:00:05.500000");
+        assert_eq!(norm("2026-10-17 12:00 UTC"), "2026-10-17 12:00:00 UTC");
+        assert_eq!(norm("*-02~01"), "*-02~01 00:00:00");
+        assert_eq!(
+            norm("Fri *-1..6 3:0 Europe/Berlin"),
+            "Fri *-*-01..06 03:00:00 Europe/Berlin"
+        );
+        assert_eq!(norm("@0"), "1970-01-01 00:00:00 UTC");
+        assert_eq!(norm("@1790000000"), "2026-09-21 14:13:20 UTC");
+    }
+
+    #[test]
+    fn calendar_spec_rejects_bad_expressions() {
+        for bad in [
+            "",
+            "25:00",
+            "*:61",
+            "Funday",
+            "*-13-01",
+            "*-*-32",
+            "Fri..Mon",
+            "*:*/0",
+            "*:*/5",
+            "daily extra",
+            "1969-01-01",
+            "10..5:00",
+            "*-*-* *:*:*:*",
+            "@x",
+            "dai\0ly",
+        ] {
+            assert!(CalendarSpec::parse(bad).is_err(), "{bad:?} must fail");
+        }
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn calendar_analysis_parses_systemd_analyze_output() {
+        let local = "  Original form: Mon 9:00\n\
+                     Normalized form: Mon *-*-* 09:00:00\n    \
+                     Next elapse: Mon 2026-10-19 09:00:00 CEST\n       \
+                     (in UTC): Mon 2026-10-19 07:00:00 UTC\n       \
+                     From now: 2 days left\n   \
+                     Iter. #2: Mon 2026-10-26 09:00:00 CET\n       \
+                     (in UTC): Mon 2026-10-26 08:00:00 UTC\n       \
+                     From now: 1 week 2 days left\n";
+        let a = CalendarAnalysis::from_output(local).expect("parse");
+        assert_eq!(a.normalized, "Mon *-*-* 09:00:00");
+        let secs: Vec<u64> = a
+            .next_elapses
+            .iter()
+            .map(|t| t.duration_since(UNIX_EPOCH).unwrap().as_secs())
+            .collect();
+        assert_eq!(secs, vec![1_792_393_200, 1_793_001_600]);
+
+        let utc = "Normalized form: *-*-* 00:00:00 UTC\n    \
+                   Next elapse: Sat 1970-01-03 00:00:00 UTC\n       \
+                   From now: 1 day left\n   \
+                   Iteration #2: Sun 1970-01-04 00:00:00 UTC\n";
+        let a = CalendarAnalysis::from_output(utc).expect("parse");
+        assert_eq!(
+            a.next_elapses,
+            vec![
+                UNIX_EPOCH + Duration::from_secs(2 * 86_400),
+                UNIX_EPOCH + Duration::from_secs(3 * 86_400),
+            ]
+        );
+
+        let never = "Normalized form: 2000-01-01 00:00:00\n    Next elapse: never\n";
+        assert!(
+            CalendarAnalysis::from_output(never)
+                .unwrap()
+                .next_elapses
+                .is_empty()
+        );
+        assert!(CalendarAnalysis::from_output("garbage").is_none());
+    }
+}
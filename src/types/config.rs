@@ -1,5 +1,7 @@
 use std::collections::BTreeMap;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum RecommendedAction {
@@ -9,6 +11,9 @@ pub enum RecommendedAction {
 }
 
 /// Specification for generating/applying a systemd drop-in (feature=`config`).
+///
+/// Environment entries, `working_directory` and `exec_start_override` are specifier-expanded by
+/// systemd, so a literal percent must be written as `%%`; a bare `%` is rejected.
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct DropInSpec {
@@ -23,11 +28,49 @@ pub struct DropInSpec {
     /// Optional `Restart=...`.
     pub restart: Option<String>,
     /// Optional `TimeoutStartSec=...`.
-    pub timeout_start_sec: Option<u32>,
+    pub timeout_start_sec: Option<crate::SystemdDuration>,
+    /// Optional `MemoryMax=...`.
+    pub memory_max: Option<crate::SystemdSize>,
+    /// Optional `CPUQuota=...` (must be `SystemdSize::Percent`).
+    pub cpu_quota: Option<crate::SystemdSize>,
     /// Optional `ExecStart` override (reset + set).
     pub exec_start_override: Option<Vec<String>>,
+    /// `DynamicUser=yes` (`false` leaves the unit's setting alone).
+    pub dynamic_user: bool,
+    /// `StateDirectory=` entries, relative to `/var/lib` (e.g. `myapp`).
+    pub state_directory: Vec<String>,
+    /// `CacheDirectory=` entries, relative to `/var/cache`.
+    pub cache_directory: Vec<String>,
+    /// `LogsDirectory=` entries, relative to `/var/log`.
+    pub logs_directory: Vec<String>,
+    /// `RuntimeDirectory=` entries, relative to `/run`.
+    pub runtime_directory: Vec<String>,
+    /// Credentials exposed below `$CREDENTIALS_DIRECTORY` (`LoadCredential=`,
+    /// `SetCredential=`, `SetCredentialEncrypted=`).
+    pub credentials: Vec<crate::Credential>,
+    /// Hold a logind shutdown inhibitor while `Config::apply_dropin` writes the file (and
+    /// `apply_dropin_verified` reloads), so a reboot cannot land in between (default: `false`).
+    pub inhibit_shutdown: bool,
 }
 
+impl DropInSpec {
+    /// Set `TimeoutStartSec=` in seconds.
+    pub fn set_timeout_start_sec(&mut self, secs: u32) -> &mut Self {
+        self.timeout_start_sec = Some(secs.into());
+        self
+    }
+
+    /// Render the drop-in with specifiers expanded as the system manager would for `instance`.
+    ///
+    /// `instance` is required to preview `%i`/`%I` when `unit` is a template (`app@.service`).
+    pub fn preview_expansion(&self, instance: Option<&str>) -> crate::Result<String> {
+        let rendered = crate::fsutil::render_dropin(self)?;
+        let ctx = crate::SpecifierContext::system(&self.unit, instance)?;
+        Ok(ctx.expand_unit_file(&rendered))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct ApplyReport {
@@ -39,8 +82,13 @@ pub struct ApplyReport {
     pub requires_daemon_reload: bool,
     /// Recommended next action for callers.
     pub recommended_action: RecommendedAction,
+    /// Computed under `UnitBusOptions.dry_run`; nothing was written.
+    pub dry_run: bool,
+    /// Line diff from the current drop-in to the new content (dry run only).
+    pub diff: Option<Vec<DiffLine>>,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct RemoveReport {
@@ -50,4 +98,344 @@ pub struct RemoveReport {
     pub path_removed: String,
     /// Whether a daemon reload is required for systemd to pick up the change.
     pub requires_daemon_reload: bool,
+    /// Computed under `UnitBusOptions.dry_run`; nothing was removed.
+    pub dry_run: bool,
+    /// Lines that removing the drop-in would drop (dry run only).
+    pub diff: Option<Vec<DiffLine>>,
+}
+
+/// Digest of one file that contributes to a unit's configuration (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ConfigFileDigest {
+    /// Absolute path (fragment or drop-in).
+    pub path: String,
+    /// Content digest (hex), or `None` when the file no longer exists.
+    pub digest: Option<String>,
+}
+
+/// Fingerprint of a unit's effective on-disk configuration (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitConfigFingerprint {
+    /// Canonical unit name.
+    pub unit: String,
+    /// `FragmentPath` as reported by systemd (if any).
+    pub fragment_path: Option<String>,
+    /// Fragment followed by all drop-ins (`DropInPaths`), in systemd order.
+    pub files: Vec<ConfigFileDigest>,
+    /// Combined digest over `files` (hex); stable for identical paths and contents.
+    pub digest: String,
+    /// systemd's `NeedDaemonReload`: files changed on disk since the last reload.
+    pub need_daemon_reload: bool,
+}
+
+/// Result of comparing a unit's current configuration with an expected fingerprint.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ConfigDrift {
+    /// `true` if the combined digest differs or systemd reports `NeedDaemonReload`.
+    pub drifted: bool,
+    /// Files present in both fingerprints whose content differs.
+    pub changed: Vec<String>,
+    /// Files present now but not in the expected fingerprint.
+    pub added: Vec<String>,
+    /// Files in the expected fingerprint that no longer contribute.
+    pub removed: Vec<String>,
+    /// The freshly computed fingerprint.
+    pub current: UnitConfigFingerprint,
+}
+
+/// One file of a unit's installed configuration (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitConfigFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// Installed configuration of a unit, like `systemctl cat` (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitFileContent {
+    pub unit: String,
+    /// Main unit file: `FragmentPath`, or the file in `UnitBusOptions.unit_dir` if systemd has
+    /// not loaded one. `None` if neither exists.
+    pub fragment: Option<UnitConfigFile>,
+    /// Drop-ins in the order systemd applies them (`DropInPaths`); files missing on disk are
+    /// skipped.
+    pub dropins: Vec<UnitConfigFile>,
+    /// The files changed on disk since systemd last loaded them.
+    pub need_daemon_reload: bool,
+}
+
+/// One line of a `UnitFileDiff`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "op", content = "line", rename_all = "snake_case")
+)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Dry-run preview of `Config::install_service_unit` (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitFileDiff {
+    pub unit: String,
+    /// File `install_service_unit` would write (in `UnitBusOptions.unit_dir`).
+    pub path: String,
+    /// Whether `path` exists yet.
+    pub exists: bool,
+    /// Whether writing the spec would change `path`.
+    pub changed: bool,
+    /// Line diff from the current content of `path` to the rendered spec.
+    pub lines: Vec<DiffLine>,
+    /// `FragmentPath` systemd currently uses for the unit.
+    pub loaded_fragment: Option<String>,
+    /// Drop-ins that keep applying on top of the written file.
+    pub dropins: Vec<String>,
+}
+
+impl UnitFileDiff {
+    /// `true` if systemd loads the unit from a different file than `path` (e.g. a copy in
+    /// `/etc/systemd/system` overriding `/run/systemd/system`), so installing would have no
+    /// effect.
+    pub fn shadowed(&self) -> bool {
+        self.loaded_fragment
+            .as_deref()
+            .is_some_and(|fragment| fragment != self.path)
+    }
+
+    /// Render `lines` in unified style (`+`, `-` and ` ` prefixes, no hunk headers).
+    pub fn unified(&self) -> String {
+        let mut out = String::new();
+        for line in &self.lines {
+            let (prefix, text) = match line {
+                DiffLine::Same(t) => (' ', t),
+                DiffLine::Added(t) => ('+', t),
+                DiffLine::Removed(t) => ('-', t),
+            };
+            out.push(prefix);
+            out.push_str(text);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Result of `Config::apply_dropin_verified` / `Config::write_service_unit_verified`
+/// (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct VerifiedApplyReport {
+    pub unit: String,
+    /// The file written and confirmed as loaded.
+    pub path: String,
+    /// Whether the file content changed.
+    pub changed: bool,
+    pub daemon_reload_performed: bool,
+    /// `FragmentPath` as systemd reports it after the change.
+    pub fragment_path: Option<String>,
+    /// `DropInPaths` as systemd reports it after the change.
+    pub dropin_paths: Vec<String>,
+    /// `UnitFileState` (e.g. `enabled`, `disabled`, `static`).
+    pub unit_file_state: Option<String>,
+}
+
+/// Result of `ReloadCoalescer::flush` (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReloadFlushReport {
+    /// `false` if no write since the last flush needed a reload.
+    pub daemon_reload_performed: bool,
+    /// Units whose writes the reload covered.
+    pub units: Vec<String>,
+    /// Units still reporting `NeedDaemonReload` afterwards (changed again during the reload).
+    pub still_need_reload: Vec<String>,
+}
+
+/// New listeners for `Config::update_socket_listener` (feature=`config`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SocketListenerUpdate {
+    /// `ListenStream=` addresses (e.g. `"0.0.0.0:8081"`, `"/run/app.sock"`).
+    pub listen_stream: Vec<String>,
+    /// `ListenDatagram=` addresses.
+    pub listen_datagram: Vec<String>,
+    /// How long to wait for the socket restart job (default: 30s).
+    pub timeout: std::time::Duration,
+    /// Hold a logind shutdown inhibitor while writing, reloading and restarting the socket
+    /// (default: `false`).
+    pub inhibit_shutdown: bool,
+}
+
+impl Default for SocketListenerUpdate {
+    fn default() -> Self {
+        Self {
+            listen_stream: Vec::new(),
+            listen_datagram: Vec::new(),
+            timeout: std::time::Duration::from_secs(30),
+            inhibit_shutdown: false,
+        }
+    }
+}
+
+impl SocketListenerUpdate {
+    /// A single `ListenStream=` address.
+    pub fn stream(address: impl Into<String>) -> Self {
+        Self {
+            listen_stream: vec![address.into()],
+            ..Self::default()
+        }
+    }
+}
+
+/// Result of `Config::update_socket_listener` (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SocketListenerReport {
+    pub unit: String,
+    /// Listeners systemd reported before the change.
+    pub old_listen: Vec<crate::SocketListen>,
+    /// Listeners systemd reports afterwards (empty if the restarted socket failed).
+    pub new_listen: Vec<crate::SocketListen>,
+    /// The listener drop-in write.
+    pub dropin: ApplyReport,
+    pub daemon_reload_performed: bool,
+    /// Outcome of the socket restart; `None` if the drop-in was already up to date.
+    pub restart_outcome: Option<crate::JobOutcome>,
+}
+
+/// Result of `Config::wire_on_failure` (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct OnFailureReport {
+    /// The monitored unit that received the drop-in.
+    pub unit: String,
+    /// The handler unit file written (e.g. `notify-failure@.service`).
+    pub handler_unit: String,
+    /// The `OnFailure=` entry added (e.g. `notify-failure@%n.service`).
+    pub on_failure: String,
+    /// The handler unit file write.
+    pub handler: crate::UnitFileWriteReport,
+    /// The `OnFailure=` drop-in write.
+    pub dropin: ApplyReport,
+    pub daemon_reload_performed: bool,
+}
+
+/// Application group target for `Config::define_app_target` (feature=`config`).
+///
+/// The target `Wants=` every member (the runtime equivalent of a `WantedBy=` link), and each
+/// member gets a `PartOf=` drop-in, so stopping or restarting the target does the same to all
+/// members.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct AppTargetSpec {
+    /// Target unit name; a bare name gets `.target` appended (`myapp` becomes `myapp.target`).
+    pub target: String,
+    /// Optional `Description=` of the target.
+    pub description: Option<String>,
+    /// Member units (shorthand names will be canonicalized, e.g. `api` becomes `api.service`).
+    pub members: Vec<String>,
+    /// `WantedBy=` of the target itself (e.g. `multi-user.target`), used when it is enabled.
+    pub wanted_by: Vec<String>,
+}
+
+impl AppTargetSpec {
+    pub fn new<I, S>(target: impl Into<String>, members: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            target: target.into(),
+            members: members.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Canonical target unit name (e.g. `myapp.target`).
+    pub fn canonical_target_name(&self) -> crate::Result<String> {
+        let name = self.target.trim();
+        let name = if name.contains('.') {
+            crate::util::canonicalize_unit_name(name)?
+        } else {
+            crate::util::canonicalize_unit_name(&format!("{name}.target"))?
+        };
+        if !name.ends_with(".target") {
+            return Err(crate::Error::invalid_input(format!(
+                "app target must be a .target unit, got {name}"
+            )));
+        }
+        Ok(name)
+    }
 }
+
+/// Result of `Config::define_app_target` (feature=`config`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct AppTargetReport {
+    /// The canonical target unit (e.g. `myapp.target`).
+    pub target: String,
+    /// Canonical member units, in the order given.
+    pub members: Vec<String>,
+    /// The target unit file write.
+    pub target_file: crate::UnitFileWriteReport,
+    /// The `PartOf=` drop-in write of each member, in `members` order.
+    pub dropins: Vec<ApplyReport>,
+    /// `PartOf=` drop-ins removed from units that are no longer members.
+    pub removed: Vec<RemoveReport>,
+    pub daemon_reload_performed: bool,
+}
+
+/// A held logind shutdown inhibitor (see `Config::inhibit_shutdown`).
+///
+/// Shutdown and reboot are delayed while this value is alive; the lock is released on drop (or
+/// explicitly via `release`).
+#[derive(Debug)]
+pub struct ShutdownInhibitor {
+    #[cfg(unix)]
+    _fd: std::os::fd::OwnedFd,
+}
+
+impl ShutdownInhibitor {
+    #[cfg(unix)]
+    pub(crate) fn new(fd: std::os::fd::OwnedFd) -> Self {
+        Self { _fd: fd }
+    }
+
+    /// Release the lock now.
+    pub fn release(self) {}
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(
+    ApplyReport,
+    RemoveReport,
+    UnitConfigFingerprint,
+    ConfigDrift,
+    OnFailureReport,
+    AppTargetReport,
+    UnitFileContent,
+    UnitFileDiff,
+    ReloadFlushReport,
+    SocketListenerReport,
+    VerifiedApplyReport
+);
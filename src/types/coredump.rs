@@ -0,0 +1,211 @@
+use std::time::SystemTime;
+
+/// `MESSAGE_ID` of the journal entries written by `systemd-coredump`.
+pub(crate) const COREDUMP_MESSAGE_ID: &str = "fc2e22bc6ee647b6b90729ab34a250b1";
+
+/// Journal fields needed to build a `CoredumpEntry` (the inline `COREDUMP` blob is skipped).
+pub(crate) const COREDUMP_FIELDS: [&str; 12] = [
+    "COREDUMP_PID",
+    "COREDUMP_UID",
+    "COREDUMP_GID",
+    "COREDUMP_SIGNAL",
+    "COREDUMP_SIGNAL_NAME",
+    "COREDUMP_TIMESTAMP",
+    "COREDUMP_EXE",
+    "COREDUMP_COMM",
+    "COREDUMP_UNIT",
+    "COREDUMP_USER_UNIT",
+    "COREDUMP_FILENAME",
+    "COREDUMP_HOSTNAME",
+];
+
+/// Filter for `Coredumps::list`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct CoredumpFilter {
+    /// Only coredumps of processes in this unit (`COREDUMP_UNIT`; shorthand is canonicalized).
+    pub unit: Option<String>,
+    /// Only coredumps of this executable path (`COREDUMP_EXE`).
+    pub executable: Option<String>,
+    pub since: Option<SystemTime>,
+    pub until: Option<SystemTime>,
+    /// Maximum number of entries to return, keeping the newest (default: 100).
+    pub limit: u32,
+    /// Journal query timeout (defaults to `UnitBusOptions.journal_default_timeout`).
+    pub timeout: Option<std::time::Duration>,
+}
+
+impl Default for CoredumpFilter {
+    fn default() -> Self {
+        Self {
+            unit: None,
+            executable: None,
+            since: None,
+            until: None,
+            limit: 100,
+            timeout: None,
+        }
+    }
+}
+
+/// Selects the process for `Coredumps::info`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CoredumpId {
+    Pid(u32),
+    /// Process name (`COREDUMP_COMM`).
+    Command(String),
+    /// Absolute executable path (`COREDUMP_EXE`).
+    Executable(String),
+}
+
+impl From<u32> for CoredumpId {
+    fn from(pid: u32) -> Self {
+        Self::Pid(pid)
+    }
+}
+
+impl From<&str> for CoredumpId {
+    /// Absolute paths select by executable, anything else by command name.
+    fn from(s: &str) -> Self {
+        if s.starts_with('/') {
+            Self::Executable(s.to_string())
+        } else {
+            Self::Command(s.to_string())
+        }
+    }
+}
+
+/// One crash recorded by `systemd-coredump`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CoredumpEntry {
+    /// Time of the crash (`COREDUMP_TIMESTAMP`, falling back to the journal timestamp).
+    pub timestamp: SystemTime,
+    pub pid: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub signal: Option<u32>,
+    /// Signal name, e.g. `SIGSEGV`.
+    pub signal_name: Option<String>,
+    /// System unit of the crashed process (`COREDUMP_UNIT`).
+    pub unit: Option<String>,
+    /// User unit of the crashed process (`COREDUMP_USER_UNIT`).
+    pub user_unit: Option<String>,
+    pub executable: Option<String>,
+    pub command: Option<String>,
+    pub hostname: Option<String>,
+    /// Path of the external core file, if one was stored.
+    pub core_file: Option<String>,
+    /// `true` if `core_file` still exists on disk.
+    pub core_present: bool,
+    /// Size of the core file on disk (compressed size for `.zst`/`.xz`/`.lz4` files).
+    pub size: Option<u64>,
+    /// Journal cursor of the coredump entry.
+    pub cursor: Option<String>,
+}
+
+impl CoredumpEntry {
+    /// Build an entry from a coredump journal record; `None` if it is not one.
+    pub(crate) fn from_journal(entry: &crate::JournalEntry) -> Option<Self> {
+        let str_field = |key: &str| entry.get_trimmed(key).map(str::to_string);
+        let num_field = |key: &str| entry.get_u64(key).and_then(|v| u32::try_from(v).ok());
+
+        let pid = num_field("COREDUMP_PID");
+        let executable = str_field("COREDUMP_EXE");
+        if pid.is_none() && executable.is_none() {
+            return None;
+        }
+
+        let signal = num_field("COREDUMP_SIGNAL");
+        let signal_name = str_field("COREDUMP_SIGNAL_NAME").or_else(|| {
+            signal
+                .and_then(|n| i32::try_from(n).ok())
+                .and_then(crate::Signal::from_number)
+                .map(|s| s.as_str().to_string())
+        });
+        let timestamp = entry
+            .get_u64("COREDUMP_TIMESTAMP")
+            .map(crate::util::system_time_from_unix_micros)
+            .unwrap_or(entry.timestamp);
+
+        Some(Self {
+            timestamp,
+            pid,
+            uid: num_field("COREDUMP_UID"),
+            gid: num_field("COREDUMP_GID"),
+            signal,
+            signal_name,
+            unit: str_field("COREDUMP_UNIT"),
+            user_unit: str_field("COREDUMP_USER_UNIT"),
+            executable,
+            command: str_field("COREDUMP_COMM"),
+            hostname: str_field("COREDUMP_HOSTNAME"),
+            core_file: str_field("COREDUMP_FILENAME"),
+            core_present: false,
+            size: None,
+            cursor: entry.cursor.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn journal_entry(fields: &[(&str, &str)]) -> crate::JournalEntry {
+        crate::JournalEntry {
+            timestamp: crate::util::system_time_from_unix_micros(5_000_000),
+            priority: Some(2),
+            ..crate::JournalEntry::from_pairs(fields)
+        }
+    }
+
+    #[test]
+    fn coredump_entry_decodes_journal_fields() {
+        let e = CoredumpEntry::from_journal(&journal_entry(&[
+            ("COREDUMP_PID", "4242"),
+            ("COREDUMP_UID", "0"),
+            ("COREDUMP_SIGNAL", "11"),
+            ("COREDUMP_TIMESTAMP", "1000000"),
+            ("COREDUMP_EXE", "/usr/bin/app"),
+            ("COREDUMP_COMM", "app"),
+            ("COREDUMP_UNIT", "app.service"),
+            (
+                "COREDUMP_FILENAME",
+                "/var/lib/systemd/coredump/core.app.0.x.4242.1.zst",
+            ),
+        ]))
+        .expect("coredump entry");
+
+        assert_eq!(e.pid, Some(4242));
+        assert_eq!(e.signal, Some(11));
+        assert_eq!(e.signal_name.as_deref(), Some("SIGSEGV"));
+        assert_eq!(e.unit.as_deref(), Some("app.service"));
+        assert_eq!(
+            e.timestamp,
+            crate::util::system_time_from_unix_micros(1_000_000)
+        );
+        assert_eq!(e.cursor.as_deref(), Some("s=1"));
+        assert!(e.core_file.is_some());
+        assert!(!e.core_present);
+
+        let e = CoredumpEntry::from_journal(&journal_entry(&[
+            ("COREDUMP_PID", "7"),
+            ("COREDUMP_SIGNAL", "6"),
+            ("COREDUMP_SIGNAL_NAME", "SIGABRT"),
+        ]))
+        .expect("coredump entry");
+        assert_eq!(e.signal_name.as_deref(), Some("SIGABRT"));
+        assert_eq!(
+            e.timestamp,
+            crate::util::system_time_from_unix_micros(5_000_000)
+        );
+
+        assert!(CoredumpEntry::from_journal(&journal_entry(&[("MESSAGE", "x")])).is_none());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::types::specifier;
+use crate::{Error, Result, util};
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// A credential passed to a service through systemd's credential mechanism
+/// (`systemd.exec(5)`, "Credentials").
+///
+/// The service reads it from `$CREDENTIALS_DIRECTORY/<id>` (see `read_credential`) instead of
+/// an environment variable, so it does not leak into `/proc/<pid>/environ`, `systemctl show`
+/// or child processes.
+#[derive(Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Credential {
+    /// `LoadCredential=<id>:<path>`: copy a file (or read a `AF_UNIX` socket) at start. A
+    /// relative `path` is looked up in the credential store (`/etc/credstore`, ...).
+    Load { id: String, path: String },
+    /// `SetCredential=<id>:<value>`: embed the value in the unit. Prefer `SetEncrypted` for
+    /// secrets written to unit files.
+    Set { id: String, value: String },
+    /// `SetCredentialEncrypted=<id>:<data>`: embed base64 output of `systemd-creds encrypt`,
+    /// decrypted by systemd with the host key or TPM at start.
+    SetEncrypted { id: String, data: String },
+}
+
+impl Credential {
+    pub fn load(id: impl Into<String>, path: impl Into<String>) -> Self {
+        Credential::Load {
+            id: id.into(),
+            path: path.into(),
+        }
+    }
+
+    pub fn set(id: impl Into<String>, value: impl Into<String>) -> Self {
+        Credential::Set {
+            id: id.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn set_encrypted(id: impl Into<String>, data: impl Into<String>) -> Self {
+        Credential::SetEncrypted {
+            id: id.into(),
+            data: data.into(),
+        }
+    }
+
+    /// The credential name (file name below `$CREDENTIALS_DIRECTORY`).
+    pub fn id(&self) -> &str {
+        match self {
+            Credential::Load { id, .. }
+            | Credential::Set { id, .. }
+            | Credential::SetEncrypted { id, .. } => id,
+        }
+    }
+
+    /// Check the id and payload; `template` allows `%i`-style specifiers in ids and paths.
+    pub(crate) fn validate(&self, template: bool) -> Result<()> {
+        validate_credential_id(self.id())?;
+        specifier::validate_specifiers("credential id", self.id(), template)?;
+        match self {
+            Credential::Load { path, .. } => {
+                util::validate_no_control("credential path", path)?;
+                if path.is_empty() || path.chars().any(char::is_whitespace) {
+                    return Err(Error::invalid_input(format!(
+                        "credential path must be non-empty without whitespace: {path:?}"
+                    )));
+                }
+                specifier::validate_specifiers("credential path", path, template)?;
+            }
+            Credential::Set { value, .. } => {
+                util::validate_no_control("credential value", value)?;
+            }
+            Credential::SetEncrypted { data, .. } => {
+                decode_base64(data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The unit file line, e.g. `LoadCredential=tls.key:/etc/app/tls.key`.
+    pub(crate) fn render(&self) -> String {
+        match self {
+            Credential::Load { id, path } => format!("LoadCredential={id}:{path}"),
+            // The value is C-unescaped by systemd, so only backslashes need escaping.
+            Credential::Set { id, value } => {
+                format!("SetCredential={id}:{}", value.replace('\\', "\\\\"))
+            }
+            Credential::SetEncrypted { id, data } => {
+                let data: String = data.split_whitespace().collect();
+                format!("SetCredentialEncrypted={id}:{data}")
+            }
+        }
+    }
+}
+
+/// Plaintext values are redacted.
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credential::Load { id, path } => f
+                .debug_struct("Load")
+                .field("id", id)
+                .field("path", path)
+                .finish(),
+            Credential::Set { id, .. } => f
+                .debug_struct("Set")
+                .field("id", id)
+                .field("value", &"<redacted>")
+                .finish(),
+            Credential::SetEncrypted { id, .. } => f
+                .debug_struct("SetEncrypted")
+                .field("id", id)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Render one line per credential after validating all of them.
+pub(crate) fn render_credentials(
+    out: &mut String,
+    credentials: &[Credential],
+    template: bool,
+) -> Result<()> {
+    for credential in credentials {
+        credential.validate(template)?;
+    }
+    for credential in credentials {
+        out.push_str(&credential.render());
+        out.push('\n');
+    }
+    Ok(())
+}
+
+fn validate_credential_id(id: &str) -> Result<()> {
+    util::validate_no_control("credential id", id)?;
+    if id.is_empty() || id.len() > 255 || id == "." || id == ".." {
+        return Err(Error::invalid_input(format!(
+            "invalid credential id: {id:?}"
+        )));
+    }
+    if id
+        .chars()
+        .any(|c| c == '/' || c == ':' || c.is_whitespace())
+    {
+        return Err(Error::invalid_input(format!(
+            "credential id must not contain '/', ':' or whitespace: {id:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// Decode standard (padded or unpadded) base64, ignoring whitespace.
+pub(crate) fn decode_base64(input: &str) -> Result<Vec<u8>> {
+    let invalid = || Error::invalid_input("encrypted credential data must be base64");
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut padding = 0usize;
+    let mut symbols = 0usize;
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                padding += 1;
+                continue;
+            }
+            _ => return Err(invalid()),
+        };
+        if padding > 0 {
+            return Err(invalid());
+        }
+        symbols += 1;
+        acc = ((acc << 6) | u32::from(v)) & 0xffff;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    if symbols == 0 || symbols % 4 == 1 || padding > 2 {
+        return Err(invalid());
+    }
+    Ok(out)
+}
+
+/// `$CREDENTIALS_DIRECTORY` of the current process, set by systemd for services with
+/// credentials.
+pub fn credentials_directory() -> Option<PathBuf> {
+    std::env::var_os("CREDENTIALS_DIRECTORY")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Read credential `id` from inside a service (`$CREDENTIALS_DIRECTORY/<id>`).
+///
+/// Returns `Error::InvalidInput` if the process has no credentials directory or `id` is not a
+/// valid credential name.
+pub fn read_credential(id: &str) -> Result<Vec<u8>> {
+    validate_credential_id(id)?;
+    let dir = credentials_directory().ok_or_else(|| {
+        Error::invalid_input("CREDENTIALS_DIRECTORY is not set (not running under systemd?)")
+    })?;
+    let path = dir.join(id);
+    std::fs::read(&path).map_err(|e| Error::IoError {
+        context: format!("read credential {}: {e}", path.to_string_lossy()),
+    })
+}
+
+/// `read_credential` as UTF-8 text with one trailing newline removed.
+pub fn read_credential_string(id: &str) -> Result<String> {
+    let bytes = read_credential(id)?;
+    let mut s = String::from_utf8(bytes)
+        .map_err(|_| Error::invalid_input(format!("credential {id} is not valid UTF-8")))?;
+    if s.ends_with('\n') {
+        s.pop();
+    }
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn credentials_validate_and_render() {
+        let mut out = String::new();
+        render_credentials(
+            &mut out,
+            &[
+                Credential::load("tls.key", "/etc/app/tls.key"),
+                Credential::set("token", "a\\b c"),
+                Credential::set_encrypted("db", "aGVs\nbG8="),
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            "LoadCredential=tls.key:/etc/app/tls.key\nSetCredential=token:a\\\\b c\n\
+             SetCredentialEncrypted=db:aGVsbG8=\n"
+        );
+
+        for bad in [
+            Credential::load("a/b", "/x"),
+            Credential::load("a:b", "/x"),
+            Credential::load("..", "/x"),
+            Credential::load("k", ""),
+            Credential::load("k", "/etc/%i.key"),
+            Credential::set("k", "line\nbreak"),
+            Credential::set_encrypted("k", "not base64!"),
+        ] {
+            assert!(bad.validate(false).is_err(), "{bad:?} accepted");
+        }
+        assert!(
+            Credential::load("k-%i", "/etc/%i.key")
+                .validate(true)
+                .is_ok()
+        );
+        assert!(!format!("{:?}", Credential::set("k", "hunter2")).contains("hunter2"));
+    }
+
+    #[test]
+    fn base64_decodes_padded_and_unpadded() {
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVsbG8").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGk=").unwrap(), b"hi");
+        assert!(decode_base64("").is_err());
+        assert!(decode_base64("a").is_err());
+        assert!(decode_base64("aG=k").is_err());
+    }
+}
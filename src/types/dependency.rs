@@ -0,0 +1,531 @@
+use crate::types::unit::{ActiveState, LoadState};
+
+/// Dependency property of `org.freedesktop.systemd1.Unit` followed by `Units::dependency_graph`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DependencyKind {
+    Requires,
+    Requisite,
+    Wants,
+    BindsTo,
+    PartOf,
+    Upholds,
+    Conflicts,
+    After,
+    Before,
+    RequiredBy,
+    RequisiteOf,
+    WantedBy,
+    BoundBy,
+    ConsistsOf,
+    UpheldBy,
+    ConflictedBy,
+}
+
+impl DependencyKind {
+    /// The dependencies `systemctl list-dependencies` follows (requirement, not ordering).
+    pub const REQUIREMENTS: [DependencyKind; 5] = [
+        DependencyKind::Requires,
+        DependencyKind::Requisite,
+        DependencyKind::Wants,
+        DependencyKind::BindsTo,
+        DependencyKind::PartOf,
+    ];
+
+    /// The reverse dependencies systemd derives from other units' settings (`RequiredBy` for
+    /// `Requires=`, ...); follow these to find what a restart of the unit affects.
+    pub const REVERSE: [DependencyKind; 7] = [
+        DependencyKind::RequiredBy,
+        DependencyKind::RequisiteOf,
+        DependencyKind::WantedBy,
+        DependencyKind::BoundBy,
+        DependencyKind::ConsistsOf,
+        DependencyKind::UpheldBy,
+        DependencyKind::ConflictedBy,
+    ];
+
+    /// The D-Bus property name, e.g. `"Requires"` (for forward kinds also the unit file setting).
+    pub fn property(self) -> &'static str {
+        match self {
+            DependencyKind::Requires => "Requires",
+            DependencyKind::Requisite => "Requisite",
+            DependencyKind::Wants => "Wants",
+            DependencyKind::BindsTo => "BindsTo",
+            DependencyKind::PartOf => "PartOf",
+            DependencyKind::Upholds => "Upholds",
+            DependencyKind::Conflicts => "Conflicts",
+            DependencyKind::After => "After",
+            DependencyKind::Before => "Before",
+            DependencyKind::RequiredBy => "RequiredBy",
+            DependencyKind::RequisiteOf => "RequisiteOf",
+            DependencyKind::WantedBy => "WantedBy",
+            DependencyKind::BoundBy => "BoundBy",
+            DependencyKind::ConsistsOf => "ConsistsOf",
+            DependencyKind::UpheldBy => "UpheldBy",
+            DependencyKind::ConflictedBy => "ConflictedBy",
+        }
+    }
+
+    /// The kind an edge is checked as for cycles, and whether it points the other way (`b
+    /// RequiredBy a` is `a Requires b`); `None` for conflicts, which are symmetric.
+    fn cycle_kind(self) -> Option<(DependencyKind, bool)> {
+        Some(match self {
+            DependencyKind::Conflicts | DependencyKind::ConflictedBy => return None,
+            DependencyKind::Before => (DependencyKind::After, true),
+            DependencyKind::RequiredBy => (DependencyKind::Requires, true),
+            DependencyKind::RequisiteOf => (DependencyKind::Requisite, true),
+            DependencyKind::WantedBy => (DependencyKind::Wants, true),
+            DependencyKind::BoundBy => (DependencyKind::BindsTo, true),
+            DependencyKind::ConsistsOf => (DependencyKind::PartOf, true),
+            DependencyKind::UpheldBy => (DependencyKind::Upholds, true),
+            kind => (kind, false),
+        })
+    }
+}
+
+/// Direct dependencies of one unit, as returned by `Units::dependencies`.
+///
+/// Forward lists come from the unit's own settings (including drop-ins and implicit
+/// dependencies added by systemd); reverse lists from the settings of other loaded units.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitDependencies {
+    pub unit: String,
+    pub requires: Vec<String>,
+    pub requisite: Vec<String>,
+    pub wants: Vec<String>,
+    pub binds_to: Vec<String>,
+    pub part_of: Vec<String>,
+    pub upholds: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub after: Vec<String>,
+    pub before: Vec<String>,
+    pub required_by: Vec<String>,
+    pub requisite_of: Vec<String>,
+    pub wanted_by: Vec<String>,
+    pub bound_by: Vec<String>,
+    pub consists_of: Vec<String>,
+    pub upheld_by: Vec<String>,
+    pub conflicted_by: Vec<String>,
+}
+
+impl UnitDependencies {
+    /// Build from a lookup of dependency properties (`kind` → unit names).
+    pub(crate) fn from_lookup(
+        unit: String,
+        mut get: impl FnMut(DependencyKind) -> Vec<String>,
+    ) -> Self {
+        let mut deps = UnitDependencies {
+            unit,
+            ..Default::default()
+        };
+        for kind in DependencyKind::REQUIREMENTS
+            .into_iter()
+            .chain([
+                DependencyKind::Upholds,
+                DependencyKind::Conflicts,
+                DependencyKind::After,
+                DependencyKind::Before,
+            ])
+            .chain(DependencyKind::REVERSE)
+        {
+            *deps.get_mut(kind) = get(kind);
+        }
+        deps
+    }
+
+    /// The units listed for `kind`.
+    pub fn get(&self, kind: DependencyKind) -> &[String] {
+        match kind {
+            DependencyKind::Requires => &self.requires,
+            DependencyKind::Requisite => &self.requisite,
+            DependencyKind::Wants => &self.wants,
+            DependencyKind::BindsTo => &self.binds_to,
+            DependencyKind::PartOf => &self.part_of,
+            DependencyKind::Upholds => &self.upholds,
+            DependencyKind::Conflicts => &self.conflicts,
+            DependencyKind::After => &self.after,
+            DependencyKind::Before => &self.before,
+            DependencyKind::RequiredBy => &self.required_by,
+            DependencyKind::RequisiteOf => &self.requisite_of,
+            DependencyKind::WantedBy => &self.wanted_by,
+            DependencyKind::BoundBy => &self.bound_by,
+            DependencyKind::ConsistsOf => &self.consists_of,
+            DependencyKind::UpheldBy => &self.upheld_by,
+            DependencyKind::ConflictedBy => &self.conflicted_by,
+        }
+    }
+
+    fn get_mut(&mut self, kind: DependencyKind) -> &mut Vec<String> {
+        match kind {
+            DependencyKind::Requires => &mut self.requires,
+            DependencyKind::Requisite => &mut self.requisite,
+            DependencyKind::Wants => &mut self.wants,
+            DependencyKind::BindsTo => &mut self.binds_to,
+            DependencyKind::PartOf => &mut self.part_of,
+            DependencyKind::Upholds => &mut self.upholds,
+            DependencyKind::Conflicts => &mut self.conflicts,
+            DependencyKind::After => &mut self.after,
+            DependencyKind::Before => &mut self.before,
+            DependencyKind::RequiredBy => &mut self.required_by,
+            DependencyKind::RequisiteOf => &mut self.requisite_of,
+            DependencyKind::WantedBy => &mut self.wanted_by,
+            DependencyKind::BoundBy => &mut self.bound_by,
+            DependencyKind::ConsistsOf => &mut self.consists_of,
+            DependencyKind::UpheldBy => &mut self.upheld_by,
+            DependencyKind::ConflictedBy => &mut self.conflicted_by,
+        }
+    }
+}
+
+/// Options for `Units::dependency_graph`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DependencyGraphOptions {
+    /// Dependency kinds to follow (default: `DependencyKind::REQUIREMENTS`).
+    pub kinds: Vec<DependencyKind>,
+    /// How many hops from the roots to expand (default: 3; `0` lists only the roots).
+    pub max_depth: u32,
+    /// Stop expanding once this many units are in the graph (default: 256).
+    pub max_nodes: usize,
+}
+
+impl Default for DependencyGraphOptions {
+    fn default() -> Self {
+        Self {
+            kinds: DependencyKind::REQUIREMENTS.to_vec(),
+            max_depth: 3,
+            max_nodes: 256,
+        }
+    }
+}
+
+/// A unit in a `DependencyGraph`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DependencyNode {
+    pub unit: String,
+    /// Hops from the nearest root (units at `max_depth` are listed but not expanded).
+    pub depth: u32,
+    /// `not-found` for dependencies on units that do not exist.
+    pub load_state: LoadState,
+    pub active_state: ActiveState,
+}
+
+/// A `from` → `to` dependency of the given kind (`from` has `Kind=to`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: DependencyKind,
+}
+
+/// A dependency cycle of one kind: each unit has `kind` on the next, the last on the first.
+///
+/// Reverse and `Before` edges are folded into their forward kind (`RequiredBy` → `Requires`,
+/// `Before` → `After`), so a cycle of `After` edges is an ordering cycle systemd must break.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DependencyCycle {
+    pub kind: DependencyKind,
+    pub units: Vec<String>,
+}
+
+/// Dependencies reachable from a set of units, as returned by `Units::dependency_graph`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DependencyGraph {
+    /// The units the walk started from (canonicalized, without duplicates).
+    pub roots: Vec<String>,
+    /// Units in breadth-first order, the roots first.
+    pub nodes: Vec<DependencyNode>,
+    pub edges: Vec<DependencyEdge>,
+    /// Cycles among `edges`, one per back edge of a depth-first walk (so every cyclic kind is
+    /// reported, though not every cycle through the same units). Conflicts are not checked.
+    pub cycles: Vec<DependencyCycle>,
+    /// `true` if `max_nodes` cut the walk short.
+    pub truncated: bool,
+}
+
+impl DependencyGraph {
+    /// Render as a Graphviz `digraph` (e.g. `dot -Tsvg`).
+    ///
+    /// Edges are labelled with their kind; weak (`Wants`) and ordering edges are dashed. Failed
+    /// units are red, missing ones grey and the roots bold.
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph {} {{\n", dot_id(&self.roots.join(", ")));
+        out.push_str("  rankdir=LR;\n  node [shape=box];\n");
+        for node in &self.nodes {
+            let mut attrs = Vec::new();
+            if self.roots.contains(&node.unit) {
+                attrs.push("style=bold".to_string());
+            }
+            if node.active_state == ActiveState::Failed {
+                attrs.push("color=red".to_string());
+            }
+            if node.load_state != LoadState::Loaded {
+                attrs.push("color=grey".to_string());
+            }
+            attrs.push(format!(
+                "tooltip={}",
+                dot_id(&format!(
+                    "{} ({}, {})",
+                    node.unit,
+                    node.load_state.as_str(),
+                    node.active_state.as_str()
+                ))
+            ));
+            out.push_str(&format!(
+                "  {} [{}];\n",
+                dot_id(&node.unit),
+                attrs.join(", ")
+            ));
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                DependencyKind::Wants
+                | DependencyKind::WantedBy
+                | DependencyKind::After
+                | DependencyKind::Before => ", style=dashed",
+                _ => "",
+            };
+            out.push_str(&format!(
+                "  {} -> {} [label={}{style}];\n",
+                dot_id(&edge.from),
+                dot_id(&edge.to),
+                dot_id(edge.kind.property())
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(DependencyGraph, UnitDependencies);
+
+/// Find cycles among `edges` (see `DependencyGraph::cycles`).
+pub(crate) fn find_cycles(edges: &[DependencyEdge]) -> Vec<DependencyCycle> {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let mut by_kind: BTreeMap<&str, (DependencyKind, BTreeMap<&str, BTreeSet<&str>>)> =
+        BTreeMap::new();
+    for edge in edges {
+        let Some((kind, flipped)) = edge.kind.cycle_kind() else {
+            continue;
+        };
+        let (from, to) = if flipped {
+            (edge.to.as_str(), edge.from.as_str())
+        } else {
+            (edge.from.as_str(), edge.to.as_str())
+        };
+        let (_, adjacency) = by_kind
+            .entry(kind.property())
+            .or_insert_with(|| (kind, BTreeMap::new()));
+        adjacency.entry(from).or_default().insert(to);
+        adjacency.entry(to).or_default();
+    }
+
+    let mut cycles = Vec::new();
+    for (kind, adjacency) in by_kind.into_values() {
+        // Iterative DFS; `path` holds the units on the current branch ("grey").
+        let mut done = BTreeSet::new();
+        for &start in adjacency.keys() {
+            if done.contains(start) {
+                continue;
+            }
+            let mut path = vec![start];
+            let mut stack = vec![adjacency[start].iter()];
+            while let Some(next) = stack.last_mut().map(|it| it.next()) {
+                match next {
+                    Some(&to) => {
+                        if let Some(pos) = path.iter().position(|u| *u == to) {
+                            cycles.push(DependencyCycle {
+                                kind,
+                                units: path[pos..].iter().map(|u| u.to_string()).collect(),
+                            });
+                        } else if !done.contains(to) {
+                            path.push(to);
+                            stack.push(adjacency[to].iter());
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        if let Some(unit) = path.pop() {
+                            done.insert(unit);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    cycles
+}
+
+/// Quoted DOT identifier.
+fn dot_id(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn node(
+        unit: &str,
+        depth: u32,
+        load_state: LoadState,
+        active_state: ActiveState,
+    ) -> DependencyNode {
+        DependencyNode {
+            unit: unit.to_string(),
+            depth,
+            load_state,
+            active_state,
+        }
+    }
+
+    fn edge(from: &str, to: &str, kind: DependencyKind) -> DependencyEdge {
+        DependencyEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        }
+    }
+
+    fn graph() -> DependencyGraph {
+        DependencyGraph {
+            roots: vec!["app.service".to_string()],
+            nodes: vec![
+                node("app.service", 0, LoadState::Loaded, ActiveState::Active),
+                node("db.service", 1, LoadState::Loaded, ActiveState::Failed),
+                node(
+                    "cache.service",
+                    1,
+                    LoadState::NotFound,
+                    ActiveState::Inactive,
+                ),
+            ],
+            edges: vec![
+                DependencyEdge {
+                    from: "app.service".to_string(),
+                    to: "db.service".to_string(),
+                    kind: DependencyKind::Requires,
+                },
+                DependencyEdge {
+                    from: "app.service".to_string(),
+                    to: "cache.service".to_string(),
+                    kind: DependencyKind::Wants,
+                },
+            ],
+            cycles: Vec::new(),
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn find_cycles_folds_reverse_kinds() {
+        let edges = vec![
+            edge("a.service", "b.service", DependencyKind::Requires),
+            edge("b.service", "c.service", DependencyKind::Requires),
+            edge("a.service", "c.service", DependencyKind::RequiredBy),
+            // `a After b` and `b Before a` are the same ordering, not a cycle.
+            edge("a.service", "b.service", DependencyKind::After),
+            edge("b.service", "a.service", DependencyKind::Before),
+            edge("a.service", "b.service", DependencyKind::Conflicts),
+            edge("b.service", "a.service", DependencyKind::Conflicts),
+        ];
+        assert_eq!(
+            find_cycles(&edges),
+            vec![DependencyCycle {
+                kind: DependencyKind::Requires,
+                units: vec![
+                    "a.service".to_string(),
+                    "b.service".to_string(),
+                    "c.service".to_string()
+                ],
+            }]
+        );
+
+        let ordering = vec![
+            edge("a.service", "b.service", DependencyKind::After),
+            edge("a.service", "b.service", DependencyKind::Before),
+        ];
+        let cycles = find_cycles(&ordering);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].kind, DependencyKind::After);
+        assert_eq!(cycles[0].units, vec!["a.service", "b.service"]);
+    }
+
+    #[test]
+    fn to_dot_renders_nodes_and_edges() {
+        let dot = graph().to_dot();
+        assert!(dot.starts_with("digraph \"app.service\" {\n"));
+        assert!(dot.contains(
+            "  \"app.service\" [style=bold, tooltip=\"app.service (loaded, active)\"];\n"
+        ));
+        assert!(
+            dot.contains(
+                "  \"db.service\" [color=red, tooltip=\"db.service (loaded, failed)\"];\n"
+            )
+        );
+        assert!(dot.contains(
+            "  \"cache.service\" [color=grey, tooltip=\"cache.service (not-found, inactive)\"];\n"
+        ));
+        assert!(dot.contains("  \"app.service\" -> \"db.service\" [label=\"Requires\"];\n"));
+        assert!(
+            dot.contains(
+                "  \"app.service\" -> \"cache.service\" [label=\"Wants\", style=dashed];\n"
+            )
+        );
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot_id("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+
+    #[test]
+    fn unit_dependencies_cover_every_kind() {
+        let deps = UnitDependencies::from_lookup("app.service".to_string(), |kind| {
+            vec![format!("{}.target", kind.property())]
+        });
+        assert_eq!(deps.requires, vec!["Requires.target"]);
+        assert_eq!(deps.wanted_by, vec!["WantedBy.target"]);
+        assert_eq!(deps.conflicted_by, vec!["ConflictedBy.target"]);
+        for kind in DependencyKind::REVERSE {
+            assert_eq!(deps.get(kind), [format!("{}.target", kind.property())]);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn graph_serializes_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&graph().to_json().unwrap()).unwrap();
+        assert_eq!(json["roots"][0], "app.service");
+        assert_eq!(json["nodes"][1]["active_state"], "failed");
+        assert_eq!(json["nodes"][2]["load_state"], "not-found");
+        assert_eq!(json["edges"][0]["kind"], "Requires");
+    }
+}
@@ -0,0 +1,350 @@
+use crate::types::journal::{JournalCursor, JournalEntry};
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+use crate::types::journal::{JournalFormatOptions, JournalOutputStyle};
+use crate::{Error, Result};
+
+use std::fmt;
+use std::future::Future;
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+use std::io::Write;
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Future returned by `JournalSink::send`.
+pub type SinkFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Destination of `Journal::forward` (an HTTP collector, syslog, a file, ...).
+///
+/// `send` gets one batch in journal order and should resolve once the batch is durably handed
+/// off; the cursor is only advanced after it succeeds, so a failed or interrupted batch is sent
+/// again (at-least-once delivery). Errors are retried with backoff (see `ForwardOptions`).
+pub trait JournalSink: Send + Sync {
+    fn send<'a>(&'a self, batch: &'a [JournalEntry]) -> SinkFuture<'a>;
+}
+
+impl fmt::Debug for dyn JournalSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("JournalSink")
+    }
+}
+
+/// Options for `Journal::forward`. Batch size comes from the filter (`limit`, `max_bytes`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ForwardOptions {
+    /// Key the position is saved under in the `CursorStore` (default: `journal-forward`).
+    pub cursor_key: String,
+    /// Keep waiting for new entries once caught up (default: `true`). When `false`,
+    /// `forward` returns as soon as the journal has no more matching entries.
+    pub follow: bool,
+    /// Pause between polls once caught up (default: 2s).
+    pub poll_interval: Duration,
+    /// Attempts per failing query or send before `forward` gives up (default: 5).
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled per attempt up to `max_backoff` (default: 500ms).
+    pub initial_backoff: Duration,
+    /// Upper bound of the retry delay (default: 30s).
+    pub max_backoff: Duration,
+}
+
+impl Default for ForwardOptions {
+    fn default() -> Self {
+        Self {
+            cursor_key: "journal-forward".to_string(),
+            follow: true,
+            poll_interval: Duration::from_secs(2),
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ForwardOptions {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.max_attempts == 0 {
+            return Err(Error::invalid_input("max_attempts must be > 0"));
+        }
+        if self.follow && self.poll_interval.is_zero() {
+            return Err(Error::invalid_input("poll_interval must be > 0"));
+        }
+        Ok(())
+    }
+
+    /// Delay before retry number `retry` (1-based).
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Result of a `Journal::forward` run that stopped after catching up (`follow: false`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ForwardReport {
+    pub entries_forwarded: u64,
+    pub batches: u64,
+    /// Failed queries or sends that succeeded on a later attempt.
+    pub retries: u64,
+    /// Cursor saved after the last delivered batch.
+    pub last_cursor: Option<JournalCursor>,
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(ForwardReport);
+
+/// `JournalSink` appending one formatted line per entry (see `JournalEntry::format`) to a file
+/// (feature=`journal-cli` or `journal-sdjournal`).
+///
+/// Each batch is flushed and `fsync`ed (on the blocking thread pool) before it counts as
+/// delivered.
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+#[derive(Debug)]
+pub struct FileSink {
+    path: PathBuf,
+    format: JournalFormatOptions,
+    file: Arc<Mutex<Option<std::fs::File>>>,
+}
+
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+impl FileSink {
+    /// The file is created on the first batch; existing content is kept.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: JournalFormatOptions {
+                style: JournalOutputStyle::ShortIso,
+                ..Default::default()
+            },
+            file: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Line format (default: `JournalOutputStyle::ShortIso`, UTC, with hostname).
+    pub fn format(mut self, format: JournalFormatOptions) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn append(path: &Path, file: &Mutex<Option<std::fs::File>>, buf: &[u8]) -> std::io::Result<()> {
+        let mut file = file
+            .lock()
+            .map_err(|_| std::io::Error::other("file sink lock poisoned"))?;
+        let f = match &mut *file {
+            Some(f) => f,
+            None => file.insert(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?,
+            ),
+        };
+        f.write_all(buf)?;
+        f.sync_data()
+    }
+}
+
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+impl JournalSink for FileSink {
+    fn send<'a>(&'a self, batch: &'a [JournalEntry]) -> SinkFuture<'a> {
+        let mut buf = Vec::new();
+        for entry in batch {
+            buf.extend_from_slice(entry.format(&self.format).as_bytes());
+            buf.push(b'\n');
+        }
+        let path = self.path.clone();
+        let file = self.file.clone();
+        Box::pin(async move {
+            blocking::unblock(move || {
+                Self::append(&path, &file, &buf).map_err(|e| Error::IoError {
+                    context: format!("write {}: {e}", path.to_string_lossy()),
+                })
+            })
+            .await
+        })
+    }
+}
+
+/// `JournalSink` sending each entry as an RFC 5424 syslog datagram over UDP (feature=`journal-cli`
+/// or `journal-sdjournal`). Datagrams are sent from the blocking thread pool.
+///
+/// The facility is taken from the entry's `SYSLOG_FACILITY` (default: `user`), the app name
+/// from `SYSLOG_IDENTIFIER` (falling back to the unit). Messages longer than `max_message_bytes`
+/// are cut so each datagram fits the collector's limit.
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+#[derive(Debug)]
+pub struct SyslogSink {
+    socket: Arc<std::net::UdpSocket>,
+    max_message_bytes: usize,
+}
+
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+impl SyslogSink {
+    /// Send to `addr` (e.g. `"logs.example.com:514"`).
+    pub fn udp(addr: impl std::net::ToSocketAddrs) -> Result<Self> {
+        let io_error = |e: std::io::Error| Error::IoError {
+            context: format!("syslog socket: {e}"),
+        };
+        let socket = std::net::UdpSocket::bind(("0.0.0.0", 0)).map_err(io_error)?;
+        socket.connect(addr).map_err(io_error)?;
+        Ok(Self {
+            socket: Arc::new(socket),
+            max_message_bytes: 2048,
+        })
+    }
+
+    /// Cap on the message part of each datagram (default: 2048 bytes).
+    pub fn max_message_bytes(mut self, max: usize) -> Self {
+        self.max_message_bytes = max;
+        self
+    }
+}
+
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+impl JournalSink for SyslogSink {
+    fn send<'a>(&'a self, batch: &'a [JournalEntry]) -> SinkFuture<'a> {
+        let datagrams: Vec<Vec<u8>> = batch
+            .iter()
+            .map(|entry| rfc5424(entry, self.max_message_bytes))
+            .collect();
+        let socket = self.socket.clone();
+        Box::pin(async move {
+            blocking::unblock(move || {
+                datagrams.iter().try_for_each(|datagram| {
+                    socket
+                        .send(datagram)
+                        .map(|_| ())
+                        .map_err(|e| Error::IoError {
+                            context: format!("send syslog datagram: {e}"),
+                        })
+                })
+            })
+            .await
+        })
+    }
+}
+
+/// `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID - - MSG`
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+fn rfc5424(entry: &JournalEntry, max_message_bytes: usize) -> Vec<u8> {
+    // A header field is `-` when absent and must not contain spaces.
+    fn header(value: Option<&str>) -> String {
+        match value.filter(|v| !v.is_empty()) {
+            Some(v) => v.chars().filter(|c| c.is_ascii_graphic()).collect(),
+            None => "-".to_string(),
+        }
+    }
+
+    let facility = entry
+        .get_str("SYSLOG_FACILITY")
+        .and_then(|v| v.parse::<u8>().ok())
+        .filter(|f| *f < 24)
+        .unwrap_or(1);
+    let severity = entry.priority.unwrap_or(6).min(7);
+    let pri = u16::from(facility) * 8 + u16::from(severity);
+
+    let timestamp =
+        super::journal::format_timestamp(entry.timestamp, JournalOutputStyle::ShortIso, 0);
+    let app = entry.syslog_identifier().or(entry.unit.as_deref());
+    let pid = entry.pid.map(|p| p.to_string());
+
+    let mut out = format!(
+        "<{pri}>1 {timestamp} {} {} {} - - ",
+        header(entry.hostname()),
+        header(app),
+        header(pid.as_deref())
+    )
+    .into_bytes();
+    let message = entry.message.as_deref().unwrap_or_default().as_bytes();
+    out.extend_from_slice(&message[..message.len().min(max_message_bytes)]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    fn entry(message: &str, fields: &[(&str, &str)]) -> JournalEntry {
+        JournalEntry {
+            timestamp: crate::util::system_time_from_unix_micros(1_792_154_096_000_000),
+            message: Some(message.to_string()),
+            priority: Some(3),
+            unit: Some("app.service".to_string()),
+            pid: Some(812),
+            ..JournalEntry::from_pairs(fields)
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    fn rfc5424_renders_header_and_caps_message() {
+        let e = entry(
+            "disk full",
+            &[
+                ("_HOSTNAME", "web1"),
+                ("SYSLOG_IDENTIFIER", "app"),
+                ("SYSLOG_FACILITY", "3"),
+            ],
+        );
+        assert_eq!(
+            String::from_utf8(rfc5424(&e, 2048)).unwrap(),
+            "<27>1 2026-10-16T12:34:56+00:00 web1 app 812 - - disk full"
+        );
+        assert!(rfc5424(&e, 4).ends_with(b" - - disk"));
+
+        let bare = entry("x", &[]);
+        assert_eq!(
+            String::from_utf8(rfc5424(&bare, 2048)).unwrap(),
+            "<11>1 2026-10-16T12:34:56+00:00 - app.service 812 - - x"
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        let opts = ForwardOptions::default();
+        assert_eq!(opts.backoff(1), Duration::from_millis(500));
+        assert_eq!(opts.backoff(3), Duration::from_secs(2));
+        assert_eq!(opts.backoff(40), Duration::from_secs(30));
+        assert!(
+            ForwardOptions {
+                max_attempts: 0,
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    fn file_sink_appends_formatted_lines() {
+        let path = std::env::temp_dir().join(format!("unitbus-forward-{}.log", std::process::id()));
+        let sink = FileSink::new(&path);
+        let batch = [entry("one", &[]), entry("two", &[])];
+        futures_lite::future::block_on(sink.send(&batch)).unwrap();
+        futures_lite::future::block_on(sink.send(&batch[..1])).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "2026-10-16T12:34:56+00:00 unknown[812]: one");
+        assert!(lines[2].ends_with(": one"));
+    }
+}
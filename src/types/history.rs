@@ -0,0 +1,236 @@
+use std::time::SystemTime;
+
+// systemd's catalog `MESSAGE_ID`s for unit lifecycle records (`sd-messages.h`).
+const UNIT_STARTING: &str = "7d4958e842da4a758f6c1cdc7b36dcc5";
+const UNIT_STARTED: &str = "39f53479d3a045ac8e11786248231fbf";
+const UNIT_FAILED: &str = "be02cf6855d2428ba40df7e9d022f03d";
+const UNIT_STOPPING: &str = "de5b426a63be47a7b6ac3eaac82e2f6f";
+const UNIT_STOPPED: &str = "9d1aaa27d60140bd96365438aad20286";
+const UNIT_RELOADING: &str = "d34d037fff1847e6ae669a370e694725";
+const UNIT_RELOADED: &str = "7b05ebc668384222baa8881179cfda54";
+const UNIT_RESTART_SCHEDULED: &str = "5eb03494b6584870a536b337290809b3";
+const UNIT_SUCCESS: &str = "7ad2d189f7e94e70a38c781354912448";
+const UNIT_FAILURE_RESULT: &str = "d9b373ed55a64feb8242e02dbe79a49c";
+const UNIT_PROCESS_EXIT: &str = "98e322203f7a4ed290d09fe03c09fe15";
+
+/// Journal fields needed to build a `UnitLifecycleEvent`.
+pub(crate) const LIFECYCLE_FIELDS: [&str; 8] = [
+    "MESSAGE_ID",
+    "INVOCATION_ID",
+    "JOB_RESULT",
+    "UNIT_RESULT",
+    "N_RESTARTS",
+    "COMMAND",
+    "EXIT_CODE",
+    "EXIT_STATUS",
+];
+
+/// What happened to the unit in a `UnitLifecycleEvent`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnitLifecycleKind {
+    Starting,
+    Started,
+    Stopping,
+    Stopped,
+    Reloading,
+    Reloaded,
+    /// A start/stop/reload job did not complete successfully ("Failed to start ...").
+    JobFailed {
+        job_result: Option<String>,
+    },
+    /// The unit entered the failed state (`UNIT_RESULT`, e.g. `exit-code`, `timeout`).
+    Failed {
+        result: Option<String>,
+    },
+    /// The unit finished successfully ("Deactivated successfully").
+    Succeeded,
+    /// `Restart=` scheduled an automatic restart.
+    RestartScheduled {
+        restart_count: Option<u32>,
+    },
+    /// A unit process exited.
+    ProcessExited {
+        command: Option<String>,
+        /// `exited`, `killed` or `dumped`.
+        code: Option<String>,
+        /// Exit status, or signal name when killed.
+        status: Option<String>,
+    },
+}
+
+/// One entry of a unit's lifecycle timeline, decoded from a systemd journal record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitLifecycleEvent {
+    pub timestamp: SystemTime,
+    pub kind: UnitLifecycleKind,
+    /// `INVOCATION_ID` of the run the event belongs to.
+    pub invocation_id: Option<String>,
+    pub message: Option<String>,
+    pub cursor: Option<String>,
+}
+
+impl UnitLifecycleEvent {
+    /// Decode a lifecycle record; `None` for entries without a known lifecycle `MESSAGE_ID`.
+    pub(crate) fn from_journal(entry: &crate::JournalEntry) -> Option<Self> {
+        let str_field = |key: &str| entry.get_trimmed(key).map(str::to_string);
+
+        let kind = match entry.get_trimmed("MESSAGE_ID")? {
+            UNIT_STARTING => UnitLifecycleKind::Starting,
+            UNIT_STARTED => UnitLifecycleKind::Started,
+            UNIT_STOPPING => UnitLifecycleKind::Stopping,
+            UNIT_STOPPED => UnitLifecycleKind::Stopped,
+            UNIT_RELOADING => UnitLifecycleKind::Reloading,
+            UNIT_RELOADED => UnitLifecycleKind::Reloaded,
+            UNIT_FAILED => UnitLifecycleKind::JobFailed {
+                job_result: str_field("JOB_RESULT"),
+            },
+            UNIT_FAILURE_RESULT => UnitLifecycleKind::Failed {
+                result: str_field("UNIT_RESULT"),
+            },
+            UNIT_SUCCESS => UnitLifecycleKind::Succeeded,
+            UNIT_RESTART_SCHEDULED => UnitLifecycleKind::RestartScheduled {
+                restart_count: entry
+                    .get_u64("N_RESTARTS")
+                    .and_then(|v| u32::try_from(v).ok()),
+            },
+            UNIT_PROCESS_EXIT => UnitLifecycleKind::ProcessExited {
+                command: str_field("COMMAND"),
+                code: str_field("EXIT_CODE"),
+                status: str_field("EXIT_STATUS"),
+            },
+            _ => return None,
+        };
+
+        Some(Self {
+            timestamp: entry.timestamp,
+            kind,
+            invocation_id: str_field("INVOCATION_ID"),
+            message: entry.message.clone(),
+            cursor: entry.cursor.clone(),
+        })
+    }
+}
+
+/// Lifecycle timeline of a unit, returned by `Units::restart_history`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RestartHistory {
+    pub unit: String,
+    /// Start of the queried window.
+    pub since: SystemTime,
+    /// Events in the window, oldest first.
+    pub events: Vec<UnitLifecycleEvent>,
+    /// `true` if the window held more records than were scanned; `events` then covers only the
+    /// oldest part of the window.
+    pub truncated: bool,
+}
+
+impl RestartHistory {
+    /// Number of automatic restarts scheduled by `Restart=` in the window.
+    pub fn restarts(&self) -> usize {
+        self.count(|k| matches!(k, UnitLifecycleKind::RestartScheduled { .. }))
+    }
+
+    /// Number of times the unit entered the failed state in the window.
+    pub fn failures(&self) -> usize {
+        self.count(|k| matches!(k, UnitLifecycleKind::Failed { .. }))
+    }
+
+    /// Number of starts (`Starting` events) in the window, including automatic restarts.
+    pub fn starts(&self) -> usize {
+        self.count(|k| *k == UnitLifecycleKind::Starting)
+    }
+
+    fn count(&self, pred: impl Fn(&UnitLifecycleKind) -> bool) -> usize {
+        self.events.iter().filter(|e| pred(&e.kind)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn kind(fields: &[(&str, &str)]) -> Option<UnitLifecycleKind> {
+        UnitLifecycleEvent::from_journal(&crate::JournalEntry::from_pairs(fields)).map(|e| e.kind)
+    }
+
+    #[test]
+    fn lifecycle_events_decode_message_ids() {
+        assert_eq!(
+            kind(&[("MESSAGE_ID", UNIT_STARTING)]),
+            Some(UnitLifecycleKind::Starting)
+        );
+        assert_eq!(
+            kind(&[
+                ("MESSAGE_ID", UNIT_FAILURE_RESULT),
+                ("UNIT_RESULT", "exit-code")
+            ]),
+            Some(UnitLifecycleKind::Failed {
+                result: Some("exit-code".to_string())
+            })
+        );
+        assert_eq!(
+            kind(&[("MESSAGE_ID", UNIT_RESTART_SCHEDULED), ("N_RESTARTS", "3")]),
+            Some(UnitLifecycleKind::RestartScheduled {
+                restart_count: Some(3)
+            })
+        );
+        assert_eq!(
+            kind(&[
+                ("MESSAGE_ID", UNIT_PROCESS_EXIT),
+                ("COMMAND", "app"),
+                ("EXIT_CODE", "killed"),
+                ("EXIT_STATUS", "SEGV"),
+            ]),
+            Some(UnitLifecycleKind::ProcessExited {
+                command: Some("app".to_string()),
+                code: Some("killed".to_string()),
+                status: Some("SEGV".to_string()),
+            })
+        );
+        assert_eq!(kind(&[("MESSAGE_ID", "0123")]), None);
+        assert_eq!(kind(&[("MESSAGE", "hello")]), None);
+
+        let e = UnitLifecycleEvent::from_journal(&crate::JournalEntry::from_pairs(&[
+            ("MESSAGE_ID", UNIT_STARTED),
+            ("INVOCATION_ID", "abc"),
+        ]))
+        .unwrap();
+        assert_eq!(e.invocation_id.as_deref(), Some("abc"));
+        assert_eq!(e.cursor.as_deref(), Some("s=1"));
+    }
+
+    #[test]
+    fn restart_history_counts_by_kind() {
+        let event = |kind| UnitLifecycleEvent {
+            timestamp: SystemTime::UNIX_EPOCH,
+            kind,
+            invocation_id: None,
+            message: None,
+            cursor: None,
+        };
+        let history = RestartHistory {
+            unit: "app.service".to_string(),
+            since: SystemTime::UNIX_EPOCH,
+            events: vec![
+                event(UnitLifecycleKind::Starting),
+                event(UnitLifecycleKind::Failed { result: None }),
+                event(UnitLifecycleKind::RestartScheduled {
+                    restart_count: Some(1),
+                }),
+                event(UnitLifecycleKind::Starting),
+                event(UnitLifecycleKind::Started),
+            ],
+            truncated: false,
+        };
+        assert_eq!(history.starts(), 2);
+        assert_eq!(history.failures(), 1);
+        assert_eq!(history.restarts(), 1);
+    }
+}
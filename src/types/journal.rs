@@ -21,10 +21,24 @@ pub struct JournalFilter {
     pub unit: Option<String>,
     /// Optional start time (inclusive).
     pub since: Option<SystemTime>,
+    /// Only entries of the current boot logged at least this long after it started
+    /// (`__MONOTONIC_TIMESTAMP`), AND-ed with `since`/`until`.
+    ///
+    /// Unlike `since`, this is unaffected by wall-clock jumps (e.g. an NTP sync during boot).
+    /// Not supported together with `machine`.
+    pub since_monotonic: Option<std::time::Duration>,
     /// Optional end time (inclusive).
     pub until: Option<SystemTime>,
     /// Optional cursor for pagination.
     pub after_cursor: Option<JournalCursor>,
+    /// Read order (default: `Forward`). `Reverse` returns the newest matching entries first.
+    ///
+    /// The sdjournal backend answers reverse queries by scanning forward from `since` (or the
+    /// start of the journal) and keeping the newest entries; set `since` on large journals.
+    pub direction: QueryDirection,
+    /// Only entries older than this cursor, for paging backwards with `QueryDirection::Reverse`:
+    /// pass the previous page's `next_cursor`.
+    pub before_cursor: Option<JournalCursor>,
     /// Maximum number of entries to return (default: 200).
     pub limit: u32,
     /// Maximum total payload size (approximate; default: 1 MiB).
@@ -35,6 +49,31 @@ pub struct JournalFilter {
     pub timeout: Option<std::time::Duration>,
     /// How to handle malformed JSON lines.
     pub parse_error: ParseErrorMode,
+    /// Field names to keep in `JournalEntry.fields` (default: all fields).
+    ///
+    /// The typed entry fields (`message`, `priority`, `unit`, ...) are populated either way; a
+    /// narrow allowlist lets the `journalctl` backend skip decoding everything else.
+    pub fields: Option<Vec<String>>,
+    /// Only entries at this priority or more severe (`journalctl -p`); e.g. `Priority::Err`
+    /// keeps `err`, `crit`, `alert` and `emerg`.
+    pub priority_max: Option<Priority>,
+    /// Only entries at this priority or less severe; together with `priority_max` this selects a
+    /// range (`journalctl -p min..max`).
+    pub priority_min: Option<Priority>,
+    /// Extra exact `FIELD=value` matches (e.g. `SYSLOG_IDENTIFIER=sshd`, `_PID=123`), AND-ed
+    /// with the other criteria. See `with_match`.
+    pub matches: Vec<(String, String)>,
+    /// Fold runs of identical messages into one entry with a `repeat_count` (default: off).
+    ///
+    /// Folding happens while reading, so `limit` and `max_bytes` count the folded entries and a
+    /// crash loop's repeated output does not crowd out the lines after it.
+    pub collapse_repeats: Option<CollapseOptions>,
+    /// Read the journal of this local container/VM (`journalctl --machine`) instead of the host's.
+    #[cfg_attr(
+        not(any(feature = "journal-cli", feature = "journal-sdjournal")),
+        allow(dead_code)
+    )]
+    pub(crate) machine: Option<String>,
 }
 
 impl Default for JournalFilter {
@@ -42,17 +81,264 @@ impl Default for JournalFilter {
         Self {
             unit: None,
             since: None,
+            since_monotonic: None,
             until: None,
             after_cursor: None,
+            direction: QueryDirection::Forward,
+            before_cursor: None,
             limit: 200,
             max_bytes: 1024 * 1024,
             max_message_bytes: 16 * 1024,
             timeout: None,
             parse_error: ParseErrorMode::FailFast,
+            fields: None,
+            priority_max: None,
+            priority_min: None,
+            matches: Vec::new(),
+            collapse_repeats: None,
+            machine: None,
         }
     }
 }
 
+/// Read order of a journal query (`JournalFilter.direction`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum QueryDirection {
+    /// Oldest first; page with `after_cursor`.
+    #[default]
+    Forward,
+    /// Newest first (`journalctl --reverse`); page with `before_cursor`.
+    Reverse,
+}
+
+/// Folding of repeated messages (`JournalFilter.collapse_repeats`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CollapseOptions {
+    /// An entry is folded into the previous one if it has the same `MESSAGE`, `PRIORITY` and
+    /// unit and was logged at most this long after the first entry of the run (default: 60s).
+    pub window: std::time::Duration,
+    /// Maximum number of raw entries read per query while folding (default: 100000); reaching
+    /// it ends the query as truncated.
+    pub max_scanned: u32,
+}
+
+impl Default for CollapseOptions {
+    fn default() -> Self {
+        Self {
+            window: std::time::Duration::from_secs(60),
+            max_scanned: 100_000,
+        }
+    }
+}
+
+impl CollapseOptions {
+    pub fn new(window: std::time::Duration) -> Self {
+        Self {
+            window,
+            ..Self::default()
+        }
+    }
+
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn validate(&self) -> crate::Result<()> {
+        if self.window.is_zero() {
+            return Err(crate::Error::invalid_input("collapse window must be > 0"));
+        }
+        if self.max_scanned == 0 {
+            return Err(crate::Error::invalid_input(
+                "collapse max_scanned must be > 0",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fold `entry` into the last of `entries` if it repeats it within the window; otherwise
+    /// hand it back.
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn fold(
+        &self,
+        entries: &mut [JournalEntry],
+        entry: JournalEntry,
+    ) -> Option<JournalEntry> {
+        let Some(last) = entries.last_mut() else {
+            return Some(entry);
+        };
+        let within = entry
+            .timestamp
+            .duration_since(last.timestamp)
+            .is_ok_and(|d| d <= self.window);
+        if !within
+            || entry.message != last.message
+            || entry.priority != last.priority
+            || entry.unit != last.unit
+        {
+            return Some(entry);
+        }
+        last.repeat_count = last.repeat_count.saturating_add(1);
+        if entry.cursor.is_some() {
+            last.cursor = entry.cursor;
+        }
+        None
+    }
+}
+
+impl JournalFilter {
+    /// Entries logged since the current boot, matched by boot id (`journalctl -b`) so clock
+    /// changes during boot do not drop early entries.
+    pub fn since_boot() -> crate::Result<Self> {
+        Ok(Self {
+            since_monotonic: Some(std::time::Duration::ZERO),
+            ..Default::default()
+        })
+    }
+
+    /// Entries logged within the last `window`.
+    pub fn last(window: std::time::Duration) -> Self {
+        let now = SystemTime::now();
+        Self {
+            since: Some(now.checked_sub(window).unwrap_or(std::time::UNIX_EPOCH)),
+            ..Default::default()
+        }
+    }
+
+    /// Entries logged between `since` and `until` (both inclusive).
+    pub fn between(since: SystemTime, until: SystemTime) -> Self {
+        Self {
+            since: Some(since),
+            until: Some(until),
+            ..Default::default()
+        }
+    }
+
+    /// The newest `limit` entries, newest first (`journalctl -n <limit> --reverse`).
+    pub fn tail(limit: u32) -> Self {
+        Self {
+            direction: QueryDirection::Reverse,
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// Reject `QueryDirection::Reverse` for `action`, which pages forward with `after_cursor`.
+    pub(crate) fn require_forward(&self, action: &str) -> crate::Result<()> {
+        if self.direction == QueryDirection::Reverse {
+            return Err(crate::Error::invalid_input(format!(
+                "{action} reads oldest first; QueryDirection::Reverse is not supported"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject cursor and collapsing options that do not fit `direction`.
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn validate_direction(&self) -> crate::Result<()> {
+        match self.direction {
+            QueryDirection::Forward if self.before_cursor.is_some() => Err(
+                crate::Error::invalid_input("before_cursor requires QueryDirection::Reverse"),
+            ),
+            QueryDirection::Reverse if self.after_cursor.is_some() => {
+                Err(crate::Error::invalid_input(
+                    "after_cursor is not supported with QueryDirection::Reverse; use before_cursor",
+                ))
+            }
+            QueryDirection::Reverse if self.collapse_repeats.is_some() => {
+                Err(crate::Error::invalid_input(
+                    "collapse_repeats is not supported with QueryDirection::Reverse",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Add an exact `field=value` match (e.g. `("SYSLOG_IDENTIFIER", "sshd")`).
+    pub fn with_match(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.matches.push((field.into(), value.into()));
+        self
+    }
+
+    /// Numeric `PRIORITY` range `(most severe, least severe)` selected by `priority_min` and
+    /// `priority_max`, or `None` if neither is set.
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn priority_range(&self) -> crate::Result<Option<(u8, u8)>> {
+        if self.priority_min.is_none() && self.priority_max.is_none() {
+            return Ok(None);
+        }
+        let most_severe = self.priority_min.unwrap_or(Priority::Emerg);
+        let least_severe = self.priority_max.unwrap_or(Priority::Debug);
+        if most_severe > least_severe {
+            return Err(crate::Error::invalid_input(format!(
+                "priority_min ({most_severe}) is less severe than priority_max ({least_severe})"
+            )));
+        }
+        Ok(Some((most_severe.number(), least_severe.number())))
+    }
+
+    /// Turn `since_monotonic` into a `_BOOT_ID` match for the current boot and return the
+    /// minimum `__MONOTONIC_TIMESTAMP` (µs) entries must have.
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn resolve_since_monotonic(&mut self) -> crate::Result<Option<u64>> {
+        let Some(offset) = self.since_monotonic.take() else {
+            return Ok(None);
+        };
+        if self.machine.is_some() {
+            return Err(crate::Error::invalid_input(
+                "since_monotonic is not supported for machine journals",
+            ));
+        }
+        self.matches
+            .push(("_BOOT_ID".to_string(), crate::util::current_boot_id()?));
+        Ok(Some(u64::try_from(offset.as_micros()).unwrap_or(u64::MAX)))
+    }
+
+    /// Reject allowlist entries and match fields that are not valid journal field names
+    /// (`[A-Z0-9_]`, at most 64 bytes, not starting with a digit).
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn validate_fields(&self) -> crate::Result<()> {
+        let matched = self.matches.iter().map(|(name, _)| name);
+        for name in self.fields.iter().flatten().chain(matched) {
+            let valid = !name.is_empty()
+                && name.len() <= 64
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name
+                    .bytes()
+                    .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_');
+            if !valid {
+                return Err(crate::Error::invalid_input(format!(
+                    "invalid journal field name: {name:?}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a systemd-style time specification (see `systemd.time(7)`) relative to now.
+    ///
+    /// Supported forms: `now`, `today`, `yesterday`, `tomorrow`, relative spans such as `-1h`,
+    /// `+30min`, `2 days ago`, `5min left`, and `@<unix-seconds>`. Absolute calendar timestamps
+    /// are not parsed; pass a `SystemTime` instead.
+    ///
+    /// Day keywords resolve to UTC midnight. systemd (`journalctl --since today`) uses local
+    /// midnight instead; use `parse_time_at_offset` with the host's UTC offset to match it, since
+    /// unitbus does not read the local timezone.
+    pub fn parse_time(input: &str) -> crate::Result<SystemTime> {
+        Self::parse_time_at_offset(input, 0)
+    }
+
+    /// `parse_time` with day keywords resolved to midnight in the zone `utc_offset_secs` east of
+    /// UTC (e.g. `3600` for CET), like systemd's local-time semantics.
+    pub fn parse_time_at_offset(input: &str, utc_offset_secs: i32) -> crate::Result<SystemTime> {
+        crate::util::parse_time_spec(input, SystemTime::now(), utc_offset_secs)
+    }
+}
+
+/// Whether `name` passes a `JournalFilter.fields` allowlist.
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+pub(crate) fn keeps_field(allowlist: Option<&[String]>, name: &str) -> bool {
+    allowlist.is_none_or(|fields| fields.iter().any(|f| f == name))
+}
+
 /// One log entry from journald.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -64,9 +350,311 @@ pub struct JournalEntry {
     pub priority: Option<u8>,
     pub unit: Option<String>,
     pub pid: Option<u32>,
+    /// `__MONOTONIC_TIMESTAMP`: time since the start of the entry's boot (`boot_id()`).
+    pub monotonic: Option<std::time::Duration>,
+    /// Number of consecutive identical entries this one stands for: `1`, unless
+    /// `JournalFilter.collapse_repeats` folded a run into it. `timestamp` and `fields` are those
+    /// of the first entry of the run, `cursor` that of the last.
+    pub repeat_count: u32,
     pub fields: BTreeMap<String, Vec<u8>>,
 }
 
+/// Line layout for `JournalEntry::format`, after `journalctl --output=`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum JournalOutputStyle {
+    /// `Oct 16 12:34:56 host sshd[812]: message`
+    #[default]
+    Short,
+    /// `2026-10-16T12:34:56+00:00 host sshd[812]: message`
+    ShortIso,
+}
+
+/// syslog priority (`PRIORITY=` in the journal, `LogLevel=`/`LogLevelMax=` in systemd).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Priority {
+    Emerg,
+    Alert,
+    Crit,
+    Err,
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+
+impl Priority {
+    const ALL: [Priority; 8] = [
+        Priority::Emerg,
+        Priority::Alert,
+        Priority::Crit,
+        Priority::Err,
+        Priority::Warning,
+        Priority::Notice,
+        Priority::Info,
+        Priority::Debug,
+    ];
+
+    /// The syslog number (`0` for `emerg` ... `7` for `debug`).
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_number(number: u8) -> Option<Priority> {
+        Self::ALL.get(usize::from(number)).copied()
+    }
+
+    /// The systemd name (e.g. `"warning"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Emerg => "emerg",
+            Priority::Alert => "alert",
+            Priority::Crit => "crit",
+            Priority::Err => "err",
+            Priority::Warning => "warning",
+            Priority::Notice => "notice",
+            Priority::Info => "info",
+            Priority::Debug => "debug",
+        }
+    }
+}
+
+/// Accepts the systemd name (`"err"`, `"warning"`, ...) or the number (`"3"`).
+impl std::str::FromStr for Priority {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let s = s.trim();
+        s.parse::<u8>()
+            .ok()
+            .and_then(Priority::from_number)
+            .or_else(|| Self::ALL.into_iter().find(|p| p.as_str() == s))
+            .ok_or_else(|| crate::Error::invalid_input(format!("invalid priority: {s:?}")))
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serialized as the systemd name (e.g. `"warning"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Priority {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// How `JournalEntry::format` marks an entry's priority.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SeverityMarker {
+    #[default]
+    None,
+    /// Prefix the message with the syslog level, e.g. `[err] `.
+    Label,
+    /// Color the message with ANSI escapes like `journalctl` on a terminal (errors red,
+    /// warnings yellow, notices bold, debug grey).
+    Color,
+}
+
+/// Options for `JournalEntry::format`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct JournalFormatOptions {
+    pub style: JournalOutputStyle,
+    /// Offset from UTC timestamps are shown in, in seconds east (default: 0, UTC). unitbus does
+    /// not read the local time zone database.
+    pub utc_offset_secs: i32,
+    /// Include `_HOSTNAME` (default: true).
+    pub hostname: bool,
+    pub severity: SeverityMarker,
+}
+
+impl Default for JournalFormatOptions {
+    fn default() -> Self {
+        Self {
+            style: JournalOutputStyle::Short,
+            utc_offset_secs: 0,
+            hostname: true,
+            severity: SeverityMarker::None,
+        }
+    }
+}
+
+impl JournalEntry {
+    /// Value of `field` as UTF-8 text (`None` if absent, not requested via `JournalFilter.fields`,
+    /// or binary).
+    pub fn get_str(&self, field: &str) -> Option<&str> {
+        std::str::from_utf8(self.fields.get(field)?).ok()
+    }
+
+    /// Test fixture: an entry at 1s past the epoch with cursor `s=1` and the given text fields.
+    #[cfg(test)]
+    pub(crate) fn from_pairs(fields: &[(&str, &str)]) -> Self {
+        Self {
+            timestamp: crate::util::system_time_from_unix_micros(1_000_000),
+            cursor: Some("s=1".to_string()),
+            message: None,
+            message_truncated: false,
+            priority: None,
+            unit: None,
+            pid: None,
+            monotonic: None,
+            repeat_count: 1,
+            fields: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.as_bytes().to_vec()))
+                .collect(),
+        }
+    }
+
+    /// `get_str` with surrounding whitespace removed; `None` if that leaves nothing.
+    pub(crate) fn get_trimmed(&self, field: &str) -> Option<&str> {
+        self.get_str(field).map(str::trim).filter(|v| !v.is_empty())
+    }
+
+    /// Value of `field` parsed as a decimal integer (e.g. `_UID`, `_SOURCE_REALTIME_TIMESTAMP`).
+    pub fn get_u64(&self, field: &str) -> Option<u64> {
+        self.get_str(field)?.trim().parse().ok()
+    }
+
+    /// `SYSLOG_IDENTIFIER`, the program tag (`journalctl -t`).
+    pub fn syslog_identifier(&self) -> Option<&str> {
+        self.get_str("SYSLOG_IDENTIFIER")
+    }
+
+    /// `_HOSTNAME` of the machine that logged the entry.
+    pub fn hostname(&self) -> Option<&str> {
+        self.get_str("_HOSTNAME")
+    }
+
+    /// `_BOOT_ID` (128-bit id as 32 hex digits).
+    pub fn boot_id(&self) -> Option<&str> {
+        self.get_str("_BOOT_ID")
+    }
+
+    /// Invocation id of the unit run that logged the entry: `_SYSTEMD_INVOCATION_ID` for
+    /// output of the service itself, `INVOCATION_ID` for systemd's messages about the unit.
+    pub fn invocation_id(&self) -> Option<&str> {
+        self.get_str("_SYSTEMD_INVOCATION_ID")
+            .or_else(|| self.get_str("INVOCATION_ID"))
+    }
+
+    /// Render the entry as one `journalctl`-style text line: timestamp, hostname,
+    /// `identifier[pid]:` and the message.
+    ///
+    /// The identifier is `SYSLOG_IDENTIFIER`, falling back to `_COMM`; continuation lines of
+    /// multi-line messages are indented under the first, as `journalctl` does.
+    pub fn format(&self, opts: &JournalFormatOptions) -> String {
+        let mut out = format_timestamp(self.timestamp, opts.style, opts.utc_offset_secs);
+
+        let field = |key: &str| self.get_str(key).filter(|v| !v.is_empty());
+        if opts.hostname
+            && let Some(host) = field("_HOSTNAME")
+        {
+            out.push(' ');
+            out.push_str(host);
+        }
+        out.push(' ');
+        out.push_str(
+            field("SYSLOG_IDENTIFIER")
+                .or_else(|| field("_COMM"))
+                .unwrap_or("unknown"),
+        );
+        let pid = self
+            .pid
+            .map(|p| p.to_string())
+            .or_else(|| field("_PID").map(str::to_string))
+            .or_else(|| field("SYSLOG_PID").map(str::to_string));
+        if let Some(pid) = pid {
+            out.push_str(&format!("[{pid}]"));
+        }
+        out.push_str(": ");
+
+        let indent = " ".repeat(out.chars().count());
+        let mut message = self
+            .message
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end()
+            .to_string();
+        if let (SeverityMarker::Label, Some(p)) = (opts.severity, self.priority) {
+            message = format!("[{}] {message}", priority_name(p));
+        }
+        let message = message.replace('\n', &format!("\n{indent}"));
+        match (opts.severity, self.priority.and_then(priority_color)) {
+            (SeverityMarker::Color, Some(color)) => {
+                out.push_str(color);
+                out.push_str(&message);
+                out.push_str(ANSI_RESET);
+            }
+            _ => out.push_str(&message),
+        }
+        out
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+fn priority_color(priority: u8) -> Option<&'static str> {
+    match priority {
+        0..=3 => Some("\x1b[0;1;31m"),
+        4 => Some("\x1b[0;1;33m"),
+        5 => Some("\x1b[0;1;39m"),
+        7 => Some("\x1b[0;38;5;245m"),
+        _ => None,
+    }
+}
+
+fn priority_name(priority: u8) -> &'static str {
+    Priority::from_number(priority)
+        .unwrap_or(Priority::Debug)
+        .as_str()
+}
+
+pub(crate) fn format_timestamp(
+    t: SystemTime,
+    style: JournalOutputStyle,
+    utc_offset_secs: i32,
+) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = t
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or(0)
+        .saturating_add(i64::from(utc_offset_secs));
+    let (y, m, d) = super::calendar::civil_from_days(secs.div_euclid(86_400));
+    let rem = secs.rem_euclid(86_400);
+    let (hh, mm, ss) = (rem / 3600, rem % 3600 / 60, rem % 60);
+
+    match style {
+        JournalOutputStyle::Short => {
+            let month = usize::try_from(m)
+                .ok()
+                .and_then(|m| MONTHS.get(m.wrapping_sub(1)))
+                .copied()
+                .unwrap_or("???");
+            format!("{month} {d:02} {hh:02}:{mm:02}:{ss:02}")
+        }
+        JournalOutputStyle::ShortIso => {
+            let sign = if utc_offset_secs < 0 { '-' } else { '+' };
+            let off = utc_offset_secs.unsigned_abs();
+            format!(
+                "{y:04}-{m:02}-{d:02}T{hh:02}:{mm:02}:{ss:02}{sign}{:02}:{:02}",
+                off / 3600,
+                off % 3600 / 60
+            )
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct JournalStats {
@@ -89,6 +677,73 @@ pub struct JournalResult {
     pub stats: JournalStats,
 }
 
+/// Options for `Journal::follow`. Batch size comes from the filter (`limit`, `max_bytes`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct FollowOptions {
+    /// Pause between polls once caught up (default: 500ms).
+    pub poll_interval: std::time::Duration,
+}
+
+impl Default for FollowOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: std::time::Duration::from_millis(500),
+        }
+    }
+}
+
+impl FollowOptions {
+    pub(crate) fn validate(&self) -> crate::Result<()> {
+        if self.poll_interval.is_zero() {
+            return Err(crate::Error::invalid_input("poll_interval must be > 0"));
+        }
+        Ok(())
+    }
+}
+
+/// Live tail of the journal (`journalctl -f`), created by `Journal::follow`.
+///
+/// A `Stream` of entries in journal order. The journal is only read while the stream is
+/// polled and its buffer is empty, so a slow consumer throttles reading instead of buffering
+/// more than one bounded batch. An error is yielded once and ends the stream.
+pub struct JournalFollow {
+    pub(crate) stream:
+        std::pin::Pin<Box<dyn futures_util::Stream<Item = crate::Result<JournalEntry>> + Send>>,
+}
+
+impl std::fmt::Debug for JournalFollow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("JournalFollow")
+    }
+}
+
+/// Per-machine result of `Journal::query_machines`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct MachineJournalResult {
+    pub machine: String,
+    /// The bounded query result (its own `truncated` flag), or the error for this machine only.
+    pub result: crate::Result<JournalResult>,
+}
+
+impl JournalResult {
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    pub(crate) fn from_entries(
+        entries: Vec<JournalEntry>,
+        truncated: bool,
+        stats: JournalStats,
+    ) -> Self {
+        let next_cursor = entries.last().and_then(|e| e.cursor.clone());
+        Self {
+            entries,
+            next_cursor,
+            truncated,
+            stats,
+        }
+    }
+}
+
 /// Options for `diagnose_unit_failure`.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -123,3 +778,205 @@ pub struct Diagnosis {
     pub logs: Vec<JournalEntry>,
     pub truncated: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    #[test]
+    fn collapse_folds_repeats_within_window() {
+        let collapse = CollapseOptions::new(std::time::Duration::from_secs(10));
+        let at = |secs: u64, message: &str, cursor: &str| JournalEntry {
+            timestamp: std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs),
+            cursor: Some(cursor.to_string()),
+            ..entry(message, 3)
+        };
+
+        let mut entries = Vec::new();
+        for e in [
+            at(100, "boom", "c1"),
+            at(101, "boom", "c2"),
+            at(105, "boom", "c3"),
+            at(111, "boom", "c4"),
+            at(112, "other", "c5"),
+        ] {
+            if let Some(e) = collapse.fold(&mut entries, e) {
+                entries.push(e);
+            }
+        }
+
+        let runs: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.message.as_deref().unwrap(),
+                    e.repeat_count,
+                    e.cursor.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            runs,
+            [("boom", 3, "c3"), ("boom", 1, "c4"), ("other", 1, "c5")]
+        );
+    }
+
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    #[test]
+    fn direction_checks_cursor_kind() {
+        assert!(JournalFilter::tail(50).validate_direction().is_ok());
+        assert!(JournalFilter::tail(50).require_forward("follow").is_err());
+        assert!(JournalFilter::default().require_forward("follow").is_ok());
+        let paged = JournalFilter {
+            before_cursor: Some("s=1".to_string()),
+            ..JournalFilter::tail(50)
+        };
+        assert!(paged.validate_direction().is_ok());
+        let forward_before = JournalFilter {
+            before_cursor: Some("s=1".to_string()),
+            ..Default::default()
+        };
+        assert!(forward_before.validate_direction().is_err());
+        let reverse_after = JournalFilter {
+            after_cursor: Some("s=1".to_string()),
+            ..JournalFilter::tail(50)
+        };
+        assert!(reverse_after.validate_direction().is_err());
+    }
+
+    #[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+    #[test]
+    fn priority_range_orders_bounds() {
+        assert_eq!(JournalFilter::default().priority_range().unwrap(), None);
+        let errors = JournalFilter {
+            priority_max: Some(Priority::Err),
+            ..Default::default()
+        };
+        assert_eq!(errors.priority_range().unwrap(), Some((0, 3)));
+        let band = JournalFilter {
+            priority_min: Some(Priority::Warning),
+            priority_max: Some(Priority::Info),
+            ..Default::default()
+        };
+        assert_eq!(band.priority_range().unwrap(), Some((4, 6)));
+        let inverted = JournalFilter {
+            priority_min: Some(Priority::Info),
+            priority_max: Some(Priority::Err),
+            ..Default::default()
+        };
+        assert!(inverted.priority_range().is_err());
+
+        let bad_match = JournalFilter::default().with_match("syslog_identifier", "sshd");
+        assert!(bad_match.validate_fields().is_err());
+    }
+
+    #[test]
+    fn follow_options_require_poll_interval() {
+        assert!(FollowOptions::default().validate().is_ok());
+        let opts = FollowOptions {
+            poll_interval: std::time::Duration::ZERO,
+        };
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn priority_parses_names_and_numbers() {
+        assert_eq!("warning".parse::<Priority>().unwrap(), Priority::Warning);
+        assert_eq!(" 3 ".parse::<Priority>().unwrap(), Priority::Err);
+        assert_eq!(Priority::Debug.number(), 7);
+        assert_eq!(Priority::from_number(8), None);
+        assert!("8".parse::<Priority>().is_err());
+        assert!("error".parse::<Priority>().is_err());
+        assert!(Priority::Err < Priority::Info);
+        for p in Priority::ALL {
+            assert_eq!(p.to_string().parse::<Priority>().unwrap(), p);
+        }
+    }
+
+    fn entry(message: &str, priority: u8) -> JournalEntry {
+        let fields = [
+            ("_HOSTNAME", "web1"),
+            ("SYSLOG_IDENTIFIER", "app"),
+            ("_COMM", "app-bin"),
+        ];
+        JournalEntry {
+            // 2026-10-16 12:34:56 UTC
+            timestamp: crate::util::system_time_from_unix_micros(1_792_154_096_000_000),
+            cursor: None,
+            message: Some(message.to_string()),
+            priority: Some(priority),
+            unit: Some("app.service".to_string()),
+            pid: Some(812),
+            ..JournalEntry::from_pairs(&fields)
+        }
+    }
+
+    #[test]
+    fn typed_field_accessors_decode_values() {
+        let mut e = entry("hello", 6);
+        e.fields.insert("_UID".to_string(), b"1000".to_vec());
+        e.fields
+            .insert("INVOCATION_ID".to_string(), b"0f1e".to_vec());
+        e.fields.insert("BLOB".to_string(), vec![0xff, 0x00]);
+
+        assert_eq!(e.hostname(), Some("web1"));
+        assert_eq!(e.syslog_identifier(), Some("app"));
+        assert_eq!(e.get_u64("_UID"), Some(1000));
+        assert_eq!(e.get_u64("_HOSTNAME"), None);
+        assert_eq!(e.get_str("BLOB"), None);
+        assert_eq!(e.boot_id(), None);
+        assert_eq!(e.invocation_id(), Some("0f1e"));
+
+        e.fields
+            .insert("_SYSTEMD_INVOCATION_ID".to_string(), b"aa11".to_vec());
+        assert_eq!(e.invocation_id(), Some("aa11"));
+    }
+
+    #[test]
+    fn format_matches_journalctl_short_styles() {
+        let e = entry("listening on :80", 6);
+        assert_eq!(
+            e.format(&JournalFormatOptions::default()),
+            "Oct 16 12:34:56 web1 app[812]: listening on :80"
+        );
+
+        let opts = JournalFormatOptions {
+            style: JournalOutputStyle::ShortIso,
+            utc_offset_secs: -(5 * 3600 + 30 * 60),
+            hostname: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            e.format(&opts),
+            "2026-10-16T07:04:56-05:30 app[812]: listening on :80"
+        );
+    }
+
+    #[test]
+    fn format_marks_severity_and_indents_continuation_lines() {
+        let e = entry("boom\nat main.rs:1", 3);
+        let label = JournalFormatOptions {
+            hostname: false,
+            severity: SeverityMarker::Label,
+            ..Default::default()
+        };
+        assert_eq!(
+            e.format(&label),
+            "Oct 16 12:34:56 app[812]: [err] boom\n                          at main.rs:1"
+        );
+
+        let color = JournalFormatOptions {
+            severity: SeverityMarker::Color,
+            ..Default::default()
+        };
+        assert!(e.format(&color).ends_with(
+            "app[812]: \x1b[0;1;31mboom\n                               at main.rs:1\x1b[0m"
+        ));
+        assert!(!entry("ok", 6).format(&color).contains('\x1b'));
+    }
+}
@@ -0,0 +1,969 @@
+use std::collections::HashSet;
+use std::fmt;
+
+/// How serious a `LintFinding` is.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum LintSeverity {
+    /// Probably unintended, but systemd loads the file.
+    Warning,
+    /// systemd ignores the line (and logs a complaint when loading the unit).
+    Error,
+}
+
+/// What `lint` found.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum LintKind {
+    /// Neither a section header, a comment nor a `Key=value` assignment, or an assignment
+    /// before the first section.
+    Syntax,
+    /// A section no unit type knows (`X-` sections are allowed).
+    UnknownSection,
+    /// A directive that does not belong to its section (`X-` keys are allowed). Only a warning:
+    /// it may be a directive newer than the built-in table.
+    UnknownDirective,
+    /// A single-valued directive assigned twice in a section; the later value silently wins.
+    DuplicateKey,
+    /// Whitespace that splits a value systemd reads as one word (`Environment=K=a b`).
+    UnquotedSpace,
+    /// A directive systemd still accepts but documents as obsolete.
+    Deprecated,
+}
+
+impl LintKind {
+    pub fn severity(self) -> LintSeverity {
+        match self {
+            LintKind::Syntax | LintKind::UnknownSection => LintSeverity::Error,
+            LintKind::UnknownDirective
+            | LintKind::DuplicateKey
+            | LintKind::UnquotedSpace
+            | LintKind::Deprecated => LintSeverity::Warning,
+        }
+    }
+}
+
+/// One problem reported by `lint`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct LintFinding {
+    /// 1-based line (the first line of a `\`-continued assignment).
+    pub line: usize,
+    pub kind: LintKind,
+    pub severity: LintSeverity,
+    pub section: Option<String>,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        if let Some(section) = &self.section {
+            write!(f, "[{section}] ")?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, "{key}: ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Whether `ServiceUnitSpec::render` runs `lint` on its output (mostly to catch mistakes in
+/// the `extra_*` raw lines).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LintStrictness {
+    /// Do not lint (default).
+    #[default]
+    Off,
+    /// Reject findings with `LintSeverity::Error`.
+    Errors,
+    /// Reject every finding, warnings included.
+    All,
+}
+
+impl LintStrictness {
+    /// The findings `render` rejects at this strictness.
+    pub(crate) fn rejected(self, findings: Vec<LintFinding>) -> Vec<LintFinding> {
+        match self {
+            LintStrictness::Off => Vec::new(),
+            LintStrictness::Errors => findings
+                .into_iter()
+                .filter(|f| f.severity == LintSeverity::Error)
+                .collect(),
+            LintStrictness::All => findings,
+        }
+    }
+}
+
+const UNIT: &[&str] = &[
+    "After",
+    "AllowIsolate",
+    "Before",
+    "BindsTo",
+    "CollectMode",
+    "Conflicts",
+    "DefaultDependencies",
+    "Description",
+    "Documentation",
+    "FailureAction",
+    "FailureActionExitStatus",
+    "IgnoreOnIsolate",
+    "JobRunningTimeoutSec",
+    "JobTimeoutAction",
+    "JobTimeoutRebootArgument",
+    "JobTimeoutSec",
+    "JoinsNamespaceOf",
+    "OnFailure",
+    "OnFailureJobMode",
+    "OnSuccess",
+    "OnSuccessJobMode",
+    "PartOf",
+    "PropagatesReloadTo",
+    "PropagatesStopTo",
+    "RebootArgument",
+    "RefuseManualStart",
+    "RefuseManualStop",
+    "ReloadPropagatedFrom",
+    "Requires",
+    "RequiresMountsFor",
+    "Requisite",
+    "SourcePath",
+    "StartLimitAction",
+    "StartLimitBurst",
+    "StartLimitIntervalSec",
+    "StopPropagatedFrom",
+    "StopWhenUnneeded",
+    "SuccessAction",
+    "SuccessActionExitStatus",
+    "SurviveFinalKillSignal",
+    "Upholds",
+    "Wants",
+    "WantsMountsFor",
+];
+
+const INSTALL: &[&str] = &[
+    "Alias",
+    "Also",
+    "DefaultInstance",
+    "RequiredBy",
+    "UpheldBy",
+    "WantedBy",
+];
+
+const SERVICE: &[&str] = &[
+    "BusName",
+    "ExecCondition",
+    "ExecReload",
+    "ExecStart",
+    "ExecStartPost",
+    "ExecStartPre",
+    "ExecStop",
+    "ExecStopPost",
+    "ExitType",
+    "FileDescriptorStoreMax",
+    "FileDescriptorStorePreserve",
+    "GuessMainPID",
+    "NonBlocking",
+    "NotifyAccess",
+    "OOMPolicy",
+    "OpenFile",
+    "PIDFile",
+    "ReloadSignal",
+    "RemainAfterExit",
+    "Restart",
+    "RestartForceExitStatus",
+    "RestartMaxDelaySec",
+    "RestartMode",
+    "RestartPreventExitStatus",
+    "RestartSec",
+    "RestartSteps",
+    "RootDirectoryStartOnly",
+    "RuntimeMaxSec",
+    "RuntimeRandomizedExtraSec",
+    "Sockets",
+    "SuccessExitStatus",
+    "TimeoutAbortSec",
+    "TimeoutSec",
+    "TimeoutStartFailureMode",
+    "TimeoutStartSec",
+    "TimeoutStopFailureMode",
+    "TimeoutStopSec",
+    "Type",
+    "USBFunctionDescriptors",
+    "USBFunctionStrings",
+    "WatchdogSec",
+];
+
+/// `systemd.exec(5)`: shared by services, sockets, mounts and swaps.
+const EXEC: &[&str] = &[
+    "AmbientCapabilities",
+    "AppArmorProfile",
+    "BindPaths",
+    "BindReadOnlyPaths",
+    "CPUAffinity",
+    "CPUSchedulingPolicy",
+    "CPUSchedulingPriority",
+    "CPUSchedulingResetOnFork",
+    "CacheDirectory",
+    "CacheDirectoryMode",
+    "CapabilityBoundingSet",
+    "ConfigurationDirectory",
+    "ConfigurationDirectoryMode",
+    "CoredumpFilter",
+    "DynamicUser",
+    "Environment",
+    "EnvironmentFile",
+    "ExecPaths",
+    "ExtensionDirectories",
+    "ExtensionImagePolicy",
+    "ExtensionImages",
+    "Group",
+    "IOSchedulingClass",
+    "IOSchedulingPriority",
+    "IPCNamespacePath",
+    "IgnoreSIGPIPE",
+    "ImportCredential",
+    "InaccessiblePaths",
+    "KeyringMode",
+    "LimitAS",
+    "LimitCORE",
+    "LimitCPU",
+    "LimitDATA",
+    "LimitFSIZE",
+    "LimitLOCKS",
+    "LimitMEMLOCK",
+    "LimitMSGQUEUE",
+    "LimitNICE",
+    "LimitNOFILE",
+    "LimitNPROC",
+    "LimitRSS",
+    "LimitRTPRIO",
+    "LimitRTTIME",
+    "LimitSIGPENDING",
+    "LimitSTACK",
+    "LoadCredential",
+    "LoadCredentialEncrypted",
+    "LockPersonality",
+    "LogExtraFields",
+    "LogFilterPatterns",
+    "LogLevelMax",
+    "LogNamespace",
+    "LogRateLimitBurst",
+    "LogRateLimitIntervalSec",
+    "LogsDirectory",
+    "LogsDirectoryMode",
+    "MemoryDenyWriteExecute",
+    "MemoryKSM",
+    "MountAPIVFS",
+    "MountFlags",
+    "MountImagePolicy",
+    "MountImages",
+    "NUMAMask",
+    "NUMAPolicy",
+    "NetworkNamespacePath",
+    "Nice",
+    "NoExecPaths",
+    "NoNewPrivileges",
+    "OOMScoreAdjust",
+    "PAMName",
+    "PassEnvironment",
+    "Personality",
+    "PrivateDevices",
+    "PrivateIPC",
+    "PrivateMounts",
+    "PrivateNetwork",
+    "PrivateTmp",
+    "PrivateUsers",
+    "ProcSubset",
+    "ProtectClock",
+    "ProtectControlGroups",
+    "ProtectHome",
+    "ProtectHostname",
+    "ProtectKernelLogs",
+    "ProtectKernelModules",
+    "ProtectKernelTunables",
+    "ProtectProc",
+    "ProtectSystem",
+    "ReadOnlyPaths",
+    "ReadWritePaths",
+    "RemoveIPC",
+    "RestrictAddressFamilies",
+    "RestrictFileSystems",
+    "RestrictNamespaces",
+    "RestrictRealtime",
+    "RestrictSUIDSGID",
+    "RootDirectory",
+    "RootEphemeral",
+    "RootHash",
+    "RootHashSignature",
+    "RootImage",
+    "RootImageOptions",
+    "RootImagePolicy",
+    "RootVerity",
+    "RuntimeDirectory",
+    "RuntimeDirectoryMode",
+    "RuntimeDirectoryPreserve",
+    "SELinuxContext",
+    "SecureBits",
+    "SetCredential",
+    "SetCredentialEncrypted",
+    "SetLoginEnvironment",
+    "SmackProcessLabel",
+    "StandardError",
+    "StandardInput",
+    "StandardInputData",
+    "StandardInputText",
+    "StandardOutput",
+    "StateDirectory",
+    "StateDirectoryMode",
+    "SupplementaryGroups",
+    "SyslogFacility",
+    "SyslogIdentifier",
+    "SyslogLevel",
+    "SyslogLevelPrefix",
+    "SystemCallArchitectures",
+    "SystemCallErrorNumber",
+    "SystemCallFilter",
+    "SystemCallLog",
+    "TTYColumns",
+    "TTYPath",
+    "TTYReset",
+    "TTYRows",
+    "TTYVHangup",
+    "TTYVTDisallocate",
+    "TemporaryFileSystem",
+    "TimeoutCleanSec",
+    "TimerSlackNSec",
+    "UMask",
+    "UnsetEnvironment",
+    "User",
+    "UtmpIdentifier",
+    "UtmpMode",
+    "WorkingDirectory",
+];
+
+/// `systemd.kill(5)`: shared by services, sockets, mounts, swaps and scopes.
+const KILL: &[&str] = &[
+    "FinalKillSignal",
+    "KillMode",
+    "KillSignal",
+    "RestartKillSignal",
+    "SendSIGHUP",
+    "SendSIGKILL",
+    "WatchdogSignal",
+];
+
+/// `systemd.resource-control(5)`: shared by every unit type with a cgroup.
+const CGROUP: &[&str] = &[
+    "AllowedCPUs",
+    "AllowedMemoryNodes",
+    "BPFProgram",
+    "CPUAccounting",
+    "CPUQuota",
+    "CPUQuotaPeriodSec",
+    "CPUWeight",
+    "CoredumpReceive",
+    "DefaultMemoryLow",
+    "DefaultMemoryMin",
+    "DefaultStartupMemoryLow",
+    "Delegate",
+    "DelegateSubgroup",
+    "DeviceAllow",
+    "DevicePolicy",
+    "DisableControllers",
+    "IOAccounting",
+    "IODeviceLatencyTargetSec",
+    "IODeviceWeight",
+    "IOReadBandwidthMax",
+    "IOReadIOPSMax",
+    "IOWeight",
+    "IOWriteBandwidthMax",
+    "IOWriteIOPSMax",
+    "IPAccounting",
+    "IPAddressAllow",
+    "IPAddressDeny",
+    "IPEgressFilterPath",
+    "IPIngressFilterPath",
+    "ManagedOOMMemoryPressure",
+    "ManagedOOMMemoryPressureDurationSec",
+    "ManagedOOMMemoryPressureLimit",
+    "ManagedOOMPreference",
+    "ManagedOOMSwap",
+    "MemoryAccounting",
+    "MemoryHigh",
+    "MemoryLow",
+    "MemoryMax",
+    "MemoryMin",
+    "MemoryPressureThresholdSec",
+    "MemoryPressureWatch",
+    "MemorySwapMax",
+    "MemoryZSwapMax",
+    "MemoryZSwapWriteback",
+    "NFTSet",
+    "RestrictNetworkInterfaces",
+    "Slice",
+    "SocketBindAllow",
+    "SocketBindDeny",
+    "StartupAllowedCPUs",
+    "StartupAllowedMemoryNodes",
+    "StartupCPUWeight",
+    "StartupIOWeight",
+    "StartupMemoryHigh",
+    "StartupMemoryLow",
+    "StartupMemoryMax",
+    "StartupMemorySwapMax",
+    "StartupMemoryZSwapMax",
+    "TasksAccounting",
+    "TasksMax",
+];
+
+const SOCKET: &[&str] = &[
+    "Accept",
+    "Backlog",
+    "BindIPv6Only",
+    "BindToDevice",
+    "Broadcast",
+    "DeferAcceptSec",
+    "DirectoryMode",
+    "ExecStartPost",
+    "ExecStartPre",
+    "ExecStopPost",
+    "ExecStopPre",
+    "FileDescriptorName",
+    "FlushPending",
+    "FreeBind",
+    "IPTOS",
+    "IPTTL",
+    "KeepAlive",
+    "KeepAliveIntervalSec",
+    "KeepAliveProbes",
+    "KeepAliveTimeSec",
+    "ListenDatagram",
+    "ListenFIFO",
+    "ListenMessageQueue",
+    "ListenNetlink",
+    "ListenSequentialPacket",
+    "ListenSpecial",
+    "ListenStream",
+    "ListenUSBFunction",
+    "Mark",
+    "MaxConnections",
+    "MaxConnectionsPerSource",
+    "MessageQueueMaxMessages",
+    "MessageQueueMessageSize",
+    "NoDelay",
+    "PassCredentials",
+    "PassFileDescriptorsToExec",
+    "PassPacketInfo",
+    "PassSecurity",
+    "PipeSize",
+    "PollLimitBurst",
+    "PollLimitIntervalSec",
+    "Priority",
+    "ReceiveBuffer",
+    "RemoveOnStop",
+    "ReusePort",
+    "SELinuxContextFromNet",
+    "SendBuffer",
+    "Service",
+    "SmackLabel",
+    "SmackLabelIPIn",
+    "SmackLabelIPOut",
+    "SocketGroup",
+    "SocketMode",
+    "SocketProtocol",
+    "SocketUser",
+    "Symlinks",
+    "TCPCongestion",
+    "TimeoutSec",
+    "Timestamping",
+    "Transparent",
+    "TriggerLimitBurst",
+    "TriggerLimitIntervalSec",
+    "Writable",
+];
+
+const TIMER: &[&str] = &[
+    "AccuracySec",
+    "FixedRandomDelay",
+    "OnActiveSec",
+    "OnBootSec",
+    "OnCalendar",
+    "OnClockChange",
+    "OnStartupSec",
+    "OnTimezoneChange",
+    "OnUnitActiveSec",
+    "OnUnitInactiveSec",
+    "Persistent",
+    "RandomizedDelaySec",
+    "RemainAfterElapse",
+    "Unit",
+    "WakeSystem",
+];
+
+const PATH: &[&str] = &[
+    "DirectoryMode",
+    "DirectoryNotEmpty",
+    "MakeDirectory",
+    "PathChanged",
+    "PathExists",
+    "PathExistsGlob",
+    "PathModified",
+    "TriggerLimitBurst",
+    "TriggerLimitIntervalSec",
+    "Unit",
+];
+
+const MOUNT: &[&str] = &[
+    "DirectoryMode",
+    "ForceUnmount",
+    "LazyUnmount",
+    "Options",
+    "ReadWriteOnly",
+    "SloppyOptions",
+    "TimeoutSec",
+    "Type",
+    "What",
+    "Where",
+];
+
+const AUTOMOUNT: &[&str] = &["DirectoryMode", "ExtraOptions", "TimeoutIdleSec", "Where"];
+
+const SWAP: &[&str] = &["Options", "Priority", "TimeoutSec", "What"];
+
+const SCOPE: &[&str] = &["OOMPolicy", "RuntimeMaxSec", "RuntimeRandomizedExtraSec"];
+
+/// Directives of each known section.
+fn section_directives(section: &str) -> Option<&'static [&'static [&'static str]]> {
+    Some(match section {
+        "Unit" => &[UNIT],
+        "Install" => &[INSTALL],
+        "Service" => &[SERVICE, EXEC, KILL, CGROUP],
+        "Socket" => &[SOCKET, EXEC, KILL, CGROUP],
+        "Mount" => &[MOUNT, EXEC, KILL, CGROUP],
+        "Swap" => &[SWAP, EXEC, KILL, CGROUP],
+        "Scope" => &[SCOPE, KILL, CGROUP],
+        "Slice" => &[CGROUP],
+        "Timer" => &[TIMER],
+        "Path" => &[PATH],
+        "Automount" => &[AUTOMOUNT],
+        _ => return None,
+    })
+}
+
+/// `(section, key, replacement)` for obsolete directives still parsed by systemd.
+const DEPRECATED: &[(&str, &str, &str)] = &[
+    ("Unit", "BindTo", "BindsTo="),
+    ("Unit", "OnFailureIsolate", "OnFailureJobMode=isolate"),
+    ("Unit", "PropagateReloadFrom", "ReloadPropagatedFrom="),
+    ("Unit", "PropagateReloadTo", "PropagatesReloadTo="),
+    ("Unit", "RequiresOverridable", "Requires="),
+    ("Unit", "RequisiteOverridable", "Requisite="),
+    ("Unit", "StartLimitInterval", "StartLimitIntervalSec="),
+    ("Service", "FailureAction", "FailureAction= in [Unit]"),
+    (
+        "Service",
+        "PermissionsStartOnly",
+        "the \"+\" prefix on Exec lines",
+    ),
+    ("Service", "RebootArgument", "RebootArgument= in [Unit]"),
+    ("Service", "StartLimitAction", "StartLimitAction= in [Unit]"),
+    ("Service", "StartLimitBurst", "StartLimitBurst= in [Unit]"),
+    (
+        "Service",
+        "StartLimitInterval",
+        "StartLimitIntervalSec= in [Unit]",
+    ),
+    ("*", "BlockIOAccounting", "IOAccounting="),
+    ("*", "BlockIODeviceWeight", "IODeviceWeight="),
+    ("*", "BlockIOReadBandwidth", "IOReadBandwidthMax="),
+    ("*", "BlockIOWeight", "IOWeight="),
+    ("*", "BlockIOWriteBandwidth", "IOWriteBandwidthMax="),
+    ("*", "CPUShares", "CPUWeight="),
+    ("*", "MemoryLimit", "MemoryMax="),
+    ("*", "StartupBlockIOWeight", "StartupIOWeight="),
+    ("*", "StartupCPUShares", "StartupCPUWeight="),
+];
+
+/// Directives that may be repeated; each assignment appends (an empty one resets the list).
+const LIST_DIRECTIVES: &[&str] = &[
+    "After",
+    "Alias",
+    "Also",
+    "AmbientCapabilities",
+    "Before",
+    "BindPaths",
+    "BindReadOnlyPaths",
+    "BindsTo",
+    "CacheDirectory",
+    "CapabilityBoundingSet",
+    "ConfigurationDirectory",
+    "Conflicts",
+    "DeviceAllow",
+    "DirectoryNotEmpty",
+    "Documentation",
+    "Environment",
+    "EnvironmentFile",
+    "ExecCondition",
+    "ExecPaths",
+    "ExecReload",
+    "ExecStart",
+    "ExecStartPost",
+    "ExecStartPre",
+    "ExecStop",
+    "ExecStopPost",
+    "ExecStopPre",
+    "ExtensionDirectories",
+    "ExtensionImages",
+    "IODeviceLatencyTargetSec",
+    "IODeviceWeight",
+    "IOReadBandwidthMax",
+    "IOReadIOPSMax",
+    "IOWriteBandwidthMax",
+    "IOWriteIOPSMax",
+    "IPAddressAllow",
+    "IPAddressDeny",
+    "IPEgressFilterPath",
+    "IPIngressFilterPath",
+    "ImportCredential",
+    "InaccessiblePaths",
+    "JoinsNamespaceOf",
+    "ListenDatagram",
+    "ListenFIFO",
+    "ListenMessageQueue",
+    "ListenNetlink",
+    "ListenSequentialPacket",
+    "ListenSpecial",
+    "ListenStream",
+    "ListenUSBFunction",
+    "LoadCredential",
+    "LoadCredentialEncrypted",
+    "LogExtraFields",
+    "LogFilterPatterns",
+    "LogsDirectory",
+    "MountImages",
+    "NoExecPaths",
+    "OnActiveSec",
+    "OnBootSec",
+    "OnCalendar",
+    "OnFailure",
+    "OnStartupSec",
+    "OnSuccess",
+    "OnUnitActiveSec",
+    "OnUnitInactiveSec",
+    "OpenFile",
+    "PartOf",
+    "PassEnvironment",
+    "PathChanged",
+    "PathExists",
+    "PathExistsGlob",
+    "PathModified",
+    "PropagatesReloadTo",
+    "PropagatesStopTo",
+    "ReadOnlyPaths",
+    "ReadWritePaths",
+    "ReloadPropagatedFrom",
+    "RequiredBy",
+    "Requires",
+    "RequiresMountsFor",
+    "Requisite",
+    "RestartForceExitStatus",
+    "RestartPreventExitStatus",
+    "RestrictAddressFamilies",
+    "RestrictFileSystems",
+    "RestrictNetworkInterfaces",
+    "RuntimeDirectory",
+    "SetCredential",
+    "SetCredentialEncrypted",
+    "SocketBindAllow",
+    "SocketBindDeny",
+    "Sockets",
+    "StandardInputData",
+    "StandardInputText",
+    "StateDirectory",
+    "StopPropagatedFrom",
+    "SuccessExitStatus",
+    "SupplementaryGroups",
+    "Symlinks",
+    "SystemCallArchitectures",
+    "SystemCallFilter",
+    "SystemCallLog",
+    "TemporaryFileSystem",
+    "UnsetEnvironment",
+    "UpheldBy",
+    "Upholds",
+    "WantedBy",
+    "Wants",
+    "WantsMountsFor",
+];
+
+/// Single-path directives where a space must be quoted or escaped (`\x20`).
+const PATH_VALUES: &[&str] = &[
+    "PIDFile",
+    "RootDirectory",
+    "RootImage",
+    "TTYPath",
+    "What",
+    "Where",
+    "WorkingDirectory",
+];
+
+/// Check unit file or drop-in `content` without systemd (`systemd-analyze verify` needs the
+/// unit loaded on a running manager).
+///
+/// Directives are checked against the sections they belong to per `systemd.directives(7)`;
+/// `X-` sections and keys, `Condition*` and `Assert*` are accepted. Directives newer than this
+/// table show up as `UnknownDirective`, so treat findings as advice on hosts with a newer
+/// systemd.
+pub fn lint(content: &str) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut section: Option<String> = None;
+    let mut seen: HashSet<String> = HashSet::new();
+
+    let mut lines = content.lines().enumerate();
+    while let Some((idx, raw)) = lines.next() {
+        let line_no = idx + 1;
+        let mut line = raw.trim().to_string();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        // A trailing backslash continues the assignment on the next line.
+        while line.ends_with('\\') {
+            line.pop();
+            line.push(' ');
+            match lines.next() {
+                Some((_, next)) => line.push_str(next.trim()),
+                None => break,
+            }
+        }
+
+        let finding =
+            |kind: LintKind, section: &Option<String>, key: Option<&str>, message| LintFinding {
+                line: line_no,
+                kind,
+                severity: kind.severity(),
+                section: section.clone(),
+                key: key.map(str::to_string),
+                message,
+            };
+
+        if let Some(name) = line.strip_prefix('[') {
+            let Some(name) = name.strip_suffix(']').filter(|n| !n.is_empty()) else {
+                findings.push(finding(
+                    LintKind::Syntax,
+                    &None,
+                    None,
+                    format!("malformed section header: {line}"),
+                ));
+                section = None;
+                continue;
+            };
+            section = Some(name.to_string());
+            seen.clear();
+            if !name.starts_with("X-") && section_directives(name).is_none() {
+                findings.push(finding(
+                    LintKind::UnknownSection,
+                    &section,
+                    None,
+                    "unknown section".to_string(),
+                ));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            findings.push(finding(
+                LintKind::Syntax,
+                &section,
+                None,
+                format!("expected Key=value: {line}"),
+            ));
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            findings.push(finding(
+                LintKind::Syntax,
+                &section,
+                None,
+                format!("invalid directive name: {key:?}"),
+            ));
+            continue;
+        }
+        let Some(section_name) = section.as_deref() else {
+            findings.push(finding(
+                LintKind::Syntax,
+                &None,
+                Some(key),
+                "assignment outside of a section".to_string(),
+            ));
+            continue;
+        };
+        if section_name.starts_with("X-") || key.starts_with("X-") {
+            continue;
+        }
+
+        let deprecated = DEPRECATED
+            .iter()
+            .find(|(s, k, _)| *k == key && (*s == section_name || *s == "*"));
+        if let Some((_, _, replacement)) = deprecated {
+            findings.push(finding(
+                LintKind::Deprecated,
+                &section,
+                Some(key),
+                format!("deprecated, use {replacement}"),
+            ));
+        } else if let Some(groups) = section_directives(section_name) {
+            let condition = section_name == "Unit"
+                && (key.starts_with("Condition") || key.starts_with("Assert"));
+            if !condition && !groups.iter().any(|g| g.contains(&key)) {
+                findings.push(finding(
+                    LintKind::UnknownDirective,
+                    &section,
+                    Some(key),
+                    format!("unknown directive in [{section_name}]"),
+                ));
+                continue;
+            }
+        } else {
+            // Keys of an unknown section were already covered by its finding.
+            continue;
+        }
+
+        let repeatable = LIST_DIRECTIVES.contains(&key)
+            || key.starts_with("Condition")
+            || key.starts_with("Assert")
+            || key.starts_with("Listen");
+        // An empty assignment resets the setting, so re-setting it afterwards is intended.
+        if value.is_empty() {
+            seen.remove(key);
+        } else if !seen.insert(key.to_string()) && !repeatable {
+            findings.push(finding(
+                LintKind::DuplicateKey,
+                &section,
+                Some(key),
+                "assigned more than once; only the last value applies".to_string(),
+            ));
+        }
+
+        if let Some(message) = unquoted_space(key, value) {
+            findings.push(finding(
+                LintKind::UnquotedSpace,
+                &section,
+                Some(key),
+                message,
+            ));
+        }
+    }
+    findings
+}
+
+fn unquoted_space(key: &str, value: &str) -> Option<String> {
+    if key == "Environment" {
+        // Words are split on whitespace outside quotes; each must be an assignment.
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut quote = None;
+        for c in value.chars() {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), c) if c == q => quote = None,
+                (None, c) if c.is_whitespace() => {
+                    if !word.is_empty() {
+                        words.push(std::mem::take(&mut word));
+                    }
+                }
+                _ => word.push(c),
+            }
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        return words
+            .iter()
+            .find(|w| !w.contains('='))
+            .map(|w| format!("{w:?} is not an assignment; quote values containing spaces"));
+    }
+    if PATH_VALUES.contains(&key)
+        && value.chars().any(char::is_whitespace)
+        && !value.starts_with('"')
+    {
+        return Some("path contains whitespace; quote it or escape as \\x20".to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn kinds(content: &str) -> Vec<(usize, LintKind, Option<String>)> {
+        lint(content)
+            .into_iter()
+            .map(|f| (f.line, f.kind, f.key))
+            .collect()
+    }
+
+    #[test]
+    fn clean_unit_has_no_findings() {
+        let unit = "# comment\n[Unit]\nDescription=App\nAfter=network.target\n\
+                    After=postgresql.service\nConditionPathExists=/etc/app\n\n\
+                    [Service]\nExecStart=\nExecStart=/usr/bin/app \\\n  --verbose\n\
+                    Environment=\"A=1 2\" B=3\nMemoryMax=1G\nX-Custom=1\n\n\
+                    [Install]\nWantedBy=multi-user.target\n\n[X-Vendor]\nAnything=goes\n";
+        assert_eq!(lint(unit), Vec::new());
+    }
+
+    #[test]
+    fn findings_cover_each_kind() {
+        let unit = "Description=orphan\n[Unit]\nDescription=a\nDescription=b\n\
+                    StartLimitInterval=10\n[Service]\nExecStrat=/bin/true\n\
+                    Environment=A=b c\nWorkingDirectory=/srv/my app\nMemoryLimit=1G\n\
+                    Type=simple\nType=\nType=notify\nnot an assignment\n[Servce]\nFoo=1\n";
+        let key = |k: &str| Some(k.to_string());
+        assert_eq!(
+            kinds(unit),
+            vec![
+                (1, LintKind::Syntax, key("Description")),
+                (4, LintKind::DuplicateKey, key("Description")),
+                (5, LintKind::Deprecated, key("StartLimitInterval")),
+                (7, LintKind::UnknownDirective, key("ExecStrat")),
+                (8, LintKind::UnquotedSpace, key("Environment")),
+                (9, LintKind::UnquotedSpace, key("WorkingDirectory")),
+                (10, LintKind::Deprecated, key("MemoryLimit")),
+                (14, LintKind::Syntax, None),
+                (15, LintKind::UnknownSection, None),
+            ]
+        );
+        let finding = &lint(unit)[3];
+        assert_eq!(finding.severity, LintSeverity::Warning);
+        assert_eq!(
+            finding.to_string(),
+            "line 7: [Service] ExecStrat: unknown directive in [Service]"
+        );
+    }
+
+    #[test]
+    fn strictness_filters_by_severity() {
+        let findings = lint("[Service]\nMemoryLimit=1G\nBogus=1\n[Servce]\n");
+        assert_eq!(findings.len(), 3);
+        assert!(LintStrictness::Off.rejected(findings.clone()).is_empty());
+        assert_eq!(LintStrictness::Errors.rejected(findings.clone()).len(), 1);
+        assert_eq!(LintStrictness::All.rejected(findings).len(), 3);
+    }
+}
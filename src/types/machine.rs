@@ -0,0 +1,454 @@
+use crate::{Error, Result, util};
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Root file system of a container started by `Machines::run_container`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ImageSpec {
+    /// A directory tree (`systemd-nspawn --directory=`).
+    Directory(PathBuf),
+    /// A raw disk image or block device (`systemd-nspawn --image=`).
+    Image(PathBuf),
+    /// An image known to `systemd-machined` (`/var/lib/machines/<name>`, `machinectl
+    /// list-images`), located by `systemd-nspawn --machine=` itself. The machine is therefore
+    /// named after the image; a different `ContainerOptions.name` is rejected.
+    Machine(String),
+}
+
+impl ImageSpec {
+    /// Machine name used when `ContainerOptions.name` is unset: the image name, or the file
+    /// name of the directory/image without extension.
+    fn default_machine_name(&self) -> Option<String> {
+        match self {
+            ImageSpec::Machine(name) => Some(name.clone()),
+            ImageSpec::Directory(path) | ImageSpec::Image(path) => {
+                let stem = path.file_stem()?.to_str()?;
+                let stem = stem.strip_suffix(".raw").unwrap_or(stem);
+                Some(stem.to_string())
+            }
+        }
+    }
+}
+
+/// Options for `Machines::run_container`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ContainerOptions {
+    /// Machine name registered with `systemd-machined` (default: derived from the image). Must
+    /// equal the image name for `ImageSpec::Machine`.
+    pub name: Option<String>,
+    /// Boot the image's init system (`--boot`, default: `true`). When `false`, `argv` is run as
+    /// the container's PID 1 instead.
+    pub boot: bool,
+    /// Command for non-booted containers; passed to init as arguments when `boot` is set.
+    pub argv: Vec<String>,
+    /// Run on a throwaway snapshot removed when the container exits (`--ephemeral`, default:
+    /// `false`).
+    pub ephemeral: bool,
+    /// Mount the root file system read-only (`--read-only`, default: `false`).
+    pub read_only: bool,
+    /// Give the container its own network namespace with a veth link to the host
+    /// (`--network-veth`, default: `false`, i.e. share the host network).
+    pub network_veth: bool,
+    /// Host paths bind-mounted into the container as `(host, container)` (`--bind=`).
+    pub bind: Vec<(String, String)>,
+    /// Read-only bind mounts as `(host, container)` (`--bind-ro=`).
+    pub bind_ro: Vec<(String, String)>,
+    /// Environment of the container's init/command (`--setenv=`).
+    pub env: Vec<(String, String)>,
+    /// Further `systemd-nspawn` options, appended verbatim before the command.
+    pub extra_args: Vec<String>,
+    /// How long to wait for the container to start and register (default: 60s).
+    pub timeout: Duration,
+}
+
+impl Default for ContainerOptions {
+    fn default() -> Self {
+        Self {
+            name: None,
+            boot: true,
+            argv: Vec::new(),
+            ephemeral: false,
+            read_only: false,
+            network_veth: false,
+            bind: Vec::new(),
+            bind_ro: Vec::new(),
+            env: Vec::new(),
+            extra_args: Vec::new(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A running container started by `Machines::run_container`.
+///
+/// Pass `machine` to `UnitBus::connect_machine` to manage units inside it, or to
+/// `Journal::query_machines` to read its journal.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContainerHandle {
+    /// Name registered with `systemd-machined`.
+    pub machine: String,
+    /// Transient host service running `systemd-nspawn` (e.g. `unitbus-nspawn-web.service`).
+    pub unit: String,
+    /// Host PID of the container's init process.
+    pub leader: u32,
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(ContainerHandle);
+
+/// How `systemd-importd` verifies a pulled image (`Machines::pull_image`).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ImageVerify {
+    /// Accept the download as is.
+    No,
+    /// Check it against the `SHA256SUMS` file next to the image.
+    Checksum,
+    /// Check `SHA256SUMS` and its GPG signature (`SHA256SUMS.gpg`) against the import keyring.
+    Signature,
+}
+
+impl ImageVerify {
+    /// The importd verify mode (`"no"`, `"checksum"`, `"signature"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImageVerify::No => "no",
+            ImageVerify::Checksum => "checksum",
+            ImageVerify::Signature => "signature",
+        }
+    }
+}
+
+/// Serialized as the importd mode (e.g. `"signature"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for ImageVerify {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// Event of an image download, see `ImagePull::next`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ImagePullEvent {
+    /// Download/unpack progress as reported by importd, from `0.0` to `1.0`.
+    Progress { fraction: f64 },
+    /// The image was downloaded, verified and installed as `local` (usable as
+    /// `ImageSpec::Machine`).
+    Done { local: String },
+}
+
+/// A running `systemd-importd` download started by `Machines::pull_image`.
+///
+/// Events are read by polling the transfer's `Progress`; completion comes from importd's
+/// `TransferRemoved` signal.
+#[derive(Debug)]
+pub struct ImagePull {
+    pub(crate) inner: std::sync::Arc<crate::Inner>,
+    pub(crate) url: String,
+    pub(crate) local: String,
+    pub(crate) transfer_id: u32,
+    pub(crate) transfer_path: String,
+    pub(crate) signals: Option<zbus::proxy::SignalStream<'static>>,
+    pub(crate) last_fraction: Option<f64>,
+    pub(crate) finished: bool,
+}
+
+/// The importd method for `url`: `PullTar` for tarballs (`.tar`, `.tar.xz`, `.tgz`, ...),
+/// `PullRaw` for everything else (raw/qcow2 disk images, compressed or not).
+pub(crate) fn pull_method(url: &str) -> Result<&'static str> {
+    let file = url_file_name(url)?;
+    let tar = file.contains(".tar") || file.ends_with(".tgz") || file.ends_with(".txz");
+    Ok(if tar { "PullTar" } else { "PullRaw" })
+}
+
+/// Local image name for `url`, derived like `importctl pull-tar`/`pull-raw` do without an
+/// explicit name: the URL's file name minus archive, compression and disk image suffixes
+/// (`debian-12.tar.xz` and `debian-12.raw.xz` both become `debian-12`).
+pub(crate) fn pull_local_name(url: &str) -> Result<String> {
+    const SUFFIXES: &[&str] = &[
+        ".tar", ".tgz", ".txz", ".tbz2", ".tzst", ".xz", ".gz", ".bz2", ".zst", ".raw", ".qcow2",
+        ".img", ".bin",
+    ];
+
+    let mut name = url_file_name(url)?;
+    while let Some(rest) = SUFFIXES.iter().find_map(|s| name.strip_suffix(s)) {
+        if rest.is_empty() {
+            break;
+        }
+        name = rest;
+    }
+    util::validate_machine_name(name).map_err(|_| {
+        Error::invalid_input(format!("cannot derive a local image name from url: {url}"))
+    })?;
+    Ok(name.to_string())
+}
+
+/// Last path component of an http(s) `url`.
+fn url_file_name(url: &str) -> Result<&str> {
+    util::validate_no_control("image url", url)?;
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return Err(Error::invalid_input(format!(
+            "image url must be http(s): {url}"
+        )));
+    };
+    let path = rest.split(['?', '#']).next().unwrap_or_default();
+    let file = match path.split_once('/') {
+        Some((_, path)) => path.rsplit('/').next().unwrap_or_default(),
+        None => "",
+    };
+    if file.is_empty() || file.chars().any(char::is_whitespace) {
+        return Err(Error::invalid_input(format!(
+            "image url must name a file: {url}"
+        )));
+    }
+    Ok(file)
+}
+
+/// Machine name for `image`/`opts`, validated like `machinectl` does.
+pub(crate) fn container_machine_name(image: &ImageSpec, opts: &ContainerOptions) -> Result<String> {
+    let name = match &opts.name {
+        Some(name) => name.clone(),
+        None => image.default_machine_name().ok_or_else(|| {
+            Error::invalid_input("cannot derive a machine name from the image; set name")
+        })?,
+    };
+    util::validate_machine_name(&name)?;
+    Ok(name)
+}
+
+/// Full `systemd-nspawn` command line for the transient service.
+pub(crate) fn nspawn_argv(
+    image: &ImageSpec,
+    opts: &ContainerOptions,
+    machine: &str,
+) -> Result<Vec<String>> {
+    if opts.timeout.is_zero() {
+        return Err(Error::invalid_input("timeout must be > 0"));
+    }
+    if !opts.boot && opts.argv.is_empty() {
+        return Err(Error::invalid_input(
+            "argv must not be empty when boot is false",
+        ));
+    }
+
+    // `--keep-unit` registers the service itself with machined instead of a new scope, so
+    // stopping the unit stops the container.
+    let mut argv = vec![
+        "/usr/bin/systemd-nspawn".to_string(),
+        "--quiet".to_string(),
+        "--keep-unit".to_string(),
+        "--register=yes".to_string(),
+        format!("--machine={machine}"),
+    ];
+    match image {
+        ImageSpec::Directory(path) | ImageSpec::Image(path) => {
+            let flag = match image {
+                ImageSpec::Directory(_) => "--directory",
+                _ => "--image",
+            };
+            let path = path
+                .to_str()
+                .ok_or_else(|| Error::invalid_input("image path must be valid UTF-8"))?;
+            util::validate_no_control("image path", path)?;
+            if !path.starts_with('/') {
+                return Err(Error::invalid_input(format!(
+                    "image path must be absolute: {path}"
+                )));
+            }
+            argv.push(format!("{flag}={path}"));
+        }
+        ImageSpec::Machine(name) => {
+            util::validate_machine_name(name)?;
+            // nspawn finds the image through `--machine=`, so another name would boot a
+            // different (or no) image.
+            if name != machine {
+                return Err(Error::invalid_input(format!(
+                    "machine name {machine} must match image {name} for ImageSpec::Machine"
+                )));
+            }
+        }
+    }
+
+    if opts.boot {
+        argv.push("--boot".to_string());
+    }
+    if opts.ephemeral {
+        argv.push("--ephemeral".to_string());
+    }
+    if opts.read_only {
+        argv.push("--read-only".to_string());
+    }
+    if opts.network_veth {
+        argv.push("--network-veth".to_string());
+    }
+    for (flag, binds) in [("--bind", &opts.bind), ("--bind-ro", &opts.bind_ro)] {
+        for (host, container) in binds {
+            for path in [host, container] {
+                util::validate_no_control("bind path", path)?;
+                if !path.starts_with('/') || path.contains(':') {
+                    return Err(Error::invalid_input(format!(
+                        "bind paths must be absolute without ':': {path}"
+                    )));
+                }
+            }
+            argv.push(format!("{flag}={host}:{container}"));
+        }
+    }
+    for (k, v) in &opts.env {
+        util::validate_env_key(k)?;
+        util::validate_no_control("env value", v)?;
+        argv.push(format!("--setenv={k}={v}"));
+    }
+    for arg in &opts.extra_args {
+        util::validate_no_control("extra_args", arg)?;
+        if !arg.starts_with('-') {
+            return Err(Error::invalid_input(format!(
+                "extra_args must be options: {arg}"
+            )));
+        }
+        argv.push(arg.clone());
+    }
+
+    if !opts.argv.is_empty() {
+        for arg in &opts.argv {
+            util::validate_no_control("argv", arg)?;
+        }
+        // Everything after `--` belongs to the container's command/init.
+        argv.push("--".to_string());
+        argv.extend(opts.argv.iter().cloned());
+    }
+    Ok(argv)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn nspawn_argv_renders_options() {
+        let image = ImageSpec::Directory(PathBuf::from("/var/lib/machines/web"));
+        let opts = ContainerOptions {
+            ephemeral: true,
+            bind_ro: vec![("/srv/app".to_string(), "/app".to_string())],
+            env: vec![("APP_ENV".to_string(), "test".to_string())],
+            argv: vec!["systemd.unit=multi-user.target".to_string()],
+            ..Default::default()
+        };
+        let machine = container_machine_name(&image, &opts).unwrap();
+        assert_eq!(machine, "web");
+        assert_eq!(
+            nspawn_argv(&image, &opts, &machine).unwrap(),
+            [
+                "/usr/bin/systemd-nspawn",
+                "--quiet",
+                "--keep-unit",
+                "--register=yes",
+                "--machine=web",
+                "--directory=/var/lib/machines/web",
+                "--boot",
+                "--ephemeral",
+                "--bind-ro=/srv/app:/app",
+                "--setenv=APP_ENV=test",
+                "--",
+                "systemd.unit=multi-user.target",
+            ]
+        );
+
+        let raw = ImageSpec::Image(PathBuf::from("/images/debian-12.raw"));
+        assert_eq!(
+            container_machine_name(&raw, &ContainerOptions::default()).unwrap(),
+            "debian-12"
+        );
+    }
+
+    #[test]
+    fn pull_method_follows_file_type() {
+        for (url, method) in [
+            ("https://example.com/images/debian-12.tar.xz", "PullTar"),
+            ("https://example.com/rootfs.tgz?sig=1", "PullTar"),
+            ("https://example.com/images/fedora.raw.xz", "PullRaw"),
+            ("http://mirror/cloud.qcow2", "PullRaw"),
+        ] {
+            assert_eq!(pull_method(url).unwrap(), method, "{url}");
+        }
+        for bad in [
+            "ftp://example.com/a.tar",
+            "https://example.com",
+            "https://example.com/images/",
+            "file:///srv/a.raw",
+        ] {
+            assert!(pull_method(bad).is_err(), "{bad} accepted");
+        }
+    }
+
+    #[test]
+    fn pull_local_name_strips_suffixes() {
+        for (url, local) in [
+            ("https://example.com/images/debian-12.tar.xz", "debian-12"),
+            ("https://example.com/rootfs.tgz?sig=1", "rootfs"),
+            ("https://example.com/images/fedora-40.raw.xz", "fedora-40"),
+            ("http://mirror/cloud.qcow2", "cloud"),
+        ] {
+            assert_eq!(pull_local_name(url).unwrap(), local, "{url}");
+        }
+        assert!(pull_local_name("http://mirror/.raw").is_err());
+        assert!(pull_local_name("http://mirror/a%20b.tar").is_err());
+    }
+
+    #[test]
+    fn nspawn_argv_rejects_bad_input() {
+        let image = ImageSpec::Machine("web".to_string());
+        let reject = |opts: ContainerOptions| assert!(nspawn_argv(&image, &opts, "web").is_err());
+        reject(ContainerOptions {
+            boot: false,
+            ..Default::default()
+        });
+        reject(ContainerOptions {
+            bind: vec![("relative".to_string(), "/x".to_string())],
+            ..Default::default()
+        });
+        reject(ContainerOptions {
+            extra_args: vec!["positional".to_string()],
+            ..Default::default()
+        });
+        assert!(
+            nspawn_argv(
+                &ImageSpec::Directory(PathBuf::from("rel/dir")),
+                &ContainerOptions::default(),
+                "web"
+            )
+            .is_err()
+        );
+        assert!(
+            nspawn_argv(&image, &ContainerOptions::default(), "db")
+                .unwrap_err()
+                .to_string()
+                .contains("must match image web")
+        );
+        assert!(
+            container_machine_name(
+                &image,
+                &ContainerOptions {
+                    name: Some("../etc".to_string()),
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{ActiveState, LoadState};
 
+use std::collections::BTreeMap;
+
 /// A single row returned by `org.freedesktop.systemd1.Manager.ListUnits*`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -77,6 +79,242 @@ impl UnitListEntry {
     }
 }
 
+/// systemd unit file enablement state (`UnitFileState`, `systemctl is-enabled`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnitFileState {
+    Enabled,
+    EnabledRuntime,
+    Linked,
+    LinkedRuntime,
+    Alias,
+    Masked,
+    MaskedRuntime,
+    Static,
+    Disabled,
+    Indirect,
+    Generated,
+    Transient,
+    Bad,
+    Unknown(String),
+}
+
+impl UnitFileState {
+    pub(crate) fn parse(s: &str) -> Self {
+        match s {
+            "enabled" => Self::Enabled,
+            "enabled-runtime" => Self::EnabledRuntime,
+            "linked" => Self::Linked,
+            "linked-runtime" => Self::LinkedRuntime,
+            "alias" => Self::Alias,
+            "masked" => Self::Masked,
+            "masked-runtime" => Self::MaskedRuntime,
+            "static" => Self::Static,
+            "disabled" => Self::Disabled,
+            "indirect" => Self::Indirect,
+            "generated" => Self::Generated,
+            "transient" => Self::Transient,
+            "bad" => Self::Bad,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+
+    /// Return the original systemd string representation (e.g. `"enabled-runtime"`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Enabled => "enabled",
+            Self::EnabledRuntime => "enabled-runtime",
+            Self::Linked => "linked",
+            Self::LinkedRuntime => "linked-runtime",
+            Self::Alias => "alias",
+            Self::Masked => "masked",
+            Self::MaskedRuntime => "masked-runtime",
+            Self::Static => "static",
+            Self::Disabled => "disabled",
+            Self::Indirect => "indirect",
+            Self::Generated => "generated",
+            Self::Transient => "transient",
+            Self::Bad => "bad",
+            Self::Unknown(s) => s.as_str(),
+        }
+    }
+
+    /// `true` for states `systemctl is-enabled` reports as enabled.
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled | Self::EnabledRuntime)
+    }
+}
+
+/// Where the systemd manager writes its own log messages (`LogTarget`, `systemd-analyze
+/// log-target`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LogTarget {
+    Console,
+    ConsolePrefixed,
+    Kmsg,
+    Journal,
+    JournalOrKmsg,
+    Syslog,
+    SyslogOrKmsg,
+    Auto,
+    Null,
+    Unknown(String),
+}
+
+impl LogTarget {
+    pub(crate) fn parse(s: &str) -> Self {
+        match s {
+            "console" => Self::Console,
+            "console-prefixed" => Self::ConsolePrefixed,
+            "kmsg" => Self::Kmsg,
+            "journal" => Self::Journal,
+            "journal-or-kmsg" => Self::JournalOrKmsg,
+            "syslog" => Self::Syslog,
+            "syslog-or-kmsg" => Self::SyslogOrKmsg,
+            "auto" => Self::Auto,
+            "null" => Self::Null,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+
+    /// Return the original systemd string representation (e.g. `"journal-or-kmsg"`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Console => "console",
+            Self::ConsolePrefixed => "console-prefixed",
+            Self::Kmsg => "kmsg",
+            Self::Journal => "journal",
+            Self::JournalOrKmsg => "journal-or-kmsg",
+            Self::Syslog => "syslog",
+            Self::SyslogOrKmsg => "syslog-or-kmsg",
+            Self::Auto => "auto",
+            Self::Null => "null",
+            Self::Unknown(s) => s.as_str(),
+        }
+    }
+}
+
+/// Vendor preset of a unit file (`UnitFilePreset`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum VendorPreset {
+    Enabled,
+    Disabled,
+    Ignore,
+    Unknown(String),
+}
+
+impl VendorPreset {
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "" => None,
+            "enabled" => Some(Self::Enabled),
+            "disabled" => Some(Self::Disabled),
+            "ignore" => Some(Self::Ignore),
+            other => Some(Self::Unknown(other.to_string())),
+        }
+    }
+
+    /// Return the original systemd string representation (e.g. `"enabled"`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Enabled => "enabled",
+            Self::Disabled => "disabled",
+            Self::Ignore => "ignore",
+            Self::Unknown(s) => s.as_str(),
+        }
+    }
+}
+
+/// Serialized as the systemd string (e.g. `"enabled-runtime"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for UnitFileState {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// Serialized as the systemd string (e.g. `"disabled"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for VendorPreset {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// One unit from `Manager::unit_inventory`: unit file, enablement and runtime state joined by
+/// unit name.
+///
+/// Units without a unit file (e.g. transient units, template instances) have `path` and
+/// `enablement` unset; unit files that are not loaded have no runtime state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitInventoryEntry {
+    pub name: String,
+    /// Unit file path (from `ListUnitFiles`).
+    pub path: Option<String>,
+    pub enablement: Option<UnitFileState>,
+    /// Only known for loaded units.
+    pub vendor_preset: Option<VendorPreset>,
+    pub load_state: Option<LoadState>,
+    pub active_state: Option<ActiveState>,
+    pub sub_state: Option<String>,
+    pub description: Option<String>,
+    /// D-Bus object path for loaded units.
+    pub unit_path: Option<String>,
+}
+
+impl UnitInventoryEntry {
+    fn named(name: String) -> Self {
+        Self {
+            name,
+            path: None,
+            enablement: None,
+            vendor_preset: None,
+            load_state: None,
+            active_state: None,
+            sub_state: None,
+            description: None,
+            unit_path: None,
+        }
+    }
+}
+
+/// Join `ListUnitFiles` rows and `ListUnits` entries by unit name (sorted by name).
+pub(crate) fn join_inventory(
+    files: Vec<crate::bus::UnitFileItem>,
+    units: Vec<UnitListEntry>,
+) -> Vec<UnitInventoryEntry> {
+    let mut by_name = BTreeMap::<String, UnitInventoryEntry>::new();
+
+    for (path, state) in files {
+        let Some(name) = path.rsplit('/').next().filter(|n| !n.is_empty()) else {
+            continue;
+        };
+        let entry = by_name
+            .entry(name.to_string())
+            .or_insert_with(|| UnitInventoryEntry::named(name.to_string()));
+        // `ListUnitFiles` reports each unit name once, with the file systemd resolved for it
+        // (e.g. `/etc` shadowing `/usr/lib`); shadowed copies are not listed.
+        entry.enablement = Some(UnitFileState::parse(&state));
+        entry.path = Some(path);
+    }
+
+    for unit in units {
+        let entry = by_name
+            .entry(unit.name.clone())
+            .or_insert_with(|| UnitInventoryEntry::named(unit.name.clone()));
+        entry.load_state = Some(unit.load_state);
+        entry.active_state = Some(unit.active_state);
+        entry.sub_state = unit.sub_state;
+        entry.description = unit.description;
+        entry.unit_path = Some(unit.unit_path);
+    }
+
+    by_name.into_values().collect()
+}
+
 /// A small snapshot of `org.freedesktop.systemd1.Manager` global information.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[non_exhaustive]
@@ -86,6 +324,100 @@ pub struct ManagerInfo {
     pub virtualization: Option<String>,
 }
 
+/// Which cgroup hierarchy layout the host runs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CgroupMode {
+    /// Pure cgroup v2 (`/sys/fs/cgroup` is the unified hierarchy).
+    Unified,
+    /// cgroup v1 controllers with the v2 hierarchy mounted at `/sys/fs/cgroup/unified`.
+    Hybrid,
+    /// cgroup v1 only.
+    Legacy,
+}
+
+/// Host inventory snapshot returned by `Manager::host_facts`.
+///
+/// Every field is best-effort: a value that cannot be read on this host is `None` rather than
+/// failing the whole snapshot.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct HostFacts {
+    /// `/etc/machine-id` (32 hex digits).
+    pub machine_id: Option<String>,
+    pub hostname: Option<String>,
+    /// Manager `Virtualization` (`None` on bare metal).
+    pub virtualization: Option<String>,
+    pub systemd_version: Option<String>,
+    /// Manager `Features`, e.g. `+PAM +AUDIT -SELINUX ...`.
+    pub systemd_features: Option<String>,
+    pub cgroup_mode: Option<CgroupMode>,
+    /// Wall-clock time the kernel booted.
+    pub boot_time: Option<std::time::SystemTime>,
+    /// IANA timezone name (e.g. `Europe/Berlin`).
+    pub timezone: Option<String>,
+    /// Manager `NFailedUnits`.
+    pub failed_units: Option<u32>,
+}
+
+/// Report returned by `Manager::dump`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ManagerDumpReport {
+    /// Number of bytes written to the caller-provided writer.
+    pub bytes_written: u64,
+    /// `true` if the dump was cut short due to `max_bytes`.
+    pub truncated: bool,
+}
+
+/// Filter for `Manager::list_units_stream`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitListQuery {
+    /// Shell-style globs on unit names (e.g. `"app-*.service"`), each fetched with its own
+    /// `ListUnitsByPatterns` call. Empty: every unit, fetched one unit type at a time.
+    pub patterns: Vec<String>,
+    /// Load, active or sub states to keep (e.g. `"failed"`); empty keeps all.
+    pub states: Vec<String>,
+}
+
+impl UnitListQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.states.push(state.into());
+        self
+    }
+}
+
+/// Units yielded incrementally by `Manager::list_units_stream`, a `Stream` of
+/// `Result<UnitListEntry>`.
+///
+/// A D-Bus error is yielded once and ends the stream.
+pub struct UnitListStream {
+    pub(crate) stream:
+        std::pin::Pin<Box<dyn futures_util::Stream<Item = crate::Result<UnitListEntry>> + Send>>,
+}
+
+impl std::fmt::Debug for UnitListStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("UnitListStream")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(HostFacts);
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -124,6 +456,81 @@ mod tests {
         assert_eq!(e.job_path, None);
     }
 
+    #[test]
+    fn inventory_joins_files_and_units_by_name() {
+        let files = vec![
+            (
+                "/etc/systemd/system/nginx.service".to_string(),
+                "enabled".to_string(),
+            ),
+            (
+                "/usr/lib/systemd/system/getty@.service".to_string(),
+                "enabled".to_string(),
+            ),
+            (
+                "/usr/lib/systemd/system/backup.timer".to_string(),
+                "disabled".to_string(),
+            ),
+        ];
+        let unit = |name: &str, active: &str| {
+            UnitListEntry::from_dbus((
+                name.to_string(),
+                format!("{name} desc"),
+                "loaded".to_string(),
+                active.to_string(),
+                "".to_string(),
+                "".to_string(),
+                path("/org/freedesktop/systemd1/unit/x"),
+                0u32,
+                "".to_string(),
+                path("/"),
+            ))
+        };
+
+        let inv = join_inventory(
+            files,
+            vec![
+                unit("nginx.service", "active"),
+                unit("getty@tty1.service", "active"),
+                unit("run-u42.service", "activating"),
+            ],
+        );
+        let names: Vec<&str> = inv.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "backup.timer",
+                "getty@.service",
+                "getty@tty1.service",
+                "nginx.service",
+                "run-u42.service"
+            ]
+        );
+
+        // Unit file and runtime state joined.
+        let nginx = &inv[3];
+        assert_eq!(
+            nginx.path.as_deref(),
+            Some("/etc/systemd/system/nginx.service")
+        );
+        assert_eq!(nginx.enablement, Some(UnitFileState::Enabled));
+        assert_eq!(nginx.active_state, Some(ActiveState::Active));
+        assert_eq!(nginx.description.as_deref(), Some("nginx.service desc"));
+
+        // Unit file that is not loaded: no runtime state.
+        assert_eq!(inv[0].enablement, Some(UnitFileState::Disabled));
+        assert_eq!(inv[0].active_state, None);
+        assert_eq!(inv[0].unit_path, None);
+        // Template instance and transient unit: runtime state only.
+        for loaded in [&inv[2], &inv[4]] {
+            assert_eq!(loaded.path, None);
+            assert_eq!(loaded.enablement, None);
+            assert!(loaded.active_state.is_some());
+        }
+        assert!(UnitFileState::parse("enabled-runtime").is_enabled());
+        assert_eq!(UnitFileState::parse("weird").as_str(), "weird");
+    }
+
     #[test]
     fn list_units_decodes_job_fields_when_present_and_normalizes_empty_strings() {
         let item = (
@@ -1,9 +1,55 @@
+/// `to_json()` plus a JSON `Display` (`{:#}` pretty-prints) for output types (feature=`serde`).
+#[cfg(feature = "serde")]
+macro_rules! impl_json_output {
+    ($($ty:ty),+ $(,)?) => {$(
+        impl $ty {
+            /// Serialize to compact JSON (feature=`serde`).
+            pub fn to_json(&self) -> crate::Result<String> {
+                serde_json::to_string(self).map_err(|e| crate::Error::ParseError {
+                    context: format!("serialize {}: {e}", stringify!($ty)),
+                    sample: String::new(),
+                })
+            }
+        }
+
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let json = if f.alternate() {
+                    serde_json::to_string_pretty(self)
+                } else {
+                    serde_json::to_string(self)
+                };
+                f.write_str(&json.map_err(|_| std::fmt::Error)?)
+            }
+        }
+    )+};
+}
+
+pub(crate) mod audit;
+pub(crate) mod bookmark;
+pub(crate) mod builder;
+pub(crate) mod calendar;
 #[cfg(feature = "config")]
 pub(crate) mod config;
+pub(crate) mod coredump;
+pub(crate) mod credential;
+pub(crate) mod dependency;
+pub(crate) mod forward;
+pub(crate) mod history;
 pub(crate) mod journal;
+pub(crate) mod lint;
+#[cfg(feature = "machines")]
+pub(crate) mod machine;
 pub(crate) mod manager;
+#[cfg(feature = "config")]
+pub(crate) mod offline_update;
+pub(crate) mod pressure;
 pub(crate) mod properties;
+pub(crate) mod reboot;
+pub(crate) mod signal;
+pub(crate) mod specifier;
 #[cfg(feature = "tasks")]
 pub(crate) mod task;
 pub(crate) mod unit;
 pub(crate) mod unit_file;
+pub(crate) mod values;
@@ -0,0 +1,390 @@
+use crate::types::unit_file::{ServiceType, ServiceUnitInstallReport, ServiceUnitSpec};
+use crate::types::values::SystemdDuration;
+use crate::{Error, Result, util};
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// An offline update run in the `system-update.target` boot mode (see
+/// `systemd.offline-updates(7)` and `Config::stage_offline_update`).
+///
+/// The update runs as `<name>.service`, pulled in by `system-update.target` on the next boot,
+/// and reboots the host when it finishes (successfully or not). Its result is recorded under
+/// `status_dir` for `Config::offline_update_status` after that reboot.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct OfflineUpdateSpec {
+    /// Unit name without suffix (`"os-update"` runs as `os-update.service`).
+    pub name: String,
+    pub description: Option<String>,
+    /// The updater (absolute path first); it runs with only `sysinit.target` up.
+    pub argv: Vec<String>,
+    /// Prepared update data; `marker` is pointed here.
+    pub update_dir: PathBuf,
+    /// The symlink that switches the next boot into `system-update.target` (default:
+    /// `/system-update`). Removed as the first step of the update, so a crashing updater does
+    /// not cause a boot loop.
+    pub marker: PathBuf,
+    /// Where the result of the run is recorded (default: `/var/lib/unitbus/offline-update`).
+    pub status_dir: PathBuf,
+    /// Upper bound for the updater (default: no limit, like distribution updaters).
+    pub timeout: SystemdDuration,
+}
+
+impl OfflineUpdateSpec {
+    pub fn new(name: impl Into<String>, argv: Vec<String>, update_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            argv,
+            update_dir: update_dir.into(),
+            marker: PathBuf::from("/system-update"),
+            status_dir: PathBuf::from("/var/lib/unitbus/offline-update"),
+            timeout: SystemdDuration::Infinity,
+        }
+    }
+
+    /// `<name>.service`.
+    pub fn unit(&self) -> Result<String> {
+        if self.name.contains('.') {
+            return Err(Error::invalid_input(
+                "offline update name must not include a unit suffix",
+            ));
+        }
+        util::canonicalize_unit_name(&self.name)
+    }
+
+    pub(crate) fn status_file(&self) -> PathBuf {
+        self.status_dir.join(format!("{}.status", self.name))
+    }
+
+    /// The service unit running the update.
+    pub(crate) fn service_spec(&self) -> Result<ServiceUnitSpec> {
+        let unit = self.unit()?;
+        if self.argv.is_empty() {
+            return Err(Error::invalid_input(
+                "offline update argv must not be empty",
+            ));
+        }
+        for (context, path) in [
+            ("update_dir", &self.update_dir),
+            ("marker", &self.marker),
+            ("status_dir", &self.status_dir),
+        ] {
+            validate_unit_path(context, path)?;
+        }
+
+        let marker = self.marker.to_string_lossy().into_owned();
+        let status = self.status_file().to_string_lossy().into_owned();
+        // `$$` leaves the variables to the shell rather than systemd's own expansion.
+        let record = format!("echo \"$$SERVICE_RESULT $$EXIT_CODE $$EXIT_STATUS\" > '{status}'");
+        let record_exec =
+            util::render_systemd_exec(&["/bin/sh".to_string(), "-c".to_string(), record])?;
+
+        Ok(ServiceUnitSpec {
+            unit,
+            description: Some(
+                self.description
+                    .clone()
+                    .unwrap_or_else(|| format!("Offline update {}", self.name)),
+            ),
+            after: vec![
+                "sysinit.target".to_string(),
+                "system-update-pre.target".to_string(),
+            ],
+            requires: vec!["sysinit.target".to_string()],
+            service_type: Some(ServiceType::Oneshot),
+            exec_start_pre: vec![vec!["/bin/rm".to_string(), "-f".to_string(), marker]],
+            exec_start: self.argv.clone(),
+            timeout_start_sec: Some(self.timeout),
+            wanted_by: vec!["system-update.target".to_string()],
+            extra_unit: vec![
+                "DefaultDependencies=no".to_string(),
+                "Before=system-update.target".to_string(),
+                "SuccessAction=reboot".to_string(),
+                "FailureAction=reboot".to_string(),
+            ],
+            extra_service: vec![format!("ExecStopPost={record_exec}")],
+            ..Default::default()
+        })
+    }
+}
+
+/// Paths end up in unit file lines and a shell command; keep them plain.
+fn validate_unit_path(context: &'static str, path: &Path) -> Result<()> {
+    let s = path.to_string_lossy();
+    if !path.is_absolute() {
+        return Err(Error::invalid_input(format!("{context} must be absolute")));
+    }
+    util::validate_no_control(context, &s)?;
+    if s.chars()
+        .any(|c| matches!(c, '\'' | '$' | '%' | '"' | '\\'))
+    {
+        return Err(Error::invalid_input(format!(
+            "{context} must not contain quotes, backslashes, '$' or '%'"
+        )));
+    }
+    Ok(())
+}
+
+/// Result of `Config::stage_offline_update`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct OfflineUpdateReport {
+    pub unit: String,
+    pub install: ServiceUnitInstallReport,
+    /// The `/system-update` symlink that was created.
+    pub marker: String,
+}
+
+/// Where an offline update stands (see `Config::offline_update_status`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "state", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum OfflineUpdateStatus {
+    /// Neither staged nor run.
+    NotStaged,
+    /// Staged; the next boot runs the update.
+    Pending,
+    /// The updater exited successfully.
+    Succeeded { finished_at: SystemTime },
+    /// The updater failed; the fields are the `$SERVICE_RESULT`, `$EXIT_CODE` and
+    /// `$EXIT_STATUS` systemd passed to `ExecStopPost=` (see its journal for details).
+    Failed {
+        result: String,
+        exit_code: String,
+        exit_status: String,
+        finished_at: SystemTime,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(OfflineUpdateReport);
+
+/// Decode a status file written by the update unit's `ExecStopPost=`.
+pub(crate) fn parse_status(content: &str, finished_at: SystemTime) -> OfflineUpdateStatus {
+    let mut fields = content.split_whitespace().map(str::to_string);
+    let result = fields.next().unwrap_or_default();
+    let exit_code = fields.next().unwrap_or_default();
+    let exit_status = fields.next().unwrap_or_default();
+    if result == "success" {
+        OfflineUpdateStatus::Succeeded { finished_at }
+    } else {
+        OfflineUpdateStatus::Failed {
+            result,
+            exit_code,
+            exit_status,
+            finished_at,
+        }
+    }
+}
+
+/// Status from the files on disk: a recorded result wins over a (re-)staged marker.
+pub(crate) fn read_status(spec: &OfflineUpdateSpec) -> Result<OfflineUpdateStatus> {
+    let path = spec.status_file();
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            let finished_at = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .map_err(|e| map_io("stat", &path, e))?;
+            return Ok(parse_status(&content, finished_at));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(map_io("read", &path, e)),
+    }
+    Ok(if marker_points_to(spec)? {
+        OfflineUpdateStatus::Pending
+    } else {
+        OfflineUpdateStatus::NotStaged
+    })
+}
+
+fn marker_points_to(spec: &OfflineUpdateSpec) -> Result<bool> {
+    match std::fs::read_link(&spec.marker) {
+        Ok(target) => Ok(target == spec.update_dir),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::InvalidInput => Ok(false),
+        Err(e) => Err(map_io("read", &spec.marker, e)),
+    }
+}
+
+/// Point the marker at `update_dir` (atomically) and clear a result of an earlier run.
+///
+/// Fails if the marker already exists for a different update.
+pub(crate) fn stage_marker(spec: &OfflineUpdateSpec) -> Result<()> {
+    if !spec.update_dir.is_dir() {
+        return Err(Error::invalid_input(format!(
+            "update_dir {} is not a directory",
+            spec.update_dir.to_string_lossy()
+        )));
+    }
+    if spec.marker.symlink_metadata().is_ok() && !marker_points_to(spec)? {
+        return Err(Error::invalid_input(format!(
+            "{} already exists for another update",
+            spec.marker.to_string_lossy()
+        )));
+    }
+
+    std::fs::create_dir_all(&spec.status_dir).map_err(|e| map_io("create", &spec.status_dir, e))?;
+    remove_if_exists(&spec.status_file())?;
+
+    let mut tmp = spec.marker.clone().into_os_string();
+    tmp.push(".unitbus-tmp");
+    let tmp = PathBuf::from(tmp);
+    remove_if_exists(&tmp)?;
+    symlink(&spec.update_dir, &tmp).map_err(|e| map_io("create", &tmp, e))?;
+    std::fs::rename(&tmp, &spec.marker).map_err(|e| map_io("rename", &spec.marker, e))
+}
+
+/// Remove the marker (only if it is ours) and the recorded result.
+pub(crate) fn clear_marker(spec: &OfflineUpdateSpec) -> Result<()> {
+    if marker_points_to(spec)? {
+        remove_if_exists(&spec.marker)?;
+    }
+    remove_if_exists(&spec.status_file())
+}
+
+/// Whether the marker is currently set for `spec`.
+pub(crate) fn is_staged(spec: &OfflineUpdateSpec) -> Result<bool> {
+    marker_points_to(spec)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(map_io("remove", path, e)),
+    }
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "symlinks require unix",
+    ))
+}
+
+fn map_io(action: &str, path: &Path, e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        return Error::PermissionDenied {
+            action: "offline_update",
+            detail: format!("{action} {}: {e}", path.to_string_lossy()),
+        };
+    }
+    Error::IoError {
+        context: format!("{action} {}: {e}", path.to_string_lossy()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn spec(root: &Path) -> OfflineUpdateSpec {
+        let update_dir = root.join("payload");
+        std::fs::create_dir_all(&update_dir).unwrap();
+        let mut spec = OfflineUpdateSpec::new(
+            "os-update",
+            vec!["/usr/libexec/os-update".to_string(), "--apply".to_string()],
+            update_dir,
+        );
+        spec.marker = root.join("system-update");
+        spec.status_dir = root.join("status");
+        spec
+    }
+
+    #[test]
+    fn service_spec_renders_system_update_unit() {
+        let mut spec = OfflineUpdateSpec::new(
+            "os-update",
+            vec!["/usr/libexec/os-update".to_string()],
+            "/var/lib/os-update",
+        );
+        let mut unit_spec = spec.service_spec().unwrap();
+        unit_spec.lint = crate::LintStrictness::All;
+        let rendered = unit_spec.render().unwrap();
+        for line in [
+            "Description=Offline update os-update\n",
+            "After=sysinit.target system-update-pre.target\n",
+            "Requires=sysinit.target\n",
+            "DefaultDependencies=no\n",
+            "Before=system-update.target\n",
+            "SuccessAction=reboot\n",
+            "FailureAction=reboot\n",
+            "Type=oneshot\n",
+            "ExecStartPre=/bin/rm -f /system-update\n",
+            "ExecStart=/usr/libexec/os-update\n",
+            "TimeoutStartSec=infinity\n",
+            "WantedBy=system-update.target\n",
+        ] {
+            assert!(rendered.contains(line), "missing {line:?} in:\n{rendered}");
+        }
+        assert!(rendered.contains(
+            "ExecStopPost=/bin/sh -c \"echo \\\"$$SERVICE_RESULT $$EXIT_CODE $$EXIT_STATUS\\\" > \
+             '/var/lib/unitbus/offline-update/os-update.status'\"\n"
+        ));
+
+        spec.name = "os-update.service".to_string();
+        assert!(spec.service_spec().is_err());
+        spec.name = "os-update".to_string();
+        spec.status_dir = PathBuf::from("/var/lib/it's");
+        assert!(spec.service_spec().is_err());
+        spec.status_dir = PathBuf::from("relative");
+        assert!(spec.service_spec().is_err());
+    }
+
+    #[test]
+    fn marker_and_status_round_trip() {
+        let dir = std::env::temp_dir().join(format!("unitbus-offline-{}", std::process::id()));
+        let spec = spec(&dir);
+        assert_eq!(read_status(&spec).unwrap(), OfflineUpdateStatus::NotStaged);
+
+        stage_marker(&spec).unwrap();
+        assert!(is_staged(&spec).unwrap());
+        assert_eq!(read_status(&spec).unwrap(), OfflineUpdateStatus::Pending);
+        // Re-staging the same update is fine; another one is refused.
+        stage_marker(&spec).unwrap();
+        let mut other = spec.clone();
+        other.update_dir = dir.clone();
+        assert!(stage_marker(&other).is_err());
+
+        std::fs::write(spec.status_file(), "exit-code exited 3\n").unwrap();
+        match read_status(&spec).unwrap() {
+            OfflineUpdateStatus::Failed {
+                result,
+                exit_code,
+                exit_status,
+                ..
+            } => {
+                assert_eq!(
+                    (result.as_str(), exit_code.as_str(), exit_status.as_str()),
+                    ("exit-code", "exited", "3")
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+        std::fs::write(spec.status_file(), "success exited 0\n").unwrap();
+        assert!(matches!(
+            read_status(&spec).unwrap(),
+            OfflineUpdateStatus::Succeeded { .. }
+        ));
+
+        clear_marker(&spec).unwrap();
+        assert!(!is_staged(&spec).unwrap());
+        assert_eq!(read_status(&spec).unwrap(), OfflineUpdateStatus::NotStaged);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
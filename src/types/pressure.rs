@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+/// Resource covered by a Linux PSI (pressure stall information) file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PressureResource {
+    /// `cpu.pressure`
+    Cpu,
+    /// `memory.pressure`
+    Memory,
+    /// `io.pressure`
+    Io,
+}
+
+impl PressureResource {
+    pub const ALL: [PressureResource; 3] = [Self::Cpu, Self::Memory, Self::Io];
+
+    /// File name in the cgroup directory, e.g. `memory.pressure`.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu.pressure",
+            Self::Memory => "memory.pressure",
+            Self::Io => "io.pressure",
+        }
+    }
+}
+
+/// PSI line: `some` (at least one task stalled) or `full` (all non-idle tasks stalled).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PressureKind {
+    Some,
+    Full,
+}
+
+/// One PSI line: share of wall time stalled, in percent, over 10s/60s/300s windows.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct PressureLine {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    /// Total stall time since the cgroup was created.
+    pub total: Duration,
+}
+
+/// Parsed PSI file.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Pressure {
+    pub some: PressureLine,
+    /// Missing for `cpu.pressure` on kernels older than 5.13.
+    pub full: Option<PressureLine>,
+}
+
+impl Pressure {
+    pub fn line(&self, kind: PressureKind) -> Option<&PressureLine> {
+        match kind {
+            PressureKind::Some => Some(&self.some),
+            PressureKind::Full => self.full.as_ref(),
+        }
+    }
+
+    /// Parse the contents of a `*.pressure` file; `None` if the `some` line is missing or
+    /// malformed.
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let mut some = None;
+        let mut full = None;
+        for line in text.lines() {
+            let mut parts = line.split_whitespace();
+            let slot = match parts.next() {
+                Some("some") => &mut some,
+                Some("full") => &mut full,
+                _ => continue,
+            };
+            *slot = Some(parse_line(parts)?);
+        }
+        Some(Self { some: some?, full })
+    }
+}
+
+fn parse_line<'a>(parts: impl Iterator<Item = &'a str>) -> Option<PressureLine> {
+    let mut line = PressureLine::default();
+    for part in parts {
+        let (key, value) = part.split_once('=')?;
+        match key {
+            "avg10" => line.avg10 = value.parse().ok()?,
+            "avg60" => line.avg60 = value.parse().ok()?,
+            "avg300" => line.avg300 = value.parse().ok()?,
+            "total" => line.total = Duration::from_micros(value.parse().ok()?),
+            _ => {}
+        }
+    }
+    Some(line)
+}
+
+/// PSI snapshot of a unit's cgroup, returned by `Units::pressure`.
+///
+/// A resource is `None` when its pressure file is absent (controller not enabled for the cgroup,
+/// or PSI disabled with `psi=0`).
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct UnitPressure {
+    pub unit: String,
+    /// `ControlGroup` of the unit, relative to the cgroupfs root.
+    pub control_group: String,
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+}
+
+impl UnitPressure {
+    pub fn get(&self, resource: PressureResource) -> Option<&Pressure> {
+        match resource {
+            PressureResource::Cpu => self.cpu.as_ref(),
+            PressureResource::Memory => self.memory.as_ref(),
+            PressureResource::Io => self.io.as_ref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn pressure_parses_some_and_full_lines() {
+        let p = Pressure::parse(
+            "some avg10=1.50 avg60=0.25 avg300=0.00 total=123456\n\
+             full avg10=0.75 avg60=0.10 avg300=0.00 total=42\n",
+        )
+        .expect("parse");
+        assert_eq!(p.some.avg10, 1.5);
+        assert_eq!(p.some.avg60, 0.25);
+        assert_eq!(p.some.total, Duration::from_micros(123_456));
+        let full = p.line(PressureKind::Full).expect("full");
+        assert_eq!(full.avg10, 0.75);
+        assert_eq!(full.total, Duration::from_micros(42));
+
+        let cpu = Pressure::parse("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").unwrap();
+        assert!(cpu.full.is_none());
+
+        assert!(Pressure::parse("").is_none());
+        assert!(Pressure::parse("some avg10=x avg60=0 avg300=0 total=0\n").is_none());
+    }
+}
@@ -12,10 +12,18 @@ pub struct Properties {
 }
 
 impl Properties {
+    /// Cap for values rendered by `get_display` and `diff`.
+    pub const MAX_DISPLAY_BYTES: usize = 4096;
+
     pub(crate) fn from_dbus(values: HashMap<String, OwnedValue>) -> Self {
         Self { values }
     }
 
+    /// Add the properties of `other`, replacing values for keys present in both.
+    pub(crate) fn merge(&mut self, other: Properties) {
+        self.values.extend(other.values);
+    }
+
     /// Returns true if the property exists.
     pub fn contains(&self, key: &str) -> bool {
         self.values.contains_key(key)
@@ -76,6 +84,111 @@ impl Properties {
     pub fn get_i64(&self, key: &str) -> Option<i64> {
         self.values.get(key).and_then(|v| i64::try_from(v).ok())
     }
+
+    /// Get a string array property (`as`, e.g. `DropInPaths`).
+    pub fn get_string_list(&self, key: &str) -> Option<Vec<String>> {
+        let v = self.values.get(key)?.try_clone().ok()?;
+        Vec::<String>::try_from(v).ok()
+    }
+
+    /// Get a byte array property (`ay`, e.g. `InvocationID`).
+    pub fn get_bytes(&self, key: &str) -> Option<Vec<u8>> {
+        let v = self.values.get(key)?.try_clone().ok()?;
+        Vec::<u8>::try_from(v).ok()
+    }
+
+    /// Render any property as text (GVariant notation, e.g. `['A=1', 'B=2']`), truncated to
+    /// `Properties::MAX_DISPLAY_BYTES` with control characters escaped.
+    pub fn get_display(&self, key: &str) -> Option<String> {
+        self.values.get(key).map(display_value)
+    }
+
+    /// Whether `key` is a runtime counter or timestamp that changes while the unit runs:
+    /// resource usage (`MemoryCurrent`, `CPUUsageNSec`, `TasksCurrent`, IO and IP accounting),
+    /// activity counters (`NRestarts`, `NAccepted`, ...) and `*Timestamp*` / timer elapse times.
+    pub fn is_volatile(key: &str) -> bool {
+        const COUNTERS: &[&str] = &[
+            "CPUUsageNSec",
+            "TasksCurrent",
+            "MemoryAvailable",
+            "IOReadBytes",
+            "IOReadOperations",
+            "IOWriteBytes",
+            "IOWriteOperations",
+            "IPIngressBytes",
+            "IPIngressPackets",
+            "IPEgressBytes",
+            "IPEgressPackets",
+            "NRestarts",
+            "NAccepted",
+            "NConnections",
+            "NRefused",
+            "NextElapseUSecRealtime",
+            "NextElapseUSecMonotonic",
+            "LastTriggerUSec",
+            "LastTriggerUSecMonotonic",
+        ];
+        COUNTERS.contains(&key)
+            || key.contains("Timestamp")
+            || (key.starts_with("Memory") && (key.ends_with("Current") || key.ends_with("Peak")))
+    }
+
+    /// Drop every `is_volatile` property, leaving configuration and state worth diffing.
+    pub fn without_volatile(mut self) -> Self {
+        self.values.retain(|key, _| !Self::is_volatile(key));
+        self
+    }
+
+    /// Keys whose values differ between `self` (old) and `other` (new), sorted by key.
+    ///
+    /// Keys present on only one side are reported with `None` on the other.
+    pub fn diff(&self, other: &Properties) -> Vec<PropertyChange> {
+        let mut keys: Vec<&String> = self.values.keys().chain(other.values.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let (old, new) = (self.values.get(key), other.values.get(key));
+                if old == new {
+                    return None;
+                }
+                Some(PropertyChange {
+                    key: key.clone(),
+                    old: old.map(display_value),
+                    new: new.map(display_value),
+                })
+            })
+            .collect()
+    }
+}
+
+/// One changed property from `Properties::diff`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PropertyChange {
+    pub key: String,
+    /// Old value as text; `None` if the key was absent.
+    pub old: Option<String>,
+    /// New value as text; `None` if the key is gone.
+    pub new: Option<String>,
+}
+
+fn display_value(v: &OwnedValue) -> String {
+    let text: String = v
+        .to_string()
+        .chars()
+        .flat_map(|c| {
+            let escaped: Vec<char> = if c.is_control() {
+                c.escape_default().collect()
+            } else {
+                vec![c]
+            };
+            escaped
+        })
+        .collect();
+    crate::util::truncate_string_bytes(&text, Properties::MAX_DISPLAY_BYTES).0
 }
 
 #[cfg(test)]
@@ -123,6 +236,90 @@ mod tests {
         assert_eq!(p.get_bool("B"), Some(true));
     }
 
+    #[test]
+    fn string_list_getter_decodes_string_arrays() {
+        let mut m = HashMap::new();
+        m.insert(
+            "DropInPaths".to_string(),
+            OwnedValue::try_from(Value::from(vec!["/etc/a.conf", "/etc/b.conf"]))
+                .expect("owned list"),
+        );
+        m.insert("Id".to_string(), owned_str("x.service"));
+
+        let p = Properties::from_dbus(m);
+        assert_eq!(
+            p.get_string_list("DropInPaths"),
+            Some(vec!["/etc/a.conf".to_string(), "/etc/b.conf".to_string()])
+        );
+        assert_eq!(p.get_string_list("Id"), None);
+    }
+
+    #[test]
+    fn bytes_getter_decodes_byte_arrays() {
+        let mut m = HashMap::new();
+        m.insert(
+            "InvocationID".to_string(),
+            OwnedValue::try_from(Value::from(vec![0xabu8, 0x01])).expect("owned bytes"),
+        );
+
+        let p = Properties::from_dbus(m);
+        assert_eq!(p.get_bytes("InvocationID"), Some(vec![0xab, 0x01]));
+        assert_eq!(p.get_bytes("Missing"), None);
+    }
+
+    #[test]
+    fn diff_reports_changed_added_and_removed_keys() {
+        let mut old = HashMap::new();
+        old.insert("MemoryMax".to_string(), OwnedValue::from(1024u64));
+        old.insert("FragmentPath".to_string(), owned_str("/etc/a.service"));
+        old.insert("Gone".to_string(), OwnedValue::from(true));
+        let mut new = HashMap::new();
+        new.insert("MemoryMax".to_string(), OwnedValue::from(2048u64));
+        new.insert("FragmentPath".to_string(), owned_str("/etc/a.service"));
+        new.insert(
+            "Environment".to_string(),
+            OwnedValue::try_from(Value::from(vec!["A=1\n"])).expect("owned list"),
+        );
+
+        let (old, new) = (Properties::from_dbus(old), Properties::from_dbus(new));
+        let diff = old.diff(&new);
+        let keys: Vec<&str> = diff.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["Environment", "Gone", "MemoryMax"]);
+
+        assert_eq!(diff[0].old, None);
+        let env = diff[0].new.as_deref().unwrap();
+        assert!(env.contains("A=1") && !env.contains('\n'));
+        assert_eq!(diff[1].new, None);
+        assert_eq!(diff[2].old.as_deref(), Some("uint64 1024"));
+        assert_eq!(diff[2].new.as_deref(), Some("uint64 2048"));
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn without_volatile_keeps_configuration_only() {
+        let mut values = HashMap::new();
+        for key in [
+            "MemoryCurrent",
+            "MemoryPeak",
+            "MemorySwapCurrent",
+            "CPUUsageNSec",
+            "TasksCurrent",
+            "ActiveEnterTimestamp",
+            "ExecMainStartTimestampMonotonic",
+            "NRestarts",
+        ] {
+            values.insert(key.to_string(), OwnedValue::from(1u64));
+        }
+        values.insert("MemoryMax".to_string(), OwnedValue::from(1024u64));
+        values.insert("TasksMax".to_string(), OwnedValue::from(64u64));
+        values.insert("FragmentPath".to_string(), owned_str("/etc/a.service"));
+
+        let p = Properties::from_dbus(values).without_volatile();
+        let mut keys: Vec<&str> = p.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["FragmentPath", "MemoryMax", "TasksMax"]);
+    }
+
     #[test]
     fn getters_return_none_on_type_mismatch() {
         let mut m = HashMap::new();
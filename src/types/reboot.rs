@@ -0,0 +1,291 @@
+use crate::{Error, Result};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Decides whether a newer kernel than the running one is installed (see
+/// `Manager::reboot_required`).
+///
+/// Implement this for distribution-specific checks (boot loader entries, package database);
+/// the default is `ModulesDirKernelCheck`.
+pub trait KernelCheck: Send + Sync {
+    /// The installed kernel that a reboot would switch to, or `None` if `running` (as in
+    /// `uname -r`) is current.
+    fn installed_kernel(&self, running: &str) -> Result<Option<String>>;
+}
+
+impl fmt::Debug for dyn KernelCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KernelCheck")
+    }
+}
+
+/// `KernelCheck` comparing the running kernel with the module directories under
+/// `/lib/modules`.
+///
+/// Reports the newest installed version (by version-aware comparison) when it sorts strictly
+/// after the running one. Directories that do not look like kernel versions (`extramodules`,
+/// `build`, ...) are ignored, and a running kernel without a modules directory (e.g. a custom
+/// build) is not reported as outdated.
+#[derive(Clone, Debug)]
+pub struct ModulesDirKernelCheck {
+    dir: PathBuf,
+}
+
+impl Default for ModulesDirKernelCheck {
+    fn default() -> Self {
+        Self::new("/lib/modules")
+    }
+}
+
+impl ModulesDirKernelCheck {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl KernelCheck for ModulesDirKernelCheck {
+    fn installed_kernel(&self, running: &str) -> Result<Option<String>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(Error::IoError {
+                    context: format!("read {}: {e}", self.dir.to_string_lossy()),
+                });
+            }
+        };
+        let installed: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect();
+        Ok(newer_kernel(running, &installed))
+    }
+}
+
+/// The newest kernel version in `installed` if it sorts strictly after `running`.
+fn newer_kernel(running: &str, installed: &[String]) -> Option<String> {
+    let newest = installed
+        .iter()
+        .filter(|k| is_kernel_version(k))
+        .max_by(|a, b| compare_versions(a, b))?;
+    (compare_versions(newest, running) == Ordering::Greater).then(|| newest.clone())
+}
+
+/// `<major>.<minor>[...]`, as in `uname -r` (`6.1.0-18-amd64`, `5.14.0-427.el9.x86_64`).
+fn is_kernel_version(name: &str) -> bool {
+    let mut parts = name.splitn(3, '.');
+    let major = parts.next().unwrap_or_default();
+    let minor = parts
+        .next()
+        .map(|m| {
+            m.split(|c: char| !c.is_ascii_digit())
+                .next()
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    !major.is_empty() && major.bytes().all(|b| b.is_ascii_digit()) && !minor.is_empty()
+}
+
+/// Compare version strings chunk by chunk, numerically for digit runs (`6.10` > `6.9`).
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn chunks(s: &str) -> Vec<&str> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        let bytes = s.as_bytes();
+        for i in 1..=bytes.len() {
+            if i == bytes.len() || bytes[i].is_ascii_digit() != bytes[start].is_ascii_digit() {
+                out.push(&s[start..i]);
+                start = i;
+            }
+        }
+        out
+    }
+
+    let (a, b) = (chunks(a), chunks(b));
+    for (x, y) in a.iter().zip(&b) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Options for `Manager::reboot_required`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RebootCheckOptions {
+    /// Files whose presence means a reboot is pending (default: `/run/reboot-required`, as
+    /// written by Debian/Ubuntu packages). A sibling `<file>.pkgs` lists the packages.
+    pub marker_files: Vec<PathBuf>,
+    /// Also collect loaded units with `NeedDaemonReload` set (default: `true`).
+    pub check_units: bool,
+    /// Kernel check (default: `ModulesDirKernelCheck`; `None` skips it).
+    pub kernel_check: Option<Arc<dyn KernelCheck>>,
+}
+
+impl Default for RebootCheckOptions {
+    fn default() -> Self {
+        Self {
+            marker_files: vec![PathBuf::from("/run/reboot-required")],
+            check_units: true,
+            kernel_check: Some(Arc::new(ModulesDirKernelCheck::default())),
+        }
+    }
+}
+
+/// Why `Manager::reboot_required` asks for a reboot.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum RebootReason {
+    /// A marker file exists.
+    MarkerFile {
+        path: String,
+        /// Packages listed in `<path>.pkgs` (empty if absent).
+        packages: Vec<String>,
+    },
+    /// A newer kernel is installed than the one running.
+    KernelUpdated { running: String, installed: String },
+}
+
+/// Result of `Manager::reboot_required`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RebootRequiredReport {
+    /// `true` if any reason was found.
+    pub reboot_required: bool,
+    pub reasons: Vec<RebootReason>,
+    /// Loaded units whose files changed on disk since the last `daemon_reload`. A reload (not a
+    /// reboot) picks these up, so they do not set `reboot_required`.
+    pub units_needing_daemon_reload: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(RebootRequiredReport);
+
+/// `RebootReason`s from the marker files and the kernel check.
+pub(crate) fn host_reboot_reasons(opts: &RebootCheckOptions) -> Result<Vec<RebootReason>> {
+    let mut reasons = Vec::new();
+    for path in &opts.marker_files {
+        if !path.exists() {
+            continue;
+        }
+        let mut pkgs = path.clone().into_os_string();
+        pkgs.push(".pkgs");
+        let packages = std::fs::read_to_string(PathBuf::from(pkgs))
+            .map(|s| {
+                let mut packages: Vec<String> = s
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                packages.sort();
+                packages.dedup();
+                packages
+            })
+            .unwrap_or_default();
+        reasons.push(RebootReason::MarkerFile {
+            path: path.to_string_lossy().into_owned(),
+            packages,
+        });
+    }
+
+    if let Some(check) = &opts.kernel_check {
+        let running =
+            std::fs::read_to_string("/proc/sys/kernel/osrelease").map_err(|e| Error::IoError {
+                context: format!("read /proc/sys/kernel/osrelease: {e}"),
+            })?;
+        let running = running.trim();
+        if let Some(installed) = check.installed_kernel(running)? {
+            reasons.push(RebootReason::KernelUpdated {
+                running: running.to_string(),
+                installed,
+            });
+        }
+    }
+    Ok(reasons)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn kernels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn newer_kernel_uses_version_order() {
+        assert_eq!(
+            compare_versions("6.10.0-1-amd64", "6.9.12-3-amd64"),
+            Ordering::Greater
+        );
+        assert_eq!(compare_versions("6.1.0-18", "6.1.0-18"), Ordering::Equal);
+
+        let installed = kernels(&["6.9.12-3-amd64", "6.10.0-1-amd64"]);
+        assert_eq!(
+            newer_kernel("6.9.12-3-amd64", &installed).as_deref(),
+            Some("6.10.0-1-amd64")
+        );
+        assert_eq!(newer_kernel("6.10.0-1-amd64", &installed), None);
+        // Older installed kernels and a missing running modules dir are not an update.
+        assert_eq!(
+            newer_kernel("6.8.0-1-amd64", &kernels(&["6.7.0-1-amd64"])),
+            None
+        );
+        assert_eq!(newer_kernel("6.8.0", &[]), None);
+        // Non-kernel directories are ignored even if they sort last.
+        assert_eq!(
+            newer_kernel(
+                "6.1.0-18-amd64",
+                &kernels(&["6.1.0-18-amd64", "extramodules", "weak-updates", "6.x"])
+            ),
+            None
+        );
+        assert!(is_kernel_version("5.14.0-427.el9.x86_64"));
+        assert!(!is_kernel_version("build"));
+    }
+
+    #[test]
+    fn marker_file_reports_packages() {
+        let dir = std::env::temp_dir().join(format!("unitbus-reboot-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = dir.join("reboot-required");
+        std::fs::write(&marker, "*** System restart required ***\n").unwrap();
+        std::fs::write(
+            dir.join("reboot-required.pkgs"),
+            "libc6\nlinux-base\nlibc6\n",
+        )
+        .unwrap();
+
+        let opts = RebootCheckOptions {
+            marker_files: vec![marker.clone(), dir.join("missing")],
+            check_units: false,
+            kernel_check: None,
+        };
+        let reasons = host_reboot_reasons(&opts).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            reasons,
+            vec![RebootReason::MarkerFile {
+                path: marker.to_string_lossy().into_owned(),
+                packages: kernels(&["libc6", "linux-base"]),
+            }]
+        );
+    }
+}
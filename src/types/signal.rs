@@ -0,0 +1,240 @@
+use crate::{Error, Result};
+
+use std::fmt;
+
+/// A standard POSIX signal with its Linux number (`signal(7)`; x86, ARM and most other
+/// architectures).
+///
+/// Parses from `"SIGTERM"`, `"TERM"` (any case) or `"15"`; displays as `"SIGTERM"`.
+/// Real-time signals (`SIGRTMIN+n`) are not covered: `from_number` returns `None` for them.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Signal {
+    Hup,
+    Int,
+    Quit,
+    Ill,
+    Trap,
+    Abrt,
+    Bus,
+    Fpe,
+    Kill,
+    Usr1,
+    Segv,
+    Usr2,
+    Pipe,
+    Alrm,
+    Term,
+    Stkflt,
+    Chld,
+    Cont,
+    Stop,
+    Tstp,
+    Ttin,
+    Ttou,
+    Urg,
+    Xcpu,
+    Xfsz,
+    Vtalrm,
+    Prof,
+    Winch,
+    Io,
+    Pwr,
+    Sys,
+}
+
+/// `(signal, number, name)` in numeric order.
+const SIGNALS: [(Signal, i32, &str); 31] = [
+    (Signal::Hup, 1, "SIGHUP"),
+    (Signal::Int, 2, "SIGINT"),
+    (Signal::Quit, 3, "SIGQUIT"),
+    (Signal::Ill, 4, "SIGILL"),
+    (Signal::Trap, 5, "SIGTRAP"),
+    (Signal::Abrt, 6, "SIGABRT"),
+    (Signal::Bus, 7, "SIGBUS"),
+    (Signal::Fpe, 8, "SIGFPE"),
+    (Signal::Kill, 9, "SIGKILL"),
+    (Signal::Usr1, 10, "SIGUSR1"),
+    (Signal::Segv, 11, "SIGSEGV"),
+    (Signal::Usr2, 12, "SIGUSR2"),
+    (Signal::Pipe, 13, "SIGPIPE"),
+    (Signal::Alrm, 14, "SIGALRM"),
+    (Signal::Term, 15, "SIGTERM"),
+    (Signal::Stkflt, 16, "SIGSTKFLT"),
+    (Signal::Chld, 17, "SIGCHLD"),
+    (Signal::Cont, 18, "SIGCONT"),
+    (Signal::Stop, 19, "SIGSTOP"),
+    (Signal::Tstp, 20, "SIGTSTP"),
+    (Signal::Ttin, 21, "SIGTTIN"),
+    (Signal::Ttou, 22, "SIGTTOU"),
+    (Signal::Urg, 23, "SIGURG"),
+    (Signal::Xcpu, 24, "SIGXCPU"),
+    (Signal::Xfsz, 25, "SIGXFSZ"),
+    (Signal::Vtalrm, 26, "SIGVTALRM"),
+    (Signal::Prof, 27, "SIGPROF"),
+    (Signal::Winch, 28, "SIGWINCH"),
+    (Signal::Io, 29, "SIGIO"),
+    (Signal::Pwr, 30, "SIGPWR"),
+    (Signal::Sys, 31, "SIGSYS"),
+];
+
+impl Signal {
+    fn entry(self) -> (Signal, i32, &'static str) {
+        // Variants are declared in numeric order, so the discriminant indexes the table.
+        SIGNALS[self as usize]
+    }
+
+    /// The Linux signal number (e.g. `15` for `SIGTERM`).
+    pub fn number(self) -> i32 {
+        self.entry().1
+    }
+
+    /// The conventional name (e.g. `"SIGTERM"`).
+    pub fn as_str(self) -> &'static str {
+        self.entry().2
+    }
+
+    /// Map a Linux signal number back to a `Signal` (e.g. `TaskResult.signal`).
+    pub fn from_number(number: i32) -> Option<Signal> {
+        let idx = usize::try_from(number.checked_sub(1)?).ok()?;
+        SIGNALS.get(idx).map(|(signal, _, _)| *signal)
+    }
+
+    /// Whether the default action of this signal dumps core (`signal(7)`).
+    pub fn dumps_core(self) -> bool {
+        matches!(
+            self,
+            Signal::Quit
+                | Signal::Ill
+                | Signal::Trap
+                | Signal::Abrt
+                | Signal::Bus
+                | Signal::Fpe
+                | Signal::Segv
+                | Signal::Xcpu
+                | Signal::Xfsz
+                | Signal::Sys
+        )
+    }
+}
+
+impl std::str::FromStr for Signal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let input = s.trim();
+        let invalid = || Error::invalid_input(format!("invalid signal: {s:?}"));
+
+        if let Ok(number) = input.parse::<i32>() {
+            return Signal::from_number(number).ok_or_else(invalid);
+        }
+        let upper = input.to_ascii_uppercase();
+        let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+        SIGNALS
+            .iter()
+            .find(|(_, _, n)| n[3..] == *name)
+            .map(|(signal, _, _)| *signal)
+            .ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Which processes of a unit `Units::kill` signals (`systemctl kill --kill-whom=`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum KillWho {
+    /// The main process (services only).
+    Main,
+    /// The process currently running an `ExecStartPre=`/`ExecReload=`/... command (services,
+    /// sockets, mounts and swaps).
+    Control,
+    /// Every process in the unit's control group (default).
+    #[default]
+    All,
+}
+
+impl KillWho {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KillWho::Main => "main",
+            KillWho::Control => "control",
+            KillWho::All => "all",
+        }
+    }
+
+    /// Reject targets that `unit` (canonical name) cannot have.
+    pub(crate) fn validate_for(self, unit: &str) -> Result<()> {
+        let suffix = unit.rsplit_once('.').map(|(_, s)| s).unwrap_or("");
+        let supported = match self {
+            KillWho::Main => suffix == "service",
+            KillWho::Control => matches!(suffix, "service" | "socket" | "mount" | "swap"),
+            KillWho::All => true,
+        };
+        if supported {
+            Ok(())
+        } else {
+            Err(Error::invalid_input(format!(
+                "{unit} has no {} process to signal",
+                self.as_str()
+            )))
+        }
+    }
+}
+
+/// Serialized as the name (e.g. `"SIGTERM"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signal {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn kill_who_matches_unit_type() {
+        assert_eq!(KillWho::default().as_str(), "all");
+        assert!(KillWho::Main.validate_for("nginx.service").is_ok());
+        assert!(KillWho::Main.validate_for("nginx.socket").is_err());
+        assert!(KillWho::Control.validate_for("data.mount").is_ok());
+        assert!(KillWho::Control.validate_for("session-1.scope").is_err());
+        assert!(KillWho::All.validate_for("session-1.scope").is_ok());
+    }
+
+    #[test]
+    fn numbers_names_and_parsing_round_trip() {
+        for (idx, (signal, number, name)) in SIGNALS.iter().enumerate() {
+            assert_eq!(*number as usize, idx + 1);
+            assert_eq!(signal.number(), *number);
+            assert_eq!(signal.as_str(), *name);
+            assert_eq!(Signal::from_number(*number), Some(*signal));
+            assert_eq!(name.parse::<Signal>().unwrap(), *signal);
+        }
+
+        assert_eq!(Signal::Kill.number(), 9);
+        assert_eq!(Signal::Term.to_string(), "SIGTERM");
+        assert_eq!("term".parse::<Signal>().unwrap(), Signal::Term);
+        assert_eq!(" 9 ".parse::<Signal>().unwrap(), Signal::Kill);
+        assert_eq!("SigHup".parse::<Signal>().unwrap(), Signal::Hup);
+
+        assert_eq!(Signal::from_number(0), None);
+        assert_eq!(Signal::from_number(34), None);
+        assert!("SIGFOO".parse::<Signal>().is_err());
+        assert!("SIG".parse::<Signal>().is_err());
+        assert!("0".parse::<Signal>().is_err());
+
+        assert!(Signal::Segv.dumps_core());
+        assert!(!Signal::Term.dumps_core());
+    }
+}
@@ -0,0 +1,425 @@
+use crate::{Error, Result, util};
+
+/// Specifiers accepted in spec values (`systemd.unit(5)`, "Specifiers").
+const KNOWN_SPECIFIERS: &str = "aAbBCdEfgGhHiIjJlLmMnNopPqsStTuUvVwWyY";
+
+/// Specifiers that only make sense in template units (`name@.service`).
+const INSTANCE_SPECIFIERS: &str = "iI";
+
+/// Values used to preview specifier expansion for one concrete unit (instance).
+///
+/// `SpecifierContext::system` fills in the values the system manager would use; fields are
+/// public so callers can override them. Specifiers without a value (e.g. `%m`, `%b`) are left
+/// verbatim in previews.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct SpecifierContext {
+    /// Full unit name, e.g. `app@web.service` (`%n`).
+    pub unit: String,
+    /// `%u` (user of the service manager).
+    pub user: Option<String>,
+    /// `%U`.
+    pub uid: Option<u32>,
+    /// `%g`.
+    pub group: Option<String>,
+    /// `%G`.
+    pub gid: Option<u32>,
+    /// `%h`.
+    pub home: Option<String>,
+    /// `%s`.
+    pub shell: Option<String>,
+    /// `%H`.
+    pub hostname: Option<String>,
+    /// `%t`.
+    pub runtime_dir: Option<String>,
+    /// `%S`.
+    pub state_dir: Option<String>,
+    /// `%C`.
+    pub cache_dir: Option<String>,
+    /// `%L`.
+    pub logs_dir: Option<String>,
+    /// `%E`.
+    pub config_dir: Option<String>,
+    /// `%T`.
+    pub tmp_dir: Option<String>,
+    /// `%V`.
+    pub var_tmp_dir: Option<String>,
+}
+
+impl SpecifierContext {
+    /// Context for `unit` as run by the system manager.
+    ///
+    /// For a template unit (`app@.service`) pass `instance` to preview `app@<instance>.service`.
+    pub fn system(unit: &str, instance: Option<&str>) -> Result<Self> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        let unit = match instance {
+            Some(instance) => instantiate(&unit, instance)?,
+            None => unit,
+        };
+
+        Ok(Self {
+            unit,
+            user: Some("root".to_string()),
+            uid: Some(0),
+            group: Some("root".to_string()),
+            gid: Some(0),
+            home: Some("/root".to_string()),
+            shell: Some("/bin/sh".to_string()),
+            hostname: None,
+            runtime_dir: Some("/run".to_string()),
+            state_dir: Some("/var/lib".to_string()),
+            cache_dir: Some("/var/cache".to_string()),
+            logs_dir: Some("/var/log".to_string()),
+            config_dir: Some("/etc".to_string()),
+            tmp_dir: Some("/tmp".to_string()),
+            var_tmp_dir: Some("/var/tmp".to_string()),
+        })
+    }
+
+    /// Expand specifiers in one value.
+    ///
+    /// Unknown specifiers and a dangling `%` are rejected; known specifiers without a value in
+    /// this context are kept as-is.
+    pub fn expand(&self, value: &str) -> Result<String> {
+        self.expand_inner(value, true)
+    }
+
+    /// Expand specifiers in rendered unit/drop-in content, leaving comments, section headers and
+    /// keys that systemd does not expand (e.g. `CPUQuota=50%`) untouched.
+    pub fn expand_unit_file(&self, content: &str) -> String {
+        let mut out = String::with_capacity(content.len());
+        for line in content.lines() {
+            let key = line.split_once('=').map(|(k, _)| k.trim());
+            let skip = line.starts_with('#')
+                || line.starts_with('[')
+                || key.is_none_or(|k| NON_EXPANDING_KEYS.contains(&k));
+            if skip {
+                out.push_str(line);
+            } else {
+                out.push_str(
+                    &self
+                        .expand_inner(line, false)
+                        .unwrap_or_else(|_| line.to_string()),
+                );
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn expand_inner(&self, value: &str, strict: bool) -> Result<String> {
+        let mut out = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let Some(spec) = chars.next() else {
+                if strict {
+                    return Err(dangling_percent("value"));
+                }
+                out.push('%');
+                break;
+            };
+            if spec == '%' {
+                out.push('%');
+                continue;
+            }
+            if strict && !KNOWN_SPECIFIERS.contains(spec) {
+                return Err(unknown_specifier("value", spec));
+            }
+            match self.resolve(spec) {
+                Some(v) => out.push_str(&v),
+                None => {
+                    out.push('%');
+                    out.push(spec);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn resolve(&self, spec: char) -> Option<String> {
+        let (name, _) = self.unit.rsplit_once('.')?;
+        let (prefix, instance) = match name.split_once('@') {
+            Some((p, i)) => (p, Some(i)),
+            None => (name, None),
+        };
+
+        let v = match spec {
+            'n' => self.unit.clone(),
+            'N' => name.to_string(),
+            'p' => prefix.to_string(),
+            'P' => unescape_unit_part(prefix),
+            'i' => instance.unwrap_or("").to_string(),
+            'I' => unescape_unit_part(instance.unwrap_or("")),
+            'j' => prefix.rsplit('-').next().unwrap_or(prefix).to_string(),
+            'J' => unescape_unit_part(prefix.rsplit('-').next().unwrap_or(prefix)),
+            'f' => {
+                let part = unescape_unit_part(instance.unwrap_or(prefix));
+                if part.starts_with('/') {
+                    part
+                } else {
+                    format!("/{part}")
+                }
+            }
+            'u' => self.user.clone()?,
+            'U' => self.uid?.to_string(),
+            'g' => self.group.clone()?,
+            'G' => self.gid?.to_string(),
+            'h' => self.home.clone()?,
+            's' => self.shell.clone()?,
+            'H' => self.hostname.clone()?,
+            'l' => self.hostname.as_deref()?.split('.').next()?.to_string(),
+            't' => self.runtime_dir.clone()?,
+            'S' => self.state_dir.clone()?,
+            'C' => self.cache_dir.clone()?,
+            'L' => self.logs_dir.clone()?,
+            'E' => self.config_dir.clone()?,
+            'T' => self.tmp_dir.clone()?,
+            'V' => self.var_tmp_dir.clone()?,
+            _ => return None,
+        };
+        Some(v)
+    }
+}
+
+/// Keys whose values systemd does not run through specifier expansion.
+const NON_EXPANDING_KEYS: [&str; 10] = [
+    "Type",
+    "Restart",
+    "RestartSec",
+    "TimeoutStartSec",
+    "TimeoutStopSec",
+    "RuntimeMaxSec",
+    "RuntimeRandomizedExtraSec",
+    "MemoryHigh",
+    "MemoryMax",
+    "CPUQuota",
+];
+
+/// Validate specifier usage in a value systemd expands.
+///
+/// Rejects unknown specifiers, a dangling `%` (a literal percent must be written as `%%`), and
+/// instance specifiers (`%i`, `%I`) outside template units.
+pub(crate) fn validate_specifiers(
+    context: &'static str,
+    value: &str,
+    template: bool,
+) -> Result<()> {
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        let Some(spec) = chars.next() else {
+            return Err(dangling_percent(context));
+        };
+        if spec == '%' {
+            continue;
+        }
+        if !KNOWN_SPECIFIERS.contains(spec) {
+            return Err(unknown_specifier(context, spec));
+        }
+        if !template && INSTANCE_SPECIFIERS.contains(spec) {
+            return Err(Error::invalid_input(format!(
+                "{context}: %{spec} is only valid in template units (name@.service)"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject specifiers in values systemd passes through literally (e.g. `Restart=`).
+pub(crate) fn reject_specifiers(context: &'static str, value: &str) -> Result<()> {
+    if value.contains('%') {
+        return Err(Error::invalid_input(format!(
+            "{context} does not support specifiers"
+        )));
+    }
+    Ok(())
+}
+
+/// Whether a unit name is a template (`name@.service`) or template instance (`name@x.service`).
+pub(crate) fn is_template_unit(unit: &str) -> bool {
+    unit.rsplit_once('.')
+        .is_some_and(|(name, _)| name.contains('@'))
+}
+
+/// `template@<escaped instance>.<type>` for a raw (unescaped) `instance`.
+#[cfg(feature = "config")]
+pub(crate) fn instance_unit(template: &str, instance: &str) -> Result<String> {
+    let template = util::canonicalize_unit_name(template)?;
+    if !template
+        .rsplit_once('.')
+        .is_some_and(|(name, _)| name.ends_with('@'))
+    {
+        return Err(Error::invalid_input(
+            "template must be a template unit (name@.service)",
+        ));
+    }
+    util::validate_no_control("instance", instance)?;
+    instantiate(&template, &escape_unit_part(instance))
+}
+
+fn instantiate(unit: &str, instance: &str) -> Result<String> {
+    util::validate_no_control("instance", instance)?;
+    if instance.is_empty() || instance.contains('/') || instance.contains('@') {
+        return Err(Error::invalid_input(
+            "instance must be non-empty and must not contain '/' or '@'",
+        ));
+    }
+    let Some((name, suffix)) = unit.rsplit_once('.') else {
+        return Err(Error::invalid_input("unit must have a type suffix"));
+    };
+    let Some(prefix) = name.strip_suffix('@') else {
+        return Err(Error::invalid_input(
+            "instance requires a template unit (name@.service)",
+        ));
+    };
+    Ok(format!("{prefix}@{instance}.{suffix}"))
+}
+
+/// `systemd-escape`: `/` becomes `-`; bytes outside `[A-Za-z0-9:_.]` (and a leading `.`)
+/// become `\xNN`.
+#[cfg(feature = "config")]
+fn escape_unit_part(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for (i, b) in input.bytes().enumerate() {
+        match b {
+            b'/' => out.push('-'),
+            b'.' if i == 0 => out.push_str("\\x2e"),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b':' | b'_' | b'.' => out.push(char::from(b)),
+            _ => out.push_str(&format!("\\x{b:02x}")),
+        }
+    }
+    out
+}
+
+/// Reverse `systemd-escape`: `-` becomes `/` and `\xNN` becomes the escaped byte.
+fn unescape_unit_part(input: &str) -> String {
+    let mut bytes = Vec::<u8>::with_capacity(input.len());
+    let raw = input.as_bytes();
+    let mut i = 0;
+    while i < raw.len() {
+        match raw[i] {
+            b'-' => {
+                bytes.push(b'/');
+                i += 1;
+            }
+            b'\\' if raw.get(i + 1) == Some(&b'x') => {
+                let hex = input
+                    .get(i + 2..i + 4)
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                match hex {
+                    Some(b) => {
+                        bytes.push(b);
+                        i += 4;
+                    }
+                    None => {
+                        bytes.push(b'\\');
+                        i += 1;
+                    }
+                }
+            }
+            b => {
+                bytes.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn dangling_percent(context: &'static str) -> Error {
+    Error::invalid_input(format!(
+        "{context}: dangling '%' (write '%%' for a literal percent)"
+    ))
+}
+
+fn unknown_specifier(context: &'static str, spec: char) -> Error {
+    Error::invalid_input(format!(
+        "{context}: unknown specifier %{spec} (write '%%' for a literal percent)"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn validate_rejects_unknown_dangling_and_misplaced_instance() {
+        assert!(validate_specifiers("v", "%h/%n 100%%", false).is_ok());
+        assert!(validate_specifiers("v", "/srv/%i", true).is_ok());
+
+        for (value, template) in [("50%", false), ("%z", false), ("/srv/%i", false)] {
+            let err = validate_specifiers("v", value, template).expect_err(value);
+            let Error::InvalidInput { .. } = err else {
+                panic!("unexpected error: {err:?}");
+            };
+        }
+        assert!(reject_specifiers("restart", "on-%i").is_err());
+
+        let err = validate_specifiers("env value", "50% off", false).expect_err("bare percent");
+        assert!(err.to_string().contains("'%%'"), "{err}");
+        assert!(validate_specifiers("env value", "50%% off", false).is_ok());
+    }
+
+    #[test]
+    fn system_context_expands_instance_and_paths() {
+        let mut ctx = SpecifierContext::system("web-api@.service", None).unwrap();
+        ctx.unit = "web-api@blue\\x2dgreen.service".to_string();
+        assert_eq!(
+            ctx.expand("%n %N %p %P %i %I %j").unwrap(),
+            "web-api@blue\\x2dgreen.service web-api@blue\\x2dgreen web-api web/api blue\\x2dgreen blue-green api"
+        );
+
+        let ctx = SpecifierContext::system("app@.service", Some("one")).unwrap();
+        assert_eq!(ctx.unit, "app@one.service");
+        assert_eq!(
+            ctx.expand("%t/%i.sock %h %f 5%%").unwrap(),
+            "/run/one.sock /root /one 5%"
+        );
+        assert_eq!(ctx.expand("%m").unwrap(), "%m");
+        assert!(ctx.expand("%z").is_err());
+
+        assert!(SpecifierContext::system("app.service", Some("one")).is_err());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn instance_unit_escapes_like_systemd_escape() {
+        assert_eq!(
+            instance_unit("worker@", "acme corp/eu-1").unwrap(),
+            "worker@acme\\x20corp-eu\\x2d1.service"
+        );
+        assert_eq!(
+            instance_unit("worker@.socket", ".hidden:ü").unwrap(),
+            "worker@\\x2ehidden:\\xc3\\xbc.socket"
+        );
+        assert_eq!(
+            unescape_unit_part(&escape_unit_part("tenant-a/b c")),
+            "tenant-a/b c"
+        );
+        assert!(instance_unit("worker.service", "a").is_err());
+        assert!(instance_unit("worker@x.service", "a").is_err());
+        assert!(instance_unit("worker@", "").is_err());
+    }
+
+    #[test]
+    fn expand_rendered_skips_non_expanding_keys() {
+        let ctx = SpecifierContext::system("app@.service", Some("x")).unwrap();
+        let out = ctx.expand_unit_file(
+            "# Unit: app@.service\n[Service]\nExecStart=/bin/app %i\nCPUQuota=50%\n",
+        );
+        assert_eq!(
+            out,
+            "# Unit: app@.service\n[Service]\nExecStart=/bin/app x\nCPUQuota=50%\n"
+        );
+    }
+}
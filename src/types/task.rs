@@ -12,8 +12,43 @@ pub struct TaskSpec {
     pub workdir: Option<String>,
     /// Task execution timeout (also applied as `TimeoutStartUSec` in systemd).
     pub timeout: std::time::Duration,
+    /// Hard cap on the unit's active time, enforced by systemd (`RuntimeMaxUSec`) even if the
+    /// caller waiting on the task goes away. Has no effect on `oneshot` tasks, which `timeout`
+    /// already bounds.
+    pub runtime_max: Option<std::time::Duration>,
+    /// Random extra time added to `runtime_max` (`RuntimeRandomizedExtraUSec`).
+    pub runtime_randomized_extra: Option<std::time::Duration>,
     /// Optional hint included in the generated transient unit name (sanitized).
     pub name_hint: Option<String>,
+    /// How the transient unit name is built (prefix, hostname).
+    pub naming: NamingPolicy,
+    /// Derive the unit name from this key instead of the time and a nonce.
+    ///
+    /// The same spec and key always yield the same unit name. `Tasks::run` keeps a keyed unit
+    /// loaded after it finishes (`RemainAfterExit=yes`) and, on resubmission, returns a handle to
+    /// the existing unit (running, finished or failed) instead of starting a duplicate, so a
+    /// submission can be safely retried after a reconnect or agent restart. The unit stays
+    /// loaded until `TaskHandle::release` (or a daemon restart or reboot); call it once the
+    /// result has been consumed, which also lets the key start a new run. The other `Tasks`
+    /// entry points fail with the systemd `org.freedesktop.systemd1.UnitExists` D-Bus error
+    /// instead.
+    pub idempotency_key: Option<String>,
+    /// Units the task is ordered after (transient `After=`; shorthand names are canonicalized).
+    pub after: Vec<String>,
+    /// Units the task requires (transient `Requires=`; shorthand names are canonicalized).
+    ///
+    /// Combine with `after` to run the task only once the required units are active.
+    pub requires: Vec<String>,
+    /// Credentials exposed to the task below `$CREDENTIALS_DIRECTORY` (transient
+    /// `LoadCredential=` / `SetCredential=` / `SetCredentialEncrypted=`).
+    pub credentials: Vec<crate::Credential>,
+    /// Capture stdout/stderr through pipes instead of routing them to journald; the bytes are
+    /// returned in `TaskResult.output` (`Tasks::run` only, not combinable with
+    /// `idempotency_key`).
+    pub capture_output: bool,
+    /// Maximum bytes kept per stream when `capture_output` is set (default: 1 MiB); further
+    /// output is read and discarded so the task never blocks on a full pipe.
+    pub max_output_bytes: u32,
 }
 
 impl Default for TaskSpec {
@@ -23,11 +58,127 @@ impl Default for TaskSpec {
             env: BTreeMap::new(),
             workdir: None,
             timeout: std::time::Duration::from_secs(0),
+            runtime_max: None,
+            runtime_randomized_extra: None,
             name_hint: None,
+            naming: NamingPolicy::default(),
+            idempotency_key: None,
+            after: Vec::new(),
+            requires: Vec::new(),
+            credentials: Vec::new(),
+            capture_output: false,
+            max_output_bytes: 1024 * 1024,
         }
     }
 }
 
+/// Transient unit naming for tasks (feature=`tasks`).
+///
+/// Names have the form `<prefix>[-<hostname>][-<name_hint>]-<suffix>.service`. The suffix is
+/// `<unix-ts>-<nonce>` (unique per call), or a hash of `TaskSpec.idempotency_key` when set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct NamingPolicy {
+    /// Name prefix (default `unitbus`; ASCII alphanumerics, `-` and `_`, at most 32 bytes).
+    pub prefix: String,
+    /// Include the short hostname (sanitized), so names stay distinct across machines that share
+    /// a log or inventory pipeline.
+    pub include_hostname: bool,
+}
+
+impl Default for NamingPolicy {
+    fn default() -> Self {
+        Self {
+            prefix: "unitbus".to_string(),
+            include_hostname: false,
+        }
+    }
+}
+
+/// Listener configuration for `Tasks::run_socket_activated` (feature=`tasks`).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct TaskSocketSpec {
+    /// `ListenStream=` addresses (e.g. `"127.0.0.1:8080"`, `"/run/app.sock"`). Port `0` is passed
+    /// to systemd unchanged; `SocketTaskHandle.listen` is read back from the socket unit.
+    pub listen_stream: Vec<String>,
+    /// `ListenDatagram=` addresses.
+    pub listen_datagram: Vec<String>,
+    /// Optional `SocketMode=` for `AF_UNIX` sockets (e.g. `0o660`).
+    pub socket_mode: Option<u32>,
+}
+
+/// Result of `Tasks::run_socket_activated` (feature=`tasks`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SocketTaskHandle {
+    /// Transient socket unit name (e.g. `unitbus-<ts>-<nonce>.socket`).
+    pub socket_unit: String,
+    /// Transient service unit name activated by the socket.
+    pub service_unit: String,
+    /// Outcome of starting the socket unit.
+    pub socket_outcome: crate::types::unit::JobOutcome,
+    /// Listen addresses reported by systemd (empty when the socket failed to start).
+    pub listen: Vec<crate::SocketListen>,
+}
+
+/// When a `Tasks::schedule` timer elapses (feature=`tasks`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TimerTrigger {
+    /// `OnCalendar=`: wall-clock schedule.
+    Calendar(crate::CalendarSpec),
+    /// `OnActiveSec=`: this long after the timer is scheduled.
+    OnActive(std::time::Duration),
+    /// `OnUnitActiveSec=`: this long after each run of the task started (repeats).
+    ///
+    /// The interval only counts from a previous run, so a timer whose triggers are all
+    /// `OnUnitActive` also gets `OnActiveSec=0` and runs the task once when it is scheduled.
+    OnUnitActive(std::time::Duration),
+}
+
+/// Transient timer + service pair for `Tasks::schedule` (feature=`tasks`).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct TimerTaskSpec {
+    /// The service run on every elapse. Its `timeout` bounds each run and the wait for the
+    /// timer to start; its naming fields name both units.
+    pub task: TaskSpec,
+    /// Elapse conditions (at least one; the timer elapses on whichever comes first).
+    pub triggers: Vec<TimerTrigger>,
+    /// `Persistent=`: catch up on calendar elapses missed while the system was down.
+    pub persistent: bool,
+    /// `AccuracySec=` (systemd default: 1min).
+    pub accuracy: Option<std::time::Duration>,
+    /// `RandomizedDelaySec=`: spread elapses of many hosts.
+    pub randomized_delay: Option<std::time::Duration>,
+}
+
+impl TimerTaskSpec {
+    pub fn new(task: TaskSpec, trigger: TimerTrigger) -> Self {
+        Self {
+            task,
+            triggers: vec![trigger],
+            ..Default::default()
+        }
+    }
+}
+
+/// Result of `Tasks::schedule` (feature=`tasks`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ScheduledTaskHandle {
+    /// Transient timer unit name (e.g. `unitbus-<ts>-<nonce>.timer`).
+    pub timer_unit: String,
+    /// Transient service unit name triggered by the timer.
+    pub service_unit: String,
+    /// Outcome of starting the timer unit.
+    pub timer_outcome: crate::types::unit::JobOutcome,
+
+    #[doc(hidden)]
+    pub(crate) inner: std::sync::Arc<crate::Inner>,
+}
+
 /// Handle for a transient task.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -39,9 +190,52 @@ pub struct TaskHandle {
 
     #[doc(hidden)]
     pub(crate) inner: crate::units::JobInner,
+    #[doc(hidden)]
+    pub(crate) output: Option<std::sync::Arc<crate::capture::OutputCapture>>,
+}
+
+/// Options for `TaskHandle::logs` (feature=`tasks`).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct TaskLogOptions {
+    /// Delay between journal polls while the task is running (default: 500ms).
+    pub poll_interval: std::time::Duration,
+    /// Overall deadline for the stream; `Error::Timeout` is returned once it passes (default: 5min).
+    pub timeout: std::time::Duration,
+    /// Maximum entries fetched per journal poll (default: 200).
+    pub batch_limit: u32,
+    /// Maximum bytes to keep from `MESSAGE` (default: 16 KiB).
+    pub max_message_bytes: u32,
+}
+
+impl Default for TaskLogOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: std::time::Duration::from_millis(500),
+            timeout: std::time::Duration::from_secs(300),
+            batch_limit: 200,
+            max_message_bytes: 16 * 1024,
+        }
+    }
+}
+
+/// Live journal stream of a transient task, created by `TaskHandle::logs` (feature=`tasks`).
+///
+/// Entries are read by polling journald for the task's unit; the stream ends once the task's job
+/// is gone, the unit is no longer active and all remaining entries have been drained.
+#[derive(Debug)]
+pub struct TaskLogStream {
+    pub(crate) task: TaskHandle,
+    pub(crate) opts: TaskLogOptions,
+    pub(crate) deadline: std::time::Instant,
+    pub(crate) cursor: Option<crate::types::journal::JournalCursor>,
+    pub(crate) buffered: std::collections::VecDeque<crate::types::journal::JournalEntry>,
+    pub(crate) draining: bool,
+    pub(crate) finished: bool,
 }
 
 /// Result of a transient task.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct TaskResult {
@@ -49,6 +243,132 @@ pub struct TaskResult {
     pub unit_status: crate::types::unit::UnitStatus,
     /// Exit status when available (`ExecMainCode == CLD_EXITED`).
     pub exit_status: Option<i32>,
-    /// Signal number when available (`ExecMainCode == CLD_KILLED/CLD_DUMPED`).
+    /// Signal number when available (`ExecMainCode == CLD_KILLED/CLD_DUMPED`); see
+    /// `killed_by` for the named signal.
     pub signal: Option<i32>,
+    /// Captured stdout/stderr (`Some` only with `TaskSpec.capture_output`).
+    pub output: Option<TaskOutput>,
+}
+
+/// Bounded stdout/stderr of a task run with `TaskSpec.capture_output` (feature=`tasks`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TaskOutput {
+    /// Bytes written to stdout (at most `TaskSpec.max_output_bytes`).
+    pub stdout: Vec<u8>,
+    /// Bytes written to stderr (at most `TaskSpec.max_output_bytes`).
+    pub stderr: Vec<u8>,
+    /// `true` if either stream exceeded `max_output_bytes`, or was still open when `wait`
+    /// returned (e.g. held by a process that outlived the unit).
+    pub truncated: bool,
+}
+
+impl TaskOutput {
+    /// stdout decoded as UTF-8 (invalid sequences replaced).
+    pub fn stdout_lossy(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.stdout)
+    }
+
+    /// stderr decoded as UTF-8 (invalid sequences replaced).
+    pub fn stderr_lossy(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.stderr)
+    }
+}
+
+impl TaskResult {
+    /// The signal that terminated the main process, e.g. `Some(Signal::Kill)` for `signal:
+    /// Some(9)`. `None` if it exited normally or the number is not a standard signal.
+    pub fn killed_by(&self) -> Option<crate::Signal> {
+        self.signal.and_then(crate::Signal::from_number)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(TaskResult);
+
+/// Handle for a transient task attached to a PTY (feature=`tasks-pty`).
+#[cfg(feature = "tasks-pty")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InteractiveTaskHandle {
+    /// Underlying task handle (use `task.wait(...)` to collect the final result).
+    pub task: TaskHandle,
+    /// Path of the terminal the task is attached to (e.g. `/dev/pts/3`).
+    pub tty_path: String,
+    /// Terminal output (the task's stdout/stderr).
+    pub reader: PtyReader,
+    /// Terminal input (the task's stdin).
+    pub writer: PtyWriter,
+}
+
+/// Read half of a task PTY (feature=`tasks-pty`).
+///
+/// Implements `futures_io::AsyncRead`. Reaches EOF once the task closes its terminal.
+#[cfg(feature = "tasks-pty")]
+#[derive(Debug)]
+pub struct PtyReader {
+    pub(crate) io: blocking::Unblock<std::fs::File>,
+}
+
+/// Write half of a task PTY (feature=`tasks-pty`).
+///
+/// Implements `futures_io::AsyncWrite`.
+#[cfg(feature = "tasks-pty")]
+#[derive(Debug)]
+pub struct PtyWriter {
+    pub(crate) io: blocking::Unblock<std::fs::File>,
+    pub(crate) control: std::fs::File,
+}
+
+#[cfg(feature = "tasks-pty")]
+impl PtyWriter {
+    /// Set the terminal window size (rows x columns).
+    pub fn resize(&self, rows: u16, cols: u16) -> crate::Result<()> {
+        crate::pty::set_window_size(&self.control, rows, cols)
+    }
+}
+
+#[cfg(feature = "tasks-pty")]
+impl futures_lite::AsyncRead for PtyReader {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        match std::pin::Pin::new(&mut self.io).poll_read(cx, buf) {
+            // Linux reports EIO on the master side once the slave has been closed (task exited).
+            std::task::Poll::Ready(Err(e))
+                if rustix::io::Errno::from_io_error(&e) == Some(rustix::io::Errno::IO) =>
+            {
+                std::task::Poll::Ready(Ok(0))
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(feature = "tasks-pty")]
+impl futures_lite::AsyncWrite for PtyWriter {
+    fn poll_write(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        std::pin::Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_flush(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::pin::Pin::new(&mut self.io).poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::pin::Pin::new(&mut self.io).poll_close(cx)
+    }
 }
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::{Duration, SystemTime};
 
 /// systemd `StartUnit`/`StopUnit` mode.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -118,7 +119,126 @@ impl ActiveState {
     }
 }
 
+/// Serialized as the systemd string (e.g. `"not-found"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for LoadState {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// Serialized as the systemd string (e.g. `"active"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for ActiveState {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// systemd `Unit.FreezerState` (cgroup freezer; systemd >= 246).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FreezerState {
+    Running,
+    Freezing,
+    Frozen,
+    Thawing,
+    Unknown(String),
+}
+
+impl FreezerState {
+    pub(crate) fn parse(s: &str) -> Self {
+        match s {
+            "running" => FreezerState::Running,
+            "freezing" => FreezerState::Freezing,
+            "frozen" => FreezerState::Frozen,
+            "thawing" => FreezerState::Thawing,
+            other => FreezerState::Unknown(other.to_string()),
+        }
+    }
+
+    /// Return the original systemd string representation (e.g. `"frozen"`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            FreezerState::Running => "running",
+            FreezerState::Freezing => "freezing",
+            FreezerState::Frozen => "frozen",
+            FreezerState::Thawing => "thawing",
+            FreezerState::Unknown(s) => s.as_str(),
+        }
+    }
+}
+
+/// Serialized as the systemd string (e.g. `"frozen"`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for FreezerState {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+/// One process in a unit's cgroup tree (`Manager.GetUnitProcesses`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitProcess {
+    /// cgroup the process lives in (the unit's cgroup or a sub-cgroup of it).
+    pub cgroup_path: String,
+    pub pid: u32,
+    /// Command line as systemd renders it (may be truncated; empty for vanished processes).
+    pub command_line: String,
+}
+
+impl UnitProcess {
+    pub(crate) fn from_dbus((cgroup_path, pid, command_line): (String, u32, String)) -> Self {
+        Self {
+            cgroup_path,
+            pid,
+            command_line,
+        }
+    }
+}
+
+/// Point-in-time cgroup accounting of a unit (`Units::resource_usage`).
+///
+/// Each value is `None` when systemd does not report it: accounting disabled for the unit
+/// (`CPUAccounting=`, `MemoryAccounting=`, `TasksAccounting=`, `IOAccounting=`), no cgroup yet,
+/// or a systemd/kernel too old for the property (`MemoryPeak` needs systemd >= 256).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnitResources {
+    /// Total CPU time consumed (`CPUUsageNSec`).
+    pub cpu_usage_nsec: Option<u64>,
+    /// Current memory usage in bytes (`MemoryCurrent`).
+    pub memory_current: Option<u64>,
+    /// Highest memory usage since the cgroup was created (`MemoryPeak`).
+    pub memory_peak: Option<u64>,
+    /// Number of tasks (`TasksCurrent`).
+    pub tasks_current: Option<u64>,
+    /// Bytes read from block devices (`IOReadBytes`).
+    pub io_read_bytes: Option<u64>,
+    /// Bytes written to block devices (`IOWriteBytes`).
+    pub io_write_bytes: Option<u64>,
+}
+
+impl UnitResources {
+    pub(crate) fn from_properties(props: &crate::Properties) -> Self {
+        // systemd reports "not available" as `u64::MAX`.
+        let get = |key: &str| props.get_u64(key).filter(|v| *v != u64::MAX);
+        Self {
+            cpu_usage_nsec: get("CPUUsageNSec"),
+            memory_current: get("MemoryCurrent"),
+            memory_peak: get("MemoryPeak"),
+            tasks_current: get("TasksCurrent"),
+            io_read_bytes: get("IOReadBytes"),
+            io_write_bytes: get("IOWriteBytes"),
+        }
+    }
+}
+
 /// Snapshot of relevant systemd unit/service properties.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct UnitStatus {
@@ -129,12 +249,156 @@ pub struct UnitStatus {
     pub sub_state: Option<String>,
     pub result: Option<String>,
     pub fragment_path: Option<String>,
+    /// `UnitFileState` (as `systemctl is-enabled` reports it); `None` when systemd reports none,
+    /// e.g. for units without a unit file.
+    pub unit_file_state: Option<crate::UnitFileState>,
+    /// `UnitFilePreset`: what the preset policy says about the unit.
+    pub vendor_preset: Option<crate::VendorPreset>,
+    /// `DropInPaths`: drop-ins applied on top of `fragment_path`, in the order systemd read them.
+    pub dropin_paths: Vec<String>,
     pub main_pid: Option<u32>,
     pub exec_main_code: Option<i32>,
     pub exec_main_status: Option<i32>,
     pub n_restarts: Option<u32>,
+    /// `InvocationID` of the current (or last) run as 32 hex digits; `None` if the unit has not
+    /// run since it was loaded.
+    pub invocation_id: Option<String>,
+    /// `ControlGroup` relative to the cgroupfs root (e.g. `/system.slice/app.service`); `None`
+    /// for unit types without a cgroup or while the unit has none.
+    pub control_group: Option<String>,
+    /// `Delegate=`: whether the unit manages its own cgroup subtree.
+    pub delegate: Option<bool>,
+    /// Slice the unit is placed in (e.g. `system.slice`).
+    pub slice: Option<String>,
+    /// `ExecStartPre=` .. `ExecStopPost=` command lines with their last run (services only), in
+    /// the order systemd runs the phases.
+    pub exec_commands: Vec<ExecCommandStatus>,
+}
+
+impl UnitStatus {
+    /// `true` if the unit is masked (`masked` or `masked-runtime`), i.e. cannot be started.
+    pub fn is_masked(&self) -> bool {
+        matches!(
+            self.unit_file_state,
+            Some(crate::UnitFileState::Masked | crate::UnitFileState::MaskedRuntime)
+        ) || self.load_state == LoadState::Masked
+    }
+
+    /// The command whose failure failed the unit: the last command that ran and failed without
+    /// `-` (ignore failure) in front of it.
+    pub fn failed_exec(&self) -> Option<&ExecCommandStatus> {
+        self.exec_commands
+            .iter()
+            .rev()
+            .find(|c| c.failed() && !c.ignore_failure)
+    }
+
+    /// Absolute cgroupfs directory of `control_group` (e.g.
+    /// `/sys/fs/cgroup/system.slice/app.service`), or `None` if the unit has no control group.
+    ///
+    /// Resolves against the unified (v2) hierarchy, including its `/sys/fs/cgroup/unified`
+    /// mount on hybrid hosts; returns `Error::BackendUnavailable` if none is mounted.
+    pub fn cgroupfs_path(&self) -> crate::Result<Option<std::path::PathBuf>> {
+        self.control_group
+            .as_deref()
+            .map(crate::cgroup::cgroupfs_path)
+            .transpose()
+    }
+}
+
+/// Service command phase (the `Exec*=` setting a command line comes from).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ExecPhase {
+    StartPre,
+    Start,
+    StartPost,
+    Reload,
+    Stop,
+    StopPost,
+}
+
+impl ExecPhase {
+    pub const ALL: [ExecPhase; 6] = [
+        Self::StartPre,
+        Self::Start,
+        Self::StartPost,
+        Self::Reload,
+        Self::Stop,
+        Self::StopPost,
+    ];
+
+    /// D-Bus property name, e.g. `ExecStartPre`.
+    pub fn property(self) -> &'static str {
+        match self {
+            Self::StartPre => "ExecStartPre",
+            Self::Start => "ExecStart",
+            Self::StartPost => "ExecStartPost",
+            Self::Reload => "ExecReload",
+            Self::Stop => "ExecStop",
+            Self::StopPost => "ExecStopPost",
+        }
+    }
+}
+
+/// One `Exec*=` command line and its most recent run (`a(sasbttttuii)` in the Service interface).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ExecCommandStatus {
+    pub phase: ExecPhase,
+    pub path: String,
+    pub argv: Vec<String>,
+    /// `true` for commands prefixed with `-`, whose failure is ignored.
+    pub ignore_failure: bool,
+    pub started_at: Option<SystemTime>,
+    pub exited_at: Option<SystemTime>,
+    pub pid: Option<u32>,
+    /// `si_code` of the exit: 1 `CLD_EXITED`, 2 `CLD_KILLED`, 3 `CLD_DUMPED`.
+    pub code: Option<i32>,
+    /// Exit status, or signal number when killed/dumped.
+    pub status: Option<i32>,
+}
+
+impl ExecCommandStatus {
+    /// `true` once the command has exited (as opposed to never run or still running).
+    pub fn exited(&self) -> bool {
+        self.exited_at.is_some() && self.code.is_some()
+    }
+
+    /// `true` if the command exited non-zero or was killed by a signal.
+    pub fn failed(&self) -> bool {
+        match (self.code, self.status) {
+            (Some(1), Some(status)) => status != 0,
+            (Some(2 | 3), _) => true,
+            _ => false,
+        }
+    }
+
+    /// Decode one entry of an `Exec*` property.
+    pub(crate) fn from_dbus(phase: ExecPhase, item: ExecCommandItem) -> Self {
+        let (path, argv, ignore_failure, start_us, _, exit_us, _, pid, code, status) = item;
+        let time = |us: u64| (us != 0).then(|| crate::util::system_time_from_unix_micros(us));
+        let ran = start_us != 0;
+        Self {
+            phase,
+            path,
+            argv,
+            ignore_failure,
+            started_at: time(start_us),
+            exited_at: time(exit_us),
+            pid: (pid != 0).then_some(pid),
+            code: (ran && code != 0).then_some(code),
+            status: (ran && code != 0).then_some(status),
+        }
+    }
 }
 
+/// Raw `(sasbttttuii)` D-Bus struct of an `Exec*` property entry.
+pub(crate) type ExecCommandItem = (String, Vec<String>, bool, u64, u64, u64, u64, u32, i32, i32);
+
 /// Handle for a systemd job.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -153,6 +417,8 @@ impl fmt::Display for JobHandle {
 }
 
 /// A best-effort classification of why a job failed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum FailureHint {
@@ -173,15 +439,26 @@ pub enum FailureHint {
         active_state: ActiveState,
         sub_state: Option<String>,
     },
+    /// The job succeeded but the unit left the expected state (or was restarted by
+    /// `Restart=`) within the `JobHandle::wait_stable` window, `after` the job completed.
+    Unstable {
+        after: Duration,
+        active_state: ActiveState,
+        sub_state: Option<String>,
+    },
     Unknown,
 }
 
 /// Normalized outcome for a job wait.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "outcome", rename_all = "snake_case"))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum JobOutcome {
     Success {
         unit_status: UnitStatus,
+        /// Things that look wrong even though the job succeeded.
+        warnings: Vec<JobWarning>,
     },
     Failed {
         unit_status: UnitStatus,
@@ -192,6 +469,547 @@ pub enum JobOutcome {
     },
 }
 
+/// Caveat attached to a successful `JobOutcome`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum JobWarning {
+    /// The job started a new run of the service, which then crashed and was restarted by
+    /// `Restart=` before the wait ended, so the reported success may not last.
+    RestartedDuringWait {
+        /// Automatic restarts since the job's start (`NRestarts`, which the start resets).
+        n_restarts: u32,
+    },
+}
+
+/// Desired end state for `Units::ensure`; `None` leaves that aspect untouched.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DesiredUnitState {
+    /// Whether the unit should be enabled.
+    pub enabled: Option<bool>,
+    /// Whether the unit should be active (running).
+    pub active: Option<bool>,
+    /// Mode used for the start/stop job.
+    pub mode: UnitStartMode,
+    /// Maximum time to wait for the start/stop job.
+    pub timeout: Duration,
+}
+
+impl Default for DesiredUnitState {
+    fn default() -> Self {
+        Self {
+            enabled: None,
+            active: None,
+            mode: UnitStartMode::Replace,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl DesiredUnitState {
+    /// Enabled and active (`systemctl enable --now`).
+    pub fn enabled_and_active() -> Self {
+        Self {
+            enabled: Some(true),
+            active: Some(true),
+            ..Self::default()
+        }
+    }
+
+    /// Disabled and stopped (`systemctl disable --now`).
+    pub fn disabled_and_stopped() -> Self {
+        Self {
+            enabled: Some(false),
+            active: Some(false),
+            ..Self::default()
+        }
+    }
+}
+
+/// A single socket listener as reported by `org.freedesktop.systemd1.Socket.Listen`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SocketListen {
+    /// Listener kind (e.g. `"Stream"`, `"Datagram"`).
+    pub kind: String,
+    /// Address as configured (e.g. `"127.0.0.1:8080"`).
+    pub address: String,
+}
+
+/// `get_status` plus kind-specific properties, from `Units::get_typed_status`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TypedUnitStatus {
+    /// `.service` units; `UnitStatus` already carries their service properties.
+    Service(UnitStatus),
+    Socket(SocketStatus),
+    Timer(TimerStatus),
+    Path(PathStatus),
+    Mount(MountStatus),
+    /// Any other unit type (target, slice, scope, device, ...).
+    Other(UnitStatus),
+}
+
+impl TypedUnitStatus {
+    /// The common status, whatever the unit kind.
+    pub fn status(&self) -> &UnitStatus {
+        match self {
+            Self::Service(status) | Self::Other(status) => status,
+            Self::Socket(s) => &s.status,
+            Self::Timer(t) => &t.status,
+            Self::Path(p) => &p.status,
+            Self::Mount(m) => &m.status,
+        }
+    }
+}
+
+/// Status of a `.socket` unit (`org.freedesktop.systemd1.Socket`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SocketStatus {
+    pub status: UnitStatus,
+    /// Configured listeners (`Listen`).
+    pub listen: Vec<SocketListen>,
+    /// Connections accepted so far (`NAccepted`; `Accept=yes` sockets only).
+    pub n_accepted: Option<u32>,
+    /// Currently open connections (`NConnections`).
+    pub n_connections: Option<u32>,
+    /// Connections refused by rate or connection limits (`NRefused`; systemd >= 246).
+    pub n_refused: Option<u32>,
+}
+
+/// Status of a `.timer` unit (`org.freedesktop.systemd1.Timer`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TimerStatus {
+    pub status: UnitStatus,
+    /// Unit activated when the timer elapses (`Unit`).
+    pub unit: Option<String>,
+    /// Next elapse of the calendar triggers (`NextElapseUSecRealtime`).
+    pub next_elapse_realtime: Option<std::time::SystemTime>,
+    /// Next elapse of the monotonic triggers, as `CLOCK_MONOTONIC` time since boot
+    /// (`NextElapseUSecMonotonic`).
+    pub next_elapse_monotonic: Option<std::time::Duration>,
+    /// Wall-clock time of the last trigger (`LastTriggerUSec`).
+    pub last_trigger: Option<std::time::SystemTime>,
+}
+
+/// Status of a `.path` unit (`org.freedesktop.systemd1.Path`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PathStatus {
+    pub status: UnitStatus,
+    /// Unit activated when a condition triggers (`Unit`).
+    pub unit: Option<String>,
+    /// Watched paths (`Paths`).
+    pub paths: Vec<PathWatch>,
+}
+
+/// One watched path of a `.path` unit.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PathWatch {
+    /// Condition (e.g. `"PathExists"`, `"PathChanged"`, `"DirectoryNotEmpty"`).
+    pub kind: String,
+    pub path: String,
+}
+
+/// Status of a `.mount` unit (`org.freedesktop.systemd1.Mount`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MountStatus {
+    pub status: UnitStatus,
+    /// Mounted device or source (`What`).
+    pub what: Option<String>,
+    /// Mount point (`Where`).
+    pub mount_point: Option<String>,
+    /// File system type (`Type`).
+    pub fs_type: Option<String>,
+    /// Mount options (`Options`).
+    pub options: Option<String>,
+}
+
+/// Result of `Units::ensure`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EnsureReport {
+    pub unit: String,
+    /// `true` if anything was changed.
+    pub changed: bool,
+    pub enablement_changed: bool,
+    pub active_changed: bool,
+    /// Outcome of the start/stop job, if one was needed.
+    pub job_outcome: Option<JobOutcome>,
+}
+
+/// Waits for `Units::stop_graceful`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EscalationPolicy {
+    /// How long the stop job gets before `SIGKILL` is sent (default: 10s).
+    pub term_wait: Duration,
+    /// How long to wait after `SIGKILL` before giving up (default: 5s).
+    pub kill_wait: Duration,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            term_wait: Duration::from_secs(10),
+            kill_wait: Duration::from_secs(5),
+        }
+    }
+}
+
+impl EscalationPolicy {
+    pub fn new(term_wait: Duration, kill_wait: Duration) -> Self {
+        Self {
+            term_wait,
+            kill_wait,
+        }
+    }
+}
+
+/// Signal level `Units::stop_graceful` needed to stop the unit.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum StopLevel {
+    /// The unit was not running; no stop job was queued.
+    AlreadyStopped,
+    /// The stop job finished within `term_wait` (systemd sent the unit's `KillSignal=`,
+    /// `SIGTERM` by default).
+    Term,
+    /// The stop job was still running after `term_wait`; `SIGKILL` was sent.
+    Kill,
+}
+
+/// Result of `Units::stop_graceful`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct GracefulStopReport {
+    pub unit: String,
+    pub level: StopLevel,
+    /// Time from queuing the stop job until it finished.
+    pub elapsed: Duration,
+    /// Outcome of the stop job; `None` if the unit was already stopped.
+    pub job_outcome: Option<JobOutcome>,
+}
+
+/// Result of `Units::start_ordered`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct OrderedStartReport {
+    /// All planned waves in start order; waves after `failed_wave` have no results.
+    pub waves: Vec<StartWave>,
+    /// Index into `waves` of the first wave with a unit that did not start.
+    pub failed_wave: Option<usize>,
+}
+
+impl OrderedStartReport {
+    /// `true` if every unit started successfully.
+    pub fn is_success(&self) -> bool {
+        self.failed_wave.is_none()
+    }
+}
+
+/// Units started together by `Units::start_ordered`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct StartWave {
+    pub units: Vec<String>,
+    /// One entry per unit once the wave ran; empty if it was never started.
+    pub results: Vec<WaveUnitResult>,
+}
+
+/// Start result of one unit in a `StartWave`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct WaveUnitResult {
+    pub unit: String,
+    /// Job outcome, or `None` if starting or waiting failed (see `error`).
+    pub outcome: Option<JobOutcome>,
+    /// The start or wait error (e.g. a job timeout).
+    pub error: Option<String>,
+}
+
+/// Options for `Units::restart_many`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BatchOptions {
+    /// Maximum number of restart jobs in flight at once (default: 4).
+    pub concurrency: usize,
+    /// Restart no further units once one has failed (default: `false`). Jobs already queued
+    /// still run to completion and are reported.
+    pub stop_on_failure: bool,
+    /// How long to wait for each restart job (default: 60s).
+    pub per_unit_timeout: Duration,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            stop_on_failure: false,
+            per_unit_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn new(concurrency: usize, per_unit_timeout: Duration) -> Self {
+        Self {
+            concurrency,
+            per_unit_timeout,
+            ..Self::default()
+        }
+    }
+
+    pub fn stop_on_failure(mut self, stop: bool) -> Self {
+        self.stop_on_failure = stop;
+        self
+    }
+}
+
+/// Result of `Units::restart_many`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BatchReport {
+    /// One entry per restarted unit, in the order the units were given.
+    pub results: Vec<BatchUnitResult>,
+    /// Units never restarted because `BatchOptions.stop_on_failure` tripped.
+    pub skipped: Vec<String>,
+}
+
+impl BatchReport {
+    /// `true` if every unit was restarted successfully.
+    pub fn is_success(&self) -> bool {
+        self.skipped.is_empty() && self.results.iter().all(BatchUnitResult::is_success)
+    }
+
+    /// Results of the units that did not restart successfully.
+    pub fn failures(&self) -> impl Iterator<Item = &BatchUnitResult> {
+        self.results.iter().filter(|r| !r.is_success())
+    }
+}
+
+/// Restart result of one unit in a `BatchReport`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BatchUnitResult {
+    pub unit: String,
+    /// Job outcome, or `None` if queuing or waiting failed (see `error`).
+    pub outcome: Option<JobOutcome>,
+    /// The restart or wait error (e.g. a job timeout).
+    pub error: Option<String>,
+}
+
+impl BatchUnitResult {
+    pub fn is_success(&self) -> bool {
+        matches!(self.outcome, Some(JobOutcome::Success { .. }))
+    }
+}
+
+/// Options for `Units::restart_and_verify`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct VerifyOptions {
+    /// Mode used for the restart job (default: `Replace`).
+    pub mode: UnitStartMode,
+    /// How long to wait for the restart job (default: 60s).
+    pub job_timeout: Duration,
+    /// How long the unit must then stay active without an automatic restart (default: 15s).
+    pub settle: Duration,
+    /// Readiness probes run in order once the unit has settled (feature=`probes`).
+    #[cfg(feature = "probes")]
+    pub probes: Vec<crate::probes::Probe>,
+    /// How long each probe may take to succeed (default: 30s).
+    #[cfg(feature = "probes")]
+    pub probe_timeout: Duration,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            mode: UnitStartMode::Replace,
+            job_timeout: Duration::from_secs(60),
+            settle: Duration::from_secs(15),
+            #[cfg(feature = "probes")]
+            probes: Vec::new(),
+            #[cfg(feature = "probes")]
+            probe_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl VerifyOptions {
+    pub fn new(job_timeout: Duration, settle: Duration) -> Self {
+        Self {
+            job_timeout,
+            settle,
+            ..Self::default()
+        }
+    }
+
+    pub fn mode(mut self, mode: UnitStartMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Require `probe` to succeed after the settle window (feature=`probes`).
+    #[cfg(feature = "probes")]
+    pub fn probe(mut self, probe: crate::probes::Probe) -> Self {
+        self.probes.push(probe);
+        self
+    }
+
+    #[cfg(feature = "probes")]
+    pub fn probe_timeout(mut self, timeout: Duration) -> Self {
+        self.probe_timeout = timeout;
+        self
+    }
+}
+
+/// Result of `Units::restart_and_verify`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "outcome", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum VerifiedOutcome {
+    /// The restart job itself did not succeed (failed, canceled or wrong end state).
+    NotStarted { outcome: JobOutcome },
+    /// The unit came up but, `after` the job completed, left `active` or was restarted by
+    /// `Restart=` (`restarts` is the `NRestarts` increase since the job was queued).
+    StartedThenFailed {
+        after: Duration,
+        restarts: u32,
+        unit_status: UnitStatus,
+    },
+    /// The unit stayed active with no automatic restart for the whole settle window (and every
+    /// readiness probe succeeded).
+    Stable {
+        settled_for: Duration,
+        unit_status: UnitStatus,
+    },
+    /// The unit settled, but `probe` did not succeed within `VerifyOptions.probe_timeout`
+    /// (feature=`probes`).
+    #[cfg(feature = "probes")]
+    NotReady {
+        probe: String,
+        unit_status: UnitStatus,
+    },
+}
+
+impl VerifiedOutcome {
+    pub fn is_stable(&self) -> bool {
+        matches!(self, VerifiedOutcome::Stable { .. })
+    }
+
+    /// Last observed status of the unit.
+    pub fn unit_status(&self) -> &UnitStatus {
+        match self {
+            VerifiedOutcome::NotStarted { outcome } => match outcome {
+                JobOutcome::Success { unit_status, .. }
+                | JobOutcome::Failed { unit_status, .. }
+                | JobOutcome::Canceled { unit_status } => unit_status,
+            },
+            VerifiedOutcome::StartedThenFailed { unit_status, .. }
+            | VerifiedOutcome::Stable { unit_status, .. } => unit_status,
+            #[cfg(feature = "probes")]
+            VerifiedOutcome::NotReady { unit_status, .. } => unit_status,
+        }
+    }
+}
+
+/// Result of `Units::start_group`, `stop_group` and `restart_group`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct GroupReport {
+    /// The group's target unit.
+    pub target: String,
+    /// Outcome of the job on the target itself.
+    pub outcome: JobOutcome,
+    /// One entry per member unit, sorted by name.
+    pub members: Vec<BatchUnitResult>,
+}
+
+impl GroupReport {
+    /// `true` if the target's job and every member's job succeeded.
+    pub fn is_success(&self) -> bool {
+        matches!(self.outcome, JobOutcome::Success { .. })
+            && self.members.iter().all(BatchUnitResult::is_success)
+    }
+
+    /// Results of the members that did not reach the requested state.
+    pub fn failures(&self) -> impl Iterator<Item = &BatchUnitResult> {
+        self.members.iter().filter(|r| !r.is_success())
+    }
+}
+
+/// Progress event of `Units::start_ordered_progress`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "event", rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum OrderedStartEvent {
+    /// Start jobs for the units of wave `wave` are being queued.
+    WaveStarted { wave: usize, units: Vec<String> },
+    /// The start job for `unit` was queued; a failure to queue it is reported as `UnitFinished`.
+    UnitStarted { wave: usize, unit: String },
+    /// The start job of one unit finished, failed or timed out (in completion order).
+    UnitFinished {
+        wave: usize,
+        result: Box<WaveUnitResult>,
+    },
+    /// The bring-up ended; always the last event.
+    Finished(OrderedStartReport),
+}
+
+/// Live progress of `Units::start_ordered_progress`, a `Stream` of `OrderedStartEvent`s.
+pub struct OrderedStartProgress {
+    pub(crate) stream:
+        std::pin::Pin<Box<dyn futures_util::Stream<Item = OrderedStartEvent> + Send>>,
+}
+
+impl std::fmt::Debug for OrderedStartProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OrderedStartProgress")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_json_output!(
+    UnitStatus,
+    TypedUnitStatus,
+    BatchReport,
+    GroupReport,
+    JobOutcome,
+    VerifiedOutcome,
+    EnsureReport,
+    GracefulStopReport,
+    OrderedStartReport,
+    UnitResources
+);
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -200,6 +1018,42 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn unit_resources_map_unreported_values_to_none() {
+        let mut m = std::collections::HashMap::new();
+        m.insert(
+            "CPUUsageNSec".to_string(),
+            zbus::zvariant::OwnedValue::from(1_500u64),
+        );
+        m.insert(
+            "MemoryCurrent".to_string(),
+            zbus::zvariant::OwnedValue::from(4096u64),
+        );
+        m.insert(
+            "IOReadBytes".to_string(),
+            zbus::zvariant::OwnedValue::from(u64::MAX),
+        );
+
+        let res = UnitResources::from_properties(&crate::Properties::from_dbus(m));
+        assert_eq!(res.cpu_usage_nsec, Some(1_500));
+        assert_eq!(res.memory_current, Some(4096));
+        assert_eq!(res.io_read_bytes, None);
+        assert_eq!(res.memory_peak, None);
+    }
+
+    #[test]
+    fn freezer_state_round_trips_systemd_strings() {
+        for s in ["running", "freezing", "frozen", "thawing"] {
+            let state = FreezerState::parse(s);
+            assert!(!matches!(state, FreezerState::Unknown(_)));
+            assert_eq!(state.as_str(), s);
+        }
+        assert_eq!(
+            FreezerState::parse("partially-frozen"),
+            FreezerState::Unknown("partially-frozen".to_string())
+        );
+    }
+
     #[test]
     fn unit_start_mode_maps_to_dbus_string() {
         assert_eq!(UnitStartMode::Replace.as_dbus_str(), "replace");
@@ -252,4 +1106,139 @@ mod tests {
         assert_eq!(ActiveState::Inactive.as_str(), "inactive");
         assert_eq!(ActiveState::Unknown("wat".to_string()).as_str(), "wat");
     }
+
+    #[test]
+    fn exec_commands_decode_and_pick_failed_line() {
+        let item = |path: &str, ignore: bool, start: u64, code: i32, status: i32| {
+            (
+                path.to_string(),
+                vec![path.to_string(), "--flag".to_string()],
+                ignore,
+                start,
+                0,
+                if start == 0 { 0 } else { start + 1_000_000 },
+                0,
+                if start == 0 { 0 } else { 42 },
+                code,
+                status,
+            )
+        };
+
+        let never =
+            ExecCommandStatus::from_dbus(ExecPhase::Stop, item("/bin/stop", false, 0, 0, 0));
+        assert_eq!(never.started_at, None);
+        assert_eq!(never.pid, None);
+        assert_eq!(never.code, None);
+        assert!(!never.exited() && !never.failed());
+
+        let pre = ExecCommandStatus::from_dbus(
+            ExecPhase::StartPre,
+            item("/bin/check", true, 5_000_000, 1, 2),
+        );
+        let start = ExecCommandStatus::from_dbus(
+            ExecPhase::Start,
+            item("/bin/app", false, 7_000_000, 2, 9),
+        );
+        assert_eq!(
+            start.started_at,
+            Some(crate::util::system_time_from_unix_micros(7_000_000))
+        );
+        assert_eq!(start.pid, Some(42));
+        assert!(pre.failed() && start.failed() && start.exited());
+        assert_eq!(start.argv, vec!["/bin/app", "--flag"]);
+
+        let ok = ExecCommandStatus::from_dbus(
+            ExecPhase::StartPost,
+            item("/bin/post", false, 8_000_000, 1, 0),
+        );
+        assert!(ok.exited() && !ok.failed());
+
+        let mut status = UnitStatus {
+            id: "app.service".to_string(),
+            description: None,
+            load_state: LoadState::Loaded,
+            active_state: ActiveState::Failed,
+            sub_state: None,
+            result: None,
+            fragment_path: None,
+            unit_file_state: None,
+            vendor_preset: None,
+            dropin_paths: Vec::new(),
+            main_pid: None,
+            exec_main_code: None,
+            exec_main_status: None,
+            n_restarts: None,
+            invocation_id: None,
+            control_group: None,
+            delegate: None,
+            slice: None,
+            exec_commands: vec![pre.clone(), ok, never],
+        };
+        // Failure of the `-` prefixed command is ignored.
+        assert_eq!(status.failed_exec(), None);
+        status.exec_commands.insert(1, start.clone());
+        assert_eq!(status.failed_exec(), Some(&start));
+    }
+
+    #[test]
+    fn batch_report_counts_errors_and_skips_as_failure() {
+        let mut report = BatchReport {
+            results: Vec::new(),
+            skipped: Vec::new(),
+        };
+        assert!(report.is_success());
+
+        report.results.push(BatchUnitResult {
+            unit: "web.service".to_string(),
+            outcome: None,
+            error: Some("timeout".to_string()),
+        });
+        report.skipped.push("worker.service".to_string());
+        assert!(!report.is_success());
+        assert_eq!(report.failures().count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn job_outcome_serializes_to_tagged_json() {
+        let status = UnitStatus {
+            id: "app.service".to_string(),
+            description: None,
+            load_state: LoadState::Loaded,
+            active_state: ActiveState::Failed,
+            sub_state: Some("failed".to_string()),
+            result: Some("exit-code".to_string()),
+            fragment_path: None,
+            unit_file_state: Some(crate::UnitFileState::MaskedRuntime),
+            vendor_preset: None,
+            dropin_paths: Vec::new(),
+            main_pid: None,
+            exec_main_code: Some(1),
+            exec_main_status: Some(3),
+            n_restarts: None,
+            invocation_id: None,
+            control_group: None,
+            delegate: None,
+            slice: None,
+            exec_commands: Vec::new(),
+        };
+        assert!(status.is_masked());
+        let outcome = JobOutcome::Failed {
+            unit_status: status,
+            reason: FailureHint::ExecMainFailed {
+                exec_main_code: 1,
+                exec_main_status: 3,
+            },
+        };
+
+        let v: serde_json::Value = serde_json::from_str(&outcome.to_json().unwrap()).unwrap();
+        assert_eq!(v["outcome"], "failed");
+        assert_eq!(v["unit_status"]["load_state"], "loaded");
+        assert_eq!(v["unit_status"]["active_state"], "failed");
+        assert_eq!(v["unit_status"]["unit_file_state"], "masked-runtime");
+        assert_eq!(v["reason"]["kind"], "exec_main_failed");
+        assert_eq!(v["reason"]["exec_main_status"], 3);
+        assert_eq!(outcome.to_string(), outcome.to_json().unwrap());
+        assert!(format!("{outcome:#}").contains('\n'));
+    }
 }
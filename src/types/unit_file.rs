@@ -1,3 +1,6 @@
+use crate::types::lint::LintStrictness;
+use crate::types::specifier::{self, SpecifierContext};
+use crate::types::values::{SystemdDuration, SystemdSize};
 use crate::{Error, Result, util};
 
 use std::collections::BTreeMap;
@@ -32,6 +35,10 @@ impl ServiceType {
 }
 
 /// Specification for generating a systemd service unit file.
+///
+/// systemd expands specifiers (`%n`, `%i`, ...) in most values, so `render` validates them: a
+/// literal percent must be written as `%%` (e.g. `Environment=RATIO=50%%`); a bare `%` followed
+/// by a non-specifier character, or at the end of a value, is rejected.
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct ServiceUnitSpec {
@@ -65,24 +72,51 @@ pub struct ServiceUnitSpec {
     pub user: Option<String>,
     /// Optional `Group=...`.
     pub group: Option<String>,
+    /// `DynamicUser=yes`: run under a user allocated when the service starts.
+    pub dynamic_user: bool,
+    /// `StateDirectory=` entries, relative to `/var/lib` (e.g. `myapp`).
+    pub state_directory: Vec<String>,
+    /// `CacheDirectory=` entries, relative to `/var/cache`.
+    pub cache_directory: Vec<String>,
+    /// `LogsDirectory=` entries, relative to `/var/log`.
+    pub logs_directory: Vec<String>,
+    /// `RuntimeDirectory=` entries, relative to `/run`.
+    pub runtime_directory: Vec<String>,
+    /// Credentials exposed below `$CREDENTIALS_DIRECTORY` (`LoadCredential=`,
+    /// `SetCredential=`, `SetCredentialEncrypted=`).
+    pub credentials: Vec<crate::Credential>,
 
     /// Environment variables rendered as `Environment="K=V"`.
     pub environment: BTreeMap<String, String>,
 
     /// Optional `Restart=...` (raw string, validated for control chars).
     pub restart: Option<String>,
-    /// Optional `RestartSec=...` seconds.
-    pub restart_sec: Option<u32>,
-    /// Optional `TimeoutStartSec=...` seconds.
-    pub timeout_start_sec: Option<u32>,
-    /// Optional `TimeoutStopSec=...` seconds.
-    pub timeout_stop_sec: Option<u32>,
+    /// Optional `RestartSec=...`.
+    pub restart_sec: Option<SystemdDuration>,
+    /// Optional `TimeoutStartSec=...`.
+    pub timeout_start_sec: Option<SystemdDuration>,
+    /// Optional `TimeoutStopSec=...`.
+    pub timeout_stop_sec: Option<SystemdDuration>,
+    /// Optional `RuntimeMaxSec=...`: systemd stops the service once it has been active this long.
+    pub runtime_max_sec: Option<SystemdDuration>,
+    /// Optional `RuntimeRandomizedExtraSec=...` (requires `runtime_max_sec`).
+    pub runtime_randomized_extra_sec: Option<SystemdDuration>,
+
+    /// Optional `MemoryHigh=...`.
+    pub memory_high: Option<SystemdSize>,
+    /// Optional `MemoryMax=...`.
+    pub memory_max: Option<SystemdSize>,
+    /// Optional `CPUQuota=...` (must be `SystemdSize::Percent`).
+    pub cpu_quota: Option<SystemdSize>,
 
     /// Optional `StandardOutput=...`.
     pub standard_output: Option<String>,
     /// Optional `StandardError=...`.
     pub standard_error: Option<String>,
 
+    /// Optional sandboxing directives (see `apply_hardening`).
+    pub hardening: Option<ServiceHardening>,
+
     /// Optional `[Install] WantedBy=...` entries.
     pub wanted_by: Vec<String>,
     /// Optional `[Install] RequiredBy=...` entries.
@@ -96,10 +130,191 @@ pub struct ServiceUnitSpec {
     pub extra_service: Vec<String>,
     /// Extra raw lines appended under `[Install]` (escape hatch).
     pub extra_install: Vec<String>,
+
+    /// Run `lint` on the rendered file and reject findings at this strictness (default off).
+    pub lint: LintStrictness,
+}
+
+/// Hardening preset for `ServiceUnitSpec::apply_hardening`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum HardeningLevel {
+    /// Low-risk sandboxing that rarely breaks services: read-only `/usr`/`/boot`/`/etc`,
+    /// read-only home directories, private `/tmp`, kernel tunables/modules protection.
+    Baseline,
+    /// `Baseline` plus a fully read-only file system (`ProtectSystem=strict`), no home access,
+    /// no devices, no capabilities and restricted namespaces/syscall architectures.
+    ///
+    /// Services that write to disk need `read_write_paths` (or `StateDirectory=` via
+    /// `extra_service`).
+    Strict,
+}
+
+/// Sandboxing directives rendered into `[Service]` (see `systemd.exec(5)`).
+///
+/// Start from `ServiceHardening::preset` (or `ServiceUnitSpec::apply_hardening`) and relax
+/// individual fields as needed. `None`/`false`/empty values are omitted from the unit file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ServiceHardening {
+    /// `NoNewPrivileges=yes`.
+    pub no_new_privileges: bool,
+    /// `ProtectSystem=` (`"true"`, `"full"` or `"strict"`).
+    pub protect_system: Option<String>,
+    /// `ProtectHome=` (`"true"`, `"read-only"` or `"tmpfs"`).
+    pub protect_home: Option<String>,
+    /// `PrivateTmp=yes`.
+    pub private_tmp: bool,
+    /// `PrivateDevices=yes`.
+    pub private_devices: bool,
+    /// `ProtectKernelTunables=yes`.
+    pub protect_kernel_tunables: bool,
+    /// `ProtectKernelModules=yes`.
+    pub protect_kernel_modules: bool,
+    /// `ProtectKernelLogs=yes`.
+    pub protect_kernel_logs: bool,
+    /// `ProtectControlGroups=yes`.
+    pub protect_control_groups: bool,
+    /// `ProtectClock=yes`.
+    pub protect_clock: bool,
+    /// `ProtectHostname=yes`.
+    pub protect_hostname: bool,
+    /// `RestrictSUIDSGID=yes`.
+    pub restrict_suid_sgid: bool,
+    /// `RestrictRealtime=yes`.
+    pub restrict_realtime: bool,
+    /// `RestrictNamespaces=yes`.
+    pub restrict_namespaces: bool,
+    /// `LockPersonality=yes`.
+    pub lock_personality: bool,
+    /// `MemoryDenyWriteExecute=yes` (breaks JIT runtimes).
+    pub memory_deny_write_execute: bool,
+    /// `SystemCallArchitectures=native`.
+    pub system_call_architectures_native: bool,
+    /// `RestrictAddressFamilies=` allow-list (empty = not restricted).
+    pub restrict_address_families: Vec<String>,
+    /// `CapabilityBoundingSet=`: `None` keeps systemd's default, `Some(vec![])` drops all.
+    pub capability_bounding_set: Option<Vec<String>>,
+    /// `ReadWritePaths=` exceptions (e.g. data directories under `ProtectSystem=strict`).
+    pub read_write_paths: Vec<String>,
+}
+
+impl ServiceHardening {
+    /// Curated directive set for `level`.
+    pub fn preset(level: HardeningLevel) -> Self {
+        let baseline = Self {
+            no_new_privileges: true,
+            protect_system: Some("full".to_string()),
+            protect_home: Some("read-only".to_string()),
+            private_tmp: true,
+            protect_kernel_tunables: true,
+            protect_kernel_modules: true,
+            protect_control_groups: true,
+            restrict_suid_sgid: true,
+            restrict_realtime: true,
+            lock_personality: true,
+            restrict_address_families: vec![
+                "AF_UNIX".to_string(),
+                "AF_INET".to_string(),
+                "AF_INET6".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        match level {
+            HardeningLevel::Baseline => baseline,
+            HardeningLevel::Strict => Self {
+                protect_system: Some("strict".to_string()),
+                protect_home: Some("true".to_string()),
+                private_devices: true,
+                protect_kernel_logs: true,
+                protect_clock: true,
+                protect_hostname: true,
+                restrict_namespaces: true,
+                memory_deny_write_execute: true,
+                system_call_architectures_native: true,
+                capability_bounding_set: Some(Vec::new()),
+                ..baseline
+            },
+        }
+    }
+
+    fn render_into(&self, out: &mut String) -> Result<()> {
+        let flags = [
+            ("NoNewPrivileges", self.no_new_privileges),
+            ("PrivateTmp", self.private_tmp),
+            ("PrivateDevices", self.private_devices),
+            ("ProtectKernelTunables", self.protect_kernel_tunables),
+            ("ProtectKernelModules", self.protect_kernel_modules),
+            ("ProtectKernelLogs", self.protect_kernel_logs),
+            ("ProtectControlGroups", self.protect_control_groups),
+            ("ProtectClock", self.protect_clock),
+            ("ProtectHostname", self.protect_hostname),
+            ("RestrictSUIDSGID", self.restrict_suid_sgid),
+            ("RestrictRealtime", self.restrict_realtime),
+            ("RestrictNamespaces", self.restrict_namespaces),
+            ("LockPersonality", self.lock_personality),
+            ("MemoryDenyWriteExecute", self.memory_deny_write_execute),
+        ];
+
+        let protect_system = normalize_opt_line("protect_system", self.protect_system.as_deref())?;
+        let protect_home = normalize_opt_line("protect_home", self.protect_home.as_deref())?;
+        let families =
+            normalize_word_list("restrict_address_families", &self.restrict_address_families)?;
+        let caps = match &self.capability_bounding_set {
+            Some(caps) => Some(normalize_word_list("capability_bounding_set", caps)?),
+            None => None,
+        };
+        for path in &self.read_write_paths {
+            util::validate_no_control("read_write_paths", path)?;
+            if path.trim().is_empty() {
+                return Err(Error::invalid_input(
+                    "read_write_paths must not contain empty items",
+                ));
+            }
+        }
+
+        for (key, enabled) in flags {
+            if enabled {
+                out.push_str(key);
+                out.push_str("=yes\n");
+            }
+        }
+        if let Some(v) = protect_system {
+            out.push_str("ProtectSystem=");
+            out.push_str(&v);
+            out.push('\n');
+        }
+        if let Some(v) = protect_home {
+            out.push_str("ProtectHome=");
+            out.push_str(&v);
+            out.push('\n');
+        }
+        if self.system_call_architectures_native {
+            out.push_str("SystemCallArchitectures=native\n");
+        }
+        if !families.is_empty() {
+            out.push_str("RestrictAddressFamilies=");
+            out.push_str(&families.join(" "));
+            out.push('\n');
+        }
+        if let Some(caps) = caps {
+            out.push_str("CapabilityBoundingSet=");
+            out.push_str(&caps.join(" "));
+            out.push('\n');
+        }
+        for path in &self.read_write_paths {
+            out.push_str("ReadWritePaths=");
+            out.push_str(&util::quote_systemd_value(path.trim()));
+            out.push('\n');
+        }
+        Ok(())
+    }
 }
 
 /// Report for writing a unit file.
 #[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct UnitFileWriteReport {
@@ -109,10 +324,15 @@ pub struct UnitFileWriteReport {
     pub path_written: String,
     /// Whether a daemon reload is required for systemd to pick up the change.
     pub requires_daemon_reload: bool,
+    /// Computed under `UnitBusOptions.dry_run`; nothing was written.
+    pub dry_run: bool,
+    /// Line diff from the current unit file to the new content (dry run only).
+    pub diff: Option<Vec<crate::DiffLine>>,
 }
 
 /// Report for removing a unit file.
 #[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct UnitFileRemoveReport {
@@ -122,10 +342,14 @@ pub struct UnitFileRemoveReport {
     pub path_removed: String,
     /// Whether a daemon reload is required for systemd to pick up the change.
     pub requires_daemon_reload: bool,
+    /// Computed under `UnitBusOptions.dry_run`; nothing was removed.
+    pub dry_run: bool,
+    /// Lines that removing the unit file would drop (dry run only).
+    pub diff: Option<Vec<crate::DiffLine>>,
 }
 
 /// A single unit file change entry returned by systemd.
-#[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct UnitFileChange {
@@ -134,7 +358,6 @@ pub struct UnitFileChange {
     pub source: Option<String>,
 }
 
-#[cfg(feature = "config")]
 impl UnitFileChange {
     pub(crate) fn from_dbus(item: (String, String, String)) -> Self {
         let (kind, path, source) = item;
@@ -148,7 +371,6 @@ impl UnitFileChange {
 }
 
 /// Options for enabling a unit file via D-Bus (`EnableUnitFiles`).
-#[cfg(feature = "config")]
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct UnitFileEnableOptions {
@@ -159,7 +381,6 @@ pub struct UnitFileEnableOptions {
 }
 
 /// Options for disabling a unit file via D-Bus (`DisableUnitFiles`).
-#[cfg(feature = "config")]
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct UnitFileDisableOptions {
@@ -168,7 +389,7 @@ pub struct UnitFileDisableOptions {
 }
 
 /// Report returned by enabling unit files.
-#[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct UnitFileEnableReport {
@@ -177,13 +398,73 @@ pub struct UnitFileEnableReport {
 }
 
 /// Report returned by disabling unit files.
-#[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct UnitFileDisableReport {
     pub changes: Vec<UnitFileChange>,
 }
 
+/// Options for masking a unit via D-Bus (`MaskUnitFiles`).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct UnitFileMaskOptions {
+    /// Mask only for the current boot (runtime).
+    pub runtime: bool,
+    /// Replace an existing file or symlink at the mask location.
+    pub force: bool,
+}
+
+/// Options for unmasking a unit via D-Bus (`UnmaskUnitFiles`).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct UnitFileUnmaskOptions {
+    /// Remove only the runtime mask.
+    pub runtime: bool,
+}
+
+/// Report returned by masking/unmasking unit files.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct UnitFileChangeReport {
+    pub changes: Vec<UnitFileChange>,
+}
+
+/// Which half of the preset policy `PresetUnitFilesWithMode` applies.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnitFilePresetMode {
+    /// Enable or disable, as the preset says (default).
+    #[default]
+    Full,
+    /// Only enable units the preset enables.
+    EnableOnly,
+    /// Only disable units the preset disables.
+    DisableOnly,
+}
+
+impl UnitFilePresetMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UnitFilePresetMode::Full => "full",
+            UnitFilePresetMode::EnableOnly => "enable-only",
+            UnitFilePresetMode::DisableOnly => "disable-only",
+        }
+    }
+}
+
+/// Options for applying preset policy via D-Bus (`PresetUnitFilesWithMode`).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct UnitFilePresetOptions {
+    pub mode: UnitFilePresetMode,
+    /// Apply only for the current boot (runtime).
+    pub runtime: bool,
+    /// Overwrite existing symlinks.
+    pub force: bool,
+}
+
 /// Options for installing a service unit file (write + optional daemon-reload + optional enable).
 #[cfg(feature = "config")]
 #[derive(Clone, Debug)]
@@ -194,6 +475,9 @@ pub struct ServiceUnitInstallOptions {
     /// Whether to enable the unit (`EnableUnitFiles`).
     pub enable: bool,
     pub enable_options: UnitFileEnableOptions,
+    /// Hold a logind shutdown inhibitor while writing, reloading and enabling, so a reboot
+    /// cannot land between the steps (default: `false`).
+    pub inhibit_shutdown: bool,
 }
 
 #[cfg(feature = "config")]
@@ -203,12 +487,14 @@ impl Default for ServiceUnitInstallOptions {
             daemon_reload: true,
             enable: true,
             enable_options: UnitFileEnableOptions::default(),
+            inhibit_shutdown: false,
         }
     }
 }
 
 /// Report returned by `install_service_unit`.
 #[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct ServiceUnitInstallReport {
@@ -228,6 +514,9 @@ pub struct UnitUninstallOptions {
     pub disable_options: UnitFileDisableOptions,
     /// Whether to call `config().daemon_reload()` after removal (recommended).
     pub daemon_reload: bool,
+    /// Hold a logind shutdown inhibitor while disabling, removing and reloading (default:
+    /// `false`).
+    pub inhibit_shutdown: bool,
 }
 
 #[cfg(feature = "config")]
@@ -237,12 +526,14 @@ impl Default for UnitUninstallOptions {
             disable: true,
             disable_options: UnitFileDisableOptions::default(),
             daemon_reload: true,
+            inhibit_shutdown: false,
         }
     }
 }
 
 /// Report returned by `uninstall_unit`.
 #[cfg(feature = "config")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct UnitUninstallReport {
@@ -262,9 +553,45 @@ impl ServiceUnitSpec {
         Ok(unit)
     }
 
+    /// Set `RestartSec=` in seconds.
+    pub fn set_restart_sec(&mut self, secs: u32) -> &mut Self {
+        self.restart_sec = Some(secs.into());
+        self
+    }
+
+    /// Set `TimeoutStartSec=` in seconds.
+    pub fn set_timeout_start_sec(&mut self, secs: u32) -> &mut Self {
+        self.timeout_start_sec = Some(secs.into());
+        self
+    }
+
+    /// Set `TimeoutStopSec=` in seconds.
+    pub fn set_timeout_stop_sec(&mut self, secs: u32) -> &mut Self {
+        self.timeout_stop_sec = Some(secs.into());
+        self
+    }
+
+    /// Fill in a curated set of sandboxing directives and return them for selective relaxing.
+    ///
+    /// Replaces any previously applied hardening. Example:
+    /// `spec.apply_hardening(HardeningLevel::Strict).read_write_paths.push("/var/lib/app".into())`.
+    pub fn apply_hardening(&mut self, level: HardeningLevel) -> &mut ServiceHardening {
+        self.hardening.insert(ServiceHardening::preset(level))
+    }
+
+    /// Render the unit file with specifiers expanded as the system manager would for `instance`.
+    ///
+    /// `instance` is required to preview `%i`/`%I` of a template unit (`app@.service`).
+    pub fn preview_expansion(&self, instance: Option<&str>) -> Result<String> {
+        let rendered = self.render()?;
+        let ctx = SpecifierContext::system(&self.canonical_unit_name()?, instance)?;
+        Ok(ctx.expand_unit_file(&rendered))
+    }
+
     /// Render the unit file content.
     pub fn render(&self) -> Result<String> {
         let unit_name = self.canonical_unit_name()?;
+        self.validate_specifiers(specifier::is_template_unit(&unit_name))?;
 
         let description = normalize_opt_line("description", self.description.as_deref())?;
 
@@ -384,6 +711,22 @@ impl ServiceUnitSpec {
             out.push_str(&g);
             out.push('\n');
         }
+        render_exec_directories(
+            &mut out,
+            self.dynamic_user,
+            [
+                &self.state_directory,
+                &self.cache_directory,
+                &self.logs_directory,
+                &self.runtime_directory,
+            ],
+            specifier::is_template_unit(&unit_name),
+        )?;
+        crate::types::credential::render_credentials(
+            &mut out,
+            &self.credentials,
+            specifier::is_template_unit(&unit_name),
+        )?;
 
         for (k, v) in &self.environment {
             let assignment = format!("{k}={v}");
@@ -397,19 +740,49 @@ impl ServiceUnitSpec {
             out.push_str(&r);
             out.push('\n');
         }
-        if let Some(sec) = self.restart_sec {
+        if let Some(v) = self.restart_sec {
             out.push_str("RestartSec=");
-            out.push_str(&sec.to_string());
+            out.push_str(&v.to_string());
             out.push('\n');
         }
-        if let Some(sec) = self.timeout_start_sec {
+        if let Some(v) = self.timeout_start_sec {
             out.push_str("TimeoutStartSec=");
-            out.push_str(&sec.to_string());
+            out.push_str(&v.to_string());
             out.push('\n');
         }
-        if let Some(sec) = self.timeout_stop_sec {
+        if let Some(v) = self.timeout_stop_sec {
             out.push_str("TimeoutStopSec=");
-            out.push_str(&sec.to_string());
+            out.push_str(&v.to_string());
+            out.push('\n');
+        }
+        if self.runtime_randomized_extra_sec.is_some() && self.runtime_max_sec.is_none() {
+            return Err(Error::invalid_input(
+                "runtime_randomized_extra_sec requires runtime_max_sec",
+            ));
+        }
+        if let Some(v) = self.runtime_max_sec {
+            out.push_str("RuntimeMaxSec=");
+            out.push_str(&v.to_string());
+            out.push('\n');
+        }
+        if let Some(v) = self.runtime_randomized_extra_sec {
+            out.push_str("RuntimeRandomizedExtraSec=");
+            out.push_str(&v.to_string());
+            out.push('\n');
+        }
+        if let Some(v) = self.memory_high {
+            out.push_str("MemoryHigh=");
+            out.push_str(&v.to_string());
+            out.push('\n');
+        }
+        if let Some(v) = self.memory_max {
+            out.push_str("MemoryMax=");
+            out.push_str(&v.to_string());
+            out.push('\n');
+        }
+        if let Some(v) = validate_cpu_quota(self.cpu_quota)? {
+            out.push_str("CPUQuota=");
+            out.push_str(&v.to_string());
             out.push('\n');
         }
 
@@ -424,6 +797,10 @@ impl ServiceUnitSpec {
             out.push('\n');
         }
 
+        if let Some(h) = &self.hardening {
+            h.render_into(&mut out)?;
+        }
+
         for line in self
             .extra_service
             .iter()
@@ -466,10 +843,116 @@ impl ServiceUnitSpec {
             }
         }
 
+        let rejected = self.lint.rejected(crate::types::lint::lint(&out));
+        if !rejected.is_empty() {
+            let findings: Vec<String> = rejected.iter().map(ToString::to_string).collect();
+            return Err(Error::invalid_input(format!(
+                "unit file lint failed: {}",
+                findings.join("; ")
+            )));
+        }
+
         Ok(out)
     }
 }
 
+impl ServiceUnitSpec {
+    fn validate_specifiers(&self, template: bool) -> Result<()> {
+        let check = |context: &'static str, value: &str| {
+            specifier::validate_specifiers(context, value, template)
+        };
+
+        for v in self.description.iter() {
+            check("description", v)?;
+        }
+        for (context, list) in [
+            ("after", &self.after),
+            ("wants", &self.wants),
+            ("requires", &self.requires),
+            ("wanted_by", &self.wanted_by),
+            ("required_by", &self.required_by),
+            ("alias", &self.alias),
+            ("exec_start", &self.exec_start),
+        ] {
+            for v in list {
+                check(context, v)?;
+            }
+        }
+        for (context, list) in [
+            ("exec_start_pre", &self.exec_start_pre),
+            ("exec_start_post", &self.exec_start_post),
+        ] {
+            for v in list.iter().flatten() {
+                check(context, v)?;
+            }
+        }
+        for (k, v) in &self.environment {
+            check("environment", k)?;
+            check("environment", v)?;
+        }
+        for (context, value) in [
+            ("working_directory", &self.working_directory),
+            ("user", &self.user),
+            ("group", &self.group),
+            ("standard_output", &self.standard_output),
+            ("standard_error", &self.standard_error),
+        ] {
+            if let Some(v) = value {
+                check(context, v)?;
+            }
+        }
+        if let Some(h) = &self.hardening {
+            for v in &h.read_write_paths {
+                check("read_write_paths", v)?;
+            }
+        }
+        if let Some(v) = &self.restart {
+            specifier::reject_specifiers("restart", v)?;
+        }
+        if let Some(ServiceType::Other(v)) = &self.service_type {
+            specifier::reject_specifiers("service_type", v)?;
+        }
+        Ok(())
+    }
+}
+
+/// Render `DynamicUser=` and the `StateDirectory=`/`CacheDirectory=`/`LogsDirectory=`/
+/// `RuntimeDirectory=` lists (in that order) shared by `ServiceUnitSpec` and `DropInSpec`.
+pub(crate) fn render_exec_directories(
+    out: &mut String,
+    dynamic_user: bool,
+    dirs: [&Vec<String>; 4],
+    template: bool,
+) -> Result<()> {
+    const SETTINGS: [(&str, &str); 4] = [
+        ("StateDirectory", "state_directory"),
+        ("CacheDirectory", "cache_directory"),
+        ("LogsDirectory", "logs_directory"),
+        ("RuntimeDirectory", "runtime_directory"),
+    ];
+
+    if dynamic_user {
+        out.push_str("DynamicUser=yes\n");
+    }
+    for ((key, context), list) in SETTINGS.into_iter().zip(dirs) {
+        if list.is_empty() {
+            continue;
+        }
+        let mut names = Vec::with_capacity(list.len());
+        for dir in list {
+            let dir = dir.trim();
+            util::validate_exec_directory(context, dir)?;
+            specifier::validate_specifiers(context, dir, template)?;
+            names.push(dir);
+        }
+        out.push_str(key);
+        out.push('=');
+        out.push_str(&names.join(" "));
+        out.push('\n');
+    }
+    Ok(())
+}
+
 fn normalize_opt_line(context: &'static str, input: Option<&str>) -> Result<Option<String>> {
     let Some(s) = input else {
         return Ok(None);
@@ -500,6 +983,30 @@ fn normalize_unit_list(context: &'static str, input: &[String]) -> Result<Vec<St
     Ok(out)
 }
 
+pub(crate) fn validate_cpu_quota(quota: Option<SystemdSize>) -> Result<Option<SystemdSize>> {
+    match quota {
+        None | Some(SystemdSize::Percent(_)) => Ok(quota),
+        Some(_) => Err(Error::invalid_input(
+            "cpu_quota must be a percentage (e.g. \"50%\")",
+        )),
+    }
+}
+
+fn normalize_word_list(context: &'static str, input: &[String]) -> Result<Vec<String>> {
+    let mut out = Vec::<String>::new();
+    for item in input {
+        util::validate_no_control(context, item)?;
+        let s = item.trim();
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            return Err(Error::invalid_input(format!(
+                "{context} items must be single non-empty words"
+            )));
+        }
+        out.push(s.to_string());
+    }
+    Ok(out)
+}
+
 fn normalize_argv(context: &'static str, argv: &[String]) -> Result<Vec<String>> {
     if argv.is_empty() {
         return Err(Error::invalid_input(format!("{context} must not be empty")));
@@ -524,6 +1031,21 @@ fn validate_raw_line(context: &'static str, line: &str) -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "serde")]
+impl_json_output!(
+    UnitFileEnableReport,
+    UnitFileDisableReport,
+    UnitFileChangeReport
+);
+
+#[cfg(all(feature = "config", feature = "serde"))]
+impl_json_output!(
+    UnitFileWriteReport,
+    UnitFileRemoveReport,
+    ServiceUnitInstallReport,
+    UnitUninstallReport,
+);
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -551,19 +1073,32 @@ mod tests {
             working_directory: Some("/srv/demo".to_string()),
             user: Some("demo".to_string()),
             group: Some("demo".to_string()),
+            dynamic_user: false,
+            state_directory: vec![],
+            cache_directory: vec![],
+            logs_directory: vec![],
+            runtime_directory: vec![],
+            credentials: vec![],
             environment: env,
             restart: Some("always".to_string()),
-            restart_sec: Some(3),
-            timeout_start_sec: Some(10),
-            timeout_stop_sec: Some(5),
+            restart_sec: Some(3.into()),
+            timeout_start_sec: Some(10.into()),
+            timeout_stop_sec: Some(5.into()),
+            runtime_max_sec: Some(3600.into()),
+            runtime_randomized_extra_sec: Some(60.into()),
+            memory_high: None,
+            memory_max: Some(SystemdSize::Bytes(512 << 20)),
+            cpu_quota: Some(SystemdSize::Percent(50)),
             standard_output: Some("journal".to_string()),
             standard_error: Some("journal".to_string()),
+            hardening: None,
             wanted_by: vec!["multi-user.target".to_string()],
             required_by: vec![],
             alias: vec![],
             extra_unit: vec![],
             extra_service: vec![],
             extra_install: vec![],
+            lint: LintStrictness::All,
         };
 
         let rendered = spec.render().expect("render ok");
@@ -574,9 +1109,144 @@ mod tests {
         let idx_a = rendered.find("Environment=\"A=1\"").expect("A exists");
         let idx_b = rendered.find("Environment=\"B=2\"").expect("B exists");
         assert!(idx_a < idx_b);
+        assert!(rendered.contains("RestartSec=3s\n"));
+        assert!(rendered.contains("RuntimeMaxSec=1h\nRuntimeRandomizedExtraSec=1min\n"));
+        assert!(rendered.contains("MemoryMax=512M\n"));
+        assert!(rendered.contains("CPUQuota=50%\n"));
         assert!(rendered.ends_with('\n'));
     }
 
+    #[test]
+    fn render_rejects_non_percent_cpu_quota() {
+        let spec = ServiceUnitSpec {
+            unit: "demo".to_string(),
+            exec_start: vec!["/bin/true".to_string()],
+            cpu_quota: Some(SystemdSize::Bytes(1024)),
+            ..Default::default()
+        };
+        let err = spec.render().expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[test]
+    fn dynamic_user_and_directories_render_and_validate() {
+        let mut spec = ServiceUnitSpec::builder()
+            .unit("worker@.service")
+            .exec(["/usr/bin/worker"])
+            .dynamic_user()
+            .state_directory("worker/%i")
+            .state_directory("worker-shared")
+            .runtime_directory("worker")
+            .build()
+            .unwrap();
+        let rendered = spec.render().unwrap();
+        assert!(rendered.contains(
+            "DynamicUser=yes\nStateDirectory=worker/%i worker-shared\nRuntimeDirectory=worker\n"
+        ));
+        assert!(!rendered.contains("CacheDirectory="));
+
+        for bad in ["/var/lib/worker", "a/../b", "a//b", "two words", ""] {
+            spec.logs_directory = vec![bad.to_string()];
+            assert!(spec.render().is_err(), "{bad:?} accepted");
+        }
+        spec.logs_directory.clear();
+
+        spec.unit = "worker.service".to_string();
+        assert!(spec.render().is_err());
+    }
+
+    #[test]
+    fn unit_file_changes_decode_from_dbus() {
+        let change = UnitFileChange::from_dbus((
+            "symlink".to_string(),
+            "/etc/systemd/system/nginx.service".to_string(),
+            "/dev/null".to_string(),
+        ));
+        assert_eq!(change.source.as_deref(), Some("/dev/null"));
+        let change = UnitFileChange::from_dbus((
+            "unlink".to_string(),
+            "/etc/systemd/system/nginx.service".to_string(),
+            String::new(),
+        ));
+        assert_eq!(change.source, None);
+
+        assert_eq!(UnitFilePresetMode::default().as_str(), "full");
+        assert_eq!(UnitFilePresetMode::EnableOnly.as_str(), "enable-only");
+        assert_eq!(UnitFilePresetMode::DisableOnly.as_str(), "disable-only");
+    }
+
+    #[test]
+    fn render_lints_extra_lines_when_enabled() {
+        let mut spec = ServiceUnitSpec {
+            unit: "app.service".to_string(),
+            exec_start: vec!["/usr/bin/app".to_string()],
+            memory_max: Some(SystemdSize::Bytes(1024)),
+            extra_service: vec!["MemoryLimit=1G".to_string()],
+            ..Default::default()
+        };
+        spec.apply_hardening(HardeningLevel::Strict);
+        assert!(spec.render().is_ok());
+
+        spec.lint = LintStrictness::Errors;
+        assert!(spec.render().is_ok());
+        spec.lint = LintStrictness::All;
+        let err = spec.render().unwrap_err().to_string();
+        assert!(
+            err.contains("MemoryLimit: deprecated, use MemoryMax="),
+            "{err}"
+        );
+
+        // Unknown directives are warnings (the table may predate the host's systemd).
+        spec.extra_service = vec!["ExecStrat=/bin/true".to_string()];
+        spec.lint = LintStrictness::Errors;
+        assert!(spec.render().is_ok());
+        spec.lint = LintStrictness::All;
+        let err = spec.render().unwrap_err().to_string();
+        assert!(
+            err.contains("[Service] ExecStrat: unknown directive"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn template_specifiers_validate_and_preview() {
+        let spec = ServiceUnitSpec {
+            unit: "worker@.service".to_string(),
+            exec_start: vec!["/usr/bin/worker".to_string(), "--queue=%i".to_string()],
+            working_directory: Some("%S/worker/%i".to_string()),
+            restart: Some("always".to_string()),
+            cpu_quota: Some(SystemdSize::Percent(50)),
+            ..Default::default()
+        };
+        assert!(
+            spec.render()
+                .unwrap()
+                .contains("ExecStart=/usr/bin/worker --queue=%i\n")
+        );
+
+        let preview = spec.preview_expansion(Some("emails")).unwrap();
+        assert!(preview.contains("ExecStart=/usr/bin/worker --queue=emails\n"));
+        assert!(preview.contains("WorkingDirectory=\"/var/lib/worker/emails\"\n"));
+        assert!(preview.contains("CPUQuota=50%\n"));
+
+        let plain = ServiceUnitSpec {
+            unit: "worker".to_string(),
+            ..spec.clone()
+        };
+        let err = plain.render().expect_err("%i outside template");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+
+        let literal = ServiceUnitSpec {
+            exec_start: vec!["/bin/echo".to_string(), "100%".to_string()],
+            ..spec
+        };
+        assert!(literal.render().is_err());
+    }
+
     #[test]
     fn canonical_unit_name_requires_service_suffix() {
         let spec = ServiceUnitSpec {
@@ -618,4 +1288,57 @@ mod tests {
             "rendered={rendered}"
         );
     }
+
+    #[test]
+    fn strict_hardening_renders_and_can_be_relaxed() {
+        let mut spec = ServiceUnitSpec {
+            unit: "demo".to_string(),
+            exec_start: vec!["/bin/true".to_string()],
+            ..Default::default()
+        };
+        let h = spec.apply_hardening(HardeningLevel::Strict);
+        h.memory_deny_write_execute = false;
+        h.read_write_paths.push("/var/lib/demo".to_string());
+
+        let rendered = spec.render().expect("render ok");
+        assert!(rendered.contains("NoNewPrivileges=yes\n"));
+        assert!(rendered.contains("ProtectSystem=strict\n"));
+        assert!(rendered.contains("PrivateTmp=yes\n"));
+        assert!(rendered.contains("RestrictAddressFamilies=AF_UNIX AF_INET AF_INET6\n"));
+        assert!(rendered.contains("CapabilityBoundingSet=\n"));
+        assert!(rendered.contains("ReadWritePaths=\"/var/lib/demo\"\n"));
+        assert!(!rendered.contains("MemoryDenyWriteExecute"));
+    }
+
+    #[test]
+    fn baseline_hardening_keeps_devices_and_capabilities() {
+        let rendered = ServiceUnitSpec {
+            unit: "demo".to_string(),
+            exec_start: vec!["/bin/true".to_string()],
+            hardening: Some(ServiceHardening::preset(HardeningLevel::Baseline)),
+            ..Default::default()
+        }
+        .render()
+        .expect("render ok");
+        assert!(rendered.contains("ProtectSystem=full\n"));
+        assert!(rendered.contains("ProtectHome=read-only\n"));
+        assert!(!rendered.contains("PrivateDevices"));
+        assert!(!rendered.contains("CapabilityBoundingSet"));
+    }
+
+    #[test]
+    fn hardening_rejects_multi_word_items() {
+        let mut spec = ServiceUnitSpec {
+            unit: "demo".to_string(),
+            exec_start: vec!["/bin/true".to_string()],
+            ..Default::default()
+        };
+        spec.apply_hardening(HardeningLevel::Baseline)
+            .restrict_address_families
+            .push("AF_UNIX AF_PACKET".to_string());
+        let err = spec.render().expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
 }
@@ -0,0 +1,240 @@
+use crate::{Error, Result};
+
+use std::time::Duration;
+
+/// A systemd time span value (`systemd.time(7)`), e.g. `RestartSec=`, `TimeoutStartSec=`.
+///
+/// Parses `"90"`, `"1h 30min"`, `"2.5s"` and `"infinity"`; renders in normalized systemd form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SystemdDuration {
+    Finite(Duration),
+    Infinity,
+}
+
+impl SystemdDuration {
+    /// A finite span of `secs` seconds.
+    pub fn from_secs(secs: u64) -> Self {
+        Self::Finite(Duration::from_secs(secs))
+    }
+}
+
+impl From<u32> for SystemdDuration {
+    /// Seconds, matching the previous raw `u32` spec fields.
+    fn from(secs: u32) -> Self {
+        Self::from_secs(u64::from(secs))
+    }
+}
+
+impl From<Duration> for SystemdDuration {
+    fn from(d: Duration) -> Self {
+        Self::Finite(d)
+    }
+}
+
+impl std::str::FromStr for SystemdDuration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s == "infinity" {
+            return Ok(Self::Infinity);
+        }
+        crate::util::parse_time_span(s).map(Self::Finite)
+    }
+}
+
+impl std::fmt::Display for SystemdDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [(u128, &str); 6] = [
+            (86_400_000_000, "d"),
+            (3_600_000_000, "h"),
+            (60_000_000, "min"),
+            (1_000_000, "s"),
+            (1_000, "ms"),
+            (1, "us"),
+        ];
+
+        let d = match self {
+            Self::Infinity => return f.write_str("infinity"),
+            Self::Finite(d) => d,
+        };
+        let mut us = d.as_micros();
+        if us == 0 {
+            return f.write_str("0");
+        }
+
+        let mut first = true;
+        for (size, name) in UNITS {
+            if us >= size {
+                if !first {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{}{name}", us / size)?;
+                us %= size;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A systemd resource size value, e.g. `MemoryMax=`, `MemoryHigh=` or `CPUQuota=`.
+///
+/// Parses `"536870912"`, `"512M"`, `"1.5G"` (base 1024, like systemd), `"50%"` and
+/// `"infinity"`; renders in the shortest exact form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SystemdSize {
+    Bytes(u64),
+    /// Percentage (of physical memory for memory limits, of one CPU for `CPUQuota=`).
+    Percent(u32),
+    Infinity,
+}
+
+const SIZE_SUFFIXES: [(char, u32); 6] = [
+    ('K', 10),
+    ('M', 20),
+    ('G', 30),
+    ('T', 40),
+    ('P', 50),
+    ('E', 60),
+];
+
+impl std::str::FromStr for SystemdSize {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let input = s.trim();
+        let invalid = || Error::invalid_input(format!("invalid size: {s:?}"));
+
+        if input == "infinity" {
+            return Ok(Self::Infinity);
+        }
+        if let Some(pct) = input.strip_suffix('%') {
+            return pct
+                .trim()
+                .parse::<u32>()
+                .map(Self::Percent)
+                .map_err(|_| invalid());
+        }
+
+        let number = input.strip_suffix('B').unwrap_or(input);
+        let (number, shift) = match number.chars().last() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                let (_, shift) = SIZE_SUFFIXES
+                    .iter()
+                    .find(|(suffix, _)| *suffix == c.to_ascii_uppercase())
+                    .ok_or_else(invalid)?;
+                (&number[..number.len() - 1], *shift)
+            }
+            _ => (number, 0),
+        };
+
+        let (whole, frac) = number.trim().split_once('.').unwrap_or((number.trim(), ""));
+        if whole.is_empty() || !whole.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let whole: u128 = whole.parse().map_err(|_| invalid())?;
+        let unit = 1u128 << shift;
+        let mut bytes = whole.checked_mul(unit).ok_or_else(invalid)?;
+        if !frac.is_empty() {
+            if !frac.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let frac = frac.get(..9).unwrap_or(frac);
+            let scale = 10u128.pow(u32::try_from(frac.len()).unwrap_or(9));
+            let frac: u128 = frac.parse().map_err(|_| invalid())?;
+            let frac = frac.checked_mul(unit).ok_or_else(invalid)? / scale;
+            bytes = bytes.checked_add(frac).ok_or_else(invalid)?;
+        }
+        u64::try_from(bytes).map(Self::Bytes).map_err(|_| invalid())
+    }
+}
+
+impl std::fmt::Display for SystemdSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Infinity => f.write_str("infinity"),
+            Self::Percent(p) => write!(f, "{p}%"),
+            Self::Bytes(b) => {
+                for (suffix, shift) in SIZE_SUFFIXES.iter().rev() {
+                    let unit = 1u64 << shift;
+                    if *b >= unit && b % unit == 0 {
+                        return write!(f, "{}{suffix}", b / unit);
+                    }
+                }
+                write!(f, "{b}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::panic)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn duration_round_trips_systemd_spans() {
+        let d: SystemdDuration = "1h 30min".parse().unwrap();
+        assert_eq!(d, SystemdDuration::from_secs(5400));
+        assert_eq!(d.to_string(), "1h 30min");
+
+        assert_eq!(SystemdDuration::from(3u32).to_string(), "3s");
+        assert_eq!(SystemdDuration::from_secs(0).to_string(), "0");
+        assert_eq!(
+            SystemdDuration::from(Duration::from_millis(1500)).to_string(),
+            "1s 500ms"
+        );
+        assert_eq!(
+            "infinity".parse::<SystemdDuration>().unwrap(),
+            SystemdDuration::Infinity
+        );
+        assert!("soon".parse::<SystemdDuration>().is_err());
+    }
+
+    #[test]
+    fn size_parses_suffixes_percent_and_infinity() {
+        assert_eq!(
+            "512M".parse::<SystemdSize>().unwrap(),
+            SystemdSize::Bytes(512 << 20)
+        );
+        assert_eq!(
+            "1.5G".parse::<SystemdSize>().unwrap(),
+            SystemdSize::Bytes(3 << 29)
+        );
+        assert_eq!(
+            "4096".parse::<SystemdSize>().unwrap(),
+            SystemdSize::Bytes(4096)
+        );
+        assert_eq!(
+            "150%".parse::<SystemdSize>().unwrap(),
+            SystemdSize::Percent(150)
+        );
+        assert_eq!(
+            "infinity".parse::<SystemdSize>().unwrap(),
+            SystemdSize::Infinity
+        );
+        assert!("12Q".parse::<SystemdSize>().is_err());
+        assert!("M".parse::<SystemdSize>().is_err());
+        assert!("-1".parse::<SystemdSize>().is_err());
+        assert!(
+            "999999999999999999999999999999999999E"
+                .parse::<SystemdSize>()
+                .is_err()
+        );
+        assert!("16E".parse::<SystemdSize>().is_err());
+    }
+
+    #[test]
+    fn size_renders_shortest_exact_form() {
+        assert_eq!(SystemdSize::Bytes(512 << 20).to_string(), "512M");
+        assert_eq!(SystemdSize::Bytes(3 << 29).to_string(), "1536M");
+        assert_eq!(SystemdSize::Bytes(1000).to_string(), "1000");
+        assert_eq!(SystemdSize::Percent(50).to_string(), "50%");
+    }
+}
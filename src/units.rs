@@ -1,16 +1,22 @@
 use crate::{
-    ActiveState, Error, FailureHint, JobHandle, JobOutcome, LoadState, Result, UnitStartMode,
-    UnitStatus, util,
+    ActiveState, Error, ExecCommandStatus, ExecPhase, FailureHint, JobHandle, JobOutcome,
+    LoadState, Result, UnitStartMode, UnitStatus, util,
 };
 
 use futures_util::StreamExt;
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 use zbus::zvariant::{OwnedObjectPath, OwnedValue};
 
 const SYSTEMD_UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
 const SYSTEMD_SERVICE_INTERFACE: &str = "org.freedesktop.systemd1.Service";
 const SYSTEMD_SOCKET_INTERFACE: &str = "org.freedesktop.systemd1.Socket";
 const SYSTEMD_TIMER_INTERFACE: &str = "org.freedesktop.systemd1.Timer";
+const SYSTEMD_PATH_INTERFACE: &str = "org.freedesktop.systemd1.Path";
+const SYSTEMD_MOUNT_INTERFACE: &str = "org.freedesktop.systemd1.Mount";
 
 #[cfg(feature = "tasks")]
 static TRANSIENT_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
@@ -127,6 +133,137 @@ impl Units {
         }
     }
 
+    /// Fetch every property of `unit` in one bag: the `Unit` interface merged with its
+    /// type-specific interface (`Service`, `Socket`, `Timer`, ...), if it has one.
+    ///
+    /// Runtime counters and timestamps (`Properties::is_volatile`) are left out, so comparing two
+    /// snapshots with `Properties::diff`, e.g. before and after a deployment, reports drift in
+    /// configuration and state rather than usage that moves on every read.
+    pub async fn snapshot_properties(&self, unit: &str) -> Result<crate::Properties> {
+        let unit = util::canonicalize_unit_name(unit)?;
+
+        crate::telemetry::traced("snapshot_properties", Some(&unit), async {
+            let unit_path = self.inner.bus.get_unit_path(&unit).await?;
+            let mut props = self.get_unit_properties_by_path(unit_path.as_str()).await?;
+            if let Some(iface) = type_interface(&unit)
+                && let Some(extra) = optional_properties(&self.inner.bus, &unit_path, iface).await?
+            {
+                props.merge(crate::Properties::from_dbus(extra));
+            }
+            Ok(props.without_volatile())
+        })
+        .await
+    }
+
+    /// Read the direct forward and reverse dependencies of `unit` from its properties.
+    ///
+    /// The unit is loaded (`LoadUnit`) if needed, so this works for inactive units too.
+    pub async fn dependencies(&self, unit: &str) -> Result<crate::UnitDependencies> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::traced("dependencies", Some(&unit), async {
+            let unit_path = self.inner.bus.load_unit(&unit).await?;
+            let props = self
+                .inner
+                .bus
+                .get_all_properties(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
+                .await?;
+            Ok(crate::UnitDependencies::from_lookup(unit.clone(), |kind| {
+                get_string_vec(&props, kind.property())
+            }))
+        })
+        .await
+    }
+
+    /// Walk the dependencies of `roots` breadth-first, like `systemctl list-dependencies`.
+    ///
+    /// Every unit in the graph is loaded (`LoadUnit`) to read its state, so dependencies on
+    /// inactive or missing units show up too. Each unit is expanded once, so cycles end the walk;
+    /// they are listed in `DependencyGraph::cycles`. Follow `DependencyKind::REVERSE` to list the
+    /// units a restart of the roots affects. Export the result with `DependencyGraph::to_dot`
+    /// or, with feature=`serde`, as JSON.
+    pub async fn dependency_graph(
+        &self,
+        roots: &[&str],
+        opts: crate::DependencyGraphOptions,
+    ) -> Result<crate::DependencyGraph> {
+        if roots.is_empty() {
+            return Err(Error::invalid_input("roots must not be empty"));
+        }
+        let mut root_units: Vec<String> = Vec::with_capacity(roots.len());
+        for root in roots {
+            let root = util::canonicalize_unit_name(root)?;
+            if !root_units.contains(&root) {
+                root_units.push(root);
+            }
+        }
+        let roots = root_units;
+        if opts.kinds.is_empty() {
+            return Err(Error::invalid_input("kinds must not be empty"));
+        }
+        if opts.max_nodes == 0 {
+            return Err(Error::invalid_input("max_nodes must be > 0"));
+        }
+
+        crate::telemetry::traced(
+            "dependency_graph",
+            roots.first().map(String::as_str),
+            async {
+                let mut nodes = Vec::new();
+                let mut edges = Vec::new();
+                let mut truncated = false;
+                let mut queue: std::collections::VecDeque<(String, u32)> =
+                    roots.iter().map(|root| (root.clone(), 0)).collect();
+                let mut seen: std::collections::HashSet<String> = roots.iter().cloned().collect();
+
+                while let Some((unit, depth)) = queue.pop_front() {
+                    let unit_path = self.inner.bus.load_unit(&unit).await?;
+                    let props = self
+                        .inner
+                        .bus
+                        .get_all_properties(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
+                        .await?;
+                    let state = |key| get_string(&props, key).unwrap_or_default();
+                    nodes.push(crate::DependencyNode {
+                        unit: unit.clone(),
+                        depth,
+                        load_state: LoadState::parse(&state("LoadState")),
+                        active_state: ActiveState::parse(&state("ActiveState")),
+                    });
+                    if depth >= opts.max_depth {
+                        continue;
+                    }
+
+                    for kind in &opts.kinds {
+                        for dep in get_string_vec(&props, kind.property()) {
+                            if !seen.contains(&dep) {
+                                if seen.len() >= opts.max_nodes {
+                                    truncated = true;
+                                    continue;
+                                }
+                                seen.insert(dep.clone());
+                                queue.push_back((dep.clone(), depth + 1));
+                            }
+                            edges.push(crate::DependencyEdge {
+                                from: unit.clone(),
+                                to: dep,
+                                kind: *kind,
+                            });
+                        }
+                    }
+                }
+
+                Ok(crate::DependencyGraph {
+                    roots: roots.clone(),
+                    cycles: crate::types::dependency::find_cycles(&edges),
+                    nodes,
+                    edges,
+                    truncated,
+                })
+            },
+        )
+        .await
+    }
+
     /// Fetch a snapshot of unit status via D-Bus.
     ///
     /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`).
@@ -136,193 +273,1458 @@ impl Units {
         unit_status_from_paths(&self.inner.bus, &unit, &unit_path).await
     }
 
-    /// Start a unit and return a job handle.
-    pub async fn start(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
-        self.start_like(JobKind::Start, "start", unit, mode).await
+    /// Fetch `get_status` plus the kind-specific properties of sockets (`NAccepted`, ...), timers
+    /// (`NextElapseUSecRealtime`, ...), paths (`Paths`) and mounts (`What`, `Where`, ...).
+    ///
+    /// Services are returned as `TypedUnitStatus::Service`, whose `UnitStatus` already carries
+    /// their properties; other unit types as `TypedUnitStatus::Other`.
+    pub async fn get_typed_status(&self, unit: &str) -> Result<crate::TypedUnitStatus> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::traced("get_typed_status", Some(&unit), async {
+            let unit_path = self.inner.bus.get_unit_path(&unit).await?;
+            let status = unit_status_from_paths(&self.inner.bus, &unit, &unit_path).await?;
+            let iface = match unit.rsplit_once('.').map(|(_, kind)| kind) {
+                Some("socket") => SYSTEMD_SOCKET_INTERFACE,
+                Some("timer") => SYSTEMD_TIMER_INTERFACE,
+                Some("path") => SYSTEMD_PATH_INTERFACE,
+                Some("mount") => SYSTEMD_MOUNT_INTERFACE,
+                Some("service") => return Ok(crate::TypedUnitStatus::Service(status)),
+                _ => return Ok(crate::TypedUnitStatus::Other(status)),
+            };
+            let props = optional_properties(&self.inner.bus, &unit_path, iface)
+                .await?
+                .unwrap_or_default();
+            Ok(typed_status(status, iface, &props))
+        })
+        .await
     }
 
-    /// Stop a unit and return a job handle.
-    pub async fn stop(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
-        self.start_like(JobKind::Stop, "stop", unit, mode).await
+    /// Read the PSI files (`cpu.pressure`, `memory.pressure`, `io.pressure`) of the unit's cgroup.
+    ///
+    /// Requires the unified (v2) cgroup hierarchy and a connection to the local system manager,
+    /// since the files are read from this host's cgroupfs. Returns `Error::InvalidInput` on a
+    /// user or machine connection, if the unit type has no cgroup or if the unit currently has
+    /// none (e.g. it is not running).
+    pub async fn pressure(&self, unit: &str) -> Result<crate::UnitPressure> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        self.require_local_system()?;
+
+        crate::telemetry::traced("pressure", Some(&unit), async {
+            self.pressure_sample(&unit).await?.ok_or_else(|| {
+                Error::invalid_input(format!("{unit} has no control group (not running?)"))
+            })
+        })
+        .await
     }
 
-    /// Restart a unit and return a job handle.
-    pub async fn restart(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
-        self.start_like(JobKind::Restart, "restart", unit, mode)
-            .await
+    /// `Listen` of a canonical socket unit, as systemd currently has it loaded.
+    #[cfg(any(feature = "tasks", feature = "config"))]
+    pub(crate) async fn socket_listeners(&self, unit: &str) -> Result<Vec<crate::SocketListen>> {
+        let unit_path = self.inner.bus.get_unit_path(unit).await?;
+        let props = self
+            .inner
+            .bus
+            .get_all_properties(unit_path.as_str(), SYSTEMD_SOCKET_INTERFACE)
+            .await?;
+        Ok(get_string_pairs(&props, "Listen")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(kind, address)| crate::SocketListen { kind, address })
+            .collect())
     }
 
-    /// Reload a unit and return a job handle.
-    pub async fn reload(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
-        self.start_like(JobKind::Reload, "reload", unit, mode).await
+    /// `Error::InvalidInput` unless this is the local system manager, whose `ControlGroup` paths
+    /// name directories in this host's cgroupfs.
+    pub(crate) fn require_local_system(&self) -> Result<()> {
+        if let Some(machine) = &self.inner.machine {
+            return Err(Error::invalid_input(format!(
+                "cgroup pressure is read from the local host, not from machine {machine}"
+            )));
+        }
+        if !matches!(self.inner.scope, crate::ManagerScope::System) {
+            return Err(Error::invalid_input(
+                "cgroup pressure needs a connection to the system manager",
+            ));
+        }
+        Ok(())
     }
 
-    async fn start_like(
+    /// PSI snapshot of a canonical unit; `None` if it currently has no control group.
+    pub(crate) async fn pressure_sample(&self, unit: &str) -> Result<Option<crate::UnitPressure>> {
+        let Some(control_group) = self.control_group(unit).await? else {
+            return Ok(None);
+        };
+        let unit = unit.to_string();
+        crate::runtime::unblock(move || {
+            let dir = crate::cgroup::cgroupfs_path(&control_group)?;
+            let read = |resource| crate::cgroup::read_pressure(&dir, resource);
+            Ok(Some(crate::UnitPressure {
+                unit,
+                cpu: read(crate::PressureResource::Cpu)?,
+                memory: read(crate::PressureResource::Memory)?,
+                io: read(crate::PressureResource::Io)?,
+                control_group,
+            }))
+        })
+        .await
+    }
+
+    /// Accounting properties (`MemoryCurrent`, `CPUUsageNSec`, `TasksCurrent` and their limits)
+    /// of a canonical unit, bypassing the property cache since systemd never signals them.
+    pub(crate) async fn accounting_properties(&self, unit: &str) -> Result<crate::Properties> {
+        let interface = cgroup_interface(unit).ok_or_else(|| {
+            Error::invalid_input(format!("{unit}: unit type has no resource accounting"))
+        })?;
+        let unit_path = self.inner.bus.get_unit_path(unit).await?;
+        let props = self
+            .inner
+            .bus
+            .get_all_properties_uncached(unit_path.as_str(), interface)
+            .await?;
+        Ok(crate::Properties::from_dbus(props))
+    }
+
+    /// `ControlGroup` of a canonical unit; `None` if it is empty.
+    async fn control_group(&self, unit: &str) -> Result<Option<String>> {
+        let interface = cgroup_interface(unit).ok_or_else(|| {
+            Error::invalid_input(format!("{unit}: unit type has no control group"))
+        })?;
+        let unit_path = self.inner.bus.get_unit_path(unit).await?;
+        let value = self
+            .inner
+            .bus
+            .get_property(unit_path.as_str(), interface, "ControlGroup")
+            .await?;
+        let control_group = String::try_from(value).map_err(|e| Error::ParseError {
+            context: "decode ControlGroup".to_string(),
+            sample: e.to_string(),
+        })?;
+        Ok(Some(control_group).filter(|cg| !cg.is_empty()))
+    }
+
+    /// Reconstruct the unit's start/stop/failure/restart timeline over the last `window` from
+    /// the structured records the manager writes to the journal.
+    ///
+    /// At most 10,000 manager records are scanned; beyond that the result is marked `truncated`.
+    pub async fn restart_history(
         &self,
-        kind: JobKind,
-        _action: &'static str,
         unit: &str,
-        mode: UnitStartMode,
-    ) -> Result<JobHandle> {
+        window: Duration,
+    ) -> Result<crate::RestartHistory> {
+        const PAGE_LIMIT: u32 = 1_000;
+        const MAX_RECORDS: usize = 10_000;
+
         let unit = util::canonicalize_unit_name(unit)?;
-        let mode_str = mode.as_dbus_str();
+        if window.is_zero() {
+            return Err(Error::invalid_input("window must be > 0"));
+        }
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(%unit, %mode_str, %_action, "systemd unit request");
+        crate::telemetry::traced("restart_history", Some(&unit), async {
+            let since = std::time::SystemTime::now()
+                .checked_sub(window)
+                .unwrap_or(std::time::UNIX_EPOCH);
+            let journal = crate::Journal::new(self.inner.clone());
+            let mut history = crate::RestartHistory {
+                unit: unit.clone(),
+                since,
+                events: Vec::new(),
+                truncated: false,
+            };
 
-        let job_path = match kind {
-            JobKind::Start => self.inner.bus.start_unit(&unit, mode_str).await?,
-            JobKind::Stop => self.inner.bus.stop_unit(&unit, mode_str).await?,
-            JobKind::Restart => self.inner.bus.restart_unit(&unit, mode_str).await?,
-            JobKind::Reload => self.inner.bus.reload_unit(&unit, mode_str).await?,
-        };
+            let mut scanned = 0usize;
+            let mut cursor = None;
+            loop {
+                let filter = crate::JournalFilter {
+                    since: Some(since),
+                    after_cursor: cursor.take(),
+                    limit: PAGE_LIMIT,
+                    max_bytes: 4 * 1024 * 1024,
+                    max_message_bytes: 1024,
+                    fields: Some(
+                        crate::types::history::LIFECYCLE_FIELDS
+                            .iter()
+                            .map(|f| f.to_string())
+                            .collect(),
+                    ),
+                    // Manager records carry the unit in `UNIT=`, unlike the unit's own output.
+                    matches: vec![("UNIT".to_string(), unit.clone())],
+                    ..Default::default()
+                };
+                let res = journal.query(filter).await?;
+                scanned += res.entries.len();
+                history.events.extend(
+                    res.entries
+                        .iter()
+                        .filter_map(crate::UnitLifecycleEvent::from_journal),
+                );
+
+                if !res.truncated || res.next_cursor.is_none() {
+                    break;
+                }
+                if scanned >= MAX_RECORDS {
+                    history.truncated = true;
+                    break;
+                }
+                cursor = res.next_cursor;
+            }
 
-        Ok(JobHandle {
-            unit,
-            job_path: job_path.to_string(),
-            inner: JobInner {
-                root: self.inner.clone(),
-                kind,
-            },
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                events = history.events.len(),
+                truncated = history.truncated,
+                "restart_history done"
+            );
+
+            Ok(history)
         })
+        .await
     }
-}
 
-#[derive(Clone, Debug)]
-pub(crate) enum JobKind {
-    Start,
-    Stop,
-    Restart,
-    Reload,
-}
+    /// Start a unit and return a job handle.
+    pub async fn start(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
+        self.start_like(JobKind::Start, "start", unit, mode, None)
+            .await
+    }
 
-#[derive(Clone, Debug)]
-pub(crate) struct JobInner {
-    pub(crate) root: Arc<crate::Inner>,
-    pub(crate) kind: JobKind,
-}
+    /// Start `units` in dependency waves: a unit is started only after every unit of the set it
+    /// is ordered after (`After=`, `Before=` on the other side) or requires (`Requires=`,
+    /// `Requisite=`, `BindsTo=`) has started successfully.
+    ///
+    /// Units within a wave are started together and their jobs awaited with `timeout` each.
+    /// The first wave with a failed, canceled or errored job stops the bring-up; later waves are
+    /// left untouched and reported without results. Dependencies outside the set are left to
+    /// systemd. Returns `Error::InvalidInput` if the ordering among the set has a cycle.
+    pub async fn start_ordered(
+        &self,
+        units: &[&str],
+        timeout: Duration,
+    ) -> Result<crate::OrderedStartReport> {
+        let set = ordered_start_set(units, timeout)?;
+        crate::telemetry::traced("start_ordered", None, async {
+            let mut run =
+                OrderedStart::new(self.clone(), self.start_waves_for(&set).await?, timeout);
+            while run.next_event().await.is_some() {}
+            let report = run.report();
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                units = set.len(),
+                waves = report.waves.len(),
+                failed_wave = ?report.failed_wave,
+                "start_ordered done"
+            );
+
+            Ok(report)
+        })
+        .await
+    }
 
-impl JobHandle {
-    /// Wait for the job to complete or return `Error::JobTimeout`.
+    /// Like `start_ordered`, but report progress as a `Stream` of `OrderedStartEvent`s.
     ///
-    /// Implementation prefers `JobRemoved` signals, with a bounded polling fallback.
-    pub async fn wait(&self, timeout: Duration) -> Result<JobOutcome> {
-        if timeout == Duration::from_secs(0) {
-            return Err(Error::invalid_input("timeout must be > 0"));
-        }
-        self.inner
-            .wait_job(&self.unit, &self.job_path, timeout)
-            .await
+    /// Waves are planned (and a cycle rejected) before this returns; units are started only
+    /// while the stream is polled. The last event is `OrderedStartEvent::Finished` with the same
+    /// report `start_ordered` returns. Dropping the stream stops starting further waves but does
+    /// not cancel already queued jobs.
+    pub async fn start_ordered_progress(
+        &self,
+        units: &[&str],
+        timeout: Duration,
+    ) -> Result<crate::OrderedStartProgress> {
+        let set = ordered_start_set(units, timeout)?;
+        let waves = crate::telemetry::traced("start_ordered_progress", None, async {
+            self.start_waves_for(&set).await
+        })
+        .await?;
+        let run = OrderedStart::new(self.clone(), waves, timeout);
+        Ok(crate::OrderedStartProgress {
+            stream: Box::pin(futures_util::stream::unfold(run, |mut run| async move {
+                let event = run.next_event().await?;
+                Some((event, run))
+            })),
+        })
     }
-}
 
-impl JobInner {
-    async fn wait_job(&self, unit: &str, job_path: &str, timeout: Duration) -> Result<JobOutcome> {
-        #[cfg(feature = "tracing")]
-        tracing::debug!(%unit, %job_path, ?timeout, "wait_job start");
+    /// Restart `units` with at most `opts.concurrency` restart jobs in flight, waiting for each
+    /// job with `opts.per_unit_timeout`.
+    ///
+    /// Units are restarted in the given order (shorthand names canonicalized, duplicates
+    /// dropped) and their results reported in that order. A failed, canceled or timed-out job
+    /// does not abort the batch unless `opts.stop_on_failure` is set, in which case units not yet
+    /// restarted are reported in `BatchReport.skipped`.
+    pub async fn restart_many(
+        &self,
+        units: &[&str],
+        opts: crate::BatchOptions,
+    ) -> Result<crate::BatchReport> {
+        let set = ordered_start_set(units, opts.per_unit_timeout)?;
+        if opts.concurrency == 0 {
+            return Err(Error::invalid_input("concurrency must be > 0"));
+        }
 
-        let manager = self.root.bus.manager_proxy().await?;
+        crate::telemetry::traced("restart_many", None, async {
+            let mut slots: Vec<Option<crate::BatchUnitResult>> = vec![None; set.len()];
+            let mut pending = set.into_iter().enumerate();
+            let mut running = futures_util::stream::FuturesUnordered::new();
+            let mut failed = false;
+
+            loop {
+                while running.len() < opts.concurrency && !(failed && opts.stop_on_failure) {
+                    let Some((pos, unit)) = pending.next() else {
+                        break;
+                    };
+                    let units = self.clone();
+                    running.push(async move {
+                        let job = units.restart(&unit, UnitStartMode::Replace).await;
+                        let (outcome, error) =
+                            match async { job?.wait(opts.per_unit_timeout).await }.await {
+                                Ok(outcome) => (Some(outcome), None),
+                                Err(e) => (None, Some(e.to_string())),
+                            };
+                        (
+                            pos,
+                            crate::BatchUnitResult {
+                                unit,
+                                outcome,
+                                error,
+                            },
+                        )
+                    });
+                }
+                let Some((pos, result)) = running.next().await else {
+                    break;
+                };
+                failed |= !result.is_success();
+                slots[pos] = Some(result);
+            }
 
-        let mut signals = match manager.receive_signal("JobRemoved").await {
-            Ok(s) => Some(futures_util::StreamExt::fuse(s)),
-            Err(_) => None,
-        };
+            let report = crate::BatchReport {
+                results: slots.into_iter().flatten().collect(),
+                skipped: pending.map(|(_, unit)| unit).collect(),
+            };
 
-        let mut job_result: Option<String> = None;
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                units = report.results.len() + report.skipped.len(),
+                failed = report.failures().count(),
+                skipped = report.skipped.len(),
+                "restart_many done"
+            );
 
-        let mut jitter = poll_jitter_seed(job_path);
-        let mut poll_interval = apply_jitter(
-            self.root.opts.job_poll_initial,
-            self.root.opts.job_poll_max,
-            &mut jitter,
-        );
-        let mut poll_timer = futures_util::FutureExt::fuse(crate::runtime::sleep(poll_interval));
-        let mut deadline = futures_util::FutureExt::fuse(crate::runtime::sleep(timeout));
+            Ok(report)
+        })
+        .await
+    }
 
-        if !self.root.bus.job_exists(job_path).await? {
-            let status = Units::new(self.root.clone()).get_status(unit).await?;
-            return Ok(infer_outcome(&self.kind, &status, None));
+    /// Restart a unit, wait for the job, then watch it for `opts.settle` to tell a unit that
+    /// came up and stays up from one that crash-loops right after starting.
+    ///
+    /// The settle window is failed as soon as the unit leaves `active` or `NRestarts` goes up
+    /// (an automatic restart seen while waiting for the job counts too). `opts.job_timeout`
+    /// bounds only the job wait; the total time can reach `job_timeout + settle`.
+    ///
+    /// With feature=`probes`, `opts.probes` then run in order, each for up to
+    /// `opts.probe_timeout`; the first one that does not succeed yields
+    /// `VerifiedOutcome::NotReady`.
+    pub async fn restart_and_verify(
+        &self,
+        unit: &str,
+        opts: crate::VerifyOptions,
+    ) -> Result<crate::VerifiedOutcome> {
+        if opts.job_timeout.is_zero() || opts.settle.is_zero() {
+            return Err(Error::invalid_input("job_timeout and settle must be > 0"));
         }
-
-        loop {
-            if let Some(sig) = &mut signals {
-                futures_util::select! {
-                    _ = deadline => {
-                        return Err(Error::JobTimeout { unit: unit.to_string(), timeout });
-                    }
-                    _ = poll_timer => {
-                        if !self.root.bus.job_exists(job_path).await? {
-                            break;
-                        }
-                        poll_interval = next_poll_interval(poll_interval, self.root.opts.job_poll_max, &mut jitter);
-                        poll_timer =
-                            futures_util::FutureExt::fuse(crate::runtime::sleep(poll_interval));
-                    }
-                    msg = sig.next() => {
-                        let Some(msg) = msg else {
-                            signals = None;
-                            continue;
-                        };
-                        if let Some(result) = decode_job_removed(job_path, msg)? {
-                            job_result = Some(result);
-                            break;
-                        }
-                    }
-                }
-            } else {
-                futures_util::select! {
-                    _ = deadline => {
-                        return Err(Error::JobTimeout { unit: unit.to_string(), timeout });
-                    }
-                    _ = poll_timer => {
-                        if !self.root.bus.job_exists(job_path).await? {
-                            break;
-                        }
-                        poll_interval = next_poll_interval(poll_interval, self.root.opts.job_poll_max, &mut jitter);
-                        poll_timer =
-                            futures_util::FutureExt::fuse(crate::runtime::sleep(poll_interval));
-                    }
-                }
+        #[cfg(feature = "probes")]
+        {
+            if !opts.probes.is_empty() && opts.probe_timeout.is_zero() {
+                return Err(Error::invalid_input("probe_timeout must be > 0"));
+            }
+            for probe in &opts.probes {
+                probe.validate()?;
             }
         }
+        let job = self.restart(unit, opts.mode.clone()).await?;
+        let outcome = job.wait(opts.job_timeout).await?;
+
+        crate::telemetry::traced("restart_and_verify", Some(&job.unit), async {
+            let JobOutcome::Success {
+                unit_status,
+                warnings,
+            } = outcome
+            else {
+                return Ok(crate::VerifiedOutcome::NotStarted { outcome });
+            };
+            let restarted = warnings.iter().find_map(|w| match w {
+                crate::JobWarning::RestartedDuringWait { n_restarts } => Some(*n_restarts),
+            });
+            if let Some(restarts) = restarted {
+                return Ok(crate::VerifiedOutcome::StartedThenFailed {
+                    after: Duration::ZERO,
+                    restarts,
+                    unit_status,
+                });
+            }
 
-        let status = Units::new(self.root.clone()).get_status(unit).await?;
+            let baseline = unit_status.n_restarts;
+            let outcome = JobOutcome::Success {
+                unit_status,
+                warnings,
+            };
+            let verified = match job
+                .inner
+                .hold_stable(&job.unit, opts.settle, outcome)
+                .await?
+            {
+                JobOutcome::Failed {
+                    unit_status,
+                    reason: FailureHint::Unstable { after, .. },
+                } => crate::VerifiedOutcome::StartedThenFailed {
+                    after,
+                    restarts: match (baseline, unit_status.n_restarts) {
+                        (Some(before), Some(now)) => restart_delta(before, now),
+                        _ => 0,
+                    },
+                    unit_status,
+                },
+                JobOutcome::Success { unit_status, .. } => crate::VerifiedOutcome::Stable {
+                    settled_for: opts.settle,
+                    unit_status,
+                },
+                outcome => crate::VerifiedOutcome::NotStarted { outcome },
+            };
+            #[cfg(feature = "probes")]
+            let verified = match verified {
+                crate::VerifiedOutcome::Stable {
+                    settled_for,
+                    unit_status,
+                } => run_probes(&opts, settled_for, unit_status).await?,
+                other => other,
+            };
 
-        #[cfg(feature = "tracing")]
-        tracing::debug!(%unit, %job_path, job_result = job_result.as_deref().unwrap_or(""), "wait_job done");
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %job.unit, stable = verified.is_stable(), "restart verified");
 
-        Ok(infer_outcome(&self.kind, &status, job_result.as_deref()))
+            Ok(verified)
+        })
+        .await
     }
-}
-
-fn next_poll_interval(current: Duration, max: Duration, seed: &mut u64) -> Duration {
-    let doubled = current.saturating_mul(2);
-    let base = if doubled > max { max } else { doubled };
-    apply_jitter(base, max, seed)
-}
 
-fn apply_jitter(base: Duration, max: Duration, seed: &mut u64) -> Duration {
-    if base >= max {
-        return base;
+    /// Start an application group through its target and wait for the target's and every
+    /// member's job.
+    ///
+    /// Members are the units the target `Wants=`/`Requires=` plus those declaring `PartOf=` it
+    /// (see `Config::define_app_target`). `timeout` bounds the whole group wait; a member still
+    /// busy when it runs out is reported with a timeout error rather than failing the call.
+    pub async fn start_group(&self, target: &str, timeout: Duration) -> Result<crate::GroupReport> {
+        self.group_job(JobKind::Start, "start", target, timeout)
+            .await
     }
 
-    let base_us = duration_to_micros_saturating(base);
-    let max_us = duration_to_micros_saturating(max);
+    /// Stop an application group through its target; members follow via `PartOf=`.
+    /// See `start_group`.
+    pub async fn stop_group(&self, target: &str, timeout: Duration) -> Result<crate::GroupReport> {
+        self.group_job(JobKind::Stop, "stop", target, timeout).await
+    }
 
-    let amplitude = base_us / 10;
-    if amplitude == 0 {
-        return base;
+    /// Restart an application group through its target; members follow via `PartOf=`.
+    /// See `start_group`.
+    pub async fn restart_group(
+        &self,
+        target: &str,
+        timeout: Duration,
+    ) -> Result<crate::GroupReport> {
+        self.group_job(JobKind::Restart, "restart", target, timeout)
+            .await
     }
 
-    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
-    let jitter = *seed % amplitude.saturating_add(1);
+    async fn group_job(
+        &self,
+        kind: JobKind,
+        action: &'static str,
+        target: &str,
+        timeout: Duration,
+    ) -> Result<crate::GroupReport> {
+        let target = util::canonicalize_unit_name(target)?;
+        if !target.ends_with(".target") {
+            return Err(Error::invalid_input(format!(
+                "group must be a .target unit, got {target}"
+            )));
+        }
+        if timeout.is_zero() {
+            return Err(Error::invalid_input("timeout must be > 0"));
+        }
 
-    let us = std::cmp::min(base_us.saturating_add(jitter), max_us);
-    Duration::from_micros(us)
-}
+        let started = std::time::Instant::now();
+        let job = self
+            .start_like(kind.clone(), action, &target, UnitStartMode::Replace, None)
+            .await?;
+        crate::telemetry::traced("group_job", Some(&target), async {
+            let outcome = job.wait(timeout).await?;
+            let members = self.group_members(&target).await?;
+
+            let mut results = Vec::with_capacity(members.len());
+            for unit in members {
+                let remaining = timeout.saturating_sub(started.elapsed());
+                let (outcome, error) = match self.member_job(&kind, &unit, remaining).await {
+                    Ok(outcome) => (Some(outcome), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                results.push(crate::BatchUnitResult {
+                    unit,
+                    outcome,
+                    error,
+                });
+            }
 
-fn duration_to_micros_saturating(d: Duration) -> u64 {
-    u64::try_from(d.as_micros()).unwrap_or(u64::MAX)
+            let report = crate::GroupReport {
+                target: target.clone(),
+                outcome,
+                members: results,
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %target,
+                action,
+                members = report.members.len(),
+                success = report.is_success(),
+                "group job done"
+            );
+
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Units the target pulls in (`Wants=`/`Requires=`) or that are `PartOf=` it, sorted.
+    async fn group_members(&self, target: &str) -> Result<Vec<String>> {
+        let unit_path = self.inner.bus.load_unit(target).await?;
+        let props = self
+            .inner
+            .bus
+            .get_all_properties_uncached(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
+            .await?;
+        let members = ["Wants", "Requires", "ConsistsOf"]
+            .into_iter()
+            .flat_map(|key| get_string_vec(&props, key))
+            .filter(|unit| unit != target)
+            .collect::<BTreeSet<_>>();
+        Ok(members.into_iter().collect())
+    }
+
+    /// Wait for the job queued for a group member (if any) and classify its end state.
+    async fn member_job(
+        &self,
+        kind: &JobKind,
+        unit: &str,
+        timeout: Duration,
+    ) -> Result<JobOutcome> {
+        let unit_path = self.inner.bus.load_unit(unit).await?;
+        let job = self
+            .inner
+            .bus
+            .get_property(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE, "Job")
+            .await?;
+        // `(0, "/")` when no job is queued; `wait` then reports the current unit state.
+        let (id, job_path) =
+            <(u32, OwnedObjectPath)>::try_from(job).map_err(|e| Error::IoError {
+                context: format!("decode Job property of {unit}: {e}"),
+            })?;
+        if id != 0 && timeout.is_zero() {
+            return Err(Error::JobTimeout {
+                unit: unit.to_string(),
+                timeout,
+            });
+        }
+
+        let handle = JobHandle {
+            unit: unit.to_string(),
+            job_path: job_path.to_string(),
+            inner: JobInner {
+                root: self.inner.clone(),
+                kind: kind.clone(),
+                invocation_before: None,
+            },
+        };
+        // A member without a job settled before the target did; any positive bound works.
+        handle.wait(timeout.max(Duration::from_millis(1))).await
+    }
+
+    /// Plan start waves for the canonical unit names in `set` from their dependency properties.
+    async fn start_waves_for(&self, set: &[String]) -> Result<Vec<Vec<String>>> {
+        let mut preds = HashMap::<String, BTreeSet<String>>::new();
+        for unit in set {
+            let unit_path = self.inner.bus.load_unit(unit).await?;
+            let props = self
+                .inner
+                .bus
+                .get_all_properties(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
+                .await?;
+            for key in ["After", "Requires", "Requisite", "BindsTo"] {
+                for dep in get_string_vec(&props, key) {
+                    if dep != *unit && set.contains(&dep) {
+                        preds.entry(unit.clone()).or_default().insert(dep);
+                    }
+                }
+            }
+            for dep in get_string_vec(&props, "Before") {
+                if dep != *unit && set.contains(&dep) {
+                    preds.entry(dep).or_default().insert(unit.clone());
+                }
+            }
+        }
+        start_waves(set, &preds)
+    }
+
+    /// Stop a unit and return a job handle.
+    pub async fn stop(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
+        self.start_like(JobKind::Stop, "stop", unit, mode, None)
+            .await
+    }
+
+    /// Send `signal` to the processes of a unit selected by `who` (`KillUnit`), e.g.
+    /// `Signal::Hup` to `KillWho::Main` to make a daemon reopen its logs. The unit's state is not
+    /// changed by systemd itself.
+    ///
+    /// `KillWho::Main` is only accepted for services and `KillWho::Control` for units that run
+    /// commands (service, socket, mount, swap); other combinations are `Error::InvalidInput`.
+    pub async fn kill(&self, unit: &str, who: crate::KillWho, signal: crate::Signal) -> Result<()> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        who.validate_for(&unit)?;
+        crate::telemetry::audited(&self.inner, "kill", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, who = who.as_str(), %signal, "kill");
+            self.inner
+                .bus
+                .kill_unit(&unit, who.as_str(), signal.number())
+                .await
+        })
+        .await
+    }
+
+    /// Clear `unit`'s failed state and its start-rate counter (`systemctl reset-failed`).
+    ///
+    /// A unit that hit `StartLimitBurst=` refuses further starts until this is called (or the
+    /// interval passes), so `start()` keeps yielding failed jobs. Resetting a unit that is not
+    /// failed is a no-op.
+    pub async fn reset_failed(&self, unit: &str) -> Result<()> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::audited(&self.inner, "reset_failed", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, "reset_failed");
+            self.inner.bus.reset_failed_unit(&unit).await
+        })
+        .await
+    }
+
+    /// `reset_failed` for every unit (`systemctl reset-failed` without arguments).
+    pub async fn reset_failed_all(&self) -> Result<()> {
+        crate::telemetry::audited(&self.inner, "reset_failed_all", None, async {
+            #[cfg(feature = "tracing")]
+            tracing::info!("reset_failed_all");
+            self.inner.bus.reset_failed().await
+        })
+        .await
+    }
+
+    /// Snapshot `unit`'s cgroup accounting (CPU time, memory, tasks, block IO).
+    ///
+    /// Read fresh from systemd on every call (never from the property cache). Only unit types
+    /// with a cgroup (service, socket, scope, slice, mount, swap) are accepted.
+    pub async fn resource_usage(&self, unit: &str) -> Result<crate::UnitResources> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::traced("resource_usage", Some(&unit), async {
+            let props = self.accounting_properties(&unit).await?;
+            Ok(crate::UnitResources::from_properties(&props))
+        })
+        .await
+    }
+
+    /// List every process in `unit`'s cgroup tree, including control and helper processes and
+    /// those in sub-cgroups (`Manager.GetUnitProcesses`, like `systemctl status` shows).
+    ///
+    /// Returns an empty list for units without running processes.
+    pub async fn processes(&self, unit: &str) -> Result<Vec<crate::UnitProcess>> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::traced("unit_processes", Some(&unit), async {
+            let items = self.inner.bus.get_unit_processes(&unit).await?;
+            Ok(items
+                .into_iter()
+                .map(crate::UnitProcess::from_dbus)
+                .collect())
+        })
+        .await
+    }
+
+    /// Freeze all processes of `unit` with the cgroup freezer (`systemctl freeze`).
+    ///
+    /// Returns once the unit is frozen; it stays `active` but its processes no longer run until
+    /// `thaw`. Requires systemd >= 246 and the unified cgroup hierarchy; otherwise (or for unit
+    /// types without a cgroup) this returns `Error::BackendUnavailable`.
+    pub async fn freeze(&self, unit: &str) -> Result<()> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::audited(&self.inner, "freeze", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, "freeze");
+            self.inner
+                .bus
+                .freeze_unit(&unit, true)
+                .await
+                .map_err(freezer_unavailable)
+        })
+        .await
+    }
+
+    /// Resume a unit frozen by `freeze` (`systemctl thaw`). Thawing a running unit is a no-op.
+    pub async fn thaw(&self, unit: &str) -> Result<()> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        crate::telemetry::audited(&self.inner, "thaw", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, "thaw");
+            self.inner
+                .bus
+                .freeze_unit(&unit, false)
+                .await
+                .map_err(freezer_unavailable)
+        })
+        .await
+    }
+
+    /// Current `FreezerState` of `unit`; `Error::BackendUnavailable` when systemd predates the
+    /// freezer (no such property).
+    pub async fn freezer_state(&self, unit: &str) -> Result<crate::FreezerState> {
+        let props = self.get_unit_properties(unit).await?;
+        props
+            .get_opt_string("FreezerState")
+            .map(|s| crate::FreezerState::parse(&s))
+            .ok_or_else(|| Error::BackendUnavailable {
+                backend: "systemd_freezer",
+                detail: "FreezerState requires systemd >= 246".to_string(),
+            })
+    }
+
+    /// Cap the log priority systemd keeps from a transient `unit` (`LogLevelMax=`) and return the
+    /// previous cap (`None` if unset).
+    ///
+    /// Use e.g. `Priority::Info` to silence a chatty task's debug output. systemd only accepts
+    /// `LogLevelMax=` through `SetUnitProperties` for transient units (`Tasks`, `systemd-run`);
+    /// other units are rejected with `Error::InvalidInput` (write a drop-in and restart them
+    /// instead). systemd cannot unset the value at runtime; `Priority::Debug` (keep everything)
+    /// restores the default behaviour. Only units with an execution context (service, socket,
+    /// mount, swap) accept it.
+    pub async fn set_log_level_max(
+        &self,
+        unit: &str,
+        level: crate::Priority,
+    ) -> Result<Option<crate::Priority>> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        let interface = match unit.rsplit_once('.').map(|(_, t)| t) {
+            Some("service" | "socket" | "mount" | "swap") => type_interface(&unit),
+            _ => None,
+        }
+        .ok_or_else(|| Error::invalid_input(format!("{unit} does not support LogLevelMax=")))?;
+
+        crate::telemetry::audited(&self.inner, "set_log_level_max", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            let unit_path = self.inner.bus.load_unit(&unit).await?;
+            let transient = self
+                .inner
+                .bus
+                .get_property(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE, "Transient")
+                .await?;
+            let transient = bool::try_from(transient).map_err(|e| Error::IoError {
+                context: format!("decode Transient property of {unit}: {e}"),
+            })?;
+            if !transient {
+                return Err(Error::invalid_input(format!(
+                    "{unit} is not transient; systemd only changes LogLevelMax= of transient \
+                     units at runtime (use a drop-in and restart it instead)"
+                )));
+            }
+
+            let previous = self
+                .inner
+                .bus
+                .get_property(unit_path.as_str(), interface, "LogLevelMax")
+                .await?;
+            // -1 when unset.
+            let previous = i32::try_from(previous).map_err(|e| Error::IoError {
+                context: format!("decode LogLevelMax property of {unit}: {e}"),
+            })?;
+            let previous = u8::try_from(previous)
+                .ok()
+                .and_then(crate::Priority::from_number);
+
+            let value = owned_value("LogLevelMax", i32::from(level.number()))?;
+            self.inner
+                .bus
+                .set_unit_properties(&unit, true, vec![("LogLevelMax".to_string(), value)])
+                .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, ?previous, %level, "set_log_level_max");
+
+            Ok(previous)
+        })
+        .await
+    }
+
+    /// Stop a unit, escalating to `SIGKILL` if it does not stop in time.
+    ///
+    /// A regular stop job is queued first, so `ExecStop=`, `KillSignal=` and a pending
+    /// `Restart=` are handled by systemd as usual. If the job has not finished after
+    /// `policy.term_wait`, `SIGKILL` is sent to all the unit's processes (`KillUnit`) and the job
+    /// gets another `policy.kill_wait`. The unit's `TimeoutStopSec=` is left untouched; set
+    /// `term_wait` below it for the escalation to happen here rather than in systemd.
+    /// Returns `Error::Timeout` if the job is still running after both waits.
+    pub async fn stop_graceful(
+        &self,
+        unit: &str,
+        policy: crate::EscalationPolicy,
+    ) -> Result<crate::GracefulStopReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if policy.term_wait.is_zero() || policy.kill_wait.is_zero() {
+            return Err(Error::invalid_input("term_wait and kill_wait must be > 0"));
+        }
+
+        crate::telemetry::audited(&self.inner, "stop_graceful", Some(&unit), async {
+            let lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            let mut report = crate::GracefulStopReport {
+                unit: unit.clone(),
+                level: crate::StopLevel::AlreadyStopped,
+                elapsed: Duration::ZERO,
+                job_outcome: None,
+            };
+            if processes_gone(&self.get_status(&unit).await?) {
+                return Ok(report);
+            }
+
+            let started = std::time::Instant::now();
+            let job = self
+                .start_like(
+                    JobKind::Stop,
+                    "stop",
+                    &unit,
+                    UnitStartMode::Replace,
+                    Some(&lock),
+                )
+                .await?;
+            report.level = crate::StopLevel::Term;
+            let outcome = match job.wait(policy.term_wait).await {
+                Err(Error::JobTimeout { .. }) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(%unit, term_wait = ?policy.term_wait, "escalating to SIGKILL");
+
+                    self.inner
+                        .bus
+                        .kill_unit(&unit, "all", crate::Signal::Kill.number())
+                        .await?;
+                    report.level = crate::StopLevel::Kill;
+                    match job.wait(policy.kill_wait).await {
+                        Err(Error::JobTimeout { .. }) => {
+                            return Err(Error::Timeout {
+                                action: "stop_graceful",
+                                timeout: policy.term_wait.saturating_add(policy.kill_wait),
+                            });
+                        }
+                        other => other?,
+                    }
+                }
+                other => other?,
+            };
+            report.elapsed = started.elapsed();
+            report.job_outcome = Some(outcome);
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(%unit, level = ?report.level, elapsed = ?report.elapsed, "stop_graceful done");
+
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Restart a unit and return a job handle.
+    pub async fn restart(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
+        self.start_like(JobKind::Restart, "restart", unit, mode, None)
+            .await
+    }
+
+    /// Reload a unit and return a job handle.
+    pub async fn reload(&self, unit: &str, mode: UnitStartMode) -> Result<JobHandle> {
+        self.start_like(JobKind::Reload, "reload", unit, mode, None)
+            .await
+    }
+
+    /// Enable `unit` (`org.freedesktop.systemd1.Manager.EnableUnitFiles`, like
+    /// `systemctl enable`), creating the symlinks listed in its `[Install]` section.
+    ///
+    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`). Unlike `systemctl`,
+    /// the manager is not reloaded; call `Manager::daemon_reload` when `changes` is non-empty.
+    pub async fn enable(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileEnableOptions,
+    ) -> Result<crate::UnitFileEnableReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+
+        crate::telemetry::audited(&self.inner, "enable_unit", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, runtime = opts.runtime, force = opts.force, "enable_unit");
+
+            let files = vec![unit.clone()];
+            let (carries_install_info, changes) = self
+                .inner
+                .bus
+                .enable_unit_files(&files, opts.runtime, opts.force)
+                .await?;
+
+            Ok(crate::UnitFileEnableReport {
+                carries_install_info,
+                changes: unit_file_changes(changes),
+            })
+        })
+        .await
+    }
+
+    /// Disable `unit` (`org.freedesktop.systemd1.Manager.DisableUnitFiles`).
+    ///
+    /// `unit` is canonicalized. The manager is not reloaded (see `enable`).
+    pub async fn disable(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileDisableOptions,
+    ) -> Result<crate::UnitFileDisableReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+
+        crate::telemetry::audited(&self.inner, "disable_unit", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, runtime = opts.runtime, "disable_unit");
+
+            let files = vec![unit.clone()];
+            let changes = self
+                .inner
+                .bus
+                .disable_unit_files(&files, opts.runtime)
+                .await?;
+
+            Ok(crate::UnitFileDisableReport {
+                changes: unit_file_changes(changes),
+            })
+        })
+        .await
+    }
+
+    /// Mask `unit` (`org.freedesktop.systemd1.Manager.MaskUnitFiles`): link it to `/dev/null`
+    /// so it cannot be started, not even as a dependency.
+    ///
+    /// `unit` is canonicalized. The manager is not reloaded (see `enable`); a running unit
+    /// keeps running until stopped.
+    pub async fn mask(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileMaskOptions,
+    ) -> Result<crate::UnitFileChangeReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+
+        crate::telemetry::audited(&self.inner, "mask_unit", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, runtime = opts.runtime, force = opts.force, "mask_unit");
+
+            let files = vec![unit.clone()];
+            let changes = self
+                .inner
+                .bus
+                .mask_unit_files(&files, opts.runtime, opts.force)
+                .await?;
+
+            Ok(crate::UnitFileChangeReport {
+                changes: unit_file_changes(changes),
+            })
+        })
+        .await
+    }
+
+    /// Unmask `unit` (`org.freedesktop.systemd1.Manager.UnmaskUnitFiles`).
+    ///
+    /// `unit` is canonicalized. The manager is not reloaded (see `enable`).
+    pub async fn unmask(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileUnmaskOptions,
+    ) -> Result<crate::UnitFileChangeReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+
+        crate::telemetry::audited(&self.inner, "unmask_unit", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, runtime = opts.runtime, "unmask_unit");
+
+            let files = vec![unit.clone()];
+            let changes = self
+                .inner
+                .bus
+                .unmask_unit_files(&files, opts.runtime)
+                .await?;
+
+            Ok(crate::UnitFileChangeReport {
+                changes: unit_file_changes(changes),
+            })
+        })
+        .await
+    }
+
+    /// Enable or disable `unit` as the preset policy says
+    /// (`org.freedesktop.systemd1.Manager.PresetUnitFilesWithMode`, like `systemctl preset`).
+    ///
+    /// `unit` is canonicalized. The manager is not reloaded (see `enable`).
+    pub async fn preset(
+        &self,
+        unit: &str,
+        opts: crate::UnitFilePresetOptions,
+    ) -> Result<crate::UnitFileEnableReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        let mode = opts.mode.as_str();
+
+        crate::telemetry::audited(&self.inner, "preset_unit", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, mode, runtime = opts.runtime, force = opts.force, "preset_unit");
+
+            let files = vec![unit.clone()];
+            let (carries_install_info, changes) = self
+                .inner
+                .bus
+                .preset_unit_files(&files, mode, opts.runtime, opts.force)
+                .await?;
+
+            Ok(crate::UnitFileEnableReport {
+                carries_install_info,
+                changes: unit_file_changes(changes),
+            })
+        })
+        .await
+    }
+
+    /// Converge a unit to `desired`, doing only the enable/disable/start/stop that is needed.
+    ///
+    /// Enablement is read from `UnitFileState` (`enabled`/`enabled-runtime` count as enabled);
+    /// activity from `ActiveState` (`active`/`activating`/`reloading` count as active). After an
+    /// enablement change the manager is reloaded, like `systemctl enable`. A failed start/stop job is
+    /// reported in `job_outcome`, not as an error.
+    pub async fn ensure(
+        &self,
+        unit: &str,
+        desired: crate::DesiredUnitState,
+    ) -> Result<crate::EnsureReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if desired.active.is_some() && desired.timeout == Duration::from_secs(0) {
+            return Err(Error::invalid_input("timeout must be > 0"));
+        }
+
+        crate::telemetry::audited(&self.inner, "ensure", Some(&unit), async {
+            let lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            let unit_path = self.inner.bus.load_unit(&unit).await?;
+            let props = self.get_unit_properties_by_path(unit_path.as_str()).await?;
+
+            let mut report = crate::EnsureReport {
+                unit: unit.clone(),
+                changed: false,
+                enablement_changed: false,
+                active_changed: false,
+                job_outcome: None,
+            };
+
+            if let Some(enabled) = desired.enabled {
+                let current = props.get_str("UnitFileState").unwrap_or("");
+                if is_enabled_state(current) != enabled {
+                    report.enablement_changed = self.set_enablement(&unit, enabled).await?;
+                }
+            }
+
+            if let Some(active) = desired.active {
+                let current = ActiveState::parse(props.get_str("ActiveState").unwrap_or(""));
+                if is_active_like(&current) != active {
+                    let kind = if active {
+                        JobKind::Start
+                    } else {
+                        JobKind::Stop
+                    };
+                    let action = if active { "start" } else { "stop" };
+                    let job = self
+                        .start_like(kind, action, &unit, desired.mode, Some(&lock))
+                        .await?;
+                    report.job_outcome = Some(job.wait(desired.timeout).await?);
+                    report.active_changed = true;
+                }
+            }
+
+            report.changed = report.enablement_changed || report.active_changed;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                changed = report.changed,
+                enablement_changed = report.enablement_changed,
+                active_changed = report.active_changed,
+                "ensure done"
+            );
+
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Enable/disable `unit` and reload the manager; returns whether any symlink changed.
+    async fn set_enablement(&self, unit: &str, enabled: bool) -> Result<bool> {
+        let files = vec![unit.to_string()];
+        let changes = if enabled {
+            self.inner
+                .bus
+                .enable_unit_files(&files, false, false)
+                .await?
+                .1
+        } else {
+            self.inner.bus.disable_unit_files(&files, false).await?
+        };
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        self.inner.bus.daemon_reload().await?;
+        Ok(true)
+    }
+
+    /// Queue a start/stop/restart/reload job. `held` is the caller's lock on the unit, if it
+    /// already has one (composite operations); otherwise the lock is taken here.
+    async fn start_like(
+        &self,
+        kind: JobKind,
+        action: &'static str,
+        unit: &str,
+        mode: UnitStartMode,
+        held: Option<&crate::locks::UnitLockGuard<'_>>,
+    ) -> Result<JobHandle> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        let mode_str = mode.as_dbus_str();
+
+        crate::telemetry::audited(&self.inner, action, Some(&unit), async {
+            let _lock = match held {
+                Some(lock) if lock.covers(&unit) => None,
+                _ => Some(crate::locks::lock_unit(&self.inner, &unit).await?),
+            };
+            #[cfg(feature = "tracing")]
+            tracing::info!(%unit, %mode_str, "systemd unit request");
+
+            let invocation_before = match kind {
+                JobKind::Start | JobKind::Restart => self.invocation_id(&unit).await,
+                JobKind::Stop | JobKind::Reload => None,
+            };
+            let job_path = match kind {
+                JobKind::Start => self.inner.bus.start_unit(&unit, mode_str).await?,
+                JobKind::Stop => self.inner.bus.stop_unit(&unit, mode_str).await?,
+                JobKind::Restart => self.inner.bus.restart_unit(&unit, mode_str).await?,
+                JobKind::Reload => self.inner.bus.reload_unit(&unit, mode_str).await?,
+            };
+            crate::telemetry::record_job_path(job_path.as_str());
+
+            Ok(JobHandle {
+                unit: unit.clone(),
+                job_path: job_path.to_string(),
+                inner: JobInner {
+                    root: self.inner.clone(),
+                    kind,
+                    invocation_before,
+                },
+            })
+        })
+        .await
+    }
+}
+
+impl Units {
+    /// Best-effort `InvocationID` of `unit` from the same (cacheable) `GetAll` that
+    /// `get_status` reads; `None` on error, `Some(None)` if the unit has no invocation.
+    async fn invocation_id(&self, unit: &str) -> Option<Option<String>> {
+        let props = self
+            .inner
+            .bus
+            .get_all_properties(&util::unit_object_path(unit), SYSTEMD_UNIT_INTERFACE)
+            .await
+            .ok()?;
+        Some(get_invocation_id(&props))
+    }
+}
+
+fn unit_file_changes(changes: crate::bus::UnitFileChanges) -> Vec<crate::UnitFileChange> {
+    changes
+        .into_iter()
+        .map(crate::UnitFileChange::from_dbus)
+        .collect()
+}
+
+/// `FreezeUnit`/`ThawUnit` missing (systemd < 246) or refused for lack of a cgroup freezer.
+fn freezer_unavailable(e: Error) -> Error {
+    match e {
+        Error::DbusError { name, message }
+            if name.contains("UnknownMethod")
+                || name.contains("UnknownMember")
+                || name.contains("NotSupported") =>
+        {
+            Error::BackendUnavailable {
+                backend: "systemd_freezer",
+                detail: format!("{name}: {message}"),
+            }
+        }
+        e => e,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum JobKind {
+    Start,
+    Stop,
+    Restart,
+    Reload,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct JobInner {
+    pub(crate) root: Arc<crate::Inner>,
+    pub(crate) kind: JobKind,
+    /// `InvocationID` read just before a start/restart job was queued (`Some(None)`: the unit
+    /// had none); `None` for other jobs or if it could not be read.
+    pub(crate) invocation_before: Option<Option<String>>,
+}
+
+impl JobHandle {
+    /// Wait for the job to complete or return `Error::JobTimeout`.
+    ///
+    /// Implementation prefers `JobRemoved` signals, with a bounded polling fallback.
+    pub async fn wait(&self, timeout: Duration) -> Result<JobOutcome> {
+        if timeout == Duration::from_secs(0) {
+            return Err(Error::invalid_input("timeout must be > 0"));
+        }
+        self.inner
+            .wait_job(&self.unit, &self.job_path, timeout)
+            .await
+    }
+
+    /// Like `wait`, but return `Error::Cancelled` as soon as `shutdown` fires.
+    ///
+    /// Only the wait is abandoned; the job itself stays queued in systemd.
+    pub async fn wait_with_shutdown(
+        &self,
+        timeout: Duration,
+        shutdown: &crate::shutdown::ShutdownToken,
+    ) -> Result<JobOutcome> {
+        shutdown.run("wait_job", self.wait(timeout)).await
+    }
+
+    /// Like `wait`, then require the unit to stay in the job's target state (`active`, or
+    /// `inactive` for stop jobs) for `stable_for` before reporting success.
+    ///
+    /// Catches services that exit right after starting (e.g. a misconfigured `Type=simple`):
+    /// if the unit leaves the state or `NRestarts` goes up during the window, the result is
+    /// `JobOutcome::Failed` with `FailureHint::Unstable`. `timeout` bounds only the job wait;
+    /// the total time can reach `timeout + stable_for`.
+    pub async fn wait_stable(&self, timeout: Duration, stable_for: Duration) -> Result<JobOutcome> {
+        if stable_for == Duration::from_secs(0) {
+            return Err(Error::invalid_input("stable_for must be > 0"));
+        }
+        let outcome = self.wait(timeout).await?;
+        crate::telemetry::traced("wait_stable", Some(&self.unit), async {
+            self.inner
+                .hold_stable(&self.unit, stable_for, outcome)
+                .await
+        })
+        .await
+    }
+}
+
+impl JobInner {
+    /// Poll a successful outcome's unit for `stable_for`, downgrading it on the first change.
+    async fn hold_stable(
+        &self,
+        unit: &str,
+        stable_for: Duration,
+        outcome: JobOutcome,
+    ) -> Result<JobOutcome> {
+        let JobOutcome::Success {
+            mut unit_status,
+            warnings,
+        } = outcome
+        else {
+            return Ok(outcome);
+        };
+
+        let units = Units::new(self.root.clone());
+        let started = std::time::Instant::now();
+        let mut jitter = poll_jitter_seed(unit);
+        let mut interval = self.root.opts.job_poll_initial;
+        loop {
+            let remaining = stable_for.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                return Ok(JobOutcome::Success {
+                    unit_status,
+                    warnings,
+                });
+            }
+            crate::runtime::sleep(interval.min(remaining)).await;
+            interval = next_poll_interval(interval, self.root.opts.job_poll_max, &mut jitter);
+
+            let status = units.get_status(unit).await?;
+            if !stable_since(&self.kind, &unit_status, &status) {
+                #[cfg(feature = "tracing")]
+                tracing::info!(%unit, active_state = ?status.active_state, "unit not stable after job");
+
+                return Ok(JobOutcome::Failed {
+                    reason: FailureHint::Unstable {
+                        after: started.elapsed(),
+                        active_state: status.active_state.clone(),
+                        sub_state: status.sub_state.clone(),
+                    },
+                    unit_status: status,
+                });
+            }
+            unit_status = status;
+        }
+    }
+
+    async fn wait_job(&self, unit: &str, job_path: &str, timeout: Duration) -> Result<JobOutcome> {
+        crate::instrument::job_wait(crate::telemetry::traced("wait_job", Some(unit), async {
+            crate::telemetry::record_job_path(job_path);
+            self.wait_job_inner(unit, job_path, timeout).await
+        }))
+        .await
+    }
+
+    async fn wait_job_inner(
+        &self,
+        unit: &str,
+        job_path: &str,
+        timeout: Duration,
+    ) -> Result<JobOutcome> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%unit, %job_path, ?timeout, "wait_job start");
+
+        let manager = self.root.bus.manager_proxy().await?;
+
+        let mut signals = match manager.receive_signal("JobRemoved").await {
+            Ok(s) => Some(futures_util::StreamExt::fuse(s)),
+            Err(_) => None,
+        };
+
+        let mut job_result: Option<String> = None;
+
+        let mut jitter = poll_jitter_seed(job_path);
+        let mut poll_interval = apply_jitter(
+            self.root.opts.job_poll_initial,
+            self.root.opts.job_poll_max,
+            &mut jitter,
+        );
+        let mut poll_timer = futures_util::FutureExt::fuse(crate::runtime::sleep(poll_interval));
+        let mut deadline = futures_util::FutureExt::fuse(crate::runtime::sleep(timeout));
+
+        if !self.root.bus.job_exists(job_path).await? {
+            self.root.bus.invalidate_properties();
+            let status = Units::new(self.root.clone()).get_status(unit).await?;
+            let outcome = infer_outcome(&self.kind, &status, None);
+            return Ok(with_restart_warning(
+                outcome,
+                self.invocation_before.as_ref(),
+            ));
+        }
+
+        loop {
+            if let Some(sig) = &mut signals {
+                futures_util::select! {
+                    _ = deadline => {
+                        return Err(Error::JobTimeout { unit: unit.to_string(), timeout });
+                    }
+                    _ = poll_timer => {
+                        if !self.root.bus.job_exists(job_path).await? {
+                            break;
+                        }
+                        poll_interval = next_poll_interval(poll_interval, self.root.opts.job_poll_max, &mut jitter);
+                        poll_timer =
+                            futures_util::FutureExt::fuse(crate::runtime::sleep(poll_interval));
+                    }
+                    msg = sig.next() => {
+                        let Some(msg) = msg else {
+                            signals = None;
+                            continue;
+                        };
+                        if let Some(result) = decode_job_removed(job_path, msg)? {
+                            job_result = Some(result);
+                            break;
+                        }
+                    }
+                }
+            } else {
+                futures_util::select! {
+                    _ = deadline => {
+                        return Err(Error::JobTimeout { unit: unit.to_string(), timeout });
+                    }
+                    _ = poll_timer => {
+                        if !self.root.bus.job_exists(job_path).await? {
+                            break;
+                        }
+                        poll_interval = next_poll_interval(poll_interval, self.root.opts.job_poll_max, &mut jitter);
+                        poll_timer =
+                            futures_util::FutureExt::fuse(crate::runtime::sleep(poll_interval));
+                    }
+                }
+            }
+        }
+
+        self.root.bus.invalidate_properties();
+        let status = Units::new(self.root.clone()).get_status(unit).await?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%unit, %job_path, job_result = job_result.as_deref().unwrap_or(""), "wait_job done");
+
+        let outcome = infer_outcome(&self.kind, &status, job_result.as_deref());
+        Ok(with_restart_warning(
+            outcome,
+            self.invocation_before.as_ref(),
+        ))
+    }
+}
+
+fn next_poll_interval(current: Duration, max: Duration, seed: &mut u64) -> Duration {
+    let doubled = current.saturating_mul(2);
+    let base = if doubled > max { max } else { doubled };
+    apply_jitter(base, max, seed)
+}
+
+fn apply_jitter(base: Duration, max: Duration, seed: &mut u64) -> Duration {
+    if base >= max {
+        return base;
+    }
+
+    let base_us = duration_to_micros_saturating(base);
+    let max_us = duration_to_micros_saturating(max);
+
+    let amplitude = base_us / 10;
+    if amplitude == 0 {
+        return base;
+    }
+
+    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+    let jitter = *seed % amplitude.saturating_add(1);
+
+    let us = std::cmp::min(base_us.saturating_add(jitter), max_us);
+    Duration::from_micros(us)
+}
+
+fn duration_to_micros_saturating(d: Duration) -> u64 {
+    u64::try_from(d.as_micros()).unwrap_or(u64::MAX)
 }
 
 fn poll_jitter_seed(job_path: &str) -> u64 {
@@ -332,447 +1734,1542 @@ fn poll_jitter_seed(job_path: &str) -> u64 {
         hash = hash.wrapping_mul(0x100000001b3);
     }
 
-    let now = std::time::SystemTime::now();
-    let nanos = match now.duration_since(std::time::UNIX_EPOCH) {
-        Ok(d) => u64::from(d.subsec_nanos()),
-        Err(_) => 0,
-    };
+    let now = std::time::SystemTime::now();
+    let nanos = match now.duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => u64::from(d.subsec_nanos()),
+        Err(_) => 0,
+    };
+
+    hash ^ nanos ^ u64::from(std::process::id())
+}
+
+fn decode_job_removed(job_path: &str, msg: zbus::Message) -> Result<Option<String>> {
+    let body = msg.body();
+    let decoded: std::result::Result<(u32, OwnedObjectPath, String, String), _> =
+        body.deserialize();
+    let (_id, job, _unit, result) = decoded.map_err(|e| Error::DbusError {
+        name: "SignalDecode".to_string(),
+        message: e.to_string(),
+    })?;
+
+    if job.as_str() == job_path {
+        return Ok(Some(result));
+    }
+    Ok(None)
+}
+
+fn infer_outcome(kind: &JobKind, status: &UnitStatus, job_result: Option<&str>) -> JobOutcome {
+    if status.load_state != LoadState::Loaded {
+        return JobOutcome::Failed {
+            unit_status: status.clone(),
+            reason: FailureHint::NotLoaded {
+                load_state: status.load_state.clone(),
+            },
+        };
+    }
+
+    if let Some("canceled") = job_result
+        && status.active_state != ActiveState::Active
+    {
+        return JobOutcome::Canceled {
+            unit_status: status.clone(),
+        };
+    }
+
+    if status.active_state == ActiveState::Failed {
+        if let (Some(exec_main_code), Some(exec_main_status)) =
+            (status.exec_main_code, status.exec_main_status)
+        {
+            return JobOutcome::Failed {
+                unit_status: status.clone(),
+                reason: FailureHint::ExecMainFailed {
+                    exec_main_code,
+                    exec_main_status,
+                },
+            };
+        }
+        return JobOutcome::Failed {
+            unit_status: status.clone(),
+            reason: FailureHint::UnitFailed {
+                result: status.result.clone(),
+            },
+        };
+    }
+
+    if let Some(result) = job_result
+        && result != "done"
+    {
+        return JobOutcome::Failed {
+            unit_status: status.clone(),
+            reason: FailureHint::JobFailed {
+                result: result.to_string(),
+            },
+        };
+    }
+
+    let ok = match kind {
+        JobKind::Start | JobKind::Restart => status.active_state == ActiveState::Active,
+        JobKind::Stop => status.active_state == ActiveState::Inactive,
+        JobKind::Reload => matches!(
+            status.active_state,
+            ActiveState::Active | ActiveState::Reloading
+        ),
+    };
+
+    if ok {
+        JobOutcome::Success {
+            unit_status: status.clone(),
+            warnings: Vec::new(),
+        }
+    } else {
+        JobOutcome::Failed {
+            unit_status: status.clone(),
+            reason: FailureHint::UnexpectedState {
+                active_state: status.active_state.clone(),
+                sub_state: status.sub_state.clone(),
+            },
+        }
+    }
+}
+
+/// `true` if `now` is still in the job's target state and no automatic restart happened since
+/// `then`.
+fn stable_since(kind: &JobKind, then: &UnitStatus, now: &UnitStatus) -> bool {
+    let in_state = match kind {
+        JobKind::Start | JobKind::Restart | JobKind::Reload => {
+            now.active_state == ActiveState::Active
+        }
+        JobKind::Stop => now.active_state == ActiveState::Inactive,
+    };
+    let restarted = matches!(
+        (then.n_restarts, now.n_restarts),
+        (Some(a), Some(b)) if b > a
+    );
+    in_state && !restarted
+}
+
+/// Attach `JobWarning::RestartedDuringWait` to a success if `Restart=` restarted the unit after
+/// the job started it.
+///
+/// A start that actually runs the unit begins a new invocation and resets `NRestarts`, so once
+/// `InvocationID` differs from `before` every counted restart happened during the wait. An
+/// unchanged invocation means the job found the unit running and nothing restarted it.
+fn with_restart_warning(outcome: JobOutcome, before: Option<&Option<String>>) -> JobOutcome {
+    let JobOutcome::Success {
+        unit_status,
+        mut warnings,
+    } = outcome
+    else {
+        return outcome;
+    };
+    if let Some(before) = before
+        && unit_status.invocation_id != *before
+        && let Some(n_restarts) = unit_status.n_restarts.filter(|n| *n > 0)
+    {
+        warnings.push(crate::JobWarning::RestartedDuringWait { n_restarts });
+    }
+    JobOutcome::Success {
+        unit_status,
+        warnings,
+    }
+}
+
+/// Automatic restarts between two `NRestarts` readings; an explicit start resets the counter, so
+/// a lower reading counts from zero.
+fn restart_delta(before: u32, now: u32) -> u32 {
+    if now >= before { now - before } else { now }
+}
+
+async fn unit_status_from_paths(
+    bus: &crate::bus::Bus,
+    unit: &str,
+    unit_path: &OwnedObjectPath,
+) -> Result<UnitStatus> {
+    let unit_props = bus
+        .get_all_properties(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
+        .await?;
+
+    let service_props = optional_properties(bus, unit_path, SYSTEMD_SERVICE_INTERFACE).await?;
+    // `ControlGroup`/`Delegate`/`Slice` live on the type-specific interface of cgroup-owning
+    // units; services already have it in `service_props`.
+    let other_cgroup_props = match cgroup_interface(unit) {
+        Some(iface) if iface != SYSTEMD_SERVICE_INTERFACE => {
+            optional_properties(bus, unit_path, iface).await?
+        }
+        _ => None,
+    };
+    let cgroup_props = service_props.as_ref().or(other_cgroup_props.as_ref());
+
+    Ok(UnitStatus {
+        id: get_string(&unit_props, "Id").unwrap_or_else(|| unit.to_string()),
+        description: get_opt_string(&unit_props, "Description"),
+        load_state: get_string(&unit_props, "LoadState")
+            .map(|v| LoadState::parse(&v))
+            .unwrap_or_else(|| LoadState::Unknown("missing".to_string())),
+        active_state: get_string(&unit_props, "ActiveState")
+            .map(|v| ActiveState::parse(&v))
+            .unwrap_or_else(|| ActiveState::Unknown("missing".to_string())),
+        sub_state: get_opt_string(&unit_props, "SubState"),
+        result: get_opt_string(&unit_props, "Result"),
+        fragment_path: get_opt_string(&unit_props, "FragmentPath"),
+        unit_file_state: get_opt_string(&unit_props, "UnitFileState")
+            .map(|v| crate::UnitFileState::parse(&v)),
+        vendor_preset: get_string(&unit_props, "UnitFilePreset")
+            .and_then(|v| crate::VendorPreset::parse(&v)),
+        dropin_paths: get_string_vec(&unit_props, "DropInPaths"),
+        main_pid: service_props.as_ref().and_then(|m| get_u32(m, "MainPID")),
+        exec_main_code: service_props
+            .as_ref()
+            .and_then(|m| get_i32(m, "ExecMainCode")),
+        exec_main_status: service_props
+            .as_ref()
+            .and_then(|m| get_i32(m, "ExecMainStatus")),
+        n_restarts: service_props.as_ref().and_then(|m| get_u32(m, "NRestarts")),
+        invocation_id: get_invocation_id(&unit_props),
+        control_group: cgroup_props.and_then(|m| get_opt_string(m, "ControlGroup")),
+        delegate: cgroup_props.and_then(|m| get_bool(m, "Delegate")),
+        slice: cgroup_props.and_then(|m| get_opt_string(m, "Slice")),
+        exec_commands: service_props
+            .as_ref()
+            .map(get_exec_commands)
+            .unwrap_or_default(),
+    })
+}
+
+/// Wrap `status` with the kind-specific properties read from `iface`.
+fn typed_status(
+    status: UnitStatus,
+    iface: &str,
+    props: &HashMap<String, OwnedValue>,
+) -> crate::TypedUnitStatus {
+    let pairs = |key| get_string_pairs(props, key).unwrap_or_default();
+    // `0` and `u64::MAX` both mean "not set" for systemd timestamps.
+    let usec = |key| {
+        props
+            .get(key)
+            .and_then(|v| u64::try_from(v).ok())
+            .filter(|us| *us != 0 && *us != u64::MAX)
+    };
+
+    match iface {
+        SYSTEMD_SOCKET_INTERFACE => crate::TypedUnitStatus::Socket(crate::SocketStatus {
+            status,
+            listen: pairs("Listen")
+                .into_iter()
+                .map(|(kind, address)| crate::SocketListen { kind, address })
+                .collect(),
+            n_accepted: get_u32(props, "NAccepted"),
+            n_connections: get_u32(props, "NConnections"),
+            n_refused: get_u32(props, "NRefused"),
+        }),
+        SYSTEMD_TIMER_INTERFACE => crate::TypedUnitStatus::Timer(crate::TimerStatus {
+            status,
+            unit: get_opt_string(props, "Unit"),
+            next_elapse_realtime: usec("NextElapseUSecRealtime")
+                .map(util::system_time_from_unix_micros),
+            next_elapse_monotonic: usec("NextElapseUSecMonotonic").map(Duration::from_micros),
+            last_trigger: usec("LastTriggerUSec").map(util::system_time_from_unix_micros),
+        }),
+        SYSTEMD_PATH_INTERFACE => crate::TypedUnitStatus::Path(crate::PathStatus {
+            status,
+            unit: get_opt_string(props, "Unit"),
+            paths: pairs("Paths")
+                .into_iter()
+                .map(|(kind, path)| crate::PathWatch { kind, path })
+                .collect(),
+        }),
+        SYSTEMD_MOUNT_INTERFACE => crate::TypedUnitStatus::Mount(crate::MountStatus {
+            status,
+            what: get_opt_string(props, "What"),
+            mount_point: get_opt_string(props, "Where"),
+            fs_type: get_opt_string(props, "Type"),
+            options: get_opt_string(props, "Options"),
+        }),
+        _ => crate::TypedUnitStatus::Other(status),
+    }
+}
+
+async fn optional_properties(
+    bus: &crate::bus::Bus,
+    unit_path: &OwnedObjectPath,
+    interface: &str,
+) -> Result<Option<HashMap<String, OwnedValue>>> {
+    match bus.get_all_properties(unit_path.as_str(), interface).await {
+        Ok(props) => Ok(Some(props)),
+        Err(Error::DbusError { name, .. }) if name.contains("UnknownInterface") => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn get_string(map: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
+    map.get(key)
+        .and_then(|v| <&str>::try_from(v).ok())
+        .map(|s| s.to_string())
+}
+
+fn get_string_vec(map: &HashMap<String, OwnedValue>, key: &str) -> Vec<String> {
+    map.get(key)
+        .and_then(|v| v.try_clone().ok())
+        .and_then(|v| Vec::<String>::try_from(v).ok())
+        .unwrap_or_default()
+}
+
+fn get_opt_string(map: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
+    let s = get_string(map, key)?;
+    if s.is_empty() { None } else { Some(s) }
+}
+
+fn get_u32(map: &HashMap<String, OwnedValue>, key: &str) -> Option<u32> {
+    map.get(key).and_then(|v| u32::try_from(v).ok())
+}
+
+/// `InvocationID` (`ay`) as lowercase hex; `None` while the unit has no invocation.
+fn get_invocation_id(map: &HashMap<String, OwnedValue>) -> Option<String> {
+    let bytes = map
+        .get("InvocationID")
+        .and_then(|v| v.try_clone().ok())
+        .and_then(|v| Vec::<u8>::try_from(v).ok())?;
+    if bytes.is_empty() {
+        return None;
+    }
+    Some(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+fn get_i32(map: &HashMap<String, OwnedValue>, key: &str) -> Option<i32> {
+    map.get(key).and_then(|v| i32::try_from(v).ok())
+}
+
+fn get_bool(map: &HashMap<String, OwnedValue>, key: &str) -> Option<bool> {
+    map.get(key).and_then(|v| bool::try_from(v).ok())
+}
+
+/// Decode every `Exec*` property (`a(sasbttttuii)`) of a service, phase by phase.
+fn get_exec_commands(map: &HashMap<String, OwnedValue>) -> Vec<ExecCommandStatus> {
+    let mut out = Vec::new();
+    for phase in ExecPhase::ALL {
+        let Some(v) = map.get(phase.property()).and_then(|v| v.try_clone().ok()) else {
+            continue;
+        };
+        let Ok(items) = Vec::<crate::types::unit::ExecCommandItem>::try_from(v) else {
+            continue;
+        };
+        out.extend(
+            items
+                .into_iter()
+                .map(|item| ExecCommandStatus::from_dbus(phase, item)),
+        );
+    }
+    out
+}
+
+fn get_string_pairs(map: &HashMap<String, OwnedValue>, key: &str) -> Option<Vec<(String, String)>> {
+    let v = map.get(key)?.try_clone().ok()?;
+    Vec::<(String, String)>::try_from(v).ok()
+}
+
+#[cfg(feature = "config")]
+#[derive(Clone, Debug)]
+/// systemd configuration management (unit files + drop-ins) (feature=`config`).
+pub struct Config {
+    inner: Arc<crate::Inner>,
+}
+
+#[cfg(feature = "config")]
+impl Config {
+    pub(crate) fn new(inner: Arc<crate::Inner>) -> Self {
+        Self { inner }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn unit_dir(&self) -> Result<std::path::PathBuf> {
+        self.inner.opts.resolve_unit_dir(self.inner.scope)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn unit_dir(&self) -> Result<std::path::PathBuf> {
+        Err(Error::BackendUnavailable {
+            backend: "systemd_config",
+            detail: "config APIs are only supported on Linux".to_string(),
+        })
+    }
+
+    /// Write a systemd service unit file under `UnitBusOptions.unit_dir`.
+    pub async fn write_service_unit(
+        &self,
+        mut spec: crate::ServiceUnitSpec,
+    ) -> Result<crate::UnitFileWriteReport> {
+        spec.unit = spec.canonical_unit_name()?;
+        let unit = spec.unit.clone();
+        let contents = spec.render()?;
+
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("write_service_unit", Some(&unit), async {
+                let unit_dir = self.unit_dir()?;
+                blocking::unblock(move || {
+                    crate::fsutil::preview_unit_file(&unit_dir, &spec.unit, &contents)
+                })
+                .await
+            })
+            .await;
+        }
+
+        crate::telemetry::audited(&self.inner, "write_service_unit", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, "write_service_unit");
+
+            let unit2 = unit.clone();
+            let unit_dir = self.unit_dir()?;
+            let report = blocking::unblock(move || {
+                crate::fsutil::apply_unit_file(&unit_dir, &unit2, contents)
+            })
+            .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                changed = report.changed,
+                requires_daemon_reload = report.requires_daemon_reload,
+                "write_service_unit done"
+            );
+
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Remove a unit file under `UnitBusOptions.unit_dir`.
+    ///
+    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`).
+    pub async fn remove_unit_file(&self, unit: &str) -> Result<crate::UnitFileRemoveReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("remove_unit_file", Some(&unit), async {
+                let unit2 = unit.clone();
+                let unit_dir = self.unit_dir()?;
+                blocking::unblock(move || {
+                    crate::fsutil::preview_remove_unit_file(&unit_dir, &unit2)
+                })
+                .await
+            })
+            .await;
+        }
+
+        crate::telemetry::audited(&self.inner, "remove_unit_file", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, "remove_unit_file");
+
+            let unit2 = unit.clone();
+            let unit_dir = self.unit_dir()?;
+            let report =
+                blocking::unblock(move || crate::fsutil::remove_unit_file(&unit_dir, &unit2))
+                    .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                changed = report.changed,
+                requires_daemon_reload = report.requires_daemon_reload,
+                "remove_unit_file done"
+            );
+
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Enable a unit (`org.freedesktop.systemd1.Manager.EnableUnitFiles`).
+    ///
+    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`). Same as
+    /// `Units::enable`.
+    pub async fn enable_unit(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileEnableOptions,
+    ) -> Result<crate::UnitFileEnableReport> {
+        Units::new(self.inner.clone()).enable(unit, opts).await
+    }
 
-    hash ^ nanos ^ u64::from(std::process::id())
-}
+    /// Disable a unit (`org.freedesktop.systemd1.Manager.DisableUnitFiles`).
+    ///
+    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`). Same as
+    /// `Units::disable`.
+    pub async fn disable_unit(
+        &self,
+        unit: &str,
+        opts: crate::UnitFileDisableOptions,
+    ) -> Result<crate::UnitFileDisableReport> {
+        Units::new(self.inner.clone()).disable(unit, opts).await
+    }
 
-fn decode_job_removed(job_path: &str, msg: zbus::Message) -> Result<Option<String>> {
-    let body = msg.body();
-    let decoded: std::result::Result<(u32, OwnedObjectPath, String, String), _> =
-        body.deserialize();
-    let (_id, job, _unit, result) = decoded.map_err(|e| Error::DbusError {
-        name: "SignalDecode".to_string(),
-        message: e.to_string(),
-    })?;
+    /// Install a service unit file (write + optional daemon-reload + optional enable).
+    ///
+    /// Under `UnitBusOptions.dry_run` only the write is previewed: `daemon_reload_performed` is
+    /// `false` and `enabled` is `None`.
+    pub async fn install_service_unit(
+        &self,
+        spec: crate::ServiceUnitSpec,
+        opts: crate::ServiceUnitInstallOptions,
+    ) -> Result<crate::ServiceUnitInstallReport> {
+        let unit = spec.canonical_unit_name()?;
+        if self.inner.opts.dry_run {
+            return Ok(crate::ServiceUnitInstallReport {
+                unit,
+                wrote: self.write_service_unit(spec).await?,
+                daemon_reload_performed: false,
+                enabled: None,
+            });
+        }
+        let _inhibitor = self
+            .inhibit_shutdown_if(opts.inhibit_shutdown, || format!("Installing {unit}"))
+            .await?;
+        let wrote = self.write_service_unit(spec).await?;
 
-    if job.as_str() == job_path {
-        return Ok(Some(result));
-    }
-    Ok(None)
-}
+        let mut daemon_reload_performed = false;
+        if opts.daemon_reload && wrote.requires_daemon_reload {
+            self.daemon_reload().await?;
+            daemon_reload_performed = true;
+        }
 
-fn infer_outcome(kind: &JobKind, status: &UnitStatus, job_result: Option<&str>) -> JobOutcome {
-    if status.load_state != LoadState::Loaded {
-        return JobOutcome::Failed {
-            unit_status: status.clone(),
-            reason: FailureHint::NotLoaded {
-                load_state: status.load_state.clone(),
-            },
+        let enabled = if opts.enable {
+            Some(self.enable_unit(&unit, opts.enable_options).await?)
+        } else {
+            None
         };
+
+        Ok(crate::ServiceUnitInstallReport {
+            unit,
+            wrote,
+            daemon_reload_performed,
+            enabled,
+        })
     }
 
-    if let Some("canceled") = job_result
-        && status.active_state != ActiveState::Active
-    {
-        return JobOutcome::Canceled {
-            unit_status: status.clone(),
+    /// Uninstall a unit file (optional disable + remove + optional daemon-reload).
+    ///
+    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`). Under
+    /// `UnitBusOptions.dry_run` only the removal is previewed: `disabled` is `None` and
+    /// `daemon_reload_performed` is `false`.
+    pub async fn uninstall_unit(
+        &self,
+        unit: &str,
+        opts: crate::UnitUninstallOptions,
+    ) -> Result<crate::UnitUninstallReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if self.inner.opts.dry_run {
+            return Ok(crate::UnitUninstallReport {
+                removed: self.remove_unit_file(&unit).await?,
+                unit,
+                disabled: None,
+                daemon_reload_performed: false,
+            });
+        }
+        let _inhibitor = self
+            .inhibit_shutdown_if(opts.inhibit_shutdown, || format!("Uninstalling {unit}"))
+            .await?;
+
+        let disabled = if opts.disable {
+            Some(self.disable_unit(&unit, opts.disable_options).await?)
+        } else {
+            None
         };
+
+        let removed = self.remove_unit_file(&unit).await?;
+
+        let mut daemon_reload_performed = false;
+        if opts.daemon_reload && removed.requires_daemon_reload {
+            self.daemon_reload().await?;
+            daemon_reload_performed = true;
+        }
+
+        Ok(crate::UnitUninstallReport {
+            unit,
+            disabled,
+            removed,
+            daemon_reload_performed,
+        })
     }
 
-    if status.active_state == ActiveState::Failed {
-        if let (Some(exec_main_code), Some(exec_main_status)) =
-            (status.exec_main_code, status.exec_main_status)
-        {
-            return JobOutcome::Failed {
-                unit_status: status.clone(),
-                reason: FailureHint::ExecMainFailed {
-                    exec_main_code,
-                    exec_main_status,
-                },
-            };
+    /// Apply a drop-in file under `UnitBusOptions.unit_dir`.
+    pub async fn apply_dropin(
+        &self,
+        mut spec: crate::types::config::DropInSpec,
+    ) -> Result<crate::types::config::ApplyReport> {
+        prepare_dropin(&mut spec)?;
+
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("apply_dropin", Some(&spec.unit), async {
+                let contents = crate::fsutil::render_dropin(&spec)?;
+                let (unit, name) = (spec.unit.clone(), spec.name.clone());
+                let unit_dir = self.unit_dir()?;
+                blocking::unblock(move || {
+                    crate::fsutil::preview_dropin_file(&unit_dir, &unit, &name, &contents)
+                })
+                .await
+            })
+            .await;
         }
-        return JobOutcome::Failed {
-            unit_status: status.clone(),
-            reason: FailureHint::UnitFailed {
-                result: status.result.clone(),
-            },
-        };
+
+        crate::telemetry::audited(&self.inner, "apply_dropin", Some(&spec.unit), async {
+            let _inhibitor = self
+                .inhibit_shutdown_if(spec.inhibit_shutdown, || {
+                    format!("Applying drop-in {} for {}", spec.name, spec.unit)
+                })
+                .await?;
+            let _lock = crate::locks::lock_unit(&self.inner, &spec.unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %spec.unit,
+                name = %spec.name,
+                env_keys = spec.environment.len(),
+                has_workdir = spec.working_directory.is_some(),
+                has_exec_override = spec.exec_start_override.is_some(),
+                "apply_dropin"
+            );
+
+            let unit = spec.unit.clone();
+            let name = spec.name.clone();
+            let contents = crate::fsutil::render_dropin(&spec)?;
+            let unit_dir = self.unit_dir()?;
+            let report = blocking::unblock(move || {
+                crate::fsutil::apply_dropin_file(&unit_dir, &unit, &name, contents)
+            })
+            .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %spec.unit,
+                name = %spec.name,
+                changed = report.changed,
+                requires_daemon_reload = report.requires_daemon_reload,
+                "apply_dropin done"
+            );
+
+            Ok(report)
+        })
+        .await
     }
 
-    if let Some(result) = job_result
-        && result != "done"
-    {
-        return JobOutcome::Failed {
-            unit_status: status.clone(),
-            reason: FailureHint::JobFailed {
-                result: result.to_string(),
-            },
-        };
+    /// Apply a drop-in to one instance of a template unit, under
+    /// `<template>@<instance>.<type>.d/` (e.g. per-tenant `Environment=`).
+    ///
+    /// `template` is `name@.service` (or `name@`); `instance` is escaped like `systemd-escape`,
+    /// so `"acme/eu"` targets `name@acme-eu.service`. `spec.unit` is replaced by the instance
+    /// unit. The other instances and the template itself are not affected.
+    pub async fn apply_instance_dropin(
+        &self,
+        template: &str,
+        instance: &str,
+        mut spec: crate::types::config::DropInSpec,
+    ) -> Result<crate::types::config::ApplyReport> {
+        spec.unit = crate::types::specifier::instance_unit(template, instance)?;
+        self.apply_dropin(spec).await
     }
 
-    let ok = match kind {
-        JobKind::Start | JobKind::Restart => status.active_state == ActiveState::Active,
-        JobKind::Stop => status.active_state == ActiveState::Inactive,
-        JobKind::Reload => matches!(
-            status.active_state,
-            ActiveState::Active | ActiveState::Reloading
-        ),
-    };
+    /// `apply_dropin`, then reload if needed and confirm via D-Bus that systemd loaded the
+    /// drop-in (it is listed in `DropInPaths`, the unit is `loaded` and `NeedDaemonReload` is
+    /// off).
+    ///
+    /// Returns `Error::ConfigMismatch` naming the first property that disagrees, e.g. when the
+    /// unit is masked or a drop-in directory with higher priority shadows the file. A preview
+    /// cannot be verified, so `UnitBusOptions.dry_run` is rejected; use `apply_dropin` instead.
+    pub async fn apply_dropin_verified(
+        &self,
+        mut spec: crate::types::config::DropInSpec,
+    ) -> Result<crate::types::config::VerifiedApplyReport> {
+        reject_dry_run(&self.inner, "apply_dropin_verified")?;
+        let unit = util::canonicalize_unit_name(&spec.unit)?;
+        // One inhibitor across the write and the reload.
+        let _inhibitor = self
+            .inhibit_shutdown_if(std::mem::take(&mut spec.inhibit_shutdown), || {
+                format!("Applying drop-in {} for {unit}", spec.name)
+            })
+            .await?;
+        let report = self.apply_dropin(spec).await?;
+        crate::telemetry::traced("apply_dropin_verified", Some(&unit), async {
+            self.verify_loaded(
+                &unit,
+                ConfigFileRole::DropIn,
+                report.path_written,
+                report.changed,
+                report.requires_daemon_reload,
+            )
+            .await
+        })
+        .await
+    }
 
-    if ok {
-        JobOutcome::Success {
-            unit_status: status.clone(),
+    /// `write_service_unit`, then reload if needed and confirm via D-Bus that systemd loaded
+    /// the file (it is the unit's `FragmentPath`, the unit is `loaded` and `NeedDaemonReload`
+    /// is off).
+    ///
+    /// Returns `Error::ConfigMismatch` naming the first property that disagrees, e.g. when a
+    /// unit file with higher priority shadows the written one or it fails to parse. Rejected
+    /// under `UnitBusOptions.dry_run`, like `apply_dropin_verified`.
+    pub async fn write_service_unit_verified(
+        &self,
+        spec: crate::ServiceUnitSpec,
+    ) -> Result<crate::types::config::VerifiedApplyReport> {
+        reject_dry_run(&self.inner, "write_service_unit_verified")?;
+        let unit = spec.canonical_unit_name()?;
+        let report = self.write_service_unit(spec).await?;
+        crate::telemetry::traced("write_service_unit_verified", Some(&unit), async {
+            self.verify_loaded(
+                &unit,
+                ConfigFileRole::Fragment,
+                report.path_written,
+                report.changed,
+                report.requires_daemon_reload,
+            )
+            .await
+        })
+        .await
+    }
+
+    async fn verify_loaded(
+        &self,
+        unit: &str,
+        role: ConfigFileRole,
+        path: String,
+        changed: bool,
+        requires_daemon_reload: bool,
+    ) -> Result<crate::types::config::VerifiedApplyReport> {
+        let units = Units::new(self.inner.clone());
+        let unit_path = self.inner.bus.load_unit(unit).await?;
+        let read = || units.get_unit_properties_by_path(unit_path.as_str());
+
+        let mut props = read().await?;
+        // An unchanged file can still be unseen if an earlier write was never reloaded.
+        let daemon_reload_performed =
+            requires_daemon_reload || props.get_bool("NeedDaemonReload").unwrap_or(false);
+        if daemon_reload_performed {
+            self.daemon_reload().await?;
+            props = read().await?;
         }
-    } else {
-        JobOutcome::Failed {
-            unit_status: status.clone(),
-            reason: FailureHint::UnexpectedState {
-                active_state: status.active_state.clone(),
-                sub_state: status.sub_state.clone(),
-            },
+
+        let mismatch = |property: &'static str, expected: &str, actual: &str| {
+            Err(Error::ConfigMismatch {
+                unit: unit.to_string(),
+                property,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            })
+        };
+
+        let load_state = props.get_string("LoadState").unwrap_or_default();
+        if load_state != "loaded" {
+            return mismatch("LoadState", "loaded", &load_state);
+        }
+        if props.get_bool("NeedDaemonReload").unwrap_or(false) {
+            return mismatch("NeedDaemonReload", "false", "true");
+        }
+        let fragment_path = props.get_opt_string("FragmentPath");
+        let dropin_paths = props.get_string_list("DropInPaths").unwrap_or_default();
+        match role {
+            ConfigFileRole::Fragment if fragment_path.as_deref() != Some(path.as_str()) => {
+                return mismatch(
+                    "FragmentPath",
+                    &path,
+                    fragment_path.as_deref().unwrap_or_default(),
+                );
+            }
+            ConfigFileRole::DropIn if !dropin_paths.contains(&path) => {
+                return mismatch("DropInPaths", &path, &dropin_paths.join(" "));
+            }
+            _ => {}
         }
+
+        Ok(crate::types::config::VerifiedApplyReport {
+            unit: unit.to_string(),
+            path,
+            changed,
+            daemon_reload_performed,
+            fragment_path,
+            dropin_paths,
+            unit_file_state: props.get_opt_string("UnitFileState"),
+        })
     }
-}
 
-async fn unit_status_from_paths(
-    bus: &crate::bus::Bus,
-    unit: &str,
-    unit_path: &OwnedObjectPath,
-) -> Result<UnitStatus> {
-    let unit_props = bus
-        .get_all_properties(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
-        .await?;
+    /// Remove a drop-in file under `UnitBusOptions.unit_dir`.
+    pub async fn remove_dropin(
+        &self,
+        unit: &str,
+        name: &str,
+    ) -> Result<crate::types::config::RemoveReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        util::validate_dropin_name(name)?;
+
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("remove_dropin", Some(&unit), async {
+                let (unit2, name2) = (unit.clone(), name.to_string());
+                let unit_dir = self.unit_dir()?;
+                blocking::unblock(move || {
+                    crate::fsutil::preview_remove_dropin_file(&unit_dir, &unit2, &name2)
+                })
+                .await
+            })
+            .await;
+        }
+
+        crate::telemetry::audited(&self.inner, "remove_dropin", Some(&unit), async {
+            let _lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, name = %name, "remove_dropin");
+
+            let unit2 = unit.clone();
+            let name2 = name.to_string();
+            let unit_dir = self.unit_dir()?;
+            let report = blocking::unblock(move || {
+                crate::fsutil::remove_dropin_file(&unit_dir, &unit2, &name2)
+            })
+            .await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                name = %name,
+                changed = report.changed,
+                requires_daemon_reload = report.requires_daemon_reload,
+                "remove_dropin done"
+            );
 
-    let service_props = match bus
-        .get_all_properties(unit_path.as_str(), SYSTEMD_SERVICE_INTERFACE)
+            Ok(report)
+        })
         .await
-    {
-        Ok(props) => Some(props),
-        Err(Error::DbusError { name, .. }) if name.contains("UnknownInterface") => None,
-        Err(e) => return Err(e),
-    };
+    }
 
-    Ok(UnitStatus {
-        id: get_string(&unit_props, "Id").unwrap_or_else(|| unit.to_string()),
-        description: get_opt_string(&unit_props, "Description"),
-        load_state: get_string(&unit_props, "LoadState")
-            .map(|v| LoadState::parse(&v))
-            .unwrap_or_else(|| LoadState::Unknown("missing".to_string())),
-        active_state: get_string(&unit_props, "ActiveState")
-            .map(|v| ActiveState::parse(&v))
-            .unwrap_or_else(|| ActiveState::Unknown("missing".to_string())),
-        sub_state: get_opt_string(&unit_props, "SubState"),
-        result: get_opt_string(&unit_props, "Result"),
-        fragment_path: get_opt_string(&unit_props, "FragmentPath"),
-        main_pid: service_props.as_ref().and_then(|m| get_u32(m, "MainPID")),
-        exec_main_code: service_props
-            .as_ref()
-            .and_then(|m| get_i32(m, "ExecMainCode")),
-        exec_main_status: service_props
-            .as_ref()
-            .and_then(|m| get_i32(m, "ExecMainStatus")),
-        n_restarts: service_props.as_ref().and_then(|m| get_u32(m, "NRestarts")),
-    })
-}
+    /// Move a socket unit to new listen addresses (e.g. a port migration).
+    ///
+    /// Writes a `[Socket]` drop-in named `unitbus-listen` that replaces all `Listen*=` settings,
+    /// reloads the manager and restarts only the socket unit. The service it activates is left
+    /// alone: a running instance keeps its already inherited descriptors, and the next
+    /// activation happens on the new addresses. Nothing is restarted if the drop-in is already
+    /// up to date; a failed restart is reported in `restart_outcome`, not as an error.
+    ///
+    /// Under `UnitBusOptions.dry_run` only the drop-in is previewed: nothing is reloaded or
+    /// restarted and `new_listen` is empty.
+    pub async fn update_socket_listener(
+        &self,
+        socket_unit: &str,
+        new_listen: crate::SocketListenerUpdate,
+    ) -> Result<crate::SocketListenerReport> {
+        const DROPIN_NAME: &str = "unitbus-listen";
+
+        let unit = util::canonicalize_unit_name(socket_unit)?;
+        if !unit.ends_with(".socket") {
+            return Err(Error::invalid_input(format!("{unit} is not a socket unit")));
+        }
+        if new_listen.timeout == Duration::from_secs(0) {
+            return Err(Error::invalid_input("timeout must be > 0"));
+        }
+        let contents = crate::fsutil::render_socket_listen_dropin(&unit, &new_listen)?;
+
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("update_socket_listener", Some(&unit), async {
+                let old_listen = Units::new(self.inner.clone())
+                    .socket_listeners(&unit)
+                    .await?;
+                let (unit_dir, unit2) = (self.unit_dir()?, unit.clone());
+                let dropin = blocking::unblock(move || {
+                    crate::fsutil::preview_dropin_file(&unit_dir, &unit2, DROPIN_NAME, &contents)
+                })
+                .await?;
+                Ok(crate::SocketListenerReport {
+                    unit: unit.clone(),
+                    old_listen,
+                    new_listen: Vec::new(),
+                    dropin,
+                    daemon_reload_performed: false,
+                    restart_outcome: None,
+                })
+            })
+            .await;
+        }
+
+        crate::telemetry::audited(&self.inner, "update_socket_listener", Some(&unit), async {
+            let _inhibitor = self
+                .inhibit_shutdown_if(new_listen.inhibit_shutdown, || {
+                    format!("Moving {unit} to new listen addresses")
+                })
+                .await?;
+            let lock = crate::locks::lock_unit(&self.inner, &unit).await?;
+            let units = Units::new(self.inner.clone());
+            let old_listen = units.socket_listeners(&unit).await?;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                listen_stream = ?new_listen.listen_stream,
+                listen_datagram = ?new_listen.listen_datagram,
+                "update_socket_listener"
+            );
+
+            let unit2 = unit.clone();
+            let unit_dir = self.unit_dir()?;
+            let dropin = blocking::unblock(move || {
+                crate::fsutil::apply_dropin_file(&unit_dir, &unit2, DROPIN_NAME, contents)
+            })
+            .await?;
+
+            let mut daemon_reload_performed = false;
+            let mut restart_outcome = None;
+            if dropin.changed {
+                if dropin.requires_daemon_reload {
+                    self.daemon_reload().await?;
+                    daemon_reload_performed = true;
+                }
+                let job = units
+                    .start_like(
+                        JobKind::Restart,
+                        "restart",
+                        &unit,
+                        UnitStartMode::Replace,
+                        Some(&lock),
+                    )
+                    .await?;
+                restart_outcome = Some(job.wait(new_listen.timeout).await?);
+            }
 
-fn get_string(map: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
-    map.get(key)
-        .and_then(|v| <&str>::try_from(v).ok())
-        .map(|s| s.to_string())
-}
+            let new_listen = match &restart_outcome {
+                None | Some(JobOutcome::Success { .. }) => units.socket_listeners(&unit).await?,
+                Some(_) => Vec::new(),
+            };
 
-fn get_opt_string(map: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
-    let s = get_string(map, key)?;
-    if s.is_empty() { None } else { Some(s) }
-}
+            Ok(crate::SocketListenerReport {
+                unit: unit.clone(),
+                old_listen,
+                new_listen,
+                dropin,
+                daemon_reload_performed,
+                restart_outcome,
+            })
+        })
+        .await
+    }
 
-fn get_u32(map: &HashMap<String, OwnedValue>, key: &str) -> Option<u32> {
-    map.get(key).and_then(|v| u32::try_from(v).ok())
-}
+    /// Install `handler` as a service unit file and make `unit` trigger it on failure via an
+    /// `OnFailure=` drop-in, then reload.
+    ///
+    /// A template handler (`notify-failure@.service`) is wired as `notify-failure@%n.service`,
+    /// so each instance receives the failed unit's name as `%i` (systemd also sets
+    /// `$MONITOR_UNIT` and friends for it). The drop-in adds to any existing `OnFailure=`
+    /// entries. Returns `Error::InvalidInput` if `handler` is `unit` itself.
+    ///
+    /// Under `UnitBusOptions.dry_run` both files are previewed and nothing is reloaded.
+    pub async fn wire_on_failure(
+        &self,
+        unit: &str,
+        handler: crate::ServiceUnitSpec,
+    ) -> Result<crate::OnFailureReport> {
+        const DROPIN_NAME: &str = "unitbus-on-failure";
 
-fn get_i32(map: &HashMap<String, OwnedValue>, key: &str) -> Option<i32> {
-    map.get(key).and_then(|v| i32::try_from(v).ok())
-}
+        let unit = util::canonicalize_unit_name(unit)?;
+        let handler_unit = handler.canonical_unit_name()?;
+        if handler_unit == unit {
+            return Err(Error::invalid_input(format!(
+                "{unit} cannot be its own OnFailure= handler"
+            )));
+        }
+        let on_failure = match handler_unit.split_once("@.") {
+            Some((prefix, suffix)) => format!("{prefix}@%n.{suffix}"),
+            None => handler_unit.clone(),
+        };
+        let contents = crate::fsutil::render_on_failure_dropin(&on_failure)?;
 
-#[cfg(feature = "config")]
-#[derive(Clone, Debug)]
-/// systemd configuration management (unit files + drop-ins) (feature=`config`).
-pub struct Config {
-    inner: Arc<crate::Inner>,
-}
+        let op = async {
+            let handler_report = self.write_service_unit(handler).await?;
 
-#[cfg(feature = "config")]
-impl Config {
-    pub(crate) fn new(inner: Arc<crate::Inner>) -> Self {
-        Self { inner }
-    }
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, on_failure = %on_failure, "wire_on_failure");
+            let dropin = self.write_dropin(&unit, DROPIN_NAME, contents).await?;
 
-    #[cfg(target_os = "linux")]
-    fn systemd_system_dir(&self) -> Result<std::path::PathBuf> {
-        let dir = self.inner.opts.systemd_system_dir.trim();
-        util::validate_no_control("systemd_system_dir", dir)?;
-        if dir.is_empty() {
-            return Err(Error::invalid_input("systemd_system_dir must not be empty"));
+            let daemon_reload_performed = !self.inner.opts.dry_run
+                && (handler_report.requires_daemon_reload || dropin.requires_daemon_reload);
+            if daemon_reload_performed {
+                self.daemon_reload().await?;
+            }
+
+            Ok(crate::OnFailureReport {
+                unit: unit.clone(),
+                handler_unit,
+                on_failure,
+                handler: handler_report,
+                dropin,
+                daemon_reload_performed,
+            })
+        };
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("wire_on_failure", Some(&unit), op).await;
         }
-        Ok(std::path::PathBuf::from(dir))
+        crate::telemetry::audited(&self.inner, "wire_on_failure", Some(&unit), op).await
     }
 
-    #[cfg(not(target_os = "linux"))]
-    fn systemd_system_dir(&self) -> Result<std::path::PathBuf> {
-        Err(Error::BackendUnavailable {
-            backend: "systemd_config",
-            detail: "config APIs are only supported on Linux".to_string(),
-        })
+    /// Write an application group target (e.g. `myapp.target`) and attach its members with a
+    /// `PartOf=` drop-in each, then reload systemd if anything changed.
+    ///
+    /// Units in `UnitBusOptions.unit_dir` that still carry the group's drop-in from an earlier
+    /// definition but are no longer members are detached.
+    ///
+    /// Drive the group with `Units::start_group`/`stop_group`/`restart_group`; enable the target
+    /// to bring the group up at boot via `AppTargetSpec.wanted_by`. Under
+    /// `UnitBusOptions.dry_run` every write and removal is previewed and nothing is reloaded.
+    pub async fn define_app_target(
+        &self,
+        spec: crate::AppTargetSpec,
+    ) -> Result<crate::AppTargetReport> {
+        let target = spec.canonical_target_name()?;
+        let mut members = Vec::<String>::new();
+        for member in &spec.members {
+            let member = util::canonicalize_unit_name(member)?;
+            if member == target {
+                return Err(Error::invalid_input(format!(
+                    "{target} cannot be a member of itself"
+                )));
+            }
+            if !members.contains(&member) {
+                members.push(member);
+            }
+        }
+        let wanted_by = spec
+            .wanted_by
+            .iter()
+            .map(|u| util::canonicalize_unit_name(u))
+            .collect::<Result<Vec<_>>>()?;
+        let contents =
+            crate::fsutil::render_app_target(spec.description.as_deref(), &members, &wanted_by)?;
+        let dropin_name = format!(
+            "unitbus-group-{}",
+            target.strip_suffix(".target").unwrap_or(&target)
+        );
+        util::validate_dropin_name(&dropin_name)?;
+
+        let op = async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %target, members = members.len(), "define_app_target");
+
+            let target_file = self.write_unit_file(&target, contents).await?;
+            let mut dropins = Vec::with_capacity(members.len());
+            for member in &members {
+                let contents = crate::fsutil::render_part_of_dropin(&target);
+                dropins.push(self.write_dropin(member, &dropin_name, contents).await?);
+            }
+
+            let unit_dir = self.unit_dir()?;
+            let name = dropin_name.clone();
+            let stale =
+                blocking::unblock(move || crate::fsutil::units_with_dropin(&unit_dir, &name))
+                    .await?;
+            let mut removed = Vec::new();
+            for unit in stale.iter().filter(|u| !members.contains(u)) {
+                removed.push(self.delete_dropin(unit, &dropin_name).await?);
+            }
+
+            let daemon_reload_performed = !self.inner.opts.dry_run
+                && (target_file.requires_daemon_reload
+                    || dropins.iter().any(|d| d.requires_daemon_reload)
+                    || removed.iter().any(|r| r.requires_daemon_reload));
+            if daemon_reload_performed {
+                self.daemon_reload().await?;
+            }
+
+            Ok(crate::AppTargetReport {
+                target: target.clone(),
+                members: members.clone(),
+                target_file,
+                dropins,
+                removed,
+                daemon_reload_performed,
+            })
+        };
+        if self.inner.opts.dry_run {
+            return crate::telemetry::traced("define_app_target", Some(&target), op).await;
+        }
+        crate::telemetry::audited(&self.inner, "define_app_target", Some(&target), op).await
     }
 
-    /// Write a systemd service unit file under `UnitBusOptions.systemd_system_dir`.
-    pub async fn write_service_unit(
+    /// Write a unit file under the unit lock, or preview it under `UnitBusOptions.dry_run`.
+    async fn write_unit_file(
         &self,
-        mut spec: crate::ServiceUnitSpec,
+        unit: &str,
+        contents: String,
     ) -> Result<crate::UnitFileWriteReport> {
-        spec.unit = spec.canonical_unit_name()?;
-        let unit = spec.unit.clone();
-        let contents = spec.render()?;
-
-        #[cfg(feature = "tracing")]
-        tracing::info!(unit = %unit, "write_service_unit");
+        let (unit_dir, unit2) = (self.unit_dir()?, unit.to_string());
+        if self.inner.opts.dry_run {
+            return blocking::unblock(move || {
+                crate::fsutil::preview_unit_file(&unit_dir, &unit2, &contents)
+            })
+            .await;
+        }
+        let _lock = crate::locks::lock_unit(&self.inner, unit).await?;
+        blocking::unblock(move || crate::fsutil::apply_unit_file(&unit_dir, &unit2, contents)).await
+    }
 
-        let unit2 = unit.clone();
-        let systemd_system_dir = self.systemd_system_dir()?;
-        let report = blocking::unblock(move || {
-            crate::fsutil::apply_unit_file(&systemd_system_dir, &unit2, contents)
+    /// Write a drop-in under the unit lock, or preview it under `UnitBusOptions.dry_run`.
+    async fn write_dropin(
+        &self,
+        unit: &str,
+        name: &str,
+        contents: String,
+    ) -> Result<crate::types::config::ApplyReport> {
+        let (unit_dir, unit2, name) = (self.unit_dir()?, unit.to_string(), name.to_string());
+        if self.inner.opts.dry_run {
+            return blocking::unblock(move || {
+                crate::fsutil::preview_dropin_file(&unit_dir, &unit2, &name, &contents)
+            })
+            .await;
+        }
+        let _lock = crate::locks::lock_unit(&self.inner, unit).await?;
+        blocking::unblock(move || {
+            crate::fsutil::apply_dropin_file(&unit_dir, &unit2, &name, contents)
         })
-        .await?;
+        .await
+    }
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %unit,
-            changed = report.changed,
-            requires_daemon_reload = report.requires_daemon_reload,
-            "write_service_unit done"
-        );
+    /// Remove a drop-in under the unit lock, or preview it under `UnitBusOptions.dry_run`.
+    async fn delete_dropin(
+        &self,
+        unit: &str,
+        name: &str,
+    ) -> Result<crate::types::config::RemoveReport> {
+        let (unit_dir, unit2, name) = (self.unit_dir()?, unit.to_string(), name.to_string());
+        if self.inner.opts.dry_run {
+            return blocking::unblock(move || {
+                crate::fsutil::preview_remove_dropin_file(&unit_dir, &unit2, &name)
+            })
+            .await;
+        }
+        let _lock = crate::locks::lock_unit(&self.inner, unit).await?;
+        blocking::unblock(move || crate::fsutil::remove_dropin_file(&unit_dir, &unit2, &name)).await
+    }
 
-        Ok(report)
+    /// Start a batch of configuration writes that share a single `daemon_reload`.
+    ///
+    /// Apply drop-ins and unit files through the returned `ReloadCoalescer`, then call `flush`
+    /// once at the end instead of reloading after every write.
+    pub fn reload_coalescer(&self) -> ReloadCoalescer {
+        ReloadCoalescer {
+            config: self.clone(),
+            pending: std::collections::BTreeSet::new(),
+            inhibitor: None,
+        }
     }
 
-    /// Remove a unit file under `UnitBusOptions.systemd_system_dir`.
+    /// Read the installed unit file and drop-ins of `unit`, like `systemctl cat`.
     ///
-    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`).
-    pub async fn remove_unit_file(&self, unit: &str) -> Result<crate::UnitFileRemoveReport> {
+    /// Paths come from systemd (`FragmentPath`, `DropInPaths`; the unit is loaded if needed).
+    /// When systemd knows no fragment, the file in `UnitBusOptions.unit_dir` is read instead.
+    pub async fn read_unit_file(&self, unit: &str) -> Result<crate::UnitFileContent> {
         let unit = util::canonicalize_unit_name(unit)?;
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(unit = %unit, "remove_unit_file");
-
-        let unit2 = unit.clone();
-        let systemd_system_dir = self.systemd_system_dir()?;
-        let report =
-            blocking::unblock(move || crate::fsutil::remove_unit_file(&systemd_system_dir, &unit2))
+        crate::telemetry::traced("read_unit_file", Some(&unit), async {
+            let unit_path = self.inner.bus.load_unit(&unit).await?;
+            let props = Units::new(self.inner.clone())
+                .get_unit_properties_by_path(unit_path.as_str())
                 .await?;
-
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %unit,
-            changed = report.changed,
-            requires_daemon_reload = report.requires_daemon_reload,
-            "remove_unit_file done"
-        );
-
-        Ok(report)
+            let fragment_path = props.get_opt_string("FragmentPath");
+            let dropin_paths = props.get_string_list("DropInPaths").unwrap_or_default();
+            let need_daemon_reload = props.get_bool("NeedDaemonReload").unwrap_or(false);
+
+            let unit_dir = self.unit_dir()?;
+            blocking::unblock(move || {
+                crate::fsutil::read_unit_config(
+                    &unit_dir,
+                    unit,
+                    fragment_path,
+                    dropin_paths,
+                    need_daemon_reload,
+                )
+            })
+            .await
+        })
+        .await
     }
 
-    /// Enable a unit (`org.freedesktop.systemd1.Manager.EnableUnitFiles`).
+    /// Preview `install_service_unit(spec, ..)` without writing anything: diff the unit file in
+    /// `UnitBusOptions.unit_dir` against the rendered spec.
     ///
-    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`).
-    pub async fn enable_unit(
+    /// The report also lists drop-ins that keep applying and flags a target file shadowed by
+    /// another fragment (`UnitFileDiff::shadowed`).
+    pub async fn diff_service_unit(
         &self,
-        unit: &str,
-        opts: crate::UnitFileEnableOptions,
-    ) -> Result<crate::UnitFileEnableReport> {
-        let unit = util::canonicalize_unit_name(unit)?;
+        mut spec: crate::ServiceUnitSpec,
+    ) -> Result<crate::UnitFileDiff> {
+        spec.unit = spec.canonical_unit_name()?;
+        let rendered = spec.render()?;
+        let installed = self.read_unit_file(&spec.unit).await?;
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(unit = %unit, runtime = opts.runtime, force = opts.force, "enable_unit");
+        let unit_dir = self.unit_dir()?;
+        blocking::unblock(move || crate::fsutil::diff_unit_file(&unit_dir, installed, &rendered))
+            .await
+    }
 
-        let files = vec![unit];
-        let (carries_install_info, changes) = self
-            .inner
-            .bus
-            .enable_unit_files(&files, opts.runtime, opts.force)
-            .await?;
+    /// Fingerprint the effective on-disk configuration of `unit`.
+    ///
+    /// Hashes the unit's `FragmentPath` and all `DropInPaths` (as reported by systemd) and
+    /// records `NeedDaemonReload`. Store the result and later pass it to `detect_drift` to notice
+    /// out-of-band edits. Drop-ins that are added on disk but not yet loaded by systemd only show
+    /// up via `need_daemon_reload`.
+    pub async fn fingerprint(&self, unit: &str) -> Result<crate::UnitConfigFingerprint> {
+        let unit = util::canonicalize_unit_name(unit)?;
 
-        Ok(crate::UnitFileEnableReport {
-            carries_install_info,
-            changes: changes
-                .into_iter()
-                .map(crate::UnitFileChange::from_dbus)
-                .collect(),
+        crate::telemetry::traced("config_fingerprint", Some(&unit), async {
+            let props = Units::new(self.inner.clone())
+                .get_unit_properties(&unit)
+                .await?;
+            let fragment_path = props.get_opt_string("FragmentPath");
+            let dropin_paths = props.get_string_list("DropInPaths").unwrap_or_default();
+            let need_daemon_reload = props.get_bool("NeedDaemonReload").unwrap_or(false);
+
+            let unit2 = unit.clone();
+            blocking::unblock(move || {
+                crate::fsutil::fingerprint_files(
+                    unit2,
+                    fragment_path,
+                    dropin_paths,
+                    need_daemon_reload,
+                )
+            })
+            .await
         })
+        .await
     }
 
-    /// Disable a unit (`org.freedesktop.systemd1.Manager.DisableUnitFiles`).
+    /// Compare the current configuration of `expected.unit` against a stored fingerprint.
     ///
-    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`).
-    pub async fn disable_unit(
+    /// `ConfigDrift.drifted` is set when the combined digest differs or systemd reports that
+    /// files changed since the last daemon-reload.
+    pub async fn detect_drift(
         &self,
-        unit: &str,
-        opts: crate::UnitFileDisableOptions,
-    ) -> Result<crate::UnitFileDisableReport> {
-        let unit = util::canonicalize_unit_name(unit)?;
+        expected: &crate::UnitConfigFingerprint,
+    ) -> Result<crate::ConfigDrift> {
+        let current = self.fingerprint(&expected.unit).await?;
+        Ok(crate::fsutil::compare_fingerprints(expected, current))
+    }
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(unit = %unit, runtime = opts.runtime, "disable_unit");
+    /// Stage an offline update (`systemd.offline-updates(7)`): install and enable
+    /// `<name>.service` into `system-update.target`, then point the `/system-update` marker at
+    /// `spec.update_dir`.
+    ///
+    /// The next boot enters the update mode, runs the updater and reboots; reboot with
+    /// `schedule_offline_update_reboot` and read the outcome with `offline_update_status`
+    /// afterwards. Staging the same update again is idempotent; a marker left by a different
+    /// update is `Error::InvalidInput`, and so is `UnitBusOptions.dry_run` (the offline update
+    /// calls do not preview).
+    pub async fn stage_offline_update(
+        &self,
+        spec: crate::OfflineUpdateSpec,
+    ) -> Result<crate::OfflineUpdateReport> {
+        reject_dry_run(&self.inner, "stage_offline_update")?;
+        let unit_spec = spec.service_spec()?;
+        let unit = unit_spec.canonical_unit_name()?;
+
+        crate::telemetry::audited(&self.inner, "stage_offline_update", Some(&unit), async {
+            let install = self
+                .install_service_unit(unit_spec, crate::ServiceUnitInstallOptions::default())
+                .await?;
 
-        let files = vec![unit];
-        let changes = self
-            .inner
-            .bus
-            .disable_unit_files(&files, opts.runtime)
-            .await?;
+            // The marker goes last: until it exists, the next boot is a normal one.
+            let marker = spec.marker.to_string_lossy().into_owned();
+            blocking::unblock(move || crate::types::offline_update::stage_marker(&spec)).await?;
 
-        Ok(crate::UnitFileDisableReport {
-            changes: changes
-                .into_iter()
-                .map(crate::UnitFileChange::from_dbus)
-                .collect(),
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, %marker, "stage_offline_update done");
+
+            Ok(crate::OfflineUpdateReport {
+                unit: unit.clone(),
+                install,
+                marker,
+            })
         })
+        .await
     }
 
-    /// Install a service unit file (write + optional daemon-reload + optional enable).
-    pub async fn install_service_unit(
+    /// Reboot into the staged offline update after `delay`
+    /// (`org.freedesktop.login1.Manager.ScheduleShutdown`, like `shutdown -r`); returns when the
+    /// reboot is due.
+    ///
+    /// Returns `Error::InvalidInput` if `spec` is not staged. Requires `systemd-logind` and, for
+    /// non-root callers, the polkit action `org.freedesktop.login1.reboot`.
+    pub async fn schedule_offline_update_reboot(
         &self,
-        spec: crate::ServiceUnitSpec,
-        opts: crate::ServiceUnitInstallOptions,
-    ) -> Result<crate::ServiceUnitInstallReport> {
-        let unit = spec.canonical_unit_name()?;
-        let wrote = self.write_service_unit(spec).await?;
+        spec: &crate::OfflineUpdateSpec,
+        delay: Duration,
+    ) -> Result<std::time::SystemTime> {
+        reject_dry_run(&self.inner, "schedule_offline_update_reboot")?;
+        let unit = spec.unit()?;
+        crate::telemetry::audited(&self.inner, "schedule_reboot", Some(&unit), async {
+            let check = spec.clone();
+            let staged =
+                blocking::unblock(move || crate::types::offline_update::is_staged(&check)).await?;
+            if !staged {
+                return Err(Error::invalid_input(format!(
+                    "offline update {unit} is not staged"
+                )));
+            }
 
-        let mut daemon_reload_performed = false;
-        if opts.daemon_reload && wrote.requires_daemon_reload {
-            self.daemon_reload().await?;
-            daemon_reload_performed = true;
-        }
+            let at = std::time::SystemTime::now() + delay;
+            let usec = at
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()
+                .and_then(|d| u64::try_from(d.as_micros()).ok())
+                .ok_or_else(|| Error::invalid_input("reboot time out of range"))?;
+            self.inner
+                .bus
+                .logind_schedule_shutdown("reboot", usec)
+                .await?;
 
-        let enabled = if opts.enable {
-            Some(self.enable_unit(&unit, opts.enable_options).await?)
-        } else {
-            None
-        };
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %unit, delay_ms = delay.as_millis() as u64, "schedule_reboot");
 
-        Ok(crate::ServiceUnitInstallReport {
-            unit,
-            wrote,
-            daemon_reload_performed,
-            enabled,
+            Ok(at)
         })
+        .await
     }
 
-    /// Uninstall a unit file (optional disable + remove + optional daemon-reload).
-    ///
-    /// `unit` is canonicalized (e.g. `"nginx"` becomes `"nginx.service"`).
-    pub async fn uninstall_unit(
+    /// Where the offline update `spec` stands: staged, or the recorded result of its last run.
+    pub async fn offline_update_status(
         &self,
-        unit: &str,
-        opts: crate::UnitUninstallOptions,
+        spec: &crate::OfflineUpdateSpec,
+    ) -> Result<crate::OfflineUpdateStatus> {
+        let unit = spec.unit()?;
+        crate::telemetry::traced("offline_update_status", Some(&unit), async {
+            let spec = spec.clone();
+            blocking::unblock(move || crate::types::offline_update::read_status(&spec)).await
+        })
+        .await
+    }
+
+    /// Unstage or clean up after the offline update `spec`: remove the marker (if it is still
+    /// set for this update) and the recorded result, then disable and remove the update unit.
+    pub async fn remove_offline_update(
+        &self,
+        spec: &crate::OfflineUpdateSpec,
     ) -> Result<crate::UnitUninstallReport> {
-        let unit = util::canonicalize_unit_name(unit)?;
+        reject_dry_run(&self.inner, "remove_offline_update")?;
+        let unit = spec.unit()?;
+        crate::telemetry::audited(&self.inner, "remove_offline_update", Some(&unit), async {
+            let clear = spec.clone();
+            blocking::unblock(move || crate::types::offline_update::clear_marker(&clear)).await?;
+            self.uninstall_unit(&unit, crate::UnitUninstallOptions::default())
+                .await
+        })
+        .await
+    }
 
-        let disabled = if opts.disable {
-            Some(self.disable_unit(&unit, opts.disable_options).await?)
-        } else {
-            None
-        };
+    /// Take a logind shutdown inhibitor (`block` mode) for a multi-step config change.
+    ///
+    /// Hold the returned guard across e.g. `apply_dropin` + `daemon_reload` + `restart` so a
+    /// reboot cannot interrupt a half-applied change; dropping it releases the lock. Requires
+    /// `systemd-logind` and, for non-root callers, the polkit action
+    /// `org.freedesktop.login1.inhibit-block-shutdown`.
+    pub async fn inhibit_shutdown(&self, why: &str) -> Result<crate::ShutdownInhibitor> {
+        util::validate_no_control("inhibit reason", why)?;
+
+        crate::telemetry::traced("inhibit_shutdown", None, async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(why = %why, "inhibit_shutdown");
+
+            #[cfg(unix)]
+            {
+                let fd = self
+                    .inner
+                    .bus
+                    .logind_inhibit("shutdown", "unitbus", why, "block")
+                    .await?;
+                Ok(crate::ShutdownInhibitor::new(fd))
+            }
 
-        let removed = self.remove_unit_file(&unit).await?;
+            #[cfg(not(unix))]
+            {
+                Err(Error::BackendUnavailable {
+                    backend: "logind",
+                    detail: "inhibitor locks require a unix platform".to_string(),
+                })
+            }
+        })
+        .await
+    }
 
-        let mut daemon_reload_performed = false;
-        if opts.daemon_reload && removed.requires_daemon_reload {
-            self.daemon_reload().await?;
-            daemon_reload_performed = true;
+    /// `inhibit_shutdown(why())` if `enabled`, `None` otherwise.
+    async fn inhibit_shutdown_if(
+        &self,
+        enabled: bool,
+        why: impl FnOnce() -> String,
+    ) -> Result<Option<crate::ShutdownInhibitor>> {
+        if !enabled {
+            return Ok(None);
         }
-
-        Ok(crate::UnitUninstallReport {
-            unit,
-            disabled,
-            removed,
-            daemon_reload_performed,
-        })
+        Ok(Some(self.inhibit_shutdown(&why()).await?))
     }
 
-    /// Apply a drop-in file under `UnitBusOptions.systemd_system_dir`.
-    pub async fn apply_dropin(
+    /// Check a calendar expression with `systemd-analyze calendar` and compute its next
+    /// `iterations` elapse times (1..=1000).
+    ///
+    /// Unlike `CalendarSpec::parse`, this resolves time zones and evaluates the schedule exactly
+    /// as the manager would. A rejected expression is reported as `Error::InvalidInput`; a missing
+    /// `systemd-analyze` binary as `Error::BackendUnavailable`, and a run longer than 10s as
+    /// `Error::Timeout` (the process is killed).
+    pub async fn analyze_calendar(
         &self,
-        mut spec: crate::types::config::DropInSpec,
-    ) -> Result<crate::types::config::ApplyReport> {
-        spec.unit = util::canonicalize_unit_name(&spec.unit)?;
-        util::validate_dropin_name(&spec.name)?;
-        for key in spec.environment.keys() {
-            util::validate_env_key(key)?;
+        expr: &str,
+        iterations: u32,
+    ) -> Result<crate::CalendarAnalysis> {
+        let expr = expr.trim().to_string();
+        util::validate_no_control("calendar expression", &expr)?;
+        if expr.is_empty() {
+            return Err(Error::invalid_input(
+                "calendar expression must not be empty",
+            ));
+        }
+        if !(1..=1000).contains(&iterations) {
+            return Err(Error::invalid_input("iterations must be in 1..=1000"));
         }
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %spec.unit,
-            name = %spec.name,
-            env_keys = spec.environment.len(),
-            has_workdir = spec.working_directory.is_some(),
-            has_exec_override = spec.exec_start_override.is_some(),
-            "apply_dropin"
-        );
+        crate::telemetry::traced("analyze_calendar", None, async move {
+            systemd_analyze_calendar(&expr, iterations).await
+        })
+        .await
+    }
 
-        let unit = spec.unit.clone();
-        let name = spec.name.clone();
-        let contents = crate::fsutil::render_dropin(&spec)?;
-        let systemd_system_dir = self.systemd_system_dir()?;
-        let report = blocking::unblock(move || {
-            crate::fsutil::apply_dropin_file(&systemd_system_dir, &unit, &name, contents)
+    /// Reload systemd manager configuration (`org.freedesktop.systemd1.Manager.Reload`).
+    pub async fn daemon_reload(&self) -> Result<()> {
+        crate::telemetry::audited(&self.inner, "daemon_reload", None, async {
+            #[cfg(feature = "tracing")]
+            tracing::info!("daemon_reload");
+            self.inner.bus.daemon_reload().await
         })
-        .await?;
+        .await
+    }
+}
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %spec.unit,
-            name = %spec.name,
-            changed = report.changed,
-            requires_daemon_reload = report.requires_daemon_reload,
-            "apply_dropin done"
-        );
+/// Which kind of file `Config::verify_loaded` checks for.
+#[cfg(feature = "config")]
+#[derive(Clone, Copy, Debug)]
+enum ConfigFileRole {
+    Fragment,
+    DropIn,
+}
 
+/// Batches configuration writes behind one `daemon_reload` (see `Config::reload_coalescer`)
+/// (feature=`config`).
+///
+/// Each write goes to disk immediately; only the reload is deferred. Units whose write
+/// requires a reload are tracked until `flush`. Dropping the coalescer without flushing leaves
+/// those changes unloaded (and releases a shutdown inhibitor taken with `inhibit_shutdown`).
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub struct ReloadCoalescer {
+    config: Config,
+    pending: std::collections::BTreeSet<String>,
+    inhibitor: Option<crate::ShutdownInhibitor>,
+}
+
+#[cfg(feature = "config")]
+impl ReloadCoalescer {
+    /// `Config::apply_dropin`, deferring the reload.
+    pub async fn apply_dropin(
+        &mut self,
+        spec: crate::types::config::DropInSpec,
+    ) -> Result<crate::types::config::ApplyReport> {
+        let unit = util::canonicalize_unit_name(&spec.unit)?;
+        let report = self.config.apply_dropin(spec).await?;
+        self.track(unit, report.requires_daemon_reload && !report.dry_run);
         Ok(report)
     }
 
-    /// Remove a drop-in file under `UnitBusOptions.systemd_system_dir`.
+    /// `Config::remove_dropin`, deferring the reload.
     pub async fn remove_dropin(
-        &self,
+        &mut self,
         unit: &str,
         name: &str,
     ) -> Result<crate::types::config::RemoveReport> {
         let unit = util::canonicalize_unit_name(unit)?;
-        util::validate_dropin_name(name)?;
+        let report = self.config.remove_dropin(&unit, name).await?;
+        self.track(unit, report.requires_daemon_reload && !report.dry_run);
+        Ok(report)
+    }
+
+    /// `Config::write_service_unit`, deferring the reload.
+    pub async fn write_service_unit(
+        &mut self,
+        spec: crate::ServiceUnitSpec,
+    ) -> Result<crate::UnitFileWriteReport> {
+        let unit = spec.canonical_unit_name()?;
+        let report = self.config.write_service_unit(spec).await?;
+        self.track(unit, report.requires_daemon_reload && !report.dry_run);
+        Ok(report)
+    }
+
+    /// `Config::remove_unit_file`, deferring the reload.
+    pub async fn remove_unit_file(&mut self, unit: &str) -> Result<crate::UnitFileRemoveReport> {
+        let unit = util::canonicalize_unit_name(unit)?;
+        let report = self.config.remove_unit_file(&unit).await?;
+        self.track(unit, report.requires_daemon_reload && !report.dry_run);
+        Ok(report)
+    }
+
+    /// Hold a logind shutdown inhibitor from now until the next successful `flush`, so a reboot
+    /// cannot land between the batched writes and their reload.
+    ///
+    /// Call it before the first write. A no-op under `UnitBusOptions.dry_run` or while an
+    /// inhibitor is already held.
+    pub async fn inhibit_shutdown(&mut self, why: &str) -> Result<()> {
+        if self.config.inner.opts.dry_run || self.inhibitor.is_some() {
+            return Ok(());
+        }
+        self.inhibitor = Some(self.config.inhibit_shutdown(why).await?);
+        Ok(())
+    }
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(unit = %unit, name = %name, "remove_dropin");
+    /// Units written since the last flush that still need a reload (never dry-run previews).
+    pub fn pending(&self) -> Vec<String> {
+        self.pending.iter().cloned().collect()
+    }
 
-        let unit2 = unit.clone();
-        let name2 = name.to_string();
-        let systemd_system_dir = self.systemd_system_dir()?;
-        let report = blocking::unblock(move || {
-            crate::fsutil::remove_dropin_file(&systemd_system_dir, &unit2, &name2)
-        })
-        .await?;
+    /// Reload once if any write needs it, then re-check `NeedDaemonReload` of those units.
+    ///
+    /// Units that are no longer loaded (e.g. removed unit files) are not re-checked. On error
+    /// the pending set (and a held shutdown inhibitor) is kept, so `flush` can be retried.
+    pub async fn flush(&mut self) -> Result<crate::types::config::ReloadFlushReport> {
+        if self.pending.is_empty() {
+            self.inhibitor = None;
+            return Ok(crate::types::config::ReloadFlushReport {
+                daemon_reload_performed: false,
+                units: Vec::new(),
+                still_need_reload: Vec::new(),
+            });
+        }
 
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %unit,
-            name = %name,
-            changed = report.changed,
-            requires_daemon_reload = report.requires_daemon_reload,
-            "remove_dropin done"
-        );
+        self.config.daemon_reload().await?;
 
-        Ok(report)
+        let unit_api = Units::new(self.config.inner.clone());
+        let mut still_need_reload = Vec::new();
+        for unit in &self.pending {
+            match unit_api.get_unit_properties(unit).await {
+                Ok(props) if props.get_bool("NeedDaemonReload").unwrap_or(false) => {
+                    still_need_reload.push(unit.clone());
+                }
+                Ok(_) | Err(Error::UnitNotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Only a fully successful flush clears the batch; on error it stays queued for a retry.
+        let units = std::mem::take(&mut self.pending);
+        self.inhibitor = None;
+        Ok(crate::types::config::ReloadFlushReport {
+            daemon_reload_performed: true,
+            units: units.into_iter().collect(),
+            still_need_reload,
+        })
     }
 
-    /// Reload systemd manager configuration (`org.freedesktop.systemd1.Manager.Reload`).
-    pub async fn daemon_reload(&self) -> Result<()> {
-        #[cfg(feature = "tracing")]
-        tracing::info!("daemon_reload");
-        self.inner.bus.daemon_reload().await
+    fn track(&mut self, unit: String, requires_daemon_reload: bool) {
+        if requires_daemon_reload {
+            self.pending.insert(unit);
+        }
     }
 }
 
@@ -792,131 +3289,885 @@ impl Tasks {
     /// Run a one-shot transient task using `StartTransientUnit`.
     ///
     /// The transient unit is configured as `Type=oneshot`, without a shell, and routes stdout/stderr
-    /// to journald.
+    /// to journald. With `TaskSpec.idempotency_key` set, the unit stays loaded after it finishes
+    /// (`RemainAfterExit=yes`) until `TaskHandle::release`, and a resubmission of the same key
+    /// returns a handle to it instead of starting a new one.
+    ///
+    /// With `TaskSpec.capture_output` set, stdout/stderr are instead connected to pipes read by
+    /// this process (`StandardOutputFileDescriptor` / `StandardErrorFileDescriptor`) and returned
+    /// by `TaskHandle::wait` in `TaskResult.output`.
     pub async fn run(
         &self,
         spec: crate::types::task::TaskSpec,
     ) -> Result<crate::types::task::TaskHandle> {
-        if spec.argv.is_empty() {
-            return Err(Error::invalid_input("argv must not be empty"));
-        }
-        for arg in &spec.argv {
-            util::validate_no_control("argv", arg)?;
-        }
-        if spec.argv[0].trim().is_empty() {
-            return Err(Error::invalid_input("argv[0] must not be empty"));
-        }
-        for (k, v) in &spec.env {
-            util::validate_env_key(k)?;
-            util::validate_no_control("env value", v)?;
-        }
-        if let Some(workdir) = &spec.workdir {
-            util::validate_no_control("workdir", workdir)?;
-        }
-        if let Some(name_hint) = &spec.name_hint {
-            util::validate_no_control("name_hint", name_hint)?;
-        }
-        if spec.timeout == Duration::from_secs(0) {
-            return Err(Error::invalid_input("timeout must be > 0"));
-        }
-
-        let unit = transient_unit_name(spec.name_hint.as_deref());
-
-        #[cfg(feature = "tracing")]
-        tracing::info!(
-            unit = %unit,
-            argv0 = %spec.argv[0],
-            argc = spec.argv.len(),
-            env_keys = spec.env.len(),
-            has_workdir = spec.workdir.is_some(),
-            timeout_us = duration_to_micros(spec.timeout),
-            "run_task"
-        );
-
-        let mut props: Vec<(String, OwnedValue)> = Vec::new();
-        props.push(("Type".to_string(), owned_value("Type", "oneshot")?));
-
-        let argv0 = spec.argv[0].clone();
-        let exec = vec![(argv0, spec.argv.clone(), false)];
-        props.push(("ExecStart".to_string(), owned_value("ExecStart", exec)?));
-
-        if let Some(workdir) = spec.workdir {
+        let mut props = task_properties(&spec, "oneshot")?;
+        if !spec.capture_output {
+            props.push((
+                "StandardOutput".to_string(),
+                owned_value("StandardOutput", "journal")?,
+            ));
             props.push((
-                "WorkingDirectory".to_string(),
-                owned_value("WorkingDirectory", workdir)?,
+                "StandardError".to_string(),
+                owned_value("StandardError", "journal")?,
             ));
+            let keyed = spec.idempotency_key.is_some();
+            if keyed {
+                // Keep the finished unit loaded so a resubmission finds it instead of rerunning.
+                props.push((
+                    "RemainAfterExit".to_string(),
+                    owned_value("RemainAfterExit", true)?,
+                ));
+            }
+            return self.start_task(&spec, props, keyed).await;
         }
 
-        if !spec.env.is_empty() {
-            let env = spec
-                .env
-                .iter()
-                .map(|(k, v)| format!("{k}={v}"))
-                .collect::<Vec<_>>();
-            props.push(("Environment".to_string(), owned_value("Environment", env)?));
+        if spec.idempotency_key.is_some() {
+            return Err(Error::invalid_input(
+                "capture_output cannot be combined with idempotency_key",
+            ));
         }
-
-        let timeout_us = duration_to_micros(spec.timeout);
+        let max_bytes = usize::try_from(spec.max_output_bytes).unwrap_or(usize::MAX);
+        let (pipes, capture) = crate::capture::open(max_bytes)?;
+        // The D-Bus encoding duplicates the descriptors; ours are closed right away and systemd's
+        // copies are released when the unit stops, which ends the capture.
         props.push((
-            "TimeoutStartUSec".to_string(),
-            owned_value("TimeoutStartUSec", timeout_us)?,
+            "StandardOutputFileDescriptor".to_string(),
+            owned_value(
+                "StandardOutputFileDescriptor",
+                zbus::zvariant::Fd::from(pipes.stdout),
+            )?,
         ));
-
         props.push((
+            "StandardErrorFileDescriptor".to_string(),
+            owned_value(
+                "StandardErrorFileDescriptor",
+                zbus::zvariant::Fd::from(pipes.stderr),
+            )?,
+        ));
+
+        let mut task = self.start_task(&spec, props, false).await?;
+        task.output = Some(Arc::new(capture));
+        Ok(task)
+    }
+
+    /// Run a transient task attached to a freshly allocated PTY (like `systemd-run -t`)
+    /// (feature=`tasks-pty`).
+    ///
+    /// stdin/stdout/stderr of the task are connected to the PTY; the returned handle exposes the
+    /// terminal side as async read/write streams. Nothing is routed to journald.
+    #[cfg(feature = "tasks-pty")]
+    pub async fn run_interactive(
+        &self,
+        spec: crate::types::task::TaskSpec,
+    ) -> Result<crate::types::task::InteractiveTaskHandle> {
+        reject_capture(&spec, "run_interactive")?;
+        let mut props = task_properties(&spec, "oneshot")?;
+        let pty = crate::pty::open_pty()?;
+        props.extend(tty_properties(&pty.tty_path)?);
+
+        let task = self.start_task(&spec, props, false).await?;
+        let tty_path = pty.tty_path.clone();
+        let (reader, writer) = pty.into_streams()?;
+
+        Ok(crate::types::task::InteractiveTaskHandle {
+            task,
+            tty_path,
+            reader,
+            writer,
+        })
+    }
+
+    /// Run a transient socket + service pair (like `systemd-run --socket-property=...`).
+    ///
+    /// systemd holds the listener(s) described by `socket`; the service described by `service` is
+    /// started on the first incoming connection (`Accept=no`, `Type=simple`). Service stdout/stderr
+    /// go to journald.
+    ///
+    /// This waits (bounded by `service.timeout`) for the socket to start listening and returns the
+    /// listen addresses from the socket unit's `Listen` property. Stop the socket and service
+    /// units to tear the pair down; transient units are garbage-collected once inactive.
+    pub async fn run_socket_activated(
+        &self,
+        service: crate::types::task::TaskSpec,
+        socket: crate::types::task::TaskSocketSpec,
+    ) -> Result<crate::types::task::SocketTaskHandle> {
+        reject_capture(&service, "run_socket_activated")?;
+        let mut service_props = task_properties(&service, "simple")?;
+        service_props.push((
             "StandardOutput".to_string(),
             owned_value("StandardOutput", "journal")?,
         ));
-        props.push((
+        service_props.push((
+            "StandardError".to_string(),
+            owned_value("StandardError", "journal")?,
+        ));
+        let socket_props = socket_properties(&socket)?;
+
+        let service_unit = transient_unit_name(&service)?;
+        let stem = service_unit.trim_end_matches(".service");
+        let socket_unit = format!("{stem}.socket");
+
+        crate::telemetry::audited(
+            &self.inner,
+            "run_socket_activated",
+            Some(&socket_unit),
+            async {
+                #[cfg(feature = "tracing")]
+                tracing::info!(
+                    socket_unit = %socket_unit,
+                    service_unit = %service_unit,
+                    argv = %crate::telemetry::display_argv(&self.inner.opts, &service.argv),
+                    listeners = socket.listen_stream.len() + socket.listen_datagram.len(),
+                    "run_socket_activated"
+                );
+
+                let job_path = self
+                    .inner
+                    .bus
+                    .start_transient_unit(
+                        &socket_unit,
+                        UnitStartMode::Fail.as_dbus_str(),
+                        socket_props,
+                        vec![(service_unit.clone(), service_props)],
+                    )
+                    .await?;
+
+                let job = JobHandle {
+                    unit: socket_unit.clone(),
+                    job_path: job_path.to_string(),
+                    inner: JobInner {
+                        root: self.inner.clone(),
+                        kind: JobKind::Start,
+                        invocation_before: None,
+                    },
+                };
+                let socket_outcome = job.wait(service.timeout).await?;
+
+                let listen = match &socket_outcome {
+                    JobOutcome::Success { .. } => {
+                        Units::new(self.inner.clone())
+                            .socket_listeners(&socket_unit)
+                            .await?
+                    }
+                    _ => Vec::new(),
+                };
+
+                Ok(crate::types::task::SocketTaskHandle {
+                    socket_unit: socket_unit.clone(),
+                    service_unit,
+                    socket_outcome,
+                    listen,
+                })
+            },
+        )
+        .await
+    }
+
+    /// Run a task on a schedule with a transient timer + service pair (like
+    /// `systemd-run --on-calendar=...`).
+    ///
+    /// The service is `Type=oneshot` with stdout/stderr in journald, exactly as `run` would
+    /// start it, and is activated each time the timer elapses. This waits (bounded by
+    /// `spec.task.timeout`) for the timer to start. The pair stays until
+    /// `ScheduledTaskHandle::cancel`, a daemon restart or a reboot; transient units do not
+    /// survive those.
+    pub async fn schedule(
+        &self,
+        spec: crate::types::task::TimerTaskSpec,
+    ) -> Result<crate::types::task::ScheduledTaskHandle> {
+        reject_capture(&spec.task, "schedule")?;
+        let mut service_props = task_properties(&spec.task, "oneshot")?;
+        service_props.push((
+            "StandardOutput".to_string(),
+            owned_value("StandardOutput", "journal")?,
+        ));
+        service_props.push((
             "StandardError".to_string(),
             owned_value("StandardError", "journal")?,
         ));
+        let timer_props = timer_properties(&spec)?;
+
+        let service_unit = transient_unit_name(&spec.task)?;
+        let stem = service_unit.trim_end_matches(".service");
+        let timer_unit = format!("{stem}.timer");
+
+        crate::telemetry::audited(&self.inner, "schedule_task", Some(&timer_unit), async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                timer_unit = %timer_unit,
+                service_unit = %service_unit,
+                argv = %crate::telemetry::display_argv(&self.inner.opts, &spec.task.argv),
+                triggers = spec.triggers.len(),
+                "schedule_task"
+            );
+
+            let job_path = self
+                .inner
+                .bus
+                .start_transient_unit(
+                    &timer_unit,
+                    UnitStartMode::Fail.as_dbus_str(),
+                    timer_props,
+                    vec![(service_unit.clone(), service_props)],
+                )
+                .await?;
+
+            let job = JobHandle {
+                unit: timer_unit.clone(),
+                job_path: job_path.to_string(),
+                inner: JobInner {
+                    root: self.inner.clone(),
+                    kind: JobKind::Start,
+                    invocation_before: None,
+                },
+            };
+            let timer_outcome = job.wait(spec.task.timeout).await?;
+
+            Ok(crate::types::task::ScheduledTaskHandle {
+                timer_unit: timer_unit.clone(),
+                service_unit,
+                timer_outcome,
+                inner: self.inner.clone(),
+            })
+        })
+        .await
+    }
+
+    async fn start_task(
+        &self,
+        spec: &crate::types::task::TaskSpec,
+        props: Vec<(String, OwnedValue)>,
+        reuse_existing: bool,
+    ) -> Result<crate::types::task::TaskHandle> {
+        let unit = transient_unit_name(spec)?;
+
+        crate::telemetry::audited(&self.inner, "run_task", Some(&unit), async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                unit = %unit,
+                argv = %crate::telemetry::display_argv(&self.inner.opts, &spec.argv),
+                env = %crate::telemetry::display_env(&self.inner.opts, &spec.env),
+                has_workdir = spec.workdir.is_some(),
+                timeout_us = duration_to_micros(spec.timeout),
+                "run_task"
+            );
+
+            if reuse_existing && let Some(task) = self.existing_task(&unit).await? {
+                return Ok(task);
+            }
+
+            let job_path = match self
+                .inner
+                .bus
+                .start_transient_unit(
+                    &unit,
+                    UnitStartMode::Replace.as_dbus_str(),
+                    props,
+                    Vec::new(),
+                )
+                .await
+            {
+                Ok(job_path) => job_path,
+                // Lost a race with a concurrent submission of the same key.
+                Err(Error::DbusError { name, message })
+                    if reuse_existing && name.ends_with(".UnitExists") =>
+                {
+                    if let Some(task) = self.existing_task(&unit).await? {
+                        return Ok(task);
+                    }
+                    return Err(Error::DbusError { name, message });
+                }
+                Err(e) => return Err(e),
+            };
+
+            crate::telemetry::record_job_path(job_path.as_str());
+
+            Ok(crate::types::task::TaskHandle {
+                unit: unit.clone(),
+                job_path: job_path.to_string(),
+                inner: JobInner {
+                    root: self.inner.clone(),
+                    kind: JobKind::Start,
+                    invocation_before: None,
+                },
+                output: None,
+            })
+        })
+        .await
+    }
 
-        let job_path = self
+    /// Handle to the loaded transient unit `unit`, attached to its pending start job if any
+    /// (`None` if systemd no longer knows the unit).
+    async fn existing_task(&self, unit: &str) -> Result<Option<crate::types::task::TaskHandle>> {
+        let unit_path = match self.inner.bus.get_unit_path(unit).await {
+            Ok(path) => path,
+            Err(Error::UnitNotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let job = self
             .inner
             .bus
-            .start_transient_unit(&unit, UnitStartMode::Replace.as_dbus_str(), props)
+            .get_property(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE, "Job")
             .await?;
+        // `(0, "/")` when no job is queued; `wait` then reports the current unit state.
+        let (_, job_path) =
+            <(u32, OwnedObjectPath)>::try_from(job).map_err(|e| Error::IoError {
+                context: format!("decode Job property of {unit}: {e}"),
+            })?;
 
         #[cfg(feature = "tracing")]
-        tracing::info!(unit = %unit, job_path = %job_path.as_str(), "run_task started");
+        tracing::info!(unit = %unit, job_path = %job_path.as_str(), "run_task reused existing unit");
 
-        Ok(crate::types::task::TaskHandle {
-            unit,
+        Ok(Some(crate::types::task::TaskHandle {
+            unit: unit.to_string(),
             job_path: job_path.to_string(),
             inner: JobInner {
                 root: self.inner.clone(),
                 kind: JobKind::Start,
+                invocation_before: None,
             },
+            output: None,
+        }))
+    }
+}
+
+/// Validate a `TaskSpec` and build the transient unit properties shared by all task flavors
+/// (everything except stdio routing).
+#[cfg(feature = "tasks")]
+pub(crate) fn task_properties(
+    spec: &crate::types::task::TaskSpec,
+    service_type: &'static str,
+) -> Result<Vec<(String, OwnedValue)>> {
+    if spec.argv.is_empty() {
+        return Err(Error::invalid_input("argv must not be empty"));
+    }
+    for arg in &spec.argv {
+        util::validate_no_control("argv", arg)?;
+    }
+    if spec.argv[0].trim().is_empty() {
+        return Err(Error::invalid_input("argv[0] must not be empty"));
+    }
+    for (k, v) in &spec.env {
+        util::validate_env_key(k)?;
+        util::validate_no_control("env value", v)?;
+    }
+    if let Some(workdir) = &spec.workdir {
+        util::validate_no_control("workdir", workdir)?;
+    }
+    if let Some(name_hint) = &spec.name_hint {
+        util::validate_no_control("name_hint", name_hint)?;
+    }
+    validate_naming(&spec.naming)?;
+    if let Some(key) = &spec.idempotency_key {
+        if key.is_empty() {
+            return Err(Error::invalid_input("idempotency_key must not be empty"));
+        }
+        util::validate_no_control("idempotency_key", key)?;
+    }
+    if spec.timeout == Duration::from_secs(0) {
+        return Err(Error::invalid_input("timeout must be > 0"));
+    }
+    if spec.capture_output && spec.max_output_bytes == 0 {
+        return Err(Error::invalid_input("max_output_bytes must be > 0"));
+    }
+    let after = canonicalize_unit_names(&spec.after)?;
+    let requires = canonicalize_unit_names(&spec.requires)?;
+
+    let mut props: Vec<(String, OwnedValue)> = Vec::new();
+    props.push(("Type".to_string(), owned_value("Type", service_type)?));
+
+    let argv0 = spec.argv[0].clone();
+    let exec = vec![(argv0, spec.argv.clone(), false)];
+    props.push(("ExecStart".to_string(), owned_value("ExecStart", exec)?));
+
+    if let Some(workdir) = &spec.workdir {
+        props.push((
+            "WorkingDirectory".to_string(),
+            owned_value("WorkingDirectory", workdir.clone())?,
+        ));
+    }
+
+    if !spec.env.is_empty() {
+        let env = spec
+            .env
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>();
+        props.push(("Environment".to_string(), owned_value("Environment", env)?));
+    }
+
+    let timeout_us = duration_to_micros(spec.timeout);
+    props.push((
+        "TimeoutStartUSec".to_string(),
+        owned_value("TimeoutStartUSec", timeout_us)?,
+    ));
+    match (spec.runtime_max, spec.runtime_randomized_extra) {
+        (Some(max), _) if max.is_zero() => {
+            return Err(Error::invalid_input("runtime_max must be > 0"));
+        }
+        (None, Some(_)) => {
+            return Err(Error::invalid_input(
+                "runtime_randomized_extra requires runtime_max",
+            ));
+        }
+        (max, extra) => {
+            for (key, value) in [
+                ("RuntimeMaxUSec", max),
+                ("RuntimeRandomizedExtraUSec", extra),
+            ] {
+                if let Some(value) = value {
+                    let us = duration_to_micros(value);
+                    props.push((key.to_string(), owned_value(key, us)?));
+                }
+            }
+        }
+    }
+
+    if !after.is_empty() {
+        props.push(("After".to_string(), owned_value("After", after)?));
+    }
+    if !requires.is_empty() {
+        props.push(("Requires".to_string(), owned_value("Requires", requires)?));
+    }
+
+    let mut load = Vec::new();
+    let mut set = Vec::new();
+    let mut set_encrypted = Vec::new();
+    for credential in &spec.credentials {
+        credential.validate(false)?;
+        match credential {
+            crate::Credential::Load { id, path } => load.push((id.clone(), path.clone())),
+            crate::Credential::Set { id, value } => {
+                set.push((id.clone(), value.as_bytes().to_vec()));
+            }
+            crate::Credential::SetEncrypted { id, data } => {
+                set_encrypted.push((id.clone(), crate::types::credential::decode_base64(data)?))
+            }
+        }
+    }
+    for (key, creds) in [
+        ("SetCredential", set),
+        ("SetCredentialEncrypted", set_encrypted),
+    ] {
+        if !creds.is_empty() {
+            props.push((key.to_string(), owned_value(key, creds)?));
+        }
+    }
+    if !load.is_empty() {
+        props.push((
+            "LoadCredential".to_string(),
+            owned_value("LoadCredential", load)?,
+        ));
+    }
+
+    Ok(props)
+}
+
+#[cfg(feature = "tasks")]
+fn socket_properties(
+    socket: &crate::types::task::TaskSocketSpec,
+) -> Result<Vec<(String, OwnedValue)>> {
+    if socket.listen_stream.is_empty() && socket.listen_datagram.is_empty() {
+        return Err(Error::invalid_input(
+            "socket spec must contain at least one listen address",
+        ));
+    }
+
+    let mut listen: Vec<(String, String)> = Vec::new();
+    for (kind, addrs) in [
+        ("ListenStream", &socket.listen_stream),
+        ("ListenDatagram", &socket.listen_datagram),
+    ] {
+        for addr in addrs {
+            util::validate_no_control("listen address", addr)?;
+            let addr = addr.trim();
+            if addr.is_empty() {
+                return Err(Error::invalid_input("listen address must not be empty"));
+            }
+            listen.push((kind.to_string(), addr.to_string()));
+        }
+    }
+
+    let mut props: Vec<(String, OwnedValue)> = Vec::new();
+    props.push(("Listen".to_string(), owned_value("Listen", listen)?));
+    if let Some(mode) = socket.socket_mode {
+        props.push(("SocketMode".to_string(), owned_value("SocketMode", mode)?));
+    }
+    Ok(props)
+}
+
+#[cfg(feature = "tasks")]
+fn timer_properties(spec: &crate::types::task::TimerTaskSpec) -> Result<Vec<(String, OwnedValue)>> {
+    use crate::types::task::TimerTrigger;
+
+    if spec.triggers.is_empty() {
+        return Err(Error::invalid_input(
+            "timer spec must contain at least one trigger",
+        ));
+    }
+    let mut calendar: Vec<(String, String)> = Vec::new();
+    let mut monotonic: Vec<(String, u64)> = Vec::new();
+    for trigger in &spec.triggers {
+        match trigger {
+            TimerTrigger::Calendar(expr) => {
+                calendar.push(("OnCalendar".to_string(), expr.as_str().to_string()));
+            }
+            TimerTrigger::OnActive(d) => {
+                monotonic.push(("OnActiveUSec".to_string(), duration_to_micros(*d)));
+            }
+            TimerTrigger::OnUnitActive(d) => {
+                if d.is_zero() {
+                    return Err(Error::invalid_input("OnUnitActive interval must be > 0"));
+                }
+                monotonic.push(("OnUnitActiveUSec".to_string(), duration_to_micros(*d)));
+            }
+        }
+    }
+    // `OnUnitActiveSec=` never elapses for a unit that has not run yet; start the cycle.
+    if calendar.is_empty()
+        && monotonic
+            .iter()
+            .all(|(key, _)| key.as_str() == "OnUnitActiveUSec")
+    {
+        monotonic.insert(0, ("OnActiveUSec".to_string(), 0));
+    }
+
+    let mut props: Vec<(String, OwnedValue)> = Vec::new();
+    if !calendar.is_empty() {
+        props.push((
+            "TimersCalendar".to_string(),
+            owned_value("TimersCalendar", calendar)?,
+        ));
+    }
+    if !monotonic.is_empty() {
+        props.push((
+            "TimersMonotonic".to_string(),
+            owned_value("TimersMonotonic", monotonic)?,
+        ));
+    }
+    if spec.persistent {
+        props.push(("Persistent".to_string(), owned_value("Persistent", true)?));
+    }
+    for (key, value) in [
+        ("AccuracyUSec", spec.accuracy),
+        ("RandomizedDelayUSec", spec.randomized_delay),
+    ] {
+        if let Some(value) = value {
+            props.push((
+                key.to_string(),
+                owned_value(key, duration_to_micros(value))?,
+            ));
+        }
+    }
+    Ok(props)
+}
+
+/// Next elapse of a timer from its `NextElapseUSec*` properties, mapping the monotonic one to
+/// wall-clock time through the timer's own `ActiveEnterTimestamp{,Monotonic}` pair.
+#[cfg(feature = "tasks")]
+fn next_elapse_time(
+    realtime_us: u64,
+    monotonic_us: u64,
+    enter_realtime_us: u64,
+    enter_monotonic_us: u64,
+) -> Option<std::time::SystemTime> {
+    let realtime = (realtime_us != 0 && realtime_us != u64::MAX)
+        .then(|| std::time::UNIX_EPOCH + Duration::from_micros(realtime_us));
+    let monotonic =
+        (monotonic_us != 0 && monotonic_us != u64::MAX && enter_realtime_us != 0).then(|| {
+            let enter = std::time::UNIX_EPOCH + Duration::from_micros(enter_realtime_us);
+            if monotonic_us >= enter_monotonic_us {
+                enter + Duration::from_micros(monotonic_us - enter_monotonic_us)
+            } else {
+                enter - Duration::from_micros(enter_monotonic_us - monotonic_us)
+            }
+        });
+    match (realtime, monotonic) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(feature = "tasks")]
+impl crate::types::task::ScheduledTaskHandle {
+    /// When the timer elapses next (`None` if it will not elapse again or is gone).
+    pub async fn next_elapse(&self) -> Result<Option<std::time::SystemTime>> {
+        crate::telemetry::traced("next_elapse", Some(&self.timer_unit), async {
+            let unit_path = match self.inner.bus.get_unit_path(&self.timer_unit).await {
+                Ok(path) => path,
+                Err(Error::UnitNotFound { .. }) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+            let timer = self
+                .inner
+                .bus
+                .get_all_properties(unit_path.as_str(), SYSTEMD_TIMER_INTERFACE)
+                .await?;
+            let unit = self
+                .inner
+                .bus
+                .get_all_properties(unit_path.as_str(), SYSTEMD_UNIT_INTERFACE)
+                .await?;
+            let get = |map: &HashMap<String, OwnedValue>, key| {
+                map.get(key)
+                    .and_then(|v| u64::try_from(v).ok())
+                    .unwrap_or(0)
+            };
+            Ok(next_elapse_time(
+                get(&timer, "NextElapseUSecRealtime"),
+                get(&timer, "NextElapseUSecMonotonic"),
+                get(&unit, "ActiveEnterTimestamp"),
+                get(&unit, "ActiveEnterTimestampMonotonic"),
+            ))
         })
+        .await
+    }
+
+    /// Stop the timer and any run of the task in progress; systemd then garbage-collects both
+    /// transient units. Units that are already gone are ignored.
+    pub async fn cancel(&self) -> Result<()> {
+        crate::telemetry::audited(
+            &self.inner,
+            "cancel_scheduled_task",
+            Some(&self.timer_unit),
+            async {
+                for unit in [&self.timer_unit, &self.service_unit] {
+                    match self
+                        .inner
+                        .bus
+                        .stop_unit(unit, UnitStartMode::Replace.as_dbus_str())
+                        .await
+                    {
+                        Ok(_) | Err(Error::UnitNotFound { .. }) => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok(())
+            },
+        )
+        .await
     }
 }
 
+#[cfg(feature = "tasks")]
+fn canonicalize_unit_names(units: &[String]) -> Result<Vec<String>> {
+    let mut out = Vec::with_capacity(units.len());
+    for unit in units {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if !out.contains(&unit) {
+            out.push(unit);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "tasks")]
+fn reject_capture(spec: &crate::types::task::TaskSpec, entry: &str) -> Result<()> {
+    if spec.capture_output {
+        return Err(Error::invalid_input(format!(
+            "capture_output is only supported by Tasks::run, not Tasks::{entry}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "tasks-pty")]
+fn tty_properties(tty_path: &str) -> Result<Vec<(String, OwnedValue)>> {
+    let mut props: Vec<(String, OwnedValue)> = Vec::new();
+    for key in ["StandardInput", "StandardOutput", "StandardError"] {
+        props.push((key.to_string(), owned_value(key, "tty")?));
+    }
+    props.push((
+        "TTYPath".to_string(),
+        owned_value("TTYPath", tty_path.to_string())?,
+    ));
+    Ok(props)
+}
+
 #[cfg(feature = "tasks")]
 impl crate::types::task::TaskHandle {
     /// Wait for the transient task to finish and return `TaskResult`.
     ///
-    /// This method always returns the final `UnitStatus` plus best-effort exit information.
+    /// This method always returns the final `UnitStatus` plus best-effort exit information. For
+    /// tasks started with `TaskSpec.capture_output`, it also waits (within the same `timeout`) for
+    /// the output pipes to close and returns their contents in `TaskResult.output`.
     pub async fn wait(&self, timeout: Duration) -> Result<crate::types::task::TaskResult> {
         if timeout == Duration::from_secs(0) {
             return Err(Error::invalid_input("timeout must be > 0"));
         }
+        let deadline = std::time::Instant::now() + timeout;
+
+        let outcome = self
+            .inner
+            .wait_job(&self.unit, &self.job_path, timeout)
+            .await?;
+        let unit_status = match outcome {
+            JobOutcome::Success { unit_status, .. }
+            | JobOutcome::Failed { unit_status, .. }
+            | JobOutcome::Canceled { unit_status } => unit_status,
+        };
+
+        let (exit_status, signal) = decode_exit_status(&unit_status);
+        let output = match &self.output {
+            Some(capture) => Some(capture.collect(deadline).await),
+            None => None,
+        };
+        Ok(crate::types::task::TaskResult {
+            unit_status,
+            exit_status,
+            signal,
+            output,
+        })
+    }
+
+    /// Like `wait`, but return `Error::Cancelled` as soon as `shutdown` fires.
+    ///
+    /// The transient unit keeps running; stop it (or let `TaskSpec` limits end it) if the agent
+    /// must not leave work behind.
+    pub async fn wait_with_shutdown(
+        &self,
+        timeout: Duration,
+        shutdown: &crate::shutdown::ShutdownToken,
+    ) -> Result<crate::types::task::TaskResult> {
+        shutdown.run("wait_task", self.wait(timeout)).await
+    }
+
+    /// Stop the task's unit and clear a failed state, so systemd unloads it.
+    ///
+    /// This is how a unit kept loaded for `TaskSpec.idempotency_key` is cleaned up; afterwards
+    /// the key starts a new run. Releasing a unit systemd no longer knows is a no-op.
+    pub async fn release(&self) -> Result<()> {
+        let root = &self.inner.root;
+        crate::telemetry::audited(root, "release_task", Some(&self.unit), async {
+            #[cfg(feature = "tracing")]
+            tracing::info!(unit = %self.unit, "release_task");
+
+            match root
+                .bus
+                .stop_unit(&self.unit, UnitStartMode::Replace.as_dbus_str())
+                .await
+            {
+                Ok(_) | Err(Error::UnitNotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+            match root.bus.reset_failed_unit(&self.unit).await {
+                Ok(()) | Err(Error::UnitNotFound { .. }) => Ok(()),
+                Err(e) => Err(e),
+            }
+        })
+        .await
+    }
+
+    /// Follow the task's journal output while it runs.
+    ///
+    /// Requires a journald backend (`journal-sdjournal` or `journal-cli`); entries are delivered
+    /// in journal order via `TaskLogStream::next`.
+    pub fn logs(
+        &self,
+        opts: crate::types::task::TaskLogOptions,
+    ) -> Result<crate::types::task::TaskLogStream> {
+        if opts.poll_interval == Duration::from_secs(0) {
+            return Err(Error::invalid_input("poll_interval must be > 0"));
+        }
+        if opts.timeout == Duration::from_secs(0) {
+            return Err(Error::invalid_input("timeout must be > 0"));
+        }
+        if opts.batch_limit == 0 {
+            return Err(Error::invalid_input("batch_limit must be > 0"));
+        }
+
+        Ok(crate::types::task::TaskLogStream {
+            task: self.clone(),
+            deadline: std::time::Instant::now() + opts.timeout,
+            opts,
+            cursor: None,
+            buffered: std::collections::VecDeque::new(),
+            draining: false,
+            finished: false,
+        })
+    }
+}
+
+#[cfg(feature = "tasks")]
+impl crate::types::task::TaskLogStream {
+    /// Next journal entry of the task.
+    ///
+    /// Returns `Ok(None)` once the task has finished and its output is drained, or
+    /// `Error::Timeout` when `TaskLogOptions.timeout` passes first.
+    pub async fn next(&mut self) -> Result<Option<crate::types::journal::JournalEntry>> {
+        loop {
+            if let Some(entry) = self.buffered.pop_front() {
+                return Ok(Some(entry));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+
+            // Decide whether this poll is the final drain before querying, so no entry written
+            // between the query and the state check is lost.
+            let done = self.draining || self.task_done().await?;
+            let fetched = self.poll_journal().await?;
+            if fetched > 0 {
+                continue;
+            }
+            if done {
+                self.finished = true;
+                continue;
+            }
+
+            let now = std::time::Instant::now();
+            if now >= self.deadline {
+                self.finished = true;
+                return Err(Error::Timeout {
+                    action: "task_logs",
+                    timeout: self.opts.timeout,
+                });
+            }
+            let wait = std::cmp::min(self.opts.poll_interval, self.deadline - now);
+            crate::runtime::sleep(wait).await;
+        }
+    }
 
-        let outcome = self
-            .inner
-            .wait_job(&self.unit, &self.job_path, timeout)
-            .await?;
-        let unit_status = match outcome {
-            JobOutcome::Success { unit_status }
-            | JobOutcome::Failed { unit_status, .. }
-            | JobOutcome::Canceled { unit_status } => unit_status,
+    async fn task_done(&mut self) -> Result<bool> {
+        let root = &self.task.inner.root;
+        if root.bus.job_exists(&self.task.job_path).await? {
+            return Ok(false);
+        }
+        let done = match Units::new(root.clone()).get_status(&self.task.unit).await {
+            Ok(status) => matches!(
+                status.active_state,
+                ActiveState::Inactive | ActiveState::Failed
+            ),
+            Err(Error::UnitNotFound { .. }) => true,
+            Err(e) => return Err(e),
         };
+        self.draining = done;
+        Ok(done)
+    }
 
-        let (exit_status, signal) = decode_exit_status(&unit_status);
-        Ok(crate::types::task::TaskResult {
-            unit_status,
-            exit_status,
-            signal,
-        })
+    async fn poll_journal(&mut self) -> Result<usize> {
+        let filter = crate::types::journal::JournalFilter {
+            unit: Some(self.task.unit.clone()),
+            after_cursor: self.cursor.clone(),
+            limit: self.opts.batch_limit,
+            max_message_bytes: self.opts.max_message_bytes,
+            ..Default::default()
+        };
+        let res = match crate::Journal::new(self.task.inner.root.clone())
+            .query(filter)
+            .await
+        {
+            Ok(res) => res,
+            // Keep what was read; the next poll resumes from its cursor.
+            Err(Error::JournalTimeout { partial, .. }) if !partial.entries.is_empty() => *partial,
+            Err(e) => return Err(e),
+        };
+        if res.next_cursor.is_some() {
+            self.cursor = res.next_cursor;
+        }
+        let n = res.entries.len();
+        self.buffered.extend(res.entries);
+        Ok(n)
     }
 }
 
@@ -938,39 +4189,94 @@ fn decode_exit_status(status: &UnitStatus) -> (Option<i32>, Option<i32>) {
 }
 
 #[cfg(feature = "tasks")]
-fn duration_to_micros(d: Duration) -> u64 {
+pub(crate) fn duration_to_micros(d: Duration) -> u64 {
     let us = d.as_micros();
     u64::try_from(us).unwrap_or(u64::MAX)
 }
 
-#[cfg(feature = "tasks")]
-fn owned_value<T>(context: &'static str, v: T) -> Result<OwnedValue>
+pub(crate) fn owned_value<T>(context: &'static str, v: T) -> Result<OwnedValue>
 where
     zbus::zvariant::Value<'static>: From<T>,
 {
     let value: zbus::zvariant::Value<'static> = zbus::zvariant::Value::from(v);
     OwnedValue::try_from(value).map_err(|e| Error::IoError {
-        context: format!("encode transient unit property {context}: {e}"),
+        context: format!("encode unit property {context}: {e}"),
     })
 }
 
 #[cfg(feature = "tasks")]
-fn transient_unit_name(name_hint: Option<&str>) -> String {
-    let now = std::time::SystemTime::now();
-    let ts = match now.duration_since(std::time::UNIX_EPOCH) {
-        Ok(d) => d.as_secs(),
-        Err(_) => 0,
+fn validate_naming(naming: &crate::types::task::NamingPolicy) -> Result<()> {
+    let prefix = &naming.prefix;
+    if prefix.is_empty() || prefix.len() > 32 {
+        return Err(Error::invalid_input("naming.prefix must be 1..=32 bytes"));
+    }
+    if !prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::invalid_input(format!(
+            "naming.prefix may only contain [A-Za-z0-9_-]: {prefix}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "tasks")]
+fn transient_unit_name(spec: &crate::types::task::TaskSpec) -> Result<String> {
+    let hostname = if spec.naming.include_hostname {
+        let raw = std::fs::read_to_string("/proc/sys/kernel/hostname").map_err(|e| {
+            Error::BackendUnavailable {
+                backend: "procfs",
+                detail: format!("read /proc/sys/kernel/hostname: {e}"),
+            }
+        })?;
+        Some(raw)
+    } else {
+        None
+    };
+
+    let suffix = match &spec.idempotency_key {
+        Some(key) => format!("{:016x}", util::fnv1a64(util::FNV_OFFSET, key.as_bytes())),
+        None => {
+            let now = std::time::SystemTime::now();
+            let ts = match now.duration_since(std::time::UNIX_EPOCH) {
+                Ok(d) => d.as_secs(),
+                Err(_) => 0,
+            };
+
+            let n = TRANSIENT_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let pid = u64::from(std::process::id());
+            let nonce = (ts ^ n ^ pid).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            format!("{ts}-{nonce:016x}")
+        }
     };
 
-    let n = TRANSIENT_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-    let pid = u64::from(std::process::id());
-    let nonce = (ts ^ n ^ pid).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    Ok(compose_transient_name(
+        &spec.naming.prefix,
+        hostname.as_deref(),
+        spec.name_hint.as_deref(),
+        &suffix,
+    ))
+}
 
+#[cfg(feature = "tasks")]
+fn compose_transient_name(
+    prefix: &str,
+    hostname: Option<&str>,
+    name_hint: Option<&str>,
+    suffix: &str,
+) -> String {
+    let host = hostname
+        .and_then(|h| h.trim().split('.').next())
+        .and_then(sanitize_unit_name_hint);
     let hint = name_hint.and_then(sanitize_unit_name_hint);
-    match hint {
-        Some(h) => format!("unitbus-{h}-{ts}-{nonce:016x}.service"),
-        None => format!("unitbus-{ts}-{nonce:016x}.service"),
+
+    let mut name = prefix.to_string();
+    for part in [host, hint].into_iter().flatten() {
+        name.push('-');
+        name.push_str(&part);
     }
+    format!("{name}-{suffix}.service")
 }
 
 #[cfg(feature = "tasks")]
@@ -994,6 +4300,326 @@ fn sanitize_unit_name_hint(input: &str) -> Option<String> {
     }
 }
 
+/// Upper bound for one `systemd-analyze calendar` run.
+#[cfg(feature = "config")]
+const ANALYZE_CALENDAR_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[cfg(feature = "config")]
+async fn systemd_analyze_calendar(expr: &str, iterations: u32) -> Result<crate::CalendarAnalysis> {
+    let mut cmd = async_process::Command::new("systemd-analyze");
+    cmd.arg("calendar")
+        .arg(format!("--iterations={iterations}"))
+        .arg("--")
+        .arg(expr)
+        .kill_on_drop(true);
+
+    let run = std::pin::pin!(cmd.output());
+    let timer = crate::runtime::sleep(ANALYZE_CALENDAR_TIMEOUT);
+    let output = match futures_util::future::select(run, timer).await {
+        futures_util::future::Either::Left((output, _)) => output,
+        // Dropping the pending run kills the child.
+        futures_util::future::Either::Right(((), _)) => {
+            return Err(Error::Timeout {
+                action: "systemd-analyze calendar",
+                timeout: ANALYZE_CALENDAR_TIMEOUT,
+            });
+        }
+    }
+    .map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Error::BackendUnavailable {
+            backend: "systemd-analyze",
+            detail: "systemd-analyze not found in PATH".to_string(),
+        },
+        _ => Error::IoError {
+            context: format!("run systemd-analyze: {e}"),
+        },
+    })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let (stderr, _) =
+            util::truncate_string_bytes(String::from_utf8_lossy(&output.stderr).trim(), 1024);
+        return Err(Error::invalid_input(format!(
+            "calendar {expr:?} rejected by systemd-analyze: {stderr}"
+        )));
+    }
+    crate::CalendarAnalysis::from_output(&stdout).ok_or_else(|| Error::ParseError {
+        context: "systemd-analyze calendar output".to_string(),
+        sample: util::truncate_string_bytes(&stdout, 512).0,
+    })
+}
+
+/// `UnitFileState` values that `systemctl is-enabled` reports as enabled.
+fn is_enabled_state(state: &str) -> bool {
+    matches!(state, "enabled" | "enabled-runtime")
+}
+
+fn is_active_like(state: &ActiveState) -> bool {
+    matches!(
+        state,
+        ActiveState::Active | ActiveState::Activating | ActiveState::Reloading
+    )
+}
+
+/// Validate `start_ordered` arguments and canonicalize `units`, dropping duplicates.
+fn ordered_start_set(units: &[&str], timeout: Duration) -> Result<Vec<String>> {
+    if units.is_empty() {
+        return Err(Error::invalid_input("units must not be empty"));
+    }
+    if timeout.is_zero() {
+        return Err(Error::invalid_input("timeout must be > 0"));
+    }
+    let mut set = Vec::with_capacity(units.len());
+    for unit in units {
+        let unit = util::canonicalize_unit_name(unit)?;
+        if !set.contains(&unit) {
+            set.push(unit);
+        }
+    }
+    Ok(set)
+}
+
+type WaveJob = std::pin::Pin<Box<dyn Future<Output = (usize, crate::WaveUnitResult)> + Send>>;
+
+/// State of an ordered start, advanced one `OrderedStartEvent` at a time.
+struct OrderedStart {
+    units: Units,
+    timeout: Duration,
+    waves: Vec<crate::StartWave>,
+    /// Index of the wave whose jobs are in `running`, or the next wave to start.
+    wave: usize,
+    /// Results of the current wave by position in `StartWave::units`.
+    slots: Vec<Option<crate::WaveUnitResult>>,
+    running: futures_util::stream::FuturesUnordered<WaveJob>,
+    queued: std::collections::VecDeque<crate::OrderedStartEvent>,
+    failed_wave: Option<usize>,
+    finished: bool,
+}
+
+impl OrderedStart {
+    fn new(units: Units, waves: Vec<Vec<String>>, timeout: Duration) -> Self {
+        Self {
+            units,
+            timeout,
+            waves: waves
+                .into_iter()
+                .map(|units| crate::StartWave {
+                    units,
+                    results: Vec::new(),
+                })
+                .collect(),
+            wave: 0,
+            slots: Vec::new(),
+            running: futures_util::stream::FuturesUnordered::new(),
+            queued: std::collections::VecDeque::new(),
+            failed_wave: None,
+            finished: false,
+        }
+    }
+
+    fn report(&self) -> crate::OrderedStartReport {
+        crate::OrderedStartReport {
+            waves: self.waves.clone(),
+            failed_wave: self.failed_wave,
+        }
+    }
+
+    async fn next_event(&mut self) -> Option<crate::OrderedStartEvent> {
+        loop {
+            if let Some(event) = self.queued.pop_front() {
+                return Some(event);
+            }
+            if let Some((pos, result)) = self.running.next().await {
+                self.slots[pos] = Some(result.clone());
+                return Some(crate::OrderedStartEvent::UnitFinished {
+                    wave: self.wave,
+                    result: Box::new(result),
+                });
+            }
+            if self.finished {
+                return None;
+            }
+
+            if !self.slots.is_empty() {
+                let results: Vec<_> = std::mem::take(&mut self.slots)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                let ok = results
+                    .iter()
+                    .all(|r| matches!(r.outcome, Some(JobOutcome::Success { .. })));
+                self.waves[self.wave].results = results;
+                if !ok {
+                    self.failed_wave = Some(self.wave);
+                }
+                self.wave += 1;
+            }
+            if self.failed_wave.is_some() || self.wave >= self.waves.len() {
+                self.finished = true;
+                return Some(crate::OrderedStartEvent::Finished(self.report()));
+            }
+            self.start_wave().await;
+        }
+    }
+
+    /// Queue start jobs for the current wave and the events announcing them.
+    async fn start_wave(&mut self) {
+        let units = self.waves[self.wave].units.clone();
+        self.queued
+            .push_back(crate::OrderedStartEvent::WaveStarted {
+                wave: self.wave,
+                units: units.clone(),
+            });
+        self.slots = vec![None; units.len()];
+        for (pos, unit) in units.into_iter().enumerate() {
+            let job = self.units.start(&unit, UnitStartMode::Replace).await;
+            if job.is_ok() {
+                self.queued
+                    .push_back(crate::OrderedStartEvent::UnitStarted {
+                        wave: self.wave,
+                        unit: unit.clone(),
+                    });
+            }
+            let timeout = self.timeout;
+            self.running.push(Box::pin(async move {
+                let (outcome, error) = match async { job?.wait(timeout).await }.await {
+                    Ok(outcome) => (Some(outcome), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                (
+                    pos,
+                    crate::WaveUnitResult {
+                        unit,
+                        outcome,
+                        error,
+                    },
+                )
+            }));
+        }
+    }
+}
+
+impl futures_util::Stream for crate::OrderedStartProgress {
+    type Item = crate::OrderedStartEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+/// Group `units` into start waves from `preds` (unit -> units that must start first), keeping
+/// the input order within a wave.
+fn start_waves(
+    units: &[String],
+    preds: &HashMap<String, BTreeSet<String>>,
+) -> Result<Vec<Vec<String>>> {
+    let mut started = HashSet::new();
+    let mut pending: Vec<&String> = units.iter().collect();
+    let mut waves = Vec::new();
+    while !pending.is_empty() {
+        let (ready, blocked): (Vec<&String>, Vec<&String>) = pending.into_iter().partition(|u| {
+            preds
+                .get(*u)
+                .is_none_or(|p| p.iter().all(|d| started.contains(d)))
+        });
+        if ready.is_empty() {
+            let blocked: Vec<&str> = blocked.iter().map(|u| u.as_str()).collect();
+            return Err(Error::invalid_input(format!(
+                "ordering cycle among: {}",
+                blocked.join(", ")
+            )));
+        }
+        started.extend(ready.iter().map(|u| (*u).clone()));
+        waves.push(ready.into_iter().cloned().collect());
+        pending = blocked;
+    }
+    Ok(waves)
+}
+
+/// Run `opts.probes` in order for a unit that settled; the first probe that times out turns the
+/// outcome into `NotReady`.
+#[cfg(feature = "probes")]
+async fn run_probes(
+    opts: &crate::VerifyOptions,
+    settled_for: Duration,
+    unit_status: UnitStatus,
+) -> Result<crate::VerifiedOutcome> {
+    for probe in &opts.probes {
+        match probe.wait(opts.probe_timeout).await {
+            Ok(_) => {}
+            Err(Error::Timeout { .. }) => {
+                return Ok(crate::VerifiedOutcome::NotReady {
+                    probe: probe.to_string(),
+                    unit_status,
+                });
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(crate::VerifiedOutcome::Stable {
+        settled_for,
+        unit_status,
+    })
+}
+
+/// Reject `action` under `UnitBusOptions.dry_run` (for writers that cannot preview).
+#[cfg(feature = "config")]
+fn reject_dry_run(inner: &crate::Inner, action: &str) -> Result<()> {
+    if inner.opts.dry_run {
+        return Err(Error::invalid_input(format!(
+            "{action} cannot run under dry_run"
+        )));
+    }
+    Ok(())
+}
+
+/// Canonicalize and validate a drop-in spec before it is rendered (see `Config::apply_dropin`).
+#[cfg(feature = "config")]
+fn prepare_dropin(spec: &mut crate::types::config::DropInSpec) -> Result<()> {
+    spec.unit = util::canonicalize_unit_name(&spec.unit)?;
+    util::validate_dropin_name(&spec.name)?;
+    for key in spec.environment.keys() {
+        util::validate_env_key(key)?;
+    }
+    Ok(())
+}
+
+/// `true` once a unit has no running processes: inactive, failed, or waiting for `Restart=`.
+fn processes_gone(status: &UnitStatus) -> bool {
+    matches!(
+        status.active_state,
+        ActiveState::Inactive | ActiveState::Failed
+    ) || status.sub_state.as_deref() == Some("auto-restart")
+}
+
+/// Type-specific D-Bus interface of a unit (`Service` for `.service`, ...).
+fn type_interface(unit: &str) -> Option<&'static str> {
+    cgroup_interface(unit).or(Some(match unit.rsplit_once('.')?.1 {
+        "timer" => SYSTEMD_TIMER_INTERFACE,
+        "path" => SYSTEMD_PATH_INTERFACE,
+        "automount" => "org.freedesktop.systemd1.Automount",
+        "device" => "org.freedesktop.systemd1.Device",
+        "target" => "org.freedesktop.systemd1.Target",
+        _ => return None,
+    }))
+}
+
+/// D-Bus interface carrying `ControlGroup` for the unit types that own a cgroup.
+fn cgroup_interface(unit: &str) -> Option<&'static str> {
+    Some(match unit.rsplit_once('.')?.1 {
+        "service" => SYSTEMD_SERVICE_INTERFACE,
+        "socket" => SYSTEMD_SOCKET_INTERFACE,
+        "scope" => "org.freedesktop.systemd1.Scope",
+        "slice" => "org.freedesktop.systemd1.Slice",
+        "mount" => SYSTEMD_MOUNT_INTERFACE,
+        "swap" => "org.freedesktop.systemd1.Swap",
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -1011,13 +4637,227 @@ mod tests {
             sub_state: None,
             result: None,
             fragment_path: None,
+            unit_file_state: None,
+            vendor_preset: None,
+            dropin_paths: Vec::new(),
             main_pid: None,
             exec_main_code: None,
             exec_main_status: None,
             n_restarts: None,
+            invocation_id: None,
+            control_group: None,
+            delegate: None,
+            slice: None,
+            exec_commands: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn typed_status_decodes_kind_properties() {
+        let mut props = HashMap::new();
+        props.insert(
+            "Listen".to_string(),
+            owned_value(
+                "Listen",
+                vec![("Stream".to_string(), "127.0.0.1:8080".to_string())],
+            )
+            .expect("encode"),
+        );
+        props.insert("NAccepted".to_string(), OwnedValue::from(3u32));
+        props.insert("NConnections".to_string(), OwnedValue::from(1u32));
+        let status = status(LoadState::Loaded, ActiveState::Active);
+        let crate::TypedUnitStatus::Socket(socket) =
+            typed_status(status.clone(), SYSTEMD_SOCKET_INTERFACE, &props)
+        else {
+            panic!("expected socket status");
+        };
+        assert_eq!(socket.listen[0].address, "127.0.0.1:8080");
+        assert_eq!(socket.n_accepted, Some(3));
+        assert_eq!(socket.n_refused, None);
+
+        let mut props = HashMap::new();
+        props.insert(
+            "NextElapseUSecRealtime".to_string(),
+            OwnedValue::from(2_000_000u64),
+        );
+        props.insert(
+            "NextElapseUSecMonotonic".to_string(),
+            OwnedValue::from(0u64),
+        );
+        props.insert("LastTriggerUSec".to_string(), OwnedValue::from(u64::MAX));
+        let crate::TypedUnitStatus::Timer(timer) =
+            typed_status(status.clone(), SYSTEMD_TIMER_INTERFACE, &props)
+        else {
+            panic!("expected timer status");
+        };
+        assert_eq!(
+            timer.next_elapse_realtime,
+            Some(std::time::UNIX_EPOCH + Duration::from_secs(2))
+        );
+        assert_eq!(timer.next_elapse_monotonic, None);
+        assert_eq!(timer.last_trigger, None);
+
+        let mut props = HashMap::new();
+        props.insert(
+            "Where".to_string(),
+            owned_value("Where", "/srv/data").expect("encode"),
+        );
+        props.insert("What".to_string(), owned_value("What", "").expect("encode"));
+        let crate::TypedUnitStatus::Mount(mount) =
+            typed_status(status, SYSTEMD_MOUNT_INTERFACE, &props)
+        else {
+            panic!("expected mount status");
+        };
+        assert_eq!(mount.mount_point.as_deref(), Some("/srv/data"));
+        assert_eq!(mount.what, None);
+        assert_eq!(mount.status.active_state, ActiveState::Active);
+    }
+
+    #[test]
+    fn ordered_start_set_canonicalizes_and_dedups() {
+        let set =
+            ordered_start_set(&["db", "db.service", "web"], Duration::from_secs(5)).expect("valid");
+        assert_eq!(set, vec!["db.service", "web.service"]);
+
+        for (units, timeout) in [
+            (&[][..], Duration::from_secs(5)),
+            (&["db"][..], Duration::ZERO),
+        ] {
+            let err = ordered_start_set(units, timeout).expect_err("must fail");
+            let Error::InvalidInput { .. } = err else {
+                panic!("unexpected error: {err:?}");
+            };
         }
     }
 
+    #[test]
+    fn start_waves_layers_by_dependencies() {
+        let units: Vec<String> = ["web.service", "db.service", "cache.service", "app.service"]
+            .iter()
+            .map(|u| u.to_string())
+            .collect();
+        let preds = |pairs: &[(&str, &str)]| {
+            let mut map = HashMap::<String, BTreeSet<String>>::new();
+            for (unit, dep) in pairs {
+                map.entry(unit.to_string())
+                    .or_default()
+                    .insert(dep.to_string());
+            }
+            map
+        };
+
+        let waves = start_waves(
+            &units,
+            &preds(&[
+                ("web.service", "app.service"),
+                ("app.service", "db.service"),
+                ("app.service", "cache.service"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            waves,
+            vec![
+                vec!["db.service".to_string(), "cache.service".to_string()],
+                vec!["app.service".to_string()],
+                vec!["web.service".to_string()],
+            ]
+        );
+
+        assert_eq!(
+            start_waves(&units, &HashMap::new()).unwrap(),
+            vec![units.clone()]
+        );
+
+        let err = start_waves(
+            &units,
+            &preds(&[("db.service", "app.service"), ("app.service", "db.service")]),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("db.service, app.service"), "{err}");
+    }
+
+    #[test]
+    fn processes_gone_includes_pending_restart() {
+        assert!(processes_gone(&status(
+            LoadState::Loaded,
+            ActiveState::Inactive
+        )));
+        assert!(processes_gone(&status(
+            LoadState::Loaded,
+            ActiveState::Failed
+        )));
+        assert!(!processes_gone(&status(
+            LoadState::Loaded,
+            ActiveState::Deactivating
+        )));
+
+        let mut s = status(LoadState::Loaded, ActiveState::Activating);
+        assert!(!processes_gone(&s));
+        s.sub_state = Some("auto-restart".to_string());
+        assert!(processes_gone(&s));
+    }
+
+    #[test]
+    fn stable_since_requires_state_and_no_restart() {
+        let active = status(LoadState::Loaded, ActiveState::Active);
+        assert!(stable_since(&JobKind::Start, &active, &active));
+        assert!(!stable_since(
+            &JobKind::Start,
+            &active,
+            &status(LoadState::Loaded, ActiveState::Activating)
+        ));
+
+        let mut restarted = active.clone();
+        restarted.n_restarts = Some(1);
+        let mut before = active.clone();
+        before.n_restarts = Some(0);
+        assert!(!stable_since(&JobKind::Restart, &before, &restarted));
+
+        let inactive = status(LoadState::Loaded, ActiveState::Inactive);
+        assert!(stable_since(&JobKind::Stop, &inactive, &inactive));
+        assert!(!stable_since(&JobKind::Stop, &inactive, &active));
+    }
+
+    #[test]
+    fn restart_warning_flags_restarts_of_a_new_invocation() {
+        let success = |invocation: &str, n_restarts| {
+            let mut s = status(LoadState::Loaded, ActiveState::Active);
+            s.invocation_id = Some(invocation.to_string());
+            s.n_restarts = n_restarts;
+            infer_outcome(&JobKind::Restart, &s, Some("done"))
+        };
+        let warnings = |outcome| match outcome {
+            JobOutcome::Success { warnings, .. } => warnings,
+            other => panic!("unexpected outcome: {other:?}"),
+        };
+        let before = Some("aa".to_string());
+
+        // New run, no automatic restart since.
+        assert!(warnings(with_restart_warning(success("bb", Some(0)), Some(&before))).is_empty());
+        // Start found the unit running: earlier restarts do not count.
+        assert!(warnings(with_restart_warning(success("aa", Some(3)), Some(&before))).is_empty());
+        // Before unknown.
+        assert!(warnings(with_restart_warning(success("bb", Some(1)), None)).is_empty());
+        // Counter reset by the restart and climbed back to its old value.
+        assert_eq!(
+            warnings(with_restart_warning(success("cc", Some(2)), Some(&before))),
+            vec![crate::JobWarning::RestartedDuringWait { n_restarts: 2 }]
+        );
+        // First run of a unit that never ran.
+        assert_eq!(
+            warnings(with_restart_warning(success("bb", Some(1)), Some(&None))).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn restart_delta_counts_from_reset() {
+        assert_eq!(restart_delta(2, 5), 3);
+        assert_eq!(restart_delta(3, 3), 0);
+        assert_eq!(restart_delta(4, 1), 1);
+    }
+
     #[test]
     fn infer_outcome_not_loaded() {
         let s = status(LoadState::NotFound, ActiveState::Inactive);
@@ -1119,4 +4959,318 @@ mod tests {
         s.exec_main_code = Some(999);
         assert_eq!(decode_exit_status(&s), (None, None));
     }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn task_properties_render_ordering_dependencies() {
+        let spec = crate::types::task::TaskSpec {
+            argv: vec!["/usr/bin/migrate".to_string()],
+            timeout: Duration::from_secs(60),
+            after: vec![
+                "postgresql".to_string(),
+                "network-online.target".to_string(),
+            ],
+            requires: vec!["postgresql.service".to_string()],
+            ..Default::default()
+        };
+
+        let props = task_properties(&spec, "oneshot").expect("ok");
+        let get = |key: &str| {
+            let (_, v) = props.iter().find(|(k, _)| k == key).expect("property");
+            Vec::<String>::try_from(v.try_clone().expect("clone")).expect("string array")
+        };
+        assert_eq!(
+            get("After"),
+            vec!["postgresql.service", "network-online.target"]
+        );
+        assert_eq!(get("Requires"), vec!["postgresql.service"]);
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn task_properties_render_credentials() {
+        let spec = crate::types::task::TaskSpec {
+            argv: vec!["/usr/bin/backup".to_string()],
+            timeout: Duration::from_secs(60),
+            credentials: vec![
+                crate::Credential::load("s3.key", "/etc/backup/s3.key"),
+                crate::Credential::set("bucket", "nightly"),
+                crate::Credential::set_encrypted("db", "aGk="),
+            ],
+            ..Default::default()
+        };
+
+        let props = task_properties(&spec, "oneshot").expect("ok");
+        let get = |key: &str| {
+            let (_, v) = props.iter().find(|(k, _)| k == key).expect("property");
+            v.try_clone().expect("clone")
+        };
+        assert_eq!(
+            Vec::<(String, String)>::try_from(get("LoadCredential")).expect("a(ss)"),
+            vec![("s3.key".to_string(), "/etc/backup/s3.key".to_string())]
+        );
+        assert_eq!(
+            Vec::<(String, Vec<u8>)>::try_from(get("SetCredential")).expect("a(say)"),
+            vec![("bucket".to_string(), b"nightly".to_vec())]
+        );
+        assert_eq!(
+            Vec::<(String, Vec<u8>)>::try_from(get("SetCredentialEncrypted")).expect("a(say)"),
+            vec![("db".to_string(), b"hi".to_vec())]
+        );
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn task_properties_cap_runtime() {
+        let mut spec = crate::types::task::TaskSpec {
+            argv: vec!["/usr/bin/crawler".to_string()],
+            timeout: Duration::from_secs(60),
+            runtime_max: Some(Duration::from_secs(3600)),
+            runtime_randomized_extra: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        let props = task_properties(&spec, "exec").expect("ok");
+        let get = |key: &str| {
+            let (_, v) = props.iter().find(|(k, _)| k == key).expect("property");
+            u64::try_from(v.try_clone().expect("clone")).expect("t")
+        };
+        assert_eq!(get("RuntimeMaxUSec"), 3_600_000_000);
+        assert_eq!(get("RuntimeRandomizedExtraUSec"), 30_000_000);
+
+        spec.runtime_max = None;
+        let err = task_properties(&spec, "exec").expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn task_properties_require_capture_bound() {
+        let mut spec = crate::types::task::TaskSpec {
+            argv: vec!["/usr/bin/report".to_string()],
+            timeout: Duration::from_secs(60),
+            capture_output: true,
+            max_output_bytes: 0,
+            ..Default::default()
+        };
+        let err = task_properties(&spec, "oneshot").expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+
+        spec.max_output_bytes = 4096;
+        task_properties(&spec, "oneshot").expect("ok");
+        let err = reject_capture(&spec, "schedule").expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn task_properties_reject_invalid_dependency_names() {
+        let spec = crate::types::task::TaskSpec {
+            argv: vec!["/bin/true".to_string()],
+            timeout: Duration::from_secs(1),
+            after: vec!["../etc".to_string()],
+            ..Default::default()
+        };
+        let err = task_properties(&spec, "oneshot").expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn transient_names_follow_naming_policy() {
+        assert_eq!(
+            compose_transient_name("unitbus", None, None, "1-00ff"),
+            "unitbus-1-00ff.service"
+        );
+        assert_eq!(
+            compose_transient_name(
+                "ci",
+                Some("build-07.example.com\n"),
+                Some("nightly job"),
+                "x"
+            ),
+            "ci-build-07-nightly_job-x.service"
+        );
+
+        let mut spec = crate::types::task::TaskSpec {
+            argv: vec!["/bin/true".to_string()],
+            timeout: Duration::from_secs(1),
+            ..Default::default()
+        };
+        spec.idempotency_key = Some("deploy-42".to_string());
+        let a = transient_unit_name(&spec).expect("name");
+        assert_eq!(a, transient_unit_name(&spec).expect("name"));
+        assert!(a.starts_with("unitbus-") && !a["unitbus-".len()..].contains('-'));
+
+        spec.idempotency_key = Some("deploy-43".to_string());
+        assert_ne!(a, transient_unit_name(&spec).expect("name"));
+
+        spec.naming.prefix = "bad prefix".to_string();
+        let err = task_properties(&spec, "oneshot").expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn timer_properties_encode_triggers() {
+        use crate::types::task::{TimerTaskSpec, TimerTrigger};
+
+        let mut spec = TimerTaskSpec::new(
+            crate::types::task::TaskSpec {
+                argv: vec!["/usr/bin/backup".to_string()],
+                timeout: Duration::from_secs(60),
+                ..Default::default()
+            },
+            TimerTrigger::Calendar(crate::CalendarSpec::parse("daily").expect("calendar")),
+        );
+        spec.triggers
+            .push(TimerTrigger::OnUnitActive(Duration::from_secs(3600)));
+        spec.persistent = true;
+
+        let props = timer_properties(&spec).expect("ok");
+        let get = |key: &str| {
+            let (_, v) = props.iter().find(|(k, _)| k == key).expect("property");
+            v.try_clone().expect("clone")
+        };
+        assert_eq!(
+            Vec::<(String, String)>::try_from(get("TimersCalendar")).expect("a(ss)"),
+            vec![("OnCalendar".to_string(), "*-*-* 00:00:00".to_string())]
+        );
+        assert_eq!(
+            Vec::<(String, u64)>::try_from(get("TimersMonotonic")).expect("a(st)"),
+            vec![("OnUnitActiveUSec".to_string(), 3_600_000_000)]
+        );
+        assert!(bool::try_from(get("Persistent")).expect("b"));
+
+        spec.triggers = vec![TimerTrigger::OnUnitActive(Duration::from_secs(60))];
+        let props = timer_properties(&spec).expect("ok");
+        let (_, monotonic) = props
+            .iter()
+            .find(|(k, _)| k == "TimersMonotonic")
+            .expect("property");
+        assert_eq!(
+            Vec::<(String, u64)>::try_from(monotonic.try_clone().expect("clone")).expect("a(st)"),
+            vec![
+                ("OnActiveUSec".to_string(), 0),
+                ("OnUnitActiveUSec".to_string(), 60_000_000)
+            ]
+        );
+
+        spec.triggers = vec![TimerTrigger::OnUnitActive(Duration::ZERO)];
+        assert!(timer_properties(&spec).is_err());
+        spec.triggers.clear();
+        assert!(timer_properties(&spec).is_err());
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn next_elapse_maps_monotonic_through_enter_timestamp() {
+        let at = |us: u64| std::time::UNIX_EPOCH + Duration::from_micros(us);
+        assert_eq!(next_elapse_time(0, 0, 5, 5), None);
+        assert_eq!(next_elapse_time(9_000, 0, 0, 0), Some(at(9_000)));
+        // Entered at realtime 1_000_000 / monotonic 100; monotonic 600 is 500us later.
+        assert_eq!(
+            next_elapse_time(0, 600, 1_000_000, 100),
+            Some(at(1_000_500))
+        );
+        assert_eq!(
+            next_elapse_time(1_000_200, 600, 1_000_000, 100),
+            Some(at(1_000_200))
+        );
+    }
+
+    #[cfg(feature = "tasks")]
+    #[test]
+    fn socket_properties_encode_listeners_and_require_one() {
+        let empty = crate::types::task::TaskSocketSpec::default();
+        let err = socket_properties(&empty).expect_err("must fail");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+
+        let spec = crate::types::task::TaskSocketSpec {
+            listen_stream: vec!["127.0.0.1:8080".to_string()],
+            listen_datagram: vec!["/run/demo.dgram".to_string()],
+            socket_mode: None,
+        };
+        let props = socket_properties(&spec).expect("ok");
+        let mut map = HashMap::new();
+        for (k, v) in props {
+            map.insert(k, v);
+        }
+        assert_eq!(
+            get_string_pairs(&map, "Listen").expect("listen"),
+            vec![
+                ("ListenStream".to_string(), "127.0.0.1:8080".to_string()),
+                ("ListenDatagram".to_string(), "/run/demo.dgram".to_string()),
+            ]
+        );
+        assert!(!map.contains_key("SocketMode"));
+    }
+
+    #[cfg(feature = "tasks-pty")]
+    #[test]
+    fn tty_properties_route_all_stdio_to_tty_path() {
+        let props = tty_properties("/dev/pts/7").expect("ok");
+        let keys: Vec<&str> = props.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "StandardInput",
+                "StandardOutput",
+                "StandardError",
+                "TTYPath"
+            ]
+        );
+        let path = <&str>::try_from(&props[3].1).expect("string");
+        assert_eq!(path, "/dev/pts/7");
+    }
+
+    #[test]
+    fn ensure_state_classification() {
+        assert!(is_enabled_state("enabled"));
+        assert!(is_enabled_state("enabled-runtime"));
+        for state in ["disabled", "static", "masked", "indirect", ""] {
+            assert!(!is_enabled_state(state), "{state}");
+        }
+
+        assert!(is_active_like(&ActiveState::Activating));
+        assert!(is_active_like(&ActiveState::Reloading));
+        assert!(!is_active_like(&ActiveState::Failed));
+        assert!(!is_active_like(&ActiveState::Deactivating));
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn instance_dropin_writes_under_escaped_unit_dir() {
+        let mut spec = crate::types::config::DropInSpec {
+            unit: crate::types::specifier::instance_unit("tenant@.service", "acme corp-eu")
+                .expect("instance"),
+            name: "limits".to_string(),
+            ..Default::default()
+        };
+        spec.set_timeout_start_sec(30);
+        prepare_dropin(&mut spec).expect("escaped instance accepted");
+        assert_eq!(spec.unit, r"tenant@acme\x20corp\x2deu.service");
+
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("unitbus-instance-dropin-{}", std::process::id()));
+        let contents = crate::fsutil::render_dropin(&spec).expect("render");
+        let report = crate::fsutil::apply_dropin_file(&dir, &spec.unit, &spec.name, contents)
+            .expect("write");
+        let expected = dir
+            .join(r"tenant@acme\x20corp\x2deu.service.d")
+            .join("limits.conf");
+        assert_eq!(report.path_written, expected.display().to_string());
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
 }
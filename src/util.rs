@@ -1,6 +1,5 @@
 use crate::{Error, Result};
 
-#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub(crate) fn canonicalize_unit_name(input: &str) -> Result<String> {
@@ -9,11 +8,22 @@ pub(crate) fn canonicalize_unit_name(input: &str) -> Result<String> {
     if input.is_empty() {
         return Err(Error::invalid_input("unit must not be empty"));
     }
-    if input.contains('/') || input.contains('\\') {
+    if input.contains('/') {
         return Err(Error::invalid_input(
             "unit must not contain path separators",
         ));
     }
+    // `systemd-escape`d instances (`app@acme\x20corp.service`) carry backslashes; anywhere else
+    // a `\` is rejected like a path separator.
+    let (outer, instance) = split_instance(input);
+    if outer.contains('\\') {
+        return Err(Error::invalid_input(
+            "unit must not contain path separators",
+        ));
+    }
+    if let Some(instance) = instance {
+        validate_escaped_instance(instance)?;
+    }
     if input.contains("..") {
         return Err(Error::invalid_input("unit must not contain '..'"));
     }
@@ -24,6 +34,42 @@ pub(crate) fn canonicalize_unit_name(input: &str) -> Result<String> {
     Ok(format!("{input}.service"))
 }
 
+/// Split `name@instance.type` into `name@.type` and `instance`; the whole input and `None` when
+/// there is no instance.
+fn split_instance(input: &str) -> (String, Option<&str>) {
+    let Some((prefix, rest)) = input.split_once('@') else {
+        return (input.to_string(), None);
+    };
+    let (instance, suffix) = match rest.rsplit_once('.') {
+        Some((instance, suffix)) => (instance, Some(suffix)),
+        None => (rest, None),
+    };
+    let outer = match suffix {
+        Some(suffix) => format!("{prefix}@.{suffix}"),
+        None => format!("{prefix}@"),
+    };
+    (outer, Some(instance))
+}
+
+/// Backslashes in an instance must start a `\xNN` escape (as `systemd-escape` writes them).
+fn validate_escaped_instance(instance: &str) -> Result<()> {
+    let bytes = instance.as_bytes();
+    for (i, b) in bytes.iter().enumerate() {
+        if *b != b'\\' {
+            continue;
+        }
+        let escape = bytes.get(i + 1..i + 4);
+        let valid = escape
+            .is_some_and(|e| e[0] == b'x' && e[1].is_ascii_hexdigit() && e[2].is_ascii_hexdigit());
+        if !valid {
+            return Err(Error::invalid_input(format!(
+                "invalid escape in unit instance: {instance}"
+            )));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(feature = "config")]
 pub(crate) fn validate_dropin_name(input: &str) -> Result<()> {
     validate_no_control("drop-in name", input)?;
@@ -58,6 +104,56 @@ pub(crate) fn validate_env_key(input: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate a local machine (container/VM) name as accepted by `machinectl`: a hostname of at
+/// most 64 bytes.
+pub(crate) fn validate_machine_name(input: &str) -> Result<()> {
+    if input.is_empty() || input.len() > 64 {
+        return Err(Error::invalid_input("machine name must be 1..=64 bytes"));
+    }
+    if input.starts_with('.')
+        || input.contains("..")
+        || !input
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+    {
+        return Err(Error::invalid_input(format!(
+            "invalid machine name: {input}"
+        )));
+    }
+    Ok(())
+}
+
+/// Validate one `StateDirectory=`-style entry: a relative path below the directory's base
+/// (`/var/lib`, `/run`, ...) without `.`/`..` components.
+pub(crate) fn validate_exec_directory(context: &'static str, input: &str) -> Result<()> {
+    validate_no_control(context, input)?;
+    if input.is_empty() {
+        return Err(Error::invalid_input(format!("{context} must not be empty")));
+    }
+    if input.starts_with('/') {
+        return Err(Error::invalid_input(format!(
+            "{context} must be relative to its base directory: {input}"
+        )));
+    }
+    if input
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '\\' | '"' | '\''))
+    {
+        return Err(Error::invalid_input(format!(
+            "{context} must not contain whitespace, quotes or backslashes: {input}"
+        )));
+    }
+    if input
+        .split('/')
+        .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        return Err(Error::invalid_input(format!(
+            "{context} must not contain empty, '.' or '..' components: {input}"
+        )));
+    }
+    Ok(())
+}
+
 pub(crate) fn validate_no_control(context: &'static str, input: &str) -> Result<()> {
     if input.contains('\0') {
         return Err(Error::invalid_input(format!(
@@ -107,32 +203,28 @@ fn quote_systemd_exec_arg(arg: &str) -> String {
     }
 }
 
+/// Seconds since the epoch; pre-epoch times (skewed clocks) clamp to 0, before every entry.
 #[cfg(feature = "journal-cli")]
 pub(crate) fn unix_seconds(t: SystemTime) -> Result<i64> {
-    let dur = t.duration_since(UNIX_EPOCH).map_err(|e| Error::IoError {
-        context: format!("system time before unix epoch: {e}"),
-    })?;
+    let dur = t.duration_since(UNIX_EPOCH).unwrap_or_default();
     i64::try_from(dur.as_secs()).map_err(|e| Error::IoError {
         context: format!("unix seconds overflow: {e}"),
     })
 }
 
+/// Microseconds since the epoch; pre-epoch times clamp to 0 like `unix_seconds`.
 #[cfg(all(feature = "journal-sdjournal", not(feature = "journal-cli")))]
 pub(crate) fn unix_micros(t: SystemTime) -> Result<u64> {
-    let dur = t.duration_since(UNIX_EPOCH).map_err(|e| Error::IoError {
-        context: format!("system time before unix epoch: {e}"),
-    })?;
+    let dur = t.duration_since(UNIX_EPOCH).unwrap_or_default();
     u64::try_from(dur.as_micros()).map_err(|e| Error::IoError {
         context: format!("unix micros overflow: {e}"),
     })
 }
 
-#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
 pub(crate) fn system_time_from_unix_micros(us: u64) -> SystemTime {
     UNIX_EPOCH + Duration::from_micros(us)
 }
 
-#[cfg(feature = "journal-cli")]
 pub(crate) fn truncate_string_bytes(input: &str, max_bytes: usize) -> (String, bool) {
     if input.len() <= max_bytes {
         return (input.to_string(), false);
@@ -144,6 +236,192 @@ pub(crate) fn truncate_string_bytes(input: &str, max_bytes: usize) -> (String, b
     (input[..end].to_string(), true)
 }
 
+/// Parse a systemd-style time specification relative to `now` (see `JournalFilter::parse_time`);
+/// day keywords use midnight at `utc_offset_secs` east of UTC.
+pub(crate) fn parse_time_spec(
+    input: &str,
+    now: SystemTime,
+    utc_offset_secs: i32,
+) -> Result<SystemTime> {
+    let spec = input.trim();
+    let invalid = || Error::invalid_input(format!("unsupported time specification: {spec:?}"));
+
+    match spec {
+        "" => return Err(invalid()),
+        "now" => return Ok(now),
+        "today" => return day_start(now, utc_offset_secs, 0),
+        "yesterday" => return day_start(now, utc_offset_secs, -1),
+        "tomorrow" => return day_start(now, utc_offset_secs, 1),
+        _ => {}
+    }
+
+    if let Some(epoch) = spec.strip_prefix('@') {
+        let offset = parse_time_span(epoch).map_err(|_| invalid())?;
+        return UNIX_EPOCH.checked_add(offset).ok_or_else(invalid);
+    }
+
+    let (span, past) = if let Some(rest) = spec.strip_prefix('-') {
+        (rest, true)
+    } else if let Some(rest) = spec.strip_prefix('+') {
+        (rest, false)
+    } else if let Some(rest) = spec.strip_suffix(" ago") {
+        (rest, true)
+    } else if let Some(rest) = spec.strip_suffix(" left") {
+        (rest, false)
+    } else {
+        return Err(invalid());
+    };
+
+    let span = parse_time_span(span).map_err(|_| invalid())?;
+    let t = if past {
+        now.checked_sub(span)
+    } else {
+        now.checked_add(span)
+    };
+    t.ok_or_else(invalid)
+}
+
+/// Parse a systemd-style time span such as `"90s"`, `"1h 30min"` or `"2.5d"`.
+///
+/// A bare number is interpreted as seconds, like `systemd.time(7)`.
+pub(crate) fn parse_time_span(input: &str) -> Result<Duration> {
+    let invalid = || Error::invalid_input(format!("invalid time span: {input:?}"));
+
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total_us = 0u128;
+    while !rest.is_empty() {
+        let digits_end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(digits_end);
+        let tail = tail.trim_start();
+        let unit_end = tail
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_end);
+
+        let multiplier = time_unit_micros(unit).ok_or_else(invalid)?;
+        let (whole, frac) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() && frac.is_empty() {
+            return Err(invalid());
+        }
+        let whole: u128 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| invalid())?
+        };
+        let mut us = whole.checked_mul(multiplier).ok_or_else(invalid)?;
+        if !frac.is_empty() {
+            let frac = frac.get(..9).unwrap_or(frac);
+            let scale = 10u128.pow(u32::try_from(frac.len()).unwrap_or(9));
+            let frac: u128 = frac.parse().map_err(|_| invalid())?;
+            us = us.saturating_add(frac.saturating_mul(multiplier) / scale);
+        }
+        total_us = total_us.saturating_add(us);
+        rest = tail.trim_start();
+    }
+
+    let total_us = u64::try_from(total_us).map_err(|_| invalid())?;
+    Ok(Duration::from_micros(total_us))
+}
+
+fn time_unit_micros(unit: &str) -> Option<u128> {
+    const SEC: u128 = 1_000_000;
+    let us = match unit {
+        "usec" | "us" | "\u{b5}s" | "\u{3bc}s" => 1,
+        "msec" | "ms" => 1_000,
+        "" | "seconds" | "second" | "sec" | "s" => SEC,
+        "minutes" | "minute" | "min" | "m" => 60 * SEC,
+        "hours" | "hour" | "hr" | "h" => 3_600 * SEC,
+        "days" | "day" | "d" => 86_400 * SEC,
+        "weeks" | "week" | "w" => 7 * 86_400 * SEC,
+        // systemd uses the average Gregorian month/year lengths.
+        "months" | "month" | "M" => 2_629_800 * SEC,
+        "years" | "year" | "y" => 31_557_600 * SEC,
+        _ => return None,
+    };
+    Some(us)
+}
+
+/// Midnight `day_offset` days from the day containing `now`, in the zone `utc_offset_secs` east.
+fn day_start(now: SystemTime, utc_offset_secs: i32, day_offset: i64) -> Result<SystemTime> {
+    const DAY: i64 = 86_400;
+    let out_of_range = || Error::invalid_input("time specification out of range");
+    let secs = now
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| Error::invalid_input(format!("system time before unix epoch: {e}")))?
+        .as_secs();
+    let local = i64::try_from(secs)
+        .map_err(|_| out_of_range())?
+        .checked_add(i64::from(utc_offset_secs))
+        .ok_or_else(out_of_range)?;
+    let target = (local - local.rem_euclid(DAY))
+        .checked_add(day_offset.checked_mul(DAY).ok_or_else(out_of_range)?)
+        .and_then(|s| s.checked_sub(i64::from(utc_offset_secs)))
+        .ok_or_else(out_of_range)?;
+    u64::try_from(target)
+        .map(|s| UNIX_EPOCH + Duration::from_secs(s))
+        .map_err(|_| out_of_range())
+}
+
+#[cfg(any(feature = "config", feature = "tasks"))]
+pub(crate) const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// FNV-1a: a stable (across builds and Rust versions) hash for digests and derived names.
+#[cfg(any(feature = "config", feature = "tasks"))]
+pub(crate) fn fnv1a64(mut hash: u64, bytes: &[u8]) -> u64 {
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// `_BOOT_ID` of the current boot (32 hex digits, from `/proc/sys/kernel/random/boot_id`).
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal"))]
+pub(crate) fn current_boot_id() -> Result<String> {
+    let raw = std::fs::read_to_string("/proc/sys/kernel/random/boot_id").map_err(|e| {
+        Error::BackendUnavailable {
+            backend: "procfs",
+            detail: format!("read /proc/sys/kernel/random/boot_id: {e}"),
+        }
+    })?;
+    parse_boot_id(&raw).ok_or_else(|| Error::BackendUnavailable {
+        backend: "procfs",
+        detail: format!("unexpected boot_id {:?}", raw.trim()),
+    })
+}
+
+#[cfg(any(feature = "journal-cli", feature = "journal-sdjournal", test))]
+fn parse_boot_id(raw: &str) -> Option<String> {
+    let id: String = raw.trim().chars().filter(|c| *c != '-').collect();
+    (id.len() == 32 && id.bytes().all(|b| b.is_ascii_hexdigit())).then(|| id.to_ascii_lowercase())
+}
+
+/// D-Bus object path of `unit` as systemd exports it (`/org/freedesktop/systemd1/unit/<label>`),
+/// so a unit's properties can be read without a `GetUnit` round trip.
+///
+/// The label escapes like sd-bus: every byte except ASCII letters (and digits after the first
+/// byte) becomes `_xx`.
+pub(crate) fn unit_object_path(unit: &str) -> String {
+    let mut path = String::from("/org/freedesktop/systemd1/unit/");
+    if unit.is_empty() {
+        path.push('_');
+    }
+    for (i, b) in unit.bytes().enumerate() {
+        if b.is_ascii_alphabetic() || (i > 0 && b.is_ascii_digit()) {
+            path.push(char::from(b));
+        } else {
+            path.push_str(&format!("_{b:02x}"));
+        }
+    }
+    path
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -152,12 +430,39 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn unit_object_path_escapes_like_sd_bus() {
+        assert_eq!(
+            unit_object_path("nginx.service"),
+            "/org/freedesktop/systemd1/unit/nginx_2eservice"
+        );
+        assert_eq!(
+            unit_object_path("getty@tty1.service"),
+            "/org/freedesktop/systemd1/unit/getty_40tty1_2eservice"
+        );
+        assert_eq!(
+            unit_object_path("1-app.service"),
+            "/org/freedesktop/systemd1/unit/_31_2dapp_2eservice"
+        );
+    }
+
     #[test]
     fn canonicalize_unit_appends_service_suffix() {
         let name = canonicalize_unit_name("nginx").expect("ok");
         assert_eq!(name, "nginx.service");
     }
 
+    #[test]
+    fn machine_names_follow_hostname_rules() {
+        assert!(validate_machine_name("web-01").is_ok());
+        assert!(validate_machine_name("db.example_2").is_ok());
+        assert!(validate_machine_name("").is_err());
+        assert!(validate_machine_name(".hidden").is_err());
+        assert!(validate_machine_name("a/../b").is_err());
+        assert!(validate_machine_name("a b").is_err());
+        assert!(validate_machine_name(&"x".repeat(65)).is_err());
+    }
+
     #[test]
     fn canonicalize_unit_keeps_existing_suffix() {
         let name = canonicalize_unit_name("nginx.timer").expect("ok");
@@ -180,6 +485,27 @@ mod tests {
         };
     }
 
+    #[test]
+    fn canonicalize_unit_accepts_escaped_instances_only() {
+        assert_eq!(
+            canonicalize_unit_name(r"tenant@acme\x20corp\x2deu.service").unwrap(),
+            r"tenant@acme\x20corp\x2deu.service"
+        );
+        assert_eq!(
+            canonicalize_unit_name(r"tenant@a\x2db").unwrap(),
+            r"tenant@a\x2db.service"
+        );
+        for bad in [
+            r"ten\ant@x.service",
+            r"tenant@x.serv\ice",
+            r"tenant@a\b.service",
+            r"tenant@a\x2.service",
+            "tenant@a/b.service",
+        ] {
+            assert!(canonicalize_unit_name(bad).is_err(), "{bad} accepted");
+        }
+    }
+
     #[test]
     fn canonicalize_unit_rejects_dotdot() {
         let err = canonicalize_unit_name("../nginx").expect_err("must fail");
@@ -187,4 +513,76 @@ mod tests {
             panic!("unexpected error: {err:?}");
         };
     }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn parse_time_span_accepts_systemd_units() {
+        assert_eq!(parse_time_span("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(
+            parse_time_span("1h 30min").unwrap(),
+            Duration::from_secs(5400)
+        );
+        assert_eq!(parse_time_span("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(
+            parse_time_span("2.5d").unwrap(),
+            Duration::from_secs(216_000)
+        );
+        assert_eq!(
+            parse_time_span("250ms").unwrap(),
+            Duration::from_millis(250)
+        );
+        assert!(parse_time_span("").is_err());
+        assert!(parse_time_span("5 fortnights").is_err());
+        assert!(parse_time_span("h").is_err());
+    }
+
+    #[test]
+    fn parse_time_spec_handles_relative_and_keywords() {
+        // 2023-11-14T22:13:20Z
+        let now = at(1_700_000_000);
+        assert_eq!(parse_time_spec("now", now, 0).unwrap(), now);
+        assert_eq!(parse_time_spec("-1h", now, 0).unwrap(), at(1_699_996_400));
+        assert_eq!(parse_time_spec("+5min", now, 0).unwrap(), at(1_700_000_300));
+        assert_eq!(
+            parse_time_spec("2 days ago", now, 0).unwrap(),
+            at(1_699_827_200)
+        );
+        assert_eq!(parse_time_spec("today", now, 0).unwrap(), at(1_699_920_000));
+        assert_eq!(
+            parse_time_spec("yesterday", now, 0).unwrap(),
+            at(1_699_833_600)
+        );
+        assert_eq!(
+            parse_time_spec("tomorrow", now, 0).unwrap(),
+            at(1_700_006_400)
+        );
+        assert_eq!(parse_time_spec("@1700000000", now, 0).unwrap(), now);
+
+        // 00:13:20 on Nov 15 at UTC+2, 17:13:20 on Nov 14 at UTC-5.
+        assert_eq!(
+            parse_time_spec("today", now, 2 * 3600).unwrap(),
+            at(1_699_999_200)
+        );
+        assert_eq!(
+            parse_time_spec("today", now, -5 * 3600).unwrap(),
+            at(1_699_938_000)
+        );
+
+        let err = parse_time_spec("2023-11-14", now, 0).expect_err("absolute dates");
+        let Error::InvalidInput { .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+    }
+
+    #[test]
+    fn parse_boot_id_strips_dashes() {
+        assert_eq!(
+            parse_boot_id("4F6A0C6B-3C4E-4D7A-9E1B-2A3B4C5D6E7F\n").as_deref(),
+            Some("4f6a0c6b3c4e4d7a9e1b2a3b4c5d6e7f")
+        );
+        assert_eq!(parse_boot_id("not-a-boot-id"), None);
+    }
 }
@@ -309,6 +309,63 @@ fn run_task_failure_can_diagnose() {
     .unwrap();
 }
 
+#[cfg(feature = "tasks")]
+#[test]
+#[ignore]
+fn run_task_idempotency_key_reuses_loaded_unit() {
+    let true_bin = match find_executable(&["/bin/true", "/usr/bin/true"]) {
+        Some(p) => p,
+        None => {
+            eprintln!("cannot find /bin/true or /usr/bin/true; skipping");
+            return;
+        }
+    };
+
+    block_on(async {
+        let bus = UnitBus::connect_system().await?;
+
+        let mut spec = unitbus::TaskSpec::default();
+        spec.argv = vec![true_bin];
+        spec.timeout = Duration::from_secs(10);
+        spec.name_hint = Some("itest-key".to_string());
+        spec.idempotency_key = Some(format!("itest-{}", std::process::id()));
+
+        let first = match bus.tasks().run(spec.clone()).await {
+            Ok(t) => t,
+            Err(unitbus::Error::PermissionDenied { .. }) => {
+                eprintln!("permission denied; skipping keyed task");
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        assert_eq!(
+            first.wait(Duration::from_secs(30)).await?.exit_status,
+            Some(0)
+        );
+
+        // The finished unit stays loaded, so a resubmission attaches to it instead of rerunning.
+        let again = bus.tasks().run(spec.clone()).await?;
+        assert_eq!(again.unit, first.unit);
+        assert_eq!(again.job_path, "/", "expected no new start job");
+        assert_eq!(
+            again.wait(Duration::from_secs(30)).await?.exit_status,
+            Some(0)
+        );
+
+        // Releasing the unit frees the key for a new run.
+        again.release().await?;
+        std::thread::sleep(Duration::from_millis(500));
+        let rerun = bus.tasks().run(spec).await?;
+        assert_eq!(rerun.unit, first.unit);
+        assert_ne!(rerun.job_path, "/", "expected a new start job");
+        rerun.wait(Duration::from_secs(30)).await?;
+        rerun.release().await?;
+
+        Ok::<(), unitbus::Error>(())
+    })
+    .unwrap();
+}
+
 #[cfg(feature = "config")]
 #[test]
 #[ignore]